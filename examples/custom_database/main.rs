@@ -0,0 +1,409 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+use warpgrapher::engine::config::Configuration;
+use warpgrapher::engine::context::RequestContext;
+use warpgrapher::engine::database::{
+    Comparison, DatabaseEndpoint, DatabasePool, NodeQueryVar, Operation, PropOperations,
+    QueryFragment, QueryResult, RelQueryVar, SuffixGenerator, Transaction,
+};
+use warpgrapher::engine::loader::{AggregateLoaderKey, NodeLoaderKey, RelLoaderKey};
+use warpgrapher::engine::objects::{Node, Options, Rel};
+use warpgrapher::engine::schema::Info;
+use warpgrapher::engine::value::Value;
+use warpgrapher::{Engine, Error};
+
+static CONFIG: &str = "
+version: 1
+model:
+  - name: User
+    props:
+      - name: email
+        type: String
+";
+
+// the nodes held by the store, by type name
+type Nodes = Arc<Mutex<HashMap<String, Vec<HashMap<String, Value>>>>>;
+
+// an in-memory store of nodes, standing in for a database that warpgrapher has no back-end for.
+// it supports creating nodes and reading them by equality on their properties.
+#[derive(Clone, Default)]
+struct MemoryEndpoint {
+    nodes: Nodes,
+}
+
+#[async_trait]
+impl DatabaseEndpoint for MemoryEndpoint {
+    type PoolType = MemoryPool;
+
+    async fn pool(&self) -> Result<Self::PoolType, Error> {
+        Ok(MemoryPool {
+            nodes: self.nodes.clone(),
+        })
+    }
+}
+
+#[derive(Clone)]
+struct MemoryPool {
+    nodes: Nodes,
+}
+
+#[async_trait]
+impl DatabasePool for MemoryPool {
+    type TransactionType = MemoryTransaction;
+
+    async fn read_transaction(&self) -> Result<Self::TransactionType, Error> {
+        self.transaction().await
+    }
+
+    async fn transaction(&self) -> Result<Self::TransactionType, Error> {
+        Ok(MemoryTransaction {
+            nodes: self.nodes.clone(),
+            created: Vec::new(),
+        })
+    }
+}
+
+// creates are held by the transaction until it commits
+struct MemoryTransaction {
+    nodes: Nodes,
+    created: Vec<(String, HashMap<String, Value>)>,
+}
+
+fn unsupported(operation: &str) -> Error {
+    Error::UserDefinedError {
+        source: format!("{} is not supported by the in-memory store", operation).into(),
+    }
+}
+
+#[async_trait]
+impl Transaction for MemoryTransaction {
+    async fn begin(&mut self) -> Result<(), Error> {
+        self.created.clear();
+        Ok(())
+    }
+
+    async fn execute_query<RequestCtx: RequestContext>(
+        &mut self,
+        _query: String,
+        _params: HashMap<String, Value>,
+    ) -> Result<QueryResult, Error> {
+        Err(unsupported("execute_query"))
+    }
+
+    async fn execute_write(
+        &mut self,
+        _query: String,
+        _params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        Err(unsupported("execute_write"))
+    }
+
+    async fn execute_query_values(
+        &mut self,
+        _query: String,
+        _params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        Err(unsupported("execute_query_values"))
+    }
+
+    async fn create_node<RequestCtx: RequestContext>(
+        &mut self,
+        node_var: &NodeQueryVar,
+        mut props: HashMap<String, Value>,
+        _options: Options,
+        _info: &Info,
+        _sg: &mut SuffixGenerator,
+    ) -> Result<Node<RequestCtx>, Error> {
+        props.insert("id".to_string(), Value::String(Uuid::new_v4().to_string()));
+        self.created
+            .push((node_var.label()?.to_string(), props.clone()));
+        Ok(Node::new(node_var.label()?.to_string(), props))
+    }
+
+    async fn create_rels<RequestCtx: RequestContext>(
+        &mut self,
+        _src_query_fragment: QueryFragment,
+        _dst_query_fragment: QueryFragment,
+        _rel_var: &RelQueryVar,
+        _id_opt: Option<Value>,
+        _props: HashMap<String, Value>,
+        _options: Options,
+        _sg: &mut SuffixGenerator,
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        Err(unsupported("create_rels"))
+    }
+
+    fn node_read_by_ids_fragment<RequestCtx: RequestContext>(
+        &mut self,
+        _node_var: &NodeQueryVar,
+        _nodes: &[Node<RequestCtx>],
+    ) -> Result<QueryFragment, Error> {
+        Err(unsupported("node_read_by_ids_fragment"))
+    }
+
+    // the fragment holds the values that the properties of matching nodes must equal
+    fn node_read_fragment(
+        &mut self,
+        rel_query_fragments: Vec<QueryFragment>,
+        node_var: &NodeQueryVar,
+        props: HashMap<String, Comparison>,
+        _sg: &mut SuffixGenerator,
+    ) -> Result<QueryFragment, Error> {
+        if !rel_query_fragments.is_empty() {
+            return Err(unsupported("matching by relationship"));
+        }
+
+        let params = props
+            .into_iter()
+            .map(|(k, c)| match (c.operation(), c.negated()) {
+                (Operation::EQ, false) => Ok((k, c.operand().clone())),
+                _ => Err(unsupported("comparison other than EQ")),
+            })
+            .collect::<Result<HashMap<String, Value>, Error>>()?;
+        Ok(QueryFragment::new(
+            node_var.label()?.to_string(),
+            String::new(),
+            params,
+        ))
+    }
+
+    async fn load_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        keys: &[NodeLoaderKey],
+        info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        let nodes = self.nodes.lock().unwrap();
+        Ok(nodes
+            .get(info.name())
+            .into_iter()
+            .flatten()
+            .filter(|fields| {
+                keys.iter()
+                    .any(|k| fields.get("id") == Some(&Value::String(k.id().to_string())))
+            })
+            .map(|fields| Node::new(info.name().to_string(), fields.clone()))
+            .collect())
+    }
+
+    async fn read_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        _node_var: &NodeQueryVar,
+        query_fragment: QueryFragment,
+        _options: Options,
+        _info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        let label = query_fragment.match_fragment().to_string();
+        let params = query_fragment.params();
+        let nodes = self.nodes.lock().unwrap();
+        Ok(nodes
+            .get(&label)
+            .into_iter()
+            .flatten()
+            .filter(|fields| params.iter().all(|(k, v)| fields.get(k) == Some(v)))
+            .map(|fields| Node::new(label.clone(), fields.clone()))
+            .collect())
+    }
+
+    async fn node_exists(
+        &mut self,
+        _node_var: &NodeQueryVar,
+        _query_fragment: QueryFragment,
+    ) -> Result<bool, Error> {
+        Err(unsupported("node_exists"))
+    }
+
+    async fn read_computed_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        _query: String,
+        _src_id: Value,
+        _info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        Err(unsupported("read_computed_nodes"))
+    }
+
+    fn rel_read_by_ids_fragment<RequestCtx: RequestContext>(
+        &mut self,
+        _rel_var: &RelQueryVar,
+        _rels: &[Rel<RequestCtx>],
+    ) -> Result<QueryFragment, Error> {
+        Err(unsupported("rel_read_by_ids_fragment"))
+    }
+
+    fn rel_read_fragment(
+        &mut self,
+        _src_fragment_opt: Option<QueryFragment>,
+        _dst_fragment_opt: Option<QueryFragment>,
+        _rel_var: &RelQueryVar,
+        _props: HashMap<String, Comparison>,
+        _sg: &mut SuffixGenerator,
+    ) -> Result<QueryFragment, Error> {
+        Err(unsupported("rel_read_fragment"))
+    }
+
+    async fn load_rels<RequestCtx: RequestContext>(
+        &mut self,
+        _keys: &[RelLoaderKey],
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        Err(unsupported("load_rels"))
+    }
+
+    async fn count_rels(&mut self, _keys: &[RelLoaderKey]) -> Result<Vec<i64>, Error> {
+        Err(unsupported("count_rels"))
+    }
+
+    async fn count_matching_rels(
+        &mut self,
+        _query_fragment: QueryFragment,
+        _rel_var: &RelQueryVar,
+    ) -> Result<i64, Error> {
+        Err(unsupported("count_matching_rels"))
+    }
+
+    async fn aggregate_rels(
+        &mut self,
+        _keys: &[AggregateLoaderKey],
+    ) -> Result<Vec<HashMap<String, Value>>, Error> {
+        Err(unsupported("aggregate_rels"))
+    }
+
+    async fn read_rels<RequestCtx: RequestContext>(
+        &mut self,
+        _query_fragment: QueryFragment,
+        _rel_var: &RelQueryVar,
+        _options: Options,
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        Err(unsupported("read_rels"))
+    }
+
+    async fn update_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        _query_fragment: QueryFragment,
+        _node_var: &NodeQueryVar,
+        _props: HashMap<String, Value>,
+        _options: Options,
+        _info: &Info,
+        _sg: &mut SuffixGenerator,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        Err(unsupported("update_nodes"))
+    }
+
+    async fn update_rels<RequestCtx: RequestContext>(
+        &mut self,
+        _query_fragment: QueryFragment,
+        _rel_var: &RelQueryVar,
+        _props: HashMap<String, Value>,
+        _ops: PropOperations,
+        _options: Options,
+        _sg: &mut SuffixGenerator,
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        Err(unsupported("update_rels"))
+    }
+
+    async fn delete_nodes(
+        &mut self,
+        _query_fragment: QueryFragment,
+        _node_var: &NodeQueryVar,
+        _options: Options,
+    ) -> Result<i32, Error> {
+        Err(unsupported("delete_nodes"))
+    }
+
+    async fn delete_rels(
+        &mut self,
+        _query_fragment: QueryFragment,
+        _rel_var: &RelQueryVar,
+        _options: Options,
+    ) -> Result<i32, Error> {
+        Err(unsupported("delete_rels"))
+    }
+
+    async fn commit(&mut self) -> Result<(), Error> {
+        let mut nodes = self.nodes.lock().unwrap();
+        for (label, fields) in self.created.drain(..) {
+            nodes.entry(label).or_default().push(fields);
+        }
+        Ok(())
+    }
+
+    async fn rollback(&mut self) -> Result<(), Error> {
+        self.created.clear();
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct AppRequestContext {}
+
+impl RequestContext for AppRequestContext {
+    type DBEndpointType = MemoryEndpoint;
+    fn new() -> AppRequestContext {
+        AppRequestContext {}
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    // parse warpgrapher config
+    let config = Configuration::try_from(CONFIG.to_string()).expect("Failed to parse CONFIG");
+
+    // define the custom database endpoint
+    let db = MemoryEndpoint::default()
+        .pool()
+        .await
+        .expect("Failed to create in-memory database pool");
+
+    // create warpgrapher engine
+    let engine: Engine<AppRequestContext> = Engine::new(config, db)
+        .build()
+        .expect("Failed to build engine");
+
+    // create a user, and then read it back
+    let create = "
+        mutation {
+            UserCreate(input: { email: \"alice@example.com\" }) {
+                id
+                email
+            }
+        }
+    "
+    .to_string();
+    let result = engine
+        .execute(create, None, None, HashMap::new())
+        .await
+        .unwrap();
+    println!("result: {:#?}", result);
+
+    let read = "
+        query {
+            User(input: { email: { EQ: \"alice@example.com\" } }) {
+                id
+                email
+            }
+        }
+    "
+    .to_string();
+    let result = engine
+        .execute(read, None, None, HashMap::new())
+        .await
+        .unwrap();
+
+    // verify result
+    println!("result: {:#?}", result);
+    assert_eq!(
+        "alice@example.com",
+        result
+            .get("data")
+            .unwrap()
+            .get("User")
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .get("email")
+            .unwrap()
+            .as_str()
+            .unwrap(),
+    );
+}
@@ -77,7 +77,7 @@ async fn main() {
     "
     .to_string();
     let metadata = HashMap::new();
-    let result = engine.execute(query, None, metadata).await.unwrap();
+    let result = engine.execute(query, None, None, metadata).await.unwrap();
 
     // verify result
     assert_eq!(
@@ -1,5 +1,4 @@
 use maplit::hashmap;
-use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use warpgrapher::engine::config::{Configuration, Property, UsesFilter};
@@ -8,6 +7,7 @@ use warpgrapher::engine::database::cypher::CypherEndpoint;
 use warpgrapher::engine::database::CrudOperation;
 use warpgrapher::engine::database::DatabaseEndpoint;
 use warpgrapher::engine::events::{EventFacade, EventHandlerBag};
+use warpgrapher::engine::metadata::Metadata;
 use warpgrapher::engine::objects::{Node, Options};
 use warpgrapher::engine::value::Value;
 use warpgrapher::juniper::BoxFuture;
@@ -44,12 +44,12 @@ impl RequestContext for Rctx {
 fn insert_user_profile(
     mut rctx: Rctx,
     mut _ef: EventFacade<Rctx>,
-    _metadata: HashMap<String, String>,
+    _metadata: Metadata,
 ) -> BoxFuture<Result<Rctx, Error>> {
     Box::pin(async move {
-        // A real implementation would likely pull a user identity from an authentication token in
-        // metadata, or use that token to look up a full user profile in a database. In this
-        // example, the identify is hard-coded.
+        // A real implementation would likely verify the authentication token returned by
+        // metadata.bearer_token(), and use it to look up a full user profile in a database. In
+        // this example, the identity is hard-coded.
         rctx.username = "user-from-JWT".to_string();
         Ok(rctx)
     })
@@ -210,8 +210,8 @@ async fn main() {
         }
     "
     .to_string();
-    let metadata = HashMap::new();
-    let result = engine.execute(query, None, metadata).await.unwrap();
+    let metadata = Metadata::new();
+    let result = engine.execute(query, None, None, metadata).await.unwrap();
 
     println!("result: {:#?}", result);
 }
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use warpgrapher::engine::config::Configuration;
+use warpgrapher::engine::context::RequestContext;
+use warpgrapher::engine::database::cypher::CypherEndpoint;
+use warpgrapher::engine::database::DatabaseEndpoint;
+use warpgrapher::engine::objects::Options;
+use warpgrapher::engine::resolvers::{ExecutionResult, ResolverFacade, Resolvers};
+use warpgrapher::engine::value::Value;
+use warpgrapher::juniper::BoxFuture;
+use warpgrapher::{Engine, Error};
+
+static CONFIG: &str = "
+version: 1
+model: 
+ - name: LineItem
+   props: 
+    - name: sku
+      type: String 
+    - name: quantity
+      type: Int 
+endpoints:
+  - name: PlaceOrder
+    class: Mutation
+    input:
+      type:
+        name: OrderInput
+        props:
+          - name: customer
+            type: String
+          - name: lineItems
+            type: LineItemCreateMutationInput
+            list: true
+      required: true
+    output:
+      type: Int
+";
+
+#[derive(Clone, Debug)]
+struct AppRequestContext {}
+
+impl RequestContext for AppRequestContext {
+    type DBEndpointType = CypherEndpoint;
+    fn new() -> AppRequestContext {
+        AppRequestContext {}
+    }
+}
+
+// endpoint creating a `LineItem` node for each line item in the order, and returning the total
+// quantity ordered
+fn resolve_place_order(facade: ResolverFacade<AppRequestContext>) -> BoxFuture<ExecutionResult> {
+    Box::pin(async move {
+        // the nested input arrives as a map, with the line items as a list of maps
+        let input =
+            facade
+                .args()
+                .get::<Value>("input")
+                .ok_or_else(|| Error::InputItemNotFound {
+                    name: "input".to_string(),
+                })?;
+        let line_items = match input {
+            Value::Map(mut order) => match order.remove("lineItems") {
+                Some(Value::Array(line_items)) => line_items,
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        let mut transaction = facade.managed_transaction().await?;
+        let mut total = 0;
+        for line_item in line_items {
+            let node = ResolverFacade::create_node(
+                &facade,
+                "LineItem",
+                Options::default(),
+                line_item,
+                &mut transaction,
+            )
+            .await?;
+            if let Some(Value::Int64(quantity)) = node.fields().get("quantity") {
+                total += quantity;
+            }
+        }
+
+        facade.resolve_scalar(total as i32)
+    })
+}
+
+#[tokio::main]
+async fn main() {
+    // parse warpgrapher config
+    let config = Configuration::try_from(CONFIG.to_string()).expect("Failed to parse CONFIG");
+
+    // define database endpoint
+    let db = CypherEndpoint::from_env()
+        .expect("Failed to parse cypher endpoint from environment")
+        .pool()
+        .await
+        .expect("Failed to create cypher database pool");
+
+    // define resolvers
+    let mut resolvers = Resolvers::<AppRequestContext>::new();
+    resolvers.insert("PlaceOrder".to_string(), Box::new(resolve_place_order));
+
+    // create warpgrapher engine
+    let engine: Engine<AppRequestContext> = Engine::new(config, db)
+        .with_resolvers(resolvers)
+        .build()
+        .expect("Failed to build engine");
+
+    // place an order with two line items
+    let query = "
+        mutation {
+            PlaceOrder(input: {
+                customer: \"alice\",
+                lineItems: [
+                    { sku: \"WIDGET\", quantity: 2 },
+                    { sku: \"GADGET\", quantity: 3 }
+                ]
+            })
+        }
+    "
+    .to_string();
+    let metadata = HashMap::new();
+    let result = engine.execute(query, None, None, metadata).await.unwrap();
+
+    // verify result
+    println!("result: {:#?}", result);
+}
@@ -4,7 +4,7 @@ use warpgrapher::engine::config::Configuration;
 use warpgrapher::engine::context::RequestContext;
 use warpgrapher::engine::database::cypher::CypherEndpoint;
 use warpgrapher::engine::database::DatabaseEndpoint;
-use warpgrapher::engine::validators::Validators;
+use warpgrapher::engine::validators::{ValidationError, Validators};
 use warpgrapher::engine::value::Value;
 use warpgrapher::{Engine, Error};
 
@@ -35,29 +35,23 @@ fn name_validator(value: &Value) -> Result<(), Error> {
         if let Some(Value::String(name)) = m.get("name") {
             if name == "KENOBI" {
                 Err(Error::ValidationFailed {
-                    message: format!(
-                        "Input validator for {field_name} failed. Cannot be named KENOBI",
-                        field_name = "name"
-                    ),
+                    errors: vec![ValidationError::new(
+                        "name".to_string(),
+                        "Cannot be named KENOBI".to_string(),
+                    )],
                 })
             } else {
                 Ok(())
             }
         } else {
-            Err(Error::ValidationFailed {
-                message: format!(
-                    "Input validator for {field_name} failed.",
-                    field_name = "name"
-                ),
-            })
+            Err(Error::validation_failed(
+                "Input validator for name failed.".to_string(),
+            ))
         }
     } else {
-        Err(Error::ValidationFailed {
-            message: format!(
-                "Input validator for {field_name} failed.",
-                field_name = "name"
-            ),
-        })
+        Err(Error::validation_failed(
+            "Input validator for name failed.".to_string(),
+        ))
     }
 }
 
@@ -95,7 +89,7 @@ async fn main() {
     "
     .to_string();
     let metadata = HashMap::new();
-    let result = engine.execute(query, None, metadata).await.unwrap();
+    let result = engine.execute(query, None, None, metadata).await.unwrap();
 
     println!("result: {:#?}", result);
 }
@@ -3,12 +3,15 @@
 use crate::engine::context::RequestContext;
 use crate::{Engine, Error};
 use inflector::Inflector;
+use juniper::{InputValue, Spanning};
 use log::{debug, trace};
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::Deserialize;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// A Warpgrapher GraphQL client
 ///
@@ -31,7 +34,7 @@ pub enum Client<RequestCtx: RequestContext> {
         headers: HeaderMap,
     },
     Local {
-        engine: Box<Engine<RequestCtx>>,
+        engine: Arc<Engine<RequestCtx>>,
         metadata: Option<HashMap<String, String>>,
     },
 }
@@ -78,6 +81,10 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// Takes a Warpgrapher engine and returns a new ['Client'] initialized to query that engine.
     /// The type parameter is the [`RequestContext`] used by the engine.
     ///
+    /// The engine is held behind an [`Arc`], so cloning the client is cheap and all clones share
+    /// the same engine. Pass an `Arc<Engine>` to share an engine that is also used elsewhere.
+    ///
+    /// [`Arc`]: https://doc.rust-lang.org/std/sync/struct.Arc.html
     /// [`Client`]: ./enum.Client.html
     /// [`RequestContext`]: ../engine/context/trait.RequestContext.html
     ///
@@ -100,27 +107,57 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// # }
     /// ```
     pub fn new_with_engine(
-        engine: Engine<RequestCtx>,
+        engine: impl Into<Arc<Engine<RequestCtx>>>,
         metadata: Option<HashMap<String, String>>,
     ) -> Client<RequestCtx> {
         trace!("Client::new_with_engine called");
         Client::<RequestCtx>::Local {
-            engine: Box::new(engine),
+            engine: engine.into(),
             metadata,
         }
     }
 
+    /// Returns the version of the app served by a local engine, if one was set when building the
+    /// engine. Returns `None` for an HTTP client, whose remote version can only be read with the
+    /// `_version` GraphQL query.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Client, Configuration, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+    ///     .with_version("1.0.0".to_string())
+    ///     .build()?;
+    /// let client = Client::<()>::new_with_engine(engine, None);
+    ///
+    /// assert_eq!(Some("1.0.0"), client.version());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn version(&self) -> Option<&str> {
+        match self {
+            Client::Http { .. } => None,
+            Client::Local { engine, .. } => engine.version(),
+        }
+    }
+
     /// Executes a graphql query
     ///
     /// # Arguments
     ///
     /// * query - text of the query statement, parameterized to avoid query injection attacks
+    /// * operation_name - an optional name of the operation to run, if the query text holds more
+    ///   than one named operation. The response holds an error if the query has more than one
+    ///   operation and no name is given.
     /// * input - a [`serde_json::Value`], specifically a Value::Object, containing the arguments
     /// to the graph query
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     /// * result_field - an optional name of a field under 'data' that holds the GraphQL response.
     /// If present, the object with name `result_field` under `data` will be returned. If `None`,
     /// the `data` object will be returned.
@@ -151,78 +188,198 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// let mut client = Client::<()>::new_with_http("http://localhost:5000/graphql", None).unwrap();
     ///
     /// let query = "query { Project { id name } }";
-    /// let results = client.graphql("query { Project { id name } }", None, None, Some("Project")).await;
+    /// let results = client.graphql("query { Project { id name } }", None, None, None, Some("Project")).await;
+    ///
+    /// let query = "query Projects { Project { id name } } query Users { User { id } }";
+    /// let results = client.graphql(query, Some("Users"), None, None, Some("User")).await;
     /// # }
     /// ```
     pub async fn graphql(
         &mut self,
         query: &str,
+        operation_name: Option<&str>,
         input: Option<&Value>,
         options: Option<&Value>,
         result_field_opt: Option<&str>,
     ) -> Result<Value, Error> {
         trace!(
-            "Client::graphql called -- query: {} | input: {:#?} | options: {:#?} | result_field: {:#?}",
+            "Client::graphql called -- query: {} | operation_name: {:?} | input: {:#?} | options: {:#?} | result_field: {:#?}",
             query,
+            operation_name,
             input,
             options,
             result_field_opt,
         );
 
-        // format request body
-        let req_body = json!({
-            "query": query.to_string(),
-            "variables": {
-                "input": input,
-                "options": options
+        let mut body = self.execute(query, operation_name, input, options).await?;
+        debug!("Client::graphql -- response body: {:#?}", body);
+
+        if let Some(result_field) = result_field_opt {
+            body.as_object_mut()
+                .and_then(|m| m.remove("data"))
+                .and_then(|mut d| d.as_object_mut().and_then(|dm| dm.remove(result_field)))
+                .ok_or_else(|| Error::PayloadNotFound {
+                    response: body.to_owned(),
+                })
+        } else {
+            body.as_object_mut()
+                .and_then(|m| m.remove("data"))
+                .ok_or_else(|| Error::PayloadNotFound {
+                    response: body.to_owned(),
+                })
+        }
+    }
+
+    /// Executes a batch of GraphQL requests with a single call to the server or local engine,
+    /// saving the round trips of sending each request on its own. The server executes each
+    /// request as a request of its own, with its own request context and transactions, so a
+    /// failed request doesn't affect the others.
+    ///
+    /// # Arguments
+    ///
+    /// * requests - the [`Request`]s to execute, each with its query text and optional operation
+    ///   name, input, and options
+    ///
+    /// [`Request`]: ./struct.Request.html
+    ///
+    /// # Return
+    ///
+    /// A result for each request, in the order of the requests. The result of a request is the
+    /// `data` object of its response, or an [`Error`] variant [`PayloadNotFound`] holding the
+    /// whole response, with its `errors`, if the response has no data.
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    ///
+    /// # Errors
+    ///
+    /// * [`ClientRequestFailed`] - if the HTTP response is a non-OK
+    /// * [`PayloadNotFound`] - if the response body is not an array with a response for each
+    ///   request
+    ///
+    /// [`ClientRequestFailed`]: ../enum.Error.html#variant.ClientRequestFailed
+    /// [`PayloadNotFound`]: ../enum.Error.html#variant.PayloadNotFound
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use serde_json::json;
+    /// # use warpgrapher::Client;
+    /// # use warpgrapher::client::Request;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut client = Client::<()>::new_with_http("http://localhost:5000/graphql", None).unwrap();
+    ///
+    /// let results = client
+    ///     .graphql_batch(vec![
+    ///         Request::new("query { Project { id name } }"),
+    ///         Request::new("query Read($input: UserQueryInput) { User(input: $input) { id } }")
+    ///             .with_input(json!({"name": {"EQ": "alice"}})),
+    ///     ])
+    ///     .await;
+    /// # }
+    /// ```
+    pub async fn graphql_batch(
+        &mut self,
+        requests: Vec<Request>,
+    ) -> Result<Vec<Result<Value, Error>>, Error> {
+        trace!("Client::graphql_batch called -- requests: {:#?}", requests);
+
+        let req_body = Value::Array(requests.iter().map(Request::to_value).collect());
+        let body = match self {
+            Client::Http { endpoint, headers } => {
+                let client = reqwest::Client::new();
+                let response = client
+                    .post(endpoint.as_str())
+                    .headers(request_headers(headers))
+                    .json(&req_body)
+                    .send()
+                    .await?;
+                response.json::<serde_json::Value>().await?
+            }
+            Client::Local { engine, metadata } => {
+                engine
+                    .execute_batch(req_body, metadata.clone().unwrap_or_default())
+                    .await?
             }
-        });
+        };
+        debug!("Client::graphql_batch -- response body: {:#?}", body);
 
-        debug!("Client::graphql making request -- req_body: {}", req_body);
-        let mut body = match self {
+        match body {
+            Value::Array(responses) if responses.len() == requests.len() => Ok(responses
+                .into_iter()
+                .map(|mut response| {
+                    response
+                        .as_object_mut()
+                        .and_then(|m| m.remove("data"))
+                        .filter(|data| !data.is_null())
+                        .ok_or(Error::PayloadNotFound { response })
+                })
+                .collect()),
+            response => Err(Error::PayloadNotFound { response }),
+        }
+    }
+
+    /// Sends a query to the server or local engine and returns the whole GraphQL response,
+    /// including any errors
+    async fn execute(
+        &mut self,
+        query: &str,
+        operation_name: Option<&str>,
+        input: Option<&Value>,
+        options: Option<&Value>,
+    ) -> Result<Value, Error> {
+        debug!(
+            "Client::execute making request -- query: {}, operation_name: {:?}, input: {:?}, options: {:?}",
+            query, operation_name, input, options
+        );
+        Ok(match self {
             Client::Http { endpoint, headers } => {
+                // format request body
+                let req_body = json!({
+                    "query": query.to_string(),
+                    "operationName": operation_name,
+                    "variables": {
+                        "input": input,
+                        "options": options
+                    }
+                });
+
                 let client = reqwest::Client::new();
                 let response = client
                     .post(endpoint.as_str())
-                    .headers(headers.clone())
+                    .headers(request_headers(headers))
                     .json(&req_body)
                     .send()
                     .await?;
                 response.json::<serde_json::Value>().await?
             }
             Client::Local { engine, metadata } => {
+                // The variables are parsed straight from the borrowed values, rather than
+                // assembled into a JSON object for the engine to parse again
+                let mut variables = Vec::new();
+                for (name, value) in [("input", input), ("options", options)] {
+                    if let Some(value) = value {
+                        variables.push((
+                            Spanning::unlocated(name.to_string()),
+                            Spanning::unlocated(InputValue::deserialize(value)?),
+                        ));
+                    }
+                }
                 engine
-                    .execute(
+                    .execute_with_input_value(
                         query.to_string(),
-                        match (input, options) {
-                            (Some(i), Some(o)) => {
-                                Some(serde_json::json!({"input": i.clone(), "options": o.clone()}))
-                            }
-                            (Some(i), None) => Some(serde_json::json!({"input": i.clone()})),
-                            (None, Some(o)) => Some(serde_json::json!({"options": o.clone()})),
-                            (None, None) => None,
+                        operation_name.map(|name| name.to_string()),
+                        if variables.is_empty() {
+                            None
+                        } else {
+                            Some(InputValue::Object(variables))
                         },
                         metadata.clone().unwrap_or_default(),
                     )
                     .await?
             }
-        };
-        debug!("Client::graphql -- response body: {:#?}", body);
-
-        if let Some(result_field) = result_field_opt {
-            body.as_object_mut()
-                .and_then(|m| m.remove("data"))
-                .and_then(|mut d| d.as_object_mut().and_then(|dm| dm.remove(result_field)))
-                .ok_or_else(|| Error::PayloadNotFound {
-                    response: body.to_owned(),
-                })
-        } else {
-            body.as_object_mut()
-                .and_then(|m| m.remove("data"))
-                .ok_or_else(|| Error::PayloadNotFound {
-                    response: body.to_owned(),
-                })
-        }
+        })
     }
 
     /// Creates a node
@@ -235,9 +392,9 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// * input - a [`serde_json::Value`], specifically a Value::Object, containing the arguments
     /// to the graph query
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     ///
     /// [`Type`]: ../engine/config/struct.Type.html
     ///
@@ -287,10 +444,134 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
 
         let query = Client::<()>::fmt_create_node_query(type_name, shape);
         let result_field = type_name.to_string() + "Create";
-        self.graphql(&query, Some(input), options, Some(&result_field))
+        self.graphql(&query, None, Some(input), options, Some(&result_field))
             .await
     }
 
+    /// Creates nodes in batches, committing each batch in a transaction of its own, so that a
+    /// large import neither holds a single transaction open for its whole length nor sends all
+    /// of its nodes in one request. Each batch is sent as one request to the `CreateBatch`
+    /// mutation of the type.
+    ///
+    /// A batched create is not atomic. If a batch fails, that batch is rolled back, but the
+    /// batches before it stay committed and the batches after it are not sent.
+    ///
+    /// # Arguments
+    ///
+    /// * type_name - the name of the [`Type`] for which to create nodes
+    /// * shape - the GraphQL query shape, meaning the selection of objects and properties to be
+    ///   returned for each created node
+    /// * inputs - a slice of [`serde_json::Value`] objects, each holding the input for one node
+    /// * batch_size - the number of nodes created in each batch. A batch size of zero is treated
+    ///   as one.
+    /// * options - used to pass additional options to the query
+    /// * progress - called after each batch is committed, with a [`serde_json::Value`] array of
+    ///   the nodes created by the batch and the total number of nodes committed so far. The nodes
+    ///   are in the same order as their inputs, so the Nth node of the array was created from the
+    ///   input at index `committed - nodes.len() + N`, which lets the caller match the ids of the
+    ///   created nodes to their inputs.
+    ///
+    /// [`Type`]: ../engine/config/struct.Type.html
+    ///
+    /// # Return
+    ///
+    /// The number of nodes created
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of the following kinds:
+    ///
+    /// * [`BatchCommitFailed`] - if a batch fails, holding the number of nodes committed before it
+    /// * [`ClientRequestFailed`] - if the HTTP response is a non-OK
+    /// * [`PayloadNotFound`] - if the JSON response body is not a valid GraphQL
+    ///   response
+    ///
+    /// [`BatchCommitFailed`]: ../enum.Error.html#variant.BatchCommitFailed
+    /// [`ClientRequestFailed`]: ../enum.Error.html#variant.ClientRequestFailed
+    /// [`PayloadNotFound`]: ../enum.Error.html#variant.PayloadNotFound
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use serde_json::json;
+    /// # use warpgrapher::Client;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut client = Client::<()>::new_with_http("http://localhost:5000/graphql", None).unwrap();
+    ///
+    /// let inputs = (0..10_000)
+    ///     .map(|i| json!({"name": format!("Project {}", i)}))
+    ///     .collect::<Vec<_>>();
+    /// let created = client
+    ///     .create_nodes_batched("Project", "id", &inputs, 500, None, |_nodes, committed| {
+    ///         println!("{} of {} committed", committed, inputs.len())
+    ///     })
+    ///     .await;
+    /// # }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_nodes_batched<F>(
+        &mut self,
+        type_name: &str,
+        shape: &str,
+        inputs: &[Value],
+        batch_size: usize,
+        options: Option<&Value>,
+        mut progress: F,
+    ) -> Result<usize, Error>
+    where
+        F: FnMut(&Value, usize),
+    {
+        trace!(
+            "Client::create_nodes_batched called -- type_name: {} | shape: {} | inputs: {} | batch_size: {} | options: {:#?}",
+            type_name,
+            shape,
+            inputs.len(),
+            batch_size,
+            options
+        );
+
+        let query = Client::<()>::fmt_create_node_batch_query(type_name, shape);
+        let result_field = type_name.to_string() + "CreateBatch";
+        let mut committed = 0;
+        for batch in inputs.chunks(batch_size.max(1)) {
+            // The server's batch size matches the request, so each request is one transaction
+            let input = json!({"nodes": batch, "batchSize": batch.len()});
+            let mut body = self.execute(&query, None, Some(&input), options).await?;
+
+            let created = body
+                .get_mut("data")
+                .and_then(|d| d.get_mut(&result_field))
+                .map(Value::take)
+                .unwrap_or(Value::Null);
+            committed += created.as_array().map_or(0, |nodes| nodes.len());
+
+            if let Some(error) = body
+                .get("errors")
+                .and_then(|errors| errors.as_array())
+                .and_then(|errors| errors.first())
+            {
+                return Err(Error::BatchCommitFailed {
+                    committed,
+                    source: error
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .unwrap_or("Unknown error")
+                        .to_string()
+                        .into(),
+                });
+            }
+            if !created.is_array() {
+                return Err(Error::PayloadNotFound { response: body });
+            }
+
+            progress(&created, committed);
+        }
+
+        Ok(committed)
+    }
+
     /// Creates one or more relationships
     ///
     /// # Arguments
@@ -300,20 +581,22 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// * shape - the GraphQL query shape, meaning the selection of objects and properties to be
     /// returned in the query result
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     /// * match_input - a [`serde_json::Value`], specifically a Value::Object, containing the
     /// arguments to the graph query to select the node(s) on which to create the relationship
     /// * create_input - a [`serde_json::Value`], specifically a Value::Object, containing the
-    /// arguments to the graph query to use in creating the relationship
+    ///   arguments to the graph query to use in creating the relationship. For a relationship that
+    ///   is a list, this may instead be a Value::Array of such objects, connecting the matched
+    ///   node(s) to many destinations in a single transaction.
     ///
     /// [`Relationship`]: ../engine/config/struct.Relationship.html
     /// [`Type`]: ../engine/config/struct.Type.html
     ///
     /// # Return
     ///
-    /// A [`serde_json::Value`] containing the query response
+    /// A [`serde_json::Value`] containing the query response, a list of the created relationships
     ///
     /// # Errors
     ///
@@ -344,6 +627,15 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     ///            "dst": {"Feature": {"NEW": {"name": "NewFeature"}}}}),
     ///            None
     /// ).await;
+    ///
+    /// let proj_issues = client.create_rel("Project",
+    ///     "issues",
+    ///     "id dst { ...on Feature { name } ...on Bug { name } }",
+    ///     &json!({"name": "ProjectName"}),
+    ///     &json!([{"dst": {"Feature": {"EXISTING": {"name": {"EQ": "OldFeature"}}}}},
+    ///             {"dst": {"Bug": {"NEW": {"name": "NewBug"}}}}]),
+    ///     None
+    /// ).await;
     /// # }
     /// ```
     pub async fn create_rel(
@@ -373,7 +665,7 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
                 .collect::<String>())
             + "Create";
 
-        self.graphql(&query, Some(&input), options, Some(&result_field))
+        self.graphql(&query, None, Some(&input), options, Some(&result_field))
             .await
     }
 
@@ -389,9 +681,9 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// relationships incoming to and outgoing from the node are deleted. The delete input argument
     /// allows for extending the delete operation through relationships to destination nodes.
     ///  * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     ///
     /// [`Type`]: ../engine/config/struct.Type.html
     ///
@@ -446,7 +738,7 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
             json!({ "MATCH": match_input })
         };
         let result_field = type_name.to_string() + "Delete";
-        self.graphql(&query, Some(&input), options, Some(&result_field))
+        self.graphql(&query, None, Some(&input), options, Some(&result_field))
             .await
     }
 
@@ -467,9 +759,9 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// not deleted along with a relationship, but this parameter can be used to delete the
     /// destination node of the relationship as well.
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     ///
     /// [`Relationship`]: ../engine/config/struct.Relationship.html
     /// [`Type`]: ../engine/config/struct.Type.html
@@ -549,7 +841,7 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
                 .split_whitespace()
                 .collect::<String>())
             + "Delete";
-        self.graphql(&query, input, options, Some(&result_field))
+        self.graphql(&query, None, input, options, Some(&result_field))
             .await
     }
 
@@ -563,9 +855,9 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// * input - a [`serde_json::Value`], specifically a Value::Object, containing the arguments
     /// to the graph query
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     ///
     /// [`Type`]: ../engine/config/struct.Type.html
     ///
@@ -612,7 +904,68 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
         );
 
         let query = Client::<()>::fmt_read_node_query(type_name, shape);
-        self.graphql(&query, input, options, Some(type_name)).await
+        self.graphql(&query, None, input, options, Some(type_name))
+            .await
+    }
+
+    /// Queries whether any node matches the input, without retrieving the matching nodes
+    ///
+    /// # Arguments
+    ///
+    /// * type_name - the name of the [`Type`] to be checked
+    /// * input - a [`serde_json::Value`], specifically a Value::Object, containing the arguments
+    ///   to the graph query
+    ///
+    /// [`Type`]: ../engine/config/struct.Type.html
+    ///
+    /// # Return
+    ///
+    /// True if at least one node matches the input, false otherwise
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] of the following kinds:
+    ///
+    /// * [`ClientRequestFailed`] - if the HTTP response is a non-OK
+    /// * [`PayloadNotFound`] - if the JSON response body is not a valid GraphQL
+    ///   response, or does not contain a boolean result
+    ///
+    /// [`ClientRequestFailed`]: ../enum.Error.html#variant.ClientRequestFailed
+    /// [`PayloadNotFound`]: ../enum.Error.html#variant.PayloadNotFound
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use serde_json::json;
+    /// # use warpgrapher::Client;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut client = Client::<()>::new_with_http("http://localhost:5000/graphql", None).unwrap();
+    ///
+    /// let exists = client.node_exists("Project",
+    ///     Some(&json!({"name": {"EQ": "MJOLNIR"}}))).await;
+    /// # }
+    /// ```
+    pub async fn node_exists(
+        &mut self,
+        type_name: &str,
+        input: Option<&Value>,
+    ) -> Result<bool, Error> {
+        trace!(
+            "Client::node_exists called -- type_name: {} | input: {:#?}",
+            type_name,
+            input
+        );
+
+        let query = Client::<()>::fmt_node_exists_query(type_name);
+        let result_field = type_name.to_string() + "Exists";
+        let result = self
+            .graphql(&query, None, input, None, Some(&result_field))
+            .await?;
+        result
+            .as_bool()
+            .ok_or(Error::PayloadNotFound { response: result })
     }
 
     /// Queries for one or more relationships
@@ -626,9 +979,11 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// * input - a [`serde_json::Value`], specifically a Value::Object, containing the arguments
     /// to the graph query to select the relationship(s) to return
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. A field of the relationship is
+    ///     named as it is, and a field of the destination node is named with a `dst:` prefix,
+    ///     such as `dst:priority`. See [`Options`] to build it.
     ///
     /// [`Relationship`]: ../engine/config/struct.Relationship.html
     /// [`Type`]: ../engine/config/struct.Type.html
@@ -653,6 +1008,7 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// ```rust,no_run
     /// # use serde_json::json;
     /// # use warpgrapher::Client;
+    /// # use warpgrapher::client::Options;
     ///
     /// # #[tokio::main]
     /// # async fn main() {
@@ -662,6 +1018,9 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     ///     Some(&json!({"props": {"since": "2000"}})),
     ///     None
     /// ).await;
+    ///
+    /// let options = Options::new().with_dst_sort("priority", "desc").to_value();
+    /// let top_issues = client.read_rel("Project", "issues", "id since", None, Some(&options)).await;
     /// # }
     /// ```
     pub async fn read_rel(
@@ -686,7 +1045,7 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
             + &*((&rel_name.to_string().to_title_case())
                 .split_whitespace()
                 .collect::<String>());
-        self.graphql(&query, input, options, Some(&result_field))
+        self.graphql(&query, None, input, options, Some(&result_field))
             .await
     }
 
@@ -702,9 +1061,9 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// * update_input - a [`serde_json::Value`], specifically a Value::Object, containing the
     /// arugments to the graph query used to change the properties of the nodes being updated
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     ///
     /// [`Type`]: ../engine/config/struct.Type.html
     ///
@@ -758,7 +1117,7 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
         let query = Client::<()>::fmt_update_node_query(type_name, shape);
         let input = json!({"MATCH": match_input, "SET": update_input});
         let result_field = type_name.to_string() + "Update";
-        self.graphql(&query, Some(&input), options, Some(&result_field))
+        self.graphql(&query, None, Some(&input), options, Some(&result_field))
             .await
     }
 
@@ -775,9 +1134,9 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
     /// * update_input - a [`serde_json::Value`], specifically a Value::Object, containing the
     /// arguments to the graph query used to change the properties of the items being updated
     /// * options - used to pass additional options to the query,
-    ///   - direction - a string literal of `ascending` or `descending` indicating the order in which
-    ///     to sort results, if the `orderBy` option is also provided.
-    ///   - orderBy - the name of a scalar field by which to sort the data to be returned
+    ///   - sort - a list of objects, each with an `orderBy` field naming a scalar field by which
+    ///     to sort the data to be returned, and an optional `direction` of `ascending` or
+    ///     `descending`. Later entries break ties in earlier ones. See [`Options`] to build it.
     ///
     /// [`Relationship`]: ../engine/config/struct.Relationship.html
     /// [`Type`]: ../engine/config/struct.Type.html
@@ -841,7 +1200,7 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
                 .split_whitespace()
                 .collect::<String>())
             + "Update";
-        self.graphql(&query, Some(&input), options, Some(&result_field))
+        self.graphql(&query, None, Some(&input), options, Some(&result_field))
             .await
     }
 
@@ -855,6 +1214,16 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
         )
     }
 
+    fn fmt_create_node_batch_query(type_name: &str, shape: &str) -> String {
+        format!(
+            "mutation CreateBatch($input: {type_name}CreateBatchInput!, $options: {type_name}Options) {{
+                {type_name}CreateBatch(input: $input, options: $options) {{ {shape} }}
+            }}",
+            type_name = type_name,
+            shape = shape
+        )
+    }
+
     fn fmt_create_rel_query(type_name: &str, rel_name: &str, shape: &str) -> String {
         format!(
             "mutation Create($input: {type_name}{rel_name}CreateInput!, $options: {type_name}{rel_name}Options) {{
@@ -899,6 +1268,15 @@ impl<RequestCtx: RequestContext> Client<RequestCtx> {
         )
     }
 
+    fn fmt_node_exists_query(type_name: &str) -> String {
+        format!(
+            "query Exists($input: {type_name}QueryInput) {{ 
+                {type_name}Exists(input: $input)
+            }}",
+            type_name = type_name
+        )
+    }
+
     fn fmt_read_rel_query(type_name: &str, rel_name: &str, shape: &str) -> String {
         format!(
             "query Read($input: {type_name}{rel_name}QueryInput, $options: {type_name}{rel_name}Options) {{
@@ -950,9 +1328,273 @@ where
     }
 }
 
+/// Builds the `options` value passed to the [`Client`] query methods, such as a compound sort
+/// of several `orderBy` fields. Converts into the JSON shape the generated `Options` input types
+/// expect, which HTTP clients not using the [`Client`] can send as the `options` variable:
+///
+/// ```json
+/// { "sort": [ { "orderBy": "name", "direction": "ascending" },
+///             { "orderBy": "created", "direction": "descending" } ] }
+/// ```
+///
+/// Results are sorted by the first entry, with ties broken by the second entry, and so on.
+///
+/// [`Client`]: ./enum.Client.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use serde_json::json;
+/// # use warpgrapher::client::Options;
+///
+/// let options = Options::sort(&[("name", "asc"), ("created", "desc")]);
+///
+/// assert_eq!(
+///     json!({"sort": [
+///         {"orderBy": "name", "direction": "ascending"},
+///         {"orderBy": "created", "direction": "descending"}
+///     ]}),
+///     options.to_value()
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    sort: Vec<(String, String)>,
+}
+
+impl Options {
+    /// Creates a new, empty set of options
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::client::Options;
+    ///
+    /// let options = Options::new();
+    /// ```
+    pub fn new() -> Options {
+        Options::default()
+    }
+
+    /// Creates a set of options sorting by each of the `(orderBy, direction)` pairs in `sorts`,
+    /// in order. The direction may be `asc` or `ascending`, or `desc` or `descending`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::client::Options;
+    ///
+    /// let options = Options::sort(&[("name", "asc"), ("created", "desc")]);
+    /// ```
+    pub fn sort(sorts: &[(&str, &str)]) -> Options {
+        sorts
+            .iter()
+            .fold(Options::new(), |options, (order_by, direction)| {
+                options.with_sort(order_by, direction)
+            })
+    }
+
+    /// Adds a sort by the `order_by` field, applied after any sorts already added. The direction
+    /// may be `asc` or `ascending`, or `desc` or `descending`. Other directions are sent as given,
+    /// and rejected by the server.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::client::Options;
+    ///
+    /// let options = Options::new()
+    ///     .with_sort("name", "ascending")
+    ///     .with_sort("dst:email", "descending");
+    /// ```
+    pub fn with_sort(mut self, order_by: &str, direction: &str) -> Options {
+        let direction = match direction.to_lowercase().as_str() {
+            "asc" | "ascending" => "ascending".to_string(),
+            "desc" | "descending" => "descending".to_string(),
+            _ => direction.to_string(),
+        };
+        self.sort.push((order_by.to_string(), direction));
+        self
+    }
+
+    /// Adds a sort by the `property` of the destination nodes of the relationships being read,
+    /// applied after any sorts already added. This is the same as calling [`with_sort`] with the
+    /// property prefixed by `dst:`. It applies only to relationship reads, such as
+    /// [`Client::read_rel`], and to the relationships selected in a node query. The direction
+    /// may be `asc` or `ascending`, or `desc` or `descending`.
+    ///
+    /// [`Client::read_rel`]: ./enum.Client.html#method.read_rel
+    /// [`with_sort`]: #method.with_sort
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use serde_json::json;
+    /// # use warpgrapher::client::Options;
+    ///
+    /// let options = Options::new().with_dst_sort("priority", "desc");
+    ///
+    /// assert_eq!(
+    ///     json!({"sort": [{"orderBy": "dst:priority", "direction": "descending"}]}),
+    ///     options.to_value()
+    /// );
+    /// ```
+    pub fn with_dst_sort(self, property: &str, direction: &str) -> Options {
+        self.with_sort(&("dst:".to_string() + property), direction)
+    }
+
+    /// Returns the options as the JSON value passed to the [`Client`] query methods
+    ///
+    /// [`Client`]: ./enum.Client.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::Client;
+    /// # use warpgrapher::client::Options;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let mut client = Client::<()>::new_with_http("http://localhost:5000/graphql", None).unwrap();
+    ///
+    /// let options = Options::sort(&[("name", "asc"), ("created", "desc")]).to_value();
+    /// let projects = client.read_node("Project", "id name", None, Some(&options)).await;
+    /// # }
+    /// ```
+    pub fn to_value(&self) -> Value {
+        json!({
+            "sort": self
+                .sort
+                .iter()
+                .map(|(order_by, direction)| json!({"orderBy": order_by, "direction": direction}))
+                .collect::<Vec<Value>>()
+        })
+    }
+}
+
+impl From<Options> for Value {
+    fn from(options: Options) -> Value {
+        options.to_value()
+    }
+}
+
+/// Returns the headers of an outbound request, with the trace context of the current span if
+/// the `otel` feature is enabled
+fn request_headers(headers: &HeaderMap) -> HeaderMap {
+    #[allow(unused_mut)]
+    let mut headers = headers.clone();
+    #[cfg(feature = "otel")]
+    crate::engine::telemetry::inject_context(&mut headers);
+    headers
+}
+
+/// A GraphQL request sent in a batch with [`Client::graphql_batch`]
+///
+/// [`Client::graphql_batch`]: ./enum.Client.html#method.graphql_batch
+///
+/// # Examples
+///
+/// ```rust
+/// # use serde_json::json;
+/// # use warpgrapher::client::{Options, Request};
+///
+/// let request = Request::new("query Read($input: ProjectQueryInput, $options: ProjectOptions) { Project(input: $input, options: $options) { id } }")
+///     .with_operation_name("Read")
+///     .with_input(json!({"name": {"EQ": "Project Zero"}}))
+///     .with_options(Options::sort(&[("name", "asc")]).to_value());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Request {
+    query: String,
+    operation_name: Option<String>,
+    input: Option<Value>,
+    options: Option<Value>,
+}
+
+impl Request {
+    /// Creates a request with the given query text, and no operation name, input, or options
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::client::Request;
+    ///
+    /// let request = Request::new("query { Project { id name } }");
+    /// ```
+    pub fn new(query: &str) -> Request {
+        Request {
+            query: query.to_string(),
+            ..Request::default()
+        }
+    }
+
+    /// Sets the name of the operation to run, if the query text holds more than one named
+    /// operation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::client::Request;
+    ///
+    /// let request = Request::new("query Projects { Project { id } } query Users { User { id } }")
+    ///     .with_operation_name("Users");
+    /// ```
+    pub fn with_operation_name(mut self, operation_name: &str) -> Request {
+        self.operation_name = Some(operation_name.to_string());
+        self
+    }
+
+    /// Sets the value of the `$input` variable of the query
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use serde_json::json;
+    /// # use warpgrapher::client::Request;
+    ///
+    /// let request = Request::new("query Read($input: ProjectQueryInput) { Project(input: $input) { id } }")
+    ///     .with_input(json!({"name": {"EQ": "Project Zero"}}));
+    /// ```
+    pub fn with_input(mut self, input: Value) -> Request {
+        self.input = Some(input);
+        self
+    }
+
+    /// Sets the value of the `$options` variable of the query
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::client::{Options, Request};
+    ///
+    /// let request = Request::new("query Read($options: ProjectOptions) { Project(options: $options) { id } }")
+    ///     .with_options(Options::sort(&[("name", "asc")]).to_value());
+    /// ```
+    pub fn with_options(mut self, options: Value) -> Request {
+        self.options = Some(options);
+        self
+    }
+
+    /// Returns the request as an object in the body of a GraphQL HTTP request
+    fn to_value(&self) -> Value {
+        json!({
+            "query": self.query,
+            "operationName": self.operation_name,
+            "variables": {
+                "input": self.input,
+                "options": self.options
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Client;
+    use super::{Client, Options, Request};
+    use crate::engine::database::no_database::NoDatabasePool;
+    use crate::{Configuration, Engine, Error};
+    use serde_json::{json, Value};
+    use std::sync::Arc;
 
     /// Passes if a new client is created with the endpoint passed into the constructor
     #[test]
@@ -966,6 +1608,109 @@ mod tests {
         }
     }
 
+    /// Passes if a local client returns the version of its engine, and an HTTP client returns
+    /// none
+    #[test]
+    fn version() {
+        let engine = Engine::<()>::new(
+            Configuration::new(1, Vec::new(), Vec::new()),
+            NoDatabasePool {},
+        )
+        .with_version("1.0.0".to_string())
+        .build()
+        .unwrap();
+        let client = Client::<()>::new_with_engine(engine, None);
+        assert_eq!(Some("1.0.0"), client.version());
+
+        let client = Client::<()>::new_with_http("http://localhost:5000/graphql", None).unwrap();
+        assert_eq!(None, client.version());
+    }
+
+    /// Passes if clones of a local client share the same engine
+    #[test]
+    fn clone_local_shares_engine() {
+        let engine = Engine::<()>::new(
+            Configuration::new(1, Vec::new(), Vec::new()),
+            NoDatabasePool {},
+        )
+        .build()
+        .unwrap();
+        let client = Client::<()>::new_with_engine(engine, None);
+        let clone = client.clone();
+
+        if let (Client::Local { engine: e0, .. }, Client::Local { engine: e1, .. }) =
+            (&client, &clone)
+        {
+            assert!(Arc::ptr_eq(e0, e1));
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// Passes if a local client returns the data of each request in a batch, in order, and the
+    /// response of a request without data as an error
+    #[tokio::test]
+    async fn graphql_batch_local() {
+        let engine = Engine::<()>::new(
+            Configuration::new(1, Vec::new(), Vec::new()),
+            NoDatabasePool {},
+        )
+        .with_version("1.0.0".to_string())
+        .build()
+        .unwrap();
+        let mut client = Client::<()>::new_with_engine(engine, None);
+
+        let results = client
+            .graphql_batch(vec![
+                Request::new("query { _version }"),
+                Request::new("query { unknown }"),
+                Request::new("query A { __typename } query B { _version }")
+                    .with_operation_name("B"),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), &json!({"_version": "1.0.0"}));
+        assert!(matches!(
+            &results[1],
+            Err(Error::PayloadNotFound { response }) if response["errors"].is_array()
+        ));
+        assert_eq!(results[2].as_ref().unwrap(), &json!({"_version": "1.0.0"}));
+    }
+
+    /// Passes if options with several sorts convert to the sort list expected by the engine, in
+    /// order, with short directions expanded
+    #[test]
+    fn options_sort() {
+        let options = Options::sort(&[("name", "asc"), ("created", "DESC")])
+            .with_sort("dst:email", "descending");
+        assert_eq!(
+            json!({"sort": [
+                {"orderBy": "name", "direction": "ascending"},
+                {"orderBy": "created", "direction": "descending"},
+                {"orderBy": "dst:email", "direction": "descending"}
+            ]}),
+            Value::from(options)
+        );
+        assert_eq!(json!({ "sort": [] }), Options::new().to_value());
+    }
+
+    /// Passes if a sort by a destination node property is prefixed with dst:, in order with the
+    /// other sorts
+    #[test]
+    fn options_dst_sort() {
+        let options = Options::new()
+            .with_dst_sort("priority", "desc")
+            .with_sort("since", "asc");
+        assert_eq!(
+            json!({"sort": [
+                {"orderBy": "dst:priority", "direction": "descending"},
+                {"orderBy": "since", "direction": "ascending"}
+            ]}),
+            options.to_value()
+        );
+    }
+
     /// Passes if a client formats a read node query correctly
     #[test]
     fn fmt_read_node_query() {
@@ -976,6 +1721,16 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    /// Passes if a client formats a node exists query correctly
+    #[test]
+    fn fmt_node_exists_query() {
+        let actual = Client::<()>::fmt_node_exists_query("Project");
+        let expected = r#"query Exists($input: ProjectQueryInput) { 
+                ProjectExists(input: $input)
+            }"#;
+        assert_eq!(actual, expected);
+    }
+
     /// Passes if a client formats a create node query correctly
     #[test]
     fn fmt_create_node_query() {
@@ -986,6 +1741,16 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    /// Passes if a client formats a batched create node query correctly
+    #[test]
+    fn fmt_create_node_batch_query() {
+        let actual = Client::<()>::fmt_create_node_batch_query("Project", "id");
+        let expected = r#"mutation CreateBatch($input: ProjectCreateBatchInput!, $options: ProjectOptions) {
+                ProjectCreateBatch(input: $input, options: $options) { id }
+            }"#;
+        assert_eq!(actual, expected);
+    }
+
     /// Passes if Client implements the Send trait
     #[test]
     fn test_send() {
@@ -0,0 +1,190 @@
+//! This module provides the hashing used by the engine's query allowlist, set with
+//! [`EngineBuilder::with_query_allowlist`]. A query is hashed after it is normalized, so that
+//! whitespace, commas, and comments don't change its hash, and an allowlist can be built from a
+//! client's queries as they are written in its source.
+//!
+//! [`EngineBuilder::with_query_allowlist`]: ../struct.EngineBuilder.html#method.with_query_allowlist
+use sha2::{Digest, Sha256};
+
+/// Returns the hash of a GraphQL query, as used by the query allowlist. The hash is the SHA-256
+/// digest, as lower-case hexadecimal, of the query after it is normalized with
+/// [`normalize_query`].
+///
+/// [`normalize_query`]: ./fn.normalize_query.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::allowlist::query_hash;
+///
+/// let hash = query_hash("query { Project { id } }");
+///
+/// assert_eq!(hash.len(), 64);
+/// assert_eq!(
+///     hash,
+///     query_hash("query {\n  Project {\n    id # the project id\n  }\n}\n")
+/// );
+/// ```
+pub fn query_hash(query: &str) -> String {
+    Sha256::digest(normalize_query(query).as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Returns a GraphQL query with its comments removed, and with the whitespace and commas between
+/// its tokens removed, or reduced to a single space where two tokens would otherwise run
+/// together. String literals are kept as they are. Two queries that differ only in their
+/// formatting normalize to the same string.
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::allowlist::normalize_query;
+///
+/// assert_eq!(
+///     normalize_query("query Projects($name: String) {\n  Project(input: {name: $name}) { id, name }\n}"),
+///     "query Projects($name:String){Project(input:{name:$name}){id name}}"
+/// );
+/// ```
+pub fn normalize_query(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    let mut separated = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                // a comment runs to the end of the line
+                while matches!(chars.peek(), Some(c) if *c != '\n' && *c != '\r') {
+                    chars.next();
+                }
+                separated = true;
+            }
+            c if c.is_whitespace() || c == ',' || c == '\u{feff}' => separated = true,
+            _ => {
+                if separated
+                    && matches!(normalized.chars().next_back(),
+                        Some(last) if !is_punctuator(last) && !is_punctuator(c))
+                {
+                    normalized.push(' ');
+                }
+                separated = false;
+                normalized.push(c);
+
+                if c == '"' {
+                    copy_string(&mut chars, &mut normalized);
+                }
+            }
+        }
+    }
+
+    normalized
+}
+
+/// Copies a string literal, whose opening quote has already been copied, through its closing
+/// quote. Block strings, opened with three quotes, are copied through their closing three quotes.
+fn copy_string<I>(chars: &mut std::iter::Peekable<I>, normalized: &mut String)
+where
+    I: Clone + Iterator<Item = char>,
+{
+    if chars.peek() != Some(&'"') {
+        while let Some(c) = chars.next() {
+            normalized.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        normalized.push(escaped);
+                    }
+                }
+                '"' => return,
+                _ => (),
+            }
+        }
+        return;
+    }
+
+    normalized.push(chars.next().unwrap_or('"'));
+    if chars.peek() != Some(&'"') {
+        // the empty string, ""
+        return;
+    }
+
+    normalized.push(chars.next().unwrap_or('"'));
+    let mut quotes = 0;
+    while let Some(c) = chars.next() {
+        normalized.push(c);
+        if c == '\\' && chars.clone().take(3).filter(|c| *c == '"').count() == 3 {
+            // an escaped triple quote, \""", doesn't close the block string
+            (0..3).for_each(|_| normalized.extend(chars.next()));
+            quotes = 0;
+        } else if c == '"' {
+            quotes += 1;
+            if quotes == 3 {
+                return;
+            }
+        } else {
+            quotes = 0;
+        }
+    }
+}
+
+/// Returns true if the character is a GraphQL punctuator, or the quote opening or closing a
+/// string, neither of which needs a space to separate it from the token beside it
+fn is_punctuator(c: char) -> bool {
+    matches!(
+        c,
+        '!' | '$' | '&' | '(' | ')' | ':' | '=' | '@' | '[' | ']' | '{' | '|' | '}' | '"'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_query, query_hash};
+
+    /// Passes if queries that differ only in their whitespace, commas, and comments normalize to
+    /// the same string, and string literals are left as they are
+    #[test]
+    fn normalize() {
+        assert_eq!(
+            normalize_query("{ Project(input: { name: { EQ: \"a,  b # c\" } }) { id, name } }"),
+            "{Project(input:{name:{EQ:\"a,  b # c\"}}){id name}}"
+        );
+        assert_eq!(
+            normalize_query(
+                "# list the projects\nquery Projects {\n  Project {\n    id\n    ...Details\n  }\n}\n\nfragment Details on Project { name }"
+            ),
+            "query Projects{Project{id ...Details}}fragment Details on Project{name}"
+        );
+        assert_eq!(
+            normalize_query("{ a(s: \"\", t: \"\"\"x \\\"\"\" #\"\"\") }"),
+            "{a(s:\"\"t:\"\"\"x \\\"\"\" #\"\"\")}"
+        );
+        assert_eq!(
+            normalize_query("{ a(s: \"\"\"\\\"\"\"\"\"\") }"),
+            "{a(s:\"\"\"\\\"\"\"\"\"\")}"
+        );
+        assert_eq!(
+            normalize_query("{ a(s: \"x\\\"y\" ) }"),
+            "{a(s:\"x\\\"y\")}"
+        );
+    }
+
+    /// Passes if the hash is the SHA-256 digest of the normalized query, and differs for queries
+    /// that differ in more than their formatting
+    #[test]
+    fn hash() {
+        assert_eq!(
+            query_hash("  "),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            query_hash("{ Project { id } }"),
+            query_hash("{\n  Project {\n    id\n  }\n}")
+        );
+        assert_ne!(
+            query_hash("{ Project { id } }"),
+            query_hash("{ Project { id name } }")
+        );
+    }
+}
@@ -0,0 +1,518 @@
+//! Provides an [`async_graphql`] dynamic schema that mirrors the Warpgrapher GraphQL schema, for
+//! applications built on async-graphql rather than juniper.
+//!
+//! The async-graphql schema is generated from the type definitions of the engine's juniper
+//! schema, so both expose the same types, fields, and arguments. Once async-graphql has
+//! validated an operation, the document the client sent is executed by the engine as a single
+//! request, so all of the operation's fields share one request context and go through the same
+//! resolvers, event handlers, and database transactions as a request made with
+//! [`Engine::execute`]. The fields of the async-graphql schema then read their values from the
+//! engine's response, and the engine's errors are returned with their paths and extensions.
+//!
+//! [`Engine::execute`]: ../struct.Engine.html#method.execute
+
+use super::context::RequestContext;
+use super::metadata::Metadata;
+use super::Engine;
+use async_graphql::dynamic::{
+    Enum, EnumItem, Field, FieldFuture, FieldValue, InputObject, InputValue, Object,
+    ResolverContext, Scalar, Schema, SchemaError, TypeRef, Union,
+};
+use async_graphql::extensions::{
+    Extension, ExtensionContext, ExtensionFactory, NextExecute, NextPrepareRequest,
+};
+use async_graphql::{Data, Pos, Request, Response, ServerError, ServerResult, Value as ConstValue};
+use juniper::meta::{Argument, MetaType};
+use juniper::{DefaultScalarValue, Type};
+use log::debug;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Scalars that async-graphql registers for every schema
+const BUILT_IN_SCALARS: [&str; 5] = ["Boolean", "Float", "ID", "Int", "String"];
+
+/// Added to each selection set of the document executed by the engine, so that the members of
+/// unions can be identified in the response
+const TYPENAME: &str = " __typename ";
+
+/// Describes how a value returned by the engine is converted for async-graphql
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OutputKind {
+    Leaf,
+    Object,
+    Union,
+}
+
+/// The query, operation name, and variables of a request as sent by the client, kept in the
+/// request data for the engine to execute
+struct ClientRequest {
+    query: String,
+    operation_name: Option<String>,
+    variables: serde_json::Value,
+}
+
+/// The `data` of the engine's response to an operation, from which the root fields read their
+/// values
+struct EngineData(serde_json::Value);
+
+/// An async-graphql extension that executes each validated operation with the engine
+struct EngineExtension<RequestCtx: RequestContext> {
+    engine: Engine<RequestCtx>,
+}
+
+impl<RequestCtx: RequestContext> ExtensionFactory for EngineExtension<RequestCtx> {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(EngineExtension {
+            engine: self.engine.clone(),
+        })
+    }
+}
+
+#[async_graphql::async_trait::async_trait]
+impl<RequestCtx: RequestContext> Extension for EngineExtension<RequestCtx> {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let variables = serde_json::to_value(&request.variables)
+            .map_err(|e| ServerError::new(e.to_string(), None))?;
+        let client_request = ClientRequest {
+            query: request.query.clone(),
+            operation_name: request.operation_name.clone(),
+            variables,
+        };
+        request.data.insert(client_request);
+        next.run(ctx, request).await
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let Some(client_request) = ctx.data_opt::<ClientRequest>() else {
+            return next.run(ctx, operation_name).await;
+        };
+
+        let metadata = ctx
+            .data_opt::<Metadata>()
+            .cloned()
+            .or_else(|| {
+                ctx.data_opt::<HashMap<String, String>>()
+                    .cloned()
+                    .map(Metadata::from)
+            })
+            .unwrap_or_default();
+        let input_value =
+            match serde_json::from_value::<juniper::InputValue>(client_request.variables.clone()) {
+                Ok(input_value) => input_value,
+                Err(e) => {
+                    return Response::from_errors(vec![ServerError::new(e.to_string(), None)])
+                }
+            };
+
+        // the allowlist is checked against the client's document, not the one with __typename
+        let (query, typenames) = with_typenames(&client_request.query);
+        debug!("async_schema::EngineExtension::execute -- query: {}", query);
+        let mut response = match self
+            .engine
+            .execute_request(
+                query,
+                client_request.operation_name.clone(),
+                Some(input_value),
+                Some(client_request.variables.clone()),
+                metadata,
+                Some(&client_request.query),
+            )
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => return Response::from_errors(vec![ServerError::new(e.to_string(), None)]),
+        };
+
+        let errors = response
+            .get_mut("errors")
+            .map(serde_json::Value::take)
+            .and_then(|errors| match errors {
+                serde_json::Value::Array(errors) => Some(errors),
+                _ => None,
+            })
+            .into_iter()
+            .flatten()
+            .map(|e| server_error(e, &typenames))
+            .collect::<Vec<ServerError>>();
+        let data = response
+            .get_mut("data")
+            .map(serde_json::Value::take)
+            .unwrap_or_default();
+        if data.is_null() {
+            return Response::from_errors(errors);
+        }
+
+        let mut execute_data = Data::default();
+        execute_data.insert(EngineData(data));
+        let mut response = next.run_with_data(ctx, operation_name, execute_data).await;
+
+        // a non-null field that the engine left null is an error to async-graphql as well, but
+        // the engine's errors say why the field is null
+        if !errors.is_empty() {
+            response.errors = errors;
+        }
+
+        response
+    }
+}
+
+/// Builds an async-graphql schema for the types in the engine's juniper schema
+pub(super) fn build_schema<RequestCtx: RequestContext>(
+    engine: &Engine<RequestCtx>,
+) -> Result<Schema, SchemaError> {
+    let schema_type = &engine.root_node.schema;
+    let query_name = schema_type
+        .concrete_query_type()
+        .name()
+        .unwrap_or("Query")
+        .to_string();
+    let mutation_name = schema_type
+        .concrete_mutation_type()
+        .and_then(|mt| mt.name())
+        .map(|name| name.to_string());
+    let subscription_name = schema_type
+        .concrete_subscription_type()
+        .and_then(|st| st.name())
+        .map(|name| name.to_string());
+
+    let meta_types = schema_type
+        .concrete_type_list()
+        .into_iter()
+        .filter(|mt| {
+            mt.name().is_some_and(|name| {
+                !name.starts_with("__")
+                    && !BUILT_IN_SCALARS.contains(&name)
+                    && Some(name) != subscription_name.as_deref()
+            })
+        })
+        .collect::<Vec<&MetaType>>();
+
+    let kinds = meta_types
+        .iter()
+        .filter_map(|mt| match mt {
+            MetaType::Object(o) => Some((o.name.to_string(), OutputKind::Object)),
+            MetaType::Union(u) => Some((u.name.to_string(), OutputKind::Union)),
+            _ => None,
+        })
+        .collect::<HashMap<String, OutputKind>>();
+
+    let mut builder =
+        Schema::build(&query_name, mutation_name.as_deref(), None).extension(EngineExtension {
+            engine: engine.clone(),
+        });
+    for mt in meta_types {
+        match mt {
+            MetaType::Scalar(s) => {
+                let mut scalar = Scalar::new(s.name.to_string());
+                if let Some(d) = &s.description {
+                    scalar = scalar.description(d);
+                }
+                builder = builder.register(scalar);
+            }
+            MetaType::Enum(e) => {
+                let mut enumeration =
+                    Enum::new(e.name.to_string()).items(e.values.iter().map(|v| {
+                        let item = EnumItem::new(v.name.clone());
+                        if v.deprecation_status.is_deprecated() {
+                            item.deprecation(v.deprecation_status.reason().map(|r| r.as_str()))
+                        } else {
+                            item
+                        }
+                    }));
+                if let Some(d) = &e.description {
+                    enumeration = enumeration.description(d);
+                }
+                builder = builder.register(enumeration);
+            }
+            MetaType::InputObject(io) => {
+                let mut input_object = InputObject::new(io.name.to_string());
+                if let Some(d) = &io.description {
+                    input_object = input_object.description(d);
+                }
+                for a in io.input_fields.iter() {
+                    input_object = input_object.field(input_value(a));
+                }
+                builder = builder.register(input_object);
+            }
+            MetaType::Union(u) => {
+                let mut union = Union::new(u.name.to_string());
+                if let Some(d) = &u.description {
+                    union = union.description(d);
+                }
+                for type_name in u.of_type_names.iter() {
+                    union = union.possible_type(type_name);
+                }
+                builder = builder.register(union);
+            }
+            MetaType::Object(o) => {
+                let is_root =
+                    o.name == query_name || Some(o.name.as_ref()) == mutation_name.as_deref();
+
+                let mut object = Object::new(o.name.to_string());
+                if let Some(d) = &o.description {
+                    object = object.description(d);
+                }
+                // Introspection fields are provided by async-graphql itself
+                for f in o.fields.iter().filter(|f| !f.name.starts_with("__")) {
+                    let kind = kinds
+                        .get(f.field_type.innermost_name())
+                        .copied()
+                        .unwrap_or(OutputKind::Leaf);
+                    let mut field = if is_root {
+                        root_field(f.name.to_string(), type_ref(&f.field_type), kind)
+                    } else {
+                        nested_field(f.name.to_string(), type_ref(&f.field_type), kind)
+                    };
+                    if let Some(d) = &f.description {
+                        field = field.description(d);
+                    }
+                    if f.deprecation_status.is_deprecated() {
+                        field =
+                            field.deprecation(f.deprecation_status.reason().map(|r| r.as_str()));
+                    }
+                    for a in f.arguments.iter().flatten() {
+                        field = field.argument(input_value(a));
+                    }
+                    object = object.field(field);
+                }
+                builder = builder.register(object);
+            }
+            _ => (),
+        }
+    }
+
+    builder.finish()
+}
+
+/// Converts a juniper argument or input object field to an async-graphql input value
+fn input_value(a: &Argument<DefaultScalarValue>) -> InputValue {
+    let iv = InputValue::new(&a.name, type_ref(&a.arg_type));
+    match &a.description {
+        Some(d) => iv.description(d),
+        None => iv,
+    }
+}
+
+/// Converts a juniper type reference to an async-graphql type reference
+fn type_ref(t: &Type) -> TypeRef {
+    match t {
+        Type::Named(name) => TypeRef::Named(name.to_string().into()),
+        Type::NonNullNamed(name) => {
+            TypeRef::NonNull(Box::new(TypeRef::Named(name.to_string().into())))
+        }
+        Type::List(inner) => TypeRef::List(Box::new(type_ref(inner))),
+        Type::NonNullList(inner) => {
+            TypeRef::NonNull(Box::new(TypeRef::List(Box::new(type_ref(inner)))))
+        }
+    }
+}
+
+/// Returns a field on the root query or mutation object that reads its value from the engine's
+/// response to the operation
+fn root_field(field_name: String, ty: TypeRef, kind: OutputKind) -> Field {
+    Field::new(field_name, ty, move |ctx: ResolverContext| {
+        FieldFuture::new(async move {
+            let data = ctx.data::<EngineData>()?;
+            let key = ctx.field().alias().unwrap_or_else(|| ctx.field().name());
+            Ok(field_value(
+                data.0.get(key).cloned().unwrap_or_default(),
+                kind,
+            ))
+        })
+    })
+}
+
+/// Returns a field on a non-root object that reads its value from the parent object's part of
+/// the engine's response
+fn nested_field(field_name: String, ty: TypeRef, kind: OutputKind) -> Field {
+    Field::new(field_name, ty, move |ctx: ResolverContext| {
+        FieldFuture::new(async move {
+            let parent = ctx.parent_value.try_downcast_ref::<serde_json::Value>()?;
+            let key = ctx.field().alias().unwrap_or_else(|| ctx.field().name());
+            Ok(field_value(
+                parent.get(key).cloned().unwrap_or_default(),
+                kind,
+            ))
+        })
+    })
+}
+
+/// Converts a value from the engine's JSON response into an async-graphql field value
+fn field_value(value: serde_json::Value, kind: OutputKind) -> Option<FieldValue<'static>> {
+    match (value, kind) {
+        (serde_json::Value::Null, _) => None,
+        (serde_json::Value::Array(items), OutputKind::Object | OutputKind::Union) => {
+            Some(FieldValue::list(items.into_iter().map(|item| {
+                field_value(item, kind).unwrap_or(FieldValue::NULL)
+            })))
+        }
+        (v, OutputKind::Leaf) => ConstValue::from_json(v).ok().map(FieldValue::value),
+        (v, OutputKind::Object) => Some(FieldValue::owned_any(v)),
+        (v, OutputKind::Union) => {
+            let type_name = v
+                .get("__typename")
+                .and_then(|t| t.as_str())
+                .map(|t| t.to_string());
+            let fv = FieldValue::owned_any(v);
+            Some(match type_name {
+                Some(type_name) => fv.with_type(type_name),
+                None => fv,
+            })
+        }
+    }
+}
+
+/// Returns a copy of a GraphQL document that asks for the `__typename` of every object, along
+/// with the positions in the document where `__typename` was added. A brace opens a selection set
+/// unless it is inside parentheses, where it opens an input object value. Braces in strings and
+/// comments are left alone.
+fn with_typenames(query: &str) -> (String, Vec<Pos>) {
+    let mut rendered = String::with_capacity(query.len());
+    let mut typenames = Vec::new();
+    let mut pos = Pos { line: 1, column: 1 };
+    let mut depth = 0_usize;
+    let mut skip = 0;
+    let mut in_comment = false;
+    let mut in_string = false;
+    let mut in_block_string = false;
+    let mut escaped = false;
+
+    for (i, c) in query.char_indices() {
+        rendered.push(c);
+        if c == '\n' {
+            pos.line += 1;
+            pos.column = 1;
+        } else {
+            pos.column += 1;
+        }
+
+        if skip > 0 {
+            skip -= 1;
+        } else if in_comment {
+            in_comment = c != '\n' && c != '\r';
+        } else if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' || c == '\n' {
+                in_string = false;
+            }
+        } else if in_block_string {
+            if query[i..].starts_with("\\\"\"\"") {
+                skip = 3;
+            } else if query[i..].starts_with("\"\"\"") {
+                skip = 2;
+                in_block_string = false;
+            }
+        } else {
+            match c {
+                '#' => in_comment = true,
+                '"' if query[i..].starts_with("\"\"\"") => {
+                    skip = 2;
+                    in_block_string = true;
+                }
+                '"' => in_string = true,
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                '{' if depth == 0 => {
+                    rendered.push_str(TYPENAME);
+                    typenames.push(pos);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    (rendered, typenames)
+}
+
+/// Converts an error from the engine's response into an async-graphql error, moving its
+/// locations from the document executed by the engine back to the client's document
+fn server_error(error: serde_json::Value, typenames: &[Pos]) -> ServerError {
+    let mut server_error = match serde_json::from_value::<ServerError>(error.clone()) {
+        Ok(server_error) => server_error,
+        Err(_) => ServerError::new(
+            error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default(),
+            None,
+        ),
+    };
+
+    for location in server_error.locations.iter_mut() {
+        for typename in typenames.iter().filter(|t| t.line == location.line) {
+            if location.column >= typename.column + TYPENAME.len() {
+                location.column -= TYPENAME.len();
+            }
+        }
+    }
+
+    server_error
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{server_error, with_typenames, TYPENAME};
+    use async_graphql::Pos;
+
+    /// Passes if `__typename` is added to selection sets, but not to input object values,
+    /// strings, or comments, and if error locations are moved back to the client's document
+    #[test]
+    fn test_with_typenames() {
+        let query = "query Q($p: PIn = { a: \"{\" }) {\n  P(input: { id: \"}\" }) { id } # {\n  \"\"\"{\"\"\" }";
+        let (rendered, typenames) = with_typenames(query);
+        assert_eq!(
+            rendered,
+            "query Q($p: PIn = { a: \"{\" }) { __typename \n  P(input: { id: \"}\" }) { __typename  id } # {\n  \"\"\"{\"\"\" }"
+        );
+        assert_eq!(
+            typenames,
+            vec![
+                Pos {
+                    line: 1,
+                    column: 32
+                },
+                Pos {
+                    line: 2,
+                    column: 26
+                }
+            ]
+        );
+
+        let error = server_error(
+            serde_json::json!({
+                "message": "failed",
+                "locations": [{"line": 2, "column": 3}, {"line": 2, "column": 38}],
+                "path": ["P"],
+                "extensions": {"code": "E"}
+            }),
+            &typenames,
+        );
+        assert_eq!(
+            error.locations,
+            vec![
+                Pos { line: 2, column: 3 },
+                Pos {
+                    line: 2,
+                    column: 38 - TYPENAME.len()
+                }
+            ]
+        );
+        assert_eq!(
+            error.extensions.unwrap().get("code"),
+            Some(&async_graphql::Value::from("E"))
+        );
+    }
+}
@@ -1,11 +1,15 @@
 //! Models and custom GraphQL endpoints.
 
 use crate::engine::schema::{rel_name_variants, type_name_variants};
+use crate::engine::value::Duration;
 use crate::Error;
 use log::trace;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fs::File;
+use std::hash::{Hash, Hasher};
 use std::io::BufReader;
 use std::slice::Iter;
 
@@ -30,6 +34,11 @@ fn get_none() -> Option<String> {
 /// Warpgrapher configuration file format, a vector of [`Type`] structures, and a vector of
 /// [`Endpoint`] structures.
 ///
+/// When a configuration is parsed from YAML, each `${NAME}` token in a string value is replaced
+/// with the value of the environment variable `NAME`, or with `default` for a token written
+/// `${NAME:-default}` if the variable is unset or empty. Parsing fails if a token without a
+/// default names a variable that is not set. A `$$` stands for a literal `$`.
+///
 /// [`Endpoint`]: struct.Endpoint.html
 /// [`Type`]: struct.Type.html
 ///
@@ -40,6 +49,27 @@ fn get_none() -> Option<String> {
 ///  
 /// let c = Configuration::new(1, Vec::new(), Vec::new());
 /// ```
+///
+/// ```rust
+/// # use std::convert::TryFrom;
+/// # use warpgrapher::Configuration;
+///
+/// let c = Configuration::try_from(
+///     "
+/// version: 1
+/// model:
+///   - name: Customer
+///     props:
+///       - name: region
+///         type: String
+///         default: ${CUSTOMER_DEFAULT_REGION:-us-east-1}
+/// ",
+/// )
+/// .unwrap();
+///
+/// let region = c.types().next().unwrap().props().next().unwrap();
+/// assert_eq!(region.default_value(), Some(&serde_json::json!("us-east-1")));
+/// ```
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Configuration {
@@ -58,6 +88,13 @@ pub struct Configuration {
     /// [`Endpoint`]: struct.Endpoint.html
     #[serde(default)]
     endpoints: Vec<Endpoint>,
+
+    /// A vector of [`EnumType`] structures, each defining an enumeration that may be used as the
+    /// type of a property
+    ///
+    /// [`EnumType`]: struct.EnumType.html
+    #[serde(default)]
+    enums: Vec<EnumType>,
 }
 
 impl Configuration {
@@ -80,9 +117,51 @@ impl Configuration {
             version,
             model,
             endpoints,
+            enums: Vec::new(),
         }
     }
 
+    /// Sets the [`EnumType`] declarations for the configuration, returning the updated
+    /// [`Configuration`]
+    ///
+    /// [`Configuration`]: struct.Configuration.html
+    /// [`EnumType`]: struct.EnumType.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::Configuration;
+    /// # use warpgrapher::engine::config::EnumType;
+    ///
+    /// let c = Configuration::new(1, Vec::new(), Vec::new()).with_enums(vec![EnumType::new(
+    ///     "Status".to_string(),
+    ///     vec!["ACTIVE".to_string(), "INACTIVE".to_string()],
+    /// )]);
+    /// ```
+    pub fn with_enums(mut self, enums: Vec<EnumType>) -> Configuration {
+        self.enums = enums;
+        self
+    }
+
+    /// Returns an iterator over the [`EnumType`] structs defining enumerations in the GraphQL
+    /// schema
+    ///
+    /// [`EnumType`]: struct.EnumType.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::Configuration;
+    ///
+    /// let c = Configuration::new(1, Vec::new(), Vec::new());
+    /// for e in c.enums() {
+    ///     let _name = e.name();
+    /// }
+    /// ```
+    pub fn enums(&self) -> Iter<'_, EnumType> {
+        self.enums.iter()
+    }
+
     /// Returns an iterator over the [`Endpoint`] structs defining custom root endpoints in the
     /// GraphQL schema
     ///
@@ -116,20 +195,47 @@ impl Configuration {
         self.model.iter()
     }
 
+    /// Returns an iterator over the names of the [`Type`] structs in the model, in the order in
+    /// which they are declared
+    ///
+    /// [`Type`]: ./struct.Type.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::Configuration;
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Type};
+    ///
+    /// let c = Configuration::new(
+    ///     1,
+    ///     vec!(Type::new("User".to_string(), vec!(), vec!(), EndpointsFilter::all())),
+    ///     Vec::new()
+    /// );
+    ///
+    /// assert_eq!(vec!("User"), c.type_names().collect::<Vec<&str>>());
+    /// ```
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.model.iter().map(|t| t.name())
+    }
+
     /// Validates the [`Configuration`] data structure. Checks that there are no duplicate
     /// [`Endpoint`] or [`Type`] items, and that the [`Endpoint`] input/output types are defined
     /// in the model. Returns () if there are no validation errors.
     ///
     /// # Errors
     ///
-    /// Returns an [`Error`] variant [`ConfigItemDuplicated`] if there is more than one type or
-    /// more than one endpoint that use the same name.
+    /// Returns an [`Error`] variant [`ConfigItemDuplicated`] if there is more than one type,
+    /// enum, or endpoint that use the same name.
+    ///
+    /// Returns an [`Error`] variant [`ConfigItemInvalid`] if an enum declares no values,
+    /// declares the same value more than once, or deprecates a value it does not declare.
     ///
     /// Returns an [`Error`] variant [`ConfigItemReserved`] if a named configuration item, such as
     /// an endpoint or type, has a name that is a reserved word, such as "ID" or the name of a
     /// GraphQL scalar type.
     ///
     /// [`ConfigItemDuplicated`]: ../../error/enum.Error.html#variant.ConfigItemDuplicated
+    /// [`ConfigItemInvalid`]: ../../error/enum.Error.html#variant.ConfigItemInvalid
     /// [`ConfigItemReserved`]: ../../error/enum.Error.html#variant.ConfigItemReserved
     /// [`Error`]: ../../error/enum.Error.html
     ///
@@ -143,7 +249,9 @@ impl Configuration {
     pub fn validate(&self) -> Result<(), Error> {
         trace!("Config::validate called");
 
-        let scalar_names = ["Int", "Float", "Boolean", "String", "ID"];
+        let scalar_names = [
+            "Int", "Long", "Float", "Boolean", "String", "ID", "Base64", "Duration",
+        ];
 
         self.model
             .iter()
@@ -203,6 +311,17 @@ impl Configuration {
                     });
                 }
 
+                // Used by rel update inputs for atomic operations on rel props
+                if let Some(p) = t.rels.iter().find_map(|r| {
+                    r.props
+                        .iter()
+                        .find(|p| ["INCREMENT", "PUSH"].contains(&p.name()))
+                }) {
+                    return Err(Error::ConfigItemReserved {
+                        type_name: p.name().to_string(),
+                    });
+                }
+
                 if t.rels
                     .iter()
                     .any(|r| r.props.iter().any(|p| p.name().to_uppercase() == "SRC"))
@@ -221,7 +340,234 @@ impl Configuration {
                     });
                 }
 
+                t.props
+                    .iter()
+                    .try_for_each(|p| self.validate_property_default(t.name(), p))?;
+
+                t.props.iter().try_for_each(|p| match p.count() {
+                    Some(rel_name)
+                        if p.type_name() != "Int"
+                            || p.list()
+                            || p.resolver().is_some()
+                            || p.default_value().is_some()
+                            || !t.rels.iter().any(|r| {
+                                r.name() == rel_name && r.resolver().is_none() && r.query().is_none()
+                            }) =>
+                    {
+                        Err(Error::ConfigItemInvalid {
+                            type_name: t.name.to_string(),
+                            details: format!(
+                                "the count property {} must be a single Int without a resolver or default that counts a stored relationship of the type",
+                                p.name()
+                            ),
+                        })
+                    }
+                    _ => Ok(()),
+                })?;
+
+                t.props.iter().try_for_each(|p| match p.db_name() {
+                    Some(db_name)
+                        if p.resolver().is_some()
+                            || p.count().is_some()
+                            || db_name.to_uppercase() == "ID"
+                            || db_name.to_uppercase() == "LABEL"
+                            || t.props.iter().any(|p2| {
+                                p2.name() != p.name()
+                                    && p2.db_name().unwrap_or_else(|| p2.name()) == db_name
+                            }) =>
+                    {
+                        Err(Error::ConfigItemInvalid {
+                            type_name: t.name.to_string(),
+                            details: format!(
+                                "the database name {} of property {} must be a stored property name that is unused by the other properties of the type",
+                                db_name,
+                                p.name()
+                            ),
+                        })
+                    }
+                    _ => Ok(()),
+                })?;
+
+                t.props
+                    .iter()
+                    .filter(|p| p.unique_case_insensitive())
+                    .try_for_each(|p| {
+                        let shadow_name = p.db_name().unwrap_or_else(|| p.name()).to_string() + "_lower";
+                        if p.type_name() != "String"
+                            || p.list()
+                            || p.resolver().is_some()
+                            || p.count().is_some()
+                            || t.props.iter().any(|p2| {
+                                p2.db_name().unwrap_or_else(|| p2.name()) == shadow_name
+                            })
+                        {
+                            Err(Error::ConfigItemInvalid {
+                                type_name: t.name.to_string(),
+                                details: format!(
+                                    "the case-insensitive unique property {} must be a single String without a resolver or count, and no other property may be stored as {}",
+                                    p.name(),
+                                    shadow_name
+                                ),
+                            })
+                        } else {
+                            Ok(())
+                        }
+                    })?;
+
+                t.props.iter().try_for_each(|p| match p.unique_where_null() {
+                    Some(scope) => {
+                        let stored = t.props.iter().any(|p2| {
+                            p2.name() == scope
+                                && p2.name() != p.name()
+                                && p2.resolver().is_none()
+                                && p2.count().is_none()
+                        });
+                        if p.unique_case_insensitive() && stored {
+                            Ok(())
+                        } else {
+                            Err(Error::ConfigItemInvalid {
+                                type_name: t.name.to_string(),
+                                details: format!(
+                                    "the property {} limits its uniqueness by {}, so it must be unique ignoring case, and {} must be another stored property of the type",
+                                    p.name(),
+                                    scope,
+                                    scope
+                                ),
+                            })
+                        }
+                    }
+                    None => Ok(()),
+                })?;
+
+                t.default_sort.iter().try_for_each(|ds| {
+                    if ds.order_by != "id"
+                        && !t
+                            .props
+                            .iter()
+                            .any(|p| p.name() == ds.order_by && p.resolver().is_none())
+                    {
+                        return Err(Error::ConfigItemInvalid {
+                            type_name: t.name.to_string(),
+                            details: format!(
+                                "the default sort property {} is not a property of the type",
+                                ds.order_by
+                            ),
+                        });
+                    }
+
+                    match ds.direction.as_deref() {
+                        None | Some("ascending") | Some("descending") => Ok(()),
+                        Some(d) => Err(Error::ConfigItemInvalid {
+                            type_name: t.name.to_string(),
+                            details: format!(
+                                "the default sort direction {} is not ascending or descending",
+                                d
+                            ),
+                        }),
+                    }
+                })?;
+
                 t.rels.iter().try_for_each(|r| {
+                    if let Some(query) = &r.query {
+                        if r.resolver.is_some() {
+                            return Err(Error::ConfigItemInvalid {
+                                type_name: t.name.to_string() + "::" + r.name(),
+                                details: "a relationship cannot have both a resolver and a query"
+                                    .to_string(),
+                            });
+                        }
+
+                        if !query_refers_to_src_id(query) {
+                            return Err(Error::ConfigItemInvalid {
+                                type_name: t.name.to_string() + "::" + r.name(),
+                                details: "a relationship query must refer to $src_id".to_string(),
+                            });
+                        }
+                    }
+
+                    r.props.iter().try_for_each(|p| {
+                        self.validate_property_default(&(t.name().to_string() + "::" + r.name()), p)
+                    })?;
+
+                    if let Some(p) = r.props.iter().find(|p| p.db_name().is_some()) {
+                        return Err(Error::ConfigItemInvalid {
+                            type_name: t.name.to_string() + "::" + r.name(),
+                            details: format!(
+                                "the relationship property {} cannot have a database name",
+                                p.name()
+                            ),
+                        });
+                    }
+
+                    if let Some(p) = r.props.iter().find(|p| p.unique_case_insensitive()) {
+                        return Err(Error::ConfigItemInvalid {
+                            type_name: t.name.to_string() + "::" + r.name(),
+                            details: format!(
+                                "the relationship property {} cannot be unique",
+                                p.name()
+                            ),
+                        });
+                    }
+
+                    if let Some(p) = r.props.iter().find(|p| p.count().is_some()) {
+                        return Err(Error::ConfigItemInvalid {
+                            type_name: t.name.to_string() + "::" + r.name(),
+                            details: format!(
+                                "the relationship property {} cannot be a count",
+                                p.name()
+                            ),
+                        });
+                    }
+
+                    if let Some(position) = &r.position {
+                        let valid = r.list
+                            && !r.symmetric
+                            && r.props.iter().any(|p| {
+                                p.name() == position
+                                    && p.type_name() == "Int"
+                                    && !p.list()
+                                    && p.resolver().is_none()
+                            });
+                        if !valid {
+                            return Err(Error::ConfigItemInvalid {
+                                type_name: t.name.to_string() + "::" + r.name(),
+                                details: format!(
+                                    "the position {} must be a single Int property of a list relationship that is not symmetric",
+                                    position
+                                ),
+                            });
+                        }
+                    }
+
+                    if let Some(inverse) = &r.inverse {
+                        let conflict = r.resolver.is_some()
+                            || r.query.is_some()
+                            || r.symmetric
+                            || inverse.to_uppercase() == "ID"
+                            || r.nodes().any(|n| {
+                                self.model.iter().filter(|dt| dt.name() == n).any(|dt| {
+                                    dt.props().any(|p| p.name() == inverse)
+                                        || dt.rels().any(|dr| dr.name() == inverse)
+                                        || self.model.iter().any(|t2| {
+                                            t2.rels().any(|r2| {
+                                                !(t2.name() == t.name() && r2.name() == r.name())
+                                                    && r2.inverse() == Some(inverse)
+                                                    && r2.nodes().any(|n2| n2 == n)
+                                            })
+                                        })
+                                })
+                            });
+                        if conflict {
+                            return Err(Error::ConfigItemInvalid {
+                                type_name: t.name.to_string() + "::" + r.name(),
+                                details: format!(
+                                    "the inverse {} must be a stored, directed relationship whose name is unused on its destination types",
+                                    inverse
+                                ),
+                            });
+                        }
+                    }
+
                     let rel_name_variants = rel_name_variants(t, r);
 
                     self.model.iter().try_for_each(|t2| {
@@ -239,6 +585,51 @@ impl Configuration {
             })
             .collect::<Result<Vec<_>, Error>>()?;
 
+        self.enums.iter().try_for_each(|e| {
+            if self.enums.iter().filter(|e2| e2.name == e.name).count() > 1
+                || self.model.iter().any(|t| t.name == e.name)
+            {
+                return Err(Error::ConfigItemDuplicated {
+                    type_name: e.name.to_string(),
+                });
+            }
+
+            if scalar_names.iter().any(|s| s == &e.name) {
+                return Err(Error::ConfigItemReserved {
+                    type_name: e.name.to_string(),
+                });
+            }
+
+            if e.values.is_empty() {
+                return Err(Error::ConfigItemInvalid {
+                    type_name: e.name.to_string(),
+                    details: "an enum must declare at least one value".to_string(),
+                });
+            }
+
+            if let Some(v) = e
+                .values
+                .iter()
+                .find(|v| e.values.iter().filter(|v2| v2 == v).count() > 1)
+            {
+                return Err(Error::ConfigItemInvalid {
+                    type_name: e.name.to_string(),
+                    details: format!("the value {} is declared more than once", v),
+                });
+            }
+
+            if let Some(v) = e.deprecated.keys().find(|v| !e.values.contains(v)) {
+                return Err(Error::ConfigItemInvalid {
+                    type_name: e.name.to_string(),
+                    details: format!("the deprecated value {} is not declared", v),
+                });
+            }
+
+            e.validate_stored_values()?;
+
+            Ok(())
+        })?;
+
         self.endpoints
             .iter()
             .map(|ep| {
@@ -276,6 +667,56 @@ impl Configuration {
         Ok(())
     }
 
+    /// Checks that the default value of a property, if any, matches the type of the property
+    fn validate_property_default(&self, type_name: &str, p: &Property) -> Result<(), Error> {
+        let default = match p.default_value() {
+            Some(default) => default,
+            None => return Ok(()),
+        };
+
+        let valid = if p.resolver().is_some() {
+            false
+        } else if p.list() {
+            default.as_array().is_some_and(|values| {
+                values
+                    .iter()
+                    .all(|v| self.value_matches_type(p.type_name(), v))
+            })
+        } else {
+            self.value_matches_type(p.type_name(), default)
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::ConfigItemInvalid {
+                type_name: type_name.to_string(),
+                details: format!(
+                    "the default value of the property {} does not match its type {}",
+                    p.name(),
+                    p.type_name()
+                ),
+            })
+        }
+    }
+
+    fn value_matches_type(&self, type_name: &str, v: &serde_json::Value) -> bool {
+        match type_name {
+            "Boolean" => v.is_boolean(),
+            "Float" => v.is_number(),
+            "Int" => v.is_i64() || v.is_u64(),
+            "Long" => v.is_i64(),
+            "Duration" => v.as_str().and_then(Duration::parse).is_some(),
+            "Base64" | "ID" | "String" => v.is_string(),
+            _ => self
+                .enums
+                .iter()
+                .find(|e| e.name == type_name)
+                .zip(v.as_str())
+                .is_some_and(|(e, s)| e.values.iter().any(|ev| ev == s)),
+        }
+    }
+
     /// Returns the version number of the configuration format used for the configuration
     ///
     /// # Examples
@@ -290,6 +731,17 @@ impl Configuration {
     pub fn version(&self) -> i32 {
         self.version
     }
+
+    /// Turns off the generated create, update, and delete endpoints of every type and
+    /// relationship, leaving their read endpoints as they are
+    pub(crate) fn remove_write_endpoints(&mut self) {
+        self.model.iter_mut().for_each(|t| {
+            t.endpoints = EndpointsFilter::new(t.endpoints.read(), false, false, false);
+            t.rels.iter_mut().for_each(|r| {
+                r.endpoints = EndpointsFilter::new(r.endpoints.read(), false, false, false);
+            });
+        });
+    }
 }
 
 impl Default for Configuration {
@@ -298,6 +750,7 @@ impl Default for Configuration {
             version: 1,
             model: vec![],
             endpoints: vec![],
+            enums: vec![],
         }
     }
 }
@@ -307,7 +760,9 @@ impl TryFrom<File> for Configuration {
 
     fn try_from(f: File) -> Result<Configuration, Error> {
         let r = BufReader::new(f);
-        Ok(serde_yaml::from_reader(r)?)
+        let mut value: serde_yaml::Value = serde_yaml::from_reader(r)?;
+        interpolate_env(&mut value)?;
+        Ok(serde_yaml::from_value(value)?)
     }
 }
 
@@ -315,7 +770,7 @@ impl TryFrom<String> for Configuration {
     type Error = Error;
 
     fn try_from(s: String) -> Result<Configuration, Error> {
-        Ok(serde_yaml::from_str(&s)?)
+        Configuration::try_from(s.as_str())
     }
 }
 
@@ -323,136 +778,577 @@ impl TryFrom<&str> for Configuration {
     type Error = Error;
 
     fn try_from(s: &str) -> Result<Configuration, Error> {
-        Ok(serde_yaml::from_str(s)?)
+        let mut value: serde_yaml::Value = serde_yaml::from_str(s)?;
+        interpolate_env(&mut value)?;
+        Ok(serde_yaml::from_value(value)?)
     }
 }
 
-/// Configuration item for custom endpoints
+/// Replaces the environment variable tokens in the string values of a YAML configuration, as
+/// described for [`interpolate_env_str`]. Mapping keys are left as they are, so that
+/// interpolation doesn't change the structure of the model.
+///
+/// [`interpolate_env_str`]: fn.interpolate_env_str.html
+fn interpolate_env(value: &mut serde_yaml::Value) -> Result<(), Error> {
+    match value {
+        serde_yaml::Value::String(s) => {
+            if s.contains('$') {
+                *s = interpolate_env_str(s)?;
+            }
+            Ok(())
+        }
+        serde_yaml::Value::Sequence(seq) => seq.iter_mut().try_for_each(interpolate_env),
+        serde_yaml::Value::Mapping(m) => m.iter_mut().try_for_each(|(_k, v)| interpolate_env(v)),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the string with each `${NAME}` token replaced by the value of the environment variable
+/// `NAME`. A token written `${NAME:-default}` is replaced by `default` if the variable is unset
+/// or empty. A `$$` is replaced by a single `$`, so `$${NAME}` is left as the text `${NAME}`.
+///
+/// # Errors
+///
+/// Returns an [`Error::EnvironmentVariableNotFound`] variant if a token without a default names
+/// a variable that is not set.
+///
+/// [`Error::EnvironmentVariableNotFound`]: ../../error/enum.Error.html#variant.EnvironmentVariableNotFound
+fn interpolate_env_str(s: &str) -> Result<String, Error> {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('$') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if let Some(r) = rest.strip_prefix("$$") {
+            result.push('$');
+            rest = r;
+        } else if let Some(end) = rest.strip_prefix("${").and_then(|r| r.find('}')) {
+            let token = &rest[2..end + 2];
+            let (name, default_opt) = match token.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (token, None),
+            };
+            match (std::env::var(name), default_opt) {
+                (Ok(v), Some(default)) if v.is_empty() => result.push_str(default),
+                (Ok(v), _) => result.push_str(&v),
+                (Err(_), Some(default)) => result.push_str(default),
+                (Err(_), None) => {
+                    return Err(Error::EnvironmentVariableNotFound {
+                        name: name.to_string(),
+                    })
+                }
+            }
+            rest = &rest[end + 3..];
+        } else {
+            result.push('$');
+            rest = &rest[1..];
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Returns true if a relationship query refers to the `$src_id` parameter. The parameter must
+/// appear as a whole word, so `$src_ids` doesn't count, and appearances within a quoted string,
+/// a backtick-quoted name, or a `//` or `/* */` comment are skipped.
+fn query_refers_to_src_id(query: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut chars = query.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                while let Some((_, d)) = chars.next() {
+                    if d == '\\' {
+                        chars.next();
+                    } else if d == c {
+                        break;
+                    }
+                }
+            }
+            '/' if query[i..].starts_with("//") => {
+                while chars.next_if(|&(_, d)| d != '\n').is_some() {}
+            }
+            '/' if query[i..].starts_with("/*") => {
+                chars.next();
+                while let Some((j, _)) = chars.next() {
+                    if query[j..].starts_with("*/") {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            '$' if query[i + 1..].starts_with("src_id") && !query[i + 7..].starts_with(is_word) => {
+                return true;
+            }
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Configuration item for an enumeration. An enum may be used as the type of a property on a
+/// [`Type`] or [`Relationship`], in which case the property appears in the GraphQL schema as an
+/// enum, both in inputs and in query responses.
+///
+/// [`Relationship`]: ./struct.Relationship.html
+/// [`Type`]: ./struct.Type.html
 ///
 /// # Examples
 ///
 /// ```rust
-/// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
-/// #   GraphqlType};
+/// # use warpgrapher::engine::config::EnumType;
 ///
-/// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
-///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+/// let e = EnumType::new(
+///     "Status".to_string(),
+///     vec!["ACTIVE".to_string(), "INACTIVE".to_string()],
+/// );
 /// ```
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 #[serde(rename_all = "camelCase")]
-pub struct Endpoint {
-    /// Name of this Endpoint
+pub struct EnumType {
+    /// Name of the enumeration in the GraphQL schema
     name: String,
 
-    /// Class of endpoint (Mutation or Query)
-    class: EndpointClass,
+    /// The values of the enumeration, which are also the values stored in the database, unless
+    /// stored values are given for them
+    values: Vec<String>,
 
-    /// Defines the input of the endpoint
-    input: Option<EndpointType>,
+    /// Map from deprecated values of the enumeration to the reason each is deprecated
+    #[serde(default)]
+    deprecated: BTreeMap<String, String>,
 
-    /// Defines the type returned by the endpoint
-    output: EndpointType,
+    /// Map from values of the enumeration to the string or integer codes stored for them in the
+    /// database
+    #[serde(default)]
+    stored_values: BTreeMap<String, ConfigValue>,
+
+    /// Whether query responses present the values of the enumeration or their stored codes
+    #[serde(default)]
+    serialization: EnumSerialization,
 }
 
-impl Endpoint {
-    /// Creates a new configuration item for a custom GraphQL endpoint
+impl EnumType {
+    /// Creates a new EnumType struct.
+    ///
+    /// # Arguments
+    ///
+    /// * name - the name of the enumeration in the GraphQL schema
+    /// * values - the values of the enumeration
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
-    /// #   GraphqlType};
+    /// # use warpgrapher::engine::config::EnumType;
     ///
-    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
-    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    /// let e = EnumType::new("Status".to_string(), vec!["ACTIVE".to_string()]);
     /// ```
-    pub fn new(
-        name: String,
-        class: EndpointClass,
-        input: Option<EndpointType>,
-        output: EndpointType,
-    ) -> Endpoint {
-        Endpoint {
+    pub fn new(name: String, values: Vec<String>) -> EnumType {
+        EnumType {
             name,
-            class,
-            input,
-            output,
+            values,
+            deprecated: BTreeMap::new(),
+            stored_values: BTreeMap::new(),
+            serialization: EnumSerialization::Name,
         }
     }
 
-    /// Returns the [`EndpointClass`] of a custom GraphQL endpoint, indicating whether the custom
-    /// endpoint is a Query or Mutation.
-    ///
-    /// [`EndpointClass`]: ./enum.EndpointClass.html
+    /// Marks a value of the enumeration as deprecated. A deprecated value is still accepted in
+    /// inputs and returned in responses, but it is flagged as deprecated, with the given reason,
+    /// in the generated GraphQL schema.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
-    /// #   GraphqlType};
+    /// # use warpgrapher::engine::config::EnumType;
     ///
-    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
-    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    /// let e = EnumType::new(
+    ///     "Status".to_string(),
+    ///     vec!["ACTIVE".to_string(), "DORMANT".to_string()],
+    /// )
+    /// .with_deprecated("DORMANT".to_string(), "Use INACTIVE instead".to_string());
     ///
-    /// assert_eq!(&EndpointClass::Query, e.class());
+    /// assert_eq!(Some("Use INACTIVE instead"), e.deprecated("DORMANT"));
     /// ```
-    pub fn class(&self) -> &EndpointClass {
-        &self.class
+    pub fn with_deprecated(mut self, value: String, reason: String) -> EnumType {
+        self.deprecated.insert(value, reason);
+        self
     }
 
-    /// Returns the name of a custom GraphQL endpoint.
+    /// Sets the code stored in the database for a value of the enumeration, in place of the
+    /// value's name. The code must be a string or an integer in the range of a GraphQL `Int`. If
+    /// any value of the enumeration has a stored code, every value must have one, and the codes
+    /// must be distinct and all of the same kind, which is checked when the engine is built.
+    /// Inputs still take the names of the values, which are translated to and from the codes as
+    /// the values are written and read.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
-    /// #   GraphqlType};
+    /// # use serde_json::json;
+    /// # use warpgrapher::engine::config::EnumType;
     ///
-    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
-    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    /// let e = EnumType::new(
+    ///     "Priority".to_string(),
+    ///     vec!["LOW".to_string(), "HIGH".to_string()],
+    /// )
+    /// .with_stored_value("LOW".to_string(), json!(1))
+    /// .with_stored_value("HIGH".to_string(), json!(3));
     ///
-    /// assert_eq!("CountItems", e.name());
+    /// assert_eq!(Some(&json!(3)), e.stored_value("HIGH"));
     /// ```
-    pub fn name(&self) -> &str {
-        &self.name
+    pub fn with_stored_value(mut self, value: String, stored: serde_json::Value) -> EnumType {
+        self.stored_values.insert(value, ConfigValue(stored));
+        self
     }
 
-    /// Returns the optional type definition of the input to a custom endpoint. A value of None
-    /// indicates that the GraphQL endpoint does not take an input.
+    /// Returns the code stored in the database for a value of the enumeration, or None if the
+    /// value is stored as its name
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
-    /// #   GraphqlType};
+    /// # use warpgrapher::engine::config::EnumType;
     ///
-    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
-    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    /// let e = EnumType::new("Status".to_string(), vec!["ACTIVE".to_string()]);
     ///
-    /// assert!(e.input().is_none());
+    /// assert!(e.stored_value("ACTIVE").is_none());
     /// ```
-    pub fn input(&self) -> Option<&EndpointType> {
-        self.input.as_ref()
+    pub fn stored_value(&self, value: &str) -> Option<&serde_json::Value> {
+        self.stored_values.get(value).map(|s| &s.0)
     }
 
-    /// Returns the type definition of the output for a custom endpoint
+    /// Sets whether query responses present the values of the enumeration, as a GraphQL enum, or
+    /// the codes stored for them, as a GraphQL `Int` or `String`. Defaults to
+    /// [`EnumSerialization::Name`].
+    ///
+    /// [`EnumSerialization::Name`]: ./enum.EnumSerialization.html#variant.Name
     ///
     /// # Examples
     ///
     /// ```rust
-    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
-    /// #   GraphqlType};
+    /// # use serde_json::json;
+    /// # use warpgrapher::engine::config::{EnumSerialization, EnumType};
     ///
-    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
-    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    /// let e = EnumType::new("Priority".to_string(), vec!["LOW".to_string()])
+    ///     .with_stored_value("LOW".to_string(), json!(1))
+    ///     .with_serialization(EnumSerialization::Value);
     ///
-    /// assert_eq!(&EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true),
-    ///     e.output());
+    /// assert_eq!(EnumSerialization::Value, e.serialization());
     /// ```
-    pub fn output(&self) -> &EndpointType {
-        &self.output
+    pub fn with_serialization(mut self, serialization: EnumSerialization) -> EnumType {
+        self.serialization = serialization;
+        self
     }
-}
+
+    /// Returns whether query responses present the values of the enumeration or their stored
+    /// codes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EnumSerialization, EnumType};
+    ///
+    /// let e = EnumType::new("Status".to_string(), vec!["ACTIVE".to_string()]);
+    ///
+    /// assert_eq!(EnumSerialization::Name, e.serialization());
+    /// ```
+    pub fn serialization(&self) -> EnumSerialization {
+        self.serialization
+    }
+
+    /// Returns the reason a value of the enumeration is deprecated, or None if the value is not
+    /// deprecated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::EnumType;
+    ///
+    /// let e = EnumType::new("Status".to_string(), vec!["ACTIVE".to_string()]);
+    ///
+    /// assert!(e.deprecated("ACTIVE").is_none());
+    /// ```
+    pub fn deprecated(&self, value: &str) -> Option<&str> {
+        self.deprecated.get(value).map(|r| r.as_str())
+    }
+
+    /// Returns the name of the enumeration
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::EnumType;
+    ///
+    /// let e = EnumType::new("Status".to_string(), vec!["ACTIVE".to_string()]);
+    ///
+    /// assert_eq!("Status", e.name());
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Checks that the stored codes of the enumeration, if any, are given for every declared value
+    /// and no others, and are distinct strings, or distinct integers in the range of an `Int`
+    fn validate_stored_values(&self) -> Result<(), Error> {
+        let invalid = |details: String| Error::ConfigItemInvalid {
+            type_name: self.name.to_string(),
+            details,
+        };
+
+        if self.stored_values.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(v) = self.stored_values.keys().find(|v| !self.values.contains(v)) {
+            return Err(invalid(format!(
+                "the value {} has a stored value but is not declared",
+                v
+            )));
+        }
+
+        if let Some(v) = self
+            .values
+            .iter()
+            .find(|v| !self.stored_values.contains_key(*v))
+        {
+            return Err(invalid(format!("the value {} has no stored value", v)));
+        }
+
+        let stored = self.stored_values.values().map(|s| &s.0);
+        let all_strings = stored.clone().all(serde_json::Value::is_string);
+        let all_ints = stored.clone().all(|s| {
+            s.as_i64()
+                .map(|i| i32::try_from(i).is_ok())
+                .unwrap_or(false)
+        });
+        if !all_strings && !all_ints {
+            return Err(invalid(
+                "stored values must all be strings or all be integers in the range of Int"
+                    .to_string(),
+            ));
+        }
+
+        if let Some(s) = stored
+            .clone()
+            .find(|s| stored.clone().filter(|s2| s2 == s).count() > 1)
+        {
+            return Err(invalid(format!(
+                "the stored value {} is given for more than one value",
+                s
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns an iterator over the values of the enumeration
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::EnumType;
+    ///
+    /// let e = EnumType::new("Status".to_string(), vec!["ACTIVE".to_string()]);
+    ///
+    /// assert_eq!(Some("ACTIVE"), e.values().next().map(|v| v.as_str()));
+    /// ```
+    pub fn values(&self) -> Iter<'_, String> {
+        self.values.iter()
+    }
+}
+
+/// Whether query responses present the values of an [`EnumType`] by name or by the codes stored
+/// for them in the database
+///
+/// [`EnumType`]: ./struct.EnumType.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::config::EnumSerialization;
+///
+/// let s = EnumSerialization::Value;
+/// ```
+#[derive(
+    Copy, Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub enum EnumSerialization {
+    /// Responses present the names of the values, as a GraphQL enum
+    #[default]
+    Name,
+
+    /// Responses present the codes stored for the values, as a GraphQL `Int` or `String`
+    Value,
+}
+
+/// Configuration item for custom endpoints
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+/// #   GraphqlType};
+///
+/// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Endpoint {
+    /// Name of this Endpoint
+    name: String,
+
+    /// Class of endpoint (Mutation or Query)
+    class: EndpointClass,
+
+    /// Defines the input of the endpoint
+    input: Option<EndpointType>,
+
+    /// Defines the type returned by the endpoint
+    output: EndpointType,
+
+    /// Description of the endpoint, included in the generated GraphQL schema
+    #[serde(default = "get_none")]
+    description: Option<String>,
+}
+
+impl Endpoint {
+    /// Creates a new configuration item for a custom GraphQL endpoint
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+    /// #   GraphqlType};
+    ///
+    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    /// ```
+    pub fn new(
+        name: String,
+        class: EndpointClass,
+        input: Option<EndpointType>,
+        output: EndpointType,
+    ) -> Endpoint {
+        Endpoint {
+            name,
+            class,
+            input,
+            output,
+            description: None,
+        }
+    }
+
+    /// Sets the description of a custom GraphQL endpoint. The description documents the endpoint
+    /// field in the generated GraphQL schema, where it is visible through introspection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+    /// #   GraphqlType};
+    ///
+    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true))
+    ///     .with_description("Counts the items in the inventory".to_string());
+    ///
+    /// assert_eq!(Some("Counts the items in the inventory"), e.description());
+    /// ```
+    pub fn with_description(mut self, description: String) -> Endpoint {
+        self.description = Some(description);
+        self
+    }
+
+    /// Returns the optional description of a custom GraphQL endpoint
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+    /// #   GraphqlType};
+    ///
+    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    ///
+    /// assert!(e.description().is_none());
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the [`EndpointClass`] of a custom GraphQL endpoint, indicating whether the custom
+    /// endpoint is a Query or Mutation.
+    ///
+    /// [`EndpointClass`]: ./enum.EndpointClass.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+    /// #   GraphqlType};
+    ///
+    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    ///
+    /// assert_eq!(&EndpointClass::Query, e.class());
+    /// ```
+    pub fn class(&self) -> &EndpointClass {
+        &self.class
+    }
+
+    /// Returns the name of a custom GraphQL endpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+    /// #   GraphqlType};
+    ///
+    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    ///
+    /// assert_eq!("CountItems", e.name());
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the optional type definition of the input to a custom endpoint. A value of None
+    /// indicates that the GraphQL endpoint does not take an input.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+    /// #   GraphqlType};
+    ///
+    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    ///
+    /// assert!(e.input().is_none());
+    /// ```
+    pub fn input(&self) -> Option<&EndpointType> {
+        self.input.as_ref()
+    }
+
+    /// Returns the type definition of the output for a custom endpoint
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Endpoint, EndpointClass, EndpointType, TypeDef,
+    /// #   GraphqlType};
+    ///
+    /// let e = Endpoint::new("CountItems".to_string(), EndpointClass::Query, None,
+    ///     EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true));
+    ///
+    /// assert_eq!(&EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true),
+    ///     e.output());
+    /// ```
+    pub fn output(&self) -> &EndpointType {
+        &self.output
+    }
+}
 
 impl TryFrom<&str> for Endpoint {
     type Error = Error;
@@ -825,6 +1721,74 @@ pub struct Property {
     /// this prop. If this field is None, the prop resolves the scalar value from the database.
     #[serde(default = "get_none")]
     validator: Option<String>,
+
+    /// The value given to the property when a create mutation doesn't provide one. If this field
+    /// is None, a create without a value leaves the property unset.
+    #[serde(default)]
+    default: Option<ConfigValue>,
+
+    /// The name of a relationship of the type. If this field is set, the property is not stored,
+    /// but resolves to the number of relationships of that name from the node.
+    #[serde(default = "get_none")]
+    count: Option<String>,
+
+    /// The name under which the property is stored in the database. If this field is None, the
+    /// property is stored under its GraphQL name.
+    #[serde(default = "get_none")]
+    db_name: Option<String>,
+
+    /// True if no two nodes of the type may hold values of this property that differ only in
+    /// case. The lowercased value is stored alongside the property and checked on each write.
+    #[serde(default = "get_false")]
+    unique_case_insensitive: bool,
+
+    /// The name of another property of the type, such as a soft delete timestamp. If this field
+    /// is set, a case-insensitive unique property is unique only among the nodes on which the
+    /// named property is null, so that soft deleted nodes don't hold on to their values.
+    #[serde(default = "get_none")]
+    unique_where_null: Option<String>,
+
+    /// Description of the property, included in the generated GraphQL schema
+    #[serde(default = "get_none")]
+    description: Option<String>,
+
+    /// The reason the property is deprecated. If this field is None, the property is not
+    /// deprecated.
+    #[serde(default = "get_none")]
+    deprecated: Option<String>,
+
+    /// Directives applied to the field of the property, each enforced by the handler registered
+    /// under its name when the field is resolved
+    #[serde(default)]
+    directives: Vec<Directive>,
+}
+
+/// Value written in the configuration, such as the default value of a property or the value stored
+/// for a variant of an enumeration. The value is compared, hashed, and ordered by its JSON text, so
+/// that [`Property`] and [`EnumType`] can keep their derived traits.
+///
+/// [`EnumType`]: ./struct.EnumType.html
+/// [`Property`]: ./struct.Property.html
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+struct ConfigValue(serde_json::Value);
+
+impl Hash for ConfigValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_string().hash(state)
+    }
+}
+
+impl Ord for ConfigValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.to_string().cmp(&other.0.to_string())
+    }
+}
+
+impl PartialOrd for ConfigValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Property {
@@ -867,19 +1831,339 @@ impl Property {
             list,
             resolver,
             validator,
+            default: None,
+            count: None,
+            db_name: None,
+            unique_case_insensitive: false,
+            unique_where_null: None,
+            description: None,
+            deprecated: None,
+            directives: Vec::new(),
         }
     }
 
-    /// Returns a boolean that if true, indicates that this property contains a list of scalar
-    /// values, and if false, indicates that the property contains only one value (or potentially
-    /// zero values if required is also false).
+    /// Sets the default value of the property, used when a create mutation doesn't provide a
+    /// value for it. A property with a default is optional in the create input, even if the
+    /// property is required. The default must match the type of the property, which is checked
+    /// when the engine is built.
     ///
     /// # Examples
     ///
-    /// # use warpgrapher::engine::config::Property;
+    /// ```rust
+    /// # use serde_json::json;
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
     ///
-    /// let p = Property::new("name".to_string(), UsesFiter::all(), "String".to_string(),
-    ///         true, false, None, None);
+    /// let p = Property::new("role".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None)
+    ///     .with_default(json!("member"));
+    ///
+    /// assert_eq!(Some(&json!("member")), p.default_value());
+    /// ```
+    pub fn with_default(mut self, default: serde_json::Value) -> Property {
+        self.default = Some(ConfigValue(default));
+        self
+    }
+
+    /// Returns the optional default value of the property
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert!(p.default_value().is_none());
+    /// ```
+    pub fn default_value(&self) -> Option<&serde_json::Value> {
+        self.default.as_ref().map(|d| &d.0)
+    }
+
+    /// Makes the property a count of the node's relationships with the given name. A count
+    /// property is not stored in the database and is left out of create, update, and query
+    /// inputs. When read, it resolves to the number of relationships of that name from the node.
+    /// The property must be a single `Int` without a resolver or default, which is checked when
+    /// the engine is built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("issueCount".to_string(), UsesFilter::all(), "Int".to_string(),
+    ///     true, false, None, None)
+    ///     .with_count("issues".to_string());
+    ///
+    /// assert_eq!(Some("issues"), p.count());
+    /// ```
+    pub fn with_count(mut self, rel_name: String) -> Property {
+        self.count = Some(rel_name);
+        self
+    }
+
+    /// Returns the name of the relationship counted by the property, or None if the property is
+    /// not a count
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert!(p.count().is_none());
+    /// ```
+    pub fn count(&self) -> Option<&str> {
+        self.count.as_deref()
+    }
+
+    /// Sets the name under which the property is stored in the database. The GraphQL schema
+    /// uses the property's name, while reads, writes, filters, and sorts use the stored name, so
+    /// that a field can be renamed in the API without migrating the data. The stored name must
+    /// not be used by another property of the type, which is checked when the engine is built.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("username".to_string(), UsesFilter::all(), "String".to_string(),
+    ///     true, false, None, None)
+    ///     .with_db_name("user_name".to_string());
+    ///
+    /// assert_eq!(Some("user_name"), p.db_name());
+    /// ```
+    pub fn with_db_name(mut self, db_name: String) -> Property {
+        self.db_name = Some(db_name);
+        self
+    }
+
+    /// Returns the name under which the property is stored in the database, or None if the
+    /// property is stored under its own name
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert!(p.db_name().is_none());
+    /// ```
+    pub fn db_name(&self) -> Option<&str> {
+        self.db_name.as_deref()
+    }
+
+    /// Makes the values of the property unique among the nodes of the type, ignoring case, as
+    /// for email addresses. The lowercased value is stored in the database under the property's
+    /// stored name followed by `_lower`, and a create or update that would give two nodes the
+    /// same lowercased value fails. The property must be a single `String` without a resolver
+    /// or count, which is checked when the engine is built. The lowercased value is not loaded
+    /// into nodes, so it isn't seen by custom resolvers or event handlers.
+    ///
+    /// The check is made by reading the database before the write, without a lock, so two
+    /// requests writing the same value at the same time may both succeed. Add a uniqueness
+    /// constraint on the `_lower` property in the database to rule that out.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("email".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None)
+    ///     .with_unique_case_insensitive(true);
+    ///
+    /// assert!(p.unique_case_insensitive());
+    /// ```
+    pub fn with_unique_case_insensitive(mut self, unique_case_insensitive: bool) -> Property {
+        self.unique_case_insensitive = unique_case_insensitive;
+        self
+    }
+
+    /// Returns true if the values of the property must be unique among the nodes of the type,
+    /// ignoring case
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert!(!p.unique_case_insensitive());
+    /// ```
+    pub fn unique_case_insensitive(&self) -> bool {
+        self.unique_case_insensitive
+    }
+
+    /// Limits the uniqueness of a case-insensitive unique property to the nodes on which the
+    /// named property of the same type is null. With soft deletes, where a node is deleted by
+    /// setting a `deletedAt` timestamp, this lets a new node take a value held by a deleted one,
+    /// such as a user registering again with the email address of a deleted account. Clearing
+    /// the named property on a node fails if another node has since taken its value. The named
+    /// property must be stored, not a count or a property with a resolver, which is checked when
+    /// the engine is built. Like the uniqueness it limits, the check is a read before the write,
+    /// so a restore racing a create of the same value may let both succeed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("email".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None)
+    ///     .with_unique_case_insensitive(true)
+    ///     .with_unique_where_null(Some("deletedAt".to_string()));
+    ///
+    /// assert_eq!(p.unique_where_null(), Some("deletedAt"));
+    /// ```
+    pub fn with_unique_where_null(mut self, unique_where_null: Option<String>) -> Property {
+        self.unique_where_null = unique_where_null;
+        self
+    }
+
+    /// Returns the name of the property that must be null on a node for the uniqueness of this
+    /// property to apply to it, if the uniqueness is limited
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("email".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert!(p.unique_where_null().is_none());
+    /// ```
+    pub fn unique_where_null(&self) -> Option<&str> {
+        self.unique_where_null.as_deref()
+    }
+
+    /// Sets the description of the property. The description documents the field for the
+    /// property in the generated GraphQL schema, including the fields of the input types used to
+    /// create, update, and query nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None)
+    ///     .with_description("The full name of the user".to_string());
+    ///
+    /// assert_eq!(Some("The full name of the user"), p.description());
+    /// ```
+    pub fn with_description(mut self, description: String) -> Property {
+        self.description = Some(description);
+        self
+    }
+
+    /// Returns the optional description of the property
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert!(p.description().is_none());
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Marks the property as deprecated, with the given reason. A deprecated property still
+    /// resolves normally, but its output field is flagged as deprecated in the generated GraphQL
+    /// schema, so that tools warn clients that select it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("nickname".to_string(), UsesFilter::all(), "String".to_string(),
+    ///     false, false, None, None)
+    ///     .with_deprecated("Use displayName instead".to_string());
+    ///
+    /// assert_eq!(Some("Use displayName instead"), p.deprecated());
+    /// ```
+    pub fn with_deprecated(mut self, reason: String) -> Property {
+        self.deprecated = Some(reason);
+        self
+    }
+
+    /// Returns the reason the property is deprecated, or None if the property is not deprecated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert!(p.deprecated().is_none());
+    /// ```
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Sets the directives applied to the field of the property. When the field is resolved, the
+    /// handler registered for each directive is called, and the field resolves only if every
+    /// handler succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Directive, Property, UsesFilter};
+    ///
+    /// let p = Property::new("salary".to_string(), UsesFilter::all(), "Int".to_string(), false,
+    ///     false, None, None)
+    ///     .with_directives(vec![Directive::new("auth".to_string())]);
+    ///
+    /// assert_eq!("auth", p.directives().next().unwrap().name());
+    /// ```
+    pub fn with_directives(mut self, directives: Vec<Directive>) -> Property {
+        self.directives = directives;
+        self
+    }
+
+    /// Returns an iterator over the [`Directive`] structs applied to the field of the property
+    ///
+    /// [`Directive`]: ./struct.Directive.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Property, UsesFilter};
+    ///
+    /// let p = Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true,
+    ///     false, None, None);
+    ///
+    /// assert_eq!(0, p.directives().count());
+    /// ```
+    pub fn directives(&self) -> Iter<'_, Directive> {
+        self.directives.iter()
+    }
+
+    /// Returns a boolean that if true, indicates that this property contains a list of scalar
+    /// values, and if false, indicates that the property contains only one value (or potentially
+    /// zero values if required is also false).
+    ///
+    /// # Examples
+    ///
+    /// # use warpgrapher::engine::config::Property;
+    ///
+    /// let p = Property::new("name".to_string(), UsesFiter::all(), "String".to_string(),
+    ///         true, false, None, None);
     ///
     /// assert!(!p.list());
     pub fn list(&self) -> bool {
@@ -1015,6 +2299,43 @@ pub struct Relationship {
     /// If this field is None, the prop resolves the scalar value from the database.
     #[serde(default = "get_none")]
     resolver: Option<String>,
+
+    /// A database query that computes the destination nodes of the relationship, with the id of
+    /// the source node bound to `$src_id`. If this field is None, the relationship is read from
+    /// the database or resolved by a custom resolver.
+    #[serde(default = "get_none")]
+    query: Option<String>,
+
+    /// True if the relationship is symmetric, meaning that it is stored as a single directed edge
+    /// but read as if it pointed in both directions
+    #[serde(default = "get_false")]
+    symmetric: bool,
+
+    /// True if a single-node relationship must always be present. A required relationship is
+    /// non-nullable in the GraphQL schema, and reading a node without one is an error.
+    #[serde(default = "get_false")]
+    required: bool,
+
+    /// The name of an Int property of the relationship that holds the position of the
+    /// relationship among the relationships of the same name from its source node. If this field
+    /// is None, the relationships are unordered.
+    #[serde(default = "get_none")]
+    position: Option<String>,
+
+    /// The name of a field added to the query inputs of the destination types that filters the
+    /// destination nodes by properties of the source node. If this field is None, the
+    /// destination types can't be filtered by their source nodes.
+    #[serde(default = "get_none")]
+    inverse: Option<String>,
+
+    /// Description of the relationship, included in the generated GraphQL schema
+    #[serde(default = "get_none")]
+    description: Option<String>,
+
+    /// The reason the relationship is deprecated. If this field is None, the relationship is not
+    /// deprecated.
+    #[serde(default = "get_none")]
+    deprecated: Option<String>,
 }
 
 impl Relationship {
@@ -1058,9 +2379,231 @@ impl Relationship {
             props,
             endpoints,
             resolver,
+            query: None,
+            symmetric: false,
+            required: false,
+            position: None,
+            inverse: None,
+            description: None,
+            deprecated: None,
         }
     }
 
+    /// Sets a database query that computes the destination nodes of the relationship, making it
+    /// a computed relationship that is resolved without a custom resolver. The query is written
+    /// in the query language of the database back-end and must refer to the id of the source
+    /// node as `$src_id`. Each result of the query must be a destination node, returned in the
+    /// same form as the nodes returned by Warpgrapher's own queries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("top_contributor".to_string(), false, vec!["User".to_string()],
+    ///     vec![], EndpointsFilter::all(), None)
+    ///     .with_query("MATCH (p:Project { id: $src_id })<-[:contributor]-(u:User)
+    ///         RETURN u ORDER BY u.commits DESC LIMIT 1".to_string());
+    ///
+    /// assert!(r.query().is_some());
+    /// ```
+    pub fn with_query(mut self, query: String) -> Relationship {
+        self.query = Some(query);
+        self
+    }
+
+    /// Sets whether the relationship is symmetric. A symmetric relationship, such as a friendship
+    /// between two users, is created as a single edge, but reads match that edge in either
+    /// direction. Reading the relationship from either of its nodes returns it with that node as
+    /// the source and the node at the other end as the destination.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("friends".to_string(), true, vec!["User".to_string()],
+    ///     vec![], EndpointsFilter::all(), None)
+    ///     .with_symmetric(true);
+    ///
+    /// assert!(r.symmetric());
+    /// ```
+    pub fn with_symmetric(mut self, symmetric: bool) -> Relationship {
+        self.symmetric = symmetric;
+        self
+    }
+
+    /// Sets whether the relationship is required. A required relationship is generated as a
+    /// non-nullable field in the GraphQL schema. Reading a node that has no such relationship
+    /// returns an error for the field, rather than null.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("owner".to_string(), false, vec!["User".to_string()],
+    ///     vec![], EndpointsFilter::all(), None)
+    ///     .with_required(true);
+    ///
+    /// assert!(r.required());
+    /// ```
+    pub fn with_required(mut self, required: bool) -> Relationship {
+        self.required = required;
+        self
+    }
+
+    /// Sets the name of an Int property of the relationship that orders the relationships of the
+    /// same name from each source node. An update that sets the position property moves the
+    /// relationship to that position, shifting its siblings in the same transaction, so that the
+    /// positions of the siblings run from zero without gaps or ties.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Property, Relationship, UsesFilter};
+    ///
+    /// let r = Relationship::new("tasks".to_string(), true, vec!["Task".to_string()],
+    ///     vec![Property::new("rank".to_string(), UsesFilter::all(), "Int".to_string(), false,
+    ///         false, None, None)],
+    ///     EndpointsFilter::all(), None)
+    ///     .with_position("rank".to_string());
+    ///
+    /// assert_eq!(Some("rank"), r.position());
+    /// ```
+    pub fn with_position(mut self, position: String) -> Relationship {
+        self.position = Some(position);
+        self
+    }
+
+    /// Returns the name of the property that holds the position of the relationship among its
+    /// siblings, or None if the relationships are unordered
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("tasks".to_string(), true, vec!["Task".to_string()], vec![],
+    ///     EndpointsFilter::all(), None);
+    ///
+    /// assert!(r.position().is_none());
+    /// ```
+    pub fn position(&self) -> Option<&str> {
+        self.position.as_deref()
+    }
+
+    /// Sets the name of a field added to the query input of each destination type of the
+    /// relationship. The field takes the query input of the source type, so that destination
+    /// nodes can be read, updated, or deleted by matching properties of the nodes that have the
+    /// relationship to them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("issues".to_string(), true, vec!["Bug".to_string()], vec![],
+    ///     EndpointsFilter::all(), None)
+    ///     .with_inverse("project".to_string());
+    ///
+    /// assert_eq!(Some("project"), r.inverse());
+    /// ```
+    pub fn with_inverse(mut self, inverse: String) -> Relationship {
+        self.inverse = Some(inverse);
+        self
+    }
+
+    /// Returns the name of the field that filters destination nodes by their source nodes, or
+    /// None if the relationship has no inverse filter
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("issues".to_string(), true, vec!["Bug".to_string()], vec![],
+    ///     EndpointsFilter::all(), None);
+    ///
+    /// assert!(r.inverse().is_none());
+    /// ```
+    pub fn inverse(&self) -> Option<&str> {
+        self.inverse.as_deref()
+    }
+
+    /// Sets the description of the relationship. The description documents both the field for
+    /// the relationship on its source node type and the relationship object type in the
+    /// generated GraphQL schema.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("owner".to_string(), false, vec!["User".to_string()],
+    ///     vec![], EndpointsFilter::all(), None)
+    ///     .with_description("The user accountable for the project".to_string());
+    ///
+    /// assert_eq!(Some("The user accountable for the project"), r.description());
+    /// ```
+    pub fn with_description(mut self, description: String) -> Relationship {
+        self.description = Some(description);
+        self
+    }
+
+    /// Returns the optional description of the relationship
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("owner".to_string(), false, vec!["User".to_string()],
+    ///     vec![], EndpointsFilter::all(), None);
+    ///
+    /// assert!(r.description().is_none());
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Marks the relationship as deprecated, with the given reason. A deprecated relationship
+    /// still resolves normally, but its field on the source node type is flagged as deprecated in
+    /// the generated GraphQL schema.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("lead".to_string(), false, vec!["User".to_string()],
+    ///     vec![], EndpointsFilter::all(), None)
+    ///     .with_deprecated("Use owner instead".to_string());
+    ///
+    /// assert_eq!(Some("Use owner instead"), r.deprecated());
+    /// ```
+    pub fn with_deprecated(mut self, reason: String) -> Relationship {
+        self.deprecated = Some(reason);
+        self
+    }
+
+    /// Returns the reason the relationship is deprecated, or None if the relationship is not
+    /// deprecated
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("owner".to_string(), false, vec!["User".to_string()],
+    ///     vec![], EndpointsFilter::all(), None);
+    ///
+    /// assert!(r.deprecated().is_none());
+    /// ```
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
     /// Returns the [`EndpointsFilter`] struct that indicates which of the four basic Create, Read,
     /// Update, and Delete (CRUD) operations Warpgrapher should auto-generate for this
     /// relationship.
@@ -1163,8 +2706,59 @@ impl Relationship {
     ///
     /// assert!(r.resolver().is_none())
     /// ```
-    pub fn resolver(&self) -> Option<&String> {
-        self.resolver.as_ref()
+    pub fn resolver(&self) -> Option<&String> {
+        self.resolver.as_ref()
+    }
+
+    /// Returns an option for a string containing the database query that computes the
+    /// destination nodes of the relationship, if this is a computed relationship.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("RelName".to_string(), true, vec!["User".to_string()], vec![],
+    ///     EndpointsFilter::all(), None);
+    ///
+    /// assert!(r.query().is_none())
+    /// ```
+    pub fn query(&self) -> Option<&String> {
+        self.query.as_ref()
+    }
+
+    /// Returns true if the relationship is symmetric, meaning that reads match it in either
+    /// direction
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("RelName".to_string(), true, vec!["User".to_string()], vec![],
+    ///     EndpointsFilter::all(), None);
+    ///
+    /// assert!(!r.symmetric())
+    /// ```
+    pub fn symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    /// Returns true if the relationship is required, meaning that it is non-nullable in the
+    /// GraphQL schema
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship};
+    ///
+    /// let r = Relationship::new("RelName".to_string(), false, vec!["User".to_string()], vec![],
+    ///     EndpointsFilter::all(), None);
+    ///
+    /// assert!(!r.required())
+    /// ```
+    pub fn required(&self) -> bool {
+        self.required
     }
 }
 
@@ -1204,6 +2798,19 @@ pub struct Type {
     /// auto generated for the relationship
     #[serde(default)]
     endpoints: EndpointsFilter,
+
+    /// Sort order applied to reads of this type when the caller provides no sort
+    #[serde(default)]
+    default_sort: Vec<DefaultSort>,
+
+    /// Description of the type, included in the generated GraphQL schema
+    #[serde(default = "get_none")]
+    description: Option<String>,
+
+    /// Directives applied to the object type, each enforced by the handler registered under its
+    /// name when any field of the object is resolved
+    #[serde(default)]
+    directives: Vec<Directive>,
 }
 
 impl Type {
@@ -1249,9 +2856,125 @@ impl Type {
             props,
             rels,
             endpoints,
+            default_sort: Vec::new(),
+            description: None,
+            directives: Vec::new(),
         }
     }
 
+    /// Sets the description of the type. The description documents the GraphQL object type for
+    /// nodes of this type in the generated schema, where it is visible through introspection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Type};
+    ///
+    /// let t = Type::new("User".to_string(), vec!(), vec!(), EndpointsFilter::all())
+    ///     .with_description("A person with an account".to_string());
+    ///
+    /// assert_eq!(Some("A person with an account"), t.description());
+    /// ```
+    pub fn with_description(mut self, description: String) -> Type {
+        self.description = Some(description);
+        self
+    }
+
+    /// Returns the optional description of the type
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Type};
+    ///
+    /// let t = Type::new("User".to_string(), vec!(), vec!(), EndpointsFilter::all());
+    ///
+    /// assert!(t.description().is_none());
+    /// ```
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Sets the directives applied to the object type of nodes of this type. When any field of
+    /// the object is resolved, the handler registered for each directive is called before the
+    /// handlers for the directives of the field itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Directive, EndpointsFilter, Type};
+    ///
+    /// let t = Type::new("Payroll".to_string(), vec!(), vec!(), EndpointsFilter::all())
+    ///     .with_directives(vec![Directive::new("auth".to_string())]);
+    ///
+    /// assert_eq!("auth", t.directives().next().unwrap().name());
+    /// ```
+    pub fn with_directives(mut self, directives: Vec<Directive>) -> Type {
+        self.directives = directives;
+        self
+    }
+
+    /// Returns an iterator over the [`Directive`] structs applied to the object type
+    ///
+    /// [`Directive`]: ./struct.Directive.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Type};
+    ///
+    /// let t = Type::new("User".to_string(), vec!(), vec!(), EndpointsFilter::all());
+    ///
+    /// assert_eq!(0, t.directives().count());
+    /// ```
+    pub fn directives(&self) -> Iter<'_, Directive> {
+        self.directives.iter()
+    }
+
+    /// Sets the sort order applied to reads of nodes of this type when the caller provides no
+    /// sort option of its own. Without a sort, the order of results depends on the database
+    /// back-end and may change from one read to the next, so a default sort makes paging through
+    /// results deterministic. A sort provided by the caller replaces the default entirely.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{DefaultSort, EndpointsFilter, Property, Type,
+    /// #    UsesFilter};
+    ///
+    /// let t = Type::new(
+    ///     "User".to_string(),
+    ///     vec!(Property::new("name".to_string(), UsesFilter::all(), "String".to_string(),
+    ///         true, false, None, None)),
+    ///     vec!(),
+    ///     EndpointsFilter::all()
+    /// ).with_default_sort(vec![DefaultSort::new("name".to_string(), None)]);
+    ///
+    /// assert_eq!("name", t.default_sort().next().unwrap().order_by());
+    /// ```
+    pub fn with_default_sort(mut self, default_sort: Vec<DefaultSort>) -> Type {
+        self.default_sort = default_sort;
+        self
+    }
+
+    /// Returns an iterator over the [`DefaultSort`] structs defining the sort order applied to
+    /// reads of this type when the caller provides no sort.
+    ///
+    /// [`DefaultSort`]: ./struct.DefaultSort.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Type};
+    ///
+    /// let t = Type::new("User".to_string(), vec!(), vec!(), EndpointsFilter::all());
+    ///
+    /// assert_eq!(0, t.default_sort().count());
+    /// ```
+    pub fn default_sort(&self) -> Iter<'_, DefaultSort> {
+        self.default_sort.iter()
+    }
+
     /// Returns the name of the type. This type name is used as the label on nodes of this type in
     /// the graph database storage back-end.
     ///
@@ -1318,6 +3041,29 @@ impl Type {
         self.props.iter()
     }
 
+    /// Returns an iterator over the names of the properties on this node type, in the order in
+    /// which they are declared. The names are those of the GraphQL fields, not of the properties
+    /// stored in the database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{Type, Property, EndpointsFilter, UsesFilter};
+    ///
+    /// let t = Type::new(
+    ///     "User".to_string(),
+    ///     vec!(Property::new("name".to_string(), UsesFilter::all(), "String".to_string(), true, false, None, None),
+    ///          Property::new("role".to_string(), UsesFilter::all(), "String".to_string(), true, false, None, None)),
+    ///     vec!(),
+    ///     EndpointsFilter::all()
+    /// );
+    ///
+    /// assert_eq!(vec!("name", "role"), t.property_names().collect::<Vec<&str>>());
+    /// ```
+    pub fn property_names(&self) -> impl Iterator<Item = &str> {
+        self.props.iter().map(|p| p.name())
+    }
+
     pub fn mut_props(&mut self) -> &mut Vec<Property> {
         &mut self.props
     }
@@ -1370,6 +3116,28 @@ impl Type {
     pub fn rels(&self) -> Iter<Relationship> {
         self.rels.iter()
     }
+
+    /// Returns an iterator over the names of the relationships originating from this node type,
+    /// in the order in which they are declared
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::{EndpointsFilter, Relationship, Type};
+    ///
+    /// let t = Type::new(
+    ///     "User".to_string(),
+    ///     vec!(),
+    ///     vec!(Relationship::new("roles".to_string(), true, vec!("Role".to_string()), vec!(),
+    ///         EndpointsFilter::all(), None)),
+    ///     EndpointsFilter::all()
+    /// );
+    ///
+    /// assert_eq!(vec!("roles"), t.relationship_names().collect::<Vec<&str>>());
+    /// ```
+    pub fn relationship_names(&self) -> impl Iterator<Item = &str> {
+        self.rels.iter().map(|r| r.name())
+    }
 }
 
 impl TryFrom<&str> for Type {
@@ -1403,6 +3171,223 @@ impl TryFrom<&str> for Type {
     }
 }
 
+/// Configuration item for one level of the default sort order of a [`Type`]. Each item names a
+/// property to order by and an optional direction, which is either `ascending`, the default, or
+/// `descending`, in the same form as the `sort` option of a read query.
+///
+/// [`Type`]: ./struct.Type.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::config::DefaultSort;
+///
+/// let ds = DefaultSort::new("name".to_string(), Some("descending".to_string()));
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultSort {
+    /// Name of the property by which to order results
+    order_by: String,
+
+    /// Direction of the ordering, either ascending or descending
+    #[serde(default = "get_none")]
+    direction: Option<String>,
+}
+
+impl DefaultSort {
+    /// Creates a new DefaultSort struct.
+    ///
+    /// # Arguments
+    ///
+    /// * order_by - the name of the property by which to order results
+    /// * direction - the direction of the ordering, either `ascending` or `descending`. If None,
+    ///   results are in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::DefaultSort;
+    ///
+    /// let ds = DefaultSort::new("name".to_string(), None);
+    /// ```
+    pub fn new(order_by: String, direction: Option<String>) -> DefaultSort {
+        DefaultSort {
+            order_by,
+            direction,
+        }
+    }
+
+    /// Returns the name of the property by which to order results
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::DefaultSort;
+    ///
+    /// let ds = DefaultSort::new("name".to_string(), None);
+    ///
+    /// assert_eq!("name", ds.order_by());
+    /// ```
+    pub fn order_by(&self) -> &str {
+        &self.order_by
+    }
+
+    /// Returns the direction of the ordering, if one is set
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::DefaultSort;
+    ///
+    /// let ds = DefaultSort::new("name".to_string(), Some("descending".to_string()));
+    ///
+    /// assert_eq!(Some("descending"), ds.direction());
+    /// ```
+    pub fn direction(&self) -> Option<&str> {
+        self.direction.as_deref()
+    }
+}
+
+/// Configuration item for a directive applied to a [`Type`] or a [`Property`], such as an `auth`
+/// directive with a `role` argument. A directive is enforced by the handler registered under its
+/// name with the engine. The arguments may be any values, and are passed to the handler as
+/// written in the configuration.
+///
+/// [`Property`]: ./struct.Property.html
+/// [`Type`]: ./struct.Type.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use serde_json::json;
+/// # use warpgrapher::engine::config::Directive;
+///
+/// let d = Directive::new("auth".to_string()).with_arg("role".to_string(), json!("admin"));
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Directive {
+    /// Name of the directive, under which its handler is registered
+    name: String,
+
+    /// Arguments of the directive, by name
+    #[serde(default)]
+    args: BTreeMap<String, serde_json::Value>,
+}
+
+impl Directive {
+    /// Creates a new Directive struct with no arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * name - the name of the directive, under which its handler is registered
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::Directive;
+    ///
+    /// let d = Directive::new("auth".to_string());
+    /// ```
+    pub fn new(name: String) -> Directive {
+        Directive {
+            name,
+            args: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an argument to the directive, replacing any earlier argument of the same name
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use serde_json::json;
+    /// # use warpgrapher::engine::config::Directive;
+    ///
+    /// let d = Directive::new("auth".to_string()).with_arg("role".to_string(), json!("admin"));
+    ///
+    /// assert_eq!(Some(&json!("admin")), d.arg("role"));
+    /// ```
+    pub fn with_arg(mut self, name: String, value: serde_json::Value) -> Directive {
+        self.args.insert(name, value);
+        self
+    }
+
+    /// Returns the name of the directive
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::Directive;
+    ///
+    /// let d = Directive::new("auth".to_string());
+    ///
+    /// assert_eq!("auth", d.name());
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the value of the argument with the given name, if the directive has one
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use serde_json::json;
+    /// # use warpgrapher::engine::config::Directive;
+    ///
+    /// let d = Directive::new("auth".to_string()).with_arg("role".to_string(), json!("admin"));
+    ///
+    /// assert_eq!(Some(&json!("admin")), d.arg("role"));
+    /// assert!(d.arg("scope").is_none());
+    /// ```
+    pub fn arg(&self, name: &str) -> Option<&serde_json::Value> {
+        self.args.get(name)
+    }
+
+    /// Returns the arguments of the directive, by name
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::config::Directive;
+    ///
+    /// let d = Directive::new("auth".to_string());
+    ///
+    /// assert!(d.args().is_empty());
+    /// ```
+    pub fn args(&self) -> &BTreeMap<String, serde_json::Value> {
+        &self.args
+    }
+}
+
+// Directive arguments are compared, hashed, and ordered by their JSON text, so that Property
+// and Type can keep their derived traits.
+impl Hash for Directive {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        serde_json::to_string(&self.args)
+            .unwrap_or_default()
+            .hash(state)
+    }
+}
+
+impl Ord for Directive {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.name.cmp(&other.name).then_with(|| {
+            serde_json::to_string(&self.args)
+                .unwrap_or_default()
+                .cmp(&serde_json::to_string(&other.args).unwrap_or_default())
+        })
+    }
+}
+
+impl PartialOrd for Directive {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// Enumeration representing the definition of a type used as the optional input or the output for
 /// a custom GraphQL endpooint
 ///
@@ -1614,6 +3599,7 @@ pub fn compose(configs: Vec<Configuration>) -> Result<Configuration, Error> {
     let mut version: Option<i32> = None;
     let mut model: Vec<Type> = Vec::new();
     let mut endpoints: Vec<Endpoint> = Vec::new();
+    let mut enums: Vec<EnumType> = Vec::new();
 
     configs
         .into_iter()
@@ -1632,17 +3618,17 @@ pub fn compose(configs: Vec<Configuration>) -> Result<Configuration, Error> {
 
             model.append(&mut c.model);
             endpoints.append(&mut c.endpoints);
+            enums.append(&mut c.enums);
             Ok(())
         })
         .collect::<Result<Vec<_>, Error>>()?;
 
     // There will be no version number if the vector of Configurations is empty, in which case
     // we might as well use the latest version
-    Ok(Configuration::new(
-        version.unwrap_or(LATEST_CONFIG_VERSION),
-        model,
-        endpoints,
-    ))
+    Ok(
+        Configuration::new(version.unwrap_or(LATEST_CONFIG_VERSION), model, endpoints)
+            .with_enums(enums),
+    )
 }
 
 #[cfg(test)]
@@ -1955,15 +3941,134 @@ pub(crate) fn mock_endpoints_filter() -> Configuration {
     )
 }
 
-#[cfg(test)]
-mod tests {
-    use super::{
-        compose, Configuration, Endpoint, EndpointType, EndpointsFilter, Property, Relationship,
-        Type, UsesFilter,
-    };
-    use crate::Error;
-    use std::convert::TryInto;
-    use std::fs::File;
+#[cfg(test)]
+mod tests {
+    use super::{
+        compose, query_refers_to_src_id, Configuration, Endpoint, EndpointType, EndpointsFilter,
+        EnumSerialization, Property, Relationship, Type, UsesFilter,
+    };
+    use crate::Error;
+    use serde_json::json;
+    use std::convert::{TryFrom, TryInto};
+    use std::fs::File;
+
+    /// Passes if environment variables are interpolated into the string values of a yaml
+    /// configuration, using defaults for unset variables and leaving escaped tokens as text
+    #[test]
+    fn config_env_interpolation() {
+        std::env::set_var("WG_TEST_CONFIG_INTERP_REGION", "eu-west-2");
+        std::env::remove_var("WG_TEST_CONFIG_INTERP_UNSET");
+
+        let c = Configuration::try_from(
+            "
+version: 1
+model:
+  - name: Customer
+    description: Customers in ${WG_TEST_CONFIG_INTERP_REGION}, not $${WG_TEST_CONFIG_INTERP_UNSET}
+    props:
+      - name: region
+        type: String
+        default: ${WG_TEST_CONFIG_INTERP_REGION}
+      - name: tier
+        type: String
+        default: ${WG_TEST_CONFIG_INTERP_UNSET:-standard}
+"
+            .to_string(),
+        )
+        .unwrap();
+
+        let customer = c.types().next().unwrap();
+        assert_eq!(
+            customer.description(),
+            Some("Customers in eu-west-2, not ${WG_TEST_CONFIG_INTERP_UNSET}")
+        );
+        let region = customer.props().find(|p| p.name() == "region").unwrap();
+        assert_eq!(region.default_value(), Some(&json!("eu-west-2")));
+        let tier = customer.props().find(|p| p.name() == "tier").unwrap();
+        assert_eq!(tier.default_value(), Some(&json!("standard")));
+    }
+
+    /// Passes if parsing a yaml configuration fails when it refers to an environment variable
+    /// that is not set and has no default
+    #[test]
+    fn config_env_interpolation_missing() {
+        std::env::remove_var("WG_TEST_CONFIG_INTERP_MISSING");
+
+        let result = Configuration::try_from(
+            "
+version: 1
+model:
+  - name: Customer
+    props:
+      - name: region
+        type: String
+        default: ${WG_TEST_CONFIG_INTERP_MISSING}
+",
+        );
+
+        assert!(matches!(
+            result,
+            Err(Error::EnvironmentVariableNotFound { name }) if name == "WG_TEST_CONFIG_INTERP_MISSING"
+        ));
+    }
+
+    /// Passes if a type's default sort is deserialized from yaml and passes validation
+    #[test]
+    fn type_default_sort_from_yaml() {
+        let t = Type::try_from(
+            "
+name: User
+props:
+  - name: name
+    type: String
+defaultSort:
+  - orderBy: name
+    direction: descending
+  - orderBy: id
+",
+        )
+        .unwrap();
+
+        let sorts = t.default_sort().collect::<Vec<_>>();
+        assert_eq!(2, sorts.len());
+        assert_eq!("name", sorts[0].order_by());
+        assert_eq!(Some("descending"), sorts[0].direction());
+        assert_eq!("id", sorts[1].order_by());
+        assert_eq!(None, sorts[1].direction());
+
+        assert!(Configuration::new(1, vec![t], vec![]).validate().is_ok());
+    }
+
+    /// Passes if directives on a type and its properties are deserialized from yaml, with their
+    /// arguments
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_directives_test() {
+        let config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_directives.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(config.validate().is_ok());
+        let t = config.types().next().unwrap();
+        let type_directives = t.directives().collect::<Vec<_>>();
+        assert_eq!(1, type_directives.len());
+        assert_eq!("auth", type_directives[0].name());
+        assert!(type_directives[0].args().is_empty());
+
+        let salary = t.props().find(|p| p.name() == "salary").unwrap();
+        let prop_directives = salary.directives().collect::<Vec<_>>();
+        assert_eq!(1, prop_directives.len());
+        assert_eq!("auth", prop_directives[0].name());
+        assert_eq!(Some(&json!("admin")), prop_directives[0].arg("role"));
+
+        let name = t.props().find(|p| p.name() == "name").unwrap();
+        assert_eq!(0, name.directives().count());
+    }
 
     /// There's not really much of a "test" per se, in this first unit test.
     /// This is the example used in the book/src/warpgrapher/config.md file, so
@@ -2241,6 +4346,347 @@ mod tests {
         }
     }
 
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_rel_query_without_src_id_test() {
+        let rel_query_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_rel_query_without_src_id.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match rel_query_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name: _,
+                details: _,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    /// Passes if `$src_id` is found only as a whole word outside of string literals, quoted
+    /// names, and comments
+    #[test]
+    fn config_rel_query_refers_to_src_id() {
+        assert!(query_refers_to_src_id(
+            "MATCH (:Project { id: $src_id })<--(u:User) RETURN u"
+        ));
+        assert!(query_refers_to_src_id("g.V($src_id).in('contributor')"));
+        assert!(query_refers_to_src_id("MATCH (p) WHERE p.id = $src_id"));
+        assert!(query_refers_to_src_id(
+            "MATCH (p) WHERE p.name = 'it\\'s' AND p.id = $src_id"
+        ));
+
+        assert!(!query_refers_to_src_id("MATCH (u:User) RETURN u LIMIT 1"));
+        assert!(!query_refers_to_src_id(
+            "MATCH (p) WHERE p.id IN $src_ids RETURN p"
+        ));
+        assert!(!query_refers_to_src_id(
+            "MATCH (p) WHERE p.name = '$src_id' RETURN p"
+        ));
+        assert!(!query_refers_to_src_id(
+            "MATCH (p) WHERE p.name = \"$src_id\" RETURN p"
+        ));
+        assert!(!query_refers_to_src_id("MATCH (p) RETURN p.`$src_id`"));
+        assert!(!query_refers_to_src_id("MATCH (p) RETURN p // $src_id"));
+        assert!(!query_refers_to_src_id("MATCH (p) /* $src_id */ RETURN p"));
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_rel_prop_default_test() {
+        let default_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_rel_prop_default.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(default_config.validate().is_ok());
+        let organization = default_config
+            .types()
+            .find(|t| t.name() == "Organization")
+            .unwrap();
+        assert_eq!(
+            Some(&serde_json::json!("ACTIVE")),
+            organization
+                .props()
+                .find(|p| p.name() == "status")
+                .unwrap()
+                .default_value()
+        );
+
+        let mismatch_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_rel_prop_default_mismatch.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match mismatch_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name,
+                details: _,
+            }) => assert_eq!("Organization::members", type_name),
+            _ => panic!(),
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_rel_position_test() {
+        let position_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_rel_position.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(position_config.validate().is_ok());
+
+        let not_int_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_rel_position_not_int.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match not_int_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name,
+                details: _,
+            }) => assert_eq!("Sprint::tasks", type_name),
+            _ => panic!(),
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_prop_count_test() {
+        let count_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_prop_count.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(count_config.validate().is_ok());
+
+        let unknown_rel_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_prop_count_unknown_rel.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match unknown_rel_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name,
+                details: _,
+            }) => assert_eq!("Project", type_name),
+            _ => panic!(),
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_prop_db_name_test() {
+        let db_name_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_prop_db_name.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(db_name_config.validate().is_ok());
+        let username = db_name_config
+            .types()
+            .next()
+            .unwrap()
+            .props()
+            .find(|p| p.name() == "username")
+            .unwrap();
+        assert_eq!(Some("user_name"), username.db_name());
+
+        let conflict_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_prop_db_name_conflict.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match conflict_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name,
+                details: _,
+            }) => assert_eq!("User", type_name),
+            _ => panic!(),
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_prop_unique_case_insensitive_test() {
+        let unique_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_prop_unique.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(unique_config.validate().is_ok());
+        let email = unique_config
+            .types()
+            .next()
+            .unwrap()
+            .props()
+            .find(|p| p.name() == "email")
+            .unwrap();
+        assert!(email.unique_case_insensitive());
+
+        for fixture in [
+            "tests/fixtures/config-validation/test_config_prop_unique_invalid.yml",
+            "tests/fixtures/config-validation/test_config_prop_unique_shadow_conflict.yml",
+        ] {
+            let invalid_config: Configuration =
+                match File::open(fixture).expect("Couldn't open file").try_into() {
+                    Err(e) => panic!("{}", e),
+                    Ok(wgc) => wgc,
+                };
+
+            match invalid_config.validate() {
+                Err(Error::ConfigItemInvalid {
+                    type_name,
+                    details: _,
+                }) => assert_eq!("User", type_name),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_prop_unique_where_null_test() {
+        let unique_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_prop_unique_where_null.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        assert!(unique_config.validate().is_ok());
+        let email = unique_config
+            .types()
+            .next()
+            .unwrap()
+            .props()
+            .find(|p| p.name() == "email")
+            .unwrap();
+        assert_eq!(Some("deletedAt"), email.unique_where_null());
+
+        let invalid_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_prop_unique_where_null_invalid.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match invalid_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name,
+                details: _,
+            }) => assert_eq!("User", type_name),
+            _ => panic!(),
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_rel_inverse_test() {
+        let inverse_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_rel_inverse.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(inverse_config.validate().is_ok());
+
+        let conflict_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_rel_inverse_conflict.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match conflict_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name,
+                details: _,
+            }) => assert_eq!("Project::issues", type_name),
+            _ => panic!(),
+        }
+    }
+
+    #[allow(clippy::match_wild_err_arm)]
+    #[test]
+    fn config_rel_query_and_resolver_test() {
+        let rel_query_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_rel_query_and_resolver.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match rel_query_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name: _,
+                details: _,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[allow(clippy::match_wild_err_arm)]
     #[test]
     fn config_prop_name_dst_test() {
@@ -2517,6 +4963,183 @@ mod tests {
             Err(Error::ConfigItemReserved { type_name: _ }) => (),
             _ => panic!(),
         }
+
+        //Test Enum
+        let enum_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_enum.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(enum_config.validate().is_ok());
+        assert_eq!(1, enum_config.enums().count());
+
+        //Test default sort on a property the type doesn't have
+        let default_sort_unknown_prop_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_default_sort_unknown_prop.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match default_sort_unknown_prop_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name: _,
+                details: _,
+            }) => (),
+            _ => panic!(),
+        }
+
+        //Test Enum with no values
+        let enum_no_values_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_enum_no_values.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        match enum_no_values_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name: _,
+                details: _,
+            }) => (),
+            _ => panic!(),
+        }
+
+        //Test Enum with a duplicated value
+        let enum_duplicate_value_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_enum_duplicate_value.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match enum_duplicate_value_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name: _,
+                details: _,
+            }) => (),
+            _ => panic!(),
+        }
+
+        //Test Enum deprecating a value it doesn't declare
+        let enum_deprecated_unknown_value_config: Configuration = match File::open(
+            "tests/fixtures/config-validation/test_config_enum_deprecated_unknown_value.yml",
+        )
+        .expect("Couldn't open file")
+        .try_into()
+        {
+            Err(e) => panic!("{}", e),
+            Ok(wgc) => wgc,
+        };
+
+        match enum_deprecated_unknown_value_config.validate() {
+            Err(Error::ConfigItemInvalid {
+                type_name: _,
+                details: _,
+            }) => (),
+            _ => panic!(),
+        }
+
+        //Test Enum with stored values
+        let enum_stored_values_config: Configuration =
+            match File::open("tests/fixtures/config-validation/test_config_enum_stored_values.yml")
+                .expect("Couldn't open file")
+                .try_into()
+            {
+                Err(e) => panic!("{}", e),
+                Ok(wgc) => wgc,
+            };
+
+        assert!(enum_stored_values_config.validate().is_ok());
+        let priority = enum_stored_values_config.enums().next().unwrap();
+        assert_eq!(priority.stored_value("HIGH"), Some(&json!(3)));
+        assert_eq!(priority.serialization(), EnumSerialization::Value);
+
+        //Test Enum with a value missing a stored value, and with stored values of mixed kinds
+        for path in [
+            "tests/fixtures/config-validation/test_config_enum_stored_value_missing.yml",
+            "tests/fixtures/config-validation/test_config_enum_stored_value_mixed.yml",
+        ] {
+            let config: Configuration =
+                match File::open(path).expect("Couldn't open file").try_into() {
+                    Err(e) => panic!("{}", e),
+                    Ok(wgc) => wgc,
+                };
+
+            match config.validate() {
+                Err(Error::ConfigItemInvalid {
+                    type_name: _,
+                    details: _,
+                }) => (),
+                _ => panic!(),
+            }
+        }
+    }
+
+    /// Passes if the type, property, and relationship names of a configuration are listed in
+    /// the order in which they are declared
+    #[test]
+    fn config_names_test() {
+        let config: Configuration = File::open("tests/fixtures/minimal.yml")
+            .expect("Couldn't open file")
+            .try_into()
+            .expect("Couldn't convert to config");
+
+        assert_eq!(
+            vec!(
+                "User",
+                "Portfolio",
+                "Project",
+                "Feature",
+                "Bug",
+                "KanbanBoard",
+                "ScrumBoard",
+                "Commit"
+            ),
+            config.type_names().collect::<Vec<&str>>()
+        );
+
+        let project = config
+            .types()
+            .find(|t| t.name() == "Project")
+            .expect("Expected Project type");
+        assert_eq!(
+            vec!(
+                "name",
+                "description",
+                "status",
+                "priority",
+                "estimate",
+                "active",
+                "code",
+                "views",
+                "cycleTime",
+                "issueCount"
+            ),
+            project.property_names().collect::<Vec<&str>>()
+        );
+        assert_eq!(
+            vec!("owner", "board", "activity", "issues"),
+            project.relationship_names().collect::<Vec<&str>>()
+        );
+
+        let portfolio = config
+            .types()
+            .find(|t| t.name() == "Portfolio")
+            .expect("Expected Portfolio type");
+        assert_eq!(0, portfolio.property_names().count());
     }
 
     #[allow(clippy::match_wild_err_arm)]
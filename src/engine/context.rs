@@ -1,17 +1,26 @@
 //! This module provides a Juniper Context for Warpgrapher GraphQL queries. The
 //! context contains a connection pool for the database.
 use crate::engine::database::no_database::NoDatabaseEndpoint;
-use crate::engine::database::DatabaseEndpoint;
+use crate::engine::database::{DatabaseEndpoint, DatabasePool, ProfileLog};
 use crate::engine::events::EventHandlerBag;
-use crate::engine::loader::{NodeLoader, RelLoader};
-use crate::engine::resolvers::{ResolverFunc, Resolvers};
+use crate::engine::idempotency::{idempotency_key, Claimed, IdempotencyStore};
+use crate::engine::loader::{AggregateLoader, CountLoader, NodeCache, NodeLoader, RelLoader};
+use crate::engine::metadata::Metadata;
+#[cfg(feature = "metrics")]
+use crate::engine::metrics::LoaderMetrics;
+use crate::engine::resolvers::{
+    DirectiveFunc, Directives, ResolverFunc, ResolverTimeout, ResolverTimeouts, ResolverWrappers,
+    Resolvers,
+};
 use crate::engine::schema::Info;
 use crate::engine::validators::Validators;
+use crate::engine::value::Value;
 use crate::Error;
-use juniper::Context;
+use juniper::{Context, InputValue};
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::sync::{Arc, Mutex, OnceLock};
 use ultra_batch::Batcher;
 
 /// Juniper Context for Warpgrapher's GraphQL queries. The ['GraphQLContext'] is
@@ -23,12 +32,30 @@ pub struct GraphQLContext<RequestCtx: RequestContext> {
     pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
     node_batcher: Batcher<NodeLoader<RequestCtx>>,
     rel_batcher: Batcher<RelLoader<RequestCtx>>,
+    count_batcher: Batcher<CountLoader<RequestCtx>>,
+    aggregate_batcher: Batcher<AggregateLoader<RequestCtx>>,
+    node_cache: NodeCache<RequestCtx>,
+    #[cfg(feature = "metrics")]
+    loader_metrics: LoaderMetrics,
     resolvers: Resolvers<RequestCtx>,
+    resolver_wrappers: ResolverWrappers<RequestCtx>,
     validators: Validators,
     event_handlers: EventHandlerBag<RequestCtx>,
     request_ctx: Option<RequestCtx>,
     version: Option<String>,
-    metadata: HashMap<String, String>,
+    metadata: Metadata,
+    operation_name: Option<String>,
+    variables: OnceLock<serde_json::Value>,
+    input_value: Option<Arc<InputValue>>,
+    idempotency: Option<IdempotencyStore<RequestCtx>>,
+    profile_log: Option<ProfileLog>,
+    read_after_write: bool,
+    require_read_filter: bool,
+    directives: Directives<RequestCtx>,
+    resolver_timeouts: ResolverTimeouts,
+    max_write_depth: usize,
+    type_max_write_depths: HashMap<String, usize>,
+    write_depth: Mutex<WriteDepth>,
 }
 
 impl<RequestCtx> GraphQLContext<RequestCtx>
@@ -43,6 +70,8 @@ where
     /// * pool - the [`DatabasePool`] that provides connections to the graph storage back-end
     /// * resolvers - the [`Resolvers`] structure containing any custom resolvers provided as
     /// part of the Warpgrapher configuration
+    /// * resolver_wrappers - the [`ResolverWrappers`] run around every custom resolver, in
+    ///   registration order
     /// * validators - the [`Validators`] structure containing any custom input validators
     /// provided as part of the Warpgrapher configuration
     /// * event_handlers - the [`EventHandlerBag`] structure containing business logic
@@ -52,37 +81,291 @@ where
     /// request-specific context to custom resolvers
     /// * version - an optional version of the application service using the Warpgrapher framework,
     /// used to respond to the version static endpoint
+    /// * metadata - request metadata, such as HTTP headers, passed to the engine by the caller
+    /// * info - the schema information used by the node loader
+    /// * loader_max_batch - the maximum number of keys fetched by a single loader query
     ///
     /// [`DatabasePool`]: ../database/trait.DatabasePool.html
     /// [`EventHandlerBag`]: ../events/struct.EventHandlerBag.html
     /// [`Extensions`]: ../extensions/type.Extensions.html
     /// [`RequestContext`]: ./trait.RequestContext.html
+    /// [`ResolverWrappers`]: ../resolvers/type.ResolverWrappers.html
     /// [`Resolvers`]: ../resolvers/type.Resolvers.html
     /// [`Validators`]: ../validators/type.Validators.html
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
         resolvers: Resolvers<RequestCtx>,
+        resolver_wrappers: ResolverWrappers<RequestCtx>,
         validators: Validators,
         event_handlers: EventHandlerBag<RequestCtx>,
         request_ctx: Option<RequestCtx>,
         version: Option<String>,
-        metadata: HashMap<String, String>,
+        metadata: Metadata,
         info: Info,
+        loader_max_batch: usize,
     ) -> GraphQLContext<RequestCtx> {
-        let node_batcher =
-            Batcher::build(NodeLoader::<RequestCtx>::new(pool.clone(), info)).finish();
-        let rel_batcher = Batcher::build(RelLoader::<RequestCtx>::new(pool.clone())).finish();
+        #[cfg(feature = "metrics")]
+        let loader_metrics = LoaderMetrics::new();
+
+        let node_loader =
+            NodeLoader::<RequestCtx>::new(pool.clone(), info).with_max_batch(loader_max_batch);
+        let rel_loader =
+            RelLoader::<RequestCtx>::new(pool.clone()).with_max_batch(loader_max_batch);
+        let count_loader =
+            CountLoader::<RequestCtx>::new(pool.clone()).with_max_batch(loader_max_batch);
+        let aggregate_loader =
+            AggregateLoader::<RequestCtx>::new(pool.clone()).with_max_batch(loader_max_batch);
+        let node_cache = NodeCache::new();
+        #[cfg(feature = "metrics")]
+        let (node_loader, rel_loader, count_loader, aggregate_loader, node_cache) = (
+            node_loader.with_metrics(loader_metrics.clone()),
+            rel_loader.with_metrics(loader_metrics.clone()),
+            count_loader.with_metrics(loader_metrics.clone()),
+            aggregate_loader.with_metrics(loader_metrics.clone()),
+            node_cache.with_metrics(loader_metrics.clone()),
+        );
+
+        let node_batcher = Batcher::build(node_loader)
+            .eager_batch_size(Some(loader_max_batch))
+            .finish();
+        let rel_batcher = Batcher::build(rel_loader)
+            .eager_batch_size(Some(loader_max_batch))
+            .finish();
+        let count_batcher = Batcher::build(count_loader)
+            .eager_batch_size(Some(loader_max_batch))
+            .finish();
+        let aggregate_batcher = Batcher::build(aggregate_loader)
+            .eager_batch_size(Some(loader_max_batch))
+            .finish();
         GraphQLContext {
             pool,
             node_batcher,
             rel_batcher,
+            count_batcher,
+            aggregate_batcher,
+            node_cache,
+            #[cfg(feature = "metrics")]
+            loader_metrics,
             resolvers,
+            resolver_wrappers,
             validators,
             event_handlers,
             request_ctx,
             version,
             metadata,
+            operation_name: None,
+            variables: OnceLock::new(),
+            input_value: None,
+            idempotency: None,
+            profile_log: None,
+            read_after_write: true,
+            require_read_filter: false,
+            directives: HashMap::new(),
+            resolver_timeouts: HashMap::new(),
+            max_write_depth: DEFAULT_MAX_WRITE_DEPTH,
+            type_max_write_depths: HashMap::new(),
+            write_depth: Mutex::new(WriteDepth::default()),
+        }
+    }
+
+    /// Sets the name of the operation selected by the request and the variables sent with it
+    pub(crate) fn with_request(
+        mut self,
+        operation_name: Option<String>,
+        variables: serde_json::Value,
+    ) -> Self {
+        self.operation_name = operation_name;
+        self.variables = OnceLock::from(variables);
+        self
+    }
+
+    /// Sets the name of the operation selected by the request and the variables sent with it,
+    /// already parsed into a juniper [`InputValue`]. The variables are converted to JSON only if
+    /// they are asked for.
+    ///
+    /// [`InputValue`]: ../../juniper/enum.InputValue.html
+    pub(crate) fn with_request_input(
+        mut self,
+        operation_name: Option<String>,
+        input_value: Option<Arc<InputValue>>,
+    ) -> Self {
+        self.operation_name = operation_name;
+        self.variables = OnceLock::new();
+        self.input_value = input_value;
+        self
+    }
+
+    /// Sets the store used to record create mutations for requests that carry an idempotency key
+    pub(crate) fn with_idempotency_store(
+        mut self,
+        idempotency: Option<IdempotencyStore<RequestCtx>>,
+    ) -> Self {
+        self.idempotency = idempotency;
+        self
+    }
+
+    /// Returns the key under which a create mutation is recorded, if the engine keeps
+    /// idempotency records and the request metadata carries an idempotency key. The key is
+    /// scoped to the caller returned by [`RequestContext::idempotency_scope`].
+    ///
+    /// [`RequestContext::idempotency_scope`]: trait.RequestContext.html#method.idempotency_scope
+    pub(crate) fn idempotency_record_key(&self, field: &str) -> Option<String> {
+        self.idempotency.as_ref()?;
+        let scope = self
+            .request_ctx
+            .as_ref()
+            .and_then(|rc| rc.idempotency_scope())
+            .unwrap_or_default();
+        // the scope is length-prefixed so that no scope and key pair can be confused for another
+        idempotency_key(&self.metadata)
+            .map(|key| format!("{}:{}/{}/{}", scope.len(), scope, key, field))
+    }
+
+    /// Claims the idempotency key of a create mutation, if the engine keeps idempotency records
+    /// and the request metadata carries a key, waiting for any other create holding the same key
+    /// to finish. Returns the claim and the nodes or relationships recorded for the key, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`IdempotencyInputMismatched`] if a create was recorded for
+    /// the key with a different input
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`IdempotencyInputMismatched`]: ../../error/enum.Error.html#variant.IdempotencyInputMismatched
+    pub(crate) async fn claim_idempotency_key(
+        &self,
+        field: &str,
+        input: &Value,
+    ) -> Result<Option<Claimed<RequestCtx>>, Error> {
+        match (&self.idempotency, self.idempotency_record_key(field)) {
+            (Some(store), Some(key)) => store.claim(key, input.clone()).await.map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Sets the log that collects the profiles of mutations run with profiling. Profiling is
+    /// unavailable if the log is `None`.
+    pub(crate) fn with_profile_log(mut self, profile_log: Option<ProfileLog>) -> Self {
+        self.profile_log = profile_log;
+        self
+    }
+
+    /// Sets the handlers that enforce the directives applied to types and properties in the
+    /// configuration
+    pub(crate) fn with_directives(mut self, directives: Directives<RequestCtx>) -> Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Sets the timeouts applied to custom resolvers
+    pub(crate) fn with_resolver_timeouts(mut self, resolver_timeouts: ResolverTimeouts) -> Self {
+        self.resolver_timeouts = resolver_timeouts;
+        self
+    }
+
+    /// Sets the maximum depth of nested node writes in a mutation, and any per-type overrides of
+    /// that maximum
+    pub(crate) fn with_write_depth_limits(
+        mut self,
+        max_write_depth: usize,
+        type_max_write_depths: HashMap<String, usize>,
+    ) -> Self {
+        self.max_write_depth = max_write_depth;
+        self.type_max_write_depths = type_max_write_depths;
+        self
+    }
+
+    /// Records the start of a node create or update nested within the current mutation, returning
+    /// a guard that records its end when dropped. The limit is taken from the outermost node's
+    /// type, falling back to the engine-wide maximum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`NestedInputTooDeep`] if the write is nested more deeply than
+    /// the limit allows.
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`NestedInputTooDeep`]: ../../error/enum.Error.html#variant.NestedInputTooDeep
+    pub(crate) fn enter_write(&self, type_name: &str) -> Result<WriteDepthGuard<'_>, Error> {
+        let mut wd = self.write_depth.lock().unwrap_or_else(|e| e.into_inner());
+        if wd.depth == 0 {
+            wd.type_name = type_name.to_string();
+            wd.limit = self
+                .type_max_write_depths
+                .get(type_name)
+                .copied()
+                .unwrap_or(self.max_write_depth);
+        }
+        if wd.depth >= wd.limit {
+            return Err(Error::NestedInputTooDeep {
+                type_name: wd.type_name.clone(),
+                max_depth: wd.limit,
+            });
+        }
+        wd.depth += 1;
+
+        Ok(WriteDepthGuard {
+            write_depth: &self.write_depth,
+        })
+    }
+
+    /// Sets whether reads that follow a write in the same request are routed to the read/write
+    /// endpoint of the database, rather than to a read replica
+    pub(crate) fn with_read_after_write(mut self, read_after_write: bool) -> Self {
+        self.read_after_write = read_after_write;
+        self
+    }
+
+    /// Sets whether queries must have input to filter the nodes or relationships they read
+    pub(crate) fn with_require_read_filter(mut self, require_read_filter: bool) -> Self {
+        self.require_read_filter = require_read_filter;
+        self
+    }
+
+    /// Returns true if queries must have input to filter the nodes or relationships they read
+    pub(crate) fn require_read_filter(&self) -> bool {
+        self.require_read_filter
+    }
+
+    /// Returns a transaction for a read. Until a write in the request has invalidated the node
+    /// cache, the transaction may be served by a read replica. Once it has, and read-after-write
+    /// consistency is on, the transaction is served by the read/write endpoint, so that the read
+    /// sees the request's own writes even if the replicas have not caught up.
+    pub(crate) async fn read_transaction(
+        &self,
+    ) -> Result<
+        <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+        Error,
+    >{
+        if self.read_after_write && self.node_cache.is_stale() {
+            self.pool.transaction().await
+        } else {
+            self.pool.read_transaction().await
+        }
+    }
+
+    /// Returns the pool of database connections for a mutation. If `profile` is true, the
+    /// pool's transactions record the profile of each query they run in the profile log.
+    /// Otherwise, the request's own pool is returned, so a mutation that doesn't ask for a profile
+    /// runs unprofiled even when the engine has a profile log for the request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`ProfilingDisabled`] if `profile` is true and profiling is
+    /// not enabled for the engine
+    ///
+    /// [`Error`]: ../../enum.Error.html
+    /// [`ProfilingDisabled`]: ../../enum.Error.html#variant.ProfilingDisabled
+    pub(crate) fn mutation_pool(
+        &self,
+        profile: bool,
+    ) -> Result<<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType, Error>
+    {
+        match (profile, &self.profile_log) {
+            (false, _) => Ok(self.pool.clone()),
+            (true, Some(log)) => Ok(self.pool.with_profile_log(log.clone())),
+            (true, None) => Err(Error::ProfilingDisabled),
         }
     }
 
@@ -111,6 +394,34 @@ where
             })
     }
 
+    /// Takes the name of a directive and returns the handler enforcing that directive
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`DirectiveNotFound`] if the context does not contain a
+    /// handler associated with the name argument
+    ///
+    /// [`DirectiveNotFound`]: ../../enum.Error.html#variant.DirectiveNotFound
+    /// [`Error`]: ../../enum.Error.html
+    pub fn directive(&self, name: &str) -> Result<&DirectiveFunc<RequestCtx>, Error> {
+        self.directives
+            .get(name)
+            .map(|b| b.as_ref())
+            .ok_or_else(|| Error::DirectiveNotFound {
+                name: name.to_owned(),
+            })
+    }
+
+    /// Takes the name of a custom resolver and returns the timeout applied to it, if there is one
+    pub fn resolver_timeout(&self, name: &str) -> Option<&ResolverTimeout> {
+        self.resolver_timeouts.get(name)
+    }
+
+    /// Returns the wrappers run around every custom resolver, in registration order
+    pub fn resolver_wrappers(&self) -> &ResolverWrappers<RequestCtx> {
+        &self.resolver_wrappers
+    }
+
     /// Returns the set of custom input validation functions
     pub fn validators(&self) -> &Validators {
         &self.validators
@@ -132,10 +443,35 @@ where
         self.request_ctx.as_ref()
     }
 
-    pub fn metadata(&self) -> &HashMap<String, String> {
+    /// Returns the metadata passed to the engine with the request, such as HTTP headers
+    pub fn metadata(&self) -> &Metadata {
         &self.metadata
     }
 
+    /// Returns the name of the operation selected by the request, if the request named one
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_deref()
+    }
+
+    /// Returns the variables sent with the request, as a JSON object. The object is empty if the
+    /// request had no variables.
+    pub fn variables(&self) -> &serde_json::Value {
+        self.variables.get_or_init(|| {
+            self.input_value
+                .as_ref()
+                .and_then(|iv| serde_json::to_value(iv.as_ref()).ok())
+                .unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()))
+        })
+    }
+
+    /// Returns a copy of the variables sent with the request, in which the value of every
+    /// variable or nested field whose name is in `names`, compared without regard to case, is
+    /// replaced by `"[REDACTED]"`. Used to log the variables without exposing secrets such as
+    /// passwords or tokens.
+    pub fn redacted_variables(&self, names: &[&str]) -> serde_json::Value {
+        redact(self.variables(), names)
+    }
+
     pub fn node_batcher(&self) -> &Batcher<NodeLoader<RequestCtx>> {
         &self.node_batcher
     }
@@ -143,10 +479,79 @@ where
     pub fn rel_batcher(&self) -> &Batcher<RelLoader<RequestCtx>> {
         &self.rel_batcher
     }
+
+    /// Returns the batcher that loads relationship counts for count properties
+    pub fn count_batcher(&self) -> &Batcher<CountLoader<RequestCtx>> {
+        &self.count_batcher
+    }
+
+    /// Returns the batcher that loads relationship aggregates for aggregate fields
+    pub fn aggregate_batcher(&self) -> &Batcher<AggregateLoader<RequestCtx>> {
+        &self.aggregate_batcher
+    }
+
+    /// Returns the request-scoped cache of nodes already read during this request
+    pub fn node_cache(&self) -> &NodeCache<RequestCtx> {
+        &self.node_cache
+    }
+
+    /// Returns the metrics recorded by the loaders and node cache during this request
+    #[cfg(feature = "metrics")]
+    pub fn loader_metrics(&self) -> &LoaderMetrics {
+        &self.loader_metrics
+    }
 }
 
 impl<RequestCtx> Context for GraphQLContext<RequestCtx> where RequestCtx: RequestContext {}
 
+/// Returns a copy of `value` with the values of fields named in `names` replaced, at any depth
+fn redact(value: &serde_json::Value, names: &[&str]) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(m) => serde_json::Value::Object(
+            m.iter()
+                .map(|(k, v)| {
+                    if names.iter().any(|name| name.eq_ignore_ascii_case(k)) {
+                        (
+                            k.clone(),
+                            serde_json::Value::String("[REDACTED]".to_string()),
+                        )
+                    } else {
+                        (k.clone(), redact(v, names))
+                    }
+                })
+                .collect(),
+        ),
+        serde_json::Value::Array(a) => {
+            serde_json::Value::Array(a.iter().map(|v| redact(v, names)).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+/// Default maximum depth of nested node writes in a create or update mutation
+pub(crate) const DEFAULT_MAX_WRITE_DEPTH: usize = 10;
+
+/// Depth of the node write in progress, and the limit taken from the outermost node's type
+#[derive(Debug, Default)]
+struct WriteDepth {
+    depth: usize,
+    limit: usize,
+    type_name: String,
+}
+
+/// Marks a node write in progress until it is dropped
+#[derive(Debug)]
+pub(crate) struct WriteDepthGuard<'a> {
+    write_depth: &'a Mutex<WriteDepth>,
+}
+
+impl Drop for WriteDepthGuard<'_> {
+    fn drop(&mut self) {
+        let mut wd = self.write_depth.lock().unwrap_or_else(|e| e.into_inner());
+        wd.depth -= 1;
+    }
+}
+
 impl<RequestCtx> Debug for GraphQLContext<RequestCtx>
 where
     RequestCtx: RequestContext,
@@ -185,6 +590,114 @@ where
 pub trait RequestContext: 'static + Clone + Debug + Send + Sync {
     type DBEndpointType: DatabaseEndpoint;
     fn new() -> Self;
+
+    /// Creates a request context from the request metadata, such as HTTP headers, passed to
+    /// [`Engine::execute`]. The engine creates each request's context with this function, so a
+    /// context built here is fully initialized before any event handler or resolver runs. The
+    /// default implementation ignores the metadata and calls [`new`].
+    ///
+    /// [`Engine::execute`]: ../struct.Engine.html#method.execute
+    /// [`new`]: #tymethod.new
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use warpgrapher::engine::context::RequestContext;
+    /// # use warpgrapher::engine::database::no_database::NoDatabaseEndpoint;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct AppRequestContext {
+    ///     session_token: String
+    /// }
+    ///
+    /// impl RequestContext for AppRequestContext {
+    ///     type DBEndpointType = NoDatabaseEndpoint;
+    ///     fn new() -> Self {
+    ///         AppRequestContext { session_token: "".to_string() }
+    ///     }
+    ///
+    ///     fn from_metadata(metadata: &HashMap<String, String>) -> Self {
+    ///         AppRequestContext {
+    ///             session_token: metadata.get("authorization").cloned().unwrap_or_default()
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn from_metadata(_metadata: &HashMap<String, String>) -> Self {
+        Self::new()
+    }
+
+    /// Returns the name of the database that the request should run against, for back-ends that
+    /// host several databases behind one endpoint, such as Neo4j 4.0 and later. The engine reads
+    /// the name after the before request event handlers have run, and every transaction and
+    /// query for the rest of the request is sent to that database. The default implementation
+    /// returns `None`, which uses the server's default database.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use warpgrapher::engine::context::RequestContext;
+    /// # use warpgrapher::engine::database::no_database::NoDatabaseEndpoint;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct AppRequestContext {
+    ///     tenant: Option<String>
+    /// }
+    ///
+    /// impl RequestContext for AppRequestContext {
+    ///     type DBEndpointType = NoDatabaseEndpoint;
+    ///     fn new() -> Self {
+    ///         AppRequestContext { tenant: None }
+    ///     }
+    ///
+    ///     fn from_metadata(metadata: &HashMap<String, String>) -> Self {
+    ///         AppRequestContext { tenant: metadata.get("tenant").cloned() }
+    ///     }
+    ///
+    ///     fn database(&self) -> Option<&str> {
+    ///         self.tenant.as_deref()
+    ///     }
+    /// }
+    /// ```
+    fn database(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the caller to which idempotency keys are scoped. A create mutation sent with an
+    /// `Idempotency-Key` is only matched against creates recorded with the same key and the same
+    /// scope, so one caller can't replay the results recorded for another. The default
+    /// implementation returns `None`, which puts every request in one shared scope. Applications
+    /// that enable idempotency keys for more than one caller should return an identifier for the
+    /// authenticated caller, such as a user or tenant id.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use warpgrapher::engine::context::RequestContext;
+    /// # use warpgrapher::engine::database::no_database::NoDatabaseEndpoint;
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct AppRequestContext {
+    ///     user_id: Option<String>
+    /// }
+    ///
+    /// impl RequestContext for AppRequestContext {
+    ///     type DBEndpointType = NoDatabaseEndpoint;
+    ///     fn new() -> Self {
+    ///         AppRequestContext { user_id: None }
+    ///     }
+    ///
+    ///     fn idempotency_scope(&self) -> Option<String> {
+    ///         self.user_id.clone()
+    ///     }
+    /// }
+    /// ```
+    fn idempotency_scope(&self) -> Option<String> {
+        None
+    }
 }
 
 impl RequestContext for () {
@@ -195,13 +708,16 @@ impl RequestContext for () {
 #[cfg(test)]
 mod tests {
 
-    use super::GraphQLContext;
+    use super::{redact, GraphQLContext};
     use crate::engine::database::no_database::NoDatabaseEndpoint;
     use crate::engine::database::DatabaseEndpoint;
     use crate::engine::events::EventHandlerBag;
+    use crate::engine::loader::DEFAULT_LOADER_MAX_BATCH;
+    use crate::engine::metadata::Metadata;
     use crate::engine::resolvers::Resolvers;
     use crate::engine::schema::Info;
     use crate::engine::validators::Validators;
+    use crate::Error;
     use std::collections::HashMap;
     use std::sync::Arc;
 
@@ -216,12 +732,77 @@ mod tests {
                 .await
                 .expect("Expected to unwrap Cypher database pool."),
             resolvers,
+            Vec::new(),
             validators,
             EventHandlerBag::new(),
             Some(()),
             None,
-            HashMap::<String, String>::new(),
+            Metadata::new(),
+            Info::new(String::new(), Arc::new(HashMap::new())),
+            DEFAULT_LOADER_MAX_BATCH,
+        );
+    }
+
+    /// Passes if nested writes are rejected beyond the limit of the outermost type, and the depth
+    /// is released as each write completes
+    #[tokio::test]
+    async fn enter_write_depth() {
+        let ne = NoDatabaseEndpoint {};
+        let gqlctx: GraphQLContext<()> = GraphQLContext::new(
+            ne.pool()
+                .await
+                .expect("Expected to unwrap Cypher database pool."),
+            Resolvers::new(),
+            Vec::new(),
+            Validators::new(),
+            EventHandlerBag::new(),
+            Some(()),
+            None,
+            Metadata::new(),
             Info::new(String::new(), Arc::new(HashMap::new())),
+            DEFAULT_LOADER_MAX_BATCH,
+        )
+        .with_write_depth_limits(1, vec![("Project".to_string(), 2)].into_iter().collect());
+
+        {
+            let _outer = gqlctx.enter_write("Project").unwrap();
+            let _inner = gqlctx.enter_write("User").unwrap();
+            assert!(matches!(
+                gqlctx.enter_write("User"),
+                Err(Error::NestedInputTooDeep {
+                    type_name,
+                    max_depth: 2
+                }) if type_name == "Project"
+            ));
+        }
+
+        let _outer = gqlctx.enter_write("User").unwrap();
+        assert!(matches!(
+            gqlctx.enter_write("Project"),
+            Err(Error::NestedInputTooDeep {
+                type_name,
+                max_depth: 1
+            }) if type_name == "User"
+        ));
+    }
+
+    /// Passes if redaction replaces the values of the named variables and nested fields, without
+    /// regard to case, and leaves the others unchanged
+    #[test]
+    fn redact_variables() {
+        let variables = serde_json::json!({
+            "input": {"email": "alice@example.com", "Password": "hunter2"},
+            "tokens": [{"token": "abc"}],
+            "apiKey": "xyz"
+        });
+
+        assert_eq!(
+            serde_json::json!({
+                "input": {"email": "alice@example.com", "Password": "[REDACTED]"},
+                "tokens": [{"token": "[REDACTED]"}],
+                "apiKey": "[REDACTED]"
+            }),
+            redact(&variables, &["password", "token", "apikey"])
         );
     }
 }
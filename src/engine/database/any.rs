@@ -0,0 +1,577 @@
+//! Provides database interface types that select the Cypher or Gremlin back-end at runtime, so
+//! that a single build of an application can serve either kind of database. Available when both
+//! the `cypher` and `gremlin` features are enabled.
+//!
+//! The [`Transaction`] trait has generic methods, so it cannot be used as a trait object.
+//! Instead, each type in this module is an enum with a variant for each back-end, and each trait
+//! method matches on the variant and calls the same method on the back-end's own type. The
+//! transaction variants are boxed, so that the enum is the size of a pointer plus its tag,
+//! whichever back-end it holds.
+//!
+//! The cost of the dispatch is one branch on the variant for each call into the database layer,
+//! plus one heap allocation for each transaction. Both are small next to the network round trip
+//! that each call makes to the database. The generated code for the resolvers is not affected,
+//! as it is generic over the [`RequestContext`] either way.
+//!
+//! [`RequestContext`]: ../../context/trait.RequestContext.html
+//! [`Transaction`]: ../trait.Transaction.html
+
+use crate::engine::context::RequestContext;
+use crate::engine::database::cypher::{CypherDatabasePool, CypherEndpoint, CypherTransaction};
+use crate::engine::database::gremlin::{GremlinEndpoint, GremlinPool, GremlinTransaction};
+use crate::engine::database::{
+    env_string, Comparison, DatabaseEndpoint, DatabasePool, NodeQueryVar, ProfileLog,
+    PropOperations, QueryFragment, QueryInterceptor, QueryResult, RelQueryVar, StatementLog,
+    SuffixGenerator, Transaction,
+};
+use crate::engine::loader::{AggregateLoaderKey, NodeLoaderKey, RelLoaderKey};
+use crate::engine::objects::{Node, Options, Rel};
+use crate::engine::schema::Info;
+use crate::engine::value::Value;
+use crate::error::Error;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A database endpoint for either a Cypher or a Gremlin database, chosen at runtime
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use warpgrapher::engine::database::any::AnyEndpoint;
+/// # use warpgrapher::engine::database::cypher::CypherEndpoint;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let ae = AnyEndpoint::Cypher(CypherEndpoint::from_env()?);
+/// #    Ok(())
+/// # }
+/// ```
+pub enum AnyEndpoint {
+    Cypher(CypherEndpoint),
+    Gremlin(GremlinEndpoint),
+}
+
+impl AnyEndpoint {
+    /// Reads the WG_DATABASE environment variable to select a back-end, and then reads the
+    /// environment variables for that back-end to construct an [`AnyEndpoint`]. WG_DATABASE may
+    /// be set to `cypher` or `gremlin`. The environment variables for each back-end are those
+    /// read by [`CypherEndpoint::from_env`] and [`GremlinEndpoint::from_env`].
+    ///
+    /// [`AnyEndpoint`]: ./enum.AnyEndpoint.html
+    /// [`CypherEndpoint::from_env`]: ../cypher/struct.CypherEndpoint.html#method.from_env
+    /// [`GremlinEndpoint::from_env`]: ../gremlin/struct.GremlinEndpoint.html#method.from_env
+    ///
+    /// # Errors
+    ///
+    /// * [`EnvironmentVariableNotFound`] - if WG_DATABASE, or an environment variable required by
+    ///   the selected back-end, does not exist
+    /// * [`DatabaseTypeUnknown`] - if WG_DATABASE is set to something other than `cypher` or
+    ///   `gremlin`
+    ///
+    /// [`DatabaseTypeUnknown`]: ../../../enum.Error.html#variant.DatabaseTypeUnknown
+    /// [`EnvironmentVariableNotFound`]: ../../../enum.Error.html#variant.EnvironmentVariableNotFound
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::engine::database::any::AnyEndpoint;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let ae = AnyEndpoint::from_env()?;
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn from_env() -> Result<Self, Error> {
+        let name = env_string("WG_DATABASE")?;
+        match name.to_lowercase().as_str() {
+            "cypher" => Ok(AnyEndpoint::Cypher(CypherEndpoint::from_env()?)),
+            "gremlin" => Ok(AnyEndpoint::Gremlin(GremlinEndpoint::from_env()?)),
+            _ => Err(Error::DatabaseTypeUnknown { name }),
+        }
+    }
+}
+
+#[async_trait]
+impl DatabaseEndpoint for AnyEndpoint {
+    type PoolType = AnyPool;
+
+    async fn pool(&self) -> Result<Self::PoolType, Error> {
+        match self {
+            AnyEndpoint::Cypher(e) => Ok(AnyPool::Cypher(e.pool().await?)),
+            AnyEndpoint::Gremlin(e) => Ok(AnyPool::Gremlin(Box::new(e.pool().await?))),
+        }
+    }
+}
+
+/// A pool of connections to either a Cypher or a Gremlin database
+#[derive(Clone)]
+pub enum AnyPool {
+    Cypher(CypherDatabasePool),
+    Gremlin(Box<GremlinPool>),
+}
+
+#[async_trait]
+impl DatabasePool for AnyPool {
+    type TransactionType = AnyTransaction;
+
+    async fn read_transaction(&self) -> Result<Self::TransactionType, Error> {
+        match self {
+            AnyPool::Cypher(p) => Ok(AnyTransaction::Cypher(Box::new(
+                p.read_transaction().await?,
+            ))),
+            AnyPool::Gremlin(p) => Ok(AnyTransaction::Gremlin(Box::new(
+                p.read_transaction().await?,
+            ))),
+        }
+    }
+
+    async fn transaction(&self) -> Result<Self::TransactionType, Error> {
+        match self {
+            AnyPool::Cypher(p) => Ok(AnyTransaction::Cypher(Box::new(p.transaction().await?))),
+            AnyPool::Gremlin(p) => Ok(AnyTransaction::Gremlin(Box::new(p.transaction().await?))),
+        }
+    }
+
+    fn with_statement_log(&self, log: StatementLog) -> Result<Self, Error> {
+        match self {
+            AnyPool::Cypher(p) => Ok(AnyPool::Cypher(p.with_statement_log(log)?)),
+            AnyPool::Gremlin(p) => Ok(AnyPool::Gremlin(Box::new(p.with_statement_log(log)?))),
+        }
+    }
+
+    fn with_profile_log(&self, log: ProfileLog) -> Self {
+        match self {
+            AnyPool::Cypher(p) => AnyPool::Cypher(p.with_profile_log(log)),
+            AnyPool::Gremlin(p) => AnyPool::Gremlin(Box::new(p.with_profile_log(log))),
+        }
+    }
+
+    fn with_slow_query_threshold(&self, threshold: Option<Duration>) -> Self {
+        match self {
+            AnyPool::Cypher(p) => AnyPool::Cypher(p.with_slow_query_threshold(threshold)),
+            AnyPool::Gremlin(p) => {
+                AnyPool::Gremlin(Box::new(p.with_slow_query_threshold(threshold)))
+            }
+        }
+    }
+
+    fn with_database(&self, database: Option<String>) -> Self {
+        match self {
+            AnyPool::Cypher(p) => AnyPool::Cypher(p.with_database(database)),
+            AnyPool::Gremlin(p) => AnyPool::Gremlin(Box::new(p.with_database(database))),
+        }
+    }
+
+    fn with_query_interceptor(&self, interceptor: Option<Arc<dyn QueryInterceptor>>) -> Self {
+        match self {
+            AnyPool::Cypher(p) => AnyPool::Cypher(p.with_query_interceptor(interceptor)),
+            AnyPool::Gremlin(p) => {
+                AnyPool::Gremlin(Box::new(p.with_query_interceptor(interceptor)))
+            }
+        }
+    }
+
+    async fn close(&self) -> Result<(), Error> {
+        match self {
+            AnyPool::Cypher(p) => p.close().await,
+            AnyPool::Gremlin(p) => p.close().await,
+        }
+    }
+}
+
+/// A transaction against either a Cypher or a Gremlin database
+pub enum AnyTransaction {
+    Cypher(Box<CypherTransaction>),
+    Gremlin(Box<GremlinTransaction>),
+}
+
+#[async_trait]
+impl Transaction for AnyTransaction {
+    async fn begin(&mut self) -> Result<(), Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.begin().await,
+            AnyTransaction::Gremlin(t) => t.begin().await,
+        }
+    }
+
+    async fn execute_query<RequestCtx: RequestContext>(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<QueryResult, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.execute_query::<RequestCtx>(query, params).await,
+            AnyTransaction::Gremlin(t) => t.execute_query::<RequestCtx>(query, params).await,
+        }
+    }
+
+    async fn execute_batch<RequestCtx: RequestContext>(
+        &mut self,
+        statements: Vec<(String, HashMap<String, Value>)>,
+    ) -> Result<Vec<QueryResult>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.execute_batch::<RequestCtx>(statements).await,
+            AnyTransaction::Gremlin(t) => t.execute_batch::<RequestCtx>(statements).await,
+        }
+    }
+
+    async fn execute_write(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.execute_write(query, params).await,
+            AnyTransaction::Gremlin(t) => t.execute_write(query, params).await,
+        }
+    }
+
+    async fn execute_query_values(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.execute_query_values(query, params).await,
+            AnyTransaction::Gremlin(t) => t.execute_query_values(query, params).await,
+        }
+    }
+
+    async fn create_node<RequestCtx: RequestContext>(
+        &mut self,
+        node_var: &NodeQueryVar,
+        props: HashMap<String, Value>,
+        options: Options,
+        info: &Info,
+        sg: &mut SuffixGenerator,
+    ) -> Result<Node<RequestCtx>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.create_node(node_var, props, options, info, sg).await,
+            AnyTransaction::Gremlin(t) => t.create_node(node_var, props, options, info, sg).await,
+        }
+    }
+
+    async fn create_rels<RequestCtx: RequestContext>(
+        &mut self,
+        src_query_fragment: QueryFragment,
+        dst_query_fragment: QueryFragment,
+        rel_var: &RelQueryVar,
+        id_opt: Option<Value>,
+        props: HashMap<String, Value>,
+        options: Options,
+        sg: &mut SuffixGenerator,
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => {
+                t.create_rels(
+                    src_query_fragment,
+                    dst_query_fragment,
+                    rel_var,
+                    id_opt,
+                    props,
+                    options,
+                    sg,
+                )
+                .await
+            }
+            AnyTransaction::Gremlin(t) => {
+                t.create_rels(
+                    src_query_fragment,
+                    dst_query_fragment,
+                    rel_var,
+                    id_opt,
+                    props,
+                    options,
+                    sg,
+                )
+                .await
+            }
+        }
+    }
+
+    fn node_read_by_ids_fragment<RequestCtx: RequestContext>(
+        &mut self,
+        node_var: &NodeQueryVar,
+        nodes: &[Node<RequestCtx>],
+    ) -> Result<QueryFragment, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.node_read_by_ids_fragment(node_var, nodes),
+            AnyTransaction::Gremlin(t) => t.node_read_by_ids_fragment(node_var, nodes),
+        }
+    }
+
+    fn node_read_fragment(
+        &mut self,
+        rel_query_fragments: Vec<QueryFragment>,
+        node_var: &NodeQueryVar,
+        props: HashMap<String, Comparison>,
+        sg: &mut SuffixGenerator,
+    ) -> Result<QueryFragment, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => {
+                t.node_read_fragment(rel_query_fragments, node_var, props, sg)
+            }
+            AnyTransaction::Gremlin(t) => {
+                t.node_read_fragment(rel_query_fragments, node_var, props, sg)
+            }
+        }
+    }
+
+    async fn load_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        keys: &[NodeLoaderKey],
+        info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.load_nodes(keys, info).await,
+            AnyTransaction::Gremlin(t) => t.load_nodes(keys, info).await,
+        }
+    }
+
+    async fn read_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        node_var: &NodeQueryVar,
+        query_fragment: QueryFragment,
+        options: Options,
+        info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => {
+                t.read_nodes(node_var, query_fragment, options, info).await
+            }
+            AnyTransaction::Gremlin(t) => {
+                t.read_nodes(node_var, query_fragment, options, info).await
+            }
+        }
+    }
+
+    async fn node_exists(
+        &mut self,
+        node_var: &NodeQueryVar,
+        query_fragment: QueryFragment,
+    ) -> Result<bool, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.node_exists(node_var, query_fragment).await,
+            AnyTransaction::Gremlin(t) => t.node_exists(node_var, query_fragment).await,
+        }
+    }
+
+    async fn read_computed_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        query: String,
+        src_id: Value,
+        info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.read_computed_nodes(query, src_id, info).await,
+            AnyTransaction::Gremlin(t) => t.read_computed_nodes(query, src_id, info).await,
+        }
+    }
+
+    fn rel_read_by_ids_fragment<RequestCtx: RequestContext>(
+        &mut self,
+        rel_var: &RelQueryVar,
+        rels: &[Rel<RequestCtx>],
+    ) -> Result<QueryFragment, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.rel_read_by_ids_fragment(rel_var, rels),
+            AnyTransaction::Gremlin(t) => t.rel_read_by_ids_fragment(rel_var, rels),
+        }
+    }
+
+    fn rel_read_fragment(
+        &mut self,
+        src_fragment_opt: Option<QueryFragment>,
+        dst_fragment_opt: Option<QueryFragment>,
+        rel_var: &RelQueryVar,
+        props: HashMap<String, Comparison>,
+        sg: &mut SuffixGenerator,
+    ) -> Result<QueryFragment, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => {
+                t.rel_read_fragment(src_fragment_opt, dst_fragment_opt, rel_var, props, sg)
+            }
+            AnyTransaction::Gremlin(t) => {
+                t.rel_read_fragment(src_fragment_opt, dst_fragment_opt, rel_var, props, sg)
+            }
+        }
+    }
+
+    async fn load_rels<RequestCtx: RequestContext>(
+        &mut self,
+        keys: &[RelLoaderKey],
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.load_rels(keys).await,
+            AnyTransaction::Gremlin(t) => t.load_rels(keys).await,
+        }
+    }
+
+    async fn count_rels(&mut self, keys: &[RelLoaderKey]) -> Result<Vec<i64>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.count_rels(keys).await,
+            AnyTransaction::Gremlin(t) => t.count_rels(keys).await,
+        }
+    }
+
+    async fn count_matching_rels(
+        &mut self,
+        query_fragment: QueryFragment,
+        rel_var: &RelQueryVar,
+    ) -> Result<i64, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.count_matching_rels(query_fragment, rel_var).await,
+            AnyTransaction::Gremlin(t) => t.count_matching_rels(query_fragment, rel_var).await,
+        }
+    }
+
+    async fn aggregate_rels(
+        &mut self,
+        keys: &[AggregateLoaderKey],
+    ) -> Result<Vec<HashMap<String, Value>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.aggregate_rels(keys).await,
+            AnyTransaction::Gremlin(t) => t.aggregate_rels(keys).await,
+        }
+    }
+
+    async fn read_rels<RequestCtx: RequestContext>(
+        &mut self,
+        query_fragment: QueryFragment,
+        rel_var: &RelQueryVar,
+        options: Options,
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.read_rels(query_fragment, rel_var, options).await,
+            AnyTransaction::Gremlin(t) => t.read_rels(query_fragment, rel_var, options).await,
+        }
+    }
+
+    async fn update_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        query_fragment: QueryFragment,
+        node_var: &NodeQueryVar,
+        props: HashMap<String, Value>,
+        options: Options,
+        info: &Info,
+        sg: &mut SuffixGenerator,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => {
+                t.update_nodes(query_fragment, node_var, props, options, info, sg)
+                    .await
+            }
+            AnyTransaction::Gremlin(t) => {
+                t.update_nodes(query_fragment, node_var, props, options, info, sg)
+                    .await
+            }
+        }
+    }
+
+    async fn update_rels<RequestCtx: RequestContext>(
+        &mut self,
+        query_fragment: QueryFragment,
+        rel_var: &RelQueryVar,
+        props: HashMap<String, Value>,
+        ops: PropOperations,
+        options: Options,
+        sg: &mut SuffixGenerator,
+    ) -> Result<Vec<Rel<RequestCtx>>, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => {
+                t.update_rels(query_fragment, rel_var, props, ops, options, sg)
+                    .await
+            }
+            AnyTransaction::Gremlin(t) => {
+                t.update_rels(query_fragment, rel_var, props, ops, options, sg)
+                    .await
+            }
+        }
+    }
+
+    async fn delete_nodes(
+        &mut self,
+        query_fragment: QueryFragment,
+        node_var: &NodeQueryVar,
+        options: Options,
+    ) -> Result<i32, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.delete_nodes(query_fragment, node_var, options).await,
+            AnyTransaction::Gremlin(t) => t.delete_nodes(query_fragment, node_var, options).await,
+        }
+    }
+
+    async fn delete_rels(
+        &mut self,
+        query_fragment: QueryFragment,
+        rel_var: &RelQueryVar,
+        options: Options,
+    ) -> Result<i32, Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.delete_rels(query_fragment, rel_var, options).await,
+            AnyTransaction::Gremlin(t) => t.delete_rels(query_fragment, rel_var, options).await,
+        }
+    }
+
+    async fn commit(&mut self) -> Result<(), Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.commit().await,
+            AnyTransaction::Gremlin(t) => t.commit().await,
+        }
+    }
+
+    async fn rollback(&mut self) -> Result<(), Error> {
+        match self {
+            AnyTransaction::Cypher(t) => t.rollback().await,
+            AnyTransaction::Gremlin(t) => t.rollback().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnyEndpoint, AnyPool, AnyTransaction};
+    use crate::Error;
+
+    #[test]
+    fn test_any_endpoint_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<AnyEndpoint>();
+    }
+
+    #[test]
+    fn test_any_endpoint_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<AnyEndpoint>();
+    }
+
+    #[test]
+    fn test_any_pool_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<AnyPool>();
+    }
+
+    #[test]
+    fn test_any_transaction_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<AnyTransaction>();
+    }
+
+    #[test]
+    fn test_any_transaction_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<AnyTransaction>();
+    }
+
+    /// Passes if selecting a back-end that Warpgrapher does not support returns an error naming
+    /// the back-end
+    #[test]
+    fn test_any_endpoint_unknown_database() {
+        std::env::set_var("WG_DATABASE", "mongodb");
+        match AnyEndpoint::from_env() {
+            Err(Error::DatabaseTypeUnknown { name }) => assert_eq!("mongodb", name),
+            _ => panic!("Expected DatabaseTypeUnknown error"),
+        }
+    }
+}
@@ -2,24 +2,38 @@
 
 use crate::engine::context::RequestContext;
 use crate::engine::database::{
-    env_string, env_u16, Comparison, DatabaseEndpoint, DatabasePool, NodeQueryVar, Operation,
-    QueryFragment, QueryResult, RelQueryVar, SuffixGenerator, Transaction,
+    env_bool, env_string, env_u16, graphql_name, log_slow_query, Comparison, DatabaseEndpoint,
+    DatabasePool, NodeQueryVar, Operation, ProfileLog, PropOperations, QueryFragment,
+    QueryInterceptor, QueryProfile, QueryResult, RelQueryVar, StatementLog, SuffixGenerator,
+    Transaction,
 };
-use crate::engine::loader::{NodeLoaderKey, RelLoaderKey};
+use crate::engine::loader::{AggregateLoaderKey, NodeLoaderKey, RelLoaderKey};
 use crate::engine::objects::{Direction, Node, NodeRef, Options, Rel};
 use crate::engine::schema::Info;
 use crate::engine::value::Value;
 use crate::Error;
 use async_trait::async_trait;
-use bolt_client::{Metadata, Params};
+use bolt_client::error::{CommunicationError, ConnectionError};
+use bolt_client::{Client, Metadata, Params, Stream};
 use bolt_proto::error::ConversionError;
-use bolt_proto::message::{Message, Record};
+use bolt_proto::message::{Message, Pull, Record, Run, RunWithMetadata, Success};
+use bolt_proto::version::{V1_0, V2_0, V3_0};
+use bolt_proto::ServerState;
 use log::{debug, trace};
 use mobc::{Connection, Pool};
-use mobc_bolt::Manager;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::iter::FromIterator;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::BufStream;
+use tokio::net::{lookup_host, TcpStream};
+use tokio_rustls::rustls::{
+    Certificate, ClientConfig, OwnedTrustAnchor, PrivateKey, RootCertStore, ServerName,
+};
+use tokio_rustls::TlsConnector;
+use tokio_util::compat::{Compat, TokioAsyncReadCompatExt};
 use uuid::Uuid;
 
 /// A Cypher endpoint collects the information necessary to generate a connection string and
@@ -50,6 +64,10 @@ pub struct CypherEndpoint {
     user: String,
     pass: String,
     pool_size: u16,
+    isolation_level: CypherIsolationLevel,
+    client_cert: Option<(String, String)>,
+    ca_cert: Option<String>,
+    test_on_acquire: bool,
 }
 
 impl CypherEndpoint {
@@ -88,6 +106,10 @@ impl CypherEndpoint {
             user,
             pass,
             pool_size,
+            isolation_level: CypherIsolationLevel::default(),
+            client_cert: None,
+            ca_cert: None,
+            test_on_acquire: true,
         }
     }
 
@@ -99,6 +121,10 @@ impl CypherEndpoint {
     /// * WG_CYPHER_USER - the username for the Cypher-based DB. For example, `neo4j`.
     /// * WG_CYPHER_PASS - the password for the Cypher-based DB. For example, `my-db-pass`.
     /// * WG_POOL_SIZE - connection pool size. For example, `4`. Optional.
+    /// * WG_CYPHER_CLIENT_CERT - path to a PEM client certificate chain for mutual TLS. Optional.
+    /// * WG_CYPHER_CLIENT_KEY - path to the PEM private key for the client certificate. Required if WG_CYPHER_CLIENT_CERT is set.
+    /// * WG_CYPHER_CA_CERT - path to PEM CA certificates used to verify the server. Optional.
+    /// * WG_CYPHER_TEST_ON_ACQUIRE - false to skip validating pooled connections before use. Defaults to true.
     ///
     /// [`CypherEndpoint`]: ./struct.CypherEndpoint.html
     ///
@@ -119,6 +145,11 @@ impl CypherEndpoint {
     /// # }
     /// ```
     pub fn from_env() -> Result<Self, Error> {
+        let client_cert = match env_string("WG_CYPHER_CLIENT_CERT").ok() {
+            Some(cert_path) => Some((cert_path, env_string("WG_CYPHER_CLIENT_KEY")?)),
+            None => None,
+        };
+
         Ok(CypherEndpoint {
             host: env_string("WG_CYPHER_HOST")?,
             read_host: env_string("WG_CYPHER_READ_REPLICAS")
@@ -128,18 +159,318 @@ impl CypherEndpoint {
             pass: env_string("WG_CYPHER_PASS")?,
             pool_size: env_u16("WG_POOL_SIZE")
                 .unwrap_or_else(|_| num_cpus::get().try_into().unwrap_or(8)),
+            isolation_level: CypherIsolationLevel::default(),
+            client_cert,
+            ca_cert: env_string("WG_CYPHER_CA_CERT").ok(),
+            test_on_acquire: env_bool("WG_CYPHER_TEST_ON_ACQUIRE").unwrap_or(true),
+        })
+    }
+
+    /// Sets the isolation level requested for transactions begun on connections from this
+    /// endpoint's pool. See [`CypherIsolationLevel`] for the levels the database honors.
+    ///
+    /// [`CypherIsolationLevel`]: ./enum.CypherIsolationLevel.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::engine::database::cypher::{CypherEndpoint, CypherIsolationLevel};
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let ne = CypherEndpoint::from_env()?
+    ///         .with_isolation_level(CypherIsolationLevel::ReadCommitted);
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub fn with_isolation_level(mut self, isolation_level: CypherIsolationLevel) -> Self {
+        self.isolation_level = isolation_level;
+        self
+    }
+
+    /// Sets a client certificate and private key to present to the database for mutual TLS.
+    /// Setting a client certificate turns on TLS for connections from this endpoint's pool.
+    ///
+    /// The certificate file must hold one or more PEM-encoded X.509 certificates, starting with
+    /// the client's own certificate followed by any intermediates. The key file must hold a
+    /// PEM-encoded private key in PKCS#8 (`BEGIN PRIVATE KEY`), PKCS#1 RSA
+    /// (`BEGIN RSA PRIVATE KEY`), or SEC1 EC (`BEGIN EC PRIVATE KEY`) format. DER-encoded and
+    /// password-protected files are not supported.
+    ///
+    /// # Errors
+    ///
+    /// The files are read when the pool is created. [`DatabaseEndpoint::pool`] returns
+    /// [`CypherTlsConfigFailed`] if either file cannot be read or parsed.
+    ///
+    /// [`DatabaseEndpoint::pool`]: ../trait.DatabaseEndpoint.html#tymethod.pool
+    /// [`CypherTlsConfigFailed`]: ../../../enum.Error.html#variant.CypherTlsConfigFailed
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::engine::database::cypher::CypherEndpoint;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let ne = CypherEndpoint::from_env()?
+    ///         .with_client_cert("/etc/wg/client.pem".to_string(), "/etc/wg/client.key".to_string());
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub fn with_client_cert(mut self, cert_path: String, key_path: String) -> Self {
+        self.client_cert = Some((cert_path, key_path));
+        self
+    }
+
+    /// Sets a file of PEM-encoded X.509 CA certificates used to verify the database server's
+    /// certificate, in place of the bundled public web roots. Setting a CA certificate turns on
+    /// TLS for connections from this endpoint's pool.
+    ///
+    /// # Errors
+    ///
+    /// The file is read when the pool is created. [`DatabaseEndpoint::pool`] returns
+    /// [`CypherTlsConfigFailed`] if the file cannot be read or holds no valid certificates.
+    ///
+    /// [`DatabaseEndpoint::pool`]: ../trait.DatabaseEndpoint.html#tymethod.pool
+    /// [`CypherTlsConfigFailed`]: ../../../enum.Error.html#variant.CypherTlsConfigFailed
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::engine::database::cypher::CypherEndpoint;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let ne = CypherEndpoint::from_env()?
+    ///         .with_ca_cert("/etc/wg/ca.pem".to_string());
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub fn with_ca_cert(mut self, path: String) -> Self {
+        self.ca_cert = Some(path);
+        self
+    }
+
+    /// Sets whether connections taken from this endpoint's pool are tested before use. When set,
+    /// the pool sends a Bolt `RESET` on a pooled connection before handing it out, which both
+    /// clears any state left by the connection's last transaction and confirms that the server
+    /// is still answering. A connection that fails the test, such as one left stale by a
+    /// database failover, is discarded and replaced with a new connection, rather than failing
+    /// the request. Turning the test off saves a round trip to the database each time a
+    /// connection is taken from the pool, but a stale connection then fails the request that
+    /// takes it. Defaults to true.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::engine::database::cypher::CypherEndpoint;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let ne = CypherEndpoint::from_env()?
+    ///         .with_test_on_acquire(false);
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub fn with_test_on_acquire(mut self, test_on_acquire: bool) -> Self {
+        self.test_on_acquire = test_on_acquire;
+        self
+    }
+
+    async fn tls_config(&self) -> Result<Option<Arc<ClientConfig>>, Error> {
+        if self.client_cert.is_none() && self.ca_cert.is_none() {
+            return Ok(None);
+        }
+
+        let mut root_store = RootCertStore::empty();
+        match &self.ca_cert {
+            Some(ca_path) => {
+                let ca_certs = read_pem_certs(ca_path).await.map_err(|details| {
+                    Error::CypherTlsConfigFailed {
+                        path: ca_path.to_string(),
+                        details,
+                    }
+                })?;
+                for cert in ca_certs {
+                    root_store
+                        .add(&cert)
+                        .map_err(|e| Error::CypherTlsConfigFailed {
+                            path: ca_path.to_string(),
+                            details: e.to_string(),
+                        })?;
+                }
+            }
+            None => root_store.add_server_trust_anchors(
+                webpki_roots::TLS_SERVER_ROOTS.0.iter().map(|anchor| {
+                    OwnedTrustAnchor::from_subject_spki_name_constraints(
+                        anchor.subject,
+                        anchor.spki,
+                        anchor.name_constraints,
+                    )
+                }),
+            ),
+        }
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(root_store);
+
+        let config = match &self.client_cert {
+            Some((cert_path, key_path)) => {
+                let certs = read_pem_certs(cert_path).await.map_err(|details| {
+                    Error::CypherTlsConfigFailed {
+                        path: cert_path.to_string(),
+                        details,
+                    }
+                })?;
+                let key = read_pem_key(key_path).await.map_err(|details| {
+                    Error::CypherTlsConfigFailed {
+                        path: key_path.to_string(),
+                        details,
+                    }
+                })?;
+                builder
+                    .with_single_cert(certs, key)
+                    .map_err(|e| Error::CypherTlsConfigFailed {
+                        path: key_path.to_string(),
+                        details: e.to_string(),
+                    })?
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        Ok(Some(Arc::new(config)))
+    }
+}
+
+/// Reads the file with tokio's file API, so that loading certificates doesn't block the runtime
+/// thread that builds the pool
+async fn read_pem_file(path: &str) -> Result<Vec<u8>, String> {
+    tokio::fs::read(path).await.map_err(|e| e.to_string())
+}
+
+async fn read_pem_certs(path: &str) -> Result<Vec<Certificate>, String> {
+    let pem = read_pem_file(path).await?;
+    let certs = rustls_pemfile::certs(&mut pem.as_slice()).map_err(|e| e.to_string())?;
+
+    if certs.is_empty() {
+        return Err("no PEM-encoded certificates found".to_string());
+    }
+
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+async fn read_pem_key(path: &str) -> Result<PrivateKey, String> {
+    let pem = read_pem_file(path).await?;
+    rustls_pemfile::read_all(&mut pem.as_slice())
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| "no PEM-encoded PKCS#8, RSA, or EC private key found".to_string())
+}
+
+/// A [`mobc`] connection manager for Bolt clients. When the endpoint is configured with TLS
+/// certificates, each new connection negotiates TLS using the endpoint's client configuration
+/// before the Bolt handshake. A pooled connection is checked with a Bolt `RESET` before it is
+/// handed out, when the endpoint is configured to test connections on acquire.
+pub struct BoltManager {
+    addr: SocketAddr,
+    tls: Option<(ServerName, Arc<ClientConfig>)>,
+    version_specifiers: [u32; 4],
+    metadata: Metadata,
+}
+
+impl BoltManager {
+    async fn new(
+        addr: String,
+        tls: Option<(ServerName, Arc<ClientConfig>)>,
+        version_specifiers: [u32; 4],
+        metadata: Metadata,
+    ) -> Result<Self, std::io::Error> {
+        Ok(BoltManager {
+            addr: lookup_host(addr)
+                .await?
+                .next()
+                .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::AddrNotAvailable))?,
+            tls,
+            version_specifiers,
+            metadata,
         })
     }
 }
 
+#[async_trait]
+impl mobc::Manager for BoltManager {
+    type Connection = Client<Compat<BufStream<Stream>>>;
+    type Error = bolt_client::error::Error;
+
+    async fn connect(&self) -> Result<Self::Connection, Self::Error> {
+        let tcp_stream = TcpStream::connect(self.addr)
+            .await
+            .map_err(ConnectionError::from)?;
+
+        let stream = match &self.tls {
+            Some((server_name, config)) => Stream::SecureTcp(Box::new(
+                TlsConnector::from(config.clone())
+                    .connect(server_name.clone(), tcp_stream)
+                    .await
+                    .map_err(ConnectionError::from)?,
+            )),
+            None => Stream::Tcp(tcp_stream),
+        };
+
+        let mut client =
+            Client::new(BufStream::new(stream).compat(), &self.version_specifiers).await?;
+
+        match client.hello(self.metadata.clone()).await? {
+            Message::Success(_) => Ok(client),
+            other => Err(CommunicationError::from(std::io::Error::new(
+                std::io::ErrorKind::ConnectionAborted,
+                format!("server responded with {:?}", other),
+            ))
+            .into()),
+        }
+    }
+
+    async fn check(&self, mut conn: Self::Connection) -> Result<Self::Connection, Self::Error> {
+        Success::try_from(conn.reset().await?)
+            .map_err(bolt_proto::error::Error::from)
+            .map_err(Self::Error::from)?;
+
+        Ok(conn)
+    }
+
+    fn validate(&self, conn: &mut Self::Connection) -> bool {
+        conn.server_state() != ServerState::Defunct
+    }
+}
+
 #[async_trait]
 impl DatabaseEndpoint for CypherEndpoint {
     type PoolType = CypherDatabasePool;
 
     async fn pool(&self) -> Result<Self::PoolType, Error> {
-        let rw_manager = Manager::new(
+        let tls_config = self.tls_config().await?;
+        let rw_tls = tls_config
+            .clone()
+            .map(|config| ServerName::try_from(self.host.as_str()).map(|name| (name, config)))
+            .transpose()
+            .map_err(|e| Error::CypherTlsConfigFailed {
+                path: self.host.to_string(),
+                details: e.to_string(),
+            })?;
+        let ro_tls = tls_config
+            .map(|config| ServerName::try_from(self.read_host.as_str()).map(|name| (name, config)))
+            .transpose()
+            .map_err(|e| Error::CypherTlsConfigFailed {
+                path: self.read_host.to_string(),
+                details: e.to_string(),
+            })?;
+
+        let rw_manager = BoltManager::new(
             self.host.to_string() + ":" + &*self.port.to_string(),
-            None,
+            rw_tls,
             [4, 0, 0, 0],
             Metadata::from_iter(vec![
                 ("user_agent", "warpgrapher/0.2.0"),
@@ -150,9 +481,9 @@ impl DatabaseEndpoint for CypherEndpoint {
         )
         .await?;
 
-        let ro_manager = Manager::new(
+        let ro_manager = BoltManager::new(
             self.read_host.to_string() + ":" + &*self.port.to_string(),
-            None,
+            ro_tls,
             [4, 0, 0, 0],
             Metadata::from_iter(vec![
                 ("user_agent", "warpgrapher/0.2.0"),
@@ -166,10 +497,13 @@ impl DatabaseEndpoint for CypherEndpoint {
         let pool = CypherDatabasePool::new(
             Pool::builder()
                 .max_open(self.pool_size.into())
+                .test_on_check_out(self.test_on_acquire)
                 .build(rw_manager),
             Pool::builder()
                 .max_open(self.pool_size.into())
+                .test_on_check_out(self.test_on_acquire)
                 .build(ro_manager),
+            self.isolation_level,
         );
 
         Ok(pool)
@@ -178,13 +512,42 @@ impl DatabaseEndpoint for CypherEndpoint {
 
 #[derive(Clone)]
 pub struct CypherDatabasePool {
-    rw_pool: Pool<Manager>,
-    ro_pool: Pool<Manager>,
+    rw_pool: Pool<BoltManager>,
+    ro_pool: Pool<BoltManager>,
+    isolation_level: CypherIsolationLevel,
+    statement_log: Option<StatementLog>,
+    profile_log: Option<ProfileLog>,
+    slow_query_threshold: Option<Duration>,
+    database: Option<String>,
+    query_interceptor: Option<Arc<dyn QueryInterceptor>>,
 }
 
 impl CypherDatabasePool {
-    fn new(rw_pool: Pool<Manager>, ro_pool: Pool<Manager>) -> Self {
-        CypherDatabasePool { rw_pool, ro_pool }
+    fn new(
+        rw_pool: Pool<BoltManager>,
+        ro_pool: Pool<BoltManager>,
+        isolation_level: CypherIsolationLevel,
+    ) -> Self {
+        CypherDatabasePool {
+            rw_pool,
+            ro_pool,
+            isolation_level,
+            statement_log: None,
+            profile_log: None,
+            slow_query_threshold: None,
+            database: None,
+            query_interceptor: None,
+        }
+    }
+
+    async fn connection(
+        &self,
+        pool: &Pool<BoltManager>,
+    ) -> Result<Option<Connection<BoltManager>>, Error> {
+        match &self.statement_log {
+            Some(log) if !log.plans() => Ok(None),
+            _ => Ok(Some(pool.get().await?)),
+        }
     }
 }
 
@@ -193,21 +556,388 @@ impl DatabasePool for CypherDatabasePool {
     type TransactionType = CypherTransaction;
 
     async fn read_transaction(&self) -> Result<Self::TransactionType, Error> {
-        Ok(CypherTransaction::new(self.ro_pool.get().await?))
+        Ok(CypherTransaction {
+            client: self.connection(&self.ro_pool).await?,
+            access_mode: CypherAccessMode::Read,
+            isolation_level: self.isolation_level,
+            statement_log: self.statement_log.clone(),
+            profile_log: self.profile_log.clone(),
+            slow_query_threshold: self.slow_query_threshold,
+            database: self.database.clone(),
+            query_interceptor: self.query_interceptor.clone(),
+            begun: false,
+        })
     }
 
     async fn transaction(&self) -> Result<Self::TransactionType, Error> {
-        Ok(CypherTransaction::new(self.rw_pool.get().await?))
+        Ok(CypherTransaction {
+            client: self.connection(&self.rw_pool).await?,
+            access_mode: CypherAccessMode::Write,
+            isolation_level: self.isolation_level,
+            statement_log: self.statement_log.clone(),
+            profile_log: self.profile_log.clone(),
+            slow_query_threshold: self.slow_query_threshold,
+            database: self.database.clone(),
+            query_interceptor: self.query_interceptor.clone(),
+            begun: false,
+        })
+    }
+
+    fn with_statement_log(&self, log: StatementLog) -> Result<Self, Error> {
+        let mut pool = self.clone();
+        pool.statement_log = Some(log);
+        Ok(pool)
+    }
+
+    fn with_profile_log(&self, log: ProfileLog) -> Self {
+        let mut pool = self.clone();
+        pool.profile_log = Some(log);
+        pool
+    }
+
+    fn with_slow_query_threshold(&self, threshold: Option<Duration>) -> Self {
+        let mut pool = self.clone();
+        pool.slow_query_threshold = threshold;
+        pool
+    }
+
+    fn with_database(&self, database: Option<String>) -> Self {
+        let mut pool = self.clone();
+        pool.database = database;
+        pool
+    }
+
+    fn with_query_interceptor(&self, interceptor: Option<Arc<dyn QueryInterceptor>>) -> Self {
+        let mut pool = self.clone();
+        pool.query_interceptor = interceptor;
+        pool
+    }
+
+    async fn close(&self) -> Result<(), Error> {
+        // With no idle connections allowed, the pool closes its idle connections now, and each
+        // connection in use when it is returned
+        self.rw_pool.set_max_idle_conns(0).await;
+        self.ro_pool.set_max_idle_conns(0).await;
+        Ok(())
+    }
+}
+
+/// Access mode hint sent to the database when a transaction begins. Neo4j uses the hint to route
+/// the transaction to an appropriate cluster member, and rejects writes in a transaction begun in
+/// [`Read`] mode.
+///
+/// [`Read`]: #variant.Read
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CypherAccessMode {
+    /// The transaction only reads data. Sent as `mode: "r"`.
+    Read,
+    /// The transaction may read and write data. Sent as `mode: "w"`. This is the default.
+    #[default]
+    Write,
+}
+
+impl CypherAccessMode {
+    fn as_bolt_mode(&self) -> &'static str {
+        match self {
+            CypherAccessMode::Read => "r",
+            CypherAccessMode::Write => "w",
+        }
+    }
+}
+
+/// Isolation level requested for Cypher transactions.
+///
+/// Neo4j runs every transaction at read committed isolation, and the Bolt protocol has no field
+/// for requesting a different level. Only [`ReadCommitted`] is therefore offered, rather than
+/// levels that would silently run at a weaker isolation than asked for. Workloads that need
+/// stronger guarantees, such as guarding a read-then-write sequence, must take explicit write
+/// locks within the transaction, for example by setting a property on the nodes being read
+/// before reading them.
+///
+/// The level is passed along in the transaction's `tx_metadata` under the `isolation_level` key,
+/// where it is visible in the database's query log and transaction listings.
+///
+/// [`ReadCommitted`]: #variant.ReadCommitted
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CypherIsolationLevel {
+    /// Read committed isolation, the level Neo4j provides. This is the default.
+    #[default]
+    ReadCommitted,
+}
+
+impl CypherIsolationLevel {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CypherIsolationLevel::ReadCommitted => "READ_COMMITTED",
+        }
     }
 }
 
 pub struct CypherTransaction {
-    client: Connection<Manager>,
+    client: Option<Connection<BoltManager>>,
+    access_mode: CypherAccessMode,
+    isolation_level: CypherIsolationLevel,
+    statement_log: Option<StatementLog>,
+    profile_log: Option<ProfileLog>,
+    slow_query_threshold: Option<Duration>,
+    database: Option<String>,
+    query_interceptor: Option<Arc<dyn QueryInterceptor>>,
+    begun: bool,
 }
 
 impl CypherTransaction {
-    pub fn new(client: Connection<Manager>) -> CypherTransaction {
-        CypherTransaction { client }
+    pub fn new(client: Connection<BoltManager>) -> CypherTransaction {
+        CypherTransaction {
+            client: Some(client),
+            access_mode: CypherAccessMode::default(),
+            isolation_level: CypherIsolationLevel::default(),
+            statement_log: None,
+            profile_log: None,
+            slow_query_threshold: None,
+            database: None,
+            query_interceptor: None,
+            begun: false,
+        }
+    }
+
+    /// Sets the access mode hint sent when the transaction begins.
+    pub fn with_access_mode(mut self, access_mode: CypherAccessMode) -> Self {
+        self.access_mode = access_mode;
+        self
+    }
+
+    /// Sets the isolation level requested when the transaction begins. A level other than
+    /// [`CypherIsolationLevel::ReadCommitted`] makes [`begin`] fail.
+    ///
+    /// [`CypherIsolationLevel::ReadCommitted`]: ./enum.CypherIsolationLevel.html#variant.ReadCommitted
+    /// [`begin`]: ../trait.Transaction.html#tymethod.begin
+    pub fn with_isolation_level(mut self, isolation_level: CypherIsolationLevel) -> Self {
+        self.isolation_level = isolation_level;
+        self
+    }
+
+    /// Sets the threshold above which queries run in this transaction are logged as slow.
+    pub fn with_slow_query_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
+    /// Sets the database that the transaction, or its queries if it is never begun, run against.
+    /// `None` uses the server's default database.
+    pub fn with_database(mut self, database: Option<String>) -> Self {
+        self.database = database;
+        self
+    }
+
+    /// Sets the interceptor that may rewrite each query before it is run.
+    pub fn with_query_interceptor(
+        mut self,
+        interceptor: Option<Arc<dyn QueryInterceptor>>,
+    ) -> Self {
+        self.query_interceptor = interceptor;
+        self
+    }
+
+    /// Runs a query and pulls all of its result records. The query and its parameters are first
+    /// passed through the [`QueryInterceptor`], if one is set. If the transaction is explaining
+    /// rather than executing, the query is recorded in the statement log, along with its query
+    /// plan if plans were requested, and no records are returned. `operation` names the
+    /// [`Transaction`] method issuing the query, for slow query logging.
+    ///
+    /// [`QueryInterceptor`]: ../trait.QueryInterceptor.html
+    /// [`Transaction`]: ../trait.Transaction.html
+    async fn run_query(
+        &mut self,
+        operation: &str,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Record>, Error> {
+        let (query, params) = match &self.query_interceptor {
+            Some(interceptor) => interceptor.intercept(operation, query, params)?,
+            None => (query, params),
+        };
+        let params = Params::from(params);
+        let run_meta = self.run_metadata();
+
+        if let Some(log) = self.statement_log.clone() {
+            if log.plans() {
+                self.client
+                    .as_mut()
+                    .ok_or(Error::DatabaseNotFound)?
+                    .run(format!("EXPLAIN {}", query), Some(params), run_meta)
+                    .await?;
+                let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
+                let (_records, response) = self
+                    .client
+                    .as_mut()
+                    .ok_or(Error::DatabaseNotFound)?
+                    .pull(Some(pull_meta))
+                    .await?;
+                match response {
+                    Message::Success(success) => log.record(
+                        query
+                            + &*success
+                                .metadata()
+                                .get("plan")
+                                .map(|plan| format!("\n{:#?}", plan))
+                                .unwrap_or_default(),
+                    ),
+                    message => return Err(Error::CypherQueryFailed { message }),
+                }
+            } else {
+                log.record(query);
+            }
+
+            return Ok(Vec::new());
+        }
+
+        let start = Instant::now();
+        let profile_query;
+        let run_query = match &self.profile_log {
+            Some(_) => {
+                profile_query = format!("PROFILE {}", query);
+                &profile_query
+            }
+            None => &query,
+        };
+        self.client
+            .as_mut()
+            .ok_or(Error::DatabaseNotFound)?
+            .run(run_query.as_str(), Some(params), run_meta)
+            .await?;
+
+        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
+        let (records, response) = self
+            .client
+            .as_mut()
+            .ok_or(Error::DatabaseNotFound)?
+            .pull(Some(pull_meta))
+            .await?;
+        log_slow_query(self.slow_query_threshold, operation, start, &query);
+        match response {
+            Message::Success(success) => {
+                if let Some(log) = &self.profile_log {
+                    let (db_hits, rows) = success
+                        .metadata()
+                        .get("profile")
+                        .map(profile_totals)
+                        .unwrap_or_default();
+                    log.record(QueryProfile::new(
+                        operation.to_string(),
+                        query,
+                        db_hits,
+                        rows,
+                    ));
+                }
+                Ok(records)
+            }
+            message => Err(Error::CypherQueryFailed { message }),
+        }
+    }
+
+    fn begin_metadata(&self) -> Metadata {
+        let tx_metadata: HashMap<String, bolt_proto::Value> = HashMap::from_iter(vec![(
+            "isolation_level".to_string(),
+            bolt_proto::Value::from(self.isolation_level.as_str()),
+        )]);
+
+        let mut metadata = vec![
+            (
+                "mode",
+                bolt_proto::Value::from(self.access_mode.as_bolt_mode()),
+            ),
+            ("tx_metadata", bolt_proto::Value::from(tx_metadata)),
+        ];
+        if let Some(database) = &self.database {
+            metadata.push(("db", bolt_proto::Value::from(database.as_str())));
+        }
+
+        Metadata::from_iter(metadata)
+    }
+
+    /// Returns the metadata for a RUN message. Queries run outside of an explicit transaction
+    /// name the database themselves. Queries within a transaction use the database named when
+    /// the transaction began.
+    fn run_metadata(&self) -> Option<Metadata> {
+        let metadata = self.run_metadata_map();
+        if metadata.is_empty() {
+            None
+        } else {
+            Some(Metadata::from_iter(metadata))
+        }
+    }
+
+    /// Returns the entries of the metadata for a RUN message, as described for `run_metadata`
+    fn run_metadata_map(&self) -> HashMap<String, bolt_proto::Value> {
+        match &self.database {
+            Some(database) if !self.begun => HashMap::from_iter(vec![(
+                "db".to_string(),
+                bolt_proto::Value::from(database.as_str()),
+            )]),
+            _ => HashMap::new(),
+        }
+    }
+
+    /// Runs several queries in one round trip to the server, by pipelining a RUN and a PULL
+    /// message for each query, and returns the records of each query, in order. Each query and
+    /// its parameters are first passed through the [`QueryInterceptor`], if one is set.
+    ///
+    /// [`QueryInterceptor`]: ../trait.QueryInterceptor.html
+    async fn run_pipelined(
+        &mut self,
+        statements: Vec<(String, HashMap<String, Value>)>,
+    ) -> Result<Vec<Vec<Record>>, Error> {
+        let run_meta = self.run_metadata_map();
+        let version = self
+            .client
+            .as_ref()
+            .ok_or(Error::DatabaseNotFound)?
+            .version();
+
+        // the query text is only kept when it might be logged as slow
+        let count = statements.len();
+        let mut queries = Vec::new();
+        let mut messages = Vec::with_capacity(statements.len() * 2);
+        for (query, params) in statements {
+            let (query, params) = match &self.query_interceptor {
+                Some(interceptor) => interceptor.intercept("execute_batch", query, params)?,
+                None => (query, params),
+            };
+            let params: HashMap<String, bolt_proto::Value> =
+                params.into_iter().map(|(k, v)| (k, v.into())).collect();
+            if self.slow_query_threshold.is_some() {
+                queries.push(query.clone());
+            }
+            messages.push(match version {
+                V1_0 | V2_0 => Message::Run(Run::new(query, params)),
+                _ => {
+                    Message::RunWithMetadata(RunWithMetadata::new(query, params, run_meta.clone()))
+                }
+            });
+            messages.push(match version {
+                V1_0 | V2_0 | V3_0 => Message::PullAll,
+                _ => Message::Pull(Pull::new(HashMap::from_iter(vec![(
+                    "n".to_string(),
+                    bolt_proto::Value::from(-1i8),
+                )]))),
+            });
+        }
+
+        let start = Instant::now();
+        let responses = self
+            .client
+            .as_mut()
+            .ok_or(Error::DatabaseNotFound)?
+            .pipeline(messages)
+            .await?;
+        log_slow_query(
+            self.slow_query_threshold,
+            "execute_batch",
+            start,
+            &queries.join(";\n"),
+        );
+
+        pipelined_records(responses, count)
     }
 
     fn add_sort_to_query(
@@ -246,11 +976,27 @@ impl CypherTransaction {
 #[async_trait]
 impl Transaction for CypherTransaction {
     async fn begin(&mut self) -> Result<(), Error> {
-        debug!("CypherTransaction::begin called");
+        debug!(
+            "CypherTransaction::begin called -- access_mode: {:?}, isolation_level: {:?}",
+            self.access_mode, self.isolation_level
+        );
 
-        let response = self.client.begin(None).await;
+        if self.statement_log.is_some() {
+            return Ok(());
+        }
+
+        let metadata = self.begin_metadata();
+        let response = self
+            .client
+            .as_mut()
+            .ok_or(Error::DatabaseNotFound)?
+            .begin(Some(metadata))
+            .await;
         match response {
-            Ok(Message::Success(_)) => Ok(()),
+            Ok(Message::Success(_)) => {
+                self.begun = true;
+                Ok(())
+            }
             Ok(message) => Err(Error::CypherQueryFailed { message }),
             Err(e) => Err(Error::from(e)),
         }
@@ -268,29 +1014,106 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        let p = Params::from(params);
-        self.client.run(query, Some(p), None).await?;
+        let records = self.run_query("execute_query", query, params).await?;
 
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
+        Ok(QueryResult::Cypher(records))
+    }
+
+    #[tracing::instrument(name = "wg-cypher-execute-batch", skip(self, statements))]
+    async fn execute_batch<RequestCtx: RequestContext>(
+        &mut self,
+        statements: Vec<(String, HashMap<String, Value>)>,
+    ) -> Result<Vec<QueryResult>, Error> {
+        trace!(
+            "CypherTransaction::execute_batch called -- statements: {:#?}",
+            statements
+        );
+
+        if statements.is_empty() {
+            return Ok(Vec::new());
         }
 
-        Ok(QueryResult::Cypher(records))
+        // queries that are explained or profiled are run one at a time, to record each of them
+        if self.statement_log.is_some() || self.profile_log.is_some() {
+            let mut results = Vec::with_capacity(statements.len());
+            for (query, params) in statements {
+                let records = self.run_query("execute_batch", query, params).await?;
+                results.push(QueryResult::Cypher(records));
+            }
+            return Ok(results);
+        }
+
+        Ok(self
+            .run_pipelined(statements)
+            .await?
+            .into_iter()
+            .map(QueryResult::Cypher)
+            .collect())
+    }
+
+    async fn execute_write(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        trace!(
+            "CypherTransaction::execute_write called -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let records = self.run_query("execute_write", query, params).await?;
+
+        records
+            .iter()
+            .flat_map(|r| r.fields().iter().cloned())
+            .map(write_result_value)
+            .collect()
+    }
+
+    async fn execute_query_values(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        trace!(
+            "CypherTransaction::execute_query_values called -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let records = self
+            .run_query("execute_query_values", query, params)
+            .await?;
+
+        records
+            .into_iter()
+            .map(|r| {
+                let mut fields = r
+                    .fields()
+                    .iter()
+                    .cloned()
+                    .map(query_result_value)
+                    .collect::<Result<Vec<Value>, Error>>()?;
+                if fields.len() == 1 {
+                    Ok(fields.remove(0))
+                } else {
+                    Ok(Value::Array(fields))
+                }
+            })
+            .collect()
     }
 
     #[tracing::instrument(
         name = "wg-cypher-create-node",
-        skip(self, node_var, props, options, _info, _sg)
+        skip(self, node_var, props, options, info, _sg)
     )]
     async fn create_node<RequestCtx: RequestContext>(
         &mut self,
         node_var: &NodeQueryVar,
         mut props: HashMap<String, Value>,
         options: Options,
-        _info: &Info,
+        info: &Info,
         _sg: &mut SuffixGenerator,
     ) -> Result<Node<RequestCtx>, Error> {
         trace!(
@@ -313,8 +1136,8 @@ impl Transaction for CypherTransaction {
             + "RETURN n\n";
         query = CypherTransaction::add_sort_to_query(query, options, "n", None);
 
-        let mut params: HashMap<&str, Value> = HashMap::new();
-        params.insert("props", props.into());
+        let mut params = HashMap::new();
+        params.insert("props".to_string(), props.into());
 
         trace!(
             "CypherTransaction::create_node -- query: {}, params: {:#?}",
@@ -322,17 +1145,9 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        let p = Params::from(params);
-        self.client.run(query, Some(p), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (mut records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let mut records = self.run_query("create_node", query, params).await?;
 
-        records.pop().ok_or(Error::ResponseSetNotFound)?.try_into()
+        (records.pop().ok_or(Error::ResponseSetNotFound)?, info).try_into()
     }
 
     #[tracing::instrument(
@@ -408,16 +1223,7 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        let p = Params::from(params);
-        self.client.run(query, Some(p), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        trace!("Reached record pull");
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("create_rels", query, params).await?;
 
         trace!("Rel Records: {:#?}", records);
         records
@@ -506,7 +1312,7 @@ impl Transaction for CypherTransaction {
 
         rel_query_fragments.into_iter().for_each(|rqf| {
             match_fragment.push_str(rqf.match_fragment());
-            if !where_fragment.is_empty() {
+            if !where_fragment.is_empty() && !rqf.where_fragment().is_empty() {
                 where_fragment.push_str(" AND ");
             }
             where_fragment.push_str(rqf.where_fragment());
@@ -520,16 +1326,16 @@ impl Transaction for CypherTransaction {
         Ok(qf)
     }
 
-    #[tracing::instrument(level = "info", name = "wg-cypher-load-nodes", skip(self, _info))]
+    #[tracing::instrument(level = "info", name = "wg-cypher-load-nodes", skip(self, info))]
     async fn load_nodes<RequestCtx: RequestContext>(
         &mut self,
         keys: &[NodeLoaderKey],
-        _info: &Info,
+        info: &Info,
     ) -> Result<Vec<Node<RequestCtx>>, Error> {
         trace!("CypherTransaction::load_nodes called -- keys: {:#?}", keys);
 
         let mut query = String::new();
-        let mut params: HashMap<String, Vec<String>> = HashMap::new();
+        let mut params = HashMap::new();
 
         query.push_str("MATCH (n)\n");
         query.push_str("WHERE n.id IN $id_list\n");
@@ -537,7 +1343,11 @@ impl Transaction for CypherTransaction {
 
         params.insert(
             "id_list".to_string(),
-            keys.iter().map(|nlk| nlk.id().to_string()).collect(),
+            Value::Array(
+                keys.iter()
+                    .map(|nlk| Value::String(nlk.id().to_string()))
+                    .collect(),
+            ),
         );
 
         trace!(
@@ -545,14 +1355,7 @@ impl Transaction for CypherTransaction {
             query,
             params
         );
-        self.client.run(query, Some(params.into()), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("load_nodes", query, params).await?;
 
         trace!(
             "CypherTransaction::load_nodes -- node records: {:#?}",
@@ -561,7 +1364,7 @@ impl Transaction for CypherTransaction {
 
         records
             .into_iter()
-            .map(|n| n.try_into())
+            .map(|n| (n, info).try_into())
             .collect::<Result<Vec<Node<RequestCtx>>, Error>>()
     }
 
@@ -593,6 +1396,8 @@ impl Transaction for CypherTransaction {
         let mut query = query_fragment.match_fragment().to_string()
             + &*where_clause
             + "RETURN "
+            // Nodes matched through more than one relationship are always returned once, so the
+            // distinct option needs no clause of its own
             + "DISTINCT "
             + node_var.name()
             + "\n";
@@ -600,25 +1405,90 @@ impl Transaction for CypherTransaction {
         let params = query_fragment.params();
 
         trace!(
-            "CypherTransaction::read_nodes -- query: {}, params: {:#?}",
+            "CypherTransaction::read_nodes -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+        let records = self.run_query("read_nodes", query, params).await?;
+
+        trace!("Rel Records: {:#?}", records);
+
+        records
+            .into_iter()
+            .map(|n| (n, info).try_into())
+            .collect::<Result<Vec<Node<RequestCtx>>, Error>>()
+    }
+
+    #[tracing::instrument(name = "wg-cypher-node-exists", skip(self, node_var, query_fragment))]
+    async fn node_exists(
+        &mut self,
+        node_var: &NodeQueryVar,
+        query_fragment: QueryFragment,
+    ) -> Result<bool, Error> {
+        trace!(
+            "CypherTransaction::node_exists called -- node_var: {:#?}, query_fragment: {:#?}",
+            node_var,
+            query_fragment
+        );
+
+        let where_fragment = query_fragment.where_fragment().to_string();
+        let where_clause = if !where_fragment.is_empty() {
+            "WHERE ".to_string() + &*where_fragment + "\n"
+        } else {
+            String::new()
+        };
+
+        // Stop at the first match and count it, so that no node properties are returned
+        let query = query_fragment.match_fragment().to_string()
+            + &*where_clause
+            + "WITH "
+            + node_var.name()
+            + " LIMIT 1\n"
+            + "RETURN count("
+            + node_var.name()
+            + ") > 0 as exists\n";
+        let params = query_fragment.params();
+
+        trace!(
+            "CypherTransaction::node_exists -- query: {}, params: {:#?}",
             query,
             params
         );
-        self.client.run(query, Some(params.into()), None).await?;
 
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
+        let records = self.run_query("node_exists", query, params).await?;
+
+        match records.into_iter().next() {
+            Some(record) => record.fields()[0]
+                .clone()
+                .try_into()
+                .map_err(|e: ConversionError| e.into()),
+            // Explaining records the query without running it
+            None if self.statement_log.is_some() => Ok(false),
+            None => Err(Error::ResponseSetNotFound),
         }
+    }
 
-        trace!("Rel Records: {:#?}", records);
+    async fn read_computed_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        query: String,
+        src_id: Value,
+        info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        trace!(
+            "CypherTransaction::read_computed_nodes called -- query: {}, src_id: {:#?}, info.name: {}",
+            query,
+            src_id,
+            info.name()
+        );
+
+        let mut params = HashMap::new();
+        params.insert("src_id".to_string(), src_id);
+        let records = self.run_query("read_computed_nodes", query, params).await?;
 
         records
             .into_iter()
-            .map(|n| n.try_into())
-            .collect::<Result<Vec<Node<RequestCtx>>, Error>>()
+            .map(|n| Node::try_from((n, info)))
+            .collect()
     }
 
     fn rel_read_by_ids_fragment<RequestCtx: RequestContext>(
@@ -676,18 +1546,21 @@ impl Transaction for CypherTransaction {
             match_fragment.push_str(src_fragment.match_fragment());
             where_fragment.push_str(src_fragment.where_fragment());
             params.extend(src_fragment.params());
-
-            if dst_fragment_opt.is_some() || !props.is_empty() {
-                where_fragment.push_str(" AND ");
-            }
         }
 
         if let Some(dst_fragment) = dst_fragment_opt {
             match_fragment.push_str(dst_fragment.match_fragment());
+            if !where_fragment.is_empty() && !dst_fragment.where_fragment().is_empty() {
+                where_fragment.push_str(" AND ");
+            }
             where_fragment.push_str(dst_fragment.where_fragment());
             params.extend(dst_fragment.params());
         }
 
+        if !where_fragment.is_empty() && !props.is_empty() {
+            where_fragment.push_str(" AND ");
+        }
+
         match_fragment.push_str(
             &("MATCH (".to_string()
                 + rel_var.src().name()
@@ -697,7 +1570,7 @@ impl Transaction for CypherTransaction {
                 + rel_var.name()
                 + ":"
                 + rel_var.label()
-                + "]->("
+                + if rel_var.symmetric() { "]-(" } else { "]->(" }
                 + rel_var.dst().name()
                 + ")\n"),
         );
@@ -753,10 +1626,21 @@ impl Transaction for CypherTransaction {
             if i > 0 {
                 query.push_str("UNION ALL ");
             }
-            query.push_str(&("MATCH (src)-[rel:".to_string() + rlk.rel_name() + "]->(dst)\n"));
+            query.push_str(
+                &("MATCH (src)-[rel:".to_string()
+                    + rlk.rel_name()
+                    + if rlk.symmetric() {
+                        "]-(dst)\n"
+                    } else {
+                        "]->(dst)\n"
+                    }),
+            );
             query.push_str(&("WHERE src.id = $id".to_string() + suffix.as_str() + "\n"));
             query.push_str("RETURN src {.id} as src, rel, dst {.id} as dst\n");
-            params.insert("id".to_string() + suffix.as_str(), rlk.src_id());
+            params.insert(
+                "id".to_string() + suffix.as_str(),
+                Value::String(rlk.src_id().to_string()),
+            );
         }
 
         trace!(
@@ -765,14 +1649,7 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        self.client.run(query, Some(params.into()), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("load_rels", query, params).await?;
 
         trace!("Rel Records: {:#?}", records);
 
@@ -782,6 +1659,192 @@ impl Transaction for CypherTransaction {
             .collect::<Result<Vec<Rel<RequestCtx>>, Error>>()
     }
 
+    async fn count_rels(&mut self, keys: &[RelLoaderKey]) -> Result<Vec<i64>, Error> {
+        trace!("CypherTransaction::count_rels called -- keys: {:#?}", keys);
+
+        let mut sg = SuffixGenerator::new();
+        let mut query = String::new();
+        let mut params = HashMap::new();
+
+        // Each key's count is returned with the key's index, so that it can be matched to its key
+        for (i, rlk) in keys.iter().enumerate() {
+            let suffix = sg.suffix();
+            if i > 0 {
+                query.push_str("UNION ALL ");
+            }
+            query.push_str(
+                &("MATCH (src)\nWHERE src.id = $id".to_string() + suffix.as_str() + "\n"),
+            );
+            query.push_str(
+                &("OPTIONAL MATCH (src)-[rel:".to_string()
+                    + rlk.rel_name()
+                    + if rlk.symmetric() { "]-()\n" } else { "]->()\n" }),
+            );
+            query.push_str(
+                &("RETURN ".to_string() + &*i.to_string() + " as key, count(rel) as count\n"),
+            );
+            params.insert(
+                "id".to_string() + suffix.as_str(),
+                Value::String(rlk.src_id().to_string()),
+            );
+        }
+
+        trace!(
+            "CypherTransaction::count_rels -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let records = self.run_query("count_rels", query, params).await?;
+
+        // A source node that doesn't exist returns no record, and so has a count of zero
+        let mut counts = vec![0; keys.len()];
+        for record in records {
+            let key: i64 = record.fields()[0].clone().try_into()?;
+            let count: i64 = record.fields()[1].clone().try_into()?;
+            if let Some(c) = usize::try_from(key).ok().and_then(|k| counts.get_mut(k)) {
+                *c = count;
+            }
+        }
+
+        Ok(counts)
+    }
+
+    #[tracing::instrument(
+        name = "wg-cypher-count-matching-rels",
+        skip(self, query_fragment, rel_var)
+    )]
+    async fn count_matching_rels(
+        &mut self,
+        query_fragment: QueryFragment,
+        rel_var: &RelQueryVar,
+    ) -> Result<i64, Error> {
+        trace!(
+            "CypherTransaction::count_matching_rels called -- query_fragment: {:#?}, rel_var: {:#?}",
+            query_fragment,
+            rel_var
+        );
+
+        let where_fragment = query_fragment.where_fragment().to_string();
+        let where_clause = if !where_fragment.is_empty() {
+            "WHERE ".to_string() + &*where_fragment + "\n"
+        } else {
+            String::new()
+        };
+
+        let query = query_fragment.match_fragment().to_string()
+            + &*where_clause
+            + "RETURN count("
+            + rel_var.name()
+            + ") as count\n";
+        let params = query_fragment.params();
+
+        trace!(
+            "CypherTransaction::count_matching_rels -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let records = self.run_query("count_matching_rels", query, params).await?;
+
+        match records.into_iter().next() {
+            Some(record) => record.fields()[0]
+                .clone()
+                .try_into()
+                .map_err(|e: ConversionError| e.into()),
+            // Explaining records the query without running it
+            None if self.statement_log.is_some() => Ok(0),
+            None => Err(Error::ResponseSetNotFound),
+        }
+    }
+
+    async fn aggregate_rels(
+        &mut self,
+        keys: &[AggregateLoaderKey],
+    ) -> Result<Vec<HashMap<String, Value>>, Error> {
+        trace!(
+            "CypherTransaction::aggregate_rels called -- keys: {:#?}",
+            keys
+        );
+
+        let mut sg = SuffixGenerator::new();
+        let mut query = String::new();
+        let mut params = HashMap::new();
+
+        // Each key's aggregates are returned with the key's index, so that they can be matched to
+        // the key, and as a map, so that keys aggregating different properties share the columns
+        // required by the union
+        for (i, alk) in keys.iter().enumerate() {
+            let rlk = alk.rel_key();
+            let suffix = sg.suffix();
+            if i > 0 {
+                query.push_str("UNION ALL ");
+            }
+            query.push_str(
+                &("MATCH (src)\nWHERE src.id = $id".to_string() + suffix.as_str() + "\n"),
+            );
+            query.push_str(
+                &("OPTIONAL MATCH (src)-[rel:".to_string()
+                    + rlk.rel_name()
+                    + if rlk.symmetric() { "]-()\n" } else { "]->()\n" }),
+            );
+            query.push_str(
+                &("RETURN ".to_string()
+                    + &*i.to_string()
+                    + " as key, count(rel) as count, {"
+                    + &*alk
+                        .props()
+                        .iter()
+                        .map(|p| {
+                            let p = "`".to_string() + p.as_str() + "`";
+                            p.clone()
+                                + ": {sum: sum(rel."
+                                + &*p
+                                + "), avg: avg(rel."
+                                + &*p
+                                + "), min: min(rel."
+                                + &*p
+                                + "), max: max(rel."
+                                + &*p
+                                + ")}"
+                        })
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                    + "} as aggregates\n"),
+            );
+            params.insert(
+                "id".to_string() + suffix.as_str(),
+                Value::String(rlk.src_id().to_string()),
+            );
+        }
+
+        trace!(
+            "CypherTransaction::aggregate_rels -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let records = self.run_query("aggregate_rels", query, params).await?;
+
+        // A source node that doesn't exist returns no record, and so has no relationships
+        let mut aggregates = vec![HashMap::new(); keys.len()];
+        for record in records {
+            let key: i64 = record.fields()[0].clone().try_into()?;
+            let count: i64 = record.fields()[1].clone().try_into()?;
+            if let Some(a) = usize::try_from(key)
+                .ok()
+                .and_then(|k| aggregates.get_mut(k))
+            {
+                if let Value::Map(props) = Value::try_from(record.fields()[2].clone())? {
+                    *a = props;
+                }
+                a.insert("count".to_string(), Value::Int64(count));
+            }
+        }
+
+        Ok(aggregates)
+    }
+
     #[tracing::instrument(
         name = "wg-cypher-read-rels",
         skip(self, query_fragment, rel_var, options)
@@ -814,12 +1877,14 @@ impl Transaction for CypherTransaction {
             + rel_var.dst.name()
             + " {.id} "
             + " as dst\n";
+        let (skip, limit) = (options.skip(), options.limit());
         query = CypherTransaction::add_sort_to_query(
             query,
             options,
             rel_var.name(),
             Some(rel_var.dst.name()),
         );
+        query += &page_clause(skip, limit);
         let params = query_fragment.params();
 
         trace!(
@@ -827,14 +1892,7 @@ impl Transaction for CypherTransaction {
             query,
             params
         );
-        self.client.run(query, Some(params.into()), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("read_rels", query, params).await?;
 
         trace!("Rel Records: {:#?}", records);
 
@@ -872,11 +1930,18 @@ impl Transaction for CypherTransaction {
             String::new()
         };
 
+        // properties set to null are removed, rather than set
+        let (cleared, props): (HashMap<String, Value>, HashMap<String, Value>) = props
+            .into_iter()
+            .partition(|(_, v)| matches!(v, Value::Null));
+        let remove_clause = remove_clause(node_var.name(), &cleared);
+
         let mut query = query_fragment.match_fragment().to_string()
             + &*where_clause
             + "SET "
             + node_var.name()
             + " += $props\n"
+            + &*remove_clause
             + "RETURN "
             + node_var.name()
             + "\n";
@@ -890,19 +1955,11 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        let p = Params::from(params);
-        self.client.run(query, Some(p), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("update_nodes", query, params).await?;
 
         records
             .into_iter()
-            .map(|n| n.try_into())
+            .map(|n| (n, info).try_into())
             .collect::<Result<Vec<Node<RequestCtx>>, Error>>()
     }
 
@@ -915,11 +1972,12 @@ impl Transaction for CypherTransaction {
         query_fragment: QueryFragment,
         rel_var: &RelQueryVar,
         props: HashMap<String, Value>,
+        ops: PropOperations,
         options: Options,
         _sg: &mut SuffixGenerator,
     ) -> Result<Vec<Rel<RequestCtx>>, Error> {
-        trace!("CypherTransaction::update_rels called -- query_fragment: {:#?}, rel_var: {:#?}, props: {:#?}, options: {:#?}",
-        query_fragment, rel_var, props, options);
+        trace!("CypherTransaction::update_rels called -- query_fragment: {:#?}, rel_var: {:#?}, props: {:#?}, ops: {:#?}, options: {:#?}",
+        query_fragment, rel_var, props, ops, options);
 
         let where_fragment = query_fragment.where_fragment().to_string();
         let where_clause = if !where_fragment.is_empty() {
@@ -933,6 +1991,7 @@ impl Transaction for CypherTransaction {
             + "SET "
             + rel_var.name()
             + " += $props\n"
+            + &*operations_clause(rel_var.name(), &ops)
             + "RETURN "
             + rel_var.src.name()
             + " {.id} "
@@ -951,6 +2010,10 @@ impl Transaction for CypherTransaction {
 
         let mut params = query_fragment.params();
         params.insert("props".to_string(), props.into());
+        if !ops.is_empty() {
+            params.insert("increment".to_string(), ops.increment().clone().into());
+            params.insert("push".to_string(), ops.push().clone().into());
+        }
 
         trace!(
             "CypherTransaction::update_rels -- q: {}, params: {:#?}",
@@ -958,15 +2021,7 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        let p = Params::from(params);
-        self.client.run(query, Some(p), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("update_rels", query, params).await?;
 
         trace!("Rel Records: {:#?}", records);
         records
@@ -1012,14 +2067,7 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        self.client.run(query, Some(params.into()), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("delete_nodes", query, params).await?;
 
         records
             .into_iter()
@@ -1068,14 +2116,7 @@ impl Transaction for CypherTransaction {
             params
         );
 
-        self.client.run(query, Some(params.into()), None).await?;
-
-        let pull_meta = Metadata::from_iter(vec![("n", -1i8)]);
-        let (records, response) = self.client.pull(Some(pull_meta)).await?;
-        match response {
-            Message::Success(_) => (),
-            message => return Err(Error::CypherQueryFailed { message }),
-        }
+        let records = self.run_query("delete_rels", query, params).await?;
 
         records
             .into_iter()
@@ -1090,13 +2131,33 @@ impl Transaction for CypherTransaction {
     #[tracing::instrument(name = "wg-cypher-commit-tx", skip(self))]
     async fn commit(&mut self) -> Result<(), Error> {
         debug!("transaction::commit called");
-        Ok(self.client.commit().await.map(|_| ())?)
+        if self.statement_log.is_some() {
+            return Ok(());
+        }
+        self.begun = false;
+        Ok(self
+            .client
+            .as_mut()
+            .ok_or(Error::DatabaseNotFound)?
+            .commit()
+            .await
+            .map(|_| ())?)
     }
 
     #[tracing::instrument(name = "wg-cypher-rollback-tx", skip(self))]
     async fn rollback(&mut self) -> Result<(), Error> {
         debug!("transaction::rollback called");
-        Ok(self.client.rollback().await.map(|_| ())?)
+        if self.statement_log.is_some() {
+            return Ok(());
+        }
+        self.begun = false;
+        Ok(self
+            .client
+            .as_mut()
+            .ok_or(Error::DatabaseNotFound)?
+            .rollback()
+            .await
+            .map(|_| ())?)
     }
 }
 
@@ -1108,7 +2169,7 @@ impl TryFrom<bolt_proto::Value> for Value {
             bolt_proto::Value::Boolean(_) => Ok(Value::Bool(bv.try_into()?)),
             bolt_proto::Value::Integer(_) => Ok(Value::Int64(bv.try_into()?)),
             bolt_proto::Value::Float(_) => Ok(Value::Float64(bv.try_into()?)),
-            bolt_proto::Value::Bytes(_) => Err(ConversionError::FromValue(bv)),
+            bolt_proto::Value::Bytes(b) => Ok(Value::Bytes(b)),
             bolt_proto::Value::List(_) => Ok(Value::Array(bv.try_into()?)),
             bolt_proto::Value::Map(_) => Ok(Value::Map(bv.try_into()?)),
             bolt_proto::Value::Null => Ok(Value::Null),
@@ -1123,7 +2184,9 @@ impl TryFrom<bolt_proto::Value> for Value {
             bolt_proto::Value::DateTimeZoned(_) => Err(ConversionError::FromValue(bv)),
             bolt_proto::Value::LocalTime(_) => Err(ConversionError::FromValue(bv)),
             bolt_proto::Value::LocalDateTime(_) => Err(ConversionError::FromValue(bv)),
-            bolt_proto::Value::Duration(_) => Err(ConversionError::FromValue(bv)),
+            bolt_proto::Value::Duration(d) => duration_from_bolt(&d)
+                .map(Value::Duration)
+                .ok_or(ConversionError::FromValue(bolt_proto::Value::Duration(d))),
             bolt_proto::Value::Point2D(_) => Err(ConversionError::FromValue(bv)),
             bolt_proto::Value::Point3D(_) => Err(ConversionError::FromValue(bv)),
         }
@@ -1135,6 +2198,10 @@ impl From<Value> for bolt_proto::Value {
         match v {
             Value::Array(a) => a.into(),
             Value::Bool(b) => b.into(),
+            Value::Bytes(b) => bolt_proto::Value::Bytes(b),
+            // Durations are stored as milliseconds rather than as Cypher durations, because
+            // Cypher doesn't order durations, so they couldn't be filtered with GT or LT.
+            Value::Duration(d) => (d.as_millis() as i64).into(),
             Value::Float64(f) => f.into(),
             Value::Int64(i) => i.into(),
             Value::Map(m) => m.into(),
@@ -1148,27 +2215,71 @@ impl From<Value> for bolt_proto::Value {
     }
 }
 
-impl<RequestCtx: RequestContext> TryFrom<bolt_proto::value::Node> for Node<RequestCtx> {
+/// Returns the `SKIP` and `LIMIT` clauses selecting a page of query results, or an empty string
+/// if every result is selected
+fn page_clause(skip: usize, limit: Option<usize>) -> String {
+    let mut clause = String::new();
+    if skip > 0 {
+        clause += &format!("SKIP {}\n", skip);
+    }
+    if let Some(limit) = limit {
+        clause += &format!("LIMIT {}\n", limit);
+    }
+    clause
+}
+
+/// Converts a Cypher duration, such as one returned by a custom query, into a
+/// `std::time::Duration`. Durations that are negative, or that have months, which have no fixed
+/// length, can't be converted.
+fn duration_from_bolt(d: &bolt_proto::value::Duration) -> Option<std::time::Duration> {
+    if d.months() != 0 {
+        return None;
+    }
+    let nanos = (i128::from(d.days()) * 86_400 + i128::from(d.seconds())) * 1_000_000_000
+        + i128::from(d.nanos());
+    u128::try_from(nanos).ok().and_then(|n| {
+        Some(std::time::Duration::new(
+            u64::try_from(n / 1_000_000_000).ok()?,
+            (n % 1_000_000_000) as u32,
+        ))
+    })
+}
+
+impl<RequestCtx: RequestContext> TryFrom<(bolt_proto::value::Node, &Info)> for Node<RequestCtx> {
     type Error = crate::Error;
 
-    fn try_from(value: bolt_proto::value::Node) -> Result<Self, Error> {
-        let type_name = &value.labels()[0];
-        let properties: &HashMap<String, bolt_proto::Value> = value.properties();
+    fn try_from(value: (bolt_proto::value::Node, &Info)) -> Result<Self, Error> {
+        let type_name = &value.0.labels()[0];
+        let nt = value.1.type_def_by_name(type_name)?;
+        let properties: &HashMap<String, bolt_proto::Value> = value.0.properties();
         let props_value = Value::try_from(properties.clone())?;
-        let props = HashMap::<String, Value>::try_from(props_value)?;
+        let props = HashMap::<String, Value>::try_from(props_value)?
+            .into_iter()
+            .filter(|(k, _)| !nt.is_unique_shadow(k))
+            .map(|(k, v)| {
+                if nt
+                    .db_property(&k)
+                    .is_ok_and(|p| p.type_name() == "Duration")
+                {
+                    (graphql_name(nt, &k), v.millis_to_duration())
+                } else {
+                    (graphql_name(nt, &k), v)
+                }
+            })
+            .collect();
         Ok(Node::new(type_name.to_string(), props))
     }
 }
 
-impl<RequestCtx: RequestContext> TryFrom<Record> for Node<RequestCtx> {
+impl<RequestCtx: RequestContext> TryFrom<(Record, &Info)> for Node<RequestCtx> {
     type Error = crate::Error;
 
-    fn try_from(value: Record) -> Result<Self, Error> {
-        if let bolt_proto::Value::Node(n) = value.fields()[0].clone() {
-            n.try_into()
+    fn try_from(value: (Record, &Info)) -> Result<Self, Error> {
+        if let bolt_proto::Value::Node(n) = value.0.fields()[0].clone() {
+            (n, value.1).try_into()
         } else {
             Err(Error::TypeConversionFailed {
-                src: format!("{:#?}", value),
+                src: format!("{:#?}", value.0),
                 dst: "Node".to_string(),
             })
         }
@@ -1254,6 +2365,161 @@ impl TryFrom<HashMap<String, bolt_proto::Value>> for Value {
     }
 }
 
+/// Converts a value returned by a write query to a [`Value`], replacing nodes and relationships
+/// with their ids
+fn write_result_value(bv: bolt_proto::Value) -> Result<Value, Error> {
+    let id = |props: &HashMap<String, bolt_proto::Value>| -> Result<Value, Error> {
+        Ok(props
+            .get("id")
+            .ok_or(Error::ResponseItemNotFound {
+                name: "id".to_string(),
+            })?
+            .clone()
+            .try_into()?)
+    };
+
+    match bv {
+        bolt_proto::Value::Node(n) => id(n.properties()),
+        bolt_proto::Value::Relationship(r) => id(r.properties()),
+        bolt_proto::Value::List(l) => Ok(Value::Array(
+            l.into_iter()
+                .map(write_result_value)
+                .collect::<Result<Vec<Value>, Error>>()?,
+        )),
+        _ => Ok(bv.try_into()?),
+    }
+}
+
+/// Converts a value returned by a query run with `execute_query_values`. Nodes and relationships
+/// are converted to maps of their properties, and lists and maps are converted value by value.
+fn query_result_value(bv: bolt_proto::Value) -> Result<Value, Error> {
+    let props = |props: &HashMap<String, bolt_proto::Value>| -> Result<Value, Error> {
+        Ok(Value::Map(
+            props
+                .iter()
+                .map(|(k, v)| Ok((k.to_string(), query_result_value(v.clone())?)))
+                .collect::<Result<HashMap<String, Value>, Error>>()?,
+        ))
+    };
+
+    match bv {
+        bolt_proto::Value::Node(n) => props(n.properties()),
+        bolt_proto::Value::Relationship(r) => props(r.properties()),
+        bolt_proto::Value::UnboundRelationship(r) => props(r.properties()),
+        bolt_proto::Value::List(l) => Ok(Value::Array(
+            l.into_iter()
+                .map(query_result_value)
+                .collect::<Result<Vec<Value>, Error>>()?,
+        )),
+        bolt_proto::Value::Map(m) => Ok(Value::Map(
+            m.into_iter()
+                .map(|(k, v)| Ok((k, query_result_value(v)?)))
+                .collect::<Result<HashMap<String, Value>, Error>>()?,
+        )),
+        _ => Ok(bv.try_into()?),
+    }
+}
+
+/// Returns a clause removing the given properties from the variable, or an empty string if there
+/// are no properties to remove
+fn remove_clause(var_name: &str, props: &HashMap<String, Value>) -> String {
+    if props.is_empty() {
+        return String::new();
+    }
+
+    let mut names = props
+        .keys()
+        .map(|k| var_name.to_string() + ".`" + &**k + "`")
+        .collect::<Vec<String>>();
+    names.sort();
+    "REMOVE ".to_string() + &*names.join(", ") + "\n"
+}
+
+/// Returns a clause applying the atomic operations to the properties of the variable, or an
+/// empty string if there are no operations. A null or missing property is incremented from zero,
+/// and has values pushed onto an empty list. The operands are read from the `$increment` and
+/// `$push` parameters.
+fn operations_clause(var_name: &str, ops: &PropOperations) -> String {
+    let prop = |k: &str| var_name.to_string() + ".`" + k + "`";
+    let mut sets = ops
+        .increment()
+        .keys()
+        .map(|k| prop(k) + " = coalesce(" + &*prop(k) + ", 0) + $increment.`" + &**k + "`")
+        .chain(
+            ops.push()
+                .keys()
+                .map(|k| prop(k) + " = coalesce(" + &*prop(k) + ", []) + $push.`" + &**k + "`"),
+        )
+        .collect::<Vec<String>>();
+    if sets.is_empty() {
+        return String::new();
+    }
+
+    sets.sort();
+    "SET ".to_string() + &*sets.join(", ") + "\n"
+}
+
+/// Takes the profile returned by Neo4j for a `PROFILE` query, a tree of plan operators, and
+/// returns the total number of database hits across the operators and the number of rows
+/// produced by the root operator
+fn profile_totals(profile: &bolt_proto::Value) -> (i64, i64) {
+    fn db_hits(operator: &bolt_proto::Value) -> i64 {
+        match operator {
+            bolt_proto::Value::Map(m) => {
+                let own = match m.get("dbHits") {
+                    Some(bolt_proto::Value::Integer(hits)) => *hits,
+                    _ => 0,
+                };
+                let children = match m.get("children") {
+                    Some(bolt_proto::Value::List(children)) => children.iter().map(db_hits).sum(),
+                    _ => 0,
+                };
+                own + children
+            }
+            _ => 0,
+        }
+    }
+
+    let rows = match profile {
+        bolt_proto::Value::Map(m) => match m.get("rows") {
+            Some(bolt_proto::Value::Integer(rows)) => *rows,
+            _ => 0,
+        },
+        _ => 0,
+    };
+
+    (db_hits(profile), rows)
+}
+
+/// Takes the responses to a pipeline of a RUN and a PULL message for each of `count` queries,
+/// and returns the records of each query, in order. Returns the first response that isn't a
+/// success or a record as an error. Once a query has failed, the server ignores the messages
+/// after it, so the failure is the first such response.
+fn pipelined_records(responses: Vec<Message>, count: usize) -> Result<Vec<Vec<Record>>, Error> {
+    let mut responses = responses.into_iter();
+    let mut results = Vec::with_capacity(count);
+    for _ in 0..count {
+        match responses.next() {
+            Some(Message::Success(_)) => (),
+            Some(message) => return Err(Error::CypherQueryFailed { message }),
+            None => return Err(Error::ResponseSetNotFound),
+        }
+
+        let mut records = Vec::new();
+        loop {
+            match responses.next() {
+                Some(Message::Record(record)) => records.push(record),
+                Some(Message::Success(_)) => break,
+                Some(message) => return Err(Error::CypherQueryFailed { message }),
+                None => return Err(Error::ResponseSetNotFound),
+            }
+        }
+        results.push(records);
+    }
+
+    Ok(results)
+}
+
 fn cypher_comparison_operator(op: &Operation) -> String {
     match op {
         Operation::EQ => "=".to_string(),
@@ -1265,3 +2531,354 @@ fn cypher_comparison_operator(op: &Operation) -> String {
         Operation::LTE => "<=".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        duration_from_bolt, operations_clause, page_clause, pipelined_records, profile_totals,
+        query_result_value, remove_clause, CypherAccessMode, CypherEndpoint, CypherIsolationLevel,
+        CypherTransaction,
+    };
+    use crate::engine::config::Configuration;
+    use crate::engine::database::{PropOperations, QueryInterceptor, StatementLog};
+    use crate::engine::objects::Node;
+    use crate::engine::schema::{generate_schema, Info};
+    use crate::engine::value::Value;
+    use crate::Error;
+    use std::collections::HashMap;
+    use std::convert::{TryFrom, TryInto};
+    use std::sync::Arc;
+
+    /// Passes if the responses to a pipeline are split into the records of each query, and the
+    /// first failure is returned as an error
+    #[test]
+    fn test_pipelined_records() {
+        use bolt_proto::message::{Failure, Message, Record, Success};
+
+        let success = || Message::Success(Success::new(HashMap::new()));
+        let record = |v: i64| Message::Record(Record::new(vec![bolt_proto::Value::from(v)]));
+
+        let results = pipelined_records(
+            vec![
+                success(),
+                record(1),
+                record(2),
+                success(),
+                success(),
+                success(),
+            ],
+            2,
+        )
+        .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].len(), 2);
+        assert_eq!(results[0][1].fields(), &[bolt_proto::Value::from(2i64)]);
+        assert!(results[1].is_empty());
+
+        let failed = pipelined_records(
+            vec![
+                success(),
+                success(),
+                Message::Failure(Failure::new(HashMap::new())),
+                Message::Ignored,
+            ],
+            2,
+        );
+        assert!(matches!(
+            failed,
+            Err(Error::CypherQueryFailed {
+                message: Message::Failure(_)
+            })
+        ));
+
+        assert!(matches!(
+            pipelined_records(vec![success()], 1),
+            Err(Error::ResponseSetNotFound)
+        ));
+    }
+
+    /// Passes if Cypher durations convert to std durations, and durations that are negative or
+    /// have months are rejected
+    #[test]
+    fn test_duration_from_bolt() {
+        assert_eq!(
+            duration_from_bolt(&bolt_proto::value::Duration::new(0, 1, 3_600, 500_000_000)),
+            Some(std::time::Duration::from_millis(90_000_500))
+        );
+        assert_eq!(
+            duration_from_bolt(&bolt_proto::value::Duration::new(1, 0, 0, 0)),
+            None
+        );
+        assert_eq!(
+            duration_from_bolt(&bolt_proto::value::Duration::new(0, 0, -1, 0)),
+            None
+        );
+        assert_eq!(
+            Value::try_from(bolt_proto::Value::from(bolt_proto::value::Duration::new(
+                0, 0, 60, 0
+            )))
+            .unwrap(),
+            Value::Duration(std::time::Duration::from_secs(60))
+        );
+        assert_eq!(
+            bolt_proto::Value::from(Value::Duration(std::time::Duration::from_secs(60))),
+            bolt_proto::Value::from(60_000_i64)
+        );
+    }
+
+    struct TenantInterceptor;
+
+    impl QueryInterceptor for TenantInterceptor {
+        fn intercept(
+            &self,
+            _operation: &str,
+            query: String,
+            mut params: HashMap<String, Value>,
+        ) -> Result<(String, HashMap<String, Value>), Error> {
+            params.insert("tenant".to_string(), Value::String("acme".to_string()));
+            Ok((
+                query.replace("RETURN", "AND n.tenant_id = $tenant\nRETURN"),
+                params,
+            ))
+        }
+    }
+
+    /// Passes if the query interceptor rewrites a query before it is recorded in the statement log
+    #[tokio::test]
+    async fn test_cypher_transaction_query_interceptor() {
+        let log = StatementLog::new();
+        let mut transaction = CypherTransaction {
+            client: None,
+            access_mode: CypherAccessMode::Read,
+            isolation_level: CypherIsolationLevel::default(),
+            statement_log: Some(log.clone()),
+            profile_log: None,
+            slow_query_threshold: None,
+            database: None,
+            query_interceptor: None,
+            begun: false,
+        }
+        .with_query_interceptor(Some(Arc::new(TenantInterceptor)));
+
+        transaction
+            .run_query(
+                "read_nodes",
+                "MATCH (n:User)\nWHERE n.name = $name\nRETURN n\n".to_string(),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            log.statements(),
+            vec![
+                "MATCH (n:User)\nWHERE n.name = $name\nAND n.tenant_id = $tenant\nRETURN n\n"
+                    .to_string()
+            ]
+        );
+    }
+
+    /// Passes if properties set to null are removed in one clause, in a stable order
+    #[test]
+    fn test_remove_clause() {
+        assert_eq!(remove_clause("n", &HashMap::new()), "");
+        assert_eq!(
+            remove_clause(
+                "n",
+                &HashMap::from_iter(vec![
+                    ("status".to_string(), Value::Null),
+                    ("description".to_string(), Value::Null),
+                ])
+            ),
+            "REMOVE n.`description`, n.`status`\n"
+        );
+    }
+
+    /// Passes if increments and pushes are set from their parameters, starting from zero or an
+    /// empty list when the property is missing
+    #[test]
+    fn test_operations_clause() {
+        assert_eq!(operations_clause("r", &PropOperations::default()), "");
+        assert_eq!(
+            operations_clause(
+                "r",
+                &PropOperations::new(
+                    HashMap::from_iter(vec![("hours".to_string(), Value::Int64(2))]),
+                    HashMap::from_iter(vec![(
+                        "tags".to_string(),
+                        Value::Array(vec![Value::String("late".to_string())])
+                    )]),
+                )
+            ),
+            "SET r.`hours` = coalesce(r.`hours`, 0) + $increment.`hours`, r.`tags` = coalesce(r.`tags`, []) + $push.`tags`\n"
+        );
+    }
+
+    /// Passes if the database hits of every operator in a query profile are totalled, and the
+    /// rows are taken from the root operator
+    #[test]
+    fn test_profile_totals() {
+        let operator = |hits: i64, rows: i64, children: Vec<bolt_proto::Value>| {
+            bolt_proto::Value::from(HashMap::<String, bolt_proto::Value>::from_iter(vec![
+                ("dbHits".to_string(), bolt_proto::Value::from(hits)),
+                ("rows".to_string(), bolt_proto::Value::from(rows)),
+                ("children".to_string(), bolt_proto::Value::from(children)),
+            ]))
+        };
+        let profile = operator(
+            1,
+            2,
+            vec![
+                operator(3, 2, vec![operator(5, 4, Vec::new())]),
+                operator(7, 1, Vec::new()),
+            ],
+        );
+
+        assert_eq!(profile_totals(&profile), (16, 2));
+        assert_eq!(profile_totals(&bolt_proto::Value::Null), (0, 0));
+    }
+
+    /// Passes if a node returned by a query is converted to a map of its properties, within lists
+    /// and maps
+    #[test]
+    fn test_query_result_value() {
+        let node = bolt_proto::Value::from(bolt_proto::value::Node::new(
+            1,
+            vec!["User".to_string()],
+            HashMap::<String, bolt_proto::Value>::from_iter(vec![
+                ("id".to_string(), bolt_proto::Value::from("1234")),
+                ("name".to_string(), bolt_proto::Value::from("alice")),
+            ]),
+        ));
+        let user = Value::Map(HashMap::from_iter(vec![
+            ("id".to_string(), Value::String("1234".to_string())),
+            ("name".to_string(), Value::String("alice".to_string())),
+        ]));
+
+        assert_eq!(query_result_value(node.clone()).unwrap(), user);
+        assert_eq!(
+            query_result_value(bolt_proto::Value::from(vec![node.clone()])).unwrap(),
+            Value::Array(vec![user.clone()])
+        );
+        assert_eq!(
+            query_result_value(bolt_proto::Value::from(
+                HashMap::<String, bolt_proto::Value>::from_iter(vec![("dev".to_string(), node)])
+            ))
+            .unwrap(),
+            Value::Map(HashMap::from_iter(vec![("dev".to_string(), user)]))
+        );
+    }
+
+    /// Passes if queries name the selected database only when they run outside of an explicit
+    /// transaction
+    #[test]
+    fn test_cypher_transaction_run_metadata_database() {
+        let mut transaction = CypherTransaction {
+            client: None,
+            access_mode: CypherAccessMode::Read,
+            isolation_level: CypherIsolationLevel::default(),
+            statement_log: None,
+            profile_log: None,
+            slow_query_threshold: None,
+            database: None,
+            query_interceptor: None,
+            begun: false,
+        };
+        assert!(transaction.run_metadata().is_none());
+
+        transaction = transaction.with_database(Some("tenant_db".to_string()));
+        assert!(transaction.run_metadata().is_some());
+
+        transaction.begun = true;
+        assert!(transaction.run_metadata().is_none());
+    }
+
+    /// Passes if the lowercased shadow value of a property that is unique ignoring case isn't
+    /// loaded into the node, while the property itself is
+    #[test]
+    fn test_node_from_bolt_skips_unique_shadow() {
+        let config = Configuration::try_from(
+            "
+version: 1
+model:
+  - name: User
+    props:
+      - name: email
+        type: String
+        dbName: mail
+        uniqueCaseInsensitive: true
+"
+            .to_string(),
+        )
+        .unwrap();
+        let info = Info::new(
+            "User".to_string(),
+            Arc::new(generate_schema(&config).unwrap()),
+        );
+
+        let mut properties = HashMap::new();
+        properties.insert("id".to_string(), "1".to_string());
+        properties.insert("mail".to_string(), "Alice@Example.com".to_string());
+        properties.insert("mail_lower".to_string(), "alice@example.com".to_string());
+        let bolt_node = bolt_proto::value::Node::new(1, vec!["User".to_string()], properties);
+
+        let node: Node<()> = (bolt_node, &info).try_into().unwrap();
+        let mut fields = node.fields().keys().collect::<Vec<&String>>();
+        fields.sort();
+        assert_eq!(fields, vec!["email", "id"]);
+    }
+
+    /// Passes if a page of results is selected with SKIP and LIMIT clauses, which are left out
+    /// when every result is selected
+    #[test]
+    fn test_page_clause() {
+        assert_eq!(page_clause(0, None), "");
+        assert_eq!(page_clause(2, None), "SKIP 2\n");
+        assert_eq!(page_clause(0, Some(5)), "LIMIT 5\n");
+        assert_eq!(page_clause(2, Some(5)), "SKIP 2\nLIMIT 5\n");
+    }
+
+    #[tokio::test]
+    async fn test_cypher_endpoint_missing_client_cert() {
+        let ce = CypherEndpoint::new(
+            "127.0.0.1".to_string(),
+            None,
+            7687,
+            "neo4j".to_string(),
+            "password".to_string(),
+            1,
+        )
+        .with_client_cert(
+            "./tests/fixtures/missing-client.pem".to_string(),
+            "./tests/fixtures/missing-client.key".to_string(),
+        );
+
+        match ce.tls_config().await {
+            Err(Error::CypherTlsConfigFailed { path, details: _ }) => {
+                assert_eq!(path, "./tests/fixtures/missing-client.pem")
+            }
+            _ => panic!("Expected CypherTlsConfigFailed"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cypher_endpoint_ca_cert_without_certs() {
+        let ce = CypherEndpoint::new(
+            "127.0.0.1".to_string(),
+            None,
+            7687,
+            "neo4j".to_string(),
+            "password".to_string(),
+            1,
+        )
+        .with_ca_cert("./tests/fixtures/minimal.yml".to_string());
+
+        match ce.tls_config().await {
+            Err(Error::CypherTlsConfigFailed { path, details }) => {
+                assert_eq!(path, "./tests/fixtures/minimal.yml");
+                assert_eq!(details, "no PEM-encoded certificates found");
+            }
+            _ => panic!("Expected CypherTlsConfigFailed"),
+        }
+    }
+}
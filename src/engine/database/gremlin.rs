@@ -3,10 +3,11 @@
 use crate::engine::context::RequestContext;
 use crate::engine::database::env_bool;
 use crate::engine::database::{
-    env_string, env_u16, Comparison, DatabaseEndpoint, DatabasePool, NodeQueryVar, Operation,
-    QueryFragment, QueryResult, RelQueryVar, SuffixGenerator, Transaction,
+    env_string, env_u16, graphql_name, log_slow_query, Comparison, DatabaseEndpoint, DatabasePool,
+    NodeQueryVar, Operation, PropOperations, QueryFragment, QueryInterceptor, QueryResult,
+    RelQueryVar, StatementLog, SuffixGenerator, Transaction,
 };
-use crate::engine::loader::{NodeLoaderKey, RelLoaderKey};
+use crate::engine::loader::{AggregateLoaderKey, NodeLoaderKey, RelLoaderKey};
 use crate::engine::objects::{Direction, Node, NodeRef, Options, Rel};
 use crate::engine::schema::{Info, NodeType};
 use crate::engine::value::Value;
@@ -21,6 +22,8 @@ use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::env::var_os;
 use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 #[cfg(feature = "gremlin")]
 use uuid::Uuid;
 
@@ -51,6 +54,7 @@ pub struct GremlinEndpoint {
     sessions: bool,
     version: GraphSON,
     pool_size: u16,
+    test_on_acquire: bool,
 }
 
 impl GremlinEndpoint {
@@ -78,9 +82,12 @@ impl GremlinEndpoint {
     ///   sessions should not be used. Defaults to `false`.
     /// * WG_GREMLIN_VERSION - may be set to `1`, `2`, or `3`, to indicate the version of GraphSON
     ///   serialization that should be used in communicating with the database. Defaults to `3`.
+    /// * WG_GREMLIN_TEST_ON_ACQUIRE - must be true, if set, as described for
+    ///   [`with_test_on_acquire`]. Defaults to `true`.
     /// * WG_POOL_SIZE - connection pool size
     ///
     /// [`GremlinEndpoint`]: ./struct.GremlinEndpoint.html
+    /// [`with_test_on_acquire`]: #method.with_test_on_acquire
     ///
     /// # Errors
     ///
@@ -124,8 +131,35 @@ impl GremlinEndpoint {
             },
             pool_size: env_u16("WG_POOL_SIZE")
                 .unwrap_or_else(|_| num_cpus::get().try_into().unwrap_or(8)),
+            test_on_acquire: env_bool("WG_GREMLIN_TEST_ON_ACQUIRE").unwrap_or(true),
         })
     }
+
+    /// Sets whether connections taken from this endpoint's pool are tested before use. The
+    /// Gremlin client's pool always runs a trivial `g.inject(0)` traversal on a pooled connection
+    /// before handing it out, and discards a connection that fails it, so the test can't be
+    /// turned off. Setting this to false makes [`pool`] return an [`Error`] variant
+    /// [`PoolOptionNotSupported`], rather than leaving the test on unannounced. Defaults to true.
+    ///
+    /// [`Error`]: ../../../error/enum.Error.html
+    /// [`PoolOptionNotSupported`]: ../../../error/enum.Error.html#variant.PoolOptionNotSupported
+    /// [`pool`]: ../trait.DatabaseEndpoint.html#tymethod.pool
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::engine::database::gremlin::GremlinEndpoint;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let ge = GremlinEndpoint::from_env()?
+    ///         .with_test_on_acquire(true);
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub fn with_test_on_acquire(mut self, test_on_acquire: bool) -> Self {
+        self.test_on_acquire = test_on_acquire;
+        self
+    }
 }
 
 #[async_trait]
@@ -133,6 +167,12 @@ impl DatabaseEndpoint for GremlinEndpoint {
     type PoolType = GremlinPool;
 
     async fn pool(&self) -> Result<Self::PoolType, Error> {
+        if !self.test_on_acquire {
+            return Err(Error::PoolOptionNotSupported {
+                option: "test_on_acquire = false".to_string(),
+            });
+        }
+
         let mut ro_options_builder = ConnectionOptions::builder()
             .host(&self.read_replica)
             .port(self.port)
@@ -185,6 +225,9 @@ pub struct GremlinPool {
     rw_pool: GremlinClient,
     long_ids: bool,
     sessions: bool,
+    statement_log: Option<StatementLog>,
+    slow_query_threshold: Option<Duration>,
+    query_interceptor: Option<Arc<dyn QueryInterceptor>>,
 }
 
 impl GremlinPool {
@@ -194,6 +237,9 @@ impl GremlinPool {
             rw_pool,
             long_ids,
             sessions,
+            statement_log: None,
+            slow_query_threshold: None,
+            query_interceptor: None,
         }
     }
 }
@@ -203,19 +249,39 @@ impl DatabasePool for GremlinPool {
     type TransactionType = GremlinTransaction;
 
     async fn read_transaction(&self) -> Result<Self::TransactionType, Error> {
-        Ok(GremlinTransaction::new(
-            self.ro_pool.clone(),
-            self.long_ids,
-            false,
-        ))
+        Ok(GremlinTransaction {
+            statement_log: self.statement_log.clone(),
+            slow_query_threshold: self.slow_query_threshold,
+            query_interceptor: self.query_interceptor.clone(),
+            ..GremlinTransaction::new(self.ro_pool.clone(), self.long_ids, false)
+        })
     }
 
     async fn transaction(&self) -> Result<Self::TransactionType, Error> {
-        Ok(GremlinTransaction::new(
-            self.rw_pool.clone(),
-            self.long_ids,
-            self.sessions,
-        ))
+        Ok(GremlinTransaction {
+            statement_log: self.statement_log.clone(),
+            slow_query_threshold: self.slow_query_threshold,
+            query_interceptor: self.query_interceptor.clone(),
+            ..GremlinTransaction::new(self.rw_pool.clone(), self.long_ids, self.sessions)
+        })
+    }
+
+    fn with_statement_log(&self, log: StatementLog) -> Result<Self, Error> {
+        let mut pool = self.clone();
+        pool.statement_log = Some(log);
+        Ok(pool)
+    }
+
+    fn with_slow_query_threshold(&self, threshold: Option<Duration>) -> Self {
+        let mut pool = self.clone();
+        pool.slow_query_threshold = threshold;
+        pool
+    }
+
+    fn with_query_interceptor(&self, interceptor: Option<Arc<dyn QueryInterceptor>>) -> Self {
+        let mut pool = self.clone();
+        pool.query_interceptor = interceptor;
+        pool
     }
 }
 
@@ -223,6 +289,9 @@ pub struct GremlinTransaction {
     client: GremlinClient,
     long_ids: bool,
     sessions: bool,
+    statement_log: Option<StatementLog>,
+    slow_query_threshold: Option<Duration>,
+    query_interceptor: Option<Arc<dyn QueryInterceptor>>,
 }
 
 impl GremlinTransaction {
@@ -231,9 +300,51 @@ impl GremlinTransaction {
             client,
             long_ids,
             sessions,
+            statement_log: None,
+            slow_query_threshold: None,
+            query_interceptor: None,
         }
     }
 
+    /// Executes a query and collects its results. The query and its parameters are first passed
+    /// through the [`QueryInterceptor`], if one is set. If the transaction is explaining rather
+    /// than executing, the query is recorded in the statement log and no results are returned.
+    /// `operation` names the [`Transaction`] method issuing the query, for slow query logging.
+    ///
+    /// [`QueryInterceptor`]: ../trait.QueryInterceptor.html
+    /// [`Transaction`]: ../trait.Transaction.html
+    async fn run_query(
+        &mut self,
+        operation: &str,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<GValue>, Error> {
+        let (query, params) = match &self.query_interceptor {
+            Some(interceptor) => interceptor.intercept(operation, query, params)?,
+            None => (query, params),
+        };
+
+        if let Some(log) = &self.statement_log {
+            log.record(query);
+            return Ok(Vec::new());
+        }
+
+        let param_list: Vec<(&str, &dyn ToGValue)> =
+            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
+                pl.push((k.as_str(), v));
+                pl
+            });
+
+        let start = Instant::now();
+        let raw_results = self
+            .client
+            .execute(query.as_str(), param_list.as_slice())
+            .await?;
+        let results = raw_results.try_collect().await?;
+        log_slow_query(self.slow_query_threshold, operation, start, &query);
+        Ok(results)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn add_properties(
         mut query: String,
@@ -266,7 +377,15 @@ impl GremlinTransaction {
         props
             .into_iter()
             .try_fold((query, params), |(mut outer_q, mut outer_p), (k, v)| {
-                if let Value::Array(a) = v {
+                if let Value::Null = v {
+                    // a property set to null is dropped by an update, and not written by a create
+                    if !create_query {
+                        outer_q.push_str(
+                            &(".sideEffect(properties('".to_string() + &*k + "').drop())"),
+                        );
+                    }
+                    Ok((outer_q, outer_p))
+                } else if let Value::Array(a) = v {
                     a.into_iter()
                         .try_fold((outer_q, outer_p), |(mut inner_q, mut inner_p), val| {
                             let suffix = sg.suffix();
@@ -299,6 +418,43 @@ impl GremlinTransaction {
             })
     }
 
+    /// Adds steps applying the atomic operations to the properties of the edges traversed by the
+    /// query. A null or missing property is incremented from zero. Edge properties can't hold
+    /// lists, so pushing onto a property is rejected.
+    fn add_operations(
+        mut query: String,
+        ops: PropOperations,
+        mut params: HashMap<String, Value>,
+        sg: &mut SuffixGenerator,
+    ) -> Result<(String, HashMap<String, Value>), Error> {
+        if let Some(k) = ops.push().keys().next() {
+            return Err(Error::TypeNotExpected {
+                details: Some(format!(
+                    "PUSH onto {}, as Gremlin edge properties can't hold lists",
+                    k
+                )),
+            });
+        }
+
+        let mut increments = ops.increment().iter().collect::<Vec<_>>();
+        increments.sort_by_key(|(k, _)| *k);
+        for (k, v) in increments {
+            let suffix = sg.suffix();
+            query.push_str(
+                &(".property('".to_string()
+                    + &**k
+                    + "', union(coalesce(values('"
+                    + &**k
+                    + "'), constant(0)), constant("
+                    + &**k
+                    + &*suffix
+                    + ")).sum())"),
+            );
+            params.insert(k.to_string() + &*suffix, v.clone());
+        }
+        Ok((query, params))
+    }
+
     fn add_sort_to_query(query: String, options: Options) -> String {
         options
             .sort()
@@ -329,7 +485,7 @@ impl GremlinTransaction {
 #[async_trait]
 impl Transaction for GremlinTransaction {
     async fn begin(&mut self) -> Result<(), Error> {
-        if self.sessions {
+        if self.sessions && self.statement_log.is_none() {
             self.client = self
                 .client
                 .create_session(Uuid::new_v4().to_hyphenated().to_string())
@@ -354,14 +510,7 @@ impl Transaction for GremlinTransaction {
             params
         );
 
-        let param_list: Vec<(&str, &dyn ToGValue)> =
-            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
-                pl.push((k.as_str(), v));
-                pl
-            });
-
-        let raw_results = self.client.execute(query, param_list.as_slice()).await?;
-        let results = raw_results.try_collect().await?;
+        let results = self.run_query("execute_query", query, params).await?;
 
         trace!(
             "GremlinTransaction::execute_query -- results: {:#?}",
@@ -371,6 +520,40 @@ impl Transaction for GremlinTransaction {
         Ok(QueryResult::Gremlin(results))
     }
 
+    async fn execute_write(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        trace!(
+            "GremlinTransaction::execute_write called -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let results = self.run_query("execute_write", query, params).await?;
+
+        results.into_iter().map(write_result_value).collect()
+    }
+
+    async fn execute_query_values(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        trace!(
+            "GremlinTransaction::execute_query_values called -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let results = self
+            .run_query("execute_query_values", query, params)
+            .await?;
+
+        results.into_iter().map(query_result_value).collect()
+    }
+
     #[tracing::instrument(
         level = "info",
         name = "wg-gremlin-create-nodes",
@@ -404,13 +587,7 @@ impl Transaction for GremlinTransaction {
 
         trace!("GremlinTransaction::create_node -- q: {}, p: {:#?}", q, p);
 
-        let param_list: Vec<(&str, &dyn ToGValue)> = p.iter().fold(Vec::new(), |mut pl, (k, v)| {
-            pl.push((k.as_str(), v));
-            pl
-        });
-
-        let raw_results = self.client.execute(q, param_list.as_slice()).await?;
-        let mut results: Vec<GValue> = raw_results.try_collect().await?;
+        let mut results = self.run_query("create_node", q, p).await?;
         trace!("GremlinTransaction::create_node -- results: {:#?}", results);
 
         Ok((results.pop().ok_or(Error::ResponseSetNotFound)?, info).try_into()?)
@@ -475,13 +652,7 @@ impl Transaction for GremlinTransaction {
 
         trace!("GremlinTransaction::create_rels -- q: {}, p: {:#?}", q, p);
 
-        let param_list: Vec<(&str, &dyn ToGValue)> = p.iter().fold(Vec::new(), |mut pl, (k, v)| {
-            pl.push((k.as_str(), v));
-            pl
-        });
-
-        let raw_results = self.client.execute(q, param_list.as_slice()).await?;
-        let results: Vec<GValue> = raw_results.try_collect().await?;
+        let results = self.run_query("create_rels", q, p).await?;
 
         trace!("create_rels -- results: {:#?}", results);
 
@@ -584,10 +755,17 @@ impl Transaction for GremlinTransaction {
                 .into_iter()
                 .enumerate()
                 .for_each(|(i, rqf)| {
-                    if i == 0 {
+                    if i > 0 {
+                        query.push_str(", ");
+                    }
+
+                    // A non-empty match fragment holds the condition for a symmetric or inverse
+                    // rel, written relative to the node, which is matched on edges in either
+                    // direction from the node or on edges into the node
+                    if rqf.match_fragment().is_empty() {
                         query.push_str(&("outE()".to_string() + rqf.where_fragment()));
                     } else {
-                        query.push_str(&(", outE()".to_string() + rqf.where_fragment()));
+                        query.push_str(rqf.match_fragment());
                     }
 
                     params.extend(rqf.params());
@@ -637,13 +815,7 @@ impl Transaction for GremlinTransaction {
 
         trace!("GremlinTransaction::load_nodes -- query: {}", query,);
 
-        let param_list: Vec<(&str, &dyn ToGValue)> =
-            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
-                pl.push((k, v));
-                pl
-            });
-        let raw_results = self.client.execute(query, param_list.as_slice()).await?;
-        let results: Vec<GValue> = raw_results.try_collect().await?;
+        let results = self.run_query("load_nodes", query, params).await?;
 
         trace!("GremlinTransaction::load_nodes -- results: {:#?}", results);
 
@@ -669,6 +841,9 @@ impl Transaction for GremlinTransaction {
         query_fragment, options, info.name());
 
         let mut query = "g.V()".to_string() + query_fragment.where_fragment();
+        if options.distinct() {
+            query += ".dedup()";
+        }
         query = GremlinTransaction::add_sort_to_query(query, options);
         query += ".valueMap(true)";
 
@@ -679,14 +854,8 @@ impl Transaction for GremlinTransaction {
             query,
             params
         );
-        let param_list: Vec<(&str, &dyn ToGValue)> =
-            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
-                pl.push((k, v));
-                pl
-            });
 
-        let raw_results = self.client.execute(query, param_list.as_slice()).await?;
-        let results: Vec<GValue> = raw_results.try_collect().await?;
+        let results = self.run_query("read_nodes", query, params).await?;
 
         results
             .into_iter()
@@ -694,6 +863,75 @@ impl Transaction for GremlinTransaction {
             .collect::<Result<Vec<Node<RequestCtx>>, Error>>()
     }
 
+    #[tracing::instrument(
+        level = "info",
+        name = "wg-gremlin-node-exists",
+        skip(self, _node_var, query_fragment)
+    )]
+    async fn node_exists(
+        &mut self,
+        _node_var: &NodeQueryVar,
+        query_fragment: QueryFragment,
+    ) -> Result<bool, Error> {
+        trace!(
+            "GremlinTransaction::node_exists called -- query_fragment: {:#?}",
+            query_fragment
+        );
+
+        // hasNext stops at the first match, so that no vertex properties are returned
+        let query = "g.V()".to_string() + query_fragment.where_fragment() + ".hasNext()";
+        let params = query_fragment.params();
+
+        trace!(
+            "GremlinTransaction::node_exists -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let mut results = self.run_query("node_exists", query, params).await?;
+
+        match results.pop() {
+            Some(result) => Ok(result.try_into()?),
+            // Explaining records the query without running it
+            None if self.statement_log.is_some() => Ok(false),
+            None => Err(Error::ResponseSetNotFound),
+        }
+    }
+
+    async fn read_computed_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        query: String,
+        src_id: Value,
+        info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        trace!(
+            "GremlinTransaction::read_computed_nodes called -- query: {}, src_id: {:#?}, info.name: {}",
+            query,
+            src_id,
+            info.name()
+        );
+
+        // Gremlin bindings are referenced without the Cypher-style $ prefix
+        let query = query.replace("$src_id", "src_id");
+        let src_id = match src_id {
+            Value::String(s) if self.long_ids => match s.parse::<i64>() {
+                Ok(i) => Value::Int64(i),
+                Err(_) => Value::String(s),
+            },
+            id => id,
+        };
+
+        let mut params = HashMap::new();
+        params.insert("src_id".to_string(), src_id);
+        let results = self.run_query("read_computed_nodes", query, params).await?;
+
+        let mut nodes = Vec::new();
+        for result in results {
+            nodes.push((result, info).try_into()?);
+        }
+        Ok(nodes)
+    }
+
     fn rel_read_by_ids_fragment<RequestCtx: RequestContext>(
         &mut self,
         rel_var: &RelQueryVar,
@@ -772,28 +1010,72 @@ impl Transaction for GremlinTransaction {
             params.insert(k + &*param_suffix, c.operand);
         }
 
-        query.push_str(".where(");
+        let src_where = src_fragment_opt
+            .map(|src_fragment| {
+                let w = src_fragment.where_fragment().to_string();
+                params.extend(src_fragment.params());
+                w
+            })
+            .unwrap_or_default();
+        let dst_where_opt = dst_fragment_opt.map(|dst_fragment| {
+            let w = dst_fragment.where_fragment().to_string();
+            params.extend(dst_fragment.params());
+            w
+        });
 
-        if dst_fragment_opt.is_some() {
-            query.push_str("and(");
-        }
+        let src_label = rel_var.src.label()?;
+        // Matches edges stored from the source to the destination, or, if reversed is true, from
+        // the destination to the source
+        let direction_fragment = |reversed: bool| -> String {
+            let (src_step, dst_step) = if reversed {
+                ("inV()", "outV()")
+            } else {
+                ("outV()", "inV()")
+            };
+            let mut f = String::new();
+            if dst_where_opt.is_some() {
+                f.push_str("and(");
+            }
+            f.push_str(&(src_step.to_string() + ".hasLabel('" + src_label + "')" + &*src_where));
+            if let Some(dst_where) = &dst_where_opt {
+                f.push_str(&(", ".to_string() + dst_step + &**dst_where + ")"));
+            }
+            f
+        };
+
+        if rel_var.symmetric() {
+            // Symmetric rels match edges stored in either direction. The match fragment, which is
+            // otherwise unused for Gremlin rels, carries the same condition written relative to
+            // the source vertex, for use when the rel fragment filters nodes.
+            let mut vertex_query = "bothE()".to_string() + &*query;
+            if let Some(dst_where) = &dst_where_opt {
+                vertex_query.push_str(&(".where(otherV()".to_string() + &**dst_where + ")"));
+            }
 
-        query.push_str(&("outV().hasLabel('".to_string() + rel_var.src.label()? + "')"));
+            query.push_str(
+                &(".where(or(".to_string()
+                    + &*direction_fragment(false)
+                    + ", "
+                    + &*direction_fragment(true)
+                    + "))"),
+            );
 
-        if let Some(src_fragment) = src_fragment_opt {
-            query.push_str(src_fragment.where_fragment());
-            params.extend(src_fragment.params());
-        }
+            Ok(QueryFragment::new(vertex_query, query, params))
+        } else if rel_var.inverse() {
+            // An inverse rel filters the node being read, which is the rel's destination, so the
+            // condition is matched on edges into the node
+            query.push_str(&(".where(".to_string() + &*direction_fragment(false) + ")"));
 
-        if let Some(dst_fragment) = dst_fragment_opt {
-            query.push_str(", ");
-            query.push_str(&(", inV()".to_string() + dst_fragment.where_fragment() + ")"));
-            params.extend(dst_fragment.params());
-        }
-
-        query.push(')');
+            Ok(QueryFragment::new(
+                "inE()".to_string() + &*query,
+                query,
+                params,
+            ))
+        } else {
+            query.push_str(&(".where(".to_string() + &*direction_fragment(false) + ")"));
 
-        Ok(QueryFragment::new(String::new(), query, params))
+            Ok(QueryFragment::new(String::new(), query, params))
+        }
     }
 
     #[tracing::instrument(level = "info", name = "wg-gremlin-load-rels", skip(self))]
@@ -809,39 +1091,37 @@ impl Transaction for GremlinTransaction {
         for (i, rlk) in keys.iter().enumerate() {
             let suffix = sg.suffix();
             if i == 0 {
-                query.push_str(
-                    &("g.E().union(hasLabel('".to_string()
-                        + rlk.rel_name()
-                        + "').where(outV().has(id, id"
-                        + &*suffix
-                        + ")"),
-                );
+                query.push_str("g.E().union(");
             } else {
+                query.push_str(", ");
+            }
+            query.push_str(
+                &("hasLabel('".to_string()
+                    + rlk.rel_name()
+                    + "').where(outV().has(id, id"
+                    + &*suffix
+                    + ")).project('src_id', 'rel', 'dst_id').by(outV().id()).by(valueMap(true)).by(inV().id())"),
+            );
+            // A symmetric relationship is also loaded from edges that point into the source node,
+            // with the ends swapped so that the source node is always the rel's source.
+            if rlk.symmetric() {
                 query.push_str(
                     &(", hasLabel('".to_string()
                         + rlk.rel_name()
-                        + "').where(outV().has(id, id"
+                        + "').where(inV().has(id, id"
                         + &*suffix
-                        + ")"),
+                        + ")).project('src_id', 'rel', 'dst_id').by(inV().id()).by(valueMap(true)).by(outV().id())"),
                 );
             }
             params.insert(
                 "id".to_string() + &*suffix,
                 Value::String(rlk.src_id().to_string()),
             );
-
-            query.push(')');
         }
-        query.push_str(").project('src_id', 'rel', 'dst_id').by(outV().id()).by(valueMap(true)).by(inV().id())");
+        query.push(')');
         trace!("GremlinTransaction::load_rels -- query: {}", query,);
 
-        let param_list: Vec<(&str, &dyn ToGValue)> =
-            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
-                pl.push((k, v));
-                pl
-            });
-        let raw_results = self.client.execute(query, param_list.as_slice()).await?;
-        let results: Vec<GValue> = raw_results.try_collect().await?;
+        let results = self.run_query("load_rels", query, params).await?;
 
         results
             .into_iter()
@@ -849,6 +1129,179 @@ impl Transaction for GremlinTransaction {
             .collect::<Result<Vec<Rel<RequestCtx>>, Error>>()
     }
 
+    async fn count_rels(&mut self, keys: &[RelLoaderKey]) -> Result<Vec<i64>, Error> {
+        trace!("GremlinTransaction::count_rels called -- keys: {:#?}", keys);
+
+        let mut sg = SuffixGenerator::new();
+        let mut query = String::new();
+        let mut params = HashMap::new();
+
+        // Each key's count is returned with the key's index, so that it can be matched to its key
+        for (i, rlk) in keys.iter().enumerate() {
+            let suffix = sg.suffix();
+            if i == 0 {
+                query.push_str("g.V().union(");
+            } else {
+                query.push_str(", ");
+            }
+            query.push_str(
+                &("has(id, id".to_string()
+                    + &*suffix
+                    + ").project('key', 'count').by(constant("
+                    + &*i.to_string()
+                    + ")).by("
+                    + if rlk.symmetric() { "bothE('" } else { "outE('" }
+                    + rlk.rel_name()
+                    + "').count())"),
+            );
+            params.insert(
+                "id".to_string() + &*suffix,
+                Value::String(rlk.src_id().to_string()),
+            );
+        }
+        query.push(')');
+        trace!("GremlinTransaction::count_rels -- query: {}", query);
+
+        let results = self.run_query("count_rels", query, params).await?;
+
+        // A source node that doesn't exist returns no result, and so has a count of zero
+        let mut counts = vec![0; keys.len()];
+        for result in results {
+            if let GValue::Map(map) = result {
+                let key = map.get("key").cloned().map(Value::try_from).transpose()?;
+                let count = map.get("count").cloned().map(Value::try_from).transpose()?;
+                if let (Some(Value::Int64(key)), Some(Value::Int64(count))) = (key, count) {
+                    if let Some(c) = usize::try_from(key).ok().and_then(|k| counts.get_mut(k)) {
+                        *c = count;
+                    }
+                }
+            }
+        }
+
+        Ok(counts)
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "wg-gremlin-count-matching-rels",
+        skip(self, query_fragment, _rel_var)
+    )]
+    async fn count_matching_rels(
+        &mut self,
+        query_fragment: QueryFragment,
+        _rel_var: &RelQueryVar,
+    ) -> Result<i64, Error> {
+        trace!(
+            "GremlinTransaction::count_matching_rels called -- query_fragment: {:#?}",
+            query_fragment
+        );
+
+        let query = "g.E()".to_string() + query_fragment.where_fragment() + ".count()";
+        let params = query_fragment.params();
+
+        trace!(
+            "GremlinTransaction::count_matching_rels -- query: {}, params: {:#?}",
+            query,
+            params
+        );
+
+        let mut results = self.run_query("count_matching_rels", query, params).await?;
+
+        match results.pop().map(Value::try_from).transpose()? {
+            Some(Value::Int64(count)) => Ok(count),
+            Some(_) => Err(Error::TypeNotExpected { details: None }),
+            // Explaining records the query without running it
+            None if self.statement_log.is_some() => Ok(0),
+            None => Err(Error::ResponseSetNotFound),
+        }
+    }
+
+    async fn aggregate_rels(
+        &mut self,
+        keys: &[AggregateLoaderKey],
+    ) -> Result<Vec<HashMap<String, Value>>, Error> {
+        trace!(
+            "GremlinTransaction::aggregate_rels called -- keys: {:#?}",
+            keys
+        );
+
+        let mut sg = SuffixGenerator::new();
+        let mut query = String::new();
+        let mut params = HashMap::new();
+
+        // Each key's property values are returned with the key's index, so that they can be
+        // matched to the key, and under the index of the property, so that property names can't
+        // collide with the key and count fields. The values are aggregated once returned.
+        for (i, alk) in keys.iter().enumerate() {
+            let rlk = alk.rel_key();
+            let suffix = sg.suffix();
+            if i == 0 {
+                query.push_str("g.V().union(");
+            } else {
+                query.push_str(", ");
+            }
+            let edges = if rlk.symmetric() { "bothE('" } else { "outE('" }.to_string()
+                + rlk.rel_name()
+                + "')";
+            query.push_str(
+                &("has(id, id".to_string()
+                    + &*suffix
+                    + ").project('key', 'count'"
+                    + &*(0..alk.props().len())
+                        .map(|j| ", 'p".to_string() + &*j.to_string() + "'")
+                        .collect::<String>()
+                    + ").by(constant("
+                    + &*i.to_string()
+                    + ")).by("
+                    + &*edges
+                    + ".count())"
+                    + &*alk
+                        .props()
+                        .iter()
+                        .map(|p| {
+                            ".by(".to_string() + &*edges + ".values('" + p.as_str() + "').fold())"
+                        })
+                        .collect::<String>()),
+            );
+            params.insert(
+                "id".to_string() + &*suffix,
+                Value::String(rlk.src_id().to_string()),
+            );
+        }
+        query.push(')');
+        trace!("GremlinTransaction::aggregate_rels -- query: {}", query);
+
+        let results = self.run_query("aggregate_rels", query, params).await?;
+
+        // A source node that doesn't exist returns no result, and so has no relationships
+        let mut aggregates = vec![HashMap::new(); keys.len()];
+        for result in results {
+            if let GValue::Map(map) = result {
+                let key = map.get("key").cloned().map(Value::try_from).transpose()?;
+                let count = map.get("count").cloned().map(Value::try_from).transpose()?;
+                if let (Some(Value::Int64(key)), Some(count)) = (key, count) {
+                    let k = usize::try_from(key).ok();
+                    if let Some((a, alk)) = k.and_then(|k| aggregates.get_mut(k).zip(keys.get(k))) {
+                        a.insert("count".to_string(), count);
+                        for (j, p) in alk.props().iter().enumerate() {
+                            let values = match map.get(&*("p".to_string() + &*j.to_string())) {
+                                Some(GValue::List(l)) => l
+                                    .iter()
+                                    .cloned()
+                                    .map(Value::try_from)
+                                    .collect::<Result<Vec<Value>, Error>>()?,
+                                _ => Vec::new(),
+                            };
+                            a.insert(p.to_string(), aggregate_values(values));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(aggregates)
+    }
+
     #[tracing::instrument(
         level = "info",
         name = "wg-gremlin-read-rels",
@@ -863,10 +1316,12 @@ impl Transaction for GremlinTransaction {
         trace!("GremlinTransaction::read_rels called -- query_fragment: {:#?}, rel_var: {:#?}, options: {:#?}",
         query_fragment, rel_var, options);
 
+        let (skip, limit) = (options.skip(), options.limit());
         let mut query = GremlinTransaction::add_sort_to_query(
             "g.E()".to_string() + query_fragment.where_fragment(),
             options,
         );
+        query += &page_step(skip, limit);
         query +=
             ".project('src_id', 'rel', 'dst_id').by(outV().id()).by(valueMap(true)).by(inV().id())";
 
@@ -878,14 +1333,7 @@ impl Transaction for GremlinTransaction {
             params
         );
 
-        let param_list: Vec<(&str, &dyn ToGValue)> =
-            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
-                pl.push((k, v));
-                pl
-            });
-
-        let raw_results = self.client.execute(query, param_list.as_slice()).await?;
-        let results: Vec<GValue> = raw_results.try_collect().await?;
+        let results = self.run_query("read_rels", query, params).await?;
 
         results
             .into_iter()
@@ -926,13 +1374,8 @@ impl Transaction for GremlinTransaction {
         q.push_str(".valueMap(true)");
 
         trace!("GremlinTransaction::update_nodes -- q: {}, p: {:#?}", q, p);
-        let param_list: Vec<(&str, &dyn ToGValue)> = p.iter().fold(Vec::new(), |mut pl, (k, v)| {
-            pl.push((k.as_str(), v));
-            pl
-        });
 
-        let raw_results = self.client.execute(q, param_list.as_slice()).await?;
-        let results: Vec<GValue> = raw_results.try_collect().await?;
+        let results = self.run_query("update_nodes", q, p).await?;
 
         results
             .into_iter()
@@ -950,14 +1393,15 @@ impl Transaction for GremlinTransaction {
         query_fragment: QueryFragment,
         rel_var: &RelQueryVar,
         props: HashMap<String, Value>,
+        ops: PropOperations,
         options: Options,
         sg: &mut SuffixGenerator,
     ) -> Result<Vec<Rel<RequestCtx>>, Error> {
-        trace!("GremlinTransaction::update_rels called -- query_fragment: {:#?}, rel_var: {:#?}, props: {:#?}, options: {:#?}",
-        query_fragment, rel_var, props, options);
+        trace!("GremlinTransaction::update_rels called -- query_fragment: {:#?}, rel_var: {:#?}, props: {:#?}, ops: {:#?}, options: {:#?}",
+        query_fragment, rel_var, props, ops, options);
 
         let first = "g.E()".to_string() + query_fragment.where_fragment();
-        let (mut q, p) = GremlinTransaction::add_properties(
+        let (q, p) = GremlinTransaction::add_properties(
             first,
             props,
             query_fragment.params(),
@@ -966,6 +1410,7 @@ impl Transaction for GremlinTransaction {
             self.long_ids,
             sg,
         )?;
+        let (mut q, p) = GremlinTransaction::add_operations(q, ops, p, sg)?;
 
         q = GremlinTransaction::add_sort_to_query(q, options);
         q.push_str(
@@ -978,13 +1423,7 @@ impl Transaction for GremlinTransaction {
             p
         );
 
-        let param_list: Vec<(&str, &dyn ToGValue)> = p.iter().fold(Vec::new(), |mut pl, (k, v)| {
-            pl.push((k.as_str(), v));
-            pl
-        });
-
-        let raw_results = self.client.execute(q, param_list.as_slice()).await?;
-        let results: Vec<GValue> = raw_results.try_collect().await?;
+        let results = self.run_query("update_rels", q, p).await?;
 
         results
             .into_iter()
@@ -1016,14 +1455,7 @@ impl Transaction for GremlinTransaction {
             params
         );
 
-        let param_list: Vec<(&str, &dyn ToGValue)> =
-            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
-                pl.push((k.as_str(), v));
-                pl
-            });
-
-        let raw_results = self.client.execute(query, param_list.as_slice()).await?;
-        let mut results: Vec<GValue> = raw_results.try_collect().await?;
+        let mut results = self.run_query("delete_nodes", query, params).await?;
 
         Ok(
             TryInto::<i64>::try_into(results.pop().ok_or(Error::ResponseSetNotFound)?)?
@@ -1055,14 +1487,7 @@ impl Transaction for GremlinTransaction {
             params
         );
 
-        let param_list: Vec<(&str, &dyn ToGValue)> =
-            params.iter().fold(Vec::new(), |mut pl, (k, v)| {
-                pl.push((k.as_str(), v));
-                pl
-            });
-
-        let raw_results = self.client.execute(query, param_list.as_slice()).await?;
-        let mut results: Vec<GValue> = raw_results.try_collect().await?;
+        let mut results = self.run_query("delete_rels", query, params).await?;
 
         Ok(
             TryInto::<i64>::try_into(results.pop().ok_or(Error::ResponseSetNotFound)?)?
@@ -1071,7 +1496,7 @@ impl Transaction for GremlinTransaction {
     }
 
     async fn commit(&mut self) -> Result<(), Error> {
-        if self.sessions {
+        if self.sessions && self.statement_log.is_none() {
             self.client
                 .close_session()
                 .await
@@ -1094,6 +1519,10 @@ impl ToGValue for Value {
                 a.iter().map(|val| val.to_gvalue()).collect(),
             )),
             Value::Bool(b) => b.to_gvalue(),
+            // Gremlin has no binary type, so bytes are stored as base64-encoded strings
+            Value::Bytes(b) => GValue::String(base64::encode(b)),
+            // Gremlin has no duration type, so durations are stored as milliseconds
+            Value::Duration(d) => GValue::Int64(d.as_millis() as i64),
             Value::Float64(f) => f.to_gvalue(),
             Value::Int64(i) => i.to_gvalue(),
             Value::Map(hm) => GValue::Map(
@@ -1124,34 +1553,26 @@ impl<RequestCtx: RequestContext> TryFrom<(GValue, &Info)> for Node<RequestCtx> {
                     })?
                     .clone(),
             )?;
+            let nt = value.1.type_def_by_name(&label)?;
             let properties = map
                 .into_iter()
                 .filter(|(k, _v)| k != &GKey::String("label".to_string()))
-                .map(|(k, v)| {
-                    Ok((
-                        k.clone().try_into()?,
-                        (
-                            v,
-                            TryInto::<String>::try_into(k)?.as_str(),
-                            value.1.type_def_by_name(&label)?,
-                        )
-                            .try_into()?,
-                    ))
-                })
+                .map(|(k, v)| Ok((k.try_into()?, v)))
+                .collect::<Result<Vec<(String, GValue)>, Error>>()?
+                .into_iter()
+                .filter(|(k, _v)| !nt.is_unique_shadow(k))
+                .map(|(k, v)| Ok((graphql_name(nt, &k), (v, k.as_str(), nt).try_into()?)))
                 .collect::<Result<HashMap<String, Value>, Error>>()?;
 
             Ok(Node::new(label, properties))
         } else if let GValue::Vertex(vertex) = value.0.clone() {
             let id = vertex.id().to_gvalue().try_into()?;
             let type_name = vertex.label().clone();
+            let nt = value.1.type_def_by_name(&type_name)?;
             let mut properties: HashMap<String, Value> = vertex
                 .into_iter()
-                .map(|(k, v)| {
-                    Ok((
-                        k.clone(),
-                        (v, k.as_str(), value.1.type_def_by_name(&type_name)?).try_into()?,
-                    ))
-                })
+                .filter(|(k, _v)| !nt.is_unique_shadow(k))
+                .map(|(k, v)| Ok((graphql_name(nt, &k), (v, k.as_str(), nt).try_into()?)))
                 .collect::<Result<HashMap<String, Value>, Error>>()?;
             properties.insert("id".to_string(), id);
             Ok(Node::new(type_name, properties))
@@ -1257,7 +1678,8 @@ impl TryFrom<(Vec<VertexProperty>, &str, &NodeType)> for Value {
     type Error = crate::Error;
 
     fn try_from(value: (Vec<VertexProperty>, &str, &NodeType)) -> Result<Self, Error> {
-        if !value.2.property(value.1)?.list() {
+        let p = value.2.db_property(value.1)?;
+        let v = if !p.list() {
             value
                 .0
                 .into_iter()
@@ -1276,6 +1698,12 @@ impl TryFrom<(Vec<VertexProperty>, &str, &NodeType)> for Value {
                     .map(|val| val.value().clone().try_into())
                     .collect::<Result<Vec<Value>, Error>>()?,
             ))
+        }?;
+
+        match p.type_name() {
+            "Base64" => Ok(v.decode_base64()?),
+            "Duration" => Ok(v.millis_to_duration()),
+            _ => Ok(v),
         }
     }
 }
@@ -1288,27 +1716,149 @@ impl TryFrom<(GValue, &str, &NodeType)> for Value {
             Ok(Value::String(
                 TryInto::<Value>::try_into(value.0)?.to_string(),
             ))
-        } else if let GValue::List(list) = value.0 {
-            if !value.2.property(value.1)?.list() {
-                list.into_iter()
-                    .next()
-                    .ok_or(Error::ResponseItemNotFound {
-                        name: value.1.to_string(),
-                    })?
-                    .try_into()
-            } else {
-                Ok(Value::Array(
+        } else {
+            let type_name = value
+                .2
+                .db_property(value.1)
+                .map(|p| p.type_name().to_string())
+                .unwrap_or_default();
+            let v = if let GValue::List(list) = value.0 {
+                if !value.2.db_property(value.1)?.list() {
                     list.into_iter()
-                        .map(|val| val.try_into())
-                        .collect::<Result<Vec<Value>, Error>>()?,
-                ))
+                        .next()
+                        .ok_or(Error::ResponseItemNotFound {
+                            name: value.1.to_string(),
+                        })?
+                        .try_into()
+                } else {
+                    Ok(Value::Array(
+                        list.into_iter()
+                            .map(|val| val.try_into())
+                            .collect::<Result<Vec<Value>, Error>>()?,
+                    ))
+                }
+            } else {
+                value.0.try_into()
+            }?;
+
+            match type_name.as_str() {
+                "Base64" => Ok(v.decode_base64()?),
+                "Duration" => Ok(v.millis_to_duration()),
+                _ => Ok(v),
             }
-        } else {
-            value.0.try_into()
         }
     }
 }
 
+/// Returns a map of the `sum`, `avg`, `min`, and `max` of the given numeric values, matching the
+/// results of the Cypher aggregation functions. The sum of no values is zero, and the other
+/// aggregates of no values are null.
+fn aggregate_values(values: Vec<Value>) -> Value {
+    let as_f64 = |v: &Value| TryInto::<f64>::try_into(v.clone()).ok();
+    let numbers: Vec<f64> = values.iter().filter_map(as_f64).collect();
+
+    let sum = if values.iter().all(|v| matches!(v, Value::Int64(_))) {
+        Value::Int64(values.iter().fold(0, |acc, v| match v {
+            Value::Int64(i) => acc + i,
+            _ => acc,
+        }))
+    } else {
+        Value::Float64(numbers.iter().sum())
+    };
+    let avg = if numbers.is_empty() {
+        Value::Null
+    } else {
+        Value::Float64(numbers.iter().sum::<f64>() / numbers.len() as f64)
+    };
+    let pick = |better: fn(f64, f64) -> bool| {
+        values
+            .iter()
+            .filter_map(|v| as_f64(v).map(|f| (f, v)))
+            .fold(None, |acc: Option<(f64, &Value)>, (f, v)| match acc {
+                Some((af, _)) if !better(f, af) => acc,
+                _ => Some((f, v)),
+            })
+            .map_or(Value::Null, |(_, v)| v.clone())
+    };
+
+    let mut aggregates = HashMap::new();
+    aggregates.insert("sum".to_string(), sum);
+    aggregates.insert("avg".to_string(), avg);
+    aggregates.insert("min".to_string(), pick(|f, af| f < af));
+    aggregates.insert("max".to_string(), pick(|f, af| f > af));
+    Value::Map(aggregates)
+}
+
+/// Converts a value returned by a write query to a [`Value`], replacing vertices and edges with
+/// their ids
+fn write_result_value(gvalue: GValue) -> Result<Value, Error> {
+    match gvalue {
+        GValue::Vertex(v) => Ok(Value::String(
+            TryInto::<Value>::try_into(v.id().to_gvalue())?.to_string(),
+        )),
+        GValue::Edge(e) => Ok(Value::String(
+            TryInto::<Value>::try_into(e.id().to_gvalue())?.to_string(),
+        )),
+        GValue::List(l) => Ok(Value::Array(
+            l.into_iter()
+                .map(write_result_value)
+                .collect::<Result<Vec<Value>, Error>>()?,
+        )),
+        _ => gvalue.try_into(),
+    }
+}
+
+/// Converts a value returned by a query run with `execute_query_values`. Vertices and edges are
+/// converted to maps of their ids and properties, with a property holding several values
+/// converted to an array. Lists, sets, and maps are converted value by value.
+fn query_result_value(gvalue: GValue) -> Result<Value, Error> {
+    match gvalue {
+        GValue::Vertex(v) => {
+            let mut props = v
+                .iter()
+                .map(|(k, vps)| {
+                    let mut values = vps
+                        .iter()
+                        .map(|vp| query_result_value(vp.value().clone()))
+                        .collect::<Result<Vec<Value>, Error>>()?;
+                    let value = if values.len() == 1 {
+                        values.remove(0)
+                    } else {
+                        Value::Array(values)
+                    };
+                    Ok((k.to_string(), value))
+                })
+                .collect::<Result<HashMap<String, Value>, Error>>()?;
+            props.insert("id".to_string(), v.id().to_gvalue().try_into()?);
+            Ok(Value::Map(props))
+        }
+        GValue::Edge(e) => {
+            let mut props = e
+                .iter()
+                .map(|(k, p)| Ok((k.to_string(), query_result_value(p.value().clone())?)))
+                .collect::<Result<HashMap<String, Value>, Error>>()?;
+            props.insert("id".to_string(), e.id().to_gvalue().try_into()?);
+            Ok(Value::Map(props))
+        }
+        GValue::List(l) => Ok(Value::Array(
+            l.into_iter()
+                .map(query_result_value)
+                .collect::<Result<Vec<Value>, Error>>()?,
+        )),
+        GValue::Set(s) => Ok(Value::Array(
+            s.into_iter()
+                .map(query_result_value)
+                .collect::<Result<Vec<Value>, Error>>()?,
+        )),
+        GValue::Map(m) => Ok(Value::Map(
+            m.into_iter()
+                .map(|(k, v)| Ok((k.try_into()?, query_result_value(v)?)))
+                .collect::<Result<HashMap<String, Value>, Error>>()?,
+        )),
+        _ => gvalue.try_into(),
+    }
+}
+
 impl TryFrom<GValue> for Value {
     type Error = Error;
 
@@ -1428,6 +1978,16 @@ impl TryFrom<VertexProperty> for Value {
     }
 }
 
+/// Returns the step selecting a page of traversal results, or an empty string if every result is
+/// selected
+fn page_step(skip: usize, limit: Option<usize>) -> String {
+    match limit {
+        Some(limit) => format!(".range({}, {})", skip, skip + limit),
+        None if skip > 0 => format!(".skip({})", skip),
+        None => String::new(),
+    }
+}
+
 fn gremlin_comparison_operator(c: &Comparison) -> String {
     match (&c.operation, &c.negated) {
         (Operation::EQ, false) => "eq".to_string(),
@@ -1445,10 +2005,13 @@ fn gremlin_comparison_operator(c: &Comparison) -> String {
 
 #[cfg(test)]
 mod tests {
+    use super::aggregate_values;
+    use super::page_step;
     use super::GremlinEndpoint;
     use super::GremlinTransaction;
-    use crate::engine::database::SuffixGenerator;
-    use crate::Value;
+    use crate::engine::database::{DatabaseEndpoint, PropOperations, SuffixGenerator};
+    use crate::{Error, Value};
+    use gremlin_client::GraphSON;
     use maplit::hashmap;
     use std::collections::HashMap;
 
@@ -1459,6 +2022,31 @@ mod tests {
         assert_send::<GremlinEndpoint>();
     }
 
+    /// Passes if turning off the test on acquire, which the Gremlin client can't do, is rejected
+    /// before any connection is made
+    #[tokio::test]
+    async fn test_gremlin_endpoint_test_on_acquire_off() {
+        let ge = GremlinEndpoint {
+            host: "localhost".to_string(),
+            read_replica: "localhost".to_string(),
+            port: 8182,
+            user: None,
+            pass: None,
+            use_tls: false,
+            validate_certs: true,
+            long_ids: false,
+            sessions: false,
+            version: GraphSON::V3,
+            pool_size: 1,
+            test_on_acquire: true,
+        }
+        .with_test_on_acquire(false);
+        assert!(matches!(
+            ge.pool().await,
+            Err(Error::PoolOptionNotSupported { option }) if option == "test_on_acquire = false"
+        ));
+    }
+
     #[cfg(feature = "gremlin")]
     #[test]
     fn test_gremlin_endpoint_sync() {
@@ -1512,6 +2100,71 @@ mod tests {
         );
     }
 
+    /// Passes if a property set to null is dropped by an update, and left out of a create
+    #[test]
+    fn test_add_properties_null() {
+        let (q, p) = GremlinTransaction::add_properties(
+            String::new(),
+            hashmap! {"my_prop".to_string() => Value::Null},
+            HashMap::new(),
+            true,
+            false,
+            false,
+            &mut SuffixGenerator::new(),
+        )
+        .unwrap();
+
+        assert_eq!(".sideEffect(properties('my_prop').drop())".to_string(), q);
+        assert!(p.is_empty());
+
+        let (q, p) = GremlinTransaction::add_properties(
+            String::new(),
+            hashmap! {"my_prop".to_string() => Value::Null},
+            HashMap::new(),
+            true,
+            true,
+            false,
+            &mut SuffixGenerator::new(),
+        )
+        .unwrap();
+
+        assert_eq!(String::new(), q);
+        assert!(p.is_empty());
+    }
+
+    /// Passes if an increment adds its parameter to the property, starting from zero when the
+    /// property is missing, and a push is rejected
+    #[test]
+    fn test_add_operations() {
+        let (q, p) = GremlinTransaction::add_operations(
+            String::new(),
+            PropOperations::new(
+                hashmap! {"hours".to_string() => Value::Int64(2)},
+                HashMap::new(),
+            ),
+            HashMap::new(),
+            &mut SuffixGenerator::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            ".property('hours', union(coalesce(values('hours'), constant(0)), constant(hours_0)).sum())",
+            q
+        );
+        assert_eq!(p.get("hours_0"), Some(&Value::Int64(2)));
+
+        assert!(GremlinTransaction::add_operations(
+            String::new(),
+            PropOperations::new(
+                HashMap::new(),
+                hashmap! {"tags".to_string() => Value::Array(vec![Value::Int64(1)])},
+            ),
+            HashMap::new(),
+            &mut SuffixGenerator::new(),
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_add_properties_scalar() {
         let s1 = Value::String("String one".to_string());
@@ -1534,4 +2187,49 @@ mod tests {
             p.get(&"my_prop_0".to_string()).unwrap()
         );
     }
+
+    /// Passes if aggregates of integers keep integer sums, minimums, and maximums, and aggregates
+    /// of no values match those returned by Cypher
+    #[test]
+    fn test_aggregate_values() {
+        assert_eq!(
+            aggregate_values(vec![Value::Int64(3), Value::Int64(1), Value::Int64(5)]),
+            Value::Map(hashmap! {
+                "sum".to_string() => Value::Int64(9),
+                "avg".to_string() => Value::Float64(3.0),
+                "min".to_string() => Value::Int64(1),
+                "max".to_string() => Value::Int64(5),
+            })
+        );
+
+        assert_eq!(
+            aggregate_values(vec![Value::Float64(1.5), Value::Int64(2)]),
+            Value::Map(hashmap! {
+                "sum".to_string() => Value::Float64(3.5),
+                "avg".to_string() => Value::Float64(1.75),
+                "min".to_string() => Value::Float64(1.5),
+                "max".to_string() => Value::Int64(2),
+            })
+        );
+
+        assert_eq!(
+            aggregate_values(Vec::new()),
+            Value::Map(hashmap! {
+                "sum".to_string() => Value::Int64(0),
+                "avg".to_string() => Value::Null,
+                "min".to_string() => Value::Null,
+                "max".to_string() => Value::Null,
+            })
+        );
+    }
+
+    /// Passes if a page of results is selected with a range or skip step, which is left out when
+    /// every result is selected
+    #[test]
+    fn test_page_step() {
+        assert_eq!(page_step(0, None), "");
+        assert_eq!(page_step(2, None), ".skip(2)");
+        assert_eq!(page_step(0, Some(5)), ".range(0, 5)");
+        assert_eq!(page_step(2, Some(5)), ".range(2, 7)");
+    }
 }
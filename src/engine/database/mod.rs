@@ -1,5 +1,7 @@
 //! Traits and helper structs for interacting with the graph storage database
 
+#[cfg(all(feature = "cypher", feature = "gremlin"))]
+pub mod any;
 #[cfg(feature = "cypher")]
 pub mod cypher;
 #[cfg(feature = "gremlin")]
@@ -7,9 +9,11 @@ pub mod gremlin;
 pub mod no_database;
 
 use crate::engine::context::RequestContext;
-use crate::engine::loader::{NodeLoaderKey, RelLoaderKey};
+use crate::engine::loader::{AggregateLoaderKey, NodeLoaderKey, RelLoaderKey};
 use crate::engine::objects::{Node, Options, Rel};
 use crate::engine::schema::Info;
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
+use crate::engine::schema::NodeType;
 use crate::engine::value::Value;
 use crate::error::Error;
 use async_trait::async_trait;
@@ -17,13 +21,20 @@ use async_trait::async_trait;
 use bolt_proto::message::Record;
 #[cfg(feature = "gremlin")]
 use gremlin_client::GValue;
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
+use log::{debug, warn};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 #[cfg(any(feature = "gremlin", feature = "cypher"))]
 use std::env::var_os;
 use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
+use std::time::Instant;
 
-#[cfg(feature = "gremlin")]
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
 pub fn env_bool(var_name: &str) -> Result<bool, Error> {
     Ok(env_string(var_name)?.parse::<bool>()?)
 }
@@ -155,6 +166,351 @@ pub trait DatabasePool: Clone + Sync + Send {
     /// # }
     /// ```
     async fn transaction(&self) -> Result<Self::TransactionType, Error>;
+
+    /// Returns a copy of the pool whose transactions record the statements they would send to
+    /// the database in the [`StatementLog`], instead of executing them. Used by
+    /// [`Engine::explain`] to show the database queries generated for a GraphQL operation. A
+    /// transaction of the returned pool must not write to the database, since explaining a
+    /// mutation would otherwise run it.
+    ///
+    /// # Errors
+    ///
+    /// The default implementation returns an [`Error`] variant [`ExplainNotSupported`], so that
+    /// [`Engine::explain`] refuses to run against a back-end that can't record statements,
+    /// rather than executing them.
+    ///
+    /// [`Engine::explain`]: ../struct.Engine.html#method.explain
+    /// [`Error`]: ../../enum.Error.html
+    /// [`ExplainNotSupported`]: ../../enum.Error.html#variant.ExplainNotSupported
+    /// [`StatementLog`]: ./struct.StatementLog.html
+    fn with_statement_log(&self, _log: StatementLog) -> Result<Self, Error> {
+        Err(Error::ExplainNotSupported)
+    }
+
+    /// Returns a copy of the pool whose transactions log a warning for every database query that
+    /// takes longer than `threshold` to complete. The warning names the [`Transaction`] method
+    /// that issued the query and how long it took, and the query text is logged at debug level.
+    /// Passing `None` turns slow query logging off, which is the default.
+    ///
+    /// The default implementation returns an unmodified copy of the pool, so a back-end that does
+    /// not override it never logs slow queries.
+    ///
+    /// [`Transaction`]: ./trait.Transaction.html
+    fn with_slow_query_threshold(&self, _threshold: Option<Duration>) -> Self {
+        self.clone()
+    }
+
+    /// Returns a copy of the pool whose transactions run each database query with profiling, and
+    /// record the profile of each query in the [`ProfileLog`]. Used to profile mutations that
+    /// request it with `options: { profile: true }`. Profiled queries are otherwise executed and
+    /// committed or rolled back as usual.
+    ///
+    /// Profiling is optional for a back-end. The default implementation returns an unmodified copy
+    /// of the pool, so a mutation that asks for a profile still runs, and the response has no
+    /// `_profile` field. Only the Cypher back-end records profiles, by running each query
+    /// prefixed with `PROFILE`.
+    ///
+    /// [`ProfileLog`]: ./struct.ProfileLog.html
+    fn with_profile_log(&self, _log: ProfileLog) -> Self {
+        self.clone()
+    }
+
+    /// Returns a copy of the pool whose transactions run against the named database, for
+    /// back-ends that host several databases behind one endpoint, such as Neo4j 4.0 and later.
+    /// Passing `None` uses the server's default database, which is the default. The engine
+    /// calls this for each request with the database selected by the request's
+    /// [`RequestContext`], so that one engine can serve several tenant databases.
+    ///
+    /// The default implementation returns an unmodified copy of the pool, so a back-end that does
+    /// not override it ignores the database name and always uses its single database.
+    ///
+    /// [`RequestContext`]: ../context/trait.RequestContext.html
+    fn with_database(&self, _database: Option<String>) -> Self {
+        self.clone()
+    }
+
+    /// Returns a copy of the pool whose transactions pass every generated query, along with its
+    /// parameters, through the [`QueryInterceptor`] before sending it to the database. Passing
+    /// `None` removes any interceptor, which is the default.
+    ///
+    /// The default implementation returns an unmodified copy of the pool, so a back-end that does
+    /// not override it never calls the interceptor.
+    ///
+    /// [`QueryInterceptor`]: ./trait.QueryInterceptor.html
+    fn with_query_interceptor(&self, _interceptor: Option<Arc<dyn QueryInterceptor>>) -> Self {
+        self.clone()
+    }
+
+    /// Closes the pool's connections to the database, when the engine is shut down. Connections
+    /// that are idle are closed at once, and connections in use are closed as they are returned
+    /// to the pool. Clones of the pool, such as those returned by [`with_database`], share its
+    /// connections and are closed with it. Called by [`Engine::shutdown`] once no requests are
+    /// in flight.
+    ///
+    /// The default implementation does nothing, leaving the connections to be closed when the
+    /// pool is dropped.
+    ///
+    /// [`Engine::shutdown`]: ../struct.Engine.html#method.shutdown
+    /// [`with_database`]: #method.with_database
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the connections cannot be closed. The specific [`Error`] variant
+    /// depends on the database back-end.
+    ///
+    /// [`Error`]: ../../enum.Error.html
+    async fn close(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Rewrites or augments database queries after Warpgrapher has generated them and before they
+/// are sent to the database.
+///
+/// The interceptor is called from within the [`Transaction`] for every query, once the query
+/// visitors have assembled the complete query text and parameters, so it sees exactly what would
+/// otherwise be sent to the database. `operation` names the [`Transaction`] method issuing the
+/// query, such as `read_nodes` or `create_node`. The interceptor returns the query text and
+/// parameters to run in place of the originals. Returning an [`Error`] aborts the query, and the
+/// error is returned to the client.
+///
+/// Interceptors work on raw query text in the database's own query language, Cypher or Gremlin,
+/// and so are tied to the back-end in use. Warpgrapher does not validate the rewritten query. A
+/// rewrite that changes what the query returns, for example by renaming the variables in its
+/// `RETURN` clause, breaks the parsing of the results. The text of generated queries is not part
+/// of Warpgrapher's stable interface and may change between releases, so rewrites should be as
+/// narrow as possible. Values from the request should be passed as parameters rather than
+/// concatenated into the query text, to avoid query injection. When explaining a request, the
+/// statement log records the rewritten query.
+///
+/// [`Error`]: ../../enum.Error.html
+/// [`Transaction`]: ./trait.Transaction.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use warpgrapher::Error;
+/// # use warpgrapher::engine::database::QueryInterceptor;
+/// # use warpgrapher::engine::value::Value;
+///
+/// struct ReadLogger;
+///
+/// impl QueryInterceptor for ReadLogger {
+///     fn intercept(
+///         &self,
+///         operation: &str,
+///         query: String,
+///         params: HashMap<String, Value>,
+///     ) -> Result<(String, HashMap<String, Value>), Error> {
+///         if operation == "read_nodes" {
+///             println!("Reading nodes: {}", query);
+///         }
+///         Ok((query, params))
+///     }
+/// }
+/// ```
+pub trait QueryInterceptor: Send + Sync {
+    /// Returns the query text and parameters to send to the database in place of `query` and
+    /// `params`, which were generated by the [`Transaction`] method named by `operation`.
+    ///
+    /// [`Transaction`]: ./trait.Transaction.html
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the query should not be run. The error is returned to the client.
+    ///
+    /// [`Error`]: ../../enum.Error.html
+    fn intercept(
+        &self,
+        operation: &str,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<(String, HashMap<String, Value>), Error>;
+}
+
+/// Returns the name of the property of the node type `nt` that is stored in the database under
+/// `db_name`, or `db_name` itself if no property of the type is stored under that name
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
+pub(crate) fn graphql_name(nt: &NodeType, db_name: &str) -> String {
+    nt.db_property(db_name)
+        .map(|p| p.name())
+        .unwrap_or(db_name)
+        .to_string()
+}
+
+/// Logs a warning if a query issued by `operation` at `start` has run for longer than
+/// `threshold`, with the query text at debug level. Returns true if the query was slow.
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
+pub(crate) fn log_slow_query(
+    threshold: Option<Duration>,
+    operation: &str,
+    start: Instant,
+    query: &str,
+) -> bool {
+    let elapsed = start.elapsed();
+    match threshold {
+        Some(t) if elapsed > t => {
+            warn!(
+                "Slow query in {} -- elapsed: {:?}, threshold: {:?}",
+                operation, elapsed, t
+            );
+            debug!("Slow query in {} -- query: {}", operation, query);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Collects the database statements generated while explaining a GraphQL operation. Clones share
+/// the same underlying list of statements.
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::database::StatementLog;
+///
+/// let log = StatementLog::new();
+/// log.record("MATCH (n) RETURN n".to_string());
+/// assert_eq!(log.statements(), vec!["MATCH (n) RETURN n".to_string()]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StatementLog {
+    statements: Arc<Mutex<Vec<String>>>,
+    plans: bool,
+}
+
+impl StatementLog {
+    /// Returns a new, empty statement log
+    pub fn new() -> Self {
+        StatementLog::default()
+    }
+
+    /// Sets whether back-ends that support it should ask the database to plan each statement,
+    /// without executing it, and record the plan alongside the statement. Only the Cypher
+    /// back-end produces plans, by running each statement prefixed with `EXPLAIN`. Doing so
+    /// requires a database connection.
+    pub fn with_plans(mut self, plans: bool) -> Self {
+        self.plans = plans;
+        self
+    }
+
+    /// Returns true if the log should include database query plans
+    pub fn plans(&self) -> bool {
+        self.plans
+    }
+
+    /// Adds a statement to the log
+    pub fn record(&self, statement: String) {
+        self.statements
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(statement);
+    }
+
+    /// Returns the statements recorded so far, in the order they were generated
+    pub fn statements(&self) -> Vec<String> {
+        self.statements
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+/// The profile of a single database query, run while profiling a mutation. `db_hits` is the
+/// total number of database hits across all of the operators in the query plan, and `rows` is
+/// the number of rows produced by the query.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryProfile {
+    operation: String,
+    query: String,
+    db_hits: i64,
+    rows: i64,
+}
+
+impl QueryProfile {
+    /// Returns a new profile of the `query` issued by the [`Transaction`] method named
+    /// `operation`
+    ///
+    /// [`Transaction`]: ./trait.Transaction.html
+    pub fn new(operation: String, query: String, db_hits: i64, rows: i64) -> Self {
+        QueryProfile {
+            operation,
+            query,
+            db_hits,
+            rows,
+        }
+    }
+
+    /// Returns the name of the [`Transaction`] method that issued the query
+    ///
+    /// [`Transaction`]: ./trait.Transaction.html
+    pub fn operation(&self) -> &str {
+        &self.operation
+    }
+
+    /// Returns the text of the query
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Returns the total number of database hits made by the query
+    pub fn db_hits(&self) -> i64 {
+        self.db_hits
+    }
+
+    /// Returns the number of rows produced by the query
+    pub fn rows(&self) -> i64 {
+        self.rows
+    }
+}
+
+/// Collects the [`QueryProfile`]s of the database queries run by a profiled mutation. Clones
+/// share the same underlying list of profiles.
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::database::{ProfileLog, QueryProfile};
+///
+/// let log = ProfileLog::new();
+/// log.record(QueryProfile::new(
+///     "create_node".to_string(),
+///     "CREATE (n:Project) RETURN n".to_string(),
+///     2,
+///     1,
+/// ));
+/// assert_eq!(log.profiles()[0].db_hits(), 2);
+/// ```
+///
+/// [`QueryProfile`]: ./struct.QueryProfile.html
+#[derive(Clone, Debug, Default)]
+pub struct ProfileLog {
+    profiles: Arc<Mutex<Vec<QueryProfile>>>,
+}
+
+impl ProfileLog {
+    /// Returns a new, empty profile log
+    pub fn new() -> Self {
+        ProfileLog::default()
+    }
+
+    /// Adds a query profile to the log
+    pub fn record(&self, profile: QueryProfile) {
+        self.profiles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(profile);
+    }
+
+    /// Returns the profiles recorded so far, in the order the queries were run
+    pub fn profiles(&self) -> Vec<QueryProfile> {
+        self.profiles
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
 }
 
 #[async_trait]
@@ -167,6 +523,45 @@ pub trait Transaction: Send + Sync {
         params: HashMap<String, Value>,
     ) -> Result<QueryResult, Error>;
 
+    /// Executes several queries in order within the transaction, and returns the result of each
+    /// query, in the same order. Back-ends whose driver supports pipelining send all of the
+    /// queries to the database in one network round trip, rather than waiting for the result of
+    /// each query before sending the next, which saves the latency of a round trip per query when
+    /// issuing many small writes. Other back-ends run the queries one after another. If a query
+    /// fails, the queries after it are not run, and the error is returned.
+    async fn execute_batch<RequestCtx: RequestContext>(
+        &mut self,
+        statements: Vec<(String, HashMap<String, Value>)>,
+    ) -> Result<Vec<QueryResult>, Error> {
+        let mut results = Vec::with_capacity(statements.len());
+        for (query, params) in statements {
+            results.push(self.execute_query::<RequestCtx>(query, params).await?);
+        }
+        Ok(results)
+    }
+
+    /// Executes a write query and returns the values it returns, in a form that is the same
+    /// regardless of the database back-end. Scalar values are returned as they are, and any node
+    /// or relationship returned by the query is replaced by its id, so a query that creates a node
+    /// and returns it yields the id of the created node.
+    async fn execute_write(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error>;
+
+    /// Executes a query and returns one value per record or result, in a form that is the same
+    /// regardless of the database back-end. A Cypher record with a single column yields the value
+    /// of that column, and a record with several columns yields an array of their values, in
+    /// order. Each Gremlin result yields one value. Nodes and relationships are returned as maps
+    /// of their properties, with their ids under `id`, and maps and lists are converted value by
+    /// value.
+    async fn execute_query_values(
+        &mut self,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error>;
+
     async fn create_node<RequestCtx: RequestContext>(
         &mut self,
         node_var: &NodeQueryVar,
@@ -216,6 +611,19 @@ pub trait Transaction: Send + Sync {
         info: &Info,
     ) -> Result<Vec<Node<RequestCtx>>, Error>;
 
+    async fn node_exists(
+        &mut self,
+        node_var: &NodeQueryVar,
+        query_fragment: QueryFragment,
+    ) -> Result<bool, Error>;
+
+    async fn read_computed_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        query: String,
+        src_id: Value,
+        info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error>;
+
     fn rel_read_by_ids_fragment<RequestCtx: RequestContext>(
         &mut self,
         rel_var: &RelQueryVar,
@@ -236,6 +644,26 @@ pub trait Transaction: Send + Sync {
         keys: &[RelLoaderKey],
     ) -> Result<Vec<Rel<RequestCtx>>, Error>;
 
+    /// Counts the relationships from the source node of each key, returning the counts in the
+    /// same order as the keys
+    async fn count_rels(&mut self, keys: &[RelLoaderKey]) -> Result<Vec<i64>, Error>;
+
+    /// Counts the relationships matched by a query fragment, without returning them
+    async fn count_matching_rels(
+        &mut self,
+        query_fragment: QueryFragment,
+        rel_var: &RelQueryVar,
+    ) -> Result<i64, Error>;
+
+    /// Aggregates the relationships from the source node of each key, returning the aggregates in
+    /// the same order as the keys. Each key's aggregates hold the number of relationships under
+    /// `count` and, under the database name of each of the key's properties, a map of the `sum`,
+    /// `avg`, `min`, and `max` of the property's values.
+    async fn aggregate_rels(
+        &mut self,
+        keys: &[AggregateLoaderKey],
+    ) -> Result<Vec<HashMap<String, Value>>, Error>;
+
     async fn read_rels<RequestCtx: RequestContext>(
         &mut self,
         query_fragment: QueryFragment,
@@ -243,6 +671,9 @@ pub trait Transaction: Send + Sync {
         options: Options,
     ) -> Result<Vec<Rel<RequestCtx>>, Error>;
 
+    /// Sets the given properties on each node matched by the query fragment. A property whose
+    /// value is `Value::Null` is removed from the nodes, while properties not in `props` are left
+    /// as they are.
     async fn update_nodes<RequestCtx: RequestContext>(
         &mut self,
         query_fragment: QueryFragment,
@@ -253,12 +684,15 @@ pub trait Transaction: Send + Sync {
         sg: &mut SuffixGenerator,
     ) -> Result<Vec<Node<RequestCtx>>, Error>;
 
+    /// Sets the given properties on each relationship matched by the query fragment, and then
+    /// applies the atomic operations in `ops` to them.
     #[allow(clippy::too_many_arguments)]
     async fn update_rels<RequestCtx: RequestContext>(
         &mut self,
         query_fragment: QueryFragment,
         rel_var: &RelQueryVar,
         props: HashMap<String, Value>,
+        ops: PropOperations,
         options: Options,
         sg: &mut SuffixGenerator,
     ) -> Result<Vec<Rel<RequestCtx>>, Error>;
@@ -282,6 +716,10 @@ pub trait Transaction: Send + Sync {
     async fn rollback(&mut self) -> Result<(), Error>;
 }
 
+/// The results of a query run with [`Transaction::execute_query`], in the form native to the
+/// database back-end that ran it
+///
+/// [`Transaction::execute_query`]: ./trait.Transaction.html#tymethod.execute_query
 pub enum QueryResult {
     #[cfg(feature = "gremlin")]
     Gremlin(Vec<GValue>),
@@ -290,6 +728,9 @@ pub enum QueryResult {
     Cypher(Vec<Record>),
 
     NoDatabsae(),
+
+    /// The results of a back-end implemented outside of Warpgrapher, one value per record
+    Values(Vec<Value>),
 }
 
 /// Represents the different types of Crud Operations along with the target of the
@@ -326,11 +767,8 @@ pub enum Operation {
 /// it on the database-specific transaction for use in creating match portion of queries.
 #[derive(Clone, Debug)]
 pub struct Comparison {
-    #[allow(dead_code)]
     operation: Operation,
-    #[allow(dead_code)]
     operand: Value,
-    #[allow(dead_code)]
     negated: bool,
 }
 
@@ -346,6 +784,22 @@ impl Comparison {
     pub fn default(v: Value) -> Self {
         Self::new(Operation::EQ, false, v)
     }
+
+    /// Returns the operation used to compare a property to the operand
+    pub fn operation(&self) -> &Operation {
+        &self.operation
+    }
+
+    /// Returns the value to which a property is compared
+    pub fn operand(&self) -> &Value {
+        &self.operand
+    }
+
+    /// Returns true if the comparison matches properties for which the operation is false, as
+    /// for `NOTEQ`, `NOTCONTAINS`, and `NOTIN`
+    pub fn negated(&self) -> bool {
+        self.negated
+    }
 }
 
 impl TryFrom<Value> for Comparison {
@@ -356,6 +810,7 @@ impl TryFrom<Value> for Comparison {
             Value::String(_) => Comparison::default(v),
             Value::Int64(_) => Comparison::default(v),
             Value::Float64(_) => Comparison::default(v),
+            Value::Duration(_) => Comparison::default(v),
             Value::Bool(_) => Comparison::default(v),
             Value::Map(m) => {
                 let (operation_str, operand) =
@@ -393,19 +848,54 @@ impl TryFrom<Value> for Comparison {
     }
 }
 
+/// Atomic operations on the properties of the relationships matched by an update, applied in
+/// the database rather than by reading the properties and writing them back. An operation on a
+/// property that is null or missing starts from zero for `increment`, and from an empty list for
+/// `push`. The operations are applied after any properties set by the same update.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PropOperations {
+    increment: HashMap<String, Value>,
+    push: HashMap<String, Value>,
+}
+
+impl PropOperations {
+    /// Creates a set of operations, adding the amounts in `increment` to numeric properties, and
+    /// appending the lists in `push` to list properties.
+    pub fn new(increment: HashMap<String, Value>, push: HashMap<String, Value>) -> Self {
+        PropOperations { increment, push }
+    }
+
+    /// Returns the amount to add to each numeric property
+    pub fn increment(&self) -> &HashMap<String, Value> {
+        &self.increment
+    }
+
+    /// Returns the list of values to append to each list property
+    pub fn push(&self) -> &HashMap<String, Value> {
+        &self.push
+    }
+
+    /// Returns true if there are no operations to apply
+    pub fn is_empty(&self) -> bool {
+        self.increment.is_empty() && self.push.is_empty()
+    }
+}
+
+/// A part of a database query that matches nodes or relationships, built by a
+/// [`Transaction`] from the input of a GraphQL query, and passed back to the transaction to read,
+/// update, or delete what it matches. The match and where fragments hold text in the back-end's
+/// query language, and the params hold the values of the parameters that the text refers to.
+///
+/// [`Transaction`]: ./trait.Transaction.html
 #[derive(Clone, Debug)]
 pub struct QueryFragment {
-    #[allow(dead_code)]
     match_fragment: String,
-    #[allow(dead_code)]
     where_fragment: String,
-    #[allow(dead_code)]
     params: HashMap<String, Value>,
 }
 
 impl QueryFragment {
-    #[cfg(any(feature = "gremlin", feature = "cypher"))]
-    pub(crate) fn new(
+    pub fn new(
         match_fragment: String,
         where_fragment: String,
         params: HashMap<String, Value>,
@@ -417,36 +907,33 @@ impl QueryFragment {
         }
     }
 
-    #[cfg(feature = "cypher")]
-    pub(crate) fn match_fragment(&self) -> &str {
+    pub fn match_fragment(&self) -> &str {
         &self.match_fragment
     }
 
-    #[cfg(any(feature = "gremlin", feature = "cypher"))]
-    pub(crate) fn where_fragment(&self) -> &str {
+    pub fn where_fragment(&self) -> &str {
         &self.where_fragment
     }
 
-    #[cfg(any(feature = "gremlin", feature = "cypher"))]
-    pub(crate) fn params(self) -> HashMap<String, Value> {
+    pub fn params(self) -> HashMap<String, Value> {
         self.params
     }
 }
 
+/// The variable naming a node in a generated query. The name is the base, such as `node` or
+/// `dst`, followed by a suffix from a [`SuffixGenerator`] that makes it unique in the query.
+///
+/// [`SuffixGenerator`]: ./struct.SuffixGenerator.html
 #[derive(Clone, Debug)]
 pub struct NodeQueryVar {
-    #[allow(dead_code)]
     base: String,
-    #[allow(dead_code)]
     suffix: String,
-    #[allow(dead_code)]
     label: Option<String>,
-    #[allow(dead_code)]
     name: String,
 }
 
 impl NodeQueryVar {
-    pub(crate) fn new(label: Option<String>, base: String, suffix: String) -> NodeQueryVar {
+    pub fn new(label: Option<String>, base: String, suffix: String) -> NodeQueryVar {
         NodeQueryVar {
             base: base.clone(),
             suffix: suffix.clone(),
@@ -455,81 +942,140 @@ impl NodeQueryVar {
         }
     }
 
-    pub(crate) fn base(&self) -> &str {
+    pub fn base(&self) -> &str {
         &self.base
     }
 
-    pub(crate) fn label(&self) -> Result<&str, Error> {
+    /// Returns the type of the node, or an error if the node may be of more than one type, as is
+    /// the destination of a relationship to a union of types before it is matched
+    pub fn label(&self) -> Result<&str, Error> {
         self.label.as_deref().ok_or(Error::LabelNotFound)
     }
 
-    pub(crate) fn suffix(&self) -> &str {
+    pub fn suffix(&self) -> &str {
         &self.suffix
     }
 
-    #[cfg(any(feature = "gremlin", feature = "cypher"))]
-    pub(crate) fn name(&self) -> &str {
+    pub fn name(&self) -> &str {
         &self.name
     }
 }
 
+/// The variable naming a relationship in a generated query, along with the variables of its
+/// source and destination nodes
 #[derive(Clone, Debug)]
 pub struct RelQueryVar {
     label: String,
-    #[allow(dead_code)]
     suffix: String,
-    #[allow(dead_code)]
     name: String,
     src: NodeQueryVar,
     dst: NodeQueryVar,
+    symmetric: bool,
+    inverse: bool,
 }
 
 impl RelQueryVar {
-    pub(crate) fn new(
-        label: String,
-        suffix: String,
-        src: NodeQueryVar,
-        dst: NodeQueryVar,
-    ) -> RelQueryVar {
+    pub fn new(label: String, suffix: String, src: NodeQueryVar, dst: NodeQueryVar) -> RelQueryVar {
         RelQueryVar {
             label,
             suffix: suffix.clone(),
             name: "rel".to_string() + &*suffix,
             src,
             dst,
+            symmetric: false,
+            inverse: false,
         }
     }
 
-    pub(crate) fn label(&self) -> &str {
+    /// Sets whether the relationship is symmetric, in which case reads match it in either
+    /// direction
+    pub fn with_symmetric(mut self, symmetric: bool) -> RelQueryVar {
+        self.symmetric = symmetric;
+        self
+    }
+
+    /// Sets whether the relationship filters the node being read by the relationship's source
+    /// node, in which case the node being read is the destination of the relationship rather
+    /// than its source
+    pub fn with_inverse(mut self, inverse: bool) -> RelQueryVar {
+        self.inverse = inverse;
+        self
+    }
+
+    pub fn label(&self) -> &str {
         &self.label
     }
 
-    #[cfg(any(feature = "cypher"))]
-    pub(crate) fn name(&self) -> &str {
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    pub fn name(&self) -> &str {
         &self.name
     }
 
-    pub(crate) fn src(&self) -> &NodeQueryVar {
+    pub fn src(&self) -> &NodeQueryVar {
         &self.src
     }
 
-    pub(crate) fn dst(&self) -> &NodeQueryVar {
+    pub fn dst(&self) -> &NodeQueryVar {
         &self.dst
     }
+
+    pub fn symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    pub fn inverse(&self) -> bool {
+        self.inverse
+    }
 }
 
+/// Generates the suffixes that make the names of query variables and parameters unique within a
+/// query
 #[derive(Debug, Default)]
 pub struct SuffixGenerator {
     seed: i32,
 }
 
 impl SuffixGenerator {
-    pub(crate) fn new() -> SuffixGenerator {
+    pub fn new() -> SuffixGenerator {
         SuffixGenerator { seed: -1 }
     }
 
-    pub(crate) fn suffix(&mut self) -> String {
+    pub fn suffix(&mut self) -> String {
         self.seed += 1;
         "_".to_string() + &*self.seed.to_string()
     }
 }
+
+#[cfg(test)]
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
+mod tests {
+    use super::log_slow_query;
+    use std::time::{Duration, Instant};
+
+    /// Passes if only queries running longer than a configured threshold are reported as slow
+    #[test]
+    fn test_log_slow_query() {
+        let start = Instant::now() - Duration::from_millis(50);
+        assert!(!log_slow_query(
+            None,
+            "read_nodes",
+            start,
+            "MATCH (n) RETURN n"
+        ));
+        assert!(!log_slow_query(
+            Some(Duration::from_secs(60)),
+            "read_nodes",
+            start,
+            "MATCH (n) RETURN n"
+        ));
+        assert!(log_slow_query(
+            Some(Duration::from_millis(10)),
+            "read_nodes",
+            start,
+            "MATCH (n) RETURN n"
+        ));
+    }
+}
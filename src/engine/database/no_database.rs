@@ -3,10 +3,10 @@
 
 use crate::engine::context::RequestContext;
 use crate::engine::database::{
-    Comparison, DatabaseEndpoint, DatabasePool, NodeQueryVar, QueryFragment, QueryResult,
-    RelQueryVar, SuffixGenerator, Transaction,
+    Comparison, DatabaseEndpoint, DatabasePool, NodeQueryVar, PropOperations, QueryFragment,
+    QueryResult, RelQueryVar, StatementLog, SuffixGenerator, Transaction,
 };
-use crate::engine::loader::{NodeLoaderKey, RelLoaderKey};
+use crate::engine::loader::{AggregateLoaderKey, NodeLoaderKey, RelLoaderKey};
 use crate::engine::objects::{Node, Options, Rel};
 use crate::engine::schema::Info;
 use crate::engine::value::Value;
@@ -39,6 +39,11 @@ impl DatabasePool for NoDatabasePool {
     async fn transaction(&self) -> Result<Self::TransactionType, Error> {
         Ok(NoTransaction {})
     }
+
+    // no transaction ever reaches a database, so there is nothing to keep from executing
+    fn with_statement_log(&self, _log: StatementLog) -> Result<Self, Error> {
+        Ok(self.clone())
+    }
 }
 
 pub struct NoTransaction {}
@@ -57,6 +62,22 @@ impl Transaction for NoTransaction {
         Err(Error::DatabaseNotFound)
     }
 
+    async fn execute_write(
+        &mut self,
+        _query: String,
+        _params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        Err(Error::DatabaseNotFound)
+    }
+
+    async fn execute_query_values(
+        &mut self,
+        _query: String,
+        _params: HashMap<String, Value>,
+    ) -> Result<Vec<Value>, Error> {
+        Err(Error::DatabaseNotFound)
+    }
+
     async fn create_node<RequestCtx: RequestContext>(
         &mut self,
         _node_var: &NodeQueryVar,
@@ -117,6 +138,23 @@ impl Transaction for NoTransaction {
         Err(Error::DatabaseNotFound)
     }
 
+    async fn node_exists(
+        &mut self,
+        _node_var: &NodeQueryVar,
+        _query_fragment: QueryFragment,
+    ) -> Result<bool, Error> {
+        Err(Error::DatabaseNotFound)
+    }
+
+    async fn read_computed_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        _query: String,
+        _src_id: Value,
+        _info: &Info,
+    ) -> Result<Vec<Node<RequestCtx>>, Error> {
+        Err(Error::DatabaseNotFound)
+    }
+
     fn rel_read_by_ids_fragment<RequestCtx: RequestContext>(
         &mut self,
         _rel_var: &RelQueryVar,
@@ -143,6 +181,25 @@ impl Transaction for NoTransaction {
         Err(Error::DatabaseNotFound)
     }
 
+    async fn count_rels(&mut self, _keys: &[RelLoaderKey]) -> Result<Vec<i64>, Error> {
+        Err(Error::DatabaseNotFound)
+    }
+
+    async fn count_matching_rels(
+        &mut self,
+        _query_fragment: QueryFragment,
+        _rel_var: &RelQueryVar,
+    ) -> Result<i64, Error> {
+        Err(Error::DatabaseNotFound)
+    }
+
+    async fn aggregate_rels(
+        &mut self,
+        _keys: &[AggregateLoaderKey],
+    ) -> Result<Vec<HashMap<String, Value>>, Error> {
+        Err(Error::DatabaseNotFound)
+    }
+
     async fn read_rels<RequestCtx: RequestContext>(
         &mut self,
         _query_fragment: QueryFragment,
@@ -169,6 +226,7 @@ impl Transaction for NoTransaction {
         _query_fragment: QueryFragment,
         _rel_var: &RelQueryVar,
         _props: HashMap<String, Value>,
+        _ops: PropOperations,
         _options: Options,
         _sg: &mut SuffixGenerator,
     ) -> Result<Vec<Rel<RequestCtx>>, Error> {
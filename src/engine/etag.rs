@@ -0,0 +1,120 @@
+//! This module provides the entity tags used for HTTP caching of GraphQL responses. Each node
+//! type has an `_etag` field that resolves to the tag of the node, a hash of all of the node's
+//! stored properties, which changes whenever one of them does. A server handler can tag a whole
+//! response with [`etag`], set it as the `ETag` header, and answer a request whose
+//! `If-None-Match` header [`etag_matches`] the tag with a `304 Not Modified`.
+//!
+//! [`etag`]: ./fn.etag.html
+//! [`etag_matches`]: ./fn.etag_matches.html
+use sha1::{Digest, Sha1};
+
+/// Returns the entity tag of a JSON value, such as the body of a GraphQL response or the
+/// properties of a node. The tag is the SHA-1 digest, as lower-case hexadecimal, of the value
+/// serialized with the keys of its objects sorted, so that equal values have the same tag
+/// whatever the order of their keys. The tag is returned without the quotes of the `ETag`
+/// header.
+///
+/// # Examples
+///
+/// ```rust
+/// # use serde_json::json;
+/// # use warpgrapher::engine::etag::etag;
+///
+/// let tag = etag(&json!({"name": "Project Zero", "public": true}));
+///
+/// assert_eq!(tag.len(), 40);
+/// assert_eq!(tag, etag(&json!({"public": true, "name": "Project Zero"})));
+/// assert_ne!(tag, etag(&json!({"name": "Project One", "public": true})));
+/// ```
+pub fn etag(value: &serde_json::Value) -> String {
+    let mut canonical = String::new();
+    write_canonical(value, &mut canonical);
+    Sha1::digest(canonical.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Returns true if the value of an `If-None-Match` header matches the entity tag, so that the
+/// response can be a `304 Not Modified`. The header matches if it is `*`, or if it lists the tag,
+/// with or without quotes or a weak `W/` prefix.
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::etag::etag_matches;
+///
+/// assert!(etag_matches("\"abc\", W/\"def\"", "def"));
+/// assert!(etag_matches("*", "def"));
+/// assert!(!etag_matches("\"abc\"", "def"));
+/// ```
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|tag| {
+        tag == "*"
+            || tag
+                .trim_start_matches("W/")
+                .trim_matches('"')
+                .eq(etag.trim_start_matches("W/").trim_matches('"'))
+    })
+}
+
+/// Writes the value as JSON, with the keys of its objects in sorted order
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Array(a) => {
+            out.push('[');
+            for (i, v) in a.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(v, out);
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(m) => {
+            let mut entries: Vec<(&String, &serde_json::Value)> = m.iter().collect();
+            entries.sort_by_key(|(k, _)| *k);
+            out.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(k.to_string()).to_string());
+                out.push(':');
+                write_canonical(v, out);
+            }
+            out.push('}');
+        }
+        v => out.push_str(&v.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{etag, etag_matches};
+    use serde_json::json;
+
+    /// Passes if the tag doesn't depend on the order of keys, including in nested objects, but
+    /// does depend on the order of list elements
+    #[test]
+    fn etag_key_order() {
+        assert_eq!(
+            etag(&json!({"a": 1, "b": {"c": [1, 2], "d": null}})),
+            etag(&json!({"b": {"d": null, "c": [1, 2]}, "a": 1}))
+        );
+        assert_ne!(etag(&json!({"c": [1, 2]})), etag(&json!({"c": [2, 1]})));
+        assert_ne!(etag(&json!({"a": "1"})), etag(&json!({"a": 1})));
+    }
+
+    /// Passes if strong, weak, unquoted, and wildcard tags in an If-None-Match header match
+    #[test]
+    fn etag_matches_forms() {
+        assert!(etag_matches("\"abc\"", "abc"));
+        assert!(etag_matches("W/\"abc\"", "abc"));
+        assert!(etag_matches("abc", "\"abc\""));
+        assert!(etag_matches("\"xyz\" , \"abc\"", "abc"));
+        assert!(etag_matches("*", "abc"));
+        assert!(!etag_matches("\"abcd\"", "abc"));
+        assert!(!etag_matches("", "abc"));
+    }
+}
@@ -8,6 +8,7 @@ use crate::engine::database::{CrudOperation, Transaction};
 use crate::engine::database::{
     DatabaseEndpoint, DatabasePool, NodeQueryVar, QueryResult, RelQueryVar, SuffixGenerator,
 };
+use crate::engine::metadata::Metadata;
 use crate::engine::objects::resolvers::visitors::{
     visit_node_create_mutation_input, visit_node_delete_input, visit_node_query_input,
     visit_node_update_input, visit_rel_create_input, visit_rel_delete_input, visit_rel_query_input,
@@ -51,7 +52,11 @@ pub type BeforeEngineBuildFunc = fn(&mut Configuration) -> Result<(), Error>;
 
 /// Type alias for a function called before request execution allowing modifications
 /// to the request context. Common use case includes pulling auth tokens from the
-/// metadata and inserting user information into the request context.
+/// metadata and inserting user information into the request context. The [`Metadata`]
+/// has typed getters for common entries, such as [`bearer_token`].
+///
+/// [`bearer_token`]: ../metadata/struct.Metadata.html#method.bearer_token
+/// [`Metadata`]: ../metadata/struct.Metadata.html
 ///
 /// # Examples
 ///
@@ -60,13 +65,13 @@ pub type BeforeEngineBuildFunc = fn(&mut Configuration) -> Result<(), Error>;
 /// # use warpgrapher::engine::events::EventFacade;
 /// # use warpgrapher::engine::value::Value;
 /// # use warpgrapher::juniper::BoxFuture;
-/// # use std::collections::HashMap;
+/// # use warpgrapher::engine::metadata::Metadata;
 /// type Rctx = ();
 ///
 /// fn before_request(
 ///     mut rctx: Rctx,
 ///     mut ef: EventFacade<Rctx>,
-///     metadata: HashMap<String, String>
+///     metadata: Metadata
 /// ) -> BoxFuture<Result<Rctx, warpgrapher::Error>> {
 ///     Box::pin(async move {
 ///         // modify request context
@@ -74,8 +79,7 @@ pub type BeforeEngineBuildFunc = fn(&mut Configuration) -> Result<(), Error>;
 ///     })
 /// }
 /// ```
-pub type BeforeRequestFunc<R> =
-    fn(R, EventFacade<R>, HashMap<String, String>) -> BoxFuture<Result<R, Error>>;
+pub type BeforeRequestFunc<R> = fn(R, EventFacade<R>, Metadata) -> BoxFuture<Result<R, Error>>;
 
 /// Type alias for a function called after request execution allowing modifications
 /// to the output value.
@@ -216,6 +220,56 @@ pub type AfterRelEventFunc<RequestCtx> = fn(
     EventFacade<RequestCtx>,
 ) -> BoxFuture<Result<Vec<Rel<RequestCtx>>, Error>>;
 
+/// Type alias for a function called at the start of each generated resolver to authorize the
+/// operation about to be performed. The function receives the [`CrudOperation`] naming the type
+/// and, for relationships, the relationship affected, along with an [`EventFacade`] that gives
+/// access to the request context. Returning an `Err` rejects the operation, rolling back its
+/// transaction before any other event handlers run.
+///
+/// [`CrudOperation`]: ../database/enum.CrudOperation.html
+/// [`EventFacade`]: ./struct.EventFacade.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::Error;
+/// # use warpgrapher::engine::database::CrudOperation;
+/// # use warpgrapher::engine::events::EventFacade;
+///
+/// fn authorize(op: &CrudOperation, ef: &EventFacade<()>) -> Result<(), Error> {
+///     match op {
+///         CrudOperation::DeleteNode(type_name) if type_name == "User" => {
+///             Err(Error::UserDefinedError {
+///                 source: "Users may not be deleted".into(),
+///             })
+///         }
+///         _ => Ok(()),
+///     }
+/// }
+/// ```
+pub type AuthorizerFunc<RequestCtx> =
+    fn(&CrudOperation, &EventFacade<RequestCtx>) -> Result<(), Error>;
+
+/// Type alias for a function called before a guarded field of a node is resolved, deciding
+/// whether the field is resolved at all. The function receives an [`EventFacade`] whose
+/// operation is a [`CrudOperation::ReadNode`] naming the type that holds the field, and which
+/// gives access to the request context. If the function returns `false`, the field resolves to
+/// null without its resolver being called.
+///
+/// [`CrudOperation::ReadNode`]: ../database/enum.CrudOperation.html#variant.ReadNode
+/// [`EventFacade`]: ./struct.EventFacade.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::events::EventFacade;
+///
+/// fn admin_only(ef: &EventFacade<()>) -> bool {
+///     ef.context().metadata().get_str("role") == Some("admin")
+/// }
+/// ```
+pub type FieldGuardFunc<RequestCtx> = fn(&EventFacade<RequestCtx>) -> bool;
+
 /// Collects event handlers for application during query processing.
 ///
 /// Examples
@@ -241,6 +295,8 @@ pub struct EventHandlerBag<RequestCtx: RequestContext> {
     before_engine_build_handlers: Vec<BeforeEngineBuildFunc>,
     before_request_handlers: Vec<BeforeRequestFunc<RequestCtx>>,
     after_request_handlers: Vec<AfterRequestFunc<RequestCtx>>,
+    authorizers: Vec<AuthorizerFunc<RequestCtx>>,
+    field_guards: HashMap<(String, String), Vec<FieldGuardFunc<RequestCtx>>>,
     before_create_handlers: HashMap<String, Vec<BeforeMutationEventFunc<RequestCtx>>>,
     after_node_create_handlers: HashMap<String, Vec<AfterNodeEventFunc<RequestCtx>>>,
     after_subgraph_create_handlers: HashMap<String, Vec<AfterNodeEventFunc<RequestCtx>>>,
@@ -256,6 +312,7 @@ pub struct EventHandlerBag<RequestCtx: RequestContext> {
     before_delete_handlers: HashMap<String, Vec<BeforeMutationEventFunc<RequestCtx>>>,
     after_node_delete_handlers: HashMap<String, Vec<AfterNodeEventFunc<RequestCtx>>>,
     after_rel_delete_handlers: HashMap<String, Vec<AfterRelEventFunc<RequestCtx>>>,
+    input_normalizers: HashMap<String, Vec<BeforeMutationEventFunc<RequestCtx>>>,
 }
 
 impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
@@ -273,6 +330,8 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
             before_engine_build_handlers: vec![],
             before_request_handlers: vec![],
             after_request_handlers: vec![],
+            authorizers: vec![],
+            field_guards: HashMap::new(),
             before_create_handlers: HashMap::new(),
             after_node_create_handlers: HashMap::new(),
             after_subgraph_create_handlers: HashMap::new(),
@@ -288,6 +347,7 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
             before_delete_handlers: HashMap::new(),
             after_node_delete_handlers: HashMap::new(),
             after_rel_delete_handlers: HashMap::new(),
+            input_normalizers: HashMap::new(),
         }
     }
 
@@ -318,7 +378,7 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
     /// # Examples
     ///
     /// ```rust
-    /// # use std::collections::HashMap;
+    /// # use warpgrapher::engine::metadata::Metadata;
     /// # use warpgrapher::engine::events::{EventHandlerBag, EventFacade};
     /// # use warpgrapher::engine::config::Configuration;
     /// # use warpgrapher::Error;
@@ -329,7 +389,7 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
     /// fn before_request(
     ///     mut rctx: Rctx,
     ///     mut ef: EventFacade<Rctx>,
-    ///     metadata: HashMap<String, String>
+    ///     metadata: Metadata
     /// ) -> BoxFuture<Result<Rctx, warpgrapher::Error>> {
     ///     Box::pin(async move {
     ///         // modify request context
@@ -373,6 +433,65 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
         self.after_request_handlers.push(f);
     }
 
+    /// Registers an authorizer `f` to be called at the start of each generated resolver, before
+    /// any before event handlers. Authorizers are called in the order in which they are
+    /// registered, and the first to return an `Err` rejects the operation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::Error;
+    /// # use warpgrapher::engine::database::CrudOperation;
+    /// # use warpgrapher::engine::events::{EventHandlerBag, EventFacade};
+    ///
+    /// fn read_only(op: &CrudOperation, ef: &EventFacade<()>) -> Result<(), Error> {
+    ///     match op {
+    ///         CrudOperation::ReadNode(_) | CrudOperation::ReadRel(_, _) => Ok(()),
+    ///         _ => Err(Error::UserDefinedError {
+    ///             source: "Only reads are permitted".into(),
+    ///         }),
+    ///     }
+    /// }
+    ///
+    /// let mut handlers = EventHandlerBag::<()>::new();
+    /// handlers.register_authorizer(read_only);
+    /// ```
+    pub fn register_authorizer(&mut self, f: AuthorizerFunc<RequestCtx>) {
+        self.authorizers.push(f);
+    }
+
+    /// Registers a guard `f` on the field `field_name` of the type `type_name`. Before the field
+    /// is resolved, each guard registered on it is called, and if any returns `false`, the field
+    /// resolves to null without its resolver being called, so a caller who may not see an
+    /// expensive field doesn't pay for resolving it. Guards apply to every kind of field on a
+    /// node, including scalar properties, relationships, and fields with custom resolvers, as
+    /// well as to the fields of the root `Query` and `Mutation` types. A guarded field should be
+    /// optional, since a field that is required can't resolve to null.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::events::{EventHandlerBag, EventFacade};
+    ///
+    /// fn admin_only(ef: &EventFacade<()>) -> bool {
+    ///     ef.context().metadata().get_str("role") == Some("admin")
+    /// }
+    ///
+    /// let mut handlers = EventHandlerBag::<()>::new();
+    /// handlers.register_field_guard("Project".to_string(), "auditLog".to_string(), admin_only);
+    /// ```
+    pub fn register_field_guard(
+        &mut self,
+        type_name: String,
+        field_name: String,
+        f: FieldGuardFunc<RequestCtx>,
+    ) {
+        self.field_guards
+            .entry((type_name, field_name))
+            .or_default()
+            .push(f);
+    }
+
     /// Registers an event handler `f` to be called before a node of type `type_name` is created.
     ///
     /// # Examples
@@ -1063,6 +1182,52 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
         }
     }
 
+    /// Registers an input normalizer `f` to be called on the input for each node of type
+    /// `type_name` that is created or updated, including nodes created or updated through nested
+    /// relationship inputs. The value passed to the normalizer is the node's
+    /// `<Type>CreateMutationInput` for a create, or `<Type>UpdateMutationInput` for an update, and
+    /// the `op()` method on the `EventFacade` tells the two apart.
+    ///
+    /// Normalizers run after the before create and before update event handlers, and before
+    /// default values are applied and validators run, so validators see the normalized input.
+    /// Normalizers for the same type are called in the order in which they are registered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::events::{EventHandlerBag, EventFacade};
+    /// # use warpgrapher::Error;
+    /// # use warpgrapher::engine::value::Value;
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn normalize_email(mut value: Value, _ef: EventFacade<()>) -> BoxFuture<Result<Value, Error>> {
+    ///     Box::pin(async move {
+    ///         if let Value::Map(m) = &mut value {
+    ///             if let Some(Value::String(email)) = m.get_mut("email") {
+    ///                 *email = email.trim().to_lowercase();
+    ///             }
+    ///         }
+    ///         Ok(value)
+    ///     })
+    /// }
+    ///
+    /// let mut handlers = EventHandlerBag::<()>::new();
+    /// handlers.register_input_normalizer(vec!["User".to_string()], normalize_email);
+    /// ```
+    pub fn register_input_normalizer(
+        &mut self,
+        type_names: Vec<String>,
+        f: BeforeMutationEventFunc<RequestCtx>,
+    ) {
+        for type_name in type_names {
+            if let Some(handlers) = self.input_normalizers.get_mut(&type_name) {
+                handlers.push(f);
+            } else {
+                self.input_normalizers.insert(type_name, vec![f]);
+            }
+        }
+    }
+
     pub(crate) fn before_engine_build(&self) -> &Vec<BeforeEngineBuildFunc> {
         &self.before_engine_build_handlers
     }
@@ -1075,6 +1240,19 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
         &self.after_request_handlers
     }
 
+    pub(crate) fn authorizers(&self) -> &Vec<AuthorizerFunc<RequestCtx>> {
+        &self.authorizers
+    }
+
+    pub(crate) fn field_guards(
+        &self,
+        type_name: &str,
+        field_name: &str,
+    ) -> Option<&Vec<FieldGuardFunc<RequestCtx>>> {
+        self.field_guards
+            .get(&(type_name.to_string(), field_name.to_string()))
+    }
+
     pub(crate) fn before_node_create(
         &self,
         type_name: &str,
@@ -1110,6 +1288,13 @@ impl<RequestCtx: RequestContext> EventHandlerBag<RequestCtx> {
         self.after_rel_create_handlers.get(rel_name)
     }
 
+    pub(crate) fn input_normalizers(
+        &self,
+        type_name: &str,
+    ) -> Option<&Vec<BeforeMutationEventFunc<RequestCtx>>> {
+        self.input_normalizers.get(type_name)
+    }
+
     pub(crate) fn before_node_read(
         &self,
         type_name: &str,
@@ -1215,6 +1400,8 @@ impl<RequestCtx: RequestContext> Default for EventHandlerBag<RequestCtx> {
             before_engine_build_handlers: vec![],
             before_request_handlers: vec![],
             after_request_handlers: vec![],
+            authorizers: vec![],
+            field_guards: HashMap::new(),
             before_create_handlers: HashMap::new(),
             after_node_create_handlers: HashMap::new(),
             after_subgraph_create_handlers: HashMap::new(),
@@ -1230,6 +1417,7 @@ impl<RequestCtx: RequestContext> Default for EventHandlerBag<RequestCtx> {
             before_delete_handlers: HashMap::new(),
             after_node_delete_handlers: HashMap::new(),
             after_rel_delete_handlers: HashMap::new(),
+            input_normalizers: HashMap::new(),
         }
     }
 }
@@ -1277,6 +1465,76 @@ where
         self.context
     }
 
+    /// Returns the name of the GraphQL operation selected by the request, if the request named
+    /// one, such as for auditing or for gating a request in a `before_request` handler.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::metadata::Metadata;
+    /// # use warpgrapher::Error;
+    /// # use warpgrapher::engine::events::EventFacade;
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn before_request(
+    ///     rctx: (),
+    ///     ef: EventFacade<()>,
+    ///     _metadata: Metadata,
+    /// ) -> BoxFuture<Result<(), Error>> {
+    ///     Box::pin(async move {
+    ///         if ef.operation_name() == Some("DropEverything") {
+    ///             return Err(Error::UserDefinedError {
+    ///                 source: "Operation not allowed".into(),
+    ///             });
+    ///         }
+    ///         Ok(rctx)
+    ///     })
+    /// }
+    /// ```
+    pub fn operation_name(&self) -> Option<&'a str> {
+        self.context.operation_name()
+    }
+
+    /// Returns the variables sent with the request, as a JSON object. The object is empty if the
+    /// request had no variables. Variables may hold secrets, so use [`redacted_variables`] to log
+    /// them.
+    ///
+    /// [`redacted_variables`]: #method.redacted_variables
+    pub fn variables(&self) -> &'a serde_json::Value {
+        self.context.variables()
+    }
+
+    /// Returns a copy of the variables sent with the request, in which the value of every
+    /// variable or nested field whose name is in `names`, compared without regard to case, is
+    /// replaced by `"[REDACTED]"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::metadata::Metadata;
+    /// # use warpgrapher::Error;
+    /// # use warpgrapher::engine::events::EventFacade;
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn before_request(
+    ///     rctx: (),
+    ///     ef: EventFacade<()>,
+    ///     _metadata: Metadata,
+    /// ) -> BoxFuture<Result<(), Error>> {
+    ///     Box::pin(async move {
+    ///         println!(
+    ///             "{:?} {}",
+    ///             ef.operation_name(),
+    ///             ef.redacted_variables(&["password", "token"])
+    ///         );
+    ///         Ok(rctx)
+    ///     })
+    /// }
+    /// ```
+    pub fn redacted_variables(&self, names: &[&str]) -> serde_json::Value {
+        self.context.redacted_variables(names)
+    }
+
     /// Provides a direct database query operation. This is recommended when it is necessary to
     /// bypass the event handlers that will be triggered from a standard warpgrapher query.
     ///
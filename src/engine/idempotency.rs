@@ -0,0 +1,301 @@
+//! This module provides the in-memory store that lets clients retry create mutations safely. A
+//! client passes an `Idempotency-Key` in the request metadata, and the nodes or relationships
+//! created by a successful create are recorded under that key, along with the create's input. A
+//! repeated create with the same key and input, received before the entry expires, returns the
+//! recorded nodes or relationships instead of creating them again. A repeated create with the
+//! same key and a different input is rejected.
+//!
+//! Keys are scoped to the caller returned by [`RequestContext::idempotency_scope`], so that one
+//! caller can't receive the results recorded for another. Requests with the same key are run one
+//! at a time, so that a retry sent while the first attempt is still running waits for it, rather
+//! than creating the data a second time.
+//!
+//! [`RequestContext::idempotency_scope`]: ../context/trait.RequestContext.html#method.idempotency_scope
+use crate::engine::context::RequestContext;
+use crate::engine::metadata::Metadata;
+use crate::engine::objects::{Node, Rel};
+use crate::engine::value::Value;
+use crate::Error;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex as KeyLock, OwnedMutexGuard};
+
+/// Name of the request metadata entry carrying the idempotency key, compared case-insensitively
+pub(crate) const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+
+/// The nodes or relationships created by a create mutation
+#[derive(Clone, Debug)]
+pub(crate) enum Created<RequestCtx: RequestContext> {
+    Nodes(Vec<Node<RequestCtx>>),
+    Rels(Vec<Rel<RequestCtx>>),
+}
+
+/// A create mutation recorded under an idempotency key
+#[derive(Clone, Debug)]
+struct Record<RequestCtx: RequestContext> {
+    recorded: Instant,
+    input: Value,
+    created: Created<RequestCtx>,
+}
+
+type Records<RequestCtx> = HashMap<String, Record<RequestCtx>>;
+
+/// A claim on an idempotency key, with the nodes or relationships recorded for the key, if any
+pub(crate) type Claimed<RequestCtx> = (IdempotencyClaim<RequestCtx>, Option<Created<RequestCtx>>);
+
+/// Engine-wide record of create mutations, keyed by caller scope, idempotency key, and mutation
+/// field. Entries are held in memory for a fixed time to live. Clones of the store share its
+/// entries.
+#[derive(Clone)]
+pub(crate) struct IdempotencyStore<RequestCtx: RequestContext> {
+    ttl: Duration,
+    records: Arc<Mutex<Records<RequestCtx>>>,
+    locks: Arc<Mutex<HashMap<String, Arc<KeyLock<()>>>>>,
+}
+
+impl<RequestCtx: RequestContext> IdempotencyStore<RequestCtx> {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        IdempotencyStore {
+            ttl,
+            records: Arc::new(Mutex::new(HashMap::new())),
+            locks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Waits until no other request holds the key, and returns a guard that holds it until
+    /// dropped. A create holds its key from before it looks for a recorded result until after
+    /// it records its own, so that creates with the same key run one at a time.
+    async fn lock(&self, key: &str) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+            // a lock referenced only by the map is neither held nor awaited
+            locks.retain(|_, l| Arc::strong_count(l) > 1);
+            locks
+                .entry(key.to_string())
+                .or_insert_with(|| Arc::new(KeyLock::new(())))
+                .clone()
+        };
+        lock.lock_owned().await
+    }
+
+    /// Claims the key for a create with the given input, waiting for any other create holding
+    /// the key to finish. Returns the claim, which holds the key until it is dropped, and the
+    /// nodes or relationships recorded for the key, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`IdempotencyInputMismatched`] if a create was recorded for
+    /// the key with an input other than `input`
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`IdempotencyInputMismatched`]: ../../error/enum.Error.html#variant.IdempotencyInputMismatched
+    pub(crate) async fn claim(
+        &self,
+        key: String,
+        input: Value,
+    ) -> Result<Claimed<RequestCtx>, Error> {
+        let guard = self.lock(&key).await;
+        let recorded = self.get(&key, &input)?;
+        Ok((
+            IdempotencyClaim {
+                store: self.clone(),
+                key,
+                input,
+                _guard: guard,
+            },
+            recorded,
+        ))
+    }
+
+    /// Returns the nodes or relationships recorded for the key, if it has not expired
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`IdempotencyInputMismatched`] if a create was recorded for
+    /// the key with an input other than `input`
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`IdempotencyInputMismatched`]: ../../error/enum.Error.html#variant.IdempotencyInputMismatched
+    fn get(&self, key: &str, input: &Value) -> Result<Option<Created<RequestCtx>>, Error> {
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        self.prune(&mut records);
+        match records.get(key) {
+            Some(record) if &record.input == input => Ok(Some(record.created.clone())),
+            Some(_) => Err(Error::IdempotencyInputMismatched {
+                key: key.to_string(),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Records the input of a create and the nodes or relationships it created under the key,
+    /// replacing any earlier record
+    fn insert(&self, key: String, input: Value, created: Created<RequestCtx>) {
+        let mut records = self.records.lock().unwrap_or_else(|e| e.into_inner());
+        self.prune(&mut records);
+        records.insert(
+            key,
+            Record {
+                recorded: Instant::now(),
+                input,
+                created,
+            },
+        );
+    }
+
+    fn prune(&self, records: &mut Records<RequestCtx>) {
+        let ttl = self.ttl;
+        records.retain(|_, r| r.recorded.elapsed() < ttl);
+    }
+}
+
+/// A create's hold on its idempotency key. Other creates with the same key wait until the claim
+/// is dropped.
+pub(crate) struct IdempotencyClaim<RequestCtx: RequestContext> {
+    store: IdempotencyStore<RequestCtx>,
+    key: String,
+    input: Value,
+    _guard: OwnedMutexGuard<()>,
+}
+
+impl<RequestCtx: RequestContext> IdempotencyClaim<RequestCtx> {
+    /// Records the nodes or relationships created under the claimed key, and releases the key
+    pub(crate) fn record(self, created: Created<RequestCtx>) {
+        self.store.insert(self.key, self.input, created);
+    }
+}
+
+/// Returns the idempotency key from the request metadata, if the client supplied one
+pub(crate) fn idempotency_key(metadata: &Metadata) -> Option<&str> {
+    metadata.get_str(IDEMPOTENCY_KEY).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{idempotency_key, Created, IdempotencyStore};
+    use crate::engine::metadata::Metadata;
+    use crate::engine::objects::Node;
+    use crate::engine::value::Value;
+    use crate::Error;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn input(name: &str) -> Value {
+        let mut m = HashMap::new();
+        m.insert("name".to_string(), Value::String(name.to_string()));
+        Value::Map(m)
+    }
+
+    fn created(id: &str) -> Created<()> {
+        let mut fields = HashMap::new();
+        fields.insert("id".to_string(), Value::String(id.to_string()));
+        Created::Nodes(vec![Node::new("Project".to_string(), fields)])
+    }
+
+    fn created_id(created: Created<()>) -> Value {
+        match created {
+            Created::Nodes(nodes) => nodes[0].id().unwrap().clone(),
+            Created::Rels(_) => panic!("Expected nodes"),
+        }
+    }
+
+    /// Passes if a recorded create is returned for its key and input, and shared between clones
+    #[test]
+    fn store_get_insert() {
+        let store = IdempotencyStore::<()>::new(Duration::from_secs(60));
+        assert!(store
+            .get("k1/ProjectCreate", &input("Project Zero"))
+            .unwrap()
+            .is_none());
+
+        store.insert(
+            "k1/ProjectCreate".to_string(),
+            input("Project Zero"),
+            created("1"),
+        );
+        assert_eq!(
+            created_id(
+                store
+                    .clone()
+                    .get("k1/ProjectCreate", &input("Project Zero"))
+                    .unwrap()
+                    .unwrap()
+            ),
+            Value::String("1".to_string())
+        );
+        assert!(store
+            .get("k2/ProjectCreate", &input("Project Zero"))
+            .unwrap()
+            .is_none());
+    }
+
+    /// Passes if a key recorded with one input is rejected for another
+    #[test]
+    fn store_input_mismatched() {
+        let store = IdempotencyStore::<()>::new(Duration::from_secs(60));
+        store.insert(
+            "k1/ProjectCreate".to_string(),
+            input("Project Zero"),
+            created("1"),
+        );
+        assert!(matches!(
+            store.get("k1/ProjectCreate", &input("Project One")),
+            Err(Error::IdempotencyInputMismatched { key }) if key == "k1/ProjectCreate"
+        ));
+    }
+
+    /// Passes if records are discarded once their time to live has passed
+    #[test]
+    fn store_expires() {
+        let store = IdempotencyStore::<()>::new(Duration::from_millis(0));
+        store.insert(
+            "k1/ProjectCreate".to_string(),
+            input("Project Zero"),
+            created("1"),
+        );
+        assert!(store
+            .get("k1/ProjectCreate", &input("Project Zero"))
+            .unwrap()
+            .is_none());
+    }
+
+    /// Passes if requests holding the same key run one at a time, while other keys run freely
+    #[tokio::test]
+    async fn store_lock() {
+        let store = IdempotencyStore::<()>::new(Duration::from_secs(60));
+        let running = Arc::new(AtomicUsize::new(0));
+
+        let guard = store.lock("k1/ProjectCreate").await;
+        let waiting = {
+            let store = store.clone();
+            let running = running.clone();
+            tokio::spawn(async move {
+                let _guard = store.lock("k1/ProjectCreate").await;
+                running.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        let _other = store.lock("k2/ProjectCreate").await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(running.load(Ordering::SeqCst), 0);
+
+        drop(guard);
+        waiting.await.unwrap();
+        assert_eq!(running.load(Ordering::SeqCst), 1);
+    }
+
+    /// Passes if the key is found regardless of the case of the metadata name
+    #[test]
+    fn key_from_metadata() {
+        let mut metadata = Metadata::new();
+        assert!(idempotency_key(&metadata).is_none());
+
+        metadata.insert("idempotency-key".to_string(), "abc".to_string());
+        assert_eq!(idempotency_key(&metadata), Some("abc"));
+
+        metadata.insert("idempotency-key".to_string(), "".to_string());
+        assert!(idempotency_key(&metadata).is_none());
+    }
+}
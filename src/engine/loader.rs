@@ -1,12 +1,100 @@
 use crate::engine::context::RequestContext;
 use crate::engine::database::{DatabaseEndpoint, DatabasePool, Transaction};
+#[cfg(feature = "metrics")]
+use crate::engine::metrics::{
+    LoaderMetrics, AGGREGATE_LOADER, COUNT_LOADER, NODE_LOADER, REL_LOADER,
+};
 use crate::engine::objects::{Node, Options, Rel};
 use crate::engine::schema::Info;
+use crate::engine::value::Value;
 use crate::error::Error;
 use async_trait::async_trait;
+use futures::future::join_all;
 use log::trace;
 use std::collections::HashMap;
-use ultra_batch::{Cache, Fetcher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use ultra_batch::{Batcher, Cache, Fetcher, LoadError};
+
+/// Default maximum number of keys loaded by a single database query issued by a loader
+pub(crate) const DEFAULT_LOADER_MAX_BATCH: usize = 100;
+
+/// Request-scoped identity map of nodes, keyed by node id. Node reads by id consult the cache
+/// before the node loader, so that a node reached through several paths in the same request is
+/// only fetched from the database once.
+///
+/// Writes that may change nodes already read in the request invalidate the cache. Because the
+/// loaders' own caches cannot be cleared, an invalidated cache is also marked stale, and reads for
+/// the remainder of the request bypass the loaders and query the database directly.
+pub struct NodeCache<RequestCtx: RequestContext> {
+    nodes: Mutex<HashMap<String, Node<RequestCtx>>>,
+    stale: AtomicBool,
+    #[cfg(feature = "metrics")]
+    metrics: LoaderMetrics,
+}
+
+impl<RequestCtx> NodeCache<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    pub(crate) fn new() -> Self {
+        NodeCache {
+            nodes: Mutex::new(HashMap::new()),
+            stale: AtomicBool::new(false),
+            #[cfg(feature = "metrics")]
+            metrics: LoaderMetrics::default(),
+        }
+    }
+
+    /// Sets the metrics in which cache hits and misses are recorded
+    #[cfg(feature = "metrics")]
+    pub(crate) fn with_metrics(mut self, metrics: LoaderMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Returns the cached node with the given id, if it has been read earlier in the request
+    pub fn get(&self, id: &str) -> Option<Node<RequestCtx>> {
+        let node = self
+            .nodes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(id)
+            .cloned();
+
+        #[cfg(feature = "metrics")]
+        if node.is_some() {
+            self.metrics.record_cache_hit();
+        } else {
+            self.metrics.record_cache_miss();
+        }
+
+        node
+    }
+
+    /// Adds a node to the cache, replacing any earlier copy of the same node. Nodes without an id
+    /// are not cached.
+    pub fn insert(&self, node: &Node<RequestCtx>) {
+        if let Ok(id) = node.id() {
+            self.nodes
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(id.to_string(), node.clone());
+        }
+    }
+
+    /// Discards all cached nodes and marks the cache as stale
+    pub fn invalidate(&self) {
+        self.nodes.lock().unwrap_or_else(|e| e.into_inner()).clear();
+        self.stale.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns true if a write has invalidated the cache during this request, in which case the
+    /// node and rel loaders may hold out-of-date values
+    pub fn is_stale(&self) -> bool {
+        self.stale.load(Ordering::SeqCst)
+    }
+}
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct NodeLoaderKey {
@@ -31,6 +119,9 @@ impl NodeLoaderKey {
 pub struct NodeLoader<RequestCtx: RequestContext> {
     pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
     info: Info,
+    max_batch: usize,
+    #[cfg(feature = "metrics")]
+    metrics: LoaderMetrics,
 }
 
 impl<RequestCtx> NodeLoader<RequestCtx>
@@ -41,7 +132,28 @@ where
         pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
         info: Info,
     ) -> Self {
-        NodeLoader::<RequestCtx> { pool, info }
+        NodeLoader::<RequestCtx> {
+            pool,
+            info,
+            max_batch: DEFAULT_LOADER_MAX_BATCH,
+            #[cfg(feature = "metrics")]
+            metrics: LoaderMetrics::default(),
+        }
+    }
+
+    /// Sets the maximum number of keys loaded by a single database query. Larger batches are
+    /// split into several queries, the results of which are merged. A value of zero is treated
+    /// as one.
+    pub fn with_max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch;
+        self
+    }
+
+    /// Sets the metrics in which the size of each batch loaded is recorded
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: LoaderMetrics) -> Self {
+        self.metrics = metrics;
+        self
     }
 
     fn pool(
@@ -68,9 +180,17 @@ where
         trace!("NodeLoader::fetch called -- keys: {:#?}", keys);
 
         let mut transaction = self.pool().transaction().await?;
-        let results = transaction
-            .load_nodes::<RequestCtx>(keys, &self.info)
-            .await?;
+        let mut results = Vec::new();
+        for chunk in keys.chunks(self.max_batch.max(1)) {
+            // Each chunk is read by a query of its own, which is what the metrics count
+            #[cfg(feature = "metrics")]
+            self.metrics.record_batch(NODE_LOADER, chunk.len());
+            results.append(
+                &mut transaction
+                    .load_nodes::<RequestCtx>(chunk, &self.info)
+                    .await?,
+            );
+        }
 
         results.into_iter().try_for_each(|n| {
             values.insert(
@@ -85,11 +205,32 @@ where
     }
 }
 
+/// Loads the nodes with the given keys through the node batcher, returning the nodes found in the
+/// order of their keys. The keys are requested together, so that they are fetched in as few
+/// batches as the batcher allows. Unlike [`Batcher::load_many`], which fails if any key is not
+/// found, keys for which no node exists are left out of the result.
+///
+/// [`Batcher::load_many`]: ../../ultra_batch/struct.Batcher.html#method.load_many
+pub(crate) async fn load_nodes<RequestCtx: RequestContext>(
+    batcher: &Batcher<NodeLoader<RequestCtx>>,
+    keys: Vec<NodeLoaderKey>,
+) -> Result<Vec<Node<RequestCtx>>, LoadError> {
+    join_all(keys.into_iter().map(|key| batcher.load(key)))
+        .await
+        .into_iter()
+        .filter_map(|result| match result {
+            Err(LoadError::NotFound) => None,
+            result => Some(result),
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct RelLoaderKey {
     src_id: String,
     rel_name: String,
     options: Options,
+    symmetric: bool,
 }
 
 impl RelLoaderKey {
@@ -98,9 +239,17 @@ impl RelLoaderKey {
             src_id,
             rel_name,
             options,
+            symmetric: false,
         }
     }
 
+    /// Sets whether the relationship is symmetric, in which case it is loaded in either
+    /// direction from the source node
+    pub fn with_symmetric(mut self, symmetric: bool) -> Self {
+        self.symmetric = symmetric;
+        self
+    }
+
     pub fn src_id(&self) -> &str {
         &self.src_id
     }
@@ -112,10 +261,17 @@ impl RelLoaderKey {
     pub fn options(&self) -> &Options {
         &self.options
     }
+
+    pub fn symmetric(&self) -> bool {
+        self.symmetric
+    }
 }
 
 pub struct RelLoader<RequestCtx: RequestContext> {
     pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    max_batch: usize,
+    #[cfg(feature = "metrics")]
+    metrics: LoaderMetrics,
 }
 
 impl<RequestCtx> RelLoader<RequestCtx>
@@ -125,7 +281,27 @@ where
     pub fn new(
         pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
     ) -> Self {
-        RelLoader::<RequestCtx> { pool }
+        RelLoader::<RequestCtx> {
+            pool,
+            max_batch: DEFAULT_LOADER_MAX_BATCH,
+            #[cfg(feature = "metrics")]
+            metrics: LoaderMetrics::default(),
+        }
+    }
+
+    /// Sets the maximum number of keys loaded by a single database query. Larger batches are
+    /// split into several queries, the results of which are merged. A value of zero is treated
+    /// as one.
+    pub fn with_max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch;
+        self
+    }
+
+    /// Sets the metrics in which the size of each batch loaded is recorded
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: LoaderMetrics) -> Self {
+        self.metrics = metrics;
+        self
     }
 
     fn pool(
@@ -152,26 +328,33 @@ where
         trace!("RelLoader::fetch called -- keys: {:#?}", keys);
 
         let mut transaction = self.pool().transaction().await?;
-        let results = transaction.load_rels::<RequestCtx>(keys).await?;
+        let mut results = Vec::new();
+        for chunk in keys.chunks(self.max_batch.max(1)) {
+            #[cfg(feature = "metrics")]
+            self.metrics.record_batch(REL_LOADER, chunk.len());
+            results.append(&mut transaction.load_rels::<RequestCtx>(chunk).await?);
+        }
 
         let mut rel_map: HashMap<RelLoaderKey, Vec<Rel<RequestCtx>>> = HashMap::new();
-        let options_map: HashMap<String, Options> = keys
+        let key_map: HashMap<(String, String), RelLoaderKey> = keys
             .iter()
             .map(|rlk| {
                 rel_map.insert(rlk.clone(), Vec::new());
-                (rlk.src_id().to_string(), rlk.options().clone())
+                (
+                    (rlk.src_id().to_string(), rlk.rel_name().to_string()),
+                    rlk.clone(),
+                )
             })
             .collect();
 
         results.into_iter().try_for_each(|r| {
-            let rlk = RelLoaderKey::new(
-                r.src_id()?.to_string(),
-                r.rel_name().to_string(),
-                options_map
-                    .get(&r.src_id()?.to_string())
-                    .cloned()
-                    .unwrap_or_default(),
-            );
+            let src_id = r.src_id()?.to_string();
+            let rlk = key_map
+                .get(&(src_id.clone(), r.rel_name().to_string()))
+                .cloned()
+                .unwrap_or_else(|| {
+                    RelLoaderKey::new(src_id, r.rel_name().to_string(), Options::default())
+                });
             let mut rel_list = rel_map.remove(&rlk).unwrap_or_default();
             rel_list.push(r);
             rel_map.insert(rlk, rel_list);
@@ -183,3 +366,289 @@ where
         Ok(())
     }
 }
+
+/// Loads the number of relationships from each of a batch of source nodes, so that a count
+/// property read on many sibling nodes is resolved by one database query rather than one query
+/// per node.
+pub struct CountLoader<RequestCtx: RequestContext> {
+    pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    max_batch: usize,
+    #[cfg(feature = "metrics")]
+    metrics: LoaderMetrics,
+}
+
+impl<RequestCtx> CountLoader<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    pub fn new(
+        pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    ) -> Self {
+        CountLoader::<RequestCtx> {
+            pool,
+            max_batch: DEFAULT_LOADER_MAX_BATCH,
+            #[cfg(feature = "metrics")]
+            metrics: LoaderMetrics::default(),
+        }
+    }
+
+    /// Sets the maximum number of keys loaded by a single database query. Larger batches are
+    /// split into several queries, the results of which are merged. A value of zero is treated
+    /// as one.
+    pub fn with_max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch;
+        self
+    }
+
+    /// Sets the metrics in which the size of each batch loaded is recorded
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: LoaderMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    fn pool(
+        &self,
+    ) -> &<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl<RequestCtx> Fetcher for CountLoader<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    type Key = RelLoaderKey;
+    type Value = i64;
+    type Error = Error;
+
+    async fn fetch(
+        &self,
+        keys: &[RelLoaderKey],
+        values: &mut Cache<'_, RelLoaderKey, i64>,
+    ) -> Result<(), Error> {
+        trace!("CountLoader::fetch called -- keys: {:#?}", keys);
+
+        let mut transaction = self.pool().transaction().await?;
+        for chunk in keys.chunks(self.max_batch.max(1)) {
+            #[cfg(feature = "metrics")]
+            self.metrics.record_batch(COUNT_LOADER, chunk.len());
+            let counts = transaction.count_rels(chunk).await?;
+            chunk
+                .iter()
+                .zip(counts)
+                .for_each(|(k, count)| values.insert(k.clone(), count));
+        }
+
+        Ok(())
+    }
+}
+
+/// Identifies the relationships from a source node whose numeric properties are aggregated, and
+/// the database names of the properties to aggregate.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct AggregateLoaderKey {
+    rel_key: RelLoaderKey,
+    props: Vec<String>,
+}
+
+impl AggregateLoaderKey {
+    pub fn new(rel_key: RelLoaderKey, props: Vec<String>) -> Self {
+        AggregateLoaderKey { rel_key, props }
+    }
+
+    pub fn rel_key(&self) -> &RelLoaderKey {
+        &self.rel_key
+    }
+
+    pub fn props(&self) -> &[String] {
+        &self.props
+    }
+}
+
+/// Loads the aggregates of the relationships from each of a batch of source nodes, so that an
+/// aggregate field read on many sibling nodes is resolved by one database query rather than one
+/// query per node.
+pub struct AggregateLoader<RequestCtx: RequestContext> {
+    pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    max_batch: usize,
+    #[cfg(feature = "metrics")]
+    metrics: LoaderMetrics,
+}
+
+impl<RequestCtx> AggregateLoader<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    pub fn new(
+        pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    ) -> Self {
+        AggregateLoader::<RequestCtx> {
+            pool,
+            max_batch: DEFAULT_LOADER_MAX_BATCH,
+            #[cfg(feature = "metrics")]
+            metrics: LoaderMetrics::default(),
+        }
+    }
+
+    /// Sets the maximum number of keys loaded by a single database query. Larger batches are
+    /// split into several queries, the results of which are merged. A value of zero is treated
+    /// as one.
+    pub fn with_max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch;
+        self
+    }
+
+    /// Sets the metrics in which the size of each batch loaded is recorded
+    #[cfg(feature = "metrics")]
+    pub fn with_metrics(mut self, metrics: LoaderMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    fn pool(
+        &self,
+    ) -> &<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType {
+        &self.pool
+    }
+}
+
+#[async_trait]
+impl<RequestCtx> Fetcher for AggregateLoader<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    type Key = AggregateLoaderKey;
+    type Value = HashMap<String, Value>;
+    type Error = Error;
+
+    async fn fetch(
+        &self,
+        keys: &[AggregateLoaderKey],
+        values: &mut Cache<'_, AggregateLoaderKey, HashMap<String, Value>>,
+    ) -> Result<(), Error> {
+        trace!("AggregateLoader::fetch called -- keys: {:#?}", keys);
+
+        let mut transaction = self.pool().transaction().await?;
+        for chunk in keys.chunks(self.max_batch.max(1)) {
+            #[cfg(feature = "metrics")]
+            self.metrics.record_batch(AGGREGATE_LOADER, chunk.len());
+            let aggregates = transaction.aggregate_rels(chunk).await?;
+            chunk
+                .iter()
+                .zip(aggregates)
+                .for_each(|(k, aggregate)| values.insert(k.clone(), aggregate));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NodeCache;
+    use crate::engine::objects::Node;
+    use crate::engine::value::Value;
+    use maplit::hashmap;
+
+    /// Passes if a cached node can be retrieved by id, and is discarded when the cache is
+    /// invalidated
+    #[test]
+    fn node_cache_insert_get_invalidate() {
+        let cache = NodeCache::<()>::new();
+        let node = Node::<()>::new(
+            "Project".to_string(),
+            hashmap! {
+                "id".to_string() => Value::String("1234".to_string()),
+                "name".to_string() => Value::String("MJOLNIR".to_string())
+            },
+        );
+
+        assert!(cache.get("1234").is_none());
+        cache.insert(&node);
+        assert_eq!(cache.get("1234").unwrap().type_name(), "Project");
+        assert!(!cache.is_stale());
+
+        cache.invalidate();
+        assert!(cache.get("1234").is_none());
+        assert!(cache.is_stale());
+    }
+
+    /// Passes if a node without an id is not cached
+    #[test]
+    fn node_cache_skips_node_without_id() {
+        let cache = NodeCache::<()>::new();
+        cache.insert(&Node::<()>::new("Project".to_string(), hashmap! {}));
+        assert!(cache.nodes.lock().unwrap().is_empty());
+    }
+
+    /// Passes if NodeCache implements the Send trait
+    #[test]
+    fn test_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<NodeCache<()>>();
+    }
+
+    /// Passes if NodeCache implements the Sync trait
+    #[test]
+    fn test_sync() {
+        fn assert_sync<T: Sync>() {}
+        assert_sync::<NodeCache<()>>();
+    }
+
+    /// Passes if loading the same relationship from many source nodes at once is recorded as a
+    /// single batch of all of the keys, and cache lookups are recorded as hits and misses
+    #[cfg(feature = "metrics")]
+    #[tokio::test]
+    async fn uniform_rel_read_single_batch() {
+        use super::{RelLoaderKey, DEFAULT_LOADER_MAX_BATCH};
+        use crate::engine::context::GraphQLContext;
+        use crate::engine::database::no_database::NoDatabasePool;
+        use crate::engine::events::EventHandlerBag;
+        use crate::engine::metadata::Metadata;
+        use crate::engine::metrics::{NODE_LOADER, REL_LOADER};
+        use crate::engine::objects::Options;
+        use crate::engine::schema::Info;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let gqlctx = GraphQLContext::<()>::new(
+            NoDatabasePool {},
+            HashMap::new(),
+            Vec::new(),
+            HashMap::new(),
+            EventHandlerBag::new(),
+            Some(()),
+            None,
+            Metadata::new(),
+            Info::new(String::new(), Arc::new(HashMap::new())),
+            DEFAULT_LOADER_MAX_BATCH,
+        );
+
+        // Loads fail, as there is no database, but each batch is recorded before it is fetched
+        let _ = futures::future::join_all((0..20).map(|i| {
+            gqlctx.rel_batcher().load(RelLoaderKey::new(
+                i.to_string(),
+                "issues".to_string(),
+                Options::default(),
+            ))
+        }))
+        .await;
+
+        gqlctx.node_cache().insert(&Node::<()>::new(
+            "Project".to_string(),
+            hashmap! {"id".to_string() => Value::String("1234".to_string())},
+        ));
+        assert!(gqlctx.node_cache().get("1234").is_some());
+        assert!(gqlctx.node_cache().get("5678").is_none());
+
+        let metrics = gqlctx.loader_metrics().snapshot();
+        assert_eq!(metrics.batches(), 1);
+        assert_eq!(metrics.loader(REL_LOADER).unwrap().batches(), 1);
+        assert_eq!(metrics.loader(REL_LOADER).unwrap().keys(), 20);
+        assert!(metrics.loader(NODE_LOADER).is_none());
+        assert_eq!(metrics.cache_hits(), 1);
+        assert_eq!(metrics.cache_misses(), 1);
+    }
+}
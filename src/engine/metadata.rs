@@ -0,0 +1,225 @@
+//! This module provides the request metadata passed to the engine with each request, such as the
+//! HTTP headers of a GraphQL request. [`Metadata`] wraps the raw map of names to values, and adds
+//! typed getters for common entries, so that request handlers don't have to repeat the parsing.
+//!
+//! [`Metadata`]: ./struct.Metadata.html
+use std::collections::HashMap;
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// Name of the metadata entry carrying the request's credentials
+const AUTHORIZATION: &str = "Authorization";
+
+/// The metadata of a request, such as its HTTP headers. A [`Metadata`] is constructed from a
+/// `HashMap<String, String>`, and dereferences to the map, so it can be read with the usual map
+/// functions. The typed getters look up names without regard to ASCII case, as HTTP header names
+/// are compared, preferring an entry whose name matches exactly.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use warpgrapher::engine::metadata::Metadata;
+///
+/// let mut headers = HashMap::new();
+/// headers.insert("authorization".to_string(), "Bearer abc123".to_string());
+/// headers.insert("X-Page-Size".to_string(), "25".to_string());
+///
+/// let metadata = Metadata::from(headers);
+/// assert_eq!(metadata.authorization(), Some("Bearer abc123"));
+/// assert_eq!(metadata.bearer_token(), Some("abc123"));
+/// assert_eq!(metadata.get_parsed::<u32>("x-page-size"), Some(25));
+/// ```
+///
+/// [`Metadata`]: ./struct.Metadata.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Metadata(HashMap<String, String>);
+
+impl Metadata {
+    /// Returns a new, empty set of metadata
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::metadata::Metadata;
+    ///
+    /// let metadata = Metadata::new();
+    /// assert!(metadata.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Metadata::default()
+    }
+
+    /// Returns the value of the entry with the given name, compared without regard to ASCII case
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::metadata::Metadata;
+    ///
+    /// let metadata: Metadata = vec![("X-Tenant".to_string(), "acme".to_string())]
+    ///     .into_iter()
+    ///     .collect();
+    /// assert_eq!(metadata.get_str("x-tenant"), Some("acme"));
+    /// assert_eq!(metadata.get_str("x-region"), None);
+    /// ```
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.0
+            .get(name)
+            .or_else(|| {
+                self.0
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                    .map(|(_, v)| v)
+            })
+            .map(String::as_str)
+    }
+
+    /// Returns the value of the entry with the given name, compared without regard to ASCII case,
+    /// parsed into a `T`. Returns `None` if there is no such entry, or if its value cannot be
+    /// parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::net::IpAddr;
+    /// # use warpgrapher::engine::metadata::Metadata;
+    ///
+    /// let metadata: Metadata = vec![("X-Real-IP".to_string(), "10.0.0.7".to_string())]
+    ///     .into_iter()
+    ///     .collect();
+    /// let ip: Option<IpAddr> = metadata.get_parsed("x-real-ip");
+    /// assert_eq!(ip, Some("10.0.0.7".parse().unwrap()));
+    /// ```
+    pub fn get_parsed<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.get_str(name).and_then(|v| v.parse().ok())
+    }
+
+    /// Returns the value of the `Authorization` entry, if the request has one
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::metadata::Metadata;
+    ///
+    /// let metadata: Metadata = vec![("Authorization".to_string(), "Basic dXNlcg==".to_string())]
+    ///     .into_iter()
+    ///     .collect();
+    /// assert_eq!(metadata.authorization(), Some("Basic dXNlcg=="));
+    /// ```
+    pub fn authorization(&self) -> Option<&str> {
+        self.get_str(AUTHORIZATION)
+    }
+
+    /// Returns the token of an `Authorization` entry using the `Bearer` scheme, such as a JWT.
+    /// Returns `None` if there is no `Authorization` entry, or if it uses another scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::metadata::Metadata;
+    ///
+    /// let metadata: Metadata = vec![("Authorization".to_string(), "Bearer abc123".to_string())]
+    ///     .into_iter()
+    ///     .collect();
+    /// assert_eq!(metadata.bearer_token(), Some("abc123"));
+    /// ```
+    pub fn bearer_token(&self) -> Option<&str> {
+        let (scheme, token) = self.authorization()?.trim().split_once(' ')?;
+        if scheme.eq_ignore_ascii_case("Bearer") {
+            Some(token.trim_start()).filter(|t| !t.is_empty())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the raw map of metadata names to values
+    pub fn into_inner(self) -> HashMap<String, String> {
+        self.0
+    }
+}
+
+impl Deref for Metadata {
+    type Target = HashMap<String, String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Metadata {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<HashMap<String, String>> for Metadata {
+    fn from(metadata: HashMap<String, String>) -> Self {
+        Metadata(metadata)
+    }
+}
+
+impl From<Metadata> for HashMap<String, String> {
+    fn from(metadata: Metadata) -> Self {
+        metadata.0
+    }
+}
+
+impl FromIterator<(String, String)> for Metadata {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Metadata(HashMap::from_iter(iter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Metadata;
+
+    fn metadata(entries: &[(&str, &str)]) -> Metadata {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    /// Passes if names are looked up without regard to case, preferring an exact match
+    #[test]
+    fn get_str_case_insensitive() {
+        let m = metadata(&[("X-Tenant", "acme")]);
+        assert_eq!(m.get_str("X-Tenant"), Some("acme"));
+        assert_eq!(m.get_str("x-tenant"), Some("acme"));
+        assert_eq!(m.get_str("X-Region"), None);
+
+        let m = metadata(&[("x-tenant", "lower"), ("X-Tenant", "exact")]);
+        assert_eq!(m.get_str("X-Tenant"), Some("exact"));
+    }
+
+    /// Passes if values are parsed into the requested type, and unparsable values are ignored
+    #[test]
+    fn get_parsed() {
+        let m = metadata(&[("X-Page-Size", "25"), ("X-Page", "first")]);
+        assert_eq!(m.get_parsed::<u32>("x-page-size"), Some(25));
+        assert_eq!(m.get_parsed::<u32>("x-page"), None);
+        assert_eq!(m.get_parsed::<u32>("x-missing"), None);
+    }
+
+    /// Passes if a bearer token is read from the authorization entry, and other schemes are
+    /// ignored
+    #[test]
+    fn bearer_token() {
+        let m = metadata(&[("authorization", "bearer  abc123")]);
+        assert_eq!(m.authorization(), Some("bearer  abc123"));
+        assert_eq!(m.bearer_token(), Some("abc123"));
+
+        assert_eq!(
+            metadata(&[("Authorization", "Basic dXNlcg==")]).bearer_token(),
+            None
+        );
+        assert_eq!(
+            metadata(&[("Authorization", "Bearer")]).bearer_token(),
+            None
+        );
+        assert_eq!(metadata(&[]).bearer_token(), None);
+    }
+}
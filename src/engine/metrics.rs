@@ -0,0 +1,299 @@
+//! This module provides counters and histograms describing how well the engine's loaders batch
+//! their database reads, so that it can be confirmed that reading a relationship on many parent
+//! nodes is resolved by one batched query rather than one query per parent. The module is
+//! available with the `metrics` feature.
+//!
+//! Each request records the batches loaded by its node, rel, count, and aggregate loaders, and
+//! the hits and misses of its node cache, in a [`LoaderMetrics`]. A batch is one database query.
+//! A set of keys gathered by a loader that is larger than the engine's loader maximum batch size
+//! is split across several queries, each recorded as a batch of its own. When the request finishes, its
+//! metrics are added to the engine's, which are read with [`Engine::loader_metrics`].
+//!
+//! [`Engine::loader_metrics`]: ../struct.Engine.html#method.loader_metrics
+//! [`LoaderMetrics`]: ./struct.LoaderMetrics.html
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+/// Name under which the node loader's batches are recorded
+pub const NODE_LOADER: &str = "node";
+
+/// Name under which the rel loader's batches are recorded
+pub const REL_LOADER: &str = "rel";
+
+/// Name under which the count loader's batches are recorded
+pub const COUNT_LOADER: &str = "count";
+
+/// Name under which the aggregate loader's batches are recorded
+pub const AGGREGATE_LOADER: &str = "aggregate";
+
+/// Upper bounds, inclusive, of the buckets of each [`Histogram`]. Values above the last bound
+/// are counted in a final, unbounded bucket.
+///
+/// [`Histogram`]: ./struct.Histogram.html
+pub const HISTOGRAM_BOUNDS: [u64; 8] = [1, 2, 5, 10, 25, 50, 100, 250];
+
+/// A distribution of recorded values, counted in the buckets bounded by [`HISTOGRAM_BOUNDS`]
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::metrics::Histogram;
+///
+/// let mut histogram = Histogram::new();
+/// histogram.record(1);
+/// histogram.record(100);
+///
+/// assert_eq!(histogram.count(), 2);
+/// assert_eq!(histogram.sum(), 101);
+/// assert_eq!(histogram.buckets()[0], (Some(1), 1));
+/// assert_eq!(histogram.buckets()[6], (Some(100), 1));
+/// ```
+///
+/// [`HISTOGRAM_BOUNDS`]: ./constant.HISTOGRAM_BOUNDS.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Histogram {
+    counts: [u64; HISTOGRAM_BOUNDS.len() + 1],
+    count: u64,
+    sum: u64,
+}
+
+impl Histogram {
+    /// Returns a new, empty histogram
+    pub fn new() -> Self {
+        Histogram::default()
+    }
+
+    /// Adds a value to the histogram
+    pub fn record(&mut self, value: u64) {
+        let bucket = HISTOGRAM_BOUNDS
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(HISTOGRAM_BOUNDS.len());
+        self.counts[bucket] += 1;
+        self.count += 1;
+        self.sum += value;
+    }
+
+    /// Adds the values recorded in another histogram to this one
+    fn merge(&mut self, other: &Histogram) {
+        self.counts
+            .iter_mut()
+            .zip(other.counts.iter())
+            .for_each(|(c, o)| *c += o);
+        self.count += other.count;
+        self.sum += other.sum;
+    }
+
+    /// Returns the number of values recorded
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the sum of the values recorded
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    /// Returns the number of values recorded in each bucket, paired with the bucket's upper
+    /// bound. The bound of the last bucket is `None`, as it holds every value above the last of
+    /// the [`HISTOGRAM_BOUNDS`].
+    ///
+    /// [`HISTOGRAM_BOUNDS`]: ./constant.HISTOGRAM_BOUNDS.html
+    pub fn buckets(&self) -> Vec<(Option<u64>, u64)> {
+        HISTOGRAM_BOUNDS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain(std::iter::once(None))
+            .zip(self.counts.iter().copied())
+            .collect()
+    }
+}
+
+/// The batches loaded by one of the engine's loaders
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoaderStats {
+    batch_sizes: Histogram,
+}
+
+impl LoaderStats {
+    /// Returns the number of batches loaded, which is the number of database queries run
+    pub fn batches(&self) -> u64 {
+        self.batch_sizes.count()
+    }
+
+    /// Returns the number of keys loaded, across all batches
+    pub fn keys(&self) -> u64 {
+        self.batch_sizes.sum()
+    }
+
+    /// Returns the distribution of the number of keys read by each database query
+    pub fn batch_sizes(&self) -> &Histogram {
+        &self.batch_sizes
+    }
+}
+
+/// A point-in-time copy of the metrics held by a [`LoaderMetrics`]
+///
+/// [`LoaderMetrics`]: ./struct.LoaderMetrics.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LoaderMetricsSnapshot {
+    loaders: BTreeMap<String, LoaderStats>,
+    requests: u64,
+    batches_per_request: Histogram,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+impl LoaderMetricsSnapshot {
+    /// Returns the batches loaded by the loader with the given name, such as [`REL_LOADER`], if
+    /// the loader has loaded any
+    ///
+    /// [`REL_LOADER`]: ./constant.REL_LOADER.html
+    pub fn loader(&self, name: &str) -> Option<&LoaderStats> {
+        self.loaders.get(name)
+    }
+
+    /// Returns the number of batches loaded by all of the loaders
+    pub fn batches(&self) -> u64 {
+        self.loaders.values().map(|l| l.batches()).sum()
+    }
+
+    /// Returns the number of requests whose metrics have been recorded
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    /// Returns the distribution of the number of batches, or database queries, run by the
+    /// loaders of each request
+    pub fn batches_per_request(&self) -> &Histogram {
+        &self.batches_per_request
+    }
+
+    /// Returns the number of node reads by id answered by the request's node cache
+    pub fn cache_hits(&self) -> u64 {
+        self.cache_hits
+    }
+
+    /// Returns the number of node reads by id not found in the request's node cache
+    pub fn cache_misses(&self) -> u64 {
+        self.cache_misses
+    }
+
+    /// Returns the share of node reads by id answered by the node cache, or `None` if there have
+    /// been no such reads
+    pub fn cache_hit_rate(&self) -> Option<f64> {
+        let reads = self.cache_hits + self.cache_misses;
+        if reads == 0 {
+            None
+        } else {
+            Some(self.cache_hits as f64 / reads as f64)
+        }
+    }
+}
+
+/// Collects the batch sizes of the engine's loaders and the hits of its node cache. Clones share
+/// the same metrics.
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::metrics::{LoaderMetrics, REL_LOADER};
+///
+/// let metrics = LoaderMetrics::new();
+/// metrics.record_batch(REL_LOADER, 100);
+///
+/// let snapshot = metrics.snapshot();
+/// assert_eq!(snapshot.loader(REL_LOADER).unwrap().batches(), 1);
+/// assert_eq!(snapshot.loader(REL_LOADER).unwrap().keys(), 100);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LoaderMetrics {
+    inner: Arc<Mutex<LoaderMetricsSnapshot>>,
+}
+
+impl LoaderMetrics {
+    /// Returns a new, empty set of metrics
+    pub fn new() -> Self {
+        LoaderMetrics::default()
+    }
+
+    /// Records a database query reading `keys` keys, run by the loader with the given name
+    pub fn record_batch(&self, loader: &str, keys: usize) {
+        self.lock()
+            .loaders
+            .entry(loader.to_string())
+            .or_default()
+            .batch_sizes
+            .record(keys as u64);
+    }
+
+    /// Records a node read by id answered by the node cache
+    pub fn record_cache_hit(&self) {
+        self.lock().cache_hits += 1;
+    }
+
+    /// Records a node read by id not found in the node cache
+    pub fn record_cache_miss(&self) {
+        self.lock().cache_misses += 1;
+    }
+
+    /// Adds the metrics of a finished request to these metrics, counting the request and the
+    /// number of batches it loaded
+    pub(crate) fn record_request(&self, request: &LoaderMetrics) {
+        let request = request.snapshot();
+        let mut inner = self.lock();
+        request.loaders.iter().for_each(|(name, stats)| {
+            inner
+                .loaders
+                .entry(name.to_string())
+                .or_default()
+                .batch_sizes
+                .merge(&stats.batch_sizes)
+        });
+        inner.requests += 1;
+        inner.batches_per_request.record(request.batches());
+        inner.cache_hits += request.cache_hits;
+        inner.cache_misses += request.cache_misses;
+    }
+
+    /// Returns a copy of the metrics recorded so far
+    pub fn snapshot(&self) -> LoaderMetricsSnapshot {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LoaderMetricsSnapshot> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoaderMetrics, NODE_LOADER, REL_LOADER};
+
+    /// Passes if a request's batches and cache hits are added to the engine's metrics, with the
+    /// number of batches the request loaded
+    #[test]
+    fn record_request() {
+        let engine = LoaderMetrics::new();
+
+        let request = LoaderMetrics::new();
+        request.record_batch(NODE_LOADER, 1);
+        request.record_batch(REL_LOADER, 100);
+        request.record_cache_hit();
+        request.record_cache_miss();
+        engine.record_request(&request);
+        engine.record_request(&LoaderMetrics::new());
+
+        let snapshot = engine.snapshot();
+        assert_eq!(snapshot.requests(), 2);
+        assert_eq!(snapshot.batches(), 2);
+        assert_eq!(snapshot.loader(REL_LOADER).unwrap().keys(), 100);
+        assert_eq!(
+            snapshot.loader(REL_LOADER).unwrap().batch_sizes().buckets()[6],
+            (Some(100), 1)
+        );
+        assert_eq!(snapshot.batches_per_request().buckets()[0], (Some(1), 1));
+        assert_eq!(snapshot.batches_per_request().buckets()[1], (Some(2), 1));
+        assert_eq!(snapshot.cache_hit_rate(), Some(0.5));
+    }
+}
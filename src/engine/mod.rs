@@ -1,28 +1,53 @@
 //! This module provides the Warpgrapher engine, with supporting modules for configuration,
 //! GraphQL schema generation, resolvers, and interface to the database.
 use super::error::Error;
-use config::Configuration;
-use context::{GraphQLContext, RequestContext};
-use database::{CrudOperation, DatabaseEndpoint, DatabasePool};
+use allowlist::query_hash;
+use config::{Configuration, EndpointClass};
+use context::{GraphQLContext, RequestContext, DEFAULT_MAX_WRITE_DEPTH};
+use database::{
+    CrudOperation, DatabaseEndpoint, DatabasePool, ProfileLog, QueryInterceptor, StatementLog,
+};
 use events::{EventFacade, EventHandlerBag};
+use futures::future::join_all;
+use idempotency::IdempotencyStore;
 use juniper::http::GraphQLRequest;
-use log::debug;
-use resolvers::Resolvers;
-use schema::{create_root_node, Info, NodeType, RootRef};
-use std::collections::HashMap;
+use loader::DEFAULT_LOADER_MAX_BATCH;
+use log::{debug, warn};
+use metadata::Metadata;
+#[cfg(feature = "metrics")]
+use metrics::{LoaderMetrics, LoaderMetricsSnapshot};
+use objects::resolvers::visitors::validate_node_input;
+use resolvers::{Directives, ResolverTimeouts, ResolverWrapperFunc, ResolverWrappers, Resolvers};
+use schema::{create_root_node, remove_unknown_input_fields, Info, NodeType, RootRef};
+use shutdown::RequestTracker;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Formatter};
 use std::option::Option;
 use std::sync::Arc;
+use std::time::Duration;
 use validators::Validators;
+use value::Value;
 
+pub mod allowlist;
+#[cfg(feature = "async-graphql")]
+mod async_schema;
 pub mod config;
 pub mod context;
 pub mod database;
+pub mod etag;
 pub mod events;
+mod idempotency;
 pub mod loader;
+pub mod metadata;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod objects;
 pub mod resolvers;
 pub mod schema;
+mod shutdown;
+#[cfg(feature = "otel")]
+pub mod telemetry;
 pub mod validators;
 pub mod value;
 
@@ -47,10 +72,26 @@ pub struct EngineBuilder<RequestCtx = ()>
 where
     RequestCtx: RequestContext,
 {
+    concurrent_batches: bool,
     config: Configuration,
     db_pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    directives: Directives<RequestCtx>,
     event_handlers: EventHandlerBag<RequestCtx>,
+    idempotency_ttl: Option<Duration>,
+    ignore_unknown_input_fields: bool,
+    loader_max_batch: usize,
+    max_write_depth: usize,
+    profiling: bool,
+    query_allowlist: Option<HashSet<String>>,
+    read_after_write: bool,
+    read_only: bool,
+    require_read_filter: bool,
+    query_interceptor: Option<Arc<dyn QueryInterceptor>>,
     resolvers: Resolvers<RequestCtx>,
+    resolver_timeouts: ResolverTimeouts,
+    resolver_wrappers: ResolverWrappers<RequestCtx>,
+    slow_query_threshold: Option<Duration>,
+    type_max_write_depths: HashMap<String, usize>,
     validators: Validators,
     version: Option<String>,
 }
@@ -84,6 +125,134 @@ where
         self
     }
 
+    /// Adds timeouts to the engine's custom resolvers. Each timeout applies to the resolver of the
+    /// same name, wherever it is used, whether for a custom endpoint, field, or relationship. A
+    /// resolver that runs longer than its timeout is abandoned, and its field resolves to the
+    /// timeout's fallback value, or to an error if there is no fallback, without failing the rest
+    /// of the operation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use warpgrapher::engine::resolvers::{ExecutionResult, ResolverFacade, ResolverTimeout, ResolverTimeouts, Resolvers};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn forecast(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move { facade.resolve_scalar("sunny") })
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut resolvers = Resolvers::<()>::new();
+    /// resolvers.insert("forecast".to_string(), Box::new(forecast));
+    ///
+    /// let mut timeouts = ResolverTimeouts::new();
+    /// timeouts.insert(
+    ///     "forecast".to_string(),
+    ///     ResolverTimeout::new(Duration::from_millis(250))
+    ///         .with_fallback(warpgrapher::juniper::Value::null()),
+    /// );
+    ///
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_resolvers(resolvers)
+    ///     .with_resolver_timeouts(timeouts)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_resolver_timeouts(
+        mut self,
+        resolver_timeouts: ResolverTimeouts,
+    ) -> EngineBuilder<RequestCtx> {
+        self.resolver_timeouts = resolver_timeouts;
+        self
+    }
+
+    /// Adds a wrapper that runs around every custom resolver, including custom endpoints, custom
+    /// fields, and custom relationships. Wrappers suit cross-cutting behavior, such as timing,
+    /// authorization, or caching, that would otherwise be repeated in each resolver. Wrappers
+    /// compose in registration order, so the first wrapper added is the outermost.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use warpgrapher::engine::resolvers::{ExecutionResult, Next, ResolverFacade};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn logging<'a>(
+    ///     facade: ResolverFacade<'a, ()>,
+    ///     next: Next<'a, ()>,
+    /// ) -> BoxFuture<'a, ExecutionResult> {
+    ///     log::debug!("resolving {}", facade.field_name());
+    ///     next.run(facade)
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .wrap_resolvers(Box::new(logging))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wrap_resolvers(
+        mut self,
+        wrapper: Box<ResolverWrapperFunc<RequestCtx>>,
+    ) -> EngineBuilder<RequestCtx> {
+        self.resolver_wrappers.push(Arc::from(wrapper));
+        self
+    }
+
+    /// Adds directive handlers to the engine. Each handler enforces the directive of the same name
+    /// wherever it is applied to a type or property in the configuration. Before a field of an
+    /// object is resolved, the handlers for the directives of the object type run, followed by the
+    /// handlers for the directives of the field, and the field is resolved only if they all
+    /// succeed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::engine::config::Directive;
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use warpgrapher::engine::resolvers::{Directives, FieldError, ResolverFacade};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn audit<'a>(
+    ///     _directive: &'a Directive,
+    ///     facade: ResolverFacade<'a, ()>,
+    /// ) -> BoxFuture<'a, Result<(), FieldError>> {
+    ///     log::info!("resolving audited field {}", facade.field_name());
+    ///     Box::pin(async { Ok(()) })
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut directives = Directives::<()>::new();
+    /// directives.insert("audit".to_string(), Box::new(audit));
+    ///
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_directives(directives)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_directives(
+        mut self,
+        directives: Directives<RequestCtx>,
+    ) -> EngineBuilder<RequestCtx> {
+        self.directives = directives;
+        self
+    }
+
     /// Adds validators to the engine
     ///
     /// # Examples
@@ -137,7 +306,16 @@ where
         self
     }
 
-    /// Sets the version of the app
+    /// Sets the maximum depth of nested node writes in a create or update mutation. The node
+    /// named by the mutation is at depth one, a node created or updated through one of its
+    /// relationships is at depth two, and so on. A mutation that nests writes more deeply fails
+    /// with an [`Error`] variant [`NestedInputTooDeep`] before the deeper write reaches the
+    /// database, and its transaction is rolled back. Defaults to 10. Use
+    /// [`with_type_max_write_depth`] to raise or lower the limit for mutations of one type.
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`NestedInputTooDeep`]: ../error/enum.Error.html#variant.NestedInputTooDeep
+    /// [`with_type_max_write_depth`]: ./struct.EngineBuilder.html#method.with_type_max_write_depth
     ///
     /// # Examples
     ///
@@ -149,57 +327,22 @@ where
     /// let config = Configuration::default();
     ///
     /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
-    ///     .with_version("1.0.0".to_string())
+    ///     .with_max_write_depth(4)
     ///     .build()?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn with_version(mut self, version: String) -> EngineBuilder<RequestCtx> {
-        self.version = Some(version);
+    pub fn with_max_write_depth(mut self, max_write_depth: usize) -> EngineBuilder<RequestCtx> {
+        self.max_write_depth = max_write_depth;
         self
     }
 
-    /// Builds a configured [`Engine`] including generating the data model, CRUD operations, and
-    /// custom endpoints from the [`Configuration`] `c`. Returns the [`Engine`].
-    ///
-    /// [`Engine`]: ./struct.Engine.html
-    /// [`Configuration`]: ./config/struct.Configuration.html
-    ///
-    /// # Errors
-    ///
-    /// Returns an [`Error`] variant [`ConfigItemDuplicated`] if there is more than one type or
-    /// more than one endpoint that use the same name.
-    ///
-    /// Returns an [`Error`] variant [`ConfigItemReserved`] if a named configuration item, such as
-    /// an endpoint or type, has a name that is a reserved word, such as "ID" or the name of a
-    /// GraphQL scalar type.
-    ///
-    /// Returns an [`Error`] variant [`SchemaItemNotFound`] if there is an error in the
-    /// configuration, specifically if the configuration of type A references type B, but type B
-    /// cannot be found.
-    ///
-    /// Returns an [`Error`] variant [`ResolverNotFound`] if there is a resolver defined in the
-    /// configuration for which no [`ResolverFunc`] has been added to the [`Resolvers`] collection
-    /// applied to the EngineBuilder with [`with_resolvers`].
-    ///
-    /// Returns an [`Error`] variant [`ValidatorNotFound`] if there is a validator defined in the
-    /// configuration for which no [`ValidatorFunc`] has been added to the [`Validators`] collection
-    /// applied to the EngineBuilder with [`with_validators`].
-    ///
-    /// Returns an
+    /// Sets the maximum depth of nested node writes for create and update mutations of the type
+    /// named `type_name`, overriding the limit set with [`with_max_write_depth`]. The limit is
+    /// chosen by the type of the mutation's outermost node, so the nodes nested beneath it are
+    /// governed by that limit whatever their own types.
     ///
-    /// [`ConfigItemDuplicated`]: ../error/enum.Error.html#variant.ConfigItemDuplicated
-    /// [`ConfigItemReserved`]: ../error/enum.Error.html#variant.ConfigItemReserved
-    /// [`Error`]: ../error/enum.Error.html
-    /// [`ResolverNotFound`]: ../error/enum.Error.html#variant.ResolverNotFound
-    /// [`ResolverFunc`]: ./resolvers/type.ResolverFunc.html
-    /// [`Resolvers`]: ./resolvers/type.Resolvers.html
-    /// [`SchemaItemNotFound`]: ../error/enum.Error.html#variant.SchemaItemNotFound
-    /// [`ValidatorNotFound`]: ../error/enum.Error.html#variant.ValidatorNotFound
-    /// [`ValidatorFunc`]: ./validators/type.ValidatorFunc.html
-    /// [`Validators`]: ./validators/type.Validators.html
-    /// [`with_resolvers`]: ./struct.EngineBuilder.html#method.with_resolvers
-    /// [`with_validators`]: ./struct.EngineBuilder.html#method.with_validators
+    /// [`with_max_write_depth`]: ./struct.EngineBuilder.html#method.with_max_write_depth
     ///
     /// # Examples
     ///
@@ -208,148 +351,28 @@ where
     /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
     ///
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-    /// let config = Configuration::new(1, Vec::new(), Vec::new());
+    /// let config = Configuration::default();
     ///
-    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_type_max_write_depth("Organization".to_string(), 20)
+    ///     .build()?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn build(mut self) -> Result<Engine<RequestCtx>, Error> {
-        self.validate()?;
-
-        for event_handler in self.event_handlers.before_engine_build() {
-            event_handler(&mut self.config)?;
-        }
-
-        let root_node = create_root_node(&self.config)?;
-
-        let engine = Engine::<RequestCtx> {
-            config: self.config,
-            db_pool: self.db_pool,
-            resolvers: self.resolvers,
-            validators: self.validators,
-            event_handlers: self.event_handlers,
-            version: self.version,
-            root_node,
-        };
-
-        Ok(engine)
-    }
-
-    fn validate(&self) -> Result<(), Error> {
-        self.config.validate()?;
-
-        // Validate Custom Endpoint defined in Configuration exists as a Resolver
-        self.config
-            .endpoints()
-            .map(|e| {
-                if !self.resolvers.contains_key(e.name()) {
-                    Err(Error::ResolverNotFound {
-                        name: e.name().to_string(),
-                    })
-                } else {
-                    Ok(())
-                }
-            })
-            .collect::<Result<Vec<_>, Error>>()?;
-
-        self.config
-            .types()
-            .map(|t| {
-                // Validate that custom resolver defined in Configuration exists as a Resolver
-                t.props()
-                    .filter_map(|p| p.resolver())
-                    .map(|r| {
-                        if !self.resolvers.contains_key(r) {
-                            Err(Error::ResolverNotFound {
-                                name: r.to_string(),
-                            })
-                        } else {
-                            Ok(())
-                        }
-                    })
-                    .collect::<Result<Vec<_>, Error>>()?;
-
-                // Validate that custom validator defined in Configuration exists as a Validator
-                t.props()
-                    .filter_map(|p| p.validator())
-                    .map(|v| {
-                        if !self.validators.contains_key(v) {
-                            Err(Error::ValidatorNotFound {
-                                name: v.to_string(),
-                            })
-                        } else {
-                            Ok(())
-                        }
-                    })
-                    .collect::<Result<Vec<_>, Error>>()?;
-
-                Ok(())
-            })
-            .collect::<Result<Vec<_>, Error>>()?;
-
-        // validation passed
-        Ok(())
-    }
-}
-
-impl Debug for EngineBuilder {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        f.debug_struct("EngineBuilder")
-            .field("config", &self.config)
-            .field("version", &self.version)
-            .finish()
+    pub fn with_type_max_write_depth(
+        mut self,
+        type_name: String,
+        max_write_depth: usize,
+    ) -> EngineBuilder<RequestCtx> {
+        self.type_max_write_depths
+            .insert(type_name, max_write_depth);
+        self
     }
-}
-
-/// A Warpgrapher GraphQL engine.
-///
-/// The [`Engine`] struct Juniper GraphQL service on top of it, with an auto-generated set of
-/// resolvers that cover basic CRUD operations, and potentially custom resolvers, on a set of
-/// data types and the relationships between them.  The engine includes handling of back-end
-/// communications with the chosen databse.
-///
-/// [`Engine`]: ./struct.Engine.html
-///
-/// # Examples
-///
-/// ```rust
-/// # use warpgrapher::{Configuration, DatabasePool, Engine};
-/// # use warpgrapher::engine::database::no_database::NoDatabasePool;
-///
-/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-/// let config = Configuration::default();
-///
-/// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
-/// # Ok(())
-/// # }
-/// ```
-#[derive(Clone)]
-pub struct Engine<RequestCtx = ()>
-where
-    RequestCtx: RequestContext,
-{
-    config: Configuration,
-    db_pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
-    resolvers: Resolvers<RequestCtx>,
-    validators: Validators,
-    event_handlers: EventHandlerBag<RequestCtx>,
-    version: Option<String>,
-    root_node: RootRef<RequestCtx>,
-}
 
-impl<RequestCtx> Engine<RequestCtx>
-where
-    RequestCtx: RequestContext,
-{
-    /// Creates a new [`EngineBuilder`]. Requiered arguments are a [`Configuration`], the
-    /// deserialized configuration for a Warpgrapher engine, which contains definitions of types
-    /// and endpoints, as well as a [`DatabasePool`], which tells the ending how to connect with a
-    /// back-end graph storage engine.
-    ///
-    /// [`Configuration`]: ./config/struct.Configuration.html
-    /// [`DatabasePool`]: ./database/trait.DatabasePool.html
-    /// [`EngineBuilder`]: ./struct.EngineBuilder.html
+    /// Sets the maximum number of keys that the node, relationship, and count loaders fetch with a
+    /// single database query. When a query fans out to more ids than this, the loaders split the
+    /// ids into several bounded `IN` lists, issue one database query per list, and merge the
+    /// results. Defaults to 100. A value of zero is treated as one.
     ///
     /// # Examples
     ///
@@ -360,378 +383,3417 @@ where
     /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let config = Configuration::default();
     ///
-    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_loader_max_batch(500)
+    ///     .build()?;
     /// # Ok(())
     /// # }
     /// ```
-    #[allow(clippy::new_ret_no_self)]
-    pub fn new(
-        config: Configuration,
-        database_pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
-    ) -> EngineBuilder<RequestCtx> {
-        EngineBuilder::<RequestCtx> {
-            config,
-            db_pool: database_pool,
-            resolvers: HashMap::new(),
-            validators: HashMap::new(),
-            event_handlers: EventHandlerBag::new(),
-            version: None,
-        }
+    pub fn with_loader_max_batch(mut self, loader_max_batch: usize) -> EngineBuilder<RequestCtx> {
+        self.loader_max_batch = loader_max_batch;
+        self
     }
 
-    /// Executes a [`GraphQLRequest`], returning a serialized JSON response.
+    /// Enables idempotency keys for create mutations. When a request's metadata carries an
+    /// `Idempotency-Key` entry, the nodes or relationships created by each successful node,
+    /// batch, or relationship create in the request are recorded under the key, together with
+    /// the create's input. A repeated create with the same key, mutation field, and input,
+    /// received within the time to live, returns the recorded nodes or relationships, resolved
+    /// against its own selection set, without writing to the database again. A repeated create
+    /// with a different input fails with [`Error::IdempotencyInputMismatched`]. Keys are scoped
+    /// to the caller returned by [`RequestContext::idempotency_scope`], and creates with the same
+    /// key run one at a time. Records are kept in the memory of the engine, so they are lost on
+    /// restart and are not shared between engines. Idempotency keys are ignored by default.
     ///
-    /// [`GraphQLRequest`]: ../../juniper/http/struct.GraphQLRequest.html
+    /// [`Error::IdempotencyInputMismatched`]: ../error/enum.Error.html#variant.IdempotencyInputMismatched
+    /// [`RequestContext::idempotency_scope`]: context/trait.RequestContext.html#method.idempotency_scope
     ///
-    /// # Errors
+    /// # Examples
     ///
-    /// Returns an [`Error`] variant [`ExtensionFailed`] if a pre request hook or post request
-    /// hook extension returns an error.
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
     ///
-    /// Returns an [`Error`] variant [`SerializationFailed`] if the engine response cannot be
-    /// serialized successfully.
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
     ///
-    /// [`Error`]: ../error/enum.Error.html
-    /// [`ExtensionFailed`]: ../error/enum.Error.html#variant.ExtensionFailed
-    /// [`SerializationFailed`]: ../error/enum.Error.html#variant.SerializationFailed
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_idempotency_ttl(Duration::from_secs(3600))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> EngineBuilder<RequestCtx> {
+        self.idempotency_ttl = Some(ttl);
+        self
+    }
+
+    /// Sets a latency threshold for database queries. Any query that takes longer than the
+    /// threshold is logged at warn level, with the name of the database operation and the elapsed
+    /// time, and the query text is logged at debug level. Slow query logging is off by default.
     ///
     /// # Examples
     ///
-    /// ```rust,no_run
+    /// ```rust
+    /// # use std::time::Duration;
     /// # use warpgrapher::{Configuration, DatabasePool, Engine};
     /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
-    /// # use warpgrapher::juniper::http::GraphQLRequest;
-    /// # use serde_json::{from_value, json};
-    /// # use std::collections::HashMap;
     ///
-    /// # #[tokio::main]
-    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// let config = Configuration::default();
-    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
-    ///
-    /// let query = "query { name }".to_string();
-    /// let metadata: HashMap<String, String> = HashMap::new();
     ///
-    /// let result = engine.execute(query, None, metadata).await?;
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_slow_query_threshold(Duration::from_millis(500))
+    ///     .build()?;
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    #[tracing::instrument(name = "wg-execute", skip(self, query, input, metadata))]
-    pub async fn execute(
-        &self,
-        query: String,
-        input: Option<serde_json::Value>,
-        metadata: HashMap<String, String>,
-    ) -> Result<serde_json::Value, Error> {
-        debug!("Engine::execute called -- input: {:#?}", input);
-
-        // create new request context
-        let mut rctx = RequestCtx::new();
-
-        let gql_schema: HashMap<String, NodeType> =
-            crate::engine::schema::generate_schema(&self.config)?;
-        let info = Info::new("".to_string(), Arc::new(gql_schema));
-
-        // execute before_request handlers
-        let before_request_handlers = self.event_handlers.before_request();
-        if !before_request_handlers.is_empty() {
-            let mut dbtx = self.db_pool.transaction().await?;
-            let gqlctx_tmp = GraphQLContext::<RequestCtx>::new(
-                self.db_pool.clone(),
-                self.resolvers.clone(),
-                self.validators.clone(),
-                self.event_handlers.clone(),
-                Some(rctx.clone()),
-                self.version.clone(),
-                metadata.clone(),
-                info.clone(),
-            );
-            for handler in before_request_handlers {
-                rctx = handler(
-                    rctx,
-                    EventFacade::new(CrudOperation::None, &gqlctx_tmp, &mut dbtx, &info),
-                    metadata.clone(),
-                )
-                .await?;
-            }
-            std::mem::drop(dbtx);
-        }
-
-        // convert serde_json input to juniper input
-        let input_value: Option<juniper::InputValue> = match input {
-            Some(input) => Some(serde_json::from_value::<juniper::InputValue>(input)?),
-            None => None,
-        };
-
-        // execute graphql query
-        let gqlctx = GraphQLContext::<RequestCtx>::new(
-            self.db_pool.clone(),
-            self.resolvers.clone(),
-            self.validators.clone(),
-            self.event_handlers.clone(),
-            Some(rctx.clone()),
-            self.version.clone(),
-            metadata.clone(),
-            info.clone(),
-        );
-        let req = GraphQLRequest::new(query, None, input_value);
-        let res = req.execute(&self.root_node, &gqlctx).await;
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> EngineBuilder<RequestCtx> {
+        self.slow_query_threshold = Some(threshold);
+        self
+    }
 
-        // convert graphql response (json) to mutable serde_json::Value
-        let mut ret_value = serde_json::to_value(&res)?;
+    /// Enables query profiling for mutations. A mutation with `options: { profile: true }` runs
+    /// each of its database queries with profiling, and the profile of each query, with its
+    /// database hits and rows, is returned in the `_profile` field of the response. The
+    /// mutation's transaction is committed or rolled back as usual. Only the Cypher back-end
+    /// records profiles. Profiling is disabled by default, and a mutation that asks for a profile
+    /// while it is disabled returns an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_profiling(true)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_profiling(mut self, profiling: bool) -> EngineBuilder<RequestCtx> {
+        self.profiling = profiling;
+        self
+    }
 
-        // execute after_request handlers
-        let after_request_handlers = self.event_handlers.after_request();
-        if !after_request_handlers.is_empty() {
-            let mut dbtx = self.db_pool.transaction().await?;
-            let gqlctx_tmp = GraphQLContext::<RequestCtx>::new(
-                self.db_pool.clone(),
-                self.resolvers.clone(),
-                self.validators.clone(),
-                self.event_handlers.clone(),
-                Some(rctx.clone()),
-                self.version.clone(),
-                metadata.clone(),
-                info.clone(),
-            );
-            for handler in self.event_handlers.after_request() {
-                ret_value = handler(
-                    EventFacade::new(CrudOperation::None, &gqlctx_tmp, &mut dbtx, &info),
-                    ret_value,
-                )
-                .await?;
-            }
-            std::mem::drop(dbtx);
-        }
+    /// Sets whether the requests of a batch passed to [`execute_batch`] are executed concurrently.
+    /// By default they are executed one after another, in the order of the batch, so that a
+    /// request sees the writes of the requests before it. Executing them concurrently shortens
+    /// the time taken by a batch of independent reads. Either way, the responses are returned in
+    /// the order of the requests.
+    ///
+    /// [`execute_batch`]: ./struct.Engine.html#method.execute_batch
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_concurrent_batches(true)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_concurrent_batches(
+        mut self,
+        concurrent_batches: bool,
+    ) -> EngineBuilder<RequestCtx> {
+        self.concurrent_batches = concurrent_batches;
+        self
+    }
 
-        debug!("Engine::execute -- ret_value: {:#?}", ret_value);
-        Ok(ret_value)
+    /// Sets whether a request reads its own writes. Once a mutation in a request has written to
+    /// the database, the nodes and relationships cached by the request's loaders are bypassed,
+    /// and, when read-after-write consistency is on, the reads that follow in the same request
+    /// are sent to the read/write endpoint of the database rather than to a read replica, so that
+    /// they see the request's writes even if the replicas lag behind. Turning it off keeps all
+    /// reads on the replicas, at the risk of a read missing a write made earlier in the request.
+    /// Read-after-write consistency is on by default.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_read_after_write(false)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_read_after_write(mut self, read_after_write: bool) -> EngineBuilder<RequestCtx> {
+        self.read_after_write = read_after_write;
+        self
     }
-}
 
-impl<RequestCtx> Display for Engine<RequestCtx>
-where
-    RequestCtx: RequestContext,
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
-        write!(f, "{:#?}", self)
+    /// Sets whether queries must have input to filter the nodes or relationships they read. When
+    /// set, a root query for nodes or relationships with no input, or with an empty input, is
+    /// rejected with an [`UnfilteredReadRejected`] error rather than reading everything of its
+    /// type, guarding large graphs against accidental full scans. A request can override the
+    /// guard for a query that is meant to read everything by setting the `allowUnfilteredRead`
+    /// option of the query. Reads of relationships nested in a query are filtered by their source
+    /// node, and are not affected. Reads are not required to be filtered by default.
+    ///
+    /// [`UnfilteredReadRejected`]: ../error/enum.Error.html#variant.UnfilteredReadRejected
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_require_read_filter(true)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_require_read_filter(
+        mut self,
+        require_read_filter: bool,
+    ) -> EngineBuilder<RequestCtx> {
+        self.require_read_filter = require_read_filter;
+        self
     }
-}
 
-impl<RequestCtx> Debug for Engine<RequestCtx>
-where
-    RequestCtx: RequestContext,
-{
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        f.debug_struct("Engine")
-            .field("config", &self.config)
-            .field("version", &self.version)
-            .finish()
+    /// Sets whether fields of a request's variables that are not in the schema are ignored. By
+    /// default, a request whose variables hold a field that its input type doesn't have, such as
+    /// a field removed from the model in a newer release, is rejected. When unknown fields are
+    /// ignored, they are removed from the variables, with a warning logged, and the rest of the
+    /// request is executed as usual. This eases rolling deployments, during which some clients
+    /// and servers briefly disagree about the schema. Unknown fields written as literals in the
+    /// query itself are still rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_ignore_unknown_input_fields(true)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_ignore_unknown_input_fields(
+        mut self,
+        ignore_unknown_input_fields: bool,
+    ) -> EngineBuilder<RequestCtx> {
+        self.ignore_unknown_input_fields = ignore_unknown_input_fields;
+        self
     }
-}
 
-/// Notably, the unit tests here likely seem weak. This is because testing most
-/// of the functionality requires a database container to be running and
-/// reachable, so most of the coverage is provided by integration tests.
-#[cfg(test)]
-mod tests {
-    use super::EngineBuilder;
-    use crate::engine::database::no_database::NoDatabasePool;
-    use crate::engine::resolvers::{ResolverFacade, Resolvers};
-    use crate::engine::validators::Validators;
-    use crate::engine::value::Value;
-    use crate::{Configuration, Engine, Error};
-    use juniper::{BoxFuture, ExecutionResult};
-    use std::convert::TryInto;
-    use std::fs::File;
+    /// Restricts the engine to a known set of queries. Each entry of the allowlist is the hash of
+    /// an allowed query, as returned by [`query_hash`], which ignores differences in whitespace,
+    /// commas, and comments. A request whose query is not in the allowlist is rejected with an
+    /// [`Error`] variant [`QueryNotAllowed`] before it is executed. This suits deployments whose
+    /// clients run only the queries they were shipped with, such as a mobile app's back-end. By
+    /// default, there is no allowlist and any query is executed. A request made through the
+    /// schema returned by [`Engine::async_graphql_schema`] is checked once, against the query
+    /// its client sent.
+    ///
+    /// [`Engine::async_graphql_schema`]: ./struct.Engine.html#method.async_graphql_schema
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`QueryNotAllowed`]: ../error/enum.Error.html#variant.QueryNotAllowed
+    /// [`query_hash`]: ./allowlist/fn.query_hash.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::allowlist::query_hash;
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use std::collections::HashSet;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let allowlist: HashSet<String> = vec!["query { _version }"]
+    ///     .into_iter()
+    ///     .map(query_hash)
+    ///     .collect();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_query_allowlist(allowlist)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_query_allowlist(mut self, allowlist: HashSet<String>) -> EngineBuilder<RequestCtx> {
+        self.query_allowlist = Some(allowlist);
+        self
+    }
+
+    /// Makes the engine read-only. The generated create, update, and delete mutations of every
+    /// type and relationship are left out of the GraphQL schema, so a request for one fails
+    /// validation like a request for any other field that doesn't exist. Building the engine
+    /// fails if the configuration has a custom endpoint of the `Mutation` class.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .read_only()
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn read_only(mut self) -> EngineBuilder<RequestCtx> {
+        self.read_only = true;
+        self
+    }
+
+    /// Sets a [`QueryInterceptor`] that may rewrite or augment every database query generated by
+    /// the engine, after the query has been fully assembled and before it is sent to the database.
+    /// See [`QueryInterceptor`] for the risks of rewriting generated queries.
+    ///
+    /// [`QueryInterceptor`]: ./database/trait.QueryInterceptor.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine, Error};
+    /// # use warpgrapher::engine::database::QueryInterceptor;
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use warpgrapher::engine::value::Value;
+    ///
+    /// struct NoOpInterceptor;
+    ///
+    /// impl QueryInterceptor for NoOpInterceptor {
+    ///     fn intercept(
+    ///         &self,
+    ///         _operation: &str,
+    ///         query: String,
+    ///         params: HashMap<String, Value>,
+    ///     ) -> Result<(String, HashMap<String, Value>), Error> {
+    ///         Ok((query, params))
+    ///     }
+    /// }
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_query_interceptor(Box::new(NoOpInterceptor))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_query_interceptor(
+        mut self,
+        interceptor: Box<dyn QueryInterceptor>,
+    ) -> EngineBuilder<RequestCtx> {
+        self.query_interceptor = Some(Arc::from(interceptor));
+        self
+    }
+
+    /// Sets the version of the app
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_version("1.0.0".to_string())
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_version(mut self, version: String) -> EngineBuilder<RequestCtx> {
+        self.version = Some(version);
+        self
+    }
+
+    /// Builds a configured [`Engine`] including generating the data model, CRUD operations, and
+    /// custom endpoints from the [`Configuration`] `c`. Returns the [`Engine`].
+    ///
+    /// [`Engine`]: ./struct.Engine.html
+    /// [`Configuration`]: ./config/struct.Configuration.html
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`ConfigItemDuplicated`] if there is more than one type or
+    /// more than one endpoint that use the same name.
+    ///
+    /// Returns an [`Error`] variant [`ConfigItemReserved`] if a named configuration item, such as
+    /// an endpoint or type, has a name that is a reserved word, such as "ID" or the name of a
+    /// GraphQL scalar type.
+    ///
+    /// Returns an [`Error`] variant [`SchemaItemNotFound`] if there is an error in the
+    /// configuration, specifically if the configuration of type A references type B, but type B
+    /// cannot be found.
+    ///
+    /// Returns an [`Error`] variant [`ResolverNotFound`] if there is a resolver defined in the
+    /// configuration for which no [`ResolverFunc`] has been added to the [`Resolvers`] collection
+    /// applied to the EngineBuilder with [`with_resolvers`].
+    ///
+    /// Returns an [`Error`] variant [`ResolverNotFound`] if a timeout is added with
+    /// [`with_resolver_timeouts`] for a resolver that has not been added with [`with_resolvers`].
+    ///
+    /// Returns an [`Error`] variant [`ValidatorNotFound`] if there is a validator defined in the
+    /// configuration for which no [`ValidatorFunc`] has been added to the [`Validators`] collection
+    /// applied to the EngineBuilder with [`with_validators`].
+    ///
+    /// Returns an [`Error`] variant [`DirectiveNotFound`] if there is a directive applied in the
+    /// configuration for which no [`DirectiveFunc`] has been added to the [`Directives`]
+    /// collection applied to the EngineBuilder with [`with_directives`].
+    ///
+    /// Returns an [`Error`] variant [`ConfigItemInvalid`] if the engine is made [`read_only`] and
+    /// the configuration has a custom endpoint of the `Mutation` class.
+    ///
+    /// [`ConfigItemDuplicated`]: ../error/enum.Error.html#variant.ConfigItemDuplicated
+    /// [`ConfigItemInvalid`]: ../error/enum.Error.html#variant.ConfigItemInvalid
+    /// [`ConfigItemReserved`]: ../error/enum.Error.html#variant.ConfigItemReserved
+    /// [`DirectiveFunc`]: ./resolvers/type.DirectiveFunc.html
+    /// [`DirectiveNotFound`]: ../error/enum.Error.html#variant.DirectiveNotFound
+    /// [`Directives`]: ./resolvers/type.Directives.html
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`ResolverNotFound`]: ../error/enum.Error.html#variant.ResolverNotFound
+    /// [`ResolverFunc`]: ./resolvers/type.ResolverFunc.html
+    /// [`Resolvers`]: ./resolvers/type.Resolvers.html
+    /// [`SchemaItemNotFound`]: ../error/enum.Error.html#variant.SchemaItemNotFound
+    /// [`ValidatorNotFound`]: ../error/enum.Error.html#variant.ValidatorNotFound
+    /// [`ValidatorFunc`]: ./validators/type.ValidatorFunc.html
+    /// [`Validators`]: ./validators/type.Validators.html
+    /// [`read_only`]: ./struct.EngineBuilder.html#method.read_only
+    /// [`with_directives`]: ./struct.EngineBuilder.html#method.with_directives
+    /// [`with_resolver_timeouts`]: ./struct.EngineBuilder.html#method.with_resolver_timeouts
+    /// [`with_resolvers`]: ./struct.EngineBuilder.html#method.with_resolvers
+    /// [`with_validators`]: ./struct.EngineBuilder.html#method.with_validators
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::new(1, Vec::new(), Vec::new());
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build(mut self) -> Result<Engine<RequestCtx>, Error> {
+        self.validate()?;
+
+        for event_handler in self.event_handlers.before_engine_build() {
+            event_handler(&mut self.config)?;
+        }
+
+        if self.read_only {
+            if let Some(e) = self
+                .config
+                .endpoints()
+                .find(|e| e.class() == &EndpointClass::Mutation)
+            {
+                return Err(Error::ConfigItemInvalid {
+                    type_name: e.name().to_string(),
+                    details: "a read-only engine cannot have a Mutation endpoint".to_string(),
+                });
+            }
+            self.config.remove_write_endpoints();
+        }
+
+        let root_node = create_root_node(&self.config)?;
+
+        let db_pool = match self.slow_query_threshold {
+            Some(threshold) => self.db_pool.with_slow_query_threshold(Some(threshold)),
+            None => self.db_pool,
+        };
+        let db_pool = match self.query_interceptor {
+            Some(interceptor) => db_pool.with_query_interceptor(Some(interceptor)),
+            None => db_pool,
+        };
+
+        let engine = Engine::<RequestCtx> {
+            concurrent_batches: self.concurrent_batches,
+            config: self.config,
+            db_pool,
+            directives: self.directives,
+            resolvers: self.resolvers,
+            resolver_timeouts: self.resolver_timeouts,
+            resolver_wrappers: self.resolver_wrappers,
+            validators: self.validators,
+            event_handlers: self.event_handlers,
+            idempotency: self.idempotency_ttl.map(IdempotencyStore::new),
+            ignore_unknown_input_fields: self.ignore_unknown_input_fields,
+            loader_max_batch: self.loader_max_batch,
+            #[cfg(feature = "metrics")]
+            loader_metrics: LoaderMetrics::new(),
+            max_write_depth: self.max_write_depth,
+            type_max_write_depths: self.type_max_write_depths,
+            profiling: self.profiling,
+            query_allowlist: self.query_allowlist.map(Arc::new),
+            read_after_write: self.read_after_write,
+            require_read_filter: self.require_read_filter,
+            version: self.version,
+            root_node,
+            requests: RequestTracker::new(),
+        };
+
+        Ok(engine)
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        self.config.validate()?;
+
+        // Validate Custom Endpoint defined in Configuration exists as a Resolver
+        self.config
+            .endpoints()
+            .map(|e| {
+                if !self.resolvers.contains_key(e.name()) {
+                    Err(Error::ResolverNotFound {
+                        name: e.name().to_string(),
+                    })
+                } else {
+                    Ok(())
+                }
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // Validate that each resolver timeout applies to a Resolver
+        if let Some(name) = self
+            .resolver_timeouts
+            .keys()
+            .find(|name| !self.resolvers.contains_key(*name))
+        {
+            return Err(Error::ResolverNotFound {
+                name: name.to_string(),
+            });
+        }
+
+        self.config
+            .types()
+            .map(|t| {
+                // Validate that custom resolver defined in Configuration exists as a Resolver
+                t.props()
+                    .filter_map(|p| p.resolver())
+                    .map(|r| {
+                        if !self.resolvers.contains_key(r) {
+                            Err(Error::ResolverNotFound {
+                                name: r.to_string(),
+                            })
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                // Validate that custom validator defined in Configuration exists as a Validator
+                t.props()
+                    .filter_map(|p| p.validator())
+                    .map(|v| {
+                        if !self.validators.contains_key(v) {
+                            Err(Error::ValidatorNotFound {
+                                name: v.to_string(),
+                            })
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                // Validate that directives applied in Configuration exist as Directives
+                t.directives()
+                    .chain(t.props().flat_map(|p| p.directives()))
+                    .map(|d| {
+                        if !self.directives.contains_key(d.name()) {
+                            Err(Error::DirectiveNotFound {
+                                name: d.name().to_string(),
+                            })
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                Ok(())
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        // validation passed
+        Ok(())
+    }
+}
+
+impl Debug for EngineBuilder {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("EngineBuilder")
+            .field("config", &self.config)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+/// A Warpgrapher GraphQL engine.
+///
+/// The [`Engine`] struct Juniper GraphQL service on top of it, with an auto-generated set of
+/// resolvers that cover basic CRUD operations, and potentially custom resolvers, on a set of
+/// data types and the relationships between them.  The engine includes handling of back-end
+/// communications with the chosen databse.
+///
+/// [`Engine`]: ./struct.Engine.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::{Configuration, DatabasePool, Engine};
+/// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let config = Configuration::default();
+///
+/// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Engine<RequestCtx = ()>
+where
+    RequestCtx: RequestContext,
+{
+    concurrent_batches: bool,
+    config: Configuration,
+    db_pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    directives: Directives<RequestCtx>,
+    resolvers: Resolvers<RequestCtx>,
+    resolver_timeouts: ResolverTimeouts,
+    resolver_wrappers: ResolverWrappers<RequestCtx>,
+    validators: Validators,
+    event_handlers: EventHandlerBag<RequestCtx>,
+    idempotency: Option<IdempotencyStore<RequestCtx>>,
+    ignore_unknown_input_fields: bool,
+    loader_max_batch: usize,
+    #[cfg(feature = "metrics")]
+    loader_metrics: LoaderMetrics,
+    max_write_depth: usize,
+    type_max_write_depths: HashMap<String, usize>,
+    profiling: bool,
+    query_allowlist: Option<Arc<HashSet<String>>>,
+    read_after_write: bool,
+    require_read_filter: bool,
+    version: Option<String>,
+    root_node: RootRef<RequestCtx>,
+    requests: RequestTracker,
+}
+
+impl<RequestCtx> Engine<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    /// Creates a new [`EngineBuilder`]. Requiered arguments are a [`Configuration`], the
+    /// deserialized configuration for a Warpgrapher engine, which contains definitions of types
+    /// and endpoints, as well as a [`DatabasePool`], which tells the ending how to connect with a
+    /// back-end graph storage engine.
+    ///
+    /// [`Configuration`]: ./config/struct.Configuration.html
+    /// [`DatabasePool`]: ./database/trait.DatabasePool.html
+    /// [`EngineBuilder`]: ./struct.EngineBuilder.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        config: Configuration,
+        database_pool: <<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType,
+    ) -> EngineBuilder<RequestCtx> {
+        EngineBuilder::<RequestCtx> {
+            concurrent_batches: false,
+            config,
+            db_pool: database_pool,
+            directives: HashMap::new(),
+            resolvers: HashMap::new(),
+            resolver_timeouts: HashMap::new(),
+            resolver_wrappers: Vec::new(),
+            validators: HashMap::new(),
+            event_handlers: EventHandlerBag::new(),
+            idempotency_ttl: None,
+            ignore_unknown_input_fields: false,
+            loader_max_batch: DEFAULT_LOADER_MAX_BATCH,
+            max_write_depth: DEFAULT_MAX_WRITE_DEPTH,
+            profiling: false,
+            query_allowlist: None,
+            read_after_write: true,
+            read_only: false,
+            require_read_filter: false,
+            query_interceptor: None,
+            slow_query_threshold: None,
+            type_max_write_depths: HashMap::new(),
+            version: None,
+        }
+    }
+
+    /// Executes a [`GraphQLRequest`], returning a serialized JSON response.
+    ///
+    /// If the query document holds more than one named operation, `operation_name` selects the
+    /// operation to run, as with the `operationName` of a GraphQL HTTP request. The response
+    /// holds an error if the document has more than one operation and no `operation_name` is
+    /// given, or if no operation has the given name.
+    ///
+    /// The request `metadata`, such as HTTP headers, may be passed either as a [`Metadata`] or as
+    /// a `HashMap<String, String>`.
+    ///
+    /// [`GraphQLRequest`]: ../../juniper/http/struct.GraphQLRequest.html
+    /// [`Metadata`]: ./metadata/struct.Metadata.html
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`ExtensionFailed`] if a pre request hook or post request
+    /// hook extension returns an error.
+    ///
+    /// Returns an [`Error`] variant [`EngineShuttingDown`] if [`shutdown`] has been called on the
+    /// engine.
+    ///
+    /// Returns an [`Error`] variant [`SerializationFailed`] if the engine response cannot be
+    /// serialized successfully.
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`EngineShuttingDown`]: ../error/enum.Error.html#variant.EngineShuttingDown
+    /// [`ExtensionFailed`]: ../error/enum.Error.html#variant.ExtensionFailed
+    /// [`SerializationFailed`]: ../error/enum.Error.html#variant.SerializationFailed
+    /// [`shutdown`]: #method.shutdown
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use warpgrapher::juniper::http::GraphQLRequest;
+    /// # use serde_json::{from_value, json};
+    /// # use std::collections::HashMap;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    ///
+    /// let query = "query { name }".to_string();
+    /// let metadata: HashMap<String, String> = HashMap::new();
+    ///
+    /// let result = engine.execute(query, None, None, metadata).await?;
+    ///
+    /// let query = "query A { name } query B { id }".to_string();
+    /// let result = engine
+    ///     .execute(query, Some("B".to_string()), None, HashMap::new())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    #[tracing::instrument(
+        name = "wg-execute",
+        skip(self, query, operation_name, input, metadata)
+    )]
+    pub async fn execute(
+        &self,
+        query: String,
+        operation_name: Option<String>,
+        input: Option<serde_json::Value>,
+        metadata: impl Into<Metadata>,
+    ) -> Result<serde_json::Value, Error> {
+        debug!(
+            "Engine::execute called -- operation_name: {:?} | input: {:#?}",
+            operation_name, input
+        );
+
+        // convert serde_json input to juniper input
+        let input_value: Option<juniper::InputValue> = match &input {
+            Some(input) => Some(serde_json::from_value::<juniper::InputValue>(
+                input.clone(),
+            )?),
+            None => None,
+        };
+        let variables = input.unwrap_or_else(|| serde_json::Value::Object(serde_json::Map::new()));
+
+        self.execute_request(
+            query,
+            operation_name,
+            input_value,
+            Some(variables),
+            metadata.into(),
+            None,
+        )
+        .await
+    }
+
+    /// Executes a [`GraphQLRequest`] whose variables have already been parsed into a juniper
+    /// [`InputValue`], returning a serialized JSON response. This is the same as [`execute`],
+    /// but skips the conversion of the variables from JSON, for callers that already hold them as
+    /// an [`InputValue`], such as a [`Client`] running against a local engine. The variables are
+    /// converted to JSON only if an event handler or resolver asks for them, through the
+    /// `variables` method of its facade.
+    ///
+    /// [`Client`]: ../client/enum.Client.html
+    /// [`execute`]: #method.execute
+    /// [`GraphQLRequest`]: ../../juniper/http/struct.GraphQLRequest.html
+    /// [`InputValue`]: ../../juniper/enum.InputValue.html
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`execute`], other than those from converting the variables.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use warpgrapher::juniper::InputValue;
+    /// # use std::collections::HashMap;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    ///
+    /// let query = "query Named($name: String) { name }".to_string();
+    /// let input = InputValue::object(
+    ///     vec![("name", InputValue::scalar("Warpgrapher".to_string()))]
+    ///         .into_iter()
+    ///         .collect(),
+    /// );
+    ///
+    /// let result = engine
+    ///     .execute_with_input_value(query, None, Some(input), HashMap::new())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(
+        name = "wg-execute",
+        skip(self, query, operation_name, input, metadata)
+    )]
+    pub async fn execute_with_input_value(
+        &self,
+        query: String,
+        operation_name: Option<String>,
+        input: Option<juniper::InputValue>,
+        metadata: impl Into<Metadata>,
+    ) -> Result<serde_json::Value, Error> {
+        debug!(
+            "Engine::execute_with_input_value called -- operation_name: {:?} | input: {:#?}",
+            operation_name, input
+        );
+
+        self.execute_request(query, operation_name, input, None, metadata.into(), None)
+            .await
+    }
+
+    /// Executes the body of a GraphQL HTTP request, which is either a single request, an object
+    /// with a `query` and optional `operationName` and `variables`, or a JSON array of such
+    /// requests, as sent by clients that batch several operations into one HTTP call. A server
+    /// handler can pass the parsed body of every request to this method, rather than to
+    /// [`execute`], to support both forms.
+    ///
+    /// A single request is executed as with [`execute`]. For a batch, each request is executed
+    /// as a request of its own, with its own request context and transactions, so a failed
+    /// request doesn't roll back the others. The response is an array holding the response to
+    /// each request, in the order of the requests. A request that can't be executed, such as one
+    /// without a `query`, one that isn't in the query allowlist, or one sent while the engine is
+    /// shutting down, has a response of its own holding the error in its `errors`. The requests
+    /// are executed one after another unless concurrent execution has been turned on with
+    /// [`with_concurrent_batches`].
+    ///
+    /// [`execute`]: #method.execute
+    /// [`with_concurrent_batches`]: ./struct.EngineBuilder.html#method.with_concurrent_batches
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`execute`] for a single request. For a batch, errors are
+    /// returned in the responses to the requests that caused them.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::{Configuration, DatabasePool, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use serde_json::json;
+    /// # use std::collections::HashMap;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    /// let mut engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    ///
+    /// let body = json!([
+    ///     {"query": "query { Project { id } }"},
+    ///     {"query": "query Named { User { id } }", "operationName": "Named"}
+    /// ]);
+    /// let responses = engine.execute_batch(body, HashMap::new()).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn execute_batch(
+        &self,
+        body: serde_json::Value,
+        metadata: impl Into<Metadata>,
+    ) -> Result<serde_json::Value, Error> {
+        debug!("Engine::execute_batch called -- body: {:#?}", body);
+
+        let metadata = metadata.into();
+        let requests = match body {
+            serde_json::Value::Array(requests) => requests,
+            request => {
+                let (query, operation_name, variables) = batch_request_parts(request)?;
+                return self
+                    .execute(query, operation_name, variables, metadata)
+                    .await;
+            }
+        };
+
+        let execute_one = |request: serde_json::Value| {
+            let metadata = metadata.clone();
+            async move {
+                let result = match batch_request_parts(request) {
+                    Ok((query, operation_name, variables)) => {
+                        self.execute(query, operation_name, variables, metadata)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                result.unwrap_or_else(|e| {
+                    warn!("Engine::execute_batch -- request failed: {}", e);
+                    serde_json::json!({ "errors": [{ "message": e.to_string() }] })
+                })
+            }
+        };
+
+        let responses = if self.concurrent_batches {
+            join_all(requests.into_iter().map(execute_one)).await
+        } else {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                responses.push(execute_one(request).await);
+            }
+            responses
+        };
+
+        Ok(serde_json::Value::Array(responses))
+    }
+
+    /// Executes a request for [`execute`] and [`execute_with_input_value`]. The `variables` are
+    /// the JSON form of `input_value`, if the caller has it. If not, the event handlers and
+    /// resolvers are given the parsed input, and convert it to JSON only if they need to.
+    ///
+    /// The query allowlist is checked against `allowlist_query`, if given, rather than against
+    /// `query`. The async-graphql bridge uses this to check the document sent by its client while
+    /// executing a copy of it that also asks for the `__typename` of each object.
+    ///
+    /// [`execute`]: #method.execute
+    /// [`execute_with_input_value`]: #method.execute_with_input_value
+    async fn execute_request(
+        &self,
+        query: String,
+        operation_name: Option<String>,
+        mut input_value: Option<juniper::InputValue>,
+        mut variables: Option<serde_json::Value>,
+        metadata: Metadata,
+        allowlist_query: Option<&str>,
+    ) -> Result<serde_json::Value, Error> {
+        // link the request's spans into the caller's distributed trace
+        #[cfg(feature = "otel")]
+        telemetry::set_parent(&metadata);
+
+        // held until the response is ready, so that a shutdown waits for this request
+        let _request = self.requests.begin()?;
+
+        // reject the query if the engine has an allowlist that doesn't hold it
+        if let Some(allowlist) = &self.query_allowlist {
+            let hash = query_hash(allowlist_query.unwrap_or(&query));
+            if !allowlist.contains(&hash) {
+                warn!("Engine::execute -- query not in allowlist: {}", hash);
+                return Err(Error::QueryNotAllowed { hash });
+            }
+        }
+
+        // drop the fields of the variables that aren't in the schema, if the engine ignores them
+        if self.ignore_unknown_input_fields {
+            if let Some(input_value) = input_value.as_mut() {
+                let removed = remove_unknown_input_fields(
+                    &self.root_node,
+                    &query,
+                    operation_name.as_deref(),
+                    input_value,
+                );
+                if !removed.is_empty() {
+                    warn!(
+                        "Engine::execute -- ignoring unknown input fields: {}",
+                        removed.join(", ")
+                    );
+                    if variables.is_some() {
+                        variables = Some(serde_json::to_value(&*input_value)?);
+                    }
+                }
+            }
+        }
+
+        // create new request context
+        let mut rctx = RequestCtx::from_metadata(&metadata);
+
+        let gql_schema: HashMap<String, NodeType> =
+            crate::engine::schema::generate_schema(&self.config)?;
+        let info = Info::new("".to_string(), Arc::new(gql_schema));
+        let shared_input = match &variables {
+            Some(_) => None,
+            None => input_value.clone().map(Arc::new),
+        };
+        let with_request = |gqlctx: GraphQLContext<RequestCtx>| match &variables {
+            Some(variables) => gqlctx.with_request(operation_name.clone(), variables.clone()),
+            None => gqlctx.with_request_input(operation_name.clone(), shared_input.clone()),
+        };
+
+        // execute before_request handlers
+        let before_request_handlers = self.event_handlers.before_request();
+        if !before_request_handlers.is_empty() {
+            let db_pool = self
+                .db_pool
+                .with_database(rctx.database().map(|db| db.to_string()));
+            let mut dbtx = db_pool.transaction().await?;
+            let gqlctx_tmp = GraphQLContext::<RequestCtx>::new(
+                db_pool,
+                self.resolvers.clone(),
+                self.resolver_wrappers.clone(),
+                self.validators.clone(),
+                self.event_handlers.clone(),
+                Some(rctx.clone()),
+                self.version.clone(),
+                metadata.clone(),
+                info.clone(),
+                self.loader_max_batch,
+            );
+            let gqlctx_tmp = with_request(gqlctx_tmp);
+            for handler in before_request_handlers {
+                rctx = handler(
+                    rctx,
+                    EventFacade::new(CrudOperation::None, &gqlctx_tmp, &mut dbtx, &info),
+                    metadata.clone(),
+                )
+                .await?;
+            }
+            std::mem::drop(dbtx);
+        }
+
+        // route the rest of the request to the database selected by the request context, which
+        // the before_request handlers may have changed
+        let db_pool = self
+            .db_pool
+            .with_database(rctx.database().map(|db| db.to_string()));
+
+        // execute graphql query
+        let gqlctx = GraphQLContext::<RequestCtx>::new(
+            db_pool.clone(),
+            self.resolvers.clone(),
+            self.resolver_wrappers.clone(),
+            self.validators.clone(),
+            self.event_handlers.clone(),
+            Some(rctx.clone()),
+            self.version.clone(),
+            metadata.clone(),
+            info.clone(),
+            self.loader_max_batch,
+        )
+        .with_idempotency_store(self.idempotency.clone())
+        .with_directives(self.directives.clone())
+        .with_resolver_timeouts(self.resolver_timeouts.clone())
+        .with_write_depth_limits(self.max_write_depth, self.type_max_write_depths.clone());
+        let profile_log = if self.profiling {
+            Some(ProfileLog::new())
+        } else {
+            None
+        };
+        let gqlctx = with_request(gqlctx)
+            .with_profile_log(profile_log.clone())
+            .with_read_after_write(self.read_after_write)
+            .with_require_read_filter(self.require_read_filter);
+        let req = GraphQLRequest::new(query, operation_name.clone(), input_value);
+        let res = req.execute(&self.root_node, &gqlctx).await;
+        #[cfg(feature = "metrics")]
+        self.loader_metrics.record_request(gqlctx.loader_metrics());
+
+        // convert graphql response (json) to mutable serde_json::Value
+        let mut ret_value = serde_json::to_value(&res)?;
+
+        // attach the profiles of any mutations run with profiling
+        let profiles = profile_log.map(|log| log.profiles()).unwrap_or_default();
+        if !profiles.is_empty() {
+            if let Some(obj) = ret_value.as_object_mut() {
+                obj.insert("_profile".to_string(), serde_json::to_value(profiles)?);
+            }
+        }
+
+        // execute after_request handlers
+        let after_request_handlers = self.event_handlers.after_request();
+        if !after_request_handlers.is_empty() {
+            let mut dbtx = db_pool.transaction().await?;
+            let gqlctx_tmp = GraphQLContext::<RequestCtx>::new(
+                db_pool.clone(),
+                self.resolvers.clone(),
+                self.resolver_wrappers.clone(),
+                self.validators.clone(),
+                self.event_handlers.clone(),
+                Some(rctx.clone()),
+                self.version.clone(),
+                metadata.clone(),
+                info.clone(),
+                self.loader_max_batch,
+            );
+            let gqlctx_tmp = with_request(gqlctx_tmp);
+            for handler in self.event_handlers.after_request() {
+                ret_value = handler(
+                    EventFacade::new(CrudOperation::None, &gqlctx_tmp, &mut dbtx, &info),
+                    ret_value,
+                )
+                .await?;
+            }
+            std::mem::drop(dbtx);
+        }
+
+        debug!("Engine::execute -- ret_value: {:#?}", ret_value);
+        Ok(ret_value)
+    }
+
+    /// Returns an [`async_graphql`] schema that serves the same GraphQL API as this engine, for
+    /// applications built on async-graphql rather than juniper. The schema has the same types,
+    /// fields, and arguments as the engine's juniper schema. Once async-graphql has validated an
+    /// operation, the engine executes it as a single request, so custom resolvers, validators,
+    /// event handlers, and database transactions behave as they do for [`execute`], and errors
+    /// keep their paths and extensions. Juniper remains the engine's native GraphQL library.
+    ///
+    /// To pass request metadata, such as HTTP headers, to the engine, add a [`Metadata`] or a
+    /// `HashMap<String, String>` to the async-graphql request data.
+    ///
+    /// This method is only available with the `async-graphql` feature enabled.
+    ///
+    /// [`execute`]: #method.execute
+    /// [`Metadata`]: ./metadata/struct.Metadata.html
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`AsyncGraphqlSchemaFailed`] if async-graphql rejects the
+    /// generated schema.
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`AsyncGraphqlSchemaFailed`]: ../error/enum.Error.html#variant.AsyncGraphqlSchemaFailed
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::async_graphql::Request;
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    /// # use std::collections::HashMap;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    /// let engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    /// let schema = engine.async_graphql_schema()?;
+    ///
+    /// let metadata: HashMap<String, String> = HashMap::new();
+    /// let response = schema
+    ///     .execute(Request::new("query { _version }").data(metadata))
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async-graphql")]
+    pub fn async_graphql_schema(&self) -> Result<async_graphql::dynamic::Schema, Error> {
+        async_schema::build_schema(self).map_err(|e| Error::AsyncGraphqlSchemaFailed { source: e })
+    }
+
+    /// Returns the database statements that the engine generates for a GraphQL query, without
+    /// sending them to the database. Each statement is returned in the query language of the
+    /// back-end, with parameter placeholders rather than values. The back-end must support
+    /// recording statements, as the Cypher and Gremlin back-ends do.
+    ///
+    /// Because no statements are executed, no results come back from the database. Where a
+    /// resolver needs the results of one statement to generate the next, such as a mutation
+    /// that returns the nodes it created, the operation fails at that point, and the statements
+    /// generated up to then are returned in the error.
+    ///
+    /// Before and after request event handlers are not run, but the rest of the operation is
+    /// resolved as usual. Custom resolvers, and the event handlers run around reads and writes,
+    /// are called, and any effects they have outside of the database, such as calls to other
+    /// services, take place. Their database queries are recorded rather than executed.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - GraphQL query to be explained
+    /// * `input` - Optional JSON-formatted map of variables for the GraphQL query
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`ExplainNotSupported`] if the database back-end can't
+    /// record statements without executing them.
+    ///
+    /// Returns an [`Error`] variant [`ExplainFailed`] if resolving the operation produced GraphQL
+    /// errors. The error holds the GraphQL errors and the statements recorded before them.
+    ///
+    /// Returns an [`Error`] variant [`SchemaItemNotFound`] if there is an error generating the
+    /// schema from the configuration.
+    ///
+    /// Returns an [`Error`] variant [`SerializationFailed`] if the input variables cannot be
+    /// deserialized.
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`ExplainFailed`]: ../error/enum.Error.html#variant.ExplainFailed
+    /// [`ExplainNotSupported`]: ../error/enum.Error.html#variant.ExplainNotSupported
+    /// [`SchemaItemNotFound`]: ../error/enum.Error.html#variant.SchemaItemNotFound
+    /// [`SerializationFailed`]: ../error/enum.Error.html#variant.SerializationFailed
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    /// let engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    ///
+    /// let query = "query { name }".to_string();
+    /// let statements = engine.explain(query, None).await?;
+    /// for statement in statements {
+    ///     println!("{}", statement);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn explain(
+        &self,
+        query: String,
+        input: Option<serde_json::Value>,
+    ) -> Result<Vec<String>, Error> {
+        self.explain_with_log(query, input, StatementLog::new())
+            .await
+    }
+
+    /// Returns the database statements that the engine generates for a GraphQL query, each
+    /// followed by the query plan that the database produces for it. Unlike [`explain`], this
+    /// requires a database connection. The statements are sent to the database prefixed with
+    /// `EXPLAIN`, which plans them without executing them. Only the Cypher back-end produces
+    /// plans. Other back-ends return the same output as [`explain`].
+    ///
+    /// [`explain`]: #method.explain
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - GraphQL query to be explained
+    /// * `input` - Optional JSON-formatted map of variables for the GraphQL query
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`explain`], as well as any [`Error`] raised while
+    /// communicating with the database.
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    pub async fn explain_with_plans(
+        &self,
+        query: String,
+        input: Option<serde_json::Value>,
+    ) -> Result<Vec<String>, Error> {
+        self.explain_with_log(query, input, StatementLog::new().with_plans(true))
+            .await
+    }
+
+    /// Returns the version of the app, if one was set with [`with_version`]. This is the same
+    /// version returned by the `_version` GraphQL query, made available for uses outside of
+    /// GraphQL, such as a version route or logging.
+    ///
+    /// [`with_version`]: ./struct.EngineBuilder.html#method.with_version
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::default();
+    ///
+    /// let engine = Engine::<()>::new(config, NoDatabasePool {})
+    ///     .with_version("1.0.0".to_string())
+    ///     .build()?;
+    ///
+    /// assert_eq!(Some("1.0.0"), engine.version());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Returns the metrics recorded by the loaders of every request executed by this engine and
+    /// its clones, such as the sizes of the batches loaded and the number of batches loaded by
+    /// each request. Requests run by [`explain`] are not recorded.
+    ///
+    /// This method is only available with the `metrics` feature enabled.
+    ///
+    /// [`explain`]: ./struct.Engine.html#method.explain
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {}).build()?;
+    ///
+    /// let metrics = engine.loader_metrics();
+    /// assert_eq!(metrics.requests(), 0);
+    /// assert_eq!(metrics.cache_hit_rate(), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "metrics")]
+    pub fn loader_metrics(&self) -> LoaderMetricsSnapshot {
+        self.loader_metrics.snapshot()
+    }
+
+    /// Shuts the engine down, for a graceful stop of the service, such as when the process
+    /// receives a SIGTERM during a rolling deployment. From the time `shutdown` is called, the
+    /// engine accepts no new requests. Requests that arrive after that, on this engine or any of
+    /// its clones, are turned away by [`execute`] with an [`Error`] variant
+    /// [`EngineShuttingDown`], so that the server can answer them with a retryable status such as
+    /// 503. Requests already in flight run to completion, including their database transactions.
+    /// Once the last of them has finished, the database pool is closed by calling
+    /// [`DatabasePool::close`]. A shut down engine can't be restarted.
+    ///
+    /// `shutdown` waits for as long as requests are in flight, with no timeout of its own, since
+    /// the engine is not tied to a particular async runtime. To bound the wait, wrap the call in
+    /// the timeout of the runtime in use, such as `tokio::time::timeout`. If the timeout expires,
+    /// the engine keeps turning away new requests, while the requests in flight keep running and
+    /// the pool is left open until the engine is dropped.
+    ///
+    /// [`DatabasePool::close`]: ./database/trait.DatabasePool.html#method.close
+    /// [`EngineShuttingDown`]: ../error/enum.Error.html#variant.EngineShuttingDown
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`execute`]: #method.execute
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the database pool fails to close. The specific [`Error`] variant
+    /// depends on the database back-end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use std::time::Duration;
+    /// # use warpgrapher::{Configuration, Engine, Error};
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {}).build()?;
+    ///
+    /// tokio::time::timeout(Duration::from_secs(30), engine.shutdown()).await??;
+    ///
+    /// let result = engine
+    ///     .execute("query { _version }".to_string(), None, None, HashMap::new())
+    ///     .await;
+    /// assert!(matches!(result, Err(Error::EngineShuttingDown)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn shutdown(&self) -> Result<(), Error> {
+        debug!("Engine::shutdown called");
+        self.requests.drain().await;
+        self.db_pool.close().await
+    }
+
+    /// Checks an input for a node create or update operation without executing it, as for
+    /// validating a form before it is submitted. The input is checked against the same GraphQL
+    /// input type used by the operation's mutation, and the configured validators for its
+    /// properties are run, but nothing is read from or written to the database. Before-create
+    /// and before-update event handlers are not run, since they may query the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `operation` - a [`CrudOperation`] of `CreateNode` or `UpdateNode`, naming the type of node
+    /// * `input` - JSON-formatted map of the node's properties, as passed in the mutation's `input`
+    ///   for a create, or its `SET` for an update
+    ///
+    /// # Return
+    ///
+    /// The input converted to a [`Value`], with `Base64` properties decoded to bytes as they would
+    /// be stored.
+    ///
+    /// [`CrudOperation`]: ./database/enum.CrudOperation.html
+    /// [`Value`]: ./value/enum.Value.html
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`ValidationFailed`] listing every required property missing
+    /// from a create input, along with every failure reported by a property's validator. Returns
+    /// [`SchemaItemNotFound`] if the type doesn't exist or the input contains a field that the
+    /// operation doesn't accept, and [`TypeNotExpected`] if the operation is not `CreateNode` or
+    /// `UpdateNode`, or the input is not a map.
+    ///
+    /// [`Error`]: ../error/enum.Error.html
+    /// [`SchemaItemNotFound`]: ../error/enum.Error.html#variant.SchemaItemNotFound
+    /// [`TypeNotExpected`]: ../error/enum.Error.html#variant.TypeNotExpected
+    /// [`ValidationFailed`]: ../error/enum.Error.html#variant.ValidationFailed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use serde_json::json;
+    /// # use std::convert::TryFrom;
+    /// # use warpgrapher::{Configuration, Engine};
+    /// # use warpgrapher::engine::database::CrudOperation;
+    /// # use warpgrapher::engine::database::no_database::NoDatabasePool;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let config = Configuration::try_from("
+    /// version: 1
+    /// model:
+    ///   - name: User
+    ///     props:
+    ///       - name: email
+    ///         type: String
+    /// ".to_string())?;
+    /// let engine = Engine::<()>::new(config, NoDatabasePool {}).build()?;
+    ///
+    /// let create_user = CrudOperation::CreateNode("User".to_string());
+    /// assert!(engine.validate_input(create_user.clone(), json!({"email": "alice@example.com"})).is_ok());
+    /// assert!(engine.validate_input(create_user, json!({})).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate_input(
+        &self,
+        operation: CrudOperation,
+        input: serde_json::Value,
+    ) -> Result<Value, Error> {
+        debug!(
+            "Engine::validate_input called -- operation: {:#?}, input: {:#?}",
+            operation, input
+        );
+
+        let (input_type_name, create) = match &operation {
+            CrudOperation::CreateNode(type_name) => {
+                (type_name.to_string() + "CreateMutationInput", true)
+            }
+            CrudOperation::UpdateNode(type_name) => {
+                (type_name.to_string() + "UpdateMutationInput", false)
+            }
+            _ => {
+                return Err(Error::TypeNotExpected {
+                    details: Some(
+                        "validate_input expects a CreateNode or UpdateNode operation".to_string(),
+                    ),
+                })
+            }
+        };
+
+        let gql_schema: HashMap<String, NodeType> =
+            crate::engine::schema::generate_schema(&self.config)?;
+        let itd = gql_schema
+            .get(&input_type_name)
+            .ok_or(Error::SchemaItemNotFound {
+                name: input_type_name,
+            })?;
+
+        validate_node_input(itd, &self.validators, Value::try_from(input)?, create)
+    }
+
+    async fn explain_with_log(
+        &self,
+        query: String,
+        input: Option<serde_json::Value>,
+        log: StatementLog,
+    ) -> Result<Vec<String>, Error> {
+        debug!("Engine::explain called -- input: {:#?}", input);
+
+        let gql_schema: HashMap<String, NodeType> =
+            crate::engine::schema::generate_schema(&self.config)?;
+        let info = Info::new("".to_string(), Arc::new(gql_schema));
+
+        let input_value: Option<juniper::InputValue> = match input {
+            Some(input) => Some(serde_json::from_value::<juniper::InputValue>(input)?),
+            None => None,
+        };
+
+        let gqlctx = GraphQLContext::<RequestCtx>::new(
+            self.db_pool.with_statement_log(log.clone())?,
+            self.resolvers.clone(),
+            self.resolver_wrappers.clone(),
+            self.validators.clone(),
+            self.event_handlers.clone(),
+            Some(RequestCtx::new()),
+            self.version.clone(),
+            Metadata::new(),
+            info,
+            self.loader_max_batch,
+        )
+        .with_directives(self.directives.clone())
+        .with_resolver_timeouts(self.resolver_timeouts.clone())
+        .with_write_depth_limits(self.max_write_depth, self.type_max_write_depths.clone())
+        .with_require_read_filter(self.require_read_filter);
+        let req = GraphQLRequest::new(query, None, input_value);
+        let res = req.execute(&self.root_node, &gqlctx).await;
+        let response = serde_json::to_value(&res)?;
+        debug!("Engine::explain -- response: {:#?}", response);
+
+        match response.get("errors") {
+            Some(errors) => Err(Error::ExplainFailed {
+                errors: errors.clone(),
+                statements: log.statements(),
+            }),
+            None => Ok(log.statements()),
+        }
+    }
+}
+
+impl<RequestCtx> Display for Engine<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{:#?}", self)
+    }
+}
+
+impl<RequestCtx> Debug for Engine<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        f.debug_struct("Engine")
+            .field("config", &self.config)
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+/// Returns the query, operation name, and variables of a request in the body of a GraphQL HTTP
+/// request. The request must be an object with a string `query`. The `operationName` and
+/// `variables` fields are optional.
+fn batch_request_parts(
+    request: serde_json::Value,
+) -> Result<(String, Option<String>, Option<serde_json::Value>), Error> {
+    let mut m = match request {
+        serde_json::Value::Object(m) => m,
+        _ => {
+            return Err(Error::InputItemNotFound {
+                name: "query".to_string(),
+            })
+        }
+    };
+
+    let query = match m.remove("query") {
+        Some(serde_json::Value::String(query)) => query,
+        _ => {
+            return Err(Error::InputItemNotFound {
+                name: "query".to_string(),
+            })
+        }
+    };
+    let operation_name = match m.remove("operationName") {
+        Some(serde_json::Value::String(name)) => Some(name),
+        _ => None,
+    };
+    let variables = m.remove("variables").filter(|v| !v.is_null());
+
+    Ok((query, operation_name, variables))
+}
+
+/// Notably, the unit tests here likely seem weak. This is because testing most
+/// of the functionality requires a database container to be running and
+/// reachable, so most of the coverage is provided by integration tests.
+#[cfg(test)]
+mod tests {
+    use super::EngineBuilder;
+    use crate::engine::allowlist::query_hash;
+    use crate::engine::config::Directive;
+    use crate::engine::context::RequestContext;
+    use crate::engine::database::no_database::{NoDatabaseEndpoint, NoDatabasePool, NoTransaction};
+    use crate::engine::database::{CrudOperation, DatabaseEndpoint, DatabasePool};
+    use crate::engine::etag::etag;
+    use crate::engine::events::{EventFacade, EventHandlerBag};
+    use crate::engine::metadata::Metadata;
+    use crate::engine::resolvers::{
+        Directives, Next, ResolverFacade, ResolverTimeout, ResolverTimeouts, Resolvers,
+    };
+    use crate::engine::schema::remove_unknown_input_fields;
+    use crate::engine::validators::Validators;
+    use crate::engine::value::Value;
+    use crate::{Configuration, Engine, Error};
+    use async_trait::async_trait;
+    use juniper::{BoxFuture, ExecutionResult, FieldError};
+    use std::collections::HashMap;
+    use std::convert::TryInto;
+    use std::fs::File;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Passes if the engine can be created.
+    #[test]
+    fn engine_new() {
+        let _engine = Engine::<()>::new(
+            File::open("tests/fixtures/minimal.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .build()
+        .unwrap();
+    }
+
+    /// Passes if the loader batch size defaults when unset and is carried into the built engine
+    /// when set.
+    #[test]
+    fn engine_loader_max_batch() {
+        let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+            .build()
+            .unwrap();
+        assert_eq!(engine.loader_max_batch, super::DEFAULT_LOADER_MAX_BATCH);
+
+        let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+            .with_loader_max_batch(7)
+            .build()
+            .unwrap();
+        assert_eq!(engine.loader_max_batch, 7);
+    }
+
+    /// Passes if read-after-write consistency is on when unset and is carried into the built
+    /// engine when turned off.
+    #[test]
+    fn engine_read_after_write() {
+        let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+            .build()
+            .unwrap();
+        assert!(engine.read_after_write);
+
+        let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+            .with_read_after_write(false)
+            .build()
+            .unwrap();
+        assert!(!engine.read_after_write);
+    }
+
+    /// Passes if explaining a query whose resolution fails returns the GraphQL errors, rather
+    /// than an empty list of statements
+    #[tokio::test]
+    async fn engine_explain_errors() {
+        let config: Configuration = File::open("tests/fixtures/minimal.yml")
+            .expect("Couldn't read config")
+            .try_into()
+            .expect("Couldn't convert to config");
+        let engine = Engine::<()>::new(config, NoDatabasePool {})
+            .build()
+            .unwrap();
+
+        match engine
+            .explain("query { Project { id name } }".to_string(), None)
+            .await
+        {
+            Err(Error::ExplainFailed { errors, statements }) => {
+                assert_eq!(errors.as_array().unwrap().len(), 1);
+                assert!(statements.is_empty());
+            }
+            r => panic!("Expected ExplainFailed, got {:?}", r),
+        }
+    }
+
+    #[derive(Clone)]
+    struct UnrecordedPool {}
+
+    #[async_trait]
+    impl DatabasePool for UnrecordedPool {
+        type TransactionType = NoTransaction;
+
+        async fn read_transaction(&self) -> Result<Self::TransactionType, Error> {
+            Ok(NoTransaction {})
+        }
+
+        async fn transaction(&self) -> Result<Self::TransactionType, Error> {
+            Ok(NoTransaction {})
+        }
+    }
+
+    struct UnrecordedEndpoint {}
+
+    #[async_trait]
+    impl DatabaseEndpoint for UnrecordedEndpoint {
+        type PoolType = UnrecordedPool;
+
+        async fn pool(&self) -> Result<Self::PoolType, Error> {
+            Ok(UnrecordedPool {})
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct UnrecordedRequestContext {}
+
+    impl RequestContext for UnrecordedRequestContext {
+        type DBEndpointType = UnrecordedEndpoint;
+
+        fn new() -> Self {
+            UnrecordedRequestContext {}
+        }
+    }
+
+    /// Passes if explaining against a back-end that doesn't record statements is refused, rather
+    /// than executing the operation
+    #[tokio::test]
+    async fn engine_explain_not_supported() {
+        let config: Configuration = File::open("tests/fixtures/minimal.yml")
+            .expect("Couldn't read config")
+            .try_into()
+            .expect("Couldn't convert to config");
+        let engine = Engine::<UnrecordedRequestContext>::new(config, UnrecordedPool {})
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            engine
+                .explain(
+                    "mutation { ProjectCreate(input: {name: \"Project Zero\"}) { id } }"
+                        .to_string(),
+                    None
+                )
+                .await,
+            Err(Error::ExplainNotSupported)
+        ));
+    }
+
+    /// Passes if a query with no filter is rejected when reads must be filtered, unless the
+    /// request sets the allowUnfilteredRead option
+    #[tokio::test]
+    async fn engine_require_read_filter() {
+        let config: Configuration = File::open("tests/fixtures/minimal.yml")
+            .expect("Couldn't read config")
+            .try_into()
+            .expect("Couldn't convert to config");
+        let engine = Engine::<()>::new(config, NoDatabasePool {})
+            .with_require_read_filter(true)
+            .build()
+            .unwrap();
+
+        let rejected = Error::UnfilteredReadRejected {
+            name: "Project".to_string(),
+        }
+        .to_string();
+        for query in &[
+            "query { Project { id } }",
+            "query { Project(input: {}) { id } }",
+            "query { ProjectOwner { id } }",
+        ] {
+            let response = engine
+                .execute(query.to_string(), None, None, HashMap::new())
+                .await
+                .unwrap();
+            let message = response["errors"][0]["message"].as_str().unwrap();
+            assert!(message.contains("has no input to filter"), "{}", message);
+        }
+
+        for query in &[
+            "query { Project(input: {name: {EQ: \"Project Zero\"}}) { id } }",
+            "query { Project(options: {allowUnfilteredRead: true}) { id } }",
+        ] {
+            let response = engine
+                .execute(query.to_string(), None, None, HashMap::new())
+                .await
+                .unwrap();
+            assert_ne!(response["errors"][0]["message"], rejected);
+        }
+    }
+
+    /// Passes if the async-graphql schema forwards a root query field to the engine
+    #[cfg(feature = "async-graphql")]
+    #[tokio::test]
+    async fn engine_async_graphql_schema_version() {
+        let config: Configuration = File::open("tests/fixtures/minimal.yml")
+            .expect("Couldn't read config")
+            .try_into()
+            .expect("Couldn't convert to config");
+        let engine = Engine::<()>::new(config, NoDatabasePool {})
+            .with_version("1.0".to_string())
+            .build()
+            .unwrap();
+        let schema = engine.async_graphql_schema().unwrap();
+
+        let response = schema.execute("query { v: _version }").await;
+        assert!(response.errors.is_empty());
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({"v": "1.0"})
+        );
+    }
+
+    /// Passes if nested selections, including union members, are executed as a query the
+    /// engine accepts, so the only error is the missing database, returned with its path and
+    /// its location in the client's query
+    #[cfg(feature = "async-graphql")]
+    #[tokio::test]
+    async fn engine_async_graphql_schema_nested_query() {
+        let config: Configuration = File::open("tests/fixtures/minimal.yml")
+            .expect("Couldn't read config")
+            .try_into()
+            .expect("Couldn't convert to config");
+        let engine = Engine::<()>::new(config, NoDatabasePool {})
+            .build()
+            .unwrap();
+        let schema = engine.async_graphql_schema().unwrap();
+
+        let response = schema
+            .execute(
+                "query { Portfolio(input: { id: { EQ: \"1\" } }) { id activity { repo dst { ... on Commit { id } } } } }",
+            )
+            .await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(
+            response.errors[0].message,
+            format!("{}", Error::DatabaseNotFound)
+        );
+        assert_eq!(
+            response.errors[0].path,
+            vec![async_graphql::PathSegment::Field("Portfolio".to_string())]
+        );
+        assert_eq!(
+            response.errors[0].locations,
+            vec![async_graphql::Pos { line: 1, column: 9 }]
+        );
+    }
+
+    /// Passes if a query sent through the async-graphql schema is checked against the allowlist
+    /// as the client wrote it
+    #[cfg(feature = "async-graphql")]
+    #[tokio::test]
+    async fn engine_async_graphql_schema_query_allowlist() {
+        let config: Configuration = File::open("tests/fixtures/minimal.yml")
+            .expect("Couldn't read config")
+            .try_into()
+            .expect("Couldn't convert to config");
+        let engine = Engine::<()>::new(config, NoDatabasePool {})
+            .with_version("1.0".to_string())
+            .with_query_allowlist(vec![query_hash("query { _version }")].into_iter().collect())
+            .build()
+            .unwrap();
+        let schema = engine.async_graphql_schema().unwrap();
+
+        let response = schema.execute("query {\n  _version\n}").await;
+        assert!(response.errors.is_empty());
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({"_version": "1.0"})
+        );
+
+        let response = schema.execute("query { v: _version }").await;
+        assert_eq!(response.errors.len(), 1);
+        assert_eq!(
+            response.errors[0].message,
+            Error::QueryNotAllowed {
+                hash: query_hash("query { v: _version }")
+            }
+            .to_string()
+        );
+    }
+
+    #[cfg(feature = "async-graphql")]
+    static ASYNC_GRAPHQL_REQUESTS: AtomicUsize = AtomicUsize::new(0);
+
+    #[cfg(feature = "async-graphql")]
+    fn count_before_request(
+        rctx: CallerRequestContext,
+        _ef: EventFacade<CallerRequestContext>,
+        _metadata: Metadata,
+    ) -> BoxFuture<Result<CallerRequestContext, Error>> {
+        ASYNC_GRAPHQL_REQUESTS.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async move { Ok(rctx) })
+    }
+
+    /// Passes if the root fields of an async-graphql operation are executed by the engine as one
+    /// request, with the operation name and variables sent by the client
+    #[cfg(feature = "async-graphql")]
+    #[tokio::test]
+    async fn engine_async_graphql_schema_single_request() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(operation_resolver));
+        let mut ehb = EventHandlerBag::new();
+        ehb.register_before_request(count_before_request);
+        ehb.register_before_request(audit_before_request);
+        let engine = Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .with_resolvers(resolvers)
+        .with_event_handlers(ehb)
+        .build()
+        .unwrap();
+        let schema = engine.async_graphql_schema().unwrap();
+
+        let response = schema
+            .execute(
+                async_graphql::Request::new(
+                    "query Who($token: Boolean!) { a: Caller b: Caller @include(if: $token) }",
+                )
+                .operation_name("Who")
+                .variables(async_graphql::Variables::from_json(
+                    serde_json::json!({"token": true}),
+                )),
+            )
+            .await;
+        assert!(response.errors.is_empty());
+        let caller = "Who:{\"token\":\"[REDACTED]\"}|Who|{\"token\":true}";
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({"a": caller, "b": caller})
+        );
+        assert_eq!(ASYNC_GRAPHQL_REQUESTS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_engine_validate_minimal() {
+        //No prop resolver in config
+        //No endpoint resolver in config
+        //No validator in config
+        //No resolver defined
+        //No validator defined
+        //is_ok
+        assert!(Engine::<()>::new(
+            File::open("tests/fixtures/config-validation/test_config_ok.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .build()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_engine_validate_custom_validators() {
+        //Validator defined
+        //No validator in config
+        //is_ok
+        let mut validators = Validators::new();
+        validators.insert("MyValidator".to_string(), Box::new(my_validator));
+        assert!(Engine::<()>::new(
+            File::open("tests/fixtures/minimal.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .with_validators(validators)
+        .build()
+        .is_ok());
+
+        //Validator defined
+        //Validator in config
+        //is_ok
+        let mut validators = Validators::new();
+        validators.insert("MyValidator".to_string(), Box::new(my_validator));
+        assert!(Engine::<()>::new(
+            File::open("tests/fixtures/config-validation/test_config_with_custom_validator.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .with_validators(validators)
+        .build()
+        .is_ok());
+
+        //Validator not defined
+        //validator in config
+        //is_err
+        let validators = Validators::new();
+        assert!(Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open(
+                    "tests/fixtures/config-validation/test_config_with_custom_validator.yml"
+                )
+                .expect("Couldn't read config")
+            )
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .with_validators(validators)
+        .build()
+        .is_err());
+    }
+
+    fn allow_directive<'a>(
+        _directive: &'a Directive,
+        _facade: ResolverFacade<'a, ()>,
+    ) -> BoxFuture<'a, Result<(), FieldError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Passes if the engine builds only when a handler is registered for every directive applied
+    /// in the configuration
+    #[test]
+    fn engine_validate_directives() {
+        let config = || -> Configuration {
+            File::open("tests/fixtures/config-validation/test_config_directives.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config")
+        };
+
+        match Engine::<()>::new(config(), NoDatabasePool {}).build() {
+            Err(Error::DirectiveNotFound { name }) => assert_eq!("auth", name),
+            _ => panic!("Expected DirectiveNotFound"),
+        }
+
+        let mut directives = Directives::<()>::new();
+        directives.insert("auth".to_string(), Box::new(allow_directive));
+        assert!(Engine::<()>::new(config(), NoDatabasePool {})
+            .with_directives(directives)
+            .build()
+            .is_ok());
+    }
+
+    fn shape_validator(value: &Value) -> Result<(), Error> {
+        match value {
+            Value::Map(m) => match m.get("shape") {
+                Some(Value::String(shape)) if shape == "triangle" => Err(Error::validation_failed(
+                    "Leaves can't be triangles.".to_string(),
+                )),
+                _ => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Passes if validate_input returns the input when it is valid, and otherwise collects
+    /// missing required props and validator failures, without using the database
+    #[test]
+    fn engine_validate_input() {
+        let mut validators = Validators::new();
+        validators.insert("MyValidator".to_string(), Box::new(shape_validator));
+        let engine = Engine::<()>::new(
+            File::open("tests/fixtures/config-validation/test_config_with_custom_validator.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .with_validators(validators)
+        .build()
+        .unwrap();
+        let create_leaf = CrudOperation::CreateNode("Leaf".to_string());
+        let update_leaf = CrudOperation::UpdateNode("Leaf".to_string());
+
+        let leaf = engine
+            .validate_input(
+                create_leaf.clone(),
+                serde_json::json!({"color": "green", "shape": "round"}),
+            )
+            .unwrap();
+        match leaf {
+            Value::Map(m) => {
+                assert_eq!(m.get("color"), Some(&Value::String("green".to_string())));
+                assert_eq!(m.get("shape"), Some(&Value::String("round".to_string())));
+            }
+            _ => panic!("Expected a map"),
+        }
+
+        match engine.validate_input(
+            create_leaf.clone(),
+            serde_json::json!({"shape": "triangle"}),
+        ) {
+            Err(Error::ValidationFailed { errors }) => {
+                let fields = errors.iter().map(|e| e.field()).collect::<Vec<&str>>();
+                assert_eq!(fields, vec!["color", "shape"]);
+            }
+            _ => panic!("Expected a validation failure"),
+        }
+
+        assert!(engine
+            .validate_input(update_leaf.clone(), serde_json::json!({"shape": "round"}))
+            .is_ok());
+        assert!(matches!(
+            engine.validate_input(update_leaf, serde_json::json!({"shape": "triangle"})),
+            Err(Error::ValidationFailed { .. })
+        ));
+
+        assert!(matches!(
+            engine.validate_input(
+                create_leaf,
+                serde_json::json!({"color": "green", "weight": 1})
+            ),
+            Err(Error::SchemaItemNotFound { .. })
+        ));
+        assert!(matches!(
+            engine.validate_input(
+                CrudOperation::ReadNode("Leaf".to_string()),
+                serde_json::json!({})
+            ),
+            Err(Error::TypeNotExpected { .. })
+        ));
+    }
+
+    #[test]
+    fn test_engine_validate_custom_endpoint() {
+        //No endpoint resolvers in config
+        //No resolver defined
+        //is_ok
+        assert!(Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open("tests/fixtures/config-validation/test_config_ok.yml")
+                    .expect("Couldn't read config")
+            )
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .build()
+        .is_ok());
+
+        //Endpoint resolver in config
+        //No resolver defined
+        //is_err
+        assert!(Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open("tests/fixtures/config-validation/test_config_with_custom_resolver.yml")
+                    .expect("Couldn't read config")
+            )
+            .expect("Couldn't convert config"),
+            NoDatabasePool {}
+        )
+        .build()
+        .is_err());
+
+        //Endpoint resolver in config
+        //Resolver defined
+        //is_ok
+        let mut resolvers = Resolvers::<()>::new();
+        resolvers.insert("MyResolver".to_string(), Box::new(my_resolver));
+        assert!(Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open("tests/fixtures/config-validation/test_config_with_custom_resolver.yml")
+                    .expect("Couldn't read config")
+            )
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .with_resolvers(resolvers)
+        .build()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_engine_validate_custom_prop() {
+        //Prop resolver in config
+        //Resolver defined
+        //is_ok
+        let mut resolvers = Resolvers::<()>::new();
+        resolvers.insert("MyResolver".to_string(), Box::new(my_resolver));
+        assert!(Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open(
+                    "tests/fixtures/config-validation/test_config_with_custom_prop_resolver.yml"
+                )
+                .expect("Couldn't read config")
+            )
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .with_resolvers(resolvers)
+        .build()
+        .is_ok());
+
+        //No prop resolver in config
+        //Resolver defined
+        //is_ok
+        let mut resolvers = Resolvers::<()>::new();
+        resolvers.insert("MyResolver".to_string(), Box::new(my_resolver));
+        assert!(Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open("tests/fixtures/minimal.yml").expect("Couldn't read config")
+            )
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .with_resolvers(resolvers)
+        .build()
+        .is_ok());
+
+        //Prop resolver in config
+        //No resolver defined
+        //is_err
+        assert!(Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open(
+                    "tests/fixtures/config-validation/test_config_with_custom_prop_resolver.yml"
+                )
+                .expect("Couldn't read config")
+            )
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {}
+        )
+        .build()
+        .is_err());
+    }
+
+    #[derive(Clone, Debug)]
+    struct CallerRequestContext {
+        caller: String,
+    }
+
+    impl RequestContext for CallerRequestContext {
+        type DBEndpointType = NoDatabaseEndpoint;
+
+        fn new() -> Self {
+            CallerRequestContext {
+                caller: "anonymous".to_string(),
+            }
+        }
+
+        fn from_metadata(metadata: &HashMap<String, String>) -> Self {
+            CallerRequestContext {
+                caller: metadata
+                    .get("caller")
+                    .cloned()
+                    .unwrap_or_else(|| "anonymous".to_string()),
+            }
+        }
+    }
+
+    fn caller_resolver(facade: ResolverFacade<CallerRequestContext>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let caller = facade
+                .request_context()
+                .map(|rctx| rctx.caller.clone())
+                .unwrap_or_default();
+            facade.resolve_scalar(caller)
+        })
+    }
+
+    /// Passes if the engine creates the request context from the request metadata, so that
+    /// resolvers see a fully initialized context
+    #[tokio::test]
+    async fn engine_request_context_from_metadata() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(caller_resolver));
+        let engine = Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .with_resolvers(resolvers)
+        .build()
+        .unwrap();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("caller".to_string(), "alice".to_string());
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, metadata)
+            .await
+            .unwrap();
+        assert_eq!(response["data"]["Caller"], "alice");
+
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(response["data"]["Caller"], "anonymous");
+    }
+
+    /// Passes if the operation name selects the operation to run from a query document with
+    /// more than one operation, and a document with several operations and no operation name is
+    /// answered with an error
+    #[tokio::test]
+    async fn engine_execute_operation_name() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(caller_resolver));
+        let engine = Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .with_resolvers(resolvers)
+        .build()
+        .unwrap();
+
+        let query = "query First { first: Caller } query Second { second: Caller }";
+        let response = engine
+            .execute(
+                query.to_string(),
+                Some("Second".to_string()),
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(response["data"], serde_json::json!({"second": "anonymous"}));
+
+        let response = engine
+            .execute(query.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert!(response.get("data").is_none());
+        assert_eq!(response["errors"].as_array().unwrap().len(), 1);
+
+        let response = engine
+            .execute(
+                query.to_string(),
+                Some("Third".to_string()),
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert!(response.get("data").is_none());
+        assert_eq!(response["errors"].as_array().unwrap().len(), 1);
+    }
+
+    fn audit_before_request(
+        mut rctx: CallerRequestContext,
+        ef: EventFacade<CallerRequestContext>,
+        _metadata: Metadata,
+    ) -> BoxFuture<Result<CallerRequestContext, Error>> {
+        Box::pin(async move {
+            rctx.caller = format!(
+                "{}:{}",
+                ef.operation_name().unwrap_or("anonymous"),
+                ef.redacted_variables(&["token"])
+            );
+            Ok(rctx)
+        })
+    }
+
+    fn operation_resolver(
+        facade: ResolverFacade<CallerRequestContext>,
+    ) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let operation = format!(
+                "{}|{}|{}",
+                facade
+                    .request_context()
+                    .map(|rctx| rctx.caller.clone())
+                    .unwrap_or_default(),
+                facade.operation_name().unwrap_or("anonymous"),
+                facade.variables()
+            );
+            facade.resolve_scalar(operation)
+        })
+    }
+
+    /// Passes if event handlers and resolvers see the operation name and variables of the
+    /// request, and redacted variables hide the values of the named variables
+    #[tokio::test]
+    async fn engine_execute_operation_name_and_variables() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(operation_resolver));
+        let mut ehb = EventHandlerBag::new();
+        ehb.register_before_request(audit_before_request);
+        let engine = Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .with_resolvers(resolvers)
+        .with_event_handlers(ehb)
+        .build()
+        .unwrap();
+
+        let response = engine
+            .execute(
+                "query Who($token: Boolean!) { Caller @include(if: $token) }".to_string(),
+                Some("Who".to_string()),
+                Some(serde_json::json!({"token": true})),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(
+            response["data"]["Caller"],
+            "Who:{\"token\":\"[REDACTED]\"}|Who|{\"token\":true}"
+        );
+
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(response["data"]["Caller"], "anonymous:{}|anonymous|{}");
+    }
+
+    /// Passes if a request whose variables are already a juniper InputValue is executed the same
+    /// as one with JSON variables, with the variables given to event handlers and resolvers as
+    /// JSON
+    #[tokio::test]
+    async fn engine_execute_with_input_value() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(operation_resolver));
+        let mut ehb = EventHandlerBag::new();
+        ehb.register_before_request(audit_before_request);
+        let engine = Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .with_resolvers(resolvers)
+        .with_event_handlers(ehb)
+        .build()
+        .unwrap();
+
+        let input = juniper::InputValue::object(
+            vec![("token", juniper::InputValue::scalar(true))]
+                .into_iter()
+                .collect(),
+        );
+        let response = engine
+            .execute_with_input_value(
+                "query Who($token: Boolean!) { Caller @include(if: $token) }".to_string(),
+                Some("Who".to_string()),
+                Some(input),
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(
+            response["data"]["Caller"],
+            "Who:{\"token\":\"[REDACTED]\"}|Who|{\"token\":true}"
+        );
+
+        let response = engine
+            .execute_with_input_value("query { Caller }".to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(response["data"]["Caller"], "anonymous:{}|anonymous|{}");
+    }
+
+    fn forbidden_resolver(
+        facade: ResolverFacade<CallerRequestContext>,
+    ) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let caller = facade
+                .request_context()
+                .map(|rctx| rctx.caller.clone())
+                .unwrap_or_default();
+            if caller == "anonymous" {
+                return facade.error("FORBIDDEN", "Anonymous callers are not permitted");
+            }
+            facade.resolve_scalar(caller)
+        })
+    }
+
+    /// Passes if a mutation asking for a profile is rejected unless profiling is enabled
+    #[tokio::test]
+    async fn engine_profiling_disabled() {
+        let query = "mutation { ProjectCreate(input: { name: \"Project Zero\" }, options: { profile: true }) { id } }";
+
+        let engine = Engine::<()>::new(
+            File::open("tests/fixtures/minimal.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .build()
+        .unwrap();
+        let response = engine
+            .execute(query.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            response["errors"][0]["message"],
+            Error::ProfilingDisabled.to_string()
+        );
+
+        let engine = Engine::<()>::new(
+            File::open("tests/fixtures/minimal.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .with_profiling(true)
+        .build()
+        .unwrap();
+        let response = engine
+            .execute(query.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            response["errors"][0]["message"],
+            Error::DatabaseNotFound.to_string()
+        );
+        assert!(response.get("_profile").is_none());
+    }
+
+    /// Passes if a read-only engine leaves the generated mutations out of its schema, so that a
+    /// request for one fails validation, while reads are still resolved
+    #[tokio::test]
+    async fn engine_read_only() {
+        let engine = Engine::<()>::new(
+            File::open("tests/fixtures/minimal.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        )
+        .read_only()
+        .build()
+        .unwrap();
+
+        let sdl = engine.root_node.as_schema_language();
+        assert!(sdl.contains("  Project("));
+        assert!(sdl.contains("  ProjectIssues("));
+        assert!(!sdl.contains("ProjectCreate("));
+        assert!(!sdl.contains("ProjectUpdate("));
+        assert!(!sdl.contains("ProjectDelete("));
+        assert!(!sdl.contains("ProjectIssuesCreate("));
+
+        let response = engine
+            .execute(
+                "mutation { ProjectCreate(input: { name: \"Project Zero\" }) { id } }".to_string(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert!(response["data"].is_null());
+        assert!(response["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("Unknown field \"ProjectCreate\""));
+
+        let response = engine
+            .execute(
+                "query { Project { id } }".to_string(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response["errors"][0]["message"],
+            Error::DatabaseNotFound.to_string()
+        );
+    }
+
+    /// Passes if unknown fields of the variables, at any depth, are rejected by default, and are
+    /// removed before the request is executed when the engine ignores them
+    #[tokio::test]
+    async fn engine_ignore_unknown_input_fields() {
+        let query = "mutation Create($input: ProjectCreateMutationInput!) { \
+                     ProjectCreate(input: $input) { id } }"
+            .to_string();
+        let variables = serde_json::json!({
+            "input": {
+                "name": "Project Zero",
+                "legacyCode": "PZ",
+                "issues": [{
+                    "since": "today",
+                    "legacyEffort": 2,
+                    "dst": { "Bug": { "NEW": { "name": "Bug Zero" } } }
+                }]
+            }
+        });
+        let builder = Engine::<()>::new(
+            File::open("tests/fixtures/minimal.yml")
+                .expect("Couldn't read config")
+                .try_into()
+                .expect("Couldn't convert to config"),
+            NoDatabasePool {},
+        );
+
+        let strict = builder.clone().build().unwrap();
+        let response = strict
+            .execute(query.clone(), None, Some(variables.clone()), HashMap::new())
+            .await
+            .unwrap();
+        let errors = response["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors
+            .iter()
+            .all(|e| e["message"].as_str().unwrap().contains("Unknown field")));
+
+        let lenient = builder
+            .with_ignore_unknown_input_fields(true)
+            .build()
+            .unwrap();
+        let mut input_value: juniper::InputValue =
+            serde_json::from_value(variables.clone()).unwrap();
+        assert_eq!(
+            remove_unknown_input_fields(&lenient.root_node, &query, None, &mut input_value),
+            vec![
+                "$input.legacyCode".to_string(),
+                "$input.issues.legacyEffort".to_string()
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&input_value).unwrap()["input"]["issues"][0],
+            serde_json::json!({
+                "since": "today",
+                "dst": { "Bug": { "NEW": { "name": "Bug Zero" } } }
+            })
+        );
+
+        let response = lenient
+            .execute(query, None, Some(variables), HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            response["errors"][0]["message"],
+            Error::DatabaseNotFound.to_string()
+        );
+    }
+
+    /// Passes if a query in the allowlist is executed, however it is formatted, and any other
+    /// query is rejected
+    #[tokio::test]
+    async fn engine_query_allowlist() {
+        let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+            .with_version("1.0.0".to_string())
+            .with_query_allowlist(vec![query_hash("query { _version }")].into_iter().collect())
+            .build()
+            .unwrap();
+
+        let response = engine
+            .execute(
+                "query {\n  _version # the app version\n}\n".to_string(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response["data"]["_version"], "1.0.0");
+
+        let query = "query { _version __typename }".to_string();
+        let result = engine
+            .execute(query.clone(), None, None, HashMap::new())
+            .await;
+        assert!(
+            matches!(result, Err(Error::QueryNotAllowed { hash }) if hash == query_hash(&query))
+        );
+    }
+
+    /// Passes if a batch of requests returns a response for each request, in order, with the
+    /// errors of a failed request in its own response, and a single request is executed as is
+    #[tokio::test]
+    async fn engine_execute_batch() {
+        for concurrent in [false, true] {
+            let engine = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+                .with_version("1.0.0".to_string())
+                .with_concurrent_batches(concurrent)
+                .build()
+                .unwrap();
 
-    /// Passes if the engine can be created.
+            let responses = engine
+                .execute_batch(
+                    serde_json::json!([
+                        {"query": "query { _version }"},
+                        {"operationName": "Missing"},
+                        {"query": "query A { __typename } query B { _version }", "operationName": "B"}
+                    ]),
+                    HashMap::new(),
+                )
+                .await
+                .unwrap();
+            let responses = responses.as_array().unwrap();
+            assert_eq!(responses.len(), 3);
+            assert_eq!(responses[0]["data"]["_version"], "1.0.0");
+            assert!(responses[1]["data"].is_null());
+            assert!(responses[1]["errors"][0]["message"].is_string());
+            assert_eq!(responses[2]["data"]["_version"], "1.0.0");
+
+            let response = engine
+                .execute_batch(
+                    serde_json::json!({"query": "query { _version }", "variables": null}),
+                    HashMap::new(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response["data"]["_version"], "1.0.0");
+        }
+    }
+
+    /// Passes if a read-only engine fails to build when the configuration has a custom mutation
     #[test]
-    fn engine_new() {
-        let _engine = Engine::<()>::new(
-            File::open("tests/fixtures/minimal.yml")
+    fn engine_read_only_mutation_endpoint() {
+        let config = Configuration::try_from(
+            "
+version: 1
+model:
+  - name: User
+    props:
+      - name: name
+        type: String
+endpoints:
+  - name: Caller
+    class: Mutation
+    input: null
+    output:
+      type: String
+",
+        )
+        .unwrap();
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(forbidden_resolver));
+
+        match Engine::<CallerRequestContext>::new(config, NoDatabasePool {})
+            .with_resolvers(resolvers)
+            .read_only()
+            .build()
+        {
+            Err(Error::ConfigItemInvalid { type_name, .. }) => assert_eq!("Caller", type_name),
+            _ => panic!("Expected ConfigItemInvalid"),
+        }
+    }
+
+    /// Passes if a custom resolver can return an error carrying a machine-readable code in its
+    /// extensions
+    #[tokio::test]
+    async fn engine_resolver_error_code() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(forbidden_resolver));
+        let engine = Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
                 .expect("Couldn't read config")
                 .try_into()
                 .expect("Couldn't convert to config"),
             NoDatabasePool {},
         )
+        .with_resolvers(resolvers)
         .build()
         .unwrap();
+
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert!(response["data"]["Caller"].is_null());
+        assert_eq!(
+            response["errors"][0]["message"],
+            "Anonymous callers are not permitted"
+        );
+        assert_eq!(response["errors"][0]["extensions"]["code"], "FORBIDDEN");
+
+        let mut metadata = HashMap::new();
+        metadata.insert("caller".to_string(), "admin".to_string());
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, metadata)
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(response["data"]["Caller"], "admin");
     }
 
-    #[test]
-    fn test_engine_validate_minimal() {
-        //No prop resolver in config
-        //No endpoint resolver in config
-        //No validator in config
-        //No resolver defined
-        //No validator defined
-        //is_ok
-        assert!(Engine::<()>::new(
-            File::open("tests/fixtures/config-validation/test_config_ok.yml")
+    fn slow_resolver(facade: ResolverFacade<CallerRequestContext>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            if facade.metadata().get_str("slow").is_some() {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            }
+            facade.resolve_scalar("done")
+        })
+    }
+
+    fn timeout_engine(timeout: ResolverTimeout) -> Engine<CallerRequestContext> {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(slow_resolver));
+        let mut timeouts = ResolverTimeouts::new();
+        timeouts.insert("Caller".to_string(), timeout);
+        Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
                 .expect("Couldn't read config")
                 .try_into()
                 .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
+        .with_resolvers(resolvers)
+        .with_resolver_timeouts(timeouts)
         .build()
-        .is_ok());
+        .unwrap()
+    }
+
+    /// Passes if a custom resolver that runs longer than its timeout resolves to the timeout's
+    /// fallback value, or to an error if there is no fallback, while a resolver that finishes in
+    /// time resolves as usual
+    #[tokio::test]
+    async fn engine_resolver_timeout() {
+        let mut slow = HashMap::new();
+        slow.insert("slow".to_string(), "true".to_string());
+
+        let engine = timeout_engine(
+            ResolverTimeout::new(std::time::Duration::from_millis(10))
+                .with_fallback(juniper::Value::scalar("fallback")),
+        );
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(response["data"]["Caller"], "done");
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, slow.clone())
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(response["data"]["Caller"], "fallback");
+
+        let engine = timeout_engine(ResolverTimeout::new(std::time::Duration::from_millis(10)));
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, slow)
+            .await
+            .unwrap();
+        assert!(response["data"]["Caller"].is_null());
+        assert_eq!(
+            response["errors"][0]["message"],
+            "The custom resolver named Caller did not finish within 10ms"
+        );
     }
 
+    /// Passes if a timeout for a resolver that hasn't been added fails validation
     #[test]
-    fn test_engine_validate_custom_validators() {
-        //Validator defined
-        //No validator in config
-        //is_ok
-        let mut validators = Validators::new();
-        validators.insert("MyValidator".to_string(), Box::new(my_validator));
-        assert!(Engine::<()>::new(
-            File::open("tests/fixtures/minimal.yml")
+    fn engine_resolver_timeout_not_found() {
+        let mut timeouts = ResolverTimeouts::new();
+        timeouts.insert(
+            "Missing".to_string(),
+            ResolverTimeout::new(std::time::Duration::from_millis(10)),
+        );
+        let result = Engine::<()>::new(Configuration::default(), NoDatabasePool {})
+            .with_resolver_timeouts(timeouts)
+            .build();
+        assert!(matches!(result, Err(Error::ResolverNotFound { name }) if name == "Missing"));
+    }
+
+    fn outer_wrapper<'a>(
+        facade: ResolverFacade<'a, CallerRequestContext>,
+        next: Next<'a, CallerRequestContext>,
+    ) -> BoxFuture<'a, ExecutionResult> {
+        Box::pin(async move {
+            let v = next.run(facade).await?;
+            Ok(juniper::Value::scalar(format!(
+                "outer({})",
+                v.as_string_value().unwrap_or_default()
+            )))
+        })
+    }
+
+    fn inner_wrapper<'a>(
+        facade: ResolverFacade<'a, CallerRequestContext>,
+        next: Next<'a, CallerRequestContext>,
+    ) -> BoxFuture<'a, ExecutionResult> {
+        Box::pin(async move {
+            let v = next.run(facade).await?;
+            Ok(juniper::Value::scalar(format!(
+                "inner({})",
+                v.as_string_value().unwrap_or_default()
+            )))
+        })
+    }
+
+    /// Passes if resolver wrappers run around custom resolvers, with the first wrapper
+    /// registered as the outermost
+    #[tokio::test]
+    async fn engine_resolver_wrappers_compose_in_order() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Caller".to_string(), Box::new(caller_resolver));
+        let engine = Engine::<CallerRequestContext>::new(
+            File::open("tests/fixtures/config-validation/test_config_scalar_custom_endpoint.yml")
                 .expect("Couldn't read config")
                 .try_into()
                 .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
-        .with_validators(validators)
+        .with_resolvers(resolvers)
+        .wrap_resolvers(Box::new(outer_wrapper))
+        .wrap_resolvers(Box::new(inner_wrapper))
         .build()
-        .is_ok());
+        .unwrap();
 
-        //Validator defined
-        //Validator in config
-        //is_ok
-        let mut validators = Validators::new();
-        validators.insert("MyValidator".to_string(), Box::new(my_validator));
-        assert!(Engine::<()>::new(
-            File::open("tests/fixtures/config-validation/test_config_with_custom_validator.yml")
-                .expect("Couldn't read config")
-                .try_into()
-                .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+        let mut metadata = HashMap::new();
+        metadata.insert("caller".to_string(), "alice".to_string());
+        let response = engine
+            .execute("query { Caller }".to_string(), None, None, metadata)
+            .await
+            .unwrap();
+        assert_eq!(response["data"]["Caller"], "outer(inner(alice))");
+    }
+
+    fn leaf_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let mut props = HashMap::new();
+            props.insert("id".to_string(), Value::String("1".to_string()));
+            props.insert("color".to_string(), Value::String("green".to_string()));
+            facade.resolve_node(&facade.node("Leaf", props)).await
+        })
+    }
+
+    /// Passes if relationship fields excluded by @skip or @include are never resolved. Any
+    /// database read fails against NoDatabasePool, so a query that completes without errors
+    /// made no backend query for the excluded relationship.
+    #[tokio::test]
+    async fn engine_skip_include_rel_not_read() {
+        let mut resolvers = Resolvers::<()>::new();
+        resolvers.insert("MyResolver".to_string(), Box::new(leaf_resolver));
+        let engine = Engine::<()>::new(
+            TryInto::<Configuration>::try_into(
+                File::open("tests/fixtures/config-validation/test_config_with_custom_resolver.yml")
+                    .expect("Couldn't read config"),
+            )
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {},
         )
-        .with_validators(validators)
+        .with_resolvers(resolvers)
         .build()
-        .is_ok());
+        .unwrap();
 
-        //Validator not defined
-        //validator in config
-        //is_err
-        let validators = Validators::new();
-        assert!(Engine::<()>::new(
+        let read_stem = "query { MyResolver { id color stem { dst { ... on Leaf { id } } } } }";
+        let response = engine
+            .execute(read_stem.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_some());
+
+        for query in [
+            "query { MyResolver { id color stem @skip(if: true) { dst { ... on Leaf { id } } } } }",
+            "query { MyResolver { id color stem @include(if: false) { dst { ... on Leaf { id } } } } }",
+            "query($skip: Boolean!) { MyResolver { id color stem @skip(if: $skip) { dst { ... on Leaf { id } } } } }",
+        ] {
+            let response = engine
+                .execute(
+                    query.to_string(),
+                    None,
+                    Some(serde_json::json!({"skip": true})),
+                    HashMap::new(),
+                )
+                .await
+                .unwrap();
+            assert!(response.get("errors").is_none());
+            assert_eq!(
+                response["data"],
+                serde_json::json!({"MyResolver": {"id": "1", "color": "green"}})
+            );
+        }
+    }
+
+    fn place_order_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let line_items = match facade.args().get::<Value>("input") {
+                Some(Value::Map(mut order)) => match order.remove("lineItems") {
+                    Some(Value::Array(line_items)) => line_items,
+                    _ => Vec::new(),
+                },
+                _ => Vec::new(),
+            };
+            let quantity: i64 = line_items
+                .iter()
+                .map(|line_item| match line_item {
+                    Value::Map(m) => match m.get("quantity") {
+                        Some(Value::Int64(q)) => *q,
+                        _ => 0,
+                    },
+                    _ => 0,
+                })
+                .sum();
+            facade.resolve_scalar(quantity as i32)
+        })
+    }
+
+    /// Passes if a custom endpoint input with a list of nested input objects is handed to the
+    /// resolver as a map holding a list of maps
+    #[tokio::test]
+    async fn engine_custom_endpoint_nested_input() {
+        let mut resolvers = Resolvers::<()>::new();
+        resolvers.insert("PlaceOrder".to_string(), Box::new(place_order_resolver));
+        let engine = Engine::<()>::new(
             TryInto::<Configuration>::try_into(
                 File::open(
-                    "tests/fixtures/config-validation/test_config_with_custom_validator.yml"
+                    "tests/fixtures/config-validation/test_config_nested_custom_endpoint_input.yml",
                 )
-                .expect("Couldn't read config")
+                .expect("Couldn't read config"),
             )
             .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
-        .with_validators(validators)
+        .with_resolvers(resolvers)
         .build()
-        .is_err());
+        .unwrap();
+
+        let query = "mutation { PlaceOrder(input: {customer: \"alice\", lineItems: [{sku: \"a\", quantity: 2}, {sku: \"b\", quantity: 3}]}) }";
+        let response = engine
+            .execute(query.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(response["data"]["PlaceOrder"], 5);
     }
 
-    #[test]
-    fn test_engine_validate_custom_endpoint() {
-        //No endpoint resolvers in config
-        //No resolver defined
-        //is_ok
-        assert!(Engine::<()>::new(
+    fn featured_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let mut props = HashMap::new();
+            props.insert("id".to_string(), Value::String("1".to_string()));
+            props.insert("name".to_string(), Value::String("Apollo".to_string()));
+            facade
+                .resolve_node_list(vec![facade.node("Project", props)])
+                .await
+        })
+    }
+
+    fn failing_points_resolver(_facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move { Err(Error::DatabaseNotFound.into()) })
+    }
+
+    fn project_status_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move { facade.resolve_scalar("active") })
+    }
+
+    /// Passes if a failing custom prop resolver nulls only its own field, reporting the error
+    /// at that field's path while sibling fields still return data
+    #[tokio::test]
+    async fn engine_partial_data_on_resolver_error() {
+        let mut resolvers = Resolvers::<()>::new();
+        resolvers.insert("Featured".to_string(), Box::new(featured_resolver));
+        resolvers.insert(
+            "FailingPoints".to_string(),
+            Box::new(failing_points_resolver),
+        );
+        resolvers.insert(
+            "ProjectStatus".to_string(),
+            Box::new(project_status_resolver),
+        );
+        let engine = Engine::<()>::new(
             TryInto::<Configuration>::try_into(
-                File::open("tests/fixtures/config-validation/test_config_ok.yml")
-                    .expect("Couldn't read config")
+                File::open("tests/fixtures/config-validation/test_config_partial_data.yml")
+                    .expect("Couldn't read config"),
             )
             .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
+        .with_version("1.0".to_string())
+        .with_resolvers(resolvers)
         .build()
-        .is_ok());
+        .unwrap();
 
-        //Endpoint resolver in config
-        //No resolver defined
-        //is_err
-        assert!(Engine::<()>::new(
+        let query = "query { Featured { id name points status } _version }";
+        let response = engine
+            .execute(query.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            response["data"],
+            serde_json::json!({
+                "Featured": [{"id": "1", "name": "Apollo", "points": null, "status": "active"}],
+                "_version": "1.0"
+            })
+        );
+        let errors = response["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["path"], serde_json::json!(["Featured", "points"]));
+    }
+
+    static GUARDED_STATUS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn guarded_featured_resolver(
+        facade: ResolverFacade<CallerRequestContext>,
+    ) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let mut props = HashMap::new();
+            props.insert("id".to_string(), Value::String("1".to_string()));
+            props.insert("name".to_string(), Value::String("Apollo".to_string()));
+            facade
+                .resolve_node_list(vec![facade.node("Project", props)])
+                .await
+        })
+    }
+
+    fn guarded_status_resolver(
+        facade: ResolverFacade<CallerRequestContext>,
+    ) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            GUARDED_STATUS_CALLS.fetch_add(1, Ordering::SeqCst);
+            facade.resolve_scalar("audited")
+        })
+    }
+
+    fn admin_only(ef: &EventFacade<CallerRequestContext>) -> bool {
+        matches!(ef.context().request_context(), Some(rctx) if rctx.caller == "admin")
+    }
+
+    /// Passes if a guarded field resolves for a caller that passes its guard, and resolves to
+    /// null for any other caller without its resolver being called
+    #[tokio::test]
+    async fn engine_field_guard() {
+        let mut resolvers = Resolvers::<CallerRequestContext>::new();
+        resolvers.insert("Featured".to_string(), Box::new(guarded_featured_resolver));
+        resolvers.insert(
+            "FailingPoints".to_string(),
+            Box::new(guarded_status_resolver),
+        );
+        resolvers.insert(
+            "ProjectStatus".to_string(),
+            Box::new(guarded_status_resolver),
+        );
+        let mut handlers = EventHandlerBag::<CallerRequestContext>::new();
+        handlers.register_field_guard("Project".to_string(), "status".to_string(), admin_only);
+        let engine = Engine::<CallerRequestContext>::new(
             TryInto::<Configuration>::try_into(
-                File::open("tests/fixtures/config-validation/test_config_with_custom_resolver.yml")
-                    .expect("Couldn't read config")
+                File::open("tests/fixtures/config-validation/test_config_partial_data.yml")
+                    .expect("Couldn't read config"),
             )
-            .expect("Couldn't convert config"),
-            NoDatabasePool {}
+            .expect("Couldn't convert to config"),
+            NoDatabasePool {},
         )
+        .with_resolvers(resolvers)
+        .with_event_handlers(handlers)
         .build()
-        .is_err());
+        .unwrap();
 
-        //Endpoint resolver in config
-        //Resolver defined
-        //is_ok
+        let query = "query { Featured { name status } }";
+        let response = engine
+            .execute(query.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            response["data"],
+            serde_json::json!({"Featured": [{"name": "Apollo", "status": null}]})
+        );
+        assert!(response.get("errors").is_none());
+        assert_eq!(GUARDED_STATUS_CALLS.load(Ordering::SeqCst), 0);
+
+        let mut metadata = HashMap::new();
+        metadata.insert("caller".to_string(), "admin".to_string());
+        let response = engine
+            .execute(query.to_string(), None, None, metadata)
+            .await
+            .unwrap();
+        assert_eq!(
+            response["data"],
+            serde_json::json!({"Featured": [{"name": "Apollo", "status": "audited"}]})
+        );
+        assert_eq!(GUARDED_STATUS_CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    /// Passes if a node's entity tag is a hash of all of its fields, the same whichever fields
+    /// are selected
+    #[tokio::test]
+    async fn engine_node_etag() {
         let mut resolvers = Resolvers::<()>::new();
-        resolvers.insert("MyResolver".to_string(), Box::new(my_resolver));
-        assert!(Engine::<()>::new(
+        resolvers.insert("Featured".to_string(), Box::new(featured_resolver));
+        resolvers.insert(
+            "FailingPoints".to_string(),
+            Box::new(failing_points_resolver),
+        );
+        resolvers.insert(
+            "ProjectStatus".to_string(),
+            Box::new(project_status_resolver),
+        );
+        let engine = Engine::<()>::new(
             TryInto::<Configuration>::try_into(
-                File::open("tests/fixtures/config-validation/test_config_with_custom_resolver.yml")
-                    .expect("Couldn't read config")
+                File::open("tests/fixtures/config-validation/test_config_partial_data.yml")
+                    .expect("Couldn't read config"),
             )
             .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
         .with_resolvers(resolvers)
         .build()
-        .is_ok());
+        .unwrap();
+
+        let expected = etag(&serde_json::json!({"id": "1", "name": "Apollo"}));
+        for query in &[
+            "query { Featured { _etag } }",
+            "query { Featured { id name _etag } }",
+        ] {
+            let response = engine
+                .execute(query.to_string(), None, None, HashMap::new())
+                .await
+                .unwrap();
+            assert_eq!(response["data"]["Featured"][0]["_etag"], expected);
+        }
     }
 
-    #[test]
-    fn test_engine_validate_custom_prop() {
-        //Prop resolver in config
-        //Resolver defined
-        //is_ok
+    static EXPENSIVE_POINTS_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn expensive_points_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            EXPENSIVE_POINTS_CALLS.fetch_add(1, Ordering::SeqCst);
+            facade.resolve_scalar(42)
+        })
+    }
+
+    /// Passes if a custom prop resolver is only called when its field is selected, so that an
+    /// expensive dynamic prop costs nothing for queries that don't ask for it
+    #[tokio::test]
+    async fn engine_prop_resolver_on_demand() {
         let mut resolvers = Resolvers::<()>::new();
-        resolvers.insert("MyResolver".to_string(), Box::new(my_resolver));
-        assert!(Engine::<()>::new(
+        resolvers.insert("Featured".to_string(), Box::new(featured_resolver));
+        resolvers.insert(
+            "FailingPoints".to_string(),
+            Box::new(expensive_points_resolver),
+        );
+        resolvers.insert(
+            "ProjectStatus".to_string(),
+            Box::new(project_status_resolver),
+        );
+        let engine = Engine::<()>::new(
             TryInto::<Configuration>::try_into(
-                File::open(
-                    "tests/fixtures/config-validation/test_config_with_custom_prop_resolver.yml"
-                )
-                .expect("Couldn't read config")
+                File::open("tests/fixtures/config-validation/test_config_partial_data.yml")
+                    .expect("Couldn't read config"),
             )
             .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
         .with_resolvers(resolvers)
         .build()
-        .is_ok());
+        .unwrap();
 
-        //No prop resolver in config
-        //Resolver defined
-        //is_ok
+        let response = engine
+            .execute(
+                "query { Featured { id name status } }".to_string(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response["data"],
+            serde_json::json!({"Featured": [{"id": "1", "name": "Apollo", "status": "active"}]})
+        );
+        assert_eq!(EXPENSIVE_POINTS_CALLS.load(Ordering::SeqCst), 0);
+
+        let response = engine
+            .execute(
+                "query { Featured { name points } }".to_string(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response["data"],
+            serde_json::json!({"Featured": [{"name": "Apollo", "points": 42}]})
+        );
+        assert_eq!(EXPENSIVE_POINTS_CALLS.load(Ordering::SeqCst), 1);
+    }
+
+    fn people_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let mut props = HashMap::new();
+            props.insert("id".to_string(), Value::String("1".to_string()));
+            props.insert("firstName".to_string(), Value::String("Ada".to_string()));
+            props.insert(
+                "lastName".to_string(),
+                Value::String("Lovelace".to_string()),
+            );
+            facade
+                .resolve_node_list(vec![facade.node("Person", props)])
+                .await
+        })
+    }
+
+    fn full_name_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let fields = facade.parent_fields();
+            match (fields.get("firstName"), fields.get("lastName")) {
+                (Some(Value::String(first)), Some(Value::String(last))) => {
+                    facade.resolve_scalar(format!("{} {}", first, last))
+                }
+                _ => facade.resolve_null(),
+            }
+        })
+    }
+
+    /// Passes if a field computed from two sibling fields of its parent node resolves from the
+    /// parent's loaded fields, whether or not the sibling fields are selected. The engine has no
+    /// database, so any query for the parent would fail the request.
+    #[tokio::test]
+    async fn engine_computed_field_from_siblings() {
         let mut resolvers = Resolvers::<()>::new();
-        resolvers.insert("MyResolver".to_string(), Box::new(my_resolver));
-        assert!(Engine::<()>::new(
+        resolvers.insert("People".to_string(), Box::new(people_resolver));
+        resolvers.insert("FullName".to_string(), Box::new(full_name_resolver));
+        let engine = Engine::<()>::new(
             TryInto::<Configuration>::try_into(
-                File::open("tests/fixtures/minimal.yml").expect("Couldn't read config")
+                File::open("tests/fixtures/config-validation/test_config_computed_field.yml")
+                    .expect("Couldn't read config"),
             )
             .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
         .with_resolvers(resolvers)
         .build()
-        .is_ok());
+        .unwrap();
 
-        //Prop resolver in config
-        //No resolver defined
-        //is_err
-        assert!(Engine::<()>::new(
+        let response = engine
+            .execute(
+                "query { People { fullName } }".to_string(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(
+            response["data"],
+            serde_json::json!({"People": [{"fullName": "Ada Lovelace"}]})
+        );
+
+        let response = engine
+            .execute(
+                "query { People { firstName lastName fullName } }".to_string(),
+                None,
+                None,
+                HashMap::new(),
+            )
+            .await
+            .unwrap();
+        assert!(response.get("errors").is_none());
+        assert_eq!(
+            response["data"],
+            serde_json::json!({"People": [{
+                "firstName": "Ada",
+                "lastName": "Lovelace",
+                "fullName": "Ada Lovelace"
+            }]})
+        );
+    }
+
+    fn featured_project_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let mut props = HashMap::new();
+            props.insert("id".to_string(), Value::String("project-1".to_string()));
+            props.insert("name".to_string(), Value::String("Apollo".to_string()));
+            facade
+                .resolve_node_list(vec![facade.node("Project", props)])
+                .await
+        })
+    }
+
+    fn project_lead_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let rel = facade.create_rel_with_dst_id(
+                Value::String("lead-1".to_string()),
+                "lead",
+                HashMap::new(),
+                "User",
+                Value::String("user-1".to_string()),
+            )?;
+            facade.resolve_rel(&rel).await
+        })
+    }
+
+    fn project_sponsor_resolver(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+        Box::pin(async move {
+            let rel = facade.create_rel_with_dst_id(
+                Value::String("sponsor-1".to_string()),
+                "sponsor",
+                HashMap::new(),
+                "Project",
+                Value::String("project-2".to_string()),
+            )?;
+            facade.resolve_rel(&rel).await
+        })
+    }
+
+    /// Passes if a custom rel resolver can create a rel from the type and id of its destination,
+    /// and is refused a destination type that the relationship doesn't allow
+    #[tokio::test]
+    async fn engine_custom_rel_with_dst_id() {
+        let mut resolvers = Resolvers::<()>::new();
+        resolvers.insert("Featured".to_string(), Box::new(featured_project_resolver));
+        resolvers.insert("ProjectLead".to_string(), Box::new(project_lead_resolver));
+        resolvers.insert(
+            "ProjectSponsor".to_string(),
+            Box::new(project_sponsor_resolver),
+        );
+        let engine = Engine::<()>::new(
             TryInto::<Configuration>::try_into(
-                File::open(
-                    "tests/fixtures/config-validation/test_config_with_custom_prop_resolver.yml"
-                )
-                .expect("Couldn't read config")
+                File::open("tests/fixtures/config-validation/test_config_rel_dst_id.yml")
+                    .expect("Couldn't read config"),
             )
             .expect("Couldn't convert to config"),
-            NoDatabasePool {}
+            NoDatabasePool {},
         )
+        .with_resolvers(resolvers)
         .build()
-        .is_err());
+        .unwrap();
+
+        let query =
+            "query { Featured { name lead { id dst { ... on User { id } } } sponsor { id } } }";
+        let response = engine
+            .execute(query.to_string(), None, None, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            response["data"],
+            serde_json::json!({
+                "Featured": [{
+                    "name": "Apollo",
+                    "lead": {"id": "lead-1", "dst": {"id": "user-1"}},
+                    "sponsor": null
+                }]
+            })
+        );
+        let errors = response["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0]["path"],
+            serde_json::json!(["Featured", "sponsor"])
+        );
     }
 
     pub fn my_resolver(executor: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
@@ -5,13 +5,15 @@
 use super::context::GraphQLContext;
 use super::schema::{ArgumentKind, Info, NodeType, Property, PropertyKind, TypeKind};
 use crate::engine::context::RequestContext;
+use crate::engine::database::CrudOperation;
+use crate::engine::events::EventFacade;
 use crate::engine::resolvers::Object;
-use crate::engine::value::Value;
+use crate::engine::value::{Base64, Duration, Long, Value};
 use crate::error::Error;
 use juniper::meta::{EnumValue, MetaType};
 use juniper::{
     Arguments, BoxFuture, DefaultScalarValue, ExecutionResult, Executor, FromInputValue,
-    InputValue, Registry, Selection, ID,
+    InputValue, LookAheadMethods, Registry, Selection, Spanning, ID,
 };
 pub use juniper::{GraphQLType, GraphQLTypeAsync, GraphQLValue, GraphQLValueAsync};
 use log::{error, trace};
@@ -72,16 +74,114 @@ impl Sort {
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Options {
     sort: Vec<Sort>,
+    continue_on_error: bool,
+    distinct: bool,
+    profile: bool,
+    allow_unfiltered_update: bool,
+    allow_unfiltered_read: bool,
+    skip: usize,
+    limit: Option<usize>,
 }
 
 impl Options {
     pub fn new(sort: Vec<Sort>) -> Options {
-        Options { sort }
+        Options {
+            sort,
+            continue_on_error: false,
+            distinct: false,
+            profile: false,
+            allow_unfiltered_update: false,
+            allow_unfiltered_read: false,
+            skip: 0,
+            limit: None,
+        }
+    }
+
+    /// Sets whether a relationship create or node update mutation should apply each of its items
+    /// in its own transaction and report the items that fail, rather than rolling back all of
+    /// them when any one fails. The items of a relationship create are those of its CREATE list,
+    /// and the items of a node update are the nodes it matches. The option is only part of the
+    /// options of those mutations, and is ignored by others.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Options {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Sets whether a node read should return each node only once, even if the node matches the
+    /// query more than once, such as through several of its relationships. Duplicates are removed
+    /// before the results are sorted.
+    pub fn with_distinct(mut self, distinct: bool) -> Options {
+        self.distinct = distinct;
+        self
+    }
+
+    /// Sets whether a mutation should run its database queries with profiling, and return the
+    /// profile of each query in the `_profile` field of the response. Profiling must be enabled
+    /// with [`EngineBuilder::with_profiling`].
+    ///
+    /// [`EngineBuilder::with_profiling`]: ../struct.EngineBuilder.html#method.with_profiling
+    pub fn with_profile(mut self, profile: bool) -> Options {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets whether an update or delete mutation with no `MATCH` criteria may change every node
+    /// or relationship of its type. Such mutations are rejected unless this is set.
+    pub fn with_allow_unfiltered_update(mut self, allow_unfiltered_update: bool) -> Options {
+        self.allow_unfiltered_update = allow_unfiltered_update;
+        self
+    }
+
+    /// Sets whether a query with no input to filter what it reads may read every node or
+    /// relationship of its type, when the engine requires reads to be filtered. Such queries are
+    /// rejected unless this is set. See [`EngineBuilder::with_require_read_filter`].
+    ///
+    /// [`EngineBuilder::with_require_read_filter`]: ../struct.EngineBuilder.html#method.with_require_read_filter
+    pub fn with_allow_unfiltered_read(mut self, allow_unfiltered_read: bool) -> Options {
+        self.allow_unfiltered_read = allow_unfiltered_read;
+        self
+    }
+
+    /// Sets the page of results returned by a relationship read, after sorting. The first
+    /// `skip` relationships are left out, and at most `limit` of those remaining are returned.
+    /// The page is applied by the database query.
+    pub fn with_page(mut self, skip: usize, limit: Option<usize>) -> Options {
+        self.skip = skip;
+        self.limit = limit;
+        self
     }
 
     pub fn sort(&self) -> &[Sort] {
         &self.sort
     }
+
+    pub fn continue_on_error(&self) -> bool {
+        self.continue_on_error
+    }
+
+    pub fn distinct(&self) -> bool {
+        self.distinct
+    }
+
+    pub fn profile(&self) -> bool {
+        self.profile
+    }
+
+    pub fn allow_unfiltered_update(&self) -> bool {
+        self.allow_unfiltered_update
+    }
+
+    pub fn allow_unfiltered_read(&self) -> bool {
+        self.allow_unfiltered_read
+    }
+
+    pub fn skip(&self) -> usize {
+        self.skip
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -89,6 +189,7 @@ struct Enumeration<RequestCtx>
 where
     RequestCtx: RequestContext,
 {
+    value: String,
     _rctx: PhantomData<RequestCtx>,
 }
 
@@ -96,8 +197,11 @@ impl<RequestCtx> Enumeration<RequestCtx>
 where
     RequestCtx: RequestContext,
 {
-    fn new() -> Enumeration<RequestCtx> {
-        Enumeration { _rctx: PhantomData }
+    fn new(value: String) -> Enumeration<RequestCtx> {
+        Enumeration {
+            value,
+            _rctx: PhantomData,
+        }
     }
 }
 
@@ -105,14 +209,10 @@ impl<RequestCtx> FromInputValue for Enumeration<RequestCtx>
 where
     RequestCtx: RequestContext,
 {
-    fn from_input_value(_v: &InputValue) -> Option<Self> {
-        /*
-        serde_json::to_value(v)
-            .ok()
-            .and_then(|val| val.try_into().ok())
-            .map(Enumeration::new)
-            */
-        Some(Enumeration::new())
+    fn from_input_value(v: &InputValue) -> Option<Self> {
+        v.as_enum_value()
+            .or_else(|| v.as_string_value())
+            .map(|s| Enumeration::new(s.to_string()))
     }
 }
 
@@ -142,7 +242,13 @@ where
         let mut props = nt.props().collect::<Vec<&Property>>();
         props.sort_by_key(|p| p.name());
 
-        let variants: Vec<EnumValue> = props.iter().map(|p| EnumValue::new(p.name())).collect();
+        let variants: Vec<EnumValue> = props
+            .iter()
+            .map(|p| match p.deprecated() {
+                Some(reason) => EnumValue::new(p.name()).deprecated(Some(reason)),
+                None => EnumValue::new(p.name()),
+            })
+            .collect();
 
         registry
             .build_enum_type::<Enumeration<RequestCtx>>(info, &variants)
@@ -160,6 +266,15 @@ where
     fn type_name<'i>(&self, info: &'i Self::TypeInfo) -> Option<&'i str> {
         Some(info.name())
     }
+
+    fn resolve(
+        &self,
+        _info: &Self::TypeInfo,
+        _selection_set: Option<&[Selection]>,
+        _executor: &Executor<Self::Context>,
+    ) -> ExecutionResult {
+        Ok(juniper::Value::scalar(self.value.clone()))
+    }
 }
 
 impl<RequestCtx> GraphQLValueAsync for Enumeration<RequestCtx> where RequestCtx: RequestContext {}
@@ -226,12 +341,26 @@ where
         let args = props
             .iter()
             .filter(|p| !p.hidden())
-            .map(
-                |p| match (p.kind(), p.type_name(), p.required(), p.list()) {
+            .map(|p| {
+                let arg = match (p.kind(), p.type_name(), p.required(), p.list()) {
+                    (_, "Base64", false, false) => registry.arg::<Option<Base64>>(p.name(), &()),
+                    (_, "Base64", false, true) => {
+                        registry.arg::<Option<Vec<Base64>>>(p.name(), &())
+                    }
+                    (_, "Base64", true, false) => registry.arg::<Base64>(p.name(), &()),
+                    (_, "Base64", true, true) => registry.arg::<Vec<Base64>>(p.name(), &()),
                     (_, "Boolean", false, false) => registry.arg::<Option<bool>>(p.name(), &()),
                     (_, "Boolean", false, true) => registry.arg::<Option<Vec<bool>>>(p.name(), &()),
                     (_, "Boolean", true, false) => registry.arg::<bool>(p.name(), &()),
                     (_, "Boolean", true, true) => registry.arg::<Vec<bool>>(p.name(), &()),
+                    (_, "Duration", false, false) => {
+                        registry.arg::<Option<Duration>>(p.name(), &())
+                    }
+                    (_, "Duration", false, true) => {
+                        registry.arg::<Option<Vec<Duration>>>(p.name(), &())
+                    }
+                    (_, "Duration", true, false) => registry.arg::<Duration>(p.name(), &()),
+                    (_, "Duration", true, true) => registry.arg::<Vec<Duration>>(p.name(), &()),
                     (_, "Float", false, false) => registry.arg::<Option<f64>>(p.name(), &()),
                     (_, "Float", false, true) => registry.arg::<Option<Vec<f64>>>(p.name(), &()),
                     (_, "Float", true, false) => registry.arg::<f64>(p.name(), &()),
@@ -244,12 +373,38 @@ where
                     (_, "Int", false, true) => registry.arg::<Option<Vec<i32>>>(p.name(), &()),
                     (_, "Int", true, false) => registry.arg::<i32>(p.name(), &()),
                     (_, "Int", true, true) => registry.arg::<Vec<i32>>(p.name(), &()),
+                    (_, "Long", false, false) => registry.arg::<Option<Long>>(p.name(), &()),
+                    (_, "Long", false, true) => registry.arg::<Option<Vec<Long>>>(p.name(), &()),
+                    (_, "Long", true, false) => registry.arg::<Long>(p.name(), &()),
+                    (_, "Long", true, true) => registry.arg::<Vec<Long>>(p.name(), &()),
                     (_, "String", false, false) => registry.arg::<Option<String>>(p.name(), &()),
                     (_, "String", false, true) => {
                         registry.arg::<Option<Vec<String>>>(p.name(), &())
                     }
                     (_, "String", true, false) => registry.arg::<String>(p.name(), &()),
                     (_, "String", true, true) => registry.arg::<Vec<String>>(p.name(), &()),
+                    (_, t, false, false) if info.is_enum(t) => {
+                        registry.arg::<Option<Enumeration<RequestCtx>>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        )
+                    }
+                    (_, t, false, true) if info.is_enum(t) => {
+                        registry.arg::<Option<Vec<Enumeration<RequestCtx>>>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        )
+                    }
+                    (_, t, true, false) if info.is_enum(t) => registry
+                        .arg::<Enumeration<RequestCtx>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        ),
+                    (_, t, true, true) if info.is_enum(t) => registry
+                        .arg::<Vec<Enumeration<RequestCtx>>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        ),
                     (PropertyKind::Enum, _, true, _) => registry.arg::<Enumeration<RequestCtx>>(
                         p.name(),
                         &Info::new(p.type_name().to_string(), info.type_defs()),
@@ -275,13 +430,28 @@ where
                         p.name(),
                         &Info::new(p.type_name().to_string(), info.type_defs()),
                     ),
-                },
-            )
+                };
+
+                let arg = match p.default_value() {
+                    Some(v) => arg.default_value(default_input_value(
+                        v,
+                        p.kind() == &PropertyKind::Enum || info.is_enum(p.type_name()),
+                    )),
+                    None => arg,
+                };
+
+                match p.description() {
+                    Some(d) => arg.description(d),
+                    None => arg,
+                }
+            })
             .collect::<Vec<_>>();
 
-        registry
-            .build_input_object_type::<Input<RequestCtx>>(info, &args)
-            .into_meta()
+        let meta = registry.build_input_object_type::<Input<RequestCtx>>(info, &args);
+        match nt.description() {
+            Some(d) => meta.description(d).into_meta(),
+            None => meta.into_meta(),
+        }
     }
 }
 
@@ -330,9 +500,23 @@ where
 {
     concrete_typename: String,
     fields: HashMap<String, Value>,
+    changes: Option<Vec<Node<RequestCtx>>>,
+    bulk_outcome: Option<BulkOutcome<RequestCtx>>,
     _rctx: PhantomData<RequestCtx>,
 }
 
+/// The outcome of a bulk mutation, the nodes or relationships it applied and a `BatchItemError`
+/// node for each item that failed, resolved by the `succeeded` and `failed` fields of its result
+#[derive(Clone, Debug)]
+pub(crate) struct BulkOutcome<RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    pub(crate) nodes: Vec<Node<RequestCtx>>,
+    pub(crate) rels: Vec<Rel<RequestCtx>>,
+    pub(crate) failed: Vec<Node<RequestCtx>>,
+}
+
 impl<RequestCtx> Node<RequestCtx>
 where
     RequestCtx: RequestContext,
@@ -341,10 +525,26 @@ where
         Node {
             concrete_typename,
             fields,
+            changes: None,
+            bulk_outcome: None,
             _rctx: PhantomData,
         }
     }
 
+    /// Sets the changes made to the node's properties by the update that returned it, as
+    /// `FieldChange` nodes resolved by the `_changes` field
+    pub(crate) fn with_changes(mut self, changes: Vec<Node<RequestCtx>>) -> Self {
+        self.changes = Some(changes);
+        self
+    }
+
+    /// Sets the outcome of the bulk mutation that returned the node, as the result object
+    /// resolved by the mutation's endpoint
+    pub(crate) fn with_bulk_outcome(mut self, bulk_outcome: BulkOutcome<RequestCtx>) -> Self {
+        self.bulk_outcome = Some(bulk_outcome);
+        self
+    }
+
     /// Attempts to deserialize a `Node` into a struct.
     ///
     /// # Example
@@ -440,12 +640,26 @@ where
             .filter(|p| !p.hidden())
             .map(|p| {
                 let f = match (p.type_name(), p.required(), p.list(), p.kind()) {
+                    ("Base64", false, false, _) => registry.field::<Option<Base64>>(p.name(), &()),
+                    ("Base64", false, true, _) => {
+                        registry.field::<Option<Vec<Base64>>>(p.name(), &())
+                    }
+                    ("Base64", true, false, _) => registry.field::<Base64>(p.name(), &()),
+                    ("Base64", true, true, _) => registry.field::<Vec<Base64>>(p.name(), &()),
                     ("Boolean", false, false, _) => registry.field::<Option<bool>>(p.name(), &()),
                     ("Boolean", false, true, _) => {
                         registry.field::<Option<Vec<bool>>>(p.name(), &())
                     }
                     ("Boolean", true, false, _) => registry.field::<bool>(p.name(), &()),
                     ("Boolean", true, true, _) => registry.field::<Vec<bool>>(p.name(), &()),
+                    ("Duration", false, false, _) => {
+                        registry.field::<Option<Duration>>(p.name(), &())
+                    }
+                    ("Duration", false, true, _) => {
+                        registry.field::<Option<Vec<Duration>>>(p.name(), &())
+                    }
+                    ("Duration", true, false, _) => registry.field::<Duration>(p.name(), &()),
+                    ("Duration", true, true, _) => registry.field::<Vec<Duration>>(p.name(), &()),
                     ("Float", false, false, _) => registry.field::<Option<f64>>(p.name(), &()),
                     ("Float", false, true, _) => registry.field::<Option<Vec<f64>>>(p.name(), &()),
                     ("Float", true, false, _) => registry.field::<f64>(p.name(), &()),
@@ -458,12 +672,38 @@ where
                     ("Int", false, true, _) => registry.field::<Option<Vec<i32>>>(p.name(), &()),
                     ("Int", true, false, _) => registry.field::<i32>(p.name(), &()),
                     ("Int", true, true, _) => registry.field::<Vec<i32>>(p.name(), &()),
+                    ("Long", false, false, _) => registry.field::<Option<Long>>(p.name(), &()),
+                    ("Long", false, true, _) => registry.field::<Option<Vec<Long>>>(p.name(), &()),
+                    ("Long", true, false, _) => registry.field::<Long>(p.name(), &()),
+                    ("Long", true, true, _) => registry.field::<Vec<Long>>(p.name(), &()),
                     ("String", false, false, _) => registry.field::<Option<String>>(p.name(), &()),
                     ("String", false, true, _) => {
                         registry.field::<Option<Vec<String>>>(p.name(), &())
                     }
                     ("String", true, false, _) => registry.field::<String>(p.name(), &()),
                     ("String", true, true, _) => registry.field::<Vec<String>>(p.name(), &()),
+                    (t, false, false, _) if info.is_enum(t) => {
+                        registry.field::<Option<Enumeration<RequestCtx>>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        )
+                    }
+                    (t, false, true, _) if info.is_enum(t) => {
+                        registry.field::<Option<Vec<Enumeration<RequestCtx>>>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        )
+                    }
+                    (t, true, false, _) if info.is_enum(t) => registry
+                        .field::<Enumeration<RequestCtx>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        ),
+                    (t, true, true, _) if info.is_enum(t) => registry
+                        .field::<Vec<Enumeration<RequestCtx>>>(
+                            p.name(),
+                            &Info::new(t.to_string(), info.type_defs()),
+                        ),
                     (_, false, false, PropertyKind::Rel { rel_name: _ }) => registry
                         .field::<Option<Rel<RequestCtx>>>(
                             p.name(),
@@ -492,7 +732,8 @@ where
                         p.name(),
                         &Info::new(p.type_name().to_string(), info.type_defs()),
                     ),
-                    (_, true, true, PropertyKind::Rel { rel_name: _ }) => {
+                    (_, true, true, PropertyKind::Rel { rel_name: _ })
+                    | (_, true, true, PropertyKind::RelBulkSucceeded) => {
                         registry.field::<Vec<&Rel<RequestCtx>>>(
                             p.name(),
                             &Info::new(p.type_name().to_string(), info.type_defs()),
@@ -504,6 +745,16 @@ where
                     ),
                 };
 
+                let f = match p.description() {
+                    Some(d) => f.description(d),
+                    None => f,
+                };
+
+                let f = match p.deprecated() {
+                    Some(reason) => f.deprecated(Some(reason)),
+                    None => f,
+                };
+
                 p.arguments().fold(f, |f, arg| {
                     match (arg.name(), arg.type_name(), arg.kind()) {
                         (name, "Boolean", ArgumentKind::Optional) => {
@@ -573,9 +824,11 @@ where
             })
             .collect::<Vec<_>>();
 
-        registry
-            .build_object_type::<Node<RequestCtx>>(info, &fields)
-            .into_meta()
+        let meta = registry.build_object_type::<Node<RequestCtx>>(info, &fields);
+        match nt.description() {
+            Some(d) => meta.description(d).into_meta(),
+            None => meta.into_meta(),
+        }
     }
 
     pub(crate) fn typename(&self) -> &str {
@@ -662,8 +915,42 @@ where
             );
 
             let p = info.type_def()?.property(field_name)?;
+
+            // a field whose guards don't all pass resolves to null, without calling its resolver
+            if let Some(guards) = executor
+                .context()
+                .event_handlers()
+                .field_guards(sn, field_name)
+            {
+                let mut transaction = executor.context().read_transaction().await?;
+                let ef = EventFacade::new(
+                    CrudOperation::ReadNode(sn.to_string()),
+                    executor.context(),
+                    &mut transaction,
+                    info,
+                );
+                if !guards.iter().all(|guard| guard(&ef)) {
+                    trace!(
+                        "Node::resolve_field_async -- field guard rejected {}.{}",
+                        sn,
+                        field_name
+                    );
+                    return Ok(juniper::Value::Null);
+                }
+            }
+
             let input_opt: Option<Value> = args.get("input").map(|i: Input<RequestCtx>| i.value);
 
+            // the sort of a bulk mutation orders the items it applied, rather than its result
+            let sort_type_name = match p.kind() {
+                PropertyKind::NodeUpdateBulkMutation
+                | PropertyKind::RelCreateBulkMutation { .. } => info
+                    .type_def_by_name(p.type_name())?
+                    .property("succeeded")?
+                    .type_name(),
+                _ => p.type_name(),
+            };
+
             let options = if let Some(Value::Map(m)) =
                 args.get("options").map(|i: Input<RequestCtx>| i.value)
             {
@@ -673,11 +960,14 @@ where
                             if let Value::Map(sort_map) = sort {
                                 Ok(Sort::new(
                                     sort_map.get("direction").map(|d| d.to_string()),
-                                    sort_map.get("orderBy").map(|ob| ob.to_string()).ok_or(
-                                        Error::InputItemNotFound {
+                                    sort_map
+                                        .get("orderBy")
+                                        .map(|ob| {
+                                            db_order_by(info, sort_type_name, &ob.to_string())
+                                        })
+                                        .ok_or(Error::InputItemNotFound {
                                             name: "orderBy".to_string(),
-                                        },
-                                    )?,
+                                        })?,
                                 ))
                             } else {
                                 Err(Error::TypeNotExpected {
@@ -689,14 +979,60 @@ where
                 } else {
                     Vec::new()
                 })
+                .with_continue_on_error(matches!(m.get("continueOnError"), Some(Value::Bool(true))))
+                .with_distinct(matches!(m.get("distinct"), Some(Value::Bool(true))))
+                .with_profile(matches!(m.get("profile"), Some(Value::Bool(true))))
+                .with_allow_unfiltered_update(matches!(
+                    m.get("allowUnfilteredUpdate"),
+                    Some(Value::Bool(true))
+                ))
+                .with_allow_unfiltered_read(matches!(
+                    m.get("allowUnfilteredRead"),
+                    Some(Value::Bool(true))
+                ))
             } else {
                 Options::default()
             };
             trace!("Node::resolve_field_async -- options: {:#?}", options);
 
             let mut resolver = Resolver::new();
+            resolver
+                .resolve_directives(info, field_name, Object::Node(self), args, executor)
+                .await?;
 
             let result = match p.kind() {
+                PropertyKind::Aggregate => {
+                    resolver
+                        .resolve_aggregate(field_name, info, &self.fields, executor)
+                        .await
+                }
+                PropertyKind::BulkFailed
+                | PropertyKind::BulkSucceeded
+                | PropertyKind::RelBulkSucceeded => {
+                    resolver
+                        .resolve_bulk_outcome_field(
+                            info,
+                            field_name,
+                            self.bulk_outcome.as_ref(),
+                            executor,
+                        )
+                        .await
+                }
+                PropertyKind::Changes => {
+                    resolver
+                        .resolve_changes_field(info, field_name, self.changes.as_ref(), executor)
+                        .await
+                }
+                PropertyKind::ComputedCount { rel_name } => {
+                    resolver
+                        .resolve_computed_count(rel_name, info, self, executor)
+                        .await
+                }
+                PropertyKind::ComputedRel { rel_name, query } => {
+                    resolver
+                        .resolve_computed_rel(field_name, rel_name, query, info, self, executor)
+                        .await
+                }
                 PropertyKind::CustomResolver => {
                     resolver
                         .resolve_custom_endpoint(
@@ -708,6 +1044,8 @@ where
                         )
                         .await
                 }
+                // only reached for fields in the selection set, so a custom prop resolver runs
+                // only when a query asks for its field
                 PropertyKind::DynamicScalar => {
                     resolver
                         .resolve_custom_field(
@@ -736,10 +1074,15 @@ where
                     details: Some("PropertyKind::Enum not expected.".to_string()),
                 })
                 .into()),
+                PropertyKind::Etag => resolver.resolve_etag_field(&self.fields),
                 PropertyKind::Input => Err((Error::TypeNotExpected {
                     details: Some("PropertyKind::Input not expected".to_string()),
                 })
                 .into()),
+                PropertyKind::InverseRel { .. } => Err((Error::TypeNotExpected {
+                    details: Some("PropertyKind::InverseRel not expected".to_string()),
+                })
+                .into()),
                 PropertyKind::NodeCreateMutation => {
                     let input = input_opt.ok_or_else(|| Error::InputItemNotFound {
                         name: "input".to_string(),
@@ -748,6 +1091,16 @@ where
                         .resolve_node_create_mutation(field_name, info, input, options, executor)
                         .await
                 }
+                PropertyKind::NodeCreateBatchMutation => {
+                    let input = input_opt.ok_or_else(|| Error::InputItemNotFound {
+                        name: "input".to_string(),
+                    })?;
+                    resolver
+                        .resolve_node_create_batch_mutation(
+                            field_name, info, input, options, executor,
+                        )
+                        .await
+                }
                 PropertyKind::NodeDeleteMutation { label } => {
                     let input = input_opt.ok_or_else(|| Error::InputItemNotFound {
                         name: "input".to_string(),
@@ -766,6 +1119,21 @@ where
                         .resolve_node_update_mutation(field_name, info, input, options, executor)
                         .await
                 }
+                PropertyKind::NodeUpdateBulkMutation => {
+                    let input = input_opt.ok_or_else(|| Error::InputItemNotFound {
+                        name: "input".to_string(),
+                    })?;
+                    resolver
+                        .resolve_node_update_bulk_mutation(
+                            field_name, info, input, options, executor,
+                        )
+                        .await
+                }
+                PropertyKind::NodeExistsQuery { label } => {
+                    resolver
+                        .resolve_node_exists_query(field_name, label, info, input_opt, executor)
+                        .await
+                }
                 PropertyKind::Object => {
                     resolver
                         .resolve_node_read_query(field_name, info, input_opt, options, executor)
@@ -787,44 +1155,58 @@ where
                         // additional searching / filtering criteria to a query input in the shape,
                         // because we allow filtering on relationships at every nested relationship
                         // in the shape.
-                        let mut hm = if let Some(Value::Map(input_map)) = input_opt {
-                            input_map
-                        } else {
-                            HashMap::new()
-                        };
-                        let mut src = if let Some(Value::Map(src_map)) = hm.remove("src") {
-                            src_map
-                        } else {
-                            HashMap::new()
-                        };
-                        let mut src_node = if let Some(Value::Map(src_node_map)) =
-                            src.remove(info.type_def()?.type_name())
-                        {
-                            src_node_map
-                        } else {
-                            HashMap::new()
-                        };
-                        let mut comparison = HashMap::new();
-                        comparison.insert("EQ".to_string(), self.id()?.clone());
-                        src_node.insert("id".to_string(), Value::Map(comparison));
-                        src.insert(
-                            info.type_def()?.type_name().to_string(),
-                            Value::Map(src_node),
-                        );
-                        hm.insert("src".to_string(), Value::Map(src));
+                        let input =
+                            src_id_input(input_opt, info.type_def()?.type_name(), self.id()?);
 
                         resolver
                             .resolve_rel_read_query(
                                 field_name,
                                 rel_name,
                                 info,
-                                Some(Value::Map(hm)),
+                                Some(input),
                                 options,
                                 executor,
                             )
                             .await
                     }
                 }
+                PropertyKind::RelAggregate { rel_name } => {
+                    resolver
+                        .resolve_rel_aggregate(field_name, rel_name, info, self, executor)
+                        .await
+                }
+                PropertyKind::RelCount { rel_name } => {
+                    // As for a relationship reference, the src node id is merged into any
+                    // filtering criteria the client has added to the count's query input
+                    let input = src_id_input(input_opt, info.type_def()?.type_name(), self.id()?);
+
+                    resolver
+                        .resolve_rel_count(field_name, rel_name, info, input, executor)
+                        .await
+                }
+                PropertyKind::RelByType { .. } => {
+                    resolver
+                        .resolve_rel_by_type(field_name, info, self, executor)
+                        .await
+                }
+                PropertyKind::RelGroup {
+                    rel_name,
+                    dst_label,
+                } => {
+                    // The group object carries the id of the source node, which is merged into
+                    // the search query input as it is for a relationship reference
+                    let src_label = info
+                        .type_def_by_name(p.type_name())?
+                        .property("src")?
+                        .type_name();
+                    let input = src_id_input(input_opt, src_label, self.id()?);
+
+                    resolver
+                        .resolve_rel_group(
+                            field_name, rel_name, dst_label, info, input, options, args, executor,
+                        )
+                        .await
+                }
                 PropertyKind::RelCreateMutation {
                     src_label,
                     rel_name,
@@ -838,6 +1220,19 @@ where
                         )
                         .await
                 }
+                PropertyKind::RelCreateBulkMutation {
+                    src_label,
+                    rel_name,
+                } => {
+                    let input = input_opt.ok_or_else(|| Error::InputItemNotFound {
+                        name: "input".to_string(),
+                    })?;
+                    resolver
+                        .resolve_rel_create_bulk_mutation(
+                            field_name, src_label, rel_name, info, input, options, executor,
+                        )
+                        .await
+                }
                 PropertyKind::RelDeleteMutation {
                     src_label,
                     rel_name,
@@ -866,7 +1261,7 @@ where
                 }
                 PropertyKind::Scalar => {
                     resolver
-                        .resolve_scalar_field(info, field_name, &self.fields, executor)
+                        .resolve_scalar_field(info, field_name, &self.fields, args, executor)
                         .await
                 }
                 PropertyKind::ScalarComp => Err((Error::TypeNotExpected {
@@ -966,7 +1361,9 @@ impl<RequestCtx> Rel<RequestCtx>
 where
     RequestCtx: RequestContext,
 {
-    pub(crate) fn new(
+    /// Creates a relationship, from the node referenced by `src_ref` to the node referenced by
+    /// `dst_ref`, with properties in `fields`, including its `id`
+    pub fn new(
         rel_name: String,
         fields: HashMap<String, Value>,
         src_ref: NodeRef<RequestCtx>,
@@ -1012,6 +1409,125 @@ where
     }
 }
 
+/// Returns true if a node of type `type_name`, referenced by a relationship, can be resolved from
+/// the id held by the relationship, without reading the node from the database. That is the case
+/// if the selection on the field asks for nothing about the node but its id, and there are no
+/// authorizers or read event handlers that would otherwise be run when reading the node.
+fn resolves_from_id<RequestCtx: RequestContext>(
+    type_name: &str,
+    executor: &Executor<GraphQLContext<RequestCtx>>,
+) -> bool {
+    let event_handlers = executor.context().event_handlers();
+    if !event_handlers.authorizers().is_empty()
+        || event_handlers.before_node_read(type_name).is_some()
+        || event_handlers.after_node_read(type_name).is_some()
+    {
+        return false;
+    }
+
+    let child_names = executor.look_ahead().child_names();
+    !child_names.is_empty()
+        && child_names
+            .iter()
+            .all(|name| *name == "id" || *name == "__typename")
+}
+
+/// Returns the name of the only type in the union of relationship destination types, if the
+/// relationship has a single destination type. Otherwise, the type of the destination node can't
+/// be known without reading it from the database.
+fn single_union_type<'i>(info: &'i Info, p: &Property) -> Option<&'i str> {
+    let mut union_types = info.type_def_by_name(p.type_name()).ok()?.union_types()?;
+    match (union_types.next(), union_types.next()) {
+        (Some(type_name), None) => Some(type_name),
+        _ => None,
+    }
+}
+
+/// Takes the orderBy value of a sort on reads of the type named `type_name` and returns it naming
+/// the property as it is stored in the database. Destination properties of a relationship,
+/// prefixed with `dst:`, are looked up on the first destination type that has the property.
+pub(crate) fn db_order_by(info: &Info, type_name: &str, order_by: &str) -> String {
+    let nt = match info.type_def_by_name(type_name) {
+        Ok(nt) => nt,
+        Err(_) => return order_by.to_string(),
+    };
+    match order_by.strip_prefix("dst:") {
+        Some(dst_prop) => nt
+            .property("dst")
+            .ok()
+            .and_then(|dst| info.type_def_by_name(dst.type_name()).ok()?.union_types())
+            .and_then(|mut union_types| {
+                union_types.find_map(|ut| info.type_def_by_name(ut).ok()?.property(dst_prop).ok())
+            })
+            .map(|p| "dst:".to_string() + p.db_name()),
+        None => nt.property(order_by).ok().map(|p| p.db_name().to_string()),
+    }
+    .unwrap_or_else(|| order_by.to_string())
+}
+
+/// Converts the configured default value of a property into the GraphQL input value declared as
+/// the default of the input field, so that the default is visible in introspection and the SDL.
+/// Strings are declared as enum values if the property is of an enumeration type.
+fn default_input_value(value: &serde_json::Value, enumeration: bool) -> InputValue {
+    match value {
+        serde_json::Value::Null => InputValue::null(),
+        serde_json::Value::Bool(b) => InputValue::scalar(*b),
+        serde_json::Value::Number(n) => match n.as_i64().and_then(|i| i32::try_from(i).ok()) {
+            Some(i) => InputValue::scalar(i),
+            None => InputValue::scalar(n.as_f64().unwrap_or_default()),
+        },
+        serde_json::Value::String(s) if enumeration => InputValue::enum_value(s.clone()),
+        serde_json::Value::String(s) => InputValue::scalar(s.clone()),
+        serde_json::Value::Array(a) => InputValue::list(
+            a.iter()
+                .map(|v| default_input_value(v, enumeration))
+                .collect(),
+        ),
+        serde_json::Value::Object(o) => InputValue::parsed_object(
+            o.iter()
+                .map(|(k, v)| {
+                    (
+                        Spanning::unlocated(k.clone()),
+                        Spanning::unlocated(default_input_value(v, false)),
+                    )
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn id_fields(id: &Value) -> HashMap<String, Value> {
+    let mut fields = HashMap::new();
+    fields.insert("id".to_string(), id.clone());
+    fields
+}
+
+/// Merges a search criterion matching the source node with the given label and id into the query
+/// input of a relationship read
+fn src_id_input(input_opt: Option<Value>, src_label: &str, id: &Value) -> Value {
+    let mut hm = if let Some(Value::Map(input_map)) = input_opt {
+        input_map
+    } else {
+        HashMap::new()
+    };
+    let mut src = if let Some(Value::Map(src_map)) = hm.remove("src") {
+        src_map
+    } else {
+        HashMap::new()
+    };
+    let mut src_node = if let Some(Value::Map(src_node_map)) = src.remove(src_label) {
+        src_node_map
+    } else {
+        HashMap::new()
+    };
+    let mut comparison = HashMap::new();
+    comparison.insert("EQ".to_string(), id.clone());
+    src_node.insert("id".to_string(), Value::Map(comparison));
+    src.insert(src_label.to_string(), Value::Map(src_node));
+    hm.insert("src".to_string(), Value::Map(src));
+    Value::Map(hm)
+}
+
 impl<RequestCtx> GraphQLType for Rel<RequestCtx>
 where
     RequestCtx: RequestContext,
@@ -1037,43 +1553,77 @@ where
         let fields = props
             .iter()
             .filter(|p| !p.hidden())
-            .map(|p| match (p.type_name(), p.required(), p.list()) {
-                ("Boolean", false, false) => registry.field::<Option<bool>>(p.name(), &()),
-                ("Boolean", false, true) => registry.field::<Option<Vec<bool>>>(p.name(), &()),
-                ("Boolean", true, false) => registry.field::<bool>(p.name(), &()),
-                ("Boolean", true, true) => registry.field::<Vec<bool>>(p.name(), &()),
-                ("Float", false, false) => registry.field::<Option<f64>>(p.name(), &()),
-                ("Float", false, true) => registry.field::<Option<Vec<f64>>>(p.name(), &()),
-                ("Float", true, false) => registry.field::<f64>(p.name(), &()),
-                ("Float", true, true) => registry.field::<Vec<f64>>(p.name(), &()),
-                ("ID", false, false) => registry.field::<Option<ID>>(p.name(), &()),
-                ("ID", false, true) => registry.field::<Option<Vec<ID>>>(p.name(), &()),
-                ("ID", true, false) => registry.field::<ID>(p.name(), &()),
-                ("ID", true, true) => registry.field::<Vec<ID>>(p.name(), &()),
-                ("Int", false, false) => registry.field::<Option<i32>>(p.name(), &()),
-                ("Int", false, true) => registry.field::<Option<Vec<i32>>>(p.name(), &()),
-                ("Int", true, false) => registry.field::<i32>(p.name(), &()),
-                ("Int", true, true) => registry.field::<Vec<i32>>(p.name(), &()),
-                ("String", false, false) => registry.field::<Option<String>>(p.name(), &()),
-                ("String", false, true) => registry.field::<Option<Vec<String>>>(p.name(), &()),
-                ("String", true, false) => registry.field::<String>(p.name(), &()),
-                ("String", true, true) => registry.field::<Vec<String>>(p.name(), &()),
-                (_, false, false) => registry.field::<Option<Node<RequestCtx>>>(
-                    p.name(),
-                    &Info::new(p.type_name().to_string(), info.type_defs()),
-                ),
-                (_, false, true) => registry.field::<Option<Vec<&Node<RequestCtx>>>>(
-                    p.name(),
-                    &Info::new(p.type_name().to_string(), info.type_defs()),
-                ),
-                (_, true, false) => registry.field::<Node<RequestCtx>>(
-                    p.name(),
-                    &Info::new(p.type_name().to_string(), info.type_defs()),
-                ),
-                (_, true, true) => registry.field::<Vec<&Node<RequestCtx>>>(
-                    p.name(),
-                    &Info::new(p.type_name().to_string(), info.type_defs()),
-                ),
+            .map(|p| {
+                let f = match (p.type_name(), p.required(), p.list()) {
+                    ("Base64", false, false) => registry.field::<Option<Base64>>(p.name(), &()),
+                    ("Base64", false, true) => registry.field::<Option<Vec<Base64>>>(p.name(), &()),
+                    ("Base64", true, false) => registry.field::<Base64>(p.name(), &()),
+                    ("Base64", true, true) => registry.field::<Vec<Base64>>(p.name(), &()),
+                    ("Boolean", false, false) => registry.field::<Option<bool>>(p.name(), &()),
+                    ("Boolean", false, true) => registry.field::<Option<Vec<bool>>>(p.name(), &()),
+                    ("Boolean", true, false) => registry.field::<bool>(p.name(), &()),
+                    ("Boolean", true, true) => registry.field::<Vec<bool>>(p.name(), &()),
+                    ("Duration", false, false) => registry.field::<Option<Duration>>(p.name(), &()),
+                    ("Duration", false, true) => {
+                        registry.field::<Option<Vec<Duration>>>(p.name(), &())
+                    }
+                    ("Duration", true, false) => registry.field::<Duration>(p.name(), &()),
+                    ("Duration", true, true) => registry.field::<Vec<Duration>>(p.name(), &()),
+                    ("Float", false, false) => registry.field::<Option<f64>>(p.name(), &()),
+                    ("Float", false, true) => registry.field::<Option<Vec<f64>>>(p.name(), &()),
+                    ("Float", true, false) => registry.field::<f64>(p.name(), &()),
+                    ("Float", true, true) => registry.field::<Vec<f64>>(p.name(), &()),
+                    ("ID", false, false) => registry.field::<Option<ID>>(p.name(), &()),
+                    ("ID", false, true) => registry.field::<Option<Vec<ID>>>(p.name(), &()),
+                    ("ID", true, false) => registry.field::<ID>(p.name(), &()),
+                    ("ID", true, true) => registry.field::<Vec<ID>>(p.name(), &()),
+                    ("Int", false, false) => registry.field::<Option<i32>>(p.name(), &()),
+                    ("Int", false, true) => registry.field::<Option<Vec<i32>>>(p.name(), &()),
+                    ("Int", true, false) => registry.field::<i32>(p.name(), &()),
+                    ("Int", true, true) => registry.field::<Vec<i32>>(p.name(), &()),
+                    ("Long", false, false) => registry.field::<Option<Long>>(p.name(), &()),
+                    ("Long", false, true) => registry.field::<Option<Vec<Long>>>(p.name(), &()),
+                    ("Long", true, false) => registry.field::<Long>(p.name(), &()),
+                    ("Long", true, true) => registry.field::<Vec<Long>>(p.name(), &()),
+                    ("String", false, false) => registry.field::<Option<String>>(p.name(), &()),
+                    ("String", false, true) => registry.field::<Option<Vec<String>>>(p.name(), &()),
+                    ("String", true, false) => registry.field::<String>(p.name(), &()),
+                    ("String", true, true) => registry.field::<Vec<String>>(p.name(), &()),
+                    (_, false, false) => registry.field::<Option<Node<RequestCtx>>>(
+                        p.name(),
+                        &Info::new(p.type_name().to_string(), info.type_defs()),
+                    ),
+                    (_, false, true) => registry.field::<Option<Vec<&Node<RequestCtx>>>>(
+                        p.name(),
+                        &Info::new(p.type_name().to_string(), info.type_defs()),
+                    ),
+                    (_, true, false) => registry.field::<Node<RequestCtx>>(
+                        p.name(),
+                        &Info::new(p.type_name().to_string(), info.type_defs()),
+                    ),
+                    (_, true, true) => registry.field::<Vec<&Node<RequestCtx>>>(
+                        p.name(),
+                        &Info::new(p.type_name().to_string(), info.type_defs()),
+                    ),
+                };
+
+                p.arguments()
+                    .fold(f, |f, arg| match (arg.type_name(), arg.kind()) {
+                        ("Int", ArgumentKind::Optional) => {
+                            f.argument(registry.arg::<Option<i32>>(arg.name(), &()))
+                        }
+                        ("String", ArgumentKind::Optional) => {
+                            f.argument(registry.arg::<Option<String>>(arg.name(), &()))
+                        }
+                        (_, _) => {
+                            panic!(
+                                "{}",
+                                Error::TypeNotExpected {
+                                    details: Some("argument is not valid".to_string())
+                                }
+                            )
+                        }
+                    })
             })
             .collect::<Vec<_>>();
 
@@ -1163,6 +1713,9 @@ where
             trace!("Node::resolve_field_async -- options: {:#?}", options);
 
             let mut resolver = Resolver::new();
+            resolver
+                .resolve_directives(info, field_name, Object::Rel(self), args, executor)
+                .await?;
 
             match (p.kind(), &field_name) {
                 (PropertyKind::DynamicScalar, _) => {
@@ -1178,6 +1731,12 @@ where
                         .await
                 }
                 (PropertyKind::Object, &"src") => match &self.src_ref {
+                    NodeRef::Identifier(id) if resolves_from_id(p.type_name(), executor) => {
+                        let n = Node::new(p.type_name().to_string(), id_fields(id));
+                        executor
+                            .resolve_async(&Info::new(n.type_name().clone(), info.type_defs()), &n)
+                            .await
+                    }
                     NodeRef::Identifier(id) => {
                         let mut comparison = HashMap::new();
                         comparison.insert("EQ".to_string(), id.clone());
@@ -1205,11 +1764,19 @@ where
                 .into()),
                 (PropertyKind::Scalar, _) => {
                     resolver
-                        .resolve_scalar_field(info, field_name, &self.fields, executor)
+                        .resolve_scalar_field(info, field_name, &self.fields, args, executor)
                         .await
                 }
-                (PropertyKind::Union, _) => match &self.dst_ref {
-                    NodeRef::Identifier(id) => {
+                (PropertyKind::Union, _) => match (&self.dst_ref, single_union_type(info, p)) {
+                    (NodeRef::Identifier(id), Some(type_name))
+                        if resolves_from_id(type_name, executor) =>
+                    {
+                        let n = Node::new(type_name.to_string(), id_fields(id));
+                        executor
+                            .resolve_async(&Info::new(n.type_name().clone(), info.type_defs()), &n)
+                            .await
+                    }
+                    (NodeRef::Identifier(id), _) => {
                         let mut comparison = HashMap::new();
                         comparison.insert("EQ".to_string(), id.clone());
                         let mut hm = HashMap::new();
@@ -1224,7 +1791,7 @@ where
                             )
                             .await
                     }
-                    NodeRef::Node(n) => {
+                    (NodeRef::Node(n), _) => {
                         executor
                             .resolve_async(&Info::new(n.type_name().clone(), info.type_defs()), &n)
                             .await
@@ -1,22 +1,27 @@
 use crate::engine::context::{GraphQLContext, RequestContext};
-use crate::engine::database::DatabasePool;
 use crate::engine::database::{
     CrudOperation, NodeQueryVar, RelQueryVar, SuffixGenerator, Transaction,
 };
+use crate::engine::database::{DatabaseEndpoint, DatabasePool};
+use crate::engine::etag::etag;
 use crate::engine::events::EventFacade;
-use crate::engine::loader::{NodeLoaderKey, RelLoaderKey};
-use crate::engine::objects::Options;
+use crate::engine::idempotency::Created;
+use crate::engine::loader::{load_nodes, AggregateLoaderKey, NodeLoaderKey, RelLoaderKey};
+use crate::engine::objects::{
+    db_order_by, id_fields, BulkOutcome, Enumeration, Node, NodeRef, Options, Rel, Sort,
+};
 use crate::engine::resolvers::Object;
-use crate::engine::resolvers::ResolverFacade;
-use crate::engine::resolvers::{Arguments, ExecutionResult, Executor};
-use crate::engine::schema::Info;
-use crate::engine::value::Value;
+use crate::engine::resolvers::{Arguments, ExecutionResult, Executor, FieldError};
+use crate::engine::resolvers::{Next, ResolverFacade};
+use crate::engine::schema::{Info, NodeType, Property, PropertyKind, DEFAULT_BATCH_SIZE};
+use crate::engine::value::{Base64, Duration, Long, Value};
 use crate::error::Error;
 use inflector::Inflector;
-use log::trace;
+use juniper::LookAheadMethods;
+use log::{error, trace};
+use maplit::hashmap;
 use std::collections::HashMap;
 use std::convert::TryInto;
-use ultra_batch::LoadError;
 use visitors::{
     visit_node_create_mutation_input, visit_node_delete_input, visit_node_query_input,
     visit_node_update_input, visit_rel_create_input, visit_rel_delete_input, visit_rel_query_input,
@@ -27,12 +32,634 @@ pub(crate) mod visitors;
 
 pub(super) struct Resolver {}
 
+/// Calls each authorizer registered with the event handlers for the operation `op`, returning the
+/// first error, if any.
+fn authorize<RequestCtx: RequestContext>(
+    op: CrudOperation,
+    info: &Info,
+    transaction: &mut <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+    context: &GraphQLContext<RequestCtx>,
+) -> Result<(), Error> {
+    let authorizers = context.event_handlers().authorizers();
+    if authorizers.is_empty() {
+        return Ok(());
+    }
+
+    let ef = EventFacade::new(op.clone(), context, transaction, info);
+    authorizers.iter().try_for_each(|f| f(&op, &ef))
+}
+
+/// Splits a relationship create input with a list of CREATE items into one input per item, each
+/// with the same MATCH and ifDstExists. Any other input is returned as a single item.
+fn split_rel_create_input(input: Value) -> Vec<Value> {
+    match input {
+        Value::Map(mut m) if matches!(m.get("CREATE"), Some(Value::Array(_))) => {
+            let match_input = m.remove("MATCH");
+            let if_dst_exists = m.remove("ifDstExists");
+            if let Some(Value::Array(creates)) = m.remove("CREATE") {
+                creates
+                    .into_iter()
+                    .map(|create| {
+                        let mut item = HashMap::new();
+                        if let Some(match_input) = &match_input {
+                            item.insert("MATCH".to_string(), match_input.clone());
+                        }
+                        if let Some(if_dst_exists) = &if_dst_exists {
+                            item.insert("ifDstExists".to_string(), if_dst_exists.clone());
+                        }
+                        item.insert("CREATE".to_string(), create);
+                        Value::Map(item)
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            }
+        }
+        input => vec![input],
+    }
+}
+
+/// Returns the elements of a scalar list selected by the `contains`, `skip`, and `first`
+/// arguments of its field. Elements are filtered by `contains` before the list is sliced, and a
+/// negative `skip` or `first` is treated as zero.
+fn slice_list(values: &[Value], args: &Arguments<'_>) -> Vec<Value> {
+    let contains = args.get::<String>("contains");
+    let skip = args.get::<i32>("skip").unwrap_or(0).max(0) as usize;
+    let first = args
+        .get::<i32>("first")
+        .map_or(usize::MAX, |first| first.max(0) as usize);
+
+    values
+        .iter()
+        .filter(|v| match (&contains, v) {
+            (None, _) => true,
+            (Some(c), Value::String(s)) => s.contains(c.as_str()),
+            (Some(c), Value::Uuid(u)) => u.to_string().contains(c.as_str()),
+            (Some(_), _) => false,
+        })
+        .skip(skip)
+        .take(first)
+        .cloned()
+        .collect()
+}
+
+/// Runs the custom resolver named `resolver_name`, within the resolver wrappers, and finishes its
+/// managed transaction, if it used one. If the resolver has a timeout and runs longer, it is
+/// abandoned, its managed transaction is rolled back, and the timeout's fallback value is returned,
+/// or an error if the timeout has no fallback.
+async fn run_custom_resolver<'a, RequestCtx: RequestContext>(
+    resolver_name: &str,
+    facade: ResolverFacade<'a, RequestCtx>,
+    context: &'a GraphQLContext<RequestCtx>,
+) -> ExecutionResult {
+    let func = context.resolver(resolver_name)?;
+    let managed_transaction = facade.managed_transaction_slot();
+    let run = Next::new(context.resolver_wrappers(), func).run(facade);
+
+    let result = match context.resolver_timeout(resolver_name) {
+        None => run.await,
+        Some(timeout) => match tokio::time::timeout(timeout.duration(), run).await {
+            Ok(result) => result,
+            Err(_) => {
+                let timed_out = Err(Error::ResolverTimedOut {
+                    name: resolver_name.to_string(),
+                    timeout: timeout.duration(),
+                }
+                .into());
+                let timed_out = managed_transaction.finish(timed_out).await;
+                return match timeout.fallback() {
+                    Some(fallback) => Ok(fallback.clone()),
+                    None => timed_out,
+                };
+            }
+        },
+    };
+
+    managed_transaction.finish(result).await
+}
+
+/// Returns an error if the input of an update or delete mutation has no `MATCH` criteria, and so
+/// would change every node or relationship of its type, unless the options allow it
+fn reject_unfiltered_mutation(
+    field_name: &str,
+    input: &Value,
+    options: &Options,
+) -> Result<(), Error> {
+    if options.allow_unfiltered_update() {
+        return Ok(());
+    }
+
+    match input {
+        Value::Map(m) => match m.get("MATCH") {
+            Some(Value::Map(match_map)) if !match_map.is_empty() => Ok(()),
+            _ => Err(Error::UnfilteredMutationRejected {
+                name: field_name.to_string(),
+            }),
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Returns an error if reads must be filtered and the input of a query has nothing to filter the
+/// nodes or relationships it reads by, unless the options allow it
+fn reject_unfiltered_read(
+    field_name: &str,
+    input_opt: Option<&Value>,
+    options: &Options,
+    require_read_filter: bool,
+) -> Result<(), Error> {
+    if !require_read_filter || options.allow_unfiltered_read() {
+        return Ok(());
+    }
+
+    match input_opt {
+        Some(Value::Map(m)) if m.values().any(|v| !matches!(v, Value::Null)) => Ok(()),
+        _ => Err(Error::UnfilteredReadRejected {
+            name: field_name.to_string(),
+        }),
+    }
+}
+
+/// Reads the nodes matched by the input of an update mutation, before they are updated, so that
+/// the changes made by the update can be found
+async fn read_before_update<RequestCtx: RequestContext>(
+    label: &str,
+    input: &Value,
+    options: Options,
+    info: &Info,
+    sg: &mut SuffixGenerator,
+    transaction: &mut <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+) -> Result<Vec<Node<RequestCtx>>, Error> {
+    let match_input = match input {
+        Value::Map(m) => m.get("MATCH").cloned(),
+        _ => None,
+    };
+    let node_var = NodeQueryVar::new(Some(label.to_string()), "node".to_string(), sg.suffix());
+    let query_fragment = visit_node_query_input::<RequestCtx>(
+        &node_var,
+        match_input,
+        options.clone(),
+        &Info::new(
+            info.type_def()?.property("MATCH")?.type_name().to_owned(),
+            info.type_defs(),
+        ),
+        sg,
+        transaction,
+    )
+    .await?;
+
+    transaction
+        .read_nodes(&node_var, query_fragment, options, info)
+        .await
+}
+
+/// Reads the nodes matched by the input of a node update mutation, returning, with the id of
+/// each, an input that matches the node by its id and sets the properties in the SET of the input
+async fn split_node_update_input<RequestCtx: RequestContext>(
+    label: &str,
+    input: Value,
+    options: &Options,
+    info: &Info,
+    input_info: &Info,
+    sg: &mut SuffixGenerator,
+    context: &GraphQLContext<RequestCtx>,
+) -> Result<Vec<(Option<Value>, Value)>, Error> {
+    let mut transaction = context
+        .mutation_pool(options.profile())?
+        .transaction()
+        .await?;
+    transaction.begin().await?;
+    let matched = match authorize(
+        CrudOperation::UpdateNode(label.to_string()),
+        info,
+        &mut transaction,
+        context,
+    ) {
+        Ok(()) => {
+            read_before_update::<RequestCtx>(
+                label,
+                &input,
+                options.clone(),
+                input_info,
+                sg,
+                &mut transaction,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    let matched = match matched {
+        Ok(matched) => transaction.commit().await.map(|_| matched)?,
+        Err(e) => {
+            transaction.rollback().await?;
+            return Err(e);
+        }
+    };
+
+    let set = match input {
+        Value::Map(mut m) => m.remove("SET"),
+        _ => None,
+    }
+    .ok_or_else(|| Error::InputItemNotFound {
+        name: "input::SET".to_string(),
+    })?;
+    matched
+        .iter()
+        .map(|n| {
+            let id = n.id()?.clone();
+            Ok((
+                Some(id.clone()),
+                Value::Map(hashmap! {
+                    "MATCH".to_string() => Value::Map(hashmap! {
+                        "id".to_string() => Value::Map(hashmap! {
+                            "EQ".to_string() => id,
+                        }),
+                    }),
+                    "SET".to_string() => set.clone(),
+                }),
+            ))
+        })
+        .collect()
+}
+
+/// Applies one item of a node update mutation, reading the nodes it matches before the update
+/// only if `with_changes` is set, to find the `_changes` made to them
+#[allow(clippy::too_many_arguments)]
+async fn update_node_item<RequestCtx: RequestContext>(
+    label: &str,
+    input: Value,
+    options: Options,
+    with_changes: bool,
+    info: &Info,
+    input_info: &Info,
+    sg: &mut SuffixGenerator,
+    transaction: &mut <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+    context: &GraphQLContext<RequestCtx>,
+) -> Result<Vec<Node<RequestCtx>>, Error> {
+    let before_opt = if with_changes {
+        Some(
+            read_before_update::<RequestCtx>(
+                label,
+                &input,
+                options.clone(),
+                input_info,
+                sg,
+                transaction,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    let nodes = visit_node_update_input::<RequestCtx>(
+        &NodeQueryVar::new(Some(label.to_string()), "node".to_string(), sg.suffix()),
+        input,
+        options,
+        input_info,
+        sg,
+        transaction,
+        context,
+    )
+    .await?;
+    match &before_opt {
+        Some(before) => with_field_changes(nodes, before, info.type_def_by_name(label)?),
+        None => Ok(nodes),
+    }
+}
+
+/// Sets the changes made by an update on each updated node that was read before the update
+fn with_field_changes<RequestCtx: RequestContext>(
+    nodes: Vec<Node<RequestCtx>>,
+    before: &[Node<RequestCtx>],
+    td: &NodeType,
+) -> Result<Vec<Node<RequestCtx>>, Error> {
+    nodes
+        .into_iter()
+        .map(|n| {
+            let id = n.id()?;
+            match before.iter().find(|b| b.id().ok() == Some(id)) {
+                Some(b) => {
+                    let changes = field_changes(b.fields(), n.fields(), td)?;
+                    Ok(n.with_changes(changes))
+                }
+                None => Ok(n),
+            }
+        })
+        .collect()
+}
+
+/// Returns a `FieldChange` node for each stored, visible property of a node type whose value
+/// differs between `before` and `after`, in order of property name. A missing value and a null
+/// are the same, and each value is encoded as JSON.
+fn field_changes<RequestCtx: RequestContext>(
+    before: &HashMap<String, Value>,
+    after: &HashMap<String, Value>,
+    td: &NodeType,
+) -> Result<Vec<Node<RequestCtx>>, Error> {
+    let mut props = td
+        .props()
+        .filter(|p| p.kind() == &PropertyKind::Scalar && !p.hidden() && p.name() != "id")
+        .collect::<Vec<&Property>>();
+    props.sort_by_key(|p| p.name());
+
+    props
+        .into_iter()
+        .map(|p| {
+            (
+                p.name(),
+                before.get(p.name()).filter(|v| **v != Value::Null),
+                after.get(p.name()).filter(|v| **v != Value::Null),
+            )
+        })
+        .filter(|(_, b, a)| b != a)
+        .map(|(name, b, a)| {
+            Ok(Node::new(
+                "FieldChange".to_string(),
+                hashmap! {
+                    "field".to_string() => Value::String(name.to_string()),
+                    "before".to_string() => change_value(b)?,
+                    "after".to_string() => change_value(a)?,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Returns a property value, as it was before or after an update, encoded as JSON
+fn change_value(value_opt: Option<&Value>) -> Result<Value, Error> {
+    match value_opt {
+        Some(v) => Ok(Value::String(
+            TryInto::<serde_json::Value>::try_into(v.clone())?.to_string(),
+        )),
+        None => Ok(Value::Null),
+    }
+}
+
+/// Returns true if `v`, a value read from the database, is the code `stored` for a value of an
+/// enum
+fn stored_matches(stored: &serde_json::Value, v: &Value) -> bool {
+    match (stored, v) {
+        (serde_json::Value::String(s), Value::String(vs)) => s == vs,
+        (serde_json::Value::Number(n), Value::Int64(i)) => n.as_i64() == Some(*i),
+        (serde_json::Value::Number(n), Value::UInt64(u)) => n.as_u64() == Some(*u),
+        _ => false,
+    }
+}
+
+/// Returns the value of the only entry of `value`, if it is a map with a single entry named `key`
+fn sole_entry<'v>(value: &'v Value, key: &str) -> Option<&'v Value> {
+    match value {
+        Value::Map(m) if m.len() == 1 => m.get(key),
+        _ => None,
+    }
+}
+
+/// Returns the keys with which the node loader reads the nodes selected by a node query input, if
+/// the input selects nodes by nothing but their ids, either a single id with `EQ` or several with
+/// `IN`. Repeated ids are loaded once. Otherwise, the input has other search criteria that must be
+/// applied by a query of their own, and `None` is returned.
+fn node_loader_keys(input_opt: Option<&Value>) -> Option<Vec<NodeLoaderKey>> {
+    // Okay, this is painful, but we're testing whether after the possible additions of search
+    // criteria in the shape and the possible changes made to the input query by the
+    // before_node_read handler, we still have a query that has nothing in it but the node id
+    // search criterion. If so, this is a a basic node read (the most common case in a shape) and
+    // we should use the loader to avoid the N+1 problem.
+    let comparison = sole_entry(input_opt?, "id")?;
+    let ids = if let Some(id) = sole_entry(comparison, "EQ") {
+        vec![id]
+    } else if let Some(Value::Array(ids)) = sole_entry(comparison, "IN") {
+        ids.iter().collect()
+    } else {
+        return None;
+    };
+
+    let mut keys: Vec<NodeLoaderKey> = Vec::with_capacity(ids.len());
+    for id in ids {
+        let key = NodeLoaderKey::new(id.to_string(), Options::default());
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    Some(keys)
+}
+
+/// Returns `nodes` in the order of the ids of `keys`, leaving out nodes whose ids aren't among
+/// the keys
+fn order_by_keys<RequestCtx: RequestContext>(
+    nodes: Vec<Node<RequestCtx>>,
+    keys: &[NodeLoaderKey],
+) -> Vec<Node<RequestCtx>> {
+    let mut by_id = nodes
+        .into_iter()
+        .filter_map(|n| Some((n.id().ok()?.to_string(), n)))
+        .collect::<HashMap<String, Node<RequestCtx>>>();
+    keys.iter()
+        .filter_map(|key| by_id.remove(key.id()))
+        .collect()
+}
+
+/// Returns the keys with which the rel loader reads the relationships selected by a rel query
+/// input, if the input selects relationships by nothing but the ids of their source nodes.
+/// Otherwise, the input has other search criteria, such as on the relationships' props or
+/// destination nodes, that must be applied by a query of their own, and `None` is returned.
+fn rel_loader_keys(
+    input_opt: Option<&Value>,
+    src_label: &str,
+    rel_name: &str,
+    options: &Options,
+    symmetric: bool,
+) -> Option<Vec<RelLoaderKey>> {
+    // Okay, this is painful, but we're testing whether after the possible additions of search
+    // criteria in the shape and the possible changes made to the input query by the
+    // before_rel_read handler, we still have a query that has nothing in it but the src node id
+    // search criterion. If so, this is a a basic rel read (the most common case in a shape) and
+    // we should use the loader to avoid the N+1 problem.
+    let comparison = sole_entry(input_opt?, "src")
+        .and_then(|src| sole_entry(src, src_label))
+        .and_then(|src_node| sole_entry(src_node, "id"))?;
+    let ids = if let Some(id) = sole_entry(comparison, "EQ") {
+        vec![id]
+    } else if let Some(Value::Array(ids)) = sole_entry(comparison, "IN") {
+        ids.iter().collect()
+    } else {
+        return None;
+    };
+
+    Some(
+        ids.into_iter()
+            .map(|id| {
+                RelLoaderKey::new(id.to_string(), rel_name.to_string(), options.clone())
+                    .with_symmetric(symmetric)
+            })
+            .collect(),
+    )
+}
+
+/// Converts an integer read for the field `field_name`, of the GraphQL `Int` type, to an `i32`,
+/// returning an error rather than truncating a value outside the range of the type
+fn int_field_value(field_name: &str, v: &Value) -> Result<i32, Error> {
+    TryInto::<i32>::try_into(v.clone()).map_err(|_| Error::IntOutOfRange {
+        name: field_name.to_string(),
+        value: v.to_string(),
+    })
+}
+
+/// Converts a [`BatchItemsFailed`] error into a GraphQL error that reports the indices of the
+/// items that succeeded and the index and message of each item that failed in its extensions.
+///
+/// [`BatchItemsFailed`]: ../../../error/enum.Error.html#variant.BatchItemsFailed
+fn batch_items_failed_error(e: Error) -> FieldError {
+    let message = e.to_string();
+    let mut extensions = juniper::Object::with_capacity(2);
+    if let Error::BatchItemsFailed { succeeded, failed } = e {
+        extensions.add_field(
+            "succeeded",
+            juniper::Value::list(
+                succeeded
+                    .into_iter()
+                    .map(|index| juniper::Value::scalar(index as i32))
+                    .collect(),
+            ),
+        );
+        extensions.add_field(
+            "failed",
+            juniper::Value::list(
+                failed
+                    .into_iter()
+                    .map(|(index, e)| {
+                        let mut item = juniper::Object::with_capacity(2);
+                        item.add_field("index", juniper::Value::scalar(index as i32));
+                        item.add_field("error", juniper::Value::scalar(e.to_string()));
+                        juniper::Value::object(item)
+                    })
+                    .collect(),
+            ),
+        );
+    }
+    FieldError::new(message, juniper::Value::object(extensions))
+}
+
+/// The items of a mutation run with continueOnError, which applies each item in a transaction
+/// of its own: the index of each item that was applied, and the index, the id of the node it
+/// applied to, if known, and the error of each item that failed
+#[derive(Default)]
+struct ItemOutcomes {
+    succeeded: Vec<usize>,
+    failed: Vec<(usize, Option<Value>, Error)>,
+}
+
+impl ItemOutcomes {
+    /// Returns a [`BatchItemsFailed`] error for the items, if any of them failed
+    ///
+    /// [`BatchItemsFailed`]: ../../../error/enum.Error.html#variant.BatchItemsFailed
+    fn into_error(self) -> Option<Error> {
+        if self.failed.is_empty() {
+            None
+        } else {
+            Some(Error::BatchItemsFailed {
+                succeeded: self.succeeded,
+                failed: self
+                    .failed
+                    .into_iter()
+                    .map(|(index, _, e)| (index, e))
+                    .collect(),
+            })
+        }
+    }
+
+    /// Returns a `BatchItemError` node for each item that failed, resolved by the `failed` field
+    /// of the result of a bulk mutation
+    fn into_batch_item_errors<RequestCtx: RequestContext>(self) -> Vec<Node<RequestCtx>> {
+        self.failed
+            .into_iter()
+            .map(|(index, id, e)| {
+                let mut fields = hashmap! {
+                    "index".to_string() => Value::Int64(index as i64),
+                    "error".to_string() => Value::String(e.to_string()),
+                };
+                if let Some(id) = id {
+                    fields.insert("id".to_string(), id);
+                }
+                Node::new("BatchItemError".to_string(), fields)
+            })
+            .collect()
+    }
+}
+
+/// Converts a [`BatchCommitFailed`] error into a GraphQL error that reports the number of items
+/// committed before the failure in its extensions.
+///
+/// [`BatchCommitFailed`]: ../../../error/enum.Error.html#variant.BatchCommitFailed
+fn batch_commit_failed_error(e: Error) -> FieldError {
+    let mut extensions = juniper::Object::with_capacity(1);
+    if let Error::BatchCommitFailed { committed, .. } = &e {
+        extensions.add_field("committed", juniper::Value::scalar(*committed as i32));
+    }
+    FieldError::new(e.to_string(), juniper::Value::object(extensions))
+}
+
+/// Converts an [`Error`] into a GraphQL error. For a [`ValidationFailed`] error, the field name
+/// and message of each validation failure are listed under `validationErrors` in the extensions.
+///
+/// [`Error`]: ../../../error/enum.Error.html
+/// [`ValidationFailed`]: ../../../error/enum.Error.html#variant.ValidationFailed
+fn field_error(e: Error) -> FieldError {
+    if let Error::ValidationFailed { errors } = &e {
+        let mut extensions = juniper::Object::with_capacity(1);
+        extensions.add_field(
+            "validationErrors",
+            juniper::Value::list(
+                errors
+                    .iter()
+                    .map(|ve| {
+                        let mut item = juniper::Object::with_capacity(2);
+                        item.add_field("field", juniper::Value::scalar(ve.field().to_string()));
+                        item.add_field("message", juniper::Value::scalar(ve.message().to_string()));
+                        juniper::Value::object(item)
+                    })
+                    .collect(),
+            ),
+        );
+        FieldError::new(e.to_string(), juniper::Value::object(extensions))
+    } else {
+        e.into()
+    }
+}
+
 impl Resolver {
     pub(super) fn new() -> Resolver {
         trace!("Resolver::new called");
         Resolver {}
     }
 
+    /// Runs the handler registered for each directive applied to the object type and to the
+    /// field being resolved, the object type's directives first. Stops at the first handler that
+    /// fails, returning its error, so that the field is not resolved.
+    pub(super) async fn resolve_directives<RequestCtx: RequestContext>(
+        &mut self,
+        info: &Info,
+        field_name: &str,
+        parent: Object<'_, RequestCtx>,
+        args: &Arguments<'_>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> Result<(), FieldError> {
+        let td = info.type_def()?;
+        let p = td.property(field_name)?;
+        for directive in td.directives().iter().chain(p.directives()) {
+            trace!(
+                "Resolver::resolve_directives -- field_name: {}, directive: {}",
+                field_name,
+                directive.name()
+            );
+            let func = executor.context().directive(directive.name())?;
+            let facade = ResolverFacade::new(field_name.to_string(), info, args, parent, executor);
+            func(directive, facade).await?;
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(
         level = "info",
         name = "execute_endpoint",
@@ -52,18 +679,16 @@ impl Resolver {
             field_name,
         );
 
-        // load resolver function
-        let func = executor.context().resolver(field_name)?;
+        // results, committing or rolling back the managed transaction, if the resolver used it
+        let facade = ResolverFacade::new(field_name.to_string(), info, args, parent, executor);
+        let result = run_custom_resolver(field_name, facade, executor.context()).await;
 
-        // results
-        func(ResolverFacade::new(
-            field_name.to_string(),
-            info,
-            args,
-            parent,
-            executor,
-        ))
-        .await
+        // a custom mutation may have written to the database, so cached nodes may be stale
+        if info.name() == "Mutation" {
+            executor.context().node_cache().invalidate();
+        }
+
+        result
     }
 
     #[tracing::instrument(
@@ -90,16 +715,8 @@ impl Resolver {
             name: field_name.to_string(),
         })?;
 
-        let func = &executor.context().resolver(resolver_name)?;
-
-        func(ResolverFacade::new(
-            field_name.to_string(),
-            info,
-            args,
-            parent,
-            executor,
-        ))
-        .await
+        let facade = ResolverFacade::new(field_name.to_string(), info, args, parent, executor);
+        run_custom_resolver(resolver_name, facade, executor.context()).await
     }
 
     #[tracing::instrument(
@@ -126,16 +743,445 @@ impl Resolver {
             name: rel_name.to_string(),
         })?;
 
-        let func = &executor.context().resolver(resolver_name)?;
+        let facade = ResolverFacade::new(rel_name.to_string(), info, args, parent, executor);
+        run_custom_resolver(resolver_name, facade, executor.context()).await
+    }
 
-        func(ResolverFacade::new(
+    #[tracing::instrument(
+        level = "info",
+        name = "resolve_computed_count",
+        skip(self, info, parent, executor)
+    )]
+    pub(super) async fn resolve_computed_count<RequestCtx: RequestContext>(
+        &mut self,
+        rel_name: &str,
+        info: &Info,
+        parent: &Node<RequestCtx>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_computed_count called -- info.name: {}, rel_name: {}",
+            info.name(),
+            rel_name
+        );
+
+        let rel_p = info.type_def()?.property(rel_name)?;
+        let key = RelLoaderKey::new(
+            parent.id()?.to_string(),
             rel_name.to_string(),
+            Options::default(),
+        )
+        .with_symmetric(info.type_def_by_name(rel_p.type_name())?.symmetric());
+
+        // Counts are loaded in batches across sibling nodes, unless a write earlier in the request
+        // may have left the loader's cached counts out of date
+        let count = if executor.context().node_cache().is_stale() {
+            let mut transaction = executor.context().read_transaction().await?;
+            transaction.begin().await?;
+            let counts = match transaction.count_rels(&[key]).await {
+                Ok(counts) => counts,
+                Err(e) => {
+                    transaction.rollback().await?;
+                    return Err(e.into());
+                }
+            };
+            transaction.commit().await?;
+            counts.into_iter().next().unwrap_or_default()
+        } else {
+            executor.context().count_batcher().load(key).await?
+        };
+
+        executor.resolve_with_ctx(&(), &TryInto::<i32>::try_into(Value::Int64(count))?)
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "resolve_rel_count",
+        skip(self, info, input, executor)
+    )]
+    pub(super) async fn resolve_rel_count<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        rel_name: &str,
+        info: &Info,
+        input: Value,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_rel_count called -- info.name: {}, field_name: {}, rel_name: {}, input: {:#?}",
+            info.name(),
+            field_name,
+            rel_name,
+            input
+        );
+
+        let mut sg = SuffixGenerator::new();
+        let p = info.type_def()?.property(field_name)?;
+        let itd = p.input_type_definition(info)?;
+        let rel_p = info.type_def()?.property(rel_name)?;
+        let rtd = info.type_def_by_name(rel_p.type_name())?;
+        let src_label = rtd.property("src")?.type_name();
+
+        let dst_var = NodeQueryVar::new(None, "dst".to_string(), sg.suffix());
+        let rel_suffix = sg.suffix();
+        let src_var =
+            NodeQueryVar::new(Some(src_label.to_string()), "src".to_string(), sg.suffix());
+        let rel_var = RelQueryVar::new(rel_name.to_string(), rel_suffix, src_var, dst_var)
+            .with_symmetric(rtd.symmetric());
+
+        let mut transaction = executor.context().read_transaction().await?;
+        if let Err(e) = authorize(
+            CrudOperation::ReadRel(src_label.to_string(), rel_name.to_string()),
             info,
-            args,
-            parent,
-            executor,
-        ))
-        .await
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+
+        // A count with no criteria other than the src node is loaded in batches across sibling
+        // nodes, sharing the loader used by configured count properties. Filtered counts are
+        // counted by the database, without returning the matching relationships.
+        let key_opt = if executor.context().node_cache().is_stale() {
+            None
+        } else {
+            rel_loader_keys(
+                Some(&input),
+                src_label,
+                rel_name,
+                &Options::default(),
+                rtd.symmetric(),
+            )
+            .and_then(|keys| keys.into_iter().next())
+        };
+
+        let count = if let Some(key) = key_opt {
+            executor.context().count_batcher().load(key).await?
+        } else {
+            let query_fragment = visit_rel_query_input::<RequestCtx>(
+                None,
+                &rel_var,
+                Some(input),
+                Options::default(),
+                &Info::new(itd.type_name().to_owned(), info.type_defs()),
+                &mut sg,
+                &mut transaction,
+            )
+            .await?;
+
+            transaction
+                .count_matching_rels(query_fragment, &rel_var)
+                .await?
+        };
+        std::mem::drop(transaction);
+
+        executor.resolve_with_ctx(&(), &TryInto::<i32>::try_into(Value::Int64(count))?)
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "resolve_rel_aggregate",
+        skip(self, info, parent, executor)
+    )]
+    pub(super) async fn resolve_rel_aggregate<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        rel_name: &str,
+        info: &Info,
+        parent: &Node<RequestCtx>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_rel_aggregate called -- info.name: {}, field_name: {}, rel_name: {}",
+            info.name(),
+            field_name,
+            rel_name
+        );
+
+        let p = info.type_def()?.property(field_name)?;
+        let rel_p = info.type_def()?.property(rel_name)?;
+        let aggregate_props = info
+            .type_def_by_name(p.type_name())?
+            .props()
+            .filter(|ap| ap.kind() == &PropertyKind::Aggregate)
+            .collect::<Vec<&Property>>();
+        let mut db_names = aggregate_props
+            .iter()
+            .map(|ap| ap.db_name().to_string())
+            .collect::<Vec<String>>();
+        db_names.sort();
+        let key = AggregateLoaderKey::new(
+            RelLoaderKey::new(
+                parent.id()?.to_string(),
+                rel_name.to_string(),
+                Options::default(),
+            )
+            .with_symmetric(info.type_def_by_name(rel_p.type_name())?.symmetric()),
+            db_names,
+        );
+
+        // Aggregates are loaded in batches across sibling nodes, unless a write earlier in the
+        // request may have left the loader's cached aggregates out of date
+        let mut aggregates = if executor.context().node_cache().is_stale() {
+            let mut transaction = executor.context().read_transaction().await?;
+            transaction.begin().await?;
+            let aggregates = match transaction.aggregate_rels(&[key]).await {
+                Ok(aggregates) => aggregates,
+                Err(e) => {
+                    transaction.rollback().await?;
+                    return Err(e.into());
+                }
+            };
+            transaction.commit().await?;
+            aggregates.into_iter().next().unwrap_or_default()
+        } else {
+            executor.context().aggregate_batcher().load(key).await?
+        };
+
+        // The aggregates are returned under the properties' database names
+        let mut fields = HashMap::new();
+        fields.insert(
+            "count".to_string(),
+            aggregates.remove("count").unwrap_or(Value::Int64(0)),
+        );
+        aggregate_props.iter().for_each(|ap| {
+            if let Some(v) = aggregates.get(ap.db_name()) {
+                fields.insert(ap.name().to_string(), v.clone());
+            }
+        });
+
+        executor
+            .resolve_async(
+                &Info::new(p.type_name().to_string(), info.type_defs()),
+                &Node::new(p.type_name().to_string(), fields),
+            )
+            .await
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "resolve_rel_by_type",
+        skip(self, info, parent, executor)
+    )]
+    pub(super) async fn resolve_rel_by_type<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        info: &Info,
+        parent: &Node<RequestCtx>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_rel_by_type called -- info.name: {}, field_name: {}",
+            info.name(),
+            field_name
+        );
+
+        // The groups are read when their fields are resolved, so the object carries nothing but
+        // the id of the source node
+        let p = info.type_def()?.property(field_name)?;
+        executor
+            .resolve_async(
+                &Info::new(p.type_name().to_string(), info.type_defs()),
+                &Node::new(p.type_name().to_string(), id_fields(parent.id()?)),
+            )
+            .await
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "resolve_rel_group",
+        skip(self, info, input, args, executor)
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn resolve_rel_group<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        rel_name: &str,
+        dst_label: &str,
+        info: &Info,
+        input: Value,
+        options: Options,
+        args: &Arguments<'_>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_rel_group called -- info.name: {}, field_name: {}, rel_name: {}, dst_label: {}",
+            info.name(),
+            field_name,
+            rel_name,
+            dst_label
+        );
+
+        let p = info.type_def()?.property(field_name)?;
+
+        // The group's relationships are selected by a filter on the type of the destination
+        // node, so that the page is taken by the database query. A destination filter given
+        // only for other types matches nothing in this group.
+        let mut m = match input {
+            Value::Map(m) => m,
+            _ => HashMap::new(),
+        };
+        let mut dst = match m.remove("dst") {
+            Some(Value::Map(dst)) => dst,
+            _ => HashMap::new(),
+        };
+        let dst_input = match dst.remove(dst_label) {
+            Some(dst_input) => dst_input,
+            None if dst.is_empty() => Value::Map(HashMap::new()),
+            None => {
+                return executor
+                    .resolve_async(
+                        &Info::new(p.type_name().to_owned(), info.type_defs()),
+                        &Vec::<Rel<RequestCtx>>::new(),
+                    )
+                    .await
+            }
+        };
+        m.insert(
+            "dst".to_string(),
+            Value::Map(hashmap! { dst_label.to_string() => dst_input }),
+        );
+
+        let skip = args.get::<i32>("skip").unwrap_or(0).max(0) as usize;
+        let limit = args.get::<i32>("first").map(|first| first.max(0) as usize);
+        let results = self
+            .read_rels(
+                field_name,
+                rel_name,
+                info,
+                Some(Value::Map(m)),
+                options.with_page(skip, limit),
+                executor,
+            )
+            .await?;
+
+        executor
+            .resolve_async(
+                &Info::new(p.type_name().to_owned(), info.type_defs()),
+                &results,
+            )
+            .await
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "resolve_aggregate",
+        skip(self, info, fields, executor)
+    )]
+    pub(super) async fn resolve_aggregate<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        info: &Info,
+        fields: &HashMap<String, Value>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_aggregate called -- info.name: {}, field_name: {}",
+            info.name(),
+            field_name
+        );
+
+        let p = info.type_def()?.property(field_name)?;
+        match fields.get(field_name) {
+            Some(Value::Map(m)) => {
+                executor
+                    .resolve_async(
+                        &Info::new(p.type_name().to_string(), info.type_defs()),
+                        &Node::<RequestCtx>::new(p.type_name().to_string(), m.clone()),
+                    )
+                    .await
+            }
+            _ => executor.resolve_with_ctx(&(), &None::<String>),
+        }
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "resolve_computed_rel",
+        skip(self, info, query, parent, executor)
+    )]
+    pub(super) async fn resolve_computed_rel<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        rel_name: &str,
+        query: &str,
+        info: &Info,
+        parent: &Node<RequestCtx>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_computed_rel called -- info.name: {}, field_name: {}, rel_name: {}, query: {}",
+            info.name(),
+            field_name,
+            rel_name,
+            query
+        );
+
+        let p = info.type_def()?.property(field_name)?;
+        let src_id = parent.id()?.clone();
+        let src_id_str: String = src_id.clone().try_into()?;
+
+        let mut transaction = executor.context().read_transaction().await?;
+        transaction.begin().await?;
+        let nodes = match transaction
+            .read_computed_nodes::<RequestCtx>(query.to_string(), src_id, info)
+            .await
+        {
+            Ok(nodes) => nodes,
+            Err(e) => {
+                transaction.rollback().await?;
+                return Err(e.into());
+            }
+        };
+        transaction.commit().await?;
+        std::mem::drop(transaction);
+
+        // Computed relationships are not stored in the database, so each rel is given an id
+        // derived from the ids of the nodes it joins.
+        let mut results = Vec::new();
+        for dst in nodes {
+            let dst_id: String = dst.id()?.clone().try_into()?;
+            let mut fields = HashMap::new();
+            fields.insert(
+                "id".to_string(),
+                Value::String(src_id_str.clone() + ":" + rel_name + ":" + &*dst_id),
+            );
+            results.push(Rel::new(
+                rel_name.to_string(),
+                fields,
+                NodeRef::Identifier(Value::String(src_id_str.clone())),
+                NodeRef::Node(dst),
+            ));
+        }
+
+        if p.list() {
+            executor
+                .resolve_async(
+                    &Info::new(p.type_name().to_owned(), info.type_defs()),
+                    &results,
+                )
+                .await
+        } else {
+            if results.len() > 1 {
+                return Err(Error::RelDuplicated {
+                    rel_name: rel_name.to_string(),
+                    ids: results
+                        .iter()
+                        .filter_map(|r| r.id().ok().map(|id| id.to_string()))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                }
+                .into());
+            }
+
+            executor
+                .resolve_async(
+                    &Info::new(p.type_name().to_owned(), info.type_defs()),
+                    &results.first(),
+                )
+                .await
+        }
     }
 
     #[tracing::instrument(
@@ -159,12 +1205,54 @@ impl Resolver {
             options
         );
 
+        // A create repeated with the same idempotency key and input returns the nodes created
+        // the first time, resolved against the selection set of the repeat
+        let idempotency = executor
+            .context()
+            .claim_idempotency_key(
+                &format!("{}:{}", field_name, executor.look_ahead().field_name()),
+                &input,
+            )
+            .await?;
+        let idempotency = match idempotency {
+            Some((_, Some(Created::Nodes(nodes)))) => {
+                return executor
+                    .resolve_async(
+                        &Info::new(
+                            info.type_def()?
+                                .property(field_name)?
+                                .type_name()
+                                .to_owned(),
+                            info.type_defs(),
+                        ),
+                        &nodes.first(),
+                    )
+                    .await
+            }
+            Some((claim, _)) => Some(claim),
+            None => None,
+        };
+
         let mut sg = SuffixGenerator::new();
         let p = info.type_def()?.property(field_name)?;
         let itd = p.input_type_definition(info)?;
 
-        let mut transaction = executor.context().pool().transaction().await?;
+        let mut transaction = executor
+            .context()
+            .mutation_pool(options.profile())?
+            .transaction()
+            .await?;
         transaction.begin().await?;
+        if let Err(e) = authorize(
+            CrudOperation::CreateNode(p.type_name().to_string()),
+            info,
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+
         let node_var = NodeQueryVar::new(
             Some(p.type_name().to_string()),
             "node".to_string(),
@@ -192,10 +1280,186 @@ impl Resolver {
             "Resolver::resolve_node_create_mutation -- result: {:#?}",
             results
         );
+        let node = results.map_err(field_error)?;
+        if let Some(claim) = idempotency {
+            claim.record(Created::Nodes(vec![node.clone()]));
+        }
+        executor
+            .resolve_async(
+                &Info::new(p.type_name().to_owned(), info.type_defs()),
+                &node,
+            )
+            .await
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "create_node_batch",
+        skip(self, info, input, executor)
+    )]
+    pub(super) async fn resolve_node_create_batch_mutation<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        info: &Info,
+        input: Value,
+        options: Options,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_node_create_batch_mutation called -- info.name: {}, field_name: {}, input: {:#?}, options: {:#?}",
+            info.name(),
+            field_name,
+            input,
+            options
+        );
+
+        // A batch repeated with the same idempotency key and input returns the nodes created the
+        // first time, resolved against the selection set of the repeat
+        let idempotency = executor
+            .context()
+            .claim_idempotency_key(
+                &format!("{}:{}", field_name, executor.look_ahead().field_name()),
+                &input,
+            )
+            .await?;
+        let idempotency = match idempotency {
+            Some((_, Some(Created::Nodes(nodes)))) => {
+                return executor
+                    .resolve_async(
+                        &Info::new(
+                            info.type_def()?
+                                .property(field_name)?
+                                .type_name()
+                                .to_owned(),
+                            info.type_defs(),
+                        ),
+                        &nodes,
+                    )
+                    .await
+            }
+            Some((claim, _)) => Some(claim),
+            None => None,
+        };
+
+        let mut sg = SuffixGenerator::new();
+        let p = info.type_def()?.property(field_name)?;
+        let itd = p.input_type_definition(info)?;
+        let nodes_prop = itd.property("nodes")?;
+
+        let (nodes, batch_size) = if let Value::Map(mut m) = input {
+            let nodes = match m.remove("nodes") {
+                Some(Value::Array(nodes)) => nodes,
+                _ => {
+                    return Err((Error::InputItemNotFound {
+                        name: "nodes".to_string(),
+                    })
+                    .into())
+                }
+            };
+            let batch_size = match m.remove("batchSize") {
+                Some(Value::Null) | None => DEFAULT_BATCH_SIZE,
+                Some(v) => v.try_into()?,
+            };
+            // A batch size below one is treated as one, committing each node on its own
+            (nodes, batch_size.max(1) as usize)
+        } else {
+            return Err((Error::TypeNotExpected { details: None }).into());
+        };
+
+        let node_var = NodeQueryVar::new(
+            Some(p.type_name().to_string()),
+            "node".to_string(),
+            sg.suffix(),
+        );
+        let create_info = Info::new(nodes_prop.type_name().to_owned(), info.type_defs());
+
+        // Each batch is created in a transaction of its own, so a failed batch rolls back only
+        // its own nodes, leaving the batches before it committed. The nodes are created one at a
+        // time, in input order, and appended as they are created, so the Nth node returned is
+        // the one created from the Nth input, on every back-end. Clients rely on this to match
+        // the ids of the created nodes to their inputs.
+        let mut created = Vec::new();
+        let mut completed = true;
+        let mut items = nodes.into_iter().peekable();
+        while items.peek().is_some() {
+            let batch = items.by_ref().take(batch_size).collect::<Vec<Value>>();
+
+            let mut transaction = executor
+                .context()
+                .mutation_pool(options.profile())?
+                .transaction()
+                .await?;
+            transaction.begin().await?;
+            let results = match authorize(
+                CrudOperation::CreateNode(p.type_name().to_string()),
+                info,
+                &mut transaction,
+                executor.context(),
+            ) {
+                Ok(()) => {
+                    let mut results = Ok(Vec::with_capacity(batch.len()));
+                    for item in batch {
+                        match visit_node_create_mutation_input::<RequestCtx>(
+                            &node_var,
+                            item,
+                            options.clone(),
+                            &create_info,
+                            &mut sg,
+                            &mut transaction,
+                            executor.context(),
+                        )
+                        .await
+                        {
+                            Ok(node) => {
+                                if let Ok(nodes) = &mut results {
+                                    nodes.push(node);
+                                }
+                            }
+                            Err(e) => {
+                                results = Err(e);
+                                break;
+                            }
+                        }
+                    }
+                    results
+                }
+                Err(e) => Err(e),
+            };
+            let results = match results {
+                Ok(results) => transaction.commit().await.map(|_| results),
+                Err(e) => {
+                    transaction.rollback().await?;
+                    Err(e)
+                }
+            };
+            std::mem::drop(transaction);
+
+            match results {
+                Ok(mut results) => created.append(&mut results),
+                Err(e) => {
+                    executor.push_error(batch_commit_failed_error(Error::BatchCommitFailed {
+                        committed: created.len(),
+                        source: Box::new(e),
+                    }));
+                    completed = false;
+                    break;
+                }
+            }
+        }
+
+        trace!(
+            "Resolver::resolve_node_create_batch_mutation -- created: {}",
+            created.len()
+        );
+        // A partly committed batch isn't recorded, so a retry with the same key creates every
+        // node again
+        if let Some(claim) = idempotency.filter(|_| completed) {
+            claim.record(Created::Nodes(created.clone()));
+        }
         executor
             .resolve_async(
                 &Info::new(p.type_name().to_owned(), info.type_defs()),
-                &results?,
+                &created,
             )
             .await
     }
@@ -225,14 +1489,29 @@ impl Resolver {
             input
         );
 
+        reject_unfiltered_mutation(field_name, &input, &options)?;
+
         let mut sg = SuffixGenerator::new();
         let itd = info
             .type_def()?
             .property(field_name)?
             .input_type_definition(info)?;
 
-        let mut transaction = executor.context().pool().transaction().await?;
+        let mut transaction = executor
+            .context()
+            .mutation_pool(options.profile())?
+            .transaction()
+            .await?;
         transaction.begin().await?;
+        if let Err(e) = authorize(
+            CrudOperation::DeleteNode(label.to_string()),
+            info,
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
 
         let node_var = NodeQueryVar::new(Some(label.to_string()), "node".to_string(), sg.suffix());
         let results = visit_node_delete_input::<RequestCtx>(
@@ -252,6 +1531,7 @@ impl Resolver {
             transaction.rollback().await?;
         }
         std::mem::drop(transaction);
+        executor.context().node_cache().invalidate();
 
         trace!(
             "Resolver::resolve_node_delete_mutation -- results: {:#?}",
@@ -281,6 +1561,13 @@ impl Resolver {
             input_opt
         );
 
+        reject_unfiltered_read(
+            field_name,
+            input_opt.as_ref(),
+            &options,
+            executor.context().require_read_filter(),
+        )?;
+
         let mut sg = SuffixGenerator::new();
 
         let p = info.type_def()?.property(field_name)?;
@@ -290,10 +1577,37 @@ impl Resolver {
             sg.suffix(),
         );
 
-        let mut transaction = executor.context().pool().read_transaction().await?;
+        // Fall back to the type's configured default sort when the caller supplies none.
+        let options = match info.type_def_by_name(p.type_name()) {
+            Ok(nt) if options.sort().is_empty() && !nt.default_sort().is_empty() => Options::new(
+                nt.default_sort()
+                    .iter()
+                    .map(|ds| {
+                        Sort::new(
+                            ds.direction().map(|d| d.to_string()),
+                            db_order_by(info, p.type_name(), ds.order_by()),
+                        )
+                    })
+                    .collect(),
+            )
+            .with_distinct(options.distinct())
+            .with_profile(options.profile()),
+            _ => options,
+        };
+
+        let mut transaction = executor.context().read_transaction().await?;
         if info.name() == "Mutation" || info.name() == "Query" {
             transaction.begin().await?;
         }
+        if let Err(e) = authorize(
+            CrudOperation::ReadNode(p.type_name().to_string()),
+            info,
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
 
         let input_value_opt = if let Some(handlers) = executor
             .context()
@@ -318,47 +1632,39 @@ impl Resolver {
             input_opt
         };
 
-        let mut id_for_loader_opt = None;
-        if options.sort().is_empty() {
-            if let Some(Value::Map(im)) = &input_value_opt {
-                if im.keys().len() == 1 {
-                    if let Some(Value::Map(comparison)) = im.get("id") {
-                        // Okay, this is painful, but we're testing whether after
-                        // the possible additions of search criteria in the shape
-                        // and the possible changes made to the input query by
-                        // the before_node_read handler, we still have a query that
-                        // has nothing in it but the node id search criterion.
-                        // If so, this is a a basic node read (the most common case
-                        // in a shape) and we should use the loader to avoid the
-                        // N+1 problem.
-                        //
-                        // Unlike the rel loader, we don't handle the "IN" condition of multiple ids
-                        // because errors aren't returned for each id being loaded, only one error if
-                        // any of the operations fails. That means we can't return all found ids if
-                        // even one id isn't found.
-                        if let Some(id_val) = comparison.get("EQ") {
-                            id_for_loader_opt =
-                                Some(NodeLoaderKey::new(id_val.to_string(), options.clone()));
-                        }
-                    }
-                }
+        let loader_keys_opt = if options.sort().is_empty() {
+            node_loader_keys(input_value_opt.as_ref())
+        } else {
+            None
+        };
+
+        // Nodes are read from the request's node cache where possible, and the rest are loaded
+        // in batches, unless a write earlier in the request may have left the loader's cached
+        // nodes out of date, in which case they are read directly.
+        let mut loaded_opt = None;
+        if let Some(keys) = &loader_keys_opt {
+            let node_cache = executor.context().node_cache();
+            let (cached, uncached): (Vec<_>, Vec<_>) = keys
+                .iter()
+                .map(|key| node_cache.get(key.id()).ok_or(key))
+                .partition(Result::is_ok);
+            let cached = cached.into_iter().filter_map(Result::ok);
+            let uncached = uncached
+                .into_iter()
+                .filter_map(Result::err)
+                .cloned()
+                .collect::<Vec<NodeLoaderKey>>();
+            if uncached.is_empty() {
+                loaded_opt = Some(cached.collect::<Vec<Node<RequestCtx>>>());
+            } else if !node_cache.is_stale() {
+                let mut nodes = cached.collect::<Vec<Node<RequestCtx>>>();
+                nodes.append(&mut load_nodes(executor.context().node_batcher(), uncached).await?);
+                loaded_opt = Some(nodes);
             }
         }
 
-        let mut results = if let Some(id_for_loader) = id_for_loader_opt {
-            executor
-                .context()
-                .node_batcher()
-                .load(id_for_loader)
-                .await
-                .map(|n| vec![n])
-                .or_else(|e| {
-                    if let LoadError::NotFound = e {
-                        Ok(vec![])
-                    } else {
-                        Err(e)
-                    }
-                })?
+        let mut results = if let Some(loaded) = loaded_opt {
+            loaded
         } else {
             let itd = if info.name() == "Query" {
                 p.input_type_definition(info)?
@@ -389,6 +1695,14 @@ impl Resolver {
             }
         };
 
+        if let Some(keys) = &loader_keys_opt {
+            results = order_by_keys(results, keys);
+        }
+
+        for n in results.iter() {
+            executor.context().node_cache().insert(n);
+        }
+
         let label = match node_var.label() {
             Err(e) => {
                 transaction.rollback().await?;
@@ -442,64 +1756,286 @@ impl Resolver {
 
     #[tracing::instrument(
         level = "info",
-        name = "update_node",
-        skip(self, info, input, executor)
+        name = "node_exists",
+        skip(self, info, input_opt, executor)
     )]
-    pub(super) async fn resolve_node_update_mutation<RequestCtx: RequestContext>(
+    pub(super) async fn resolve_node_exists_query<RequestCtx: RequestContext>(
         &mut self,
         field_name: &str,
+        label: &str,
         info: &Info,
-        input: Value,
-        options: Options,
+        input_opt: Option<Value>,
         executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
     ) -> ExecutionResult {
         trace!(
-            "Resolver::resolve_node_update_mutation called -- info.name: {:#?}, field_name: {}, input: {:#?}",
+            "Resolver::resolve_node_exists_query called -- info.name: {}, field_name: {}, input_opt: {:#?}",
             info.name(),
             field_name,
-            input
+            input_opt
         );
+
         let mut sg = SuffixGenerator::new();
-        let p = info.type_def()?.property(field_name)?;
-        let itd = p.input_type_definition(info)?;
+        let itd = info
+            .type_def()?
+            .property(field_name)?
+            .input_type_definition(info)?;
+        let node_var = NodeQueryVar::new(Some(label.to_string()), "node".to_string(), sg.suffix());
 
-        let mut transaction = executor.context().pool().transaction().await?;
+        let mut transaction = executor.context().read_transaction().await?;
         transaction.begin().await?;
-        let results = visit_node_update_input::<RequestCtx>(
-            &NodeQueryVar::new(
-                Some(p.type_name().to_string()),
-                "node".to_string(),
-                sg.suffix(),
-            ),
-            input,
-            options,
+        if let Err(e) = authorize(
+            CrudOperation::ReadNode(label.to_string()),
+            info,
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+
+        // The before read handlers run so that filters they add, such as for authorization, also
+        // apply to existence checks. There are no nodes to pass to the after read handlers.
+        let input_value_opt =
+            if let Some(handlers) = executor.context().event_handlers().before_node_read(label) {
+                let mut input_opt_value = input_opt;
+                for f in handlers.iter() {
+                    input_opt_value = match f(
+                        input_opt_value,
+                        EventFacade::new(
+                            CrudOperation::ReadNode(field_name.to_string()),
+                            executor.context(),
+                            &mut transaction,
+                            info,
+                        ),
+                    )
+                    .await
+                    {
+                        Err(e) => {
+                            transaction.rollback().await?;
+                            return Err(e.into());
+                        }
+                        Ok(input_opt_value) => input_opt_value,
+                    }
+                }
+                input_opt_value
+            } else {
+                input_opt
+            };
+
+        let query_fragment = match visit_node_query_input::<RequestCtx>(
+            &node_var,
+            input_value_opt,
+            Options::default(),
             &Info::new(itd.type_name().to_owned(), info.type_defs()),
             &mut sg,
             &mut transaction,
-            executor.context(),
         )
-        .await;
+        .await
+        {
+            Err(e) => {
+                transaction.rollback().await?;
+                return Err(e.into());
+            }
+            Ok(query_fragment) => query_fragment,
+        };
 
-        if results.is_ok() {
-            transaction.commit().await?;
-        } else {
-            transaction.rollback().await?;
-        }
+        let exists = match transaction.node_exists(&node_var, query_fragment).await {
+            Err(e) => {
+                transaction.rollback().await?;
+                return Err(e.into());
+            }
+            Ok(exists) => exists,
+        };
+
+        transaction.commit().await?;
         std::mem::drop(transaction);
 
+        executor.resolve_with_ctx(&(), &exists)
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "update_node",
+        skip(self, info, input, executor)
+    )]
+    pub(super) async fn resolve_node_update_mutation<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        info: &Info,
+        input: Value,
+        options: Options,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_node_update_mutation called -- info.name: {:#?}, field_name: {}, input: {:#?}",
+            info.name(),
+            field_name,
+            input
+        );
+
+        let p = info.type_def()?.property(field_name)?;
+        let with_changes = executor.look_ahead().child_names().contains(&"_changes");
+        let (nodes, items) = self
+            .update_nodes(field_name, info, input, options, with_changes, executor)
+            .await
+            .map_err(field_error)?;
+        if let Some(e) = items.into_error() {
+            executor.push_error(batch_items_failed_error(e));
+        }
+
         trace!(
             "Resolver::resolve_node_update_mutation result: {:#?}",
-            results
+            nodes
+        );
+
+        executor
+            .resolve_async(
+                &Info::new(p.type_name().to_owned(), info.type_defs()),
+                &nodes,
+            )
+            .await
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "update_node_bulk",
+        skip(self, info, input, executor)
+    )]
+    pub(super) async fn resolve_node_update_bulk_mutation<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        info: &Info,
+        input: Value,
+        options: Options,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_node_update_bulk_mutation called -- info.name: {:#?}, field_name: {}, input: {:#?}",
+            info.name(),
+            field_name,
+            input
         );
 
+        let p = info.type_def()?.property(field_name)?;
+        let with_changes = executor
+            .look_ahead()
+            .select_child("succeeded")
+            .is_some_and(|s| s.child_names().contains(&"_changes"));
+        let (nodes, items) = self
+            .update_nodes(field_name, info, input, options, with_changes, executor)
+            .await
+            .map_err(field_error)?;
+        let outcome =
+            Node::new(p.type_name().to_string(), HashMap::new()).with_bulk_outcome(BulkOutcome {
+                nodes,
+                rels: Vec::new(),
+                failed: items.into_batch_item_errors(),
+            });
+
         executor
             .resolve_async(
                 &Info::new(p.type_name().to_owned(), info.type_defs()),
-                &results?,
+                &outcome,
             )
             .await
     }
 
+    /// Updates the nodes matched by the input of the node update mutation `field_name`. With
+    /// continueOnError, the matched nodes are read first, and each is updated, by its id, in a
+    /// transaction of its own, so that a failed update doesn't roll back the others. The
+    /// `_changes` of the updated nodes are found only if `with_changes` is set.
+    async fn update_nodes<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        info: &Info,
+        input: Value,
+        options: Options,
+        with_changes: bool,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> Result<(Vec<Node<RequestCtx>>, ItemOutcomes), Error> {
+        reject_unfiltered_mutation(field_name, &input, &options)?;
+
+        let mut sg = SuffixGenerator::new();
+        let p = info.type_def()?.property(field_name)?;
+        let itd = p.input_type_definition(info)?;
+
+        let continue_on_error = options.continue_on_error();
+        let inputs = if continue_on_error {
+            split_node_update_input::<RequestCtx>(
+                p.type_name(),
+                input,
+                &options,
+                info,
+                &Info::new(itd.type_name().to_owned(), info.type_defs()),
+                &mut sg,
+                executor.context(),
+            )
+            .await?
+        } else {
+            vec![(None, input)]
+        };
+
+        let mut nodes = Vec::new();
+        let mut items = ItemOutcomes::default();
+        for (index, (id, item)) in inputs.into_iter().enumerate() {
+            let mut transaction = executor
+                .context()
+                .mutation_pool(options.profile())?
+                .transaction()
+                .await?;
+            transaction.begin().await?;
+            let results = match authorize(
+                CrudOperation::UpdateNode(p.type_name().to_string()),
+                info,
+                &mut transaction,
+                executor.context(),
+            ) {
+                Ok(()) => {
+                    update_node_item::<RequestCtx>(
+                        p.type_name(),
+                        item,
+                        options.clone(),
+                        with_changes,
+                        info,
+                        &Info::new(itd.type_name().to_owned(), info.type_defs()),
+                        &mut sg,
+                        &mut transaction,
+                        executor.context(),
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            let results = match results {
+                Ok(results) => transaction.commit().await.map(|_| results),
+                Err(e) => {
+                    transaction.rollback().await?;
+                    Err(e)
+                }
+            };
+            std::mem::drop(transaction);
+
+            match results {
+                Ok(mut results) => {
+                    nodes.append(&mut results);
+                    items.succeeded.push(index);
+                }
+                Err(e) if continue_on_error => items.failed.push((index, id, e)),
+                Err(e) => {
+                    executor.context().node_cache().invalidate();
+                    return Err(e);
+                }
+            }
+        }
+
+        executor.context().node_cache().invalidate();
+        for n in nodes.iter() {
+            executor.context().node_cache().insert(n);
+        }
+
+        Ok((nodes, items))
+    }
+
     #[tracing::instrument(level = "info", name = "create_rel", skip(self, info, input, executor))]
     #[allow(clippy::too_many_arguments)]
     pub(super) async fn resolve_rel_create_mutation<RequestCtx: RequestContext>(
@@ -520,41 +2056,174 @@ impl Resolver {
         rel_name, input
     );
 
-        let mut sg = SuffixGenerator::new();
+        let p = info.type_def()?.property(field_name)?;
+        let (rels, items) = self
+            .create_rels(
+                field_name, src_label, rel_name, info, input, options, executor,
+            )
+            .await
+            .map_err(field_error)?;
+        if let Some(e) = items.into_error() {
+            executor.push_error(batch_items_failed_error(e));
+        }
 
-        let td = info.type_def()?;
-        let p = td.property(field_name)?;
-        let itd = p.input_type_definition(info)?;
-        let src_var =
-            NodeQueryVar::new(Some(src_label.to_string()), "src".to_string(), sg.suffix());
+        executor
+            .resolve_async(
+                &Info::new(p.type_name().to_owned(), info.type_defs()),
+                &rels,
+            )
+            .await
+    }
 
-        let mut transaction = executor.context().pool().transaction().await?;
-        transaction.begin().await?;
-        let results = visit_rel_create_input::<RequestCtx>(
-            &src_var,
-            rel_name,
-            input,
-            options,
-            &Info::new(itd.type_name().to_owned(), info.type_defs()),
-            &mut sg,
-            &mut transaction,
-            executor.context(),
-        )
-        .await;
+    #[tracing::instrument(
+        level = "info",
+        name = "create_rel_bulk",
+        skip(self, info, input, executor)
+    )]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn resolve_rel_create_bulk_mutation<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        src_label: &str,
+        rel_name: &str,
+        info: &Info,
+        input: Value,
+        options: Options,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+        "Resolver::resolve_rel_create_bulk_mutation called -- info.name: {:#?}, field_name: {}, src_label: {}, rel_name: {}, input: {:#?}",
+        info.name(),
+        field_name,
+        src_label,
+        rel_name, input
+    );
+
+        let p = info.type_def()?.property(field_name)?;
+        let (rels, items) = self
+            .create_rels(
+                field_name, src_label, rel_name, info, input, options, executor,
+            )
+            .await
+            .map_err(field_error)?;
+        let outcome =
+            Node::new(p.type_name().to_string(), HashMap::new()).with_bulk_outcome(BulkOutcome {
+                nodes: Vec::new(),
+                rels,
+                failed: items.into_batch_item_errors(),
+            });
+
+        executor
+            .resolve_async(
+                &Info::new(p.type_name().to_owned(), info.type_defs()),
+                &outcome,
+            )
+            .await
+    }
+
+    /// Creates the relationships in the input of the relationship create mutation `field_name`.
+    /// With continueOnError, each item in a CREATE list is applied in a transaction of its own,
+    /// so that a failed item doesn't roll back the others.
+    #[allow(clippy::too_many_arguments)]
+    async fn create_rels<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        src_label: &str,
+        rel_name: &str,
+        info: &Info,
+        input: Value,
+        options: Options,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> Result<(Vec<Rel<RequestCtx>>, ItemOutcomes), Error> {
+        // A create repeated with the same idempotency key and input returns the relationships created
+        // the first time, resolved against the selection set of the repeat
+        let idempotency = executor
+            .context()
+            .claim_idempotency_key(
+                &format!("{}:{}", field_name, executor.look_ahead().field_name()),
+                &input,
+            )
+            .await?;
+        let idempotency = match idempotency {
+            Some((_, Some(Created::Rels(rels)))) => return Ok((rels, ItemOutcomes::default())),
+            Some((claim, _)) => Some(claim),
+            None => None,
+        };
+
+        let mut sg = SuffixGenerator::new();
 
-        if results.is_ok() {
-            transaction.commit().await?;
+        let td = info.type_def()?;
+        let p = td.property(field_name)?;
+        let itd = p.input_type_definition(info)?;
+        let src_var =
+            NodeQueryVar::new(Some(src_label.to_string()), "src".to_string(), sg.suffix());
+
+        let continue_on_error = options.continue_on_error();
+        let inputs = if continue_on_error {
+            split_rel_create_input(input)
         } else {
-            transaction.rollback().await?;
+            vec![input]
+        };
+
+        let mut rels = Vec::new();
+        let mut items = ItemOutcomes::default();
+        for (index, item) in inputs.into_iter().enumerate() {
+            let mut transaction = executor
+                .context()
+                .mutation_pool(options.profile())?
+                .transaction()
+                .await?;
+            transaction.begin().await?;
+            let results = match authorize(
+                CrudOperation::CreateRel(src_label.to_string(), rel_name.to_string()),
+                info,
+                &mut transaction,
+                executor.context(),
+            ) {
+                Ok(()) => {
+                    visit_rel_create_input::<RequestCtx>(
+                        &src_var,
+                        rel_name,
+                        item,
+                        options.clone(),
+                        &Info::new(itd.type_name().to_owned(), info.type_defs()),
+                        &mut sg,
+                        &mut transaction,
+                        executor.context(),
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            let results = match results {
+                Ok(results) => transaction.commit().await.map(|_| results),
+                Err(e) => {
+                    transaction.rollback().await?;
+                    Err(e)
+                }
+            };
+            std::mem::drop(transaction);
+
+            match results {
+                Ok(mut results) => {
+                    rels.append(&mut results);
+                    items.succeeded.push(index);
+                }
+                Err(e) if continue_on_error => items.failed.push((index, None, e)),
+                Err(e) => {
+                    executor.context().node_cache().invalidate();
+                    return Err(e);
+                }
+            }
         }
-        std::mem::drop(transaction);
+        executor.context().node_cache().invalidate();
 
-        executor
-            .resolve_async(
-                &Info::new(p.type_name().to_owned(), info.type_defs()),
-                &results?,
-            )
-            .await
+        // A partially failed batch isn't recorded, so a retry with the same key applies every item again
+        if let Some(claim) = idempotency.filter(|_| items.failed.is_empty()) {
+            claim.record(Created::Rels(rels.clone()));
+        }
+
+        Ok((rels, items))
     }
 
     #[tracing::instrument(level = "info", name = "delete_rel", skip(self, info, input, executor))]
@@ -576,6 +2245,8 @@ impl Resolver {
         src_label, rel_name, input
     );
 
+        reject_unfiltered_mutation(field_name, &input, &options)?;
+
         let mut sg = SuffixGenerator::new();
         let td = info.type_def()?;
         let p = td.property(field_name)?;
@@ -588,8 +2259,21 @@ impl Resolver {
             NodeQueryVar::new(None, "dst".to_string(), sg.suffix()),
         );
 
-        let mut transaction = executor.context().pool().transaction().await?;
+        let mut transaction = executor
+            .context()
+            .mutation_pool(options.profile())?
+            .transaction()
+            .await?;
         transaction.begin().await?;
+        if let Err(e) = authorize(
+            CrudOperation::DeleteRel(src_label.to_string(), rel_name.to_string()),
+            info,
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
 
         let results = visit_rel_delete_input::<RequestCtx>(
             None,
@@ -609,16 +2293,15 @@ impl Resolver {
             transaction.rollback().await?;
         }
         std::mem::drop(transaction);
+        executor.context().node_cache().invalidate();
 
         executor.resolve_with_ctx(&(), &results?)
     }
 
-    #[tracing::instrument(
-        level = "info",
-        name = "read_rel",
-        skip(self, info, input_opt, executor)
-    )]
-    pub(super) async fn resolve_rel_read_query<RequestCtx: RequestContext>(
+    /// Reads the relationships selected by the rel field `field_name` of the type in `info`,
+    /// using the rel loader when the query selects nothing but the relationships of source nodes
+    /// by id
+    async fn read_rels<RequestCtx: RequestContext>(
         &mut self,
         field_name: &str,
         rel_name: &str,
@@ -626,16 +2309,7 @@ impl Resolver {
         input_opt: Option<Value>,
         options: Options,
         executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
-    ) -> ExecutionResult {
-        trace!(
-        "Resolver::resolve_rel_read_query called -- info.name: {:#?}, field_name: {}, rel_name: {}, input_opt: {:#?}, options: {:#?}",
-        info.name(),
-        field_name,
-        rel_name,
-        input_opt,
-        options
-        );
-
+    ) -> Result<Vec<Rel<RequestCtx>>, FieldError> {
         let mut sg = SuffixGenerator::new();
         let td = info.type_def()?;
         let p = td.property(field_name)?;
@@ -652,12 +2326,22 @@ impl Resolver {
             sg.suffix(),
         );
         let dst_var = NodeQueryVar::new(None, "dst".to_string(), dst_suffix);
-        let rel_var = RelQueryVar::new(rel_name.to_string(), rel_suffix, src_var, dst_var);
+        let rel_var = RelQueryVar::new(rel_name.to_string(), rel_suffix, src_var, dst_var)
+            .with_symmetric(rtd.symmetric());
 
-        let mut transaction = executor.context().pool().read_transaction().await?;
+        let mut transaction = executor.context().read_transaction().await?;
         if info.name() == "Mutation" || info.name() == "Query" {
             transaction.begin().await?;
         }
+        if let Err(e) = authorize(
+            CrudOperation::ReadRel(src_prop.type_name().to_string(), rel_name.to_string()),
+            info,
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
 
         let input_value_opt = if let Some(handlers) =
             executor.context().event_handlers().before_rel_read(
@@ -681,54 +2365,22 @@ impl Resolver {
             input_opt
         };
 
-        let mut ids_for_loader_opt = None;
-        if options.sort().is_empty() {
-            if let Some(Value::Map(im)) = &input_value_opt {
-                if im.keys().len() == 1 {
-                    if let Some(Value::Map(src_m)) = im.get("src") {
-                        if src_m.keys().len() == 1 {
-                            if let Some(Value::Map(src_node_m)) =
-                                src_m.get(info.type_def()?.type_name())
-                            {
-                                if src_node_m.keys().len() == 1 {
-                                    if let Some(Value::Map(comparison)) = src_node_m.get("id") {
-                                        // Okay, this is painful, but we're testing whether after
-                                        // the possible additions of search criteria in the shape
-                                        // and the possible changes made to the input dquery by
-                                        // the before_rel_read handler, we still have a query that
-                                        // has nothing in it but the src node id search criterion.
-                                        // If so, this is a a basic rel read (the most common case
-                                        // in a shape) and we should use the loader to avoid the
-                                        // N+1 problem.
-                                        if let Some(id_val) = comparison.get("EQ") {
-                                            ids_for_loader_opt = Some(vec![RelLoaderKey::new(
-                                                id_val.to_string(),
-                                                rel_name.to_string(),
-                                                options.clone(),
-                                            )]);
-                                        } else if let Some(Value::Array(ids)) = comparison.get("IN")
-                                        {
-                                            ids_for_loader_opt = Some(
-                                                ids.iter()
-                                                    .map(|id| {
-                                                        Ok(RelLoaderKey::new(
-                                                            id.to_string(),
-                                                            rel_name.to_string(),
-                                                            options.clone(),
-                                                        ))
-                                                    })
-                                                    .collect::<Result<Vec<RelLoaderKey>, Error>>(
-                                                    )?,
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        // The loader reads every relationship of each source node, so it can't select a page
+        let ids_for_loader_opt = if options.sort().is_empty()
+            && options.skip() == 0
+            && options.limit().is_none()
+            && !executor.context().node_cache().is_stale()
+        {
+            rel_loader_keys(
+                input_value_opt.as_ref(),
+                src_prop.type_name(),
+                rel_name,
+                &options,
+                rtd.symmetric(),
+            )
+        } else {
+            None
+        };
 
         let mut results = if let Some(ids_for_loader) = ids_for_loader_opt {
             trace!("Resolver::resolve_rel_read_query about to call load.");
@@ -786,6 +2438,44 @@ impl Resolver {
         }
         std::mem::drop(transaction);
 
+        Ok(results)
+    }
+
+    #[tracing::instrument(
+        level = "info",
+        name = "read_rel",
+        skip(self, info, input_opt, executor)
+    )]
+    pub(super) async fn resolve_rel_read_query<RequestCtx: RequestContext>(
+        &mut self,
+        field_name: &str,
+        rel_name: &str,
+        info: &Info,
+        input_opt: Option<Value>,
+        options: Options,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+        "Resolver::resolve_rel_read_query called -- info.name: {:#?}, field_name: {}, rel_name: {}, input_opt: {:#?}, options: {:#?}",
+        info.name(),
+        field_name,
+        rel_name,
+        input_opt,
+        options
+        );
+
+        reject_unfiltered_read(
+            field_name,
+            input_opt.as_ref(),
+            &options,
+            executor.context().require_read_filter(),
+        )?;
+
+        let p = info.type_def()?.property(field_name)?;
+        let results = self
+            .read_rels(field_name, rel_name, info, input_opt, options, executor)
+            .await?;
+
         if p.list() {
             executor
                 .resolve_async(
@@ -812,6 +2502,13 @@ impl Resolver {
                 .into());
             }
 
+            if p.required() && results.is_empty() {
+                return Err(Error::ResponseItemNotFound {
+                    name: field_name.to_string(),
+                }
+                .into());
+            }
+
             executor
                 .resolve_async(
                     &Info::new(p.type_name().to_owned(), info.type_defs()),
@@ -841,6 +2538,8 @@ impl Resolver {
         input
     );
 
+        reject_unfiltered_mutation(field_name, &input, &options)?;
+
         let mut sg = SuffixGenerator::new();
         let td = info.type_def()?;
         let p = td.property(field_name)?;
@@ -852,8 +2551,22 @@ impl Resolver {
             NodeQueryVar::new(None, "dst".to_string(), sg.suffix()),
         );
 
-        let mut transaction = executor.context().pool().transaction().await?;
+        let mut transaction = executor
+            .context()
+            .mutation_pool(options.profile())?
+            .transaction()
+            .await?;
         transaction.begin().await?;
+        if let Err(e) = authorize(
+            CrudOperation::UpdateRel(src_label.to_string(), rel_name.to_string()),
+            info,
+            &mut transaction,
+            executor.context(),
+        ) {
+            transaction.rollback().await?;
+            return Err(e.into());
+        }
+
         let results = visit_rel_update_input::<RequestCtx>(
             None,
             &rel_var,
@@ -872,11 +2585,12 @@ impl Resolver {
             transaction.rollback().await?;
         }
         std::mem::drop(transaction);
+        executor.context().node_cache().invalidate();
 
         executor
             .resolve_async(
                 &Info::new(p.type_name().to_owned(), info.type_defs()),
-                &results?,
+                &results.map_err(field_error)?,
             )
             .await
     }
@@ -886,6 +2600,7 @@ impl Resolver {
         info: &Info,
         field_name: &str,
         fields: &HashMap<String, Value>,
+        args: &Arguments<'_>,
         executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
     ) -> ExecutionResult {
         trace!(
@@ -894,6 +2609,26 @@ impl Resolver {
             field_name,
         );
 
+        // Narrow a list value to the elements selected by the field's arguments, if any
+        let sliced_fields;
+        let fields = match fields.get(field_name) {
+            Some(Value::Array(a)) => {
+                let mut hm = HashMap::new();
+                hm.insert(field_name.to_string(), Value::Array(slice_list(a, args)));
+                sliced_fields = hm;
+                &sliced_fields
+            }
+            _ => fields,
+        };
+
+        let prop = info.type_def().and_then(|nt| nt.property(field_name)).ok();
+        if let Some(p) = prop {
+            if info.is_enum(p.type_name()) {
+                return self.resolve_enum_field(info, p, fields, executor);
+            }
+        }
+        let prop_type = prop.map(|p| p.type_name());
+
         fields.get(field_name).map_or_else(
             || {
                 if field_name == "id" {
@@ -910,8 +2645,16 @@ impl Resolver {
                 Value::Bool(_) => {
                     executor.resolve_with_ctx(&(), &TryInto::<bool>::try_into(v.clone())?)
                 }
+                Value::Bytes(b) => executor.resolve_with_ctx(&(), &Base64(b.clone())),
+                Value::Duration(d) => executor.resolve_with_ctx(&(), &Duration(*d)),
+                Value::Int64(_) | Value::UInt64(_) if prop_type == Some("Duration") => {
+                    executor.resolve_with_ctx(&(), &TryInto::<Duration>::try_into(v.clone())?)
+                }
+                Value::Int64(_) | Value::UInt64(_) if prop_type == Some("Long") => {
+                    executor.resolve_with_ctx(&(), &TryInto::<Long>::try_into(v.clone())?)
+                }
                 Value::Int64(_) | Value::UInt64(_) => {
-                    executor.resolve_with_ctx(&(), &TryInto::<i32>::try_into(v.clone())?)
+                    executor.resolve_with_ctx(&(), &int_field_value(field_name, v)?)
                 }
                 Value::Float64(_) => {
                     executor.resolve_with_ctx(&(), &TryInto::<f64>::try_into(v.clone())?)
@@ -928,6 +2671,34 @@ impl Resolver {
                     Some(Value::Bool(_)) => {
                         executor.resolve_with_ctx(&(), &TryInto::<Vec<bool>>::try_into(v.clone())?)
                     }
+                    Some(Value::Bytes(_)) => executor.resolve_with_ctx(
+                        &(),
+                        &a.iter()
+                            .filter_map(|b| match b {
+                                Value::Bytes(b) => Some(Base64(b.clone())),
+                                _ => None,
+                            })
+                            .collect::<Vec<Base64>>(),
+                    ),
+                    Some(Value::Duration(_)) => executor
+                        .resolve_with_ctx(&(), &TryInto::<Vec<Duration>>::try_into(v.clone())?),
+                    Some(Value::Int64(_)) | Some(Value::UInt64(_))
+                        if prop_type == Some("Duration") =>
+                    {
+                        executor
+                            .resolve_with_ctx(&(), &TryInto::<Vec<Duration>>::try_into(v.clone())?)
+                    }
+                    Some(Value::Int64(_)) | Some(Value::UInt64(_)) if prop_type == Some("Long") => {
+                        executor.resolve_with_ctx(&(), &TryInto::<Vec<Long>>::try_into(v.clone())?)
+                    }
+                    Some(Value::Int64(_)) | Some(Value::UInt64(_)) if prop_type == Some("Int") => {
+                        executor.resolve_with_ctx(
+                            &(),
+                            &a.iter()
+                                .map(|i| int_field_value(field_name, i))
+                                .collect::<Result<Vec<i32>, Error>>()?,
+                        )
+                    }
                     Some(Value::Int64(_)) | Some(Value::UInt64(_)) | Some(Value::Float64(_)) => {
                         let r = TryInto::<Vec<i32>>::try_into(v.clone());
                         if r.is_ok() {
@@ -937,14 +2708,13 @@ impl Resolver {
                                 .resolve_with_ctx(&(), &TryInto::<Vec<f64>>::try_into(v.clone())?)
                         }
                     }
-                    Some(Value::Array(_)) | Some(Value::Map(_)) | None => {
-                        Err((Error::TypeNotExpected {
-                            details: Some(
-                                "Expected Array of scalar, found Array of Array/Map".to_string(),
-                            ),
-                        })
-                        .into())
-                    }
+                    None => executor.resolve_with_ctx(&(), &Vec::<String>::new()),
+                    Some(Value::Array(_)) | Some(Value::Map(_)) => Err((Error::TypeNotExpected {
+                        details: Some(
+                            "Expected Array of scalar, found Array of Array/Map".to_string(),
+                        ),
+                    })
+                    .into()),
                 },
                 Value::Map(_) => Err((Error::TypeNotExpected {
                     details: Some("Expected scalar, found Map".to_string()),
@@ -954,6 +2724,143 @@ impl Resolver {
         )
     }
 
+    /// Resolves a property declared with an enum type, checking that each value read from the
+    /// database is one of the variants declared for the enum. A value outside the declared
+    /// variants, such as one left behind by a legacy record, is logged and returned as an error
+    /// rather than passed along to the client.
+    fn resolve_enum_field<RequestCtx: RequestContext>(
+        &self,
+        info: &Info,
+        p: &Property,
+        fields: &HashMap<String, Value>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_enum_field called -- info.name: {}, field_name: {}, enum: {}",
+            info.name(),
+            p.name(),
+            p.type_name()
+        );
+
+        let enum_info = Info::new(p.type_name().to_string(), info.type_defs());
+        let variants = enum_info.type_def()?;
+        let to_variant = |v: &Value| -> Result<Enumeration<RequestCtx>, Error> {
+            if let Some(vp) = variants
+                .props()
+                .find(|vp| vp.stored_value().is_some_and(|sv| stored_matches(sv, v)))
+            {
+                return Ok(Enumeration::new(vp.name().to_string()));
+            }
+
+            match v {
+                Value::String(s) if variants.property(s).is_ok() => {
+                    Ok(Enumeration::new(s.to_string()))
+                }
+                _ => {
+                    error!(
+                        "Value {:?} of property {} on {} is not a variant of enum {}",
+                        v,
+                        p.name(),
+                        info.name(),
+                        p.type_name()
+                    );
+                    Err(Error::TypeConversionFailed {
+                        src: format!("{:?}", v),
+                        dst: p.type_name().to_string(),
+                    })
+                }
+            }
+        };
+
+        match fields.get(p.name()) {
+            None | Some(Value::Null) => {
+                executor.resolve_with_ctx(&enum_info, &None::<Enumeration<RequestCtx>>)
+            }
+            Some(Value::Array(a)) => executor.resolve_with_ctx(
+                &enum_info,
+                &a.iter()
+                    .map(to_variant)
+                    .collect::<Result<Vec<Enumeration<RequestCtx>>, Error>>()?,
+            ),
+            Some(v) => executor.resolve_with_ctx(&enum_info, &to_variant(v)?),
+        }
+    }
+
+    /// Resolves the entity tag of a node, a hash of all of its fields, whether or not they are
+    /// selected, so that the tag is the same for every query of the node
+    pub(super) fn resolve_etag_field(
+        &mut self,
+        fields: &HashMap<String, Value>,
+    ) -> ExecutionResult {
+        trace!("Resolver::resolve_etag_field called");
+
+        let fields = fields
+            .iter()
+            .map(|(k, v)| Ok((k.to_string(), v.clone().try_into()?)))
+            .collect::<Result<serde_json::Map<String, serde_json::Value>, Error>>()?;
+        Ok(juniper::Value::scalar(etag(&serde_json::Value::Object(
+            fields,
+        ))))
+    }
+
+    /// Resolves the changes made to a node's properties by the update mutation that returned it,
+    /// or null if the node wasn't returned by an update
+    pub(super) async fn resolve_changes_field<RequestCtx: RequestContext>(
+        &mut self,
+        info: &Info,
+        field_name: &str,
+        changes_opt: Option<&Vec<Node<RequestCtx>>>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_changes_field called -- info.name: {}, field_name: {}",
+            info.name(),
+            field_name
+        );
+
+        let p = info.type_def()?.property(field_name)?;
+        match changes_opt {
+            Some(changes) => {
+                executor
+                    .resolve_async(
+                        &Info::new(p.type_name().to_string(), info.type_defs()),
+                        changes,
+                    )
+                    .await
+            }
+            None => executor.resolve_with_ctx(&(), &None::<String>),
+        }
+    }
+
+    pub(super) async fn resolve_bulk_outcome_field<RequestCtx: RequestContext>(
+        &mut self,
+        info: &Info,
+        field_name: &str,
+        outcome_opt: Option<&BulkOutcome<RequestCtx>>,
+        executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
+    ) -> ExecutionResult {
+        trace!(
+            "Resolver::resolve_bulk_outcome_field called -- info.name: {}, field_name: {}",
+            info.name(),
+            field_name
+        );
+
+        let p = info.type_def()?.property(field_name)?;
+        let outcome = outcome_opt.ok_or_else(|| Error::ResponseItemNotFound {
+            name: field_name.to_string(),
+        })?;
+        let field_info = Info::new(p.type_name().to_string(), info.type_defs());
+        match p.kind() {
+            PropertyKind::BulkSucceeded => {
+                executor.resolve_async(&field_info, &outcome.nodes).await
+            }
+            PropertyKind::RelBulkSucceeded => {
+                executor.resolve_async(&field_info, &outcome.rels).await
+            }
+            _ => executor.resolve_async(&field_info, &outcome.failed).await,
+        }
+    }
+
     pub(super) async fn resolve_static_version_query<RequestCtx: RequestContext>(
         &mut self,
         executor: &Executor<'_, '_, GraphQLContext<RequestCtx>>,
@@ -964,3 +2871,314 @@ impl Resolver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        field_changes, node_loader_keys, order_by_keys, reject_unfiltered_mutation,
+        reject_unfiltered_read, rel_loader_keys, stored_matches, ItemOutcomes,
+    };
+    use crate::engine::config::Configuration;
+    use crate::engine::loader::{NodeLoaderKey, RelLoaderKey};
+    use crate::engine::objects::{Node, Options};
+    use crate::engine::schema::generate_schema;
+    use crate::engine::value::Value;
+    use crate::error::Error;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    fn keys(input: serde_json::Value) -> Option<Vec<RelLoaderKey>> {
+        rel_loader_keys(
+            Some(&Value::try_from(input).unwrap()),
+            "Project",
+            "issues",
+            &Options::default(),
+            false,
+        )
+    }
+
+    fn key(src_id: &str) -> RelLoaderKey {
+        RelLoaderKey::new(src_id.to_string(), "issues".to_string(), Options::default())
+    }
+
+    /// Passes if a rel read selected by nothing but the ids of its source nodes is loaded by the
+    /// rel loader
+    #[test]
+    fn rel_loader_keys_src_id() {
+        assert_eq!(
+            keys(json!({"src": {"Project": {"id": {"EQ": "p0"}}}})),
+            Some(vec![key("p0")])
+        );
+        assert_eq!(
+            keys(json!({"src": {"Project": {"id": {"IN": ["p0", "p1"]}}}})),
+            Some(vec![key("p0"), key("p1")])
+        );
+    }
+
+    /// Passes if a rel read with any search criterion beyond the ids of its source nodes bypasses
+    /// the rel loader, so that the criterion is applied by the query
+    #[test]
+    fn rel_loader_keys_filtered() {
+        assert_eq!(
+            rel_loader_keys(None, "Project", "issues", &Options::default(), false),
+            None
+        );
+        assert_eq!(
+            keys(json!({"src": {"Project": {"id": {"EQ": "p0"}}}, "since": {"EQ": "today"}})),
+            None
+        );
+        assert_eq!(
+            keys(json!({
+                "src": {"Project": {"id": {"EQ": "p0"}}},
+                "dst": {"Bug": {"name": {"EQ": "Bug Zero"}}}
+            })),
+            None
+        );
+        assert_eq!(
+            keys(json!({"src": {"Project": {"id": {"EQ": "p0"}, "name": {"EQ": "Project Zero"}}}})),
+            None
+        );
+        assert_eq!(
+            keys(json!({"src": {"Project": {"id": {"EQ": "p0", "NOTEQ": "p0"}}}})),
+            None
+        );
+        assert_eq!(
+            keys(json!({"src": {"Project": {"id": {"NOTEQ": "p0"}}}})),
+            None
+        );
+        assert_eq!(
+            keys(json!({"src": {"Feature": {"id": {"EQ": "p0"}}}})),
+            None
+        );
+    }
+
+    fn node_key(id: &str) -> NodeLoaderKey {
+        NodeLoaderKey::new(id.to_string(), Options::default())
+    }
+
+    /// Passes if a node read selected by nothing but ids is loaded by the node loader, once for
+    /// each distinct id, and a read with other criteria is not
+    #[test]
+    fn node_loader_keys_ids() {
+        let keys =
+            |input: serde_json::Value| node_loader_keys(Some(&Value::try_from(input).unwrap()));
+        assert_eq!(
+            keys(json!({"id": {"EQ": "p0"}})),
+            Some(vec![node_key("p0")])
+        );
+        assert_eq!(
+            keys(json!({"id": {"IN": ["p1", "p0", "p1"]}})),
+            Some(vec![node_key("p1"), node_key("p0")])
+        );
+        assert_eq!(node_loader_keys(None), None);
+        assert_eq!(keys(json!({"id": {"NOTIN": ["p0"]}})), None);
+        assert_eq!(
+            keys(json!({"id": {"IN": ["p0"]}, "name": {"EQ": "Project Zero"}})),
+            None
+        );
+    }
+
+    /// Passes if nodes are returned in the order of their keys, and keys without a node are
+    /// skipped
+    #[test]
+    fn order_by_keys_input_order() {
+        let node = |id: &str| {
+            let mut fields = HashMap::new();
+            fields.insert("id".to_string(), Value::String(id.to_string()));
+            Node::<()>::new("Project".to_string(), fields)
+        };
+        let ordered = order_by_keys(
+            vec![node("p0"), node("p2")],
+            &[node_key("p2"), node_key("p1"), node_key("p0")],
+        );
+        let ids = ordered
+            .iter()
+            .map(|n| n.id().unwrap().to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(ids, vec!["p2", "p0"]);
+    }
+
+    /// Passes if values read from the database are matched to the enum codes stored for them
+    #[test]
+    fn stored_matches_codes() {
+        assert!(stored_matches(&json!(3), &Value::Int64(3)));
+        assert!(stored_matches(&json!(3), &Value::UInt64(3)));
+        assert!(stored_matches(&json!("H"), &Value::String("H".to_string())));
+        assert!(!stored_matches(&json!(3), &Value::String("3".to_string())));
+        assert!(!stored_matches(
+            &json!("H"),
+            &Value::String("HIGH".to_string())
+        ));
+    }
+
+    /// Passes if update and delete mutations without MATCH criteria are rejected, unless the
+    /// options allow them
+    #[test]
+    fn reject_unfiltered_mutations() {
+        let input = |input: serde_json::Value| Value::try_from(input).unwrap();
+        let filtered = input(json!({"MATCH": {"name": {"EQ": "Project Zero"}}, "SET": {}}));
+        assert!(
+            reject_unfiltered_mutation("ProjectUpdate", &filtered, &Options::default()).is_ok()
+        );
+
+        for unfiltered in [
+            input(json!({"SET": {"public": true}})),
+            input(json!({"MATCH": null, "SET": {"public": true}})),
+            input(json!({"MATCH": {}, "DELETE": {}})),
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                reject_unfiltered_mutation("ProjectUpdate", unfiltered, &Options::default()),
+                Err(crate::Error::UnfilteredMutationRejected { name }) if name == "ProjectUpdate"
+            ));
+            assert!(reject_unfiltered_mutation(
+                "ProjectUpdate",
+                unfiltered,
+                &Options::default().with_allow_unfiltered_update(true)
+            )
+            .is_ok());
+        }
+    }
+
+    /// Passes if reads with no filtering input are rejected only when reads must be filtered and
+    /// the options don't allow them
+    #[test]
+    fn reject_unfiltered_reads() {
+        let input = |input: serde_json::Value| Value::try_from(input).unwrap();
+        let filtered = input(json!({"name": {"EQ": "Project Zero"}}));
+        assert!(
+            reject_unfiltered_read("Project", Some(&filtered), &Options::default(), true).is_ok()
+        );
+
+        for unfiltered in [
+            None,
+            Some(input(json!({}))),
+            Some(input(json!({"name": null}))),
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                reject_unfiltered_read("Project", unfiltered.as_ref(), &Options::default(), true),
+                Err(crate::Error::UnfilteredReadRejected { name }) if name == "Project"
+            ));
+            assert!(reject_unfiltered_read(
+                "Project",
+                unfiltered.as_ref(),
+                &Options::default(),
+                false
+            )
+            .is_ok());
+            assert!(reject_unfiltered_read(
+                "Project",
+                unfiltered.as_ref(),
+                &Options::default().with_allow_unfiltered_read(true),
+                true
+            )
+            .is_ok());
+        }
+    }
+
+    /// Passes if only the visible, stored properties whose values differ are reported as
+    /// changed, with their values encoded as JSON
+    #[test]
+    fn field_changes_diff() {
+        let config = Configuration::try_from(
+            "
+version: 1
+model:
+  - name: Project
+    props:
+      - name: name
+        type: String
+      - name: points
+        type: Int
+      - name: status
+        type: String
+      - name: secret
+        type: String
+        uses:
+          output: false
+"
+            .to_string(),
+        )
+        .unwrap();
+        let type_defs = generate_schema(&config).unwrap();
+        let td = type_defs.get("Project").unwrap();
+
+        let fields = |input: serde_json::Value| match Value::try_from(input).unwrap() {
+            Value::Map(m) => m,
+            _ => panic!("Expected a map"),
+        };
+        let before = fields(json!({
+            "id": "1", "name": "Project Zero", "points": 1, "status": null, "secret": "a"
+        }));
+        let after = fields(json!({
+            "id": "1", "name": "Project Zero", "points": 2, "status": "done", "secret": "b"
+        }));
+
+        let changes = field_changes::<()>(&before, &after, td).unwrap();
+        let changes = changes
+            .iter()
+            .map(|n| serde_json::Value::try_from(Value::Map(n.fields().clone())).unwrap())
+            .collect::<Vec<serde_json::Value>>();
+        assert_eq!(
+            changes,
+            vec![
+                json!({"field": "points", "before": "1", "after": "2"}),
+                json!({"field": "status", "before": null, "after": "\"done\""}),
+            ]
+        );
+
+        assert!(field_changes::<()>(&before, &before, td)
+            .unwrap()
+            .is_empty());
+    }
+
+    /// Passes if only a mutation with failed items reports an error, and each failed item
+    /// becomes a BatchItemError node with its index, the id of its node, if known, and its error
+    #[test]
+    fn item_outcomes() {
+        assert!(ItemOutcomes::default().into_error().is_none());
+
+        let items = || ItemOutcomes {
+            succeeded: vec![0, 2],
+            failed: vec![
+                (
+                    1,
+                    Some(Value::String("a".to_string())),
+                    Error::LabelNotFound,
+                ),
+                (3, None, Error::LabelNotFound),
+            ],
+        };
+        match items().into_error() {
+            Some(Error::BatchItemsFailed { succeeded, failed }) => {
+                assert_eq!(succeeded, vec![0, 2]);
+                assert_eq!(
+                    failed
+                        .iter()
+                        .map(|(index, _)| *index)
+                        .collect::<Vec<usize>>(),
+                    vec![1, 3]
+                );
+            }
+            _ => panic!("Expected a BatchItemsFailed error"),
+        }
+
+        let errors = items()
+            .into_batch_item_errors::<()>()
+            .iter()
+            .map(|n| serde_json::Value::try_from(Value::Map(n.fields().clone())).unwrap())
+            .collect::<Vec<serde_json::Value>>();
+        let message = Error::LabelNotFound.to_string();
+        assert_eq!(
+            errors,
+            vec![
+                json!({"index": 1, "id": "a", "error": message}),
+                json!({"index": 3, "error": message}),
+            ]
+        );
+    }
+}
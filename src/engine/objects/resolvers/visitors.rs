@@ -1,13 +1,16 @@
 use crate::engine::context::{GraphQLContext, RequestContext};
 use crate::engine::database::{
-    Comparison, CrudOperation, NodeQueryVar, QueryFragment, RelQueryVar, Transaction,
+    Comparison, CrudOperation, NodeQueryVar, PropOperations, QueryFragment, RelQueryVar,
+    Transaction,
 };
 use crate::engine::database::{DatabaseEndpoint, DatabasePool};
 use crate::engine::events::EventFacade;
 use crate::engine::objects::resolvers::SuffixGenerator;
 use crate::engine::objects::{Node, Options, Rel};
-use crate::engine::schema::{Info, PropertyKind};
-use crate::engine::validators::Validators;
+use crate::engine::schema::{
+    fmt_duration_query_input_name, fmt_long_query_input_name, Info, NodeType, PropertyKind,
+};
+use crate::engine::validators::{ValidationError, Validators};
 use crate::engine::value::Value;
 use crate::error::Error;
 use inflector::Inflector;
@@ -33,6 +36,8 @@ pub(crate) fn visit_node_create_mutation_input<'a, RequestCtx: RequestContext>(
         info.name()
         );
 
+        let _write_depth = context.enter_write(node_var.label()?)?;
+
         if let Some(handlers) = context
             .event_handlers()
             .before_node_create(node_var.label()?)
@@ -51,29 +56,36 @@ pub(crate) fn visit_node_create_mutation_input<'a, RequestCtx: RequestContext>(
             }
         }
 
+        let mut input =
+            normalize_input::<RequestCtx>(node_var, true, input, info, transaction, context)
+                .await?;
+
         let itd = info.type_def()?;
 
-        if let Value::Map(ref m) = input {
-            m.keys().try_for_each(|k| {
-                let p = itd.property(k)?;
-                match p.kind() {
-                    PropertyKind::Scalar | PropertyKind::DynamicScalar => {
-                        p.validator().map_or(Ok(()), |v_name| {
-                            validate_input(context.validators(), v_name, &input)
-                        })
-                    }
-                    _ => Ok(()), // No validation action to take
-                }
-            })?
+        if let Value::Map(m) = &mut input {
+            apply_defaults(itd, m)?;
         }
+        validate_props(itd, context.validators(), &input)?;
 
         if let Value::Map(m) = input {
             let (props, inputs) = m.into_iter().try_fold(
                 (HashMap::new(), HashMap::new()),
                 |(mut props, mut inputs), (k, v)| {
-                    match itd.property(&k)?.kind() {
+                    let p = itd.property(&k)?;
+                    match p.kind() {
                         PropertyKind::Scalar | PropertyKind::DynamicScalar => {
-                            props.insert(k, v);
+                            if p.type_name() == "Base64" {
+                                props.insert(p.db_name().to_string(), v.decode_base64()?);
+                            } else if p.type_name() == "Long" {
+                                props.insert(p.db_name().to_string(), v.parse_long()?);
+                            } else if p.type_name() == "Duration" {
+                                props.insert(p.db_name().to_string(), v.parse_duration()?);
+                            } else {
+                                props.insert(
+                                    p.db_name().to_string(),
+                                    store_enum(info, p.type_name(), v)?,
+                                );
+                            }
                         }
                         PropertyKind::Input => {
                             inputs.insert(k, v);
@@ -88,6 +100,9 @@ pub(crate) fn visit_node_create_mutation_input<'a, RequestCtx: RequestContext>(
                 },
             )?;
 
+            let props = add_unique_shadows(itd, props);
+            check_unique::<RequestCtx>(node_var, None, itd, &props, info, sg, transaction).await?;
+
             let node = transaction
                 .create_node(node_var, props, options.clone(), info, sg)
                 .await?;
@@ -494,7 +509,6 @@ pub(crate) fn visit_node_query_input<'a, RequestCtx: RequestContext>(
         );
 
         let itd = info.type_def()?;
-        let dst_var = NodeQueryVar::new(None, "dst".to_string(), sg.suffix());
 
         if let Some(Value::Map(m)) = input {
             let mut props = HashMap::new();
@@ -502,13 +516,34 @@ pub(crate) fn visit_node_query_input<'a, RequestCtx: RequestContext>(
             for (k, v) in m.into_iter() {
                 let p = itd.property(&k)?;
                 match p.kind() {
+                    PropertyKind::ScalarComp if p.type_name() == fmt_long_query_input_name() => {
+                        props.insert(
+                            p.db_name().to_string(),
+                            Comparison::try_from(v.parse_long()?)?,
+                        );
+                    }
+                    PropertyKind::ScalarComp
+                        if p.type_name() == fmt_duration_query_input_name() =>
+                    {
+                        props.insert(
+                            p.db_name().to_string(),
+                            Comparison::try_from(v.parse_duration()?)?,
+                        );
+                    }
                     PropertyKind::ScalarComp => {
-                        props.insert(k, Comparison::try_from(v)?);
+                        props.insert(p.db_name().to_string(), Comparison::try_from(v)?);
                     }
                     PropertyKind::Scalar => {
-                        props.insert(k, Comparison::default(v));
+                        props.insert(
+                            p.db_name().to_string(),
+                            Comparison::default(store_enum(info, p.type_name(), v)?),
+                        );
                     }
                     PropertyKind::Input => {
+                        // Each relationship condition gets its own destination variable, so that
+                        // conditions on several relationships, or chains of relationships several
+                        // hops long, don't all bind to the same destination node.
+                        let dst_var = NodeQueryVar::new(None, "dst".to_string(), sg.suffix());
                         rqfs.push(
                             visit_rel_query_input::<RequestCtx>(
                                 None,
@@ -516,7 +551,7 @@ pub(crate) fn visit_node_query_input<'a, RequestCtx: RequestContext>(
                                     k.to_string(),
                                     sg.suffix(),
                                     node_var.clone(),
-                                    dst_var.clone(),
+                                    dst_var,
                                 ),
                                 Some(v),
                                 options.clone(),
@@ -527,6 +562,48 @@ pub(crate) fn visit_node_query_input<'a, RequestCtx: RequestContext>(
                             .await?,
                         );
                     }
+                    PropertyKind::InverseRel {
+                        src_label,
+                        rel_name,
+                    } => {
+                        // The node being read is the destination of a relationship from a source
+                        // node that matches the nested input
+                        let src_var = NodeQueryVar::new(
+                            Some(src_label.to_string()),
+                            "src".to_string(),
+                            sg.suffix(),
+                        );
+                        let src_fragment = visit_node_query_input::<RequestCtx>(
+                            &src_var,
+                            Some(v),
+                            options.clone(),
+                            &Info::new(p.type_name().to_owned(), info.type_defs()),
+                            sg,
+                            transaction,
+                        )
+                        .await?;
+                        let dst_fragment = transaction.node_read_fragment(
+                            Vec::new(),
+                            node_var,
+                            HashMap::new(),
+                            sg,
+                        )?;
+                        rqfs.push(
+                            transaction.rel_read_fragment(
+                                Some(src_fragment),
+                                Some(dst_fragment),
+                                &RelQueryVar::new(
+                                    rel_name.to_string(),
+                                    sg.suffix(),
+                                    src_var,
+                                    node_var.clone(),
+                                )
+                                .with_inverse(true),
+                                HashMap::new(),
+                                sg,
+                            )?,
+                        );
+                    }
                     _ => return Err(Error::TypeNotExpected { details: None }),
                 }
             }
@@ -632,30 +709,36 @@ fn visit_node_update_mutation_input<'a, RequestCtx: RequestContext>(
         query_fragment, node_var, input, info.name(),
     );
 
-        let itd = info.type_def()?;
+        let _write_depth = context.enter_write(node_var.label()?)?;
 
-        if let Value::Map(ref m) = input {
-            m.keys().try_for_each(|k| {
-                let p = itd.property(k)?;
+        let input =
+            normalize_input::<RequestCtx>(node_var, false, input, info, transaction, context)
+                .await?;
 
-                match p.kind() {
-                    PropertyKind::Scalar | PropertyKind::DynamicScalar => {
-                        p.validator().map_or(Ok(()), |v_name| {
-                            validate_input(context.validators(), v_name, &input)
-                        })
-                    }
-                    _ => Ok(()), // No validation action to take
-                }
-            })?;
-        }
+        let itd = info.type_def()?;
+
+        validate_props(itd, context.validators(), &input)?;
+        reject_required_nulls(info.type_def_by_name(node_var.label()?)?, &input)?;
 
         if let Value::Map(m) = input {
             let (props, inputs) = m.into_iter().try_fold(
                 (HashMap::new(), HashMap::new()),
                 |(mut props, mut inputs), (k, v)| {
-                    match itd.property(&k)?.kind() {
+                    let p = itd.property(&k)?;
+                    match p.kind() {
                         PropertyKind::Scalar | PropertyKind::DynamicScalar => {
-                            props.insert(k, v);
+                            if p.type_name() == "Base64" {
+                                props.insert(p.db_name().to_string(), v.decode_base64()?);
+                            } else if p.type_name() == "Long" {
+                                props.insert(p.db_name().to_string(), v.parse_long()?);
+                            } else if p.type_name() == "Duration" {
+                                props.insert(p.db_name().to_string(), v.parse_duration()?);
+                            } else {
+                                props.insert(
+                                    p.db_name().to_string(),
+                                    store_enum(info, p.type_name(), v)?,
+                                );
+                            }
                         }
                         PropertyKind::Input => {
                             inputs.insert(k, v);
@@ -666,6 +749,18 @@ fn visit_node_update_mutation_input<'a, RequestCtx: RequestContext>(
                 },
             )?;
 
+            let props = add_unique_shadows(itd, props);
+            check_unique::<RequestCtx>(
+                node_var,
+                Some(&query_fragment),
+                itd,
+                &props,
+                info,
+                sg,
+                transaction,
+            )
+            .await?;
+
             let mut nodes = transaction
                 .update_nodes(query_fragment, node_var, props, options.clone(), info, sg)
                 .await?;
@@ -914,6 +1009,22 @@ pub(crate) async fn visit_rel_create_input<RequestCtx: RequestContext>(
             }
         })?;
 
+        // With ifDstExists, nothing is created unless every EXISTING destination matches a node
+        if matches!(m.remove("ifDstExists"), Some(Value::Bool(true)))
+            && !visit_rel_create_dsts_exist::<RequestCtx>(
+                &create_input,
+                &Info::new(
+                    itd.property("CREATE")?.type_name().to_owned(),
+                    info.type_defs(),
+                ),
+                sg,
+                transaction,
+            )
+            .await?
+        {
+            return Ok(Vec::new());
+        }
+
         match create_input {
             Value::Map(_) => {
                 let rel_var = RelQueryVar::new(
@@ -972,6 +1083,69 @@ pub(crate) async fn visit_rel_create_input<RequestCtx: RequestContext>(
     }
 }
 
+/// Returns false if the destination of any item in the CREATE input of a relationship create
+/// mutation is an EXISTING match that finds no nodes. A NEW destination is taken to exist, as it
+/// is created along with the relationship.
+async fn visit_rel_create_dsts_exist<RequestCtx: RequestContext>(
+    create_input: &Value,
+    info: &Info,
+    sg: &mut SuffixGenerator,
+    transaction: &mut <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+) -> Result<bool, Error> {
+    trace!(
+        "visit_rel_create_dsts_exist called -- create_input: {:#?}, info.name: {}",
+        create_input,
+        info.name()
+    );
+
+    let items = match create_input {
+        Value::Array(a) => a.iter().collect(),
+        v => vec![v],
+    };
+    let dst_info = Info::new(
+        info.type_def()?.property("dst")?.type_name().to_owned(),
+        info.type_defs(),
+    );
+
+    for item in items {
+        let dst = match item {
+            Value::Map(m) => m.get("dst"),
+            _ => None,
+        };
+        if let Some(Value::Map(dst)) = dst {
+            for (label, node_input) in dst {
+                let existing = match node_input {
+                    Value::Map(node_input) => node_input.get("EXISTING"),
+                    _ => None,
+                };
+                if let Some(existing) = existing {
+                    let node_itd =
+                        info.type_def_by_name(dst_info.type_def()?.property(label)?.type_name())?;
+                    let node_var =
+                        NodeQueryVar::new(Some(label.to_string()), "dst".to_string(), sg.suffix());
+                    let fragment = visit_node_query_input::<RequestCtx>(
+                        &node_var,
+                        Some(existing.clone()),
+                        Options::default(),
+                        &Info::new(
+                            node_itd.property("EXISTING")?.type_name().to_owned(),
+                            info.type_defs(),
+                        ),
+                        sg,
+                        transaction,
+                    )
+                    .await?;
+                    if !transaction.node_exists(&node_var, fragment).await? {
+                        return Ok(false);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(true)
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn visit_rel_create_mutation_input<RequestCtx: RequestContext>(
     src_fragment: QueryFragment,
@@ -986,6 +1160,13 @@ async fn visit_rel_create_mutation_input<RequestCtx: RequestContext>(
     trace!("visit_rel_create_mutation_input called -- src_fragment: {:#?}, rel_var: {:#?}, input: {:#?}, info.name: {}",
             src_fragment, rel_var, input, info.name());
 
+    let itd = info.type_def()?;
+    let mut input = input;
+    if let Value::Map(m) = &mut input {
+        apply_defaults(itd, m)?;
+    }
+    validate_props(itd, context.validators(), &input)?;
+
     if let Value::Map(mut m) = input {
         let dst_prop = info.type_def()?.property("dst")?;
         let dst = m
@@ -1004,6 +1185,18 @@ async fn visit_rel_create_mutation_input<RequestCtx: RequestContext>(
         )
         .await?;
 
+        for (k, v) in m.iter_mut() {
+            if itd.property(k).is_ok_and(|p| p.type_name() == "Base64") {
+                *v = std::mem::replace(v, Value::Null).decode_base64()?;
+            } else if itd.property(k).is_ok_and(|p| p.type_name() == "Long") {
+                *v = std::mem::replace(v, Value::Null).parse_long()?;
+            } else if itd.property(k).is_ok_and(|p| p.type_name() == "Duration") {
+                *v = std::mem::replace(v, Value::Null).parse_duration()?;
+            } else if let Ok(p) = itd.property(k) {
+                *v = store_enum(info, p.type_name(), std::mem::replace(v, Value::Null))?;
+            }
+        }
+
         let rel_label =
             rel_var.src().label()?.to_string() + &*rel_var.label().to_title_case() + "Rel";
         let mut rels = transaction
@@ -1349,6 +1542,9 @@ async fn visit_rel_nodes_mutation_input_union<RequestCtx: RequestContext>(
     }
 }
 
+/// Returns the fragment reading the relationships matching `input_opt`. Filters on the source
+/// and destination nodes are built into the fragment, rather than applied to the relationships
+/// once read, so that a relationship to a node failing the destination filter is never returned.
 pub(crate) async fn visit_rel_query_input<RequestCtx: RequestContext>(
     src_fragment_opt: Option<QueryFragment>,
     rel_var: &RelQueryVar,
@@ -1364,6 +1560,7 @@ pub(crate) async fn visit_rel_query_input<RequestCtx: RequestContext>(
     let itd = info.type_def()?;
     let src_prop = itd.property("src")?;
     let dst_prop = itd.property("dst")?;
+    let rel_var = &rel_var.clone().with_symmetric(itd.symmetric());
 
     if let Some(Value::Map(mut m)) = input_opt {
         // Remove used to take ownership
@@ -1398,6 +1595,12 @@ pub(crate) async fn visit_rel_query_input<RequestCtx: RequestContext>(
 
         let mut value_props: HashMap<String, Comparison> = HashMap::new();
         for (k, v) in m.drain() {
+            let v = match itd.property(&k).map(|p| p.type_name()) {
+                Ok(t) if t == fmt_long_query_input_name() => v.parse_long()?,
+                Ok(t) if t == fmt_duration_query_input_name() => v.parse_duration()?,
+                Ok(t) => store_enum(info, t, v)?,
+                _ => v,
+            };
             value_props.insert(k.to_string(), Comparison::try_from(v)?);
         }
         transaction.rel_read_fragment(src_fragment_opt, dst_query_opt, rel_var, value_props, sg)
@@ -1627,17 +1830,73 @@ async fn visit_rel_update_mutation_input<RequestCtx: RequestContext>(
          "visit_rel_update_mutation_input called -- query_fragment: {:#?}, rel_var: {:#?}: input: {:#?}, info.name: {}",
          query_fragment, rel_var, input, info.name());
 
-    if let Value::Map(mut m) = input {
-        let itd = info.type_def()?;
+    let itd = info.type_def()?;
+    validate_props(itd, context.validators(), &input)?;
 
+    if let Value::Map(mut m) = input {
         let src_opt = m.remove("src");
         let dst_opt = m.remove("dst");
 
+        // A new position is applied after the other props, by moving the relationship among its
+        // siblings
+        let position_opt = match itd.position() {
+            Some(position_prop) if !matches!(m.get(position_prop), None | Some(Value::Null)) => m
+                .remove(position_prop)
+                .map(|position| (position_prop, position)),
+            _ => None,
+        };
+
+        // Atomic operations are applied to the props named in their maps, and an operation with
+        // a null operand is skipped
+        let operations = |v: Option<Value>| -> Result<HashMap<String, Value>, Error> {
+            match v {
+                Some(Value::Map(om)) => om
+                    .into_iter()
+                    .filter(|(_, v)| !matches!(v, Value::Null))
+                    .map(|(k, v)| {
+                        if itd.property(&k).is_ok_and(|p| p.type_name() == "Long") {
+                            Ok((k, v.parse_long()?))
+                        } else {
+                            Ok((k, v))
+                        }
+                    })
+                    .collect(),
+                _ => Ok(HashMap::new()),
+            }
+        };
+        let ops = PropOperations::new(
+            operations(m.remove("INCREMENT"))?,
+            operations(m.remove("PUSH"))?,
+        );
+
+        for (k, v) in m.iter_mut() {
+            if itd.property(k).is_ok_and(|p| p.type_name() == "Base64") {
+                *v = std::mem::replace(v, Value::Null).decode_base64()?;
+            } else if itd.property(k).is_ok_and(|p| p.type_name() == "Long") {
+                *v = std::mem::replace(v, Value::Null).parse_long()?;
+            } else if itd.property(k).is_ok_and(|p| p.type_name() == "Duration") {
+                *v = std::mem::replace(v, Value::Null).parse_duration()?;
+            } else if let Ok(p) = itd.property(k) {
+                *v = store_enum(info, p.type_name(), std::mem::replace(v, Value::Null))?;
+            }
+        }
+
         let rel_label =
             rel_var.src().label()?.to_string() + &*rel_var.label().to_title_case() + "Rel";
         let mut rels = transaction
-            .update_rels(query_fragment, rel_var, m, options.clone(), sg)
+            .update_rels(query_fragment, rel_var, m, ops, options.clone(), sg)
             .await?;
+        if let Some((position_prop, position)) = position_opt {
+            rels = move_rels::<RequestCtx>(
+                rel_var,
+                position_prop,
+                TryFrom::try_from(position)?,
+                rels,
+                sg,
+                transaction,
+            )
+            .await?;
+        }
 
         if let Some(handlers) = context.event_handlers().after_rel_update(&rel_label) {
             for f in handlers.iter() {
@@ -1723,6 +1982,455 @@ async fn visit_rel_update_mutation_input<RequestCtx: RequestContext>(
     }
 }
 
+/// Moves each relationship to the given position among the relationships of the same name from
+/// its source node. The siblings are ordered by their current positions, with ties broken by
+/// relationship id, and siblings without a position placed last. The relationship is inserted at
+/// the requested position, clamped to the length of the list, and the siblings are renumbered
+/// from zero, closing any gaps. Only the relationships whose positions change are written.
+async fn move_rels<RequestCtx: RequestContext>(
+    rel_var: &RelQueryVar,
+    position_prop: &str,
+    position: i32,
+    rels: Vec<Rel<RequestCtx>>,
+    sg: &mut SuffixGenerator,
+    transaction: &mut <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+) -> Result<Vec<Rel<RequestCtx>>, Error> {
+    trace!(
+        "move_rels called -- rel_var: {:#?}, position_prop: {}, position: {}",
+        rel_var,
+        position_prop,
+        position
+    );
+
+    let mut moved = Vec::new();
+    for rel in rels {
+        let mut src_props = HashMap::new();
+        src_props.insert("id".to_string(), Comparison::default(rel.src_id()?.clone()));
+        let src_fragment =
+            transaction.node_read_fragment(Vec::new(), rel_var.src(), src_props, sg)?;
+        let siblings_fragment =
+            transaction.rel_read_fragment(Some(src_fragment), None, rel_var, HashMap::new(), sg)?;
+        let rel_id = rel.id()?.to_string();
+        let mut siblings = transaction
+            .read_rels::<RequestCtx>(siblings_fragment, rel_var, Options::default())
+            .await?
+            .into_iter()
+            .filter(|sibling| sibling.id().map_or(true, |id| id.to_string() != rel_id))
+            .collect::<Vec<Rel<RequestCtx>>>();
+        siblings.sort_by_cached_key(|sibling| {
+            let position = rel_position(sibling, position_prop);
+            (
+                position.is_none(),
+                position.unwrap_or_default(),
+                sibling.id().map(|id| id.to_string()).unwrap_or_default(),
+            )
+        });
+
+        let index = (position.max(0) as usize).min(siblings.len());
+        siblings.insert(index, rel);
+        for (i, sibling) in siblings.into_iter().enumerate() {
+            if rel_position(&sibling, position_prop) == Some(i as i64) {
+                if i == index {
+                    moved.push(sibling);
+                }
+                continue;
+            }
+
+            let mut props = HashMap::new();
+            props.insert(position_prop.to_string(), Value::Int64(i as i64));
+            let fragment = transaction.rel_read_by_ids_fragment(rel_var, &[sibling])?;
+            let mut updated = transaction
+                .update_rels::<RequestCtx>(
+                    fragment,
+                    rel_var,
+                    props,
+                    PropOperations::default(),
+                    Options::default(),
+                    sg,
+                )
+                .await?;
+            if i == index {
+                moved.append(&mut updated);
+            }
+        }
+    }
+
+    Ok(moved)
+}
+
+fn rel_position<RequestCtx: RequestContext>(
+    rel: &Rel<RequestCtx>,
+    position_prop: &str,
+) -> Option<i64> {
+    match rel.fields().get(position_prop) {
+        Some(Value::Int64(i)) => Some(*i),
+        Some(Value::UInt64(i)) => i64::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+/// Checks a node create or update `input` against its input type without touching the database,
+/// as the create and update visitors would before writing it. Every field must be a visible
+/// property of the input type. When `create` is true, every required property must be present.
+/// Missing properties and the failures of property validators are collected into a single
+/// [`ValidationFailed`] error. Returns the input with `Base64` properties decoded, as they would
+/// be stored.
+///
+/// [`ValidationFailed`]: ../../../error/enum.Error.html#variant.ValidationFailed
+pub(crate) fn validate_node_input(
+    itd: &NodeType,
+    validators: &Validators,
+    input: Value,
+    create: bool,
+) -> Result<Value, Error> {
+    let mut m = if let Value::Map(m) = input {
+        m
+    } else {
+        return Err(Error::TypeNotExpected {
+            details: Some("Expected an input map".to_string()),
+        });
+    };
+
+    for k in m.keys() {
+        if itd.property(k)?.hidden() {
+            return Err(Error::SchemaItemNotFound {
+                name: itd.type_name().to_string() + "::" + k.as_str(),
+            });
+        }
+    }
+
+    let mut errors = Vec::new();
+    if create {
+        apply_defaults(itd, &mut m)?;
+
+        let mut missing = itd
+            .props()
+            .filter(|p| p.required() && !p.hidden() && !m.contains_key(p.name()))
+            .map(|p| p.name())
+            .collect::<Vec<&str>>();
+        missing.sort_unstable();
+        errors.extend(
+            missing
+                .into_iter()
+                .map(|name| ValidationError::new(name.to_string(), "is required".to_string())),
+        );
+    }
+
+    let input = Value::Map(m);
+    match validate_props(itd, validators, &input) {
+        Ok(()) => (),
+        Err(Error::ValidationFailed { errors: failures }) => errors.extend(failures),
+        Err(e) => return Err(e),
+    }
+    if !errors.is_empty() {
+        return Err(Error::ValidationFailed { errors });
+    }
+
+    if let Value::Map(m) = input {
+        m.into_iter()
+            .map(|(k, v)| {
+                let p = itd.property(&k)?;
+                match p.kind() {
+                    PropertyKind::Scalar | PropertyKind::DynamicScalar
+                        if p.type_name() == "Base64" =>
+                    {
+                        Ok((k, v.decode_base64()?))
+                    }
+                    PropertyKind::Scalar | PropertyKind::DynamicScalar
+                        if p.type_name() == "Long" =>
+                    {
+                        Ok((k, v.parse_long()?))
+                    }
+                    PropertyKind::Scalar | PropertyKind::DynamicScalar
+                        if p.type_name() == "Duration" =>
+                    {
+                        Ok((k, v.parse_duration()?))
+                    }
+                    _ => Ok((k, v)),
+                }
+            })
+            .collect::<Result<HashMap<String, Value>, Error>>()
+            .map(Value::Map)
+    } else {
+        Ok(input)
+    }
+}
+
+/// Passes the create or update input for a node through the input normalizers registered for
+/// the node's type, in the order in which they were registered
+async fn normalize_input<RequestCtx: RequestContext>(
+    node_var: &NodeQueryVar,
+    create: bool,
+    mut input: Value,
+    info: &Info,
+    transaction: &mut <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+    context: &GraphQLContext<RequestCtx>,
+) -> Result<Value, Error> {
+    let type_name = node_var.label()?;
+    if let Some(normalizers) = context.event_handlers().input_normalizers(type_name) {
+        let op = if create {
+            CrudOperation::CreateNode(type_name.to_string())
+        } else {
+            CrudOperation::UpdateNode(type_name.to_string())
+        };
+        for f in normalizers.iter() {
+            input = f(
+                input,
+                EventFacade::new(op.clone(), context, transaction, info),
+            )
+            .await?;
+        }
+    }
+    Ok(input)
+}
+
+/// Replaces the names of enum values in `v`, the input value of a property of the type named
+/// `type_name`, with the codes stored for them in the database. Values of properties that aren't
+/// enums, and of enums stored by name, are returned unchanged.
+fn store_enum(info: &Info, type_name: &str, v: Value) -> Result<Value, Error> {
+    if !info.is_enum(type_name) {
+        return Ok(v);
+    }
+
+    let variants = info.type_def_by_name(type_name)?;
+    let store = |v: Value| match &v {
+        Value::String(s) => match variants.property(s).ok().and_then(|p| p.stored_value()) {
+            Some(stored) => Value::try_from(stored.clone()),
+            None => Ok(v),
+        },
+        _ => Ok(v),
+    };
+    match v {
+        Value::Array(a) => a
+            .into_iter()
+            .map(store)
+            .collect::<Result<Vec<Value>, Error>>()
+            .map(Value::Array),
+        v => store(v),
+    }
+}
+
+/// Adds the configured default value of each property that is missing from a create input
+fn apply_defaults(itd: &NodeType, m: &mut HashMap<String, Value>) -> Result<(), Error> {
+    for p in itd.props() {
+        if let Some(default) = p.default_value() {
+            if !m.contains_key(p.name()) {
+                m.insert(p.name().to_string(), Value::try_from(default.clone())?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Adds the lowercased value of each property being written that is unique ignoring case to the
+/// props written to the database, under the property's shadow name
+fn add_unique_shadows(itd: &NodeType, mut props: HashMap<String, Value>) -> HashMap<String, Value> {
+    let shadows = itd
+        .props()
+        .filter_map(|p| Some((p.unique_shadow_name()?, props.get(p.db_name())?)))
+        .map(|(shadow_name, v)| match v {
+            Value::String(s) => (shadow_name, Value::String(s.to_lowercase())),
+            _ => (shadow_name, Value::Null),
+        })
+        .collect::<Vec<(String, Value)>>();
+    props.extend(shadows);
+    props
+}
+
+/// Checks, before a write, that no other node of the type holds the lowercased value of any
+/// property being written that is unique ignoring case. For an update, `target_fragment` matches
+/// the nodes to be updated. Those nodes may keep their own values, but an update may not give
+/// the same value to more than one node.
+///
+/// A property whose uniqueness is limited to the nodes on which another property is null is
+/// checked only against those nodes, and isn't checked at all for a write that sets the other
+/// property. An update that clears the other property brings the matched nodes back into the
+/// scope of the uniqueness, so their current values are checked as well.
+///
+/// The check is a read followed by the write, with no lock or database constraint between them,
+/// so two transactions running at the same time may both pass it and write the same value. Only
+/// a uniqueness constraint on the shadow property in the database closes that gap.
+async fn check_unique<RequestCtx: RequestContext>(
+    node_var: &NodeQueryVar,
+    target_fragment: Option<&QueryFragment>,
+    itd: &NodeType,
+    props: &HashMap<String, Value>,
+    info: &Info,
+    sg: &mut SuffixGenerator,
+    transaction: &mut <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+) -> Result<(), Error> {
+    let mut targets_opt: Option<Vec<Node<RequestCtx>>> = None;
+
+    for (p, shadow_name) in itd
+        .props()
+        .filter_map(|p| Some((p, p.unique_shadow_name()?)))
+    {
+        // The scope is the stored name of the other property, which is loaded into nodes under
+        // its own name
+        let scope = p.unique_where_null();
+        let scope_field = scope.map(|s| {
+            itd.props()
+                .find(|sp| sp.db_name() == s)
+                .map_or(s, |sp| sp.name())
+        });
+        let in_scope = |n: &Node<RequestCtx>| match scope_field {
+            Some(s) => matches!(n.fields().get(s), None | Some(Value::Null)),
+            None => true,
+        };
+
+        // A write that sets the scope property takes the nodes out of the scope of the uniqueness
+        let restores = match scope.and_then(|s| props.get(s)) {
+            Some(Value::Null) => true,
+            Some(_) => continue,
+            None => false,
+        };
+
+        let targets = match target_fragment {
+            Some(target_fragment) if targets_opt.is_none() => {
+                targets_opt = Some(
+                    transaction
+                        .read_nodes::<RequestCtx>(
+                            node_var,
+                            target_fragment.clone(),
+                            Options::default(),
+                            info,
+                        )
+                        .await?,
+                );
+                targets_opt.as_deref().unwrap_or_default()
+            }
+            _ => targets_opt.as_deref().unwrap_or_default(),
+        };
+        let target_ids = targets
+            .iter()
+            .map(|n| n.id())
+            .collect::<Result<Vec<&Value>, Error>>()?;
+
+        // The values to check, and whether the write gives each value to more than one node
+        let checks = match props.get(&shadow_name) {
+            Some(Value::String(s)) => {
+                let live = targets.iter().filter(|n| restores || in_scope(n)).count();
+                if scope.is_some() && target_fragment.is_some() && live == 0 {
+                    continue;
+                }
+                vec![(Value::String(s.clone()), live > 1)]
+            }
+            Some(_) => continue,
+            None if restores => targets
+                .iter()
+                .filter(|n| !in_scope(n))
+                .filter_map(|n| match n.fields().get(p.name()) {
+                    Some(Value::String(s)) => Some((Value::String(s.to_lowercase()), false)),
+                    _ => None,
+                })
+                .collect(),
+            None => continue,
+        };
+
+        for (lowered, shared) in checks {
+            let check_var = NodeQueryVar::new(
+                Some(node_var.label()?.to_string()),
+                "unique".to_string(),
+                sg.suffix(),
+            );
+            let mut check_props = HashMap::new();
+            check_props.insert(shadow_name.clone(), Comparison::default(lowered));
+            let fragment =
+                transaction.node_read_fragment(Vec::new(), &check_var, check_props, sg)?;
+            let holders = transaction
+                .read_nodes::<RequestCtx>(&check_var, fragment, Options::default(), info)
+                .await?;
+
+            let violated = shared
+                || holders
+                    .iter()
+                    .filter(|n| in_scope(n))
+                    .map(|n| n.id())
+                    .collect::<Result<Vec<&Value>, Error>>()?
+                    .into_iter()
+                    .any(|id| !target_ids.contains(&id));
+
+            if violated {
+                return Err(Error::UniquenessViolation {
+                    type_name: node_var.label()?.to_string(),
+                    property: p.name().to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Rejects an update input that sets a required property to null. Setting an optional property
+/// to null removes it from the node, but a required property may not be removed. The properties
+/// of the update input are all optional, so `ntd` is the type of the node being updated, which
+/// holds whether each property is required.
+fn reject_required_nulls(ntd: &NodeType, input: &Value) -> Result<(), Error> {
+    let mut cleared = if let Value::Map(m) = input {
+        m.iter()
+            .filter(|(_, v)| matches!(v, Value::Null))
+            .filter_map(|(k, _)| ntd.property(k).ok())
+            .filter(|p| p.required())
+            .map(|p| p.name())
+            .collect::<Vec<&str>>()
+    } else {
+        return Ok(());
+    };
+
+    if cleared.is_empty() {
+        return Ok(());
+    }
+
+    cleared.sort_unstable();
+    Err(Error::ValidationFailed {
+        errors: cleared
+            .into_iter()
+            .map(|name| ValidationError::new(name.to_string(), "is required".to_string()))
+            .collect(),
+    })
+}
+
+/// Runs the validator of each scalar property in the `input` map, collecting the failures of all
+/// of them into a single [`ValidationFailed`] error, rather than stopping at the first.
+///
+/// [`ValidationFailed`]: ../../../error/enum.Error.html#variant.ValidationFailed
+fn validate_props(itd: &NodeType, validators: &Validators, input: &Value) -> Result<(), Error> {
+    let mut keys = if let Value::Map(m) = input {
+        m.keys().collect::<Vec<&String>>()
+    } else {
+        return Ok(());
+    };
+    keys.sort();
+
+    let mut errors = Vec::new();
+    for k in keys {
+        let p = itd.property(k)?;
+        let v_name = match (p.kind(), p.validator()) {
+            (PropertyKind::Scalar, Some(v_name)) | (PropertyKind::DynamicScalar, Some(v_name)) => {
+                v_name
+            }
+            _ => continue, // No validation action to take
+        };
+
+        match validate_input(validators, v_name, input) {
+            Ok(()) => (),
+            Err(Error::ValidationFailed { errors: failures }) => errors.extend(
+                failures
+                    .into_iter()
+                    .map(|failure| failure.with_default_field(k)),
+            ),
+            Err(e) => return Err(e),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::ValidationFailed { errors })
+    }
+}
+
 fn validate_input(validators: &Validators, v: &str, input: &Value) -> Result<(), Error> {
     let func = validators.get(v).ok_or_else(|| Error::ValidatorNotFound {
         name: v.to_string(),
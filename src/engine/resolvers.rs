@@ -1,11 +1,13 @@
 //! Contains the type aliases, enumerations, and structures to allow for the creation of custom
 //! resolvers.
 
+use crate::engine::config::Directive;
 use crate::engine::context::GraphQLContext;
 use crate::engine::context::RequestContext;
 use crate::engine::database::{
     DatabaseEndpoint, DatabasePool, NodeQueryVar, RelQueryVar, SuffixGenerator, Transaction,
 };
+use crate::engine::metadata::Metadata;
 use crate::engine::objects::resolvers::visitors::{
     visit_node_create_mutation_input, visit_node_query_input, visit_node_update_input,
     visit_rel_query_input,
@@ -18,6 +20,9 @@ use crate::Error;
 use inflector::Inflector;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 pub use juniper::{Arguments, ExecutionResult, Executor, FieldError, FromInputValue};
 
@@ -36,6 +41,17 @@ pub enum Object<'a, RequestCtx: RequestContext> {
     Rel(&'a Rel<RequestCtx>),
 }
 
+impl<'a, RequestCtx> Clone for Object<'a, RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, RequestCtx> Copy for Object<'a, RequestCtx> where RequestCtx: RequestContext {}
+
 /// Type alias for custom resolver functions. Takes a [`ResolverFacade`] and returns an
 /// ExecutionResult.
 ///
@@ -46,6 +62,171 @@ pub type ResolverFunc<RequestCtx> = fn(ResolverFacade<RequestCtx>) -> BoxFuture<
 /// custom resolver.
 pub type Resolvers<RequestCtx> = HashMap<String, Box<ResolverFunc<RequestCtx>>>;
 
+/// A limit on how long a custom resolver may run, and an optional fallback value to return in
+/// place of its result if it runs longer. A resolver that exceeds its timeout is abandoned, and
+/// its managed transaction, if it used one, is rolled back. The field then resolves to the
+/// fallback value, or to an error if there is no fallback, without failing the rest of the
+/// operation. The timeout covers the resolver wrappers as well as the resolver itself.
+///
+/// Timeouts are enforced with Tokio timers, so the engine must be run on a Tokio runtime with the
+/// time driver enabled.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use warpgrapher::engine::resolvers::ResolverTimeout;
+///
+/// let timeout = ResolverTimeout::new(Duration::from_millis(250))
+///     .with_fallback(warpgrapher::juniper::Value::scalar("unavailable"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolverTimeout {
+    duration: Duration,
+    fallback: Option<juniper::Value>,
+}
+
+impl ResolverTimeout {
+    /// Creates a timeout that abandons a resolver after `duration`, resolving the field to an
+    /// error
+    pub fn new(duration: Duration) -> ResolverTimeout {
+        ResolverTimeout {
+            duration,
+            fallback: None,
+        }
+    }
+
+    /// Sets the value to which the field resolves if the resolver times out, in place of an error
+    pub fn with_fallback(mut self, fallback: juniper::Value) -> ResolverTimeout {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Returns how long the resolver may run
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the value to which the field resolves if the resolver times out, if there is one
+    pub fn fallback(&self) -> Option<&juniper::Value> {
+        self.fallback.as_ref()
+    }
+}
+
+/// Type alias for a mapping from a custom resolver name to the timeout applied to that resolver
+pub type ResolverTimeouts = HashMap<String, ResolverTimeout>;
+
+/// Type alias for a directive handler, a function that enforces a [`Directive`] applied to a type
+/// or property in the configuration. The handler is called before a field carrying the directive
+/// is resolved, or before any field of an object type carrying the directive is resolved. It
+/// takes the [`Directive`], with the arguments given in the configuration, and the
+/// [`ResolverFacade`] for the field. If the handler returns an error, the field is not resolved,
+/// and the error is returned in its place.
+///
+/// [`Directive`]: ../config/struct.Directive.html
+/// [`ResolverFacade`]: ./struct.ResolverFacade.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::config::Directive;
+/// # use warpgrapher::engine::resolvers::{DirectiveFunc, FieldError, ResolverFacade};
+/// # use warpgrapher::juniper::BoxFuture;
+///
+/// fn auth<'a>(
+///     directive: &'a Directive,
+///     facade: ResolverFacade<'a, ()>,
+/// ) -> BoxFuture<'a, Result<(), FieldError>> {
+///     Box::pin(async move {
+///         let role = directive.arg("role").and_then(|r| r.as_str());
+///         if role.is_some() && facade.metadata().get_str("role") != role {
+///             return facade.error("FORBIDDEN", "Not authorized").map(|_| ());
+///         }
+///         Ok(())
+///     })
+/// }
+///
+/// let f: Box<DirectiveFunc<()>> = Box::new(auth);
+/// ```
+pub type DirectiveFunc<RequestCtx> = for<'a> fn(
+    &'a Directive,
+    ResolverFacade<'a, RequestCtx>,
+) -> BoxFuture<'a, Result<(), FieldError>>;
+
+/// Type alias for a mapping from a directive name to the Rust function that enforces the
+/// directive.
+pub type Directives<RequestCtx> = HashMap<String, Box<DirectiveFunc<RequestCtx>>>;
+
+/// Type alias for a resolver wrapper, a function that runs around every custom resolver. The
+/// wrapper takes the [`ResolverFacade`] for the field being resolved and a [`Next`], which it
+/// calls to run the rest of the chain. A wrapper may do work before and after calling [`Next`],
+/// or skip the call entirely and return its own result, as an authorization check might.
+///
+/// [`Next`]: ./struct.Next.html
+/// [`ResolverFacade`]: ./struct.ResolverFacade.html
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::resolvers::{ExecutionResult, Next, ResolverFacade, ResolverWrapperFunc};
+/// # use warpgrapher::juniper::BoxFuture;
+///
+/// fn timing<'a>(facade: ResolverFacade<'a, ()>, next: Next<'a, ()>) -> BoxFuture<'a, ExecutionResult> {
+///     Box::pin(async move {
+///         let start = std::time::Instant::now();
+///         let field_name = facade.field_name().to_string();
+///         let result = next.run(facade).await;
+///         log::info!("{} resolved in {:?}", field_name, start.elapsed());
+///         result
+///     })
+/// }
+///
+/// let wrapper: Box<ResolverWrapperFunc<()>> = Box::new(timing);
+/// ```
+pub type ResolverWrapperFunc<RequestCtx> = dyn for<'a> Fn(
+        ResolverFacade<'a, RequestCtx>,
+        Next<'a, RequestCtx>,
+    ) -> BoxFuture<'a, ExecutionResult>
+    + Send
+    + Sync;
+
+/// Type alias for the resolver wrappers registered with the engine, in registration order. The
+/// first wrapper registered is the outermost, so it runs first and sees the final result last.
+pub type ResolverWrappers<RequestCtx> = Vec<Arc<ResolverWrapperFunc<RequestCtx>>>;
+
+/// The remainder of a resolver wrapper chain, passed to each [`ResolverWrapperFunc`]. Calling
+/// [`run`] invokes the next wrapper, or the custom resolver itself once no wrappers remain.
+///
+/// [`ResolverWrapperFunc`]: ./type.ResolverWrapperFunc.html
+/// [`run`]: ./struct.Next.html#method.run
+pub struct Next<'a, RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    wrappers: &'a [Arc<ResolverWrapperFunc<RequestCtx>>],
+    func: &'a ResolverFunc<RequestCtx>,
+}
+
+impl<'a, RequestCtx> Next<'a, RequestCtx>
+where
+    RequestCtx: RequestContext,
+{
+    pub(crate) fn new(
+        wrappers: &'a [Arc<ResolverWrapperFunc<RequestCtx>>],
+        func: &'a ResolverFunc<RequestCtx>,
+    ) -> Self {
+        Next { wrappers, func }
+    }
+
+    /// Runs the rest of the chain for the field described by `facade`, returning its result
+    pub fn run(self, facade: ResolverFacade<'a, RequestCtx>) -> BoxFuture<'a, ExecutionResult> {
+        match self.wrappers.split_first() {
+            Some((wrapper, rest)) => wrapper(facade, Next::new(rest, self.func)),
+            None => (self.func)(facade),
+        }
+    }
+}
+
 /// Provides a simplified interface to primitive operations such as Node creation, Rel creation,
 /// resolution of both scalar and complex types. The [`ResolverFacade`] is the primary mechanism
 /// trough which a custom resolver interacts with the rest of the framework.
@@ -60,6 +241,9 @@ where
     args: &'a Arguments<'a>,
     parent: Object<'a, RequestCtx>,
     executor: &'a Executor<'a, 'a, GraphQLContext<RequestCtx>>,
+    managed_transaction: ManagedTransactionSlot<
+        <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+    >,
 }
 
 impl<'a, RequestCtx> ResolverFacade<'a, RequestCtx>
@@ -79,9 +263,20 @@ where
             args,
             parent,
             executor,
+            managed_transaction: ManagedTransactionSlot::new(),
         }
     }
 
+    /// Returns a handle to the slot holding this facade's managed transaction, so that the
+    /// resolver framework can finish the transaction once the resolver returns
+    pub(crate) fn managed_transaction_slot(
+        &self,
+    ) -> ManagedTransactionSlot<
+        <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+    >{
+        self.managed_transaction.clone()
+    }
+
     /// Returns the resolver input deserialized into a structure of type T that
     /// implements the serde `Deserialize` trait.
     ///
@@ -115,7 +310,9 @@ where
     }
 
     /// Returns the execution metadata that was passed to the engine. If no metadata was
-    /// passed to the engine's `execute` method, an empty HashMap is returned.
+    /// passed to the engine's `execute` method, an empty [`Metadata`] is returned.
+    ///
+    /// [`Metadata`]: ../metadata/struct.Metadata.html
     ///
     /// # Examples
     ///
@@ -130,10 +327,56 @@ where
     ///     facade.resolve_null()
     /// }
     /// ```
-    pub fn metadata(&self) -> &HashMap<String, String> {
+    pub fn metadata(&self) -> &Metadata {
         self.executor.context().metadata()
     }
 
+    /// Returns the name of the GraphQL operation selected by the request, if the request named
+    /// one
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> ExecutionResult {
+    ///     let operation = facade.operation_name().unwrap_or("anonymous").to_string();
+    ///
+    ///     facade.resolve_scalar(operation)
+    /// }
+    /// ```
+    pub fn operation_name(&self) -> Option<&str> {
+        self.executor.context().operation_name()
+    }
+
+    /// Returns the variables sent with the request, as a JSON object. The object is empty if the
+    /// request had no variables. Variables may hold secrets, so use [`redacted_variables`] to log
+    /// them.
+    ///
+    /// [`redacted_variables`]: #method.redacted_variables
+    pub fn variables(&self) -> &serde_json::Value {
+        self.executor.context().variables()
+    }
+
+    /// Returns a copy of the variables sent with the request, in which the value of every
+    /// variable or nested field whose name is in `names`, compared without regard to case, is
+    /// replaced by `"[REDACTED]"`
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> ExecutionResult {
+    ///     println!("{}", facade.redacted_variables(&["password"]));
+    ///
+    ///     facade.resolve_null()
+    /// }
+    /// ```
+    pub fn redacted_variables(&self, names: &[&str]) -> serde_json::Value {
+        self.executor.context().redacted_variables(names)
+    }
+
     /// Returns the arguments provided to the resolver in the GraphQL query
     ///
     /// # Examples
@@ -153,6 +396,25 @@ where
         self.args
     }
 
+    /// Returns the name of the field or endpoint being resolved
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> ExecutionResult {
+    ///     let field_name = facade.field_name();
+    ///
+    ///     // use field name
+    ///
+    ///     facade.resolve_null()
+    /// }
+    /// ```
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
     /// Creates a [`Node`], of a given type, with a set of properites
     ///
     /// [`Node`]: ../objects/struct.Node.html
@@ -666,6 +928,69 @@ where
         }
     }
 
+    /// Creates a [`Rel`], with a id, relationship name, properties, and the type and id of an
+    /// existing destination node. The src node of the relationship is the parent node on which the
+    /// field is being resolved. Only the id of the destination is held, so if the client selects
+    /// fields of the destination beyond its id, the stored node is read from the database when
+    /// the `dst` field is resolved. This spares the resolver from reading destination nodes that
+    /// the client may not ask for.
+    ///
+    /// # Error
+    ///
+    /// Returns an [`Error`] of variant [`TypeNotExpected`] if the parent object isn't a node, or
+    /// if `dst_type` isn't one of the destination types of the relationship
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`TypeNotExpected`]: ../../error/enum.Error.html#variant.TypeNotExpected
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    /// # use warpgrapher::engine::value::Value;
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         let node_id = Value::String("12345678-1234-1234-1234-1234567890ab".to_string());
+    ///         let rel_id = Value::String("1e2ac081-b0a6-4f68-bc88-99bdc4111f00".to_string());
+    ///
+    ///         let rel = facade.create_rel_with_dst_id(rel_id, "topissues", HashMap::new(),
+    ///             "Bug", node_id)?;
+    ///         facade.resolve_rel(&rel).await
+    ///     })
+    /// }
+    /// ```
+    pub fn create_rel_with_dst_id(
+        &self,
+        id: Value,
+        rel_name: &str,
+        props: HashMap<String, Value>,
+        dst_type: &str,
+        dst_id: Value,
+    ) -> Result<Rel<RequestCtx>, Error> {
+        let rel_type = self
+            .info
+            .type_def_by_name(self.info.type_def()?.property(rel_name)?.type_name())?;
+        let dst_union = self
+            .info
+            .type_def_by_name(rel_type.property("dst")?.type_name())?;
+        if !dst_union
+            .union_types()
+            .is_some_and(|mut union_types| union_types.any(|ut| ut == dst_type))
+        {
+            return Err(Error::TypeNotExpected {
+                details: Some(format!(
+                    "{} is not a destination type of the {} relationship",
+                    dst_type, rel_name
+                )),
+            });
+        }
+
+        self.create_rel(id, rel_name, props, dst_id, Options::default())
+    }
+
     /// Returns the [`Info`] struct containing the type schema for the GraphQL model.
     ///
     /// [`Info`]: ../schema/struct.Info.html
@@ -714,6 +1039,209 @@ where
         self.executor
     }
 
+    /// Returns a new database transaction that has already been begun. For a custom endpoint
+    /// declared with `class: Mutation`, the transaction is a read/write transaction. Otherwise, it
+    /// is a read transaction, which may be routed to a read replica, unless a write earlier in the
+    /// request calls for read-after-write consistency.
+    ///
+    /// The transaction is not committed automatically. The resolver must call `commit` when its
+    /// writes succeed, or `rollback` if any of them fails, before resolving its result.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the transaction cannot be created or begun. The specific [`Error`]
+    /// variant depends on the database back-end.
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use serde_json::json;
+    /// # use warpgrapher::engine::database::Transaction;
+    /// # use warpgrapher::engine::objects::Options;
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         let mut transaction = facade.transaction().await?;
+    ///         let result = ResolverFacade::create_node(&facade, "User", Options::default(),
+    ///             json!({"name": "alice"}), &mut transaction).await;
+    ///         if result.is_ok() {
+    ///             transaction.commit().await?;
+    ///         } else {
+    ///             transaction.rollback().await?;
+    ///         }
+    ///         let alice = result?;
+    ///         std::mem::drop(transaction);
+    ///         facade.resolve_node(&alice).await
+    ///     })
+    /// }
+    /// ```
+    pub async fn transaction(
+        &self,
+    ) -> Result<
+        <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+        Error,
+    >{
+        let mut transaction = if self.info.name() == "Mutation" {
+            self.executor.context().pool().transaction().await?
+        } else {
+            self.executor.context().read_transaction().await?
+        };
+        transaction.begin().await?;
+        Ok(transaction)
+    }
+
+    /// Returns the managed transaction for this resolver, beginning it on first use. Like the
+    /// transaction returned by [`transaction`], it is a read/write transaction for a custom
+    /// endpoint declared with `class: Mutation`, and a read transaction otherwise.
+    ///
+    /// Unlike [`transaction`], the managed transaction is finished by the resolver framework.
+    /// Once the resolver returns, the transaction is committed if the resolver returned `Ok`, and
+    /// rolled back if it returned `Err`. If the commit fails, the resolver's result is replaced
+    /// by the commit error. Each call returns a handle to the same transaction, which is given
+    /// back to the framework when the handle is dropped. Resolvers that need to commit at a
+    /// point of their choosing can use [`transaction`] instead.
+    ///
+    /// Because the managed transaction is committed after the resolver returns, its writes are
+    /// not yet visible to other transactions while the resolver runs. When resolving a node
+    /// created in the managed transaction, the node's own properties are returned as written,
+    /// but relationships read through the schema do not see writes made in the transaction.
+    ///
+    /// [`transaction`]: ./struct.ResolverFacade.html#method.transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the transaction cannot be created or begun. The specific [`Error`]
+    /// variant depends on the database back-end. Returns an [`Error`] variant
+    /// [`TransactionInUse`] if a handle returned by an earlier call has not yet been dropped.
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`TransactionInUse`]: ../../error/enum.Error.html#variant.TransactionInUse
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use serde_json::json;
+    /// # use warpgrapher::engine::objects::Options;
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         let mut transaction = facade.managed_transaction().await?;
+    ///         let alice = ResolverFacade::create_node(&facade, "User", Options::default(),
+    ///             json!({"name": "alice"}), &mut transaction).await?;
+    ///         std::mem::drop(transaction);
+    ///         facade.resolve_node(&alice).await
+    ///     })
+    /// }
+    /// ```
+    pub async fn managed_transaction(
+        &self,
+    ) -> Result<
+        ManagedTransaction<
+            <<<RequestCtx as RequestContext>::DBEndpointType as DatabaseEndpoint>::PoolType as DatabasePool>::TransactionType,
+        >,
+        Error,
+    >{
+        match self.managed_transaction.take()? {
+            Some(transaction) => Ok(ManagedTransaction::new(
+                transaction,
+                self.managed_transaction.clone(),
+            )),
+            None => match self.transaction().await {
+                Ok(transaction) => Ok(ManagedTransaction::new(
+                    transaction,
+                    self.managed_transaction.clone(),
+                )),
+                Err(e) => {
+                    self.managed_transaction.release();
+                    Err(e)
+                }
+            },
+        }
+    }
+
+    /// Runs a query written for the database back-end, such as a Cypher or Gremlin query, and
+    /// returns each of its records deserialized into a `T`. The query is run in the resolver's
+    /// managed transaction, as returned by [`managed_transaction`], so it sees the writes the
+    /// resolver has made there, and its own writes are committed or rolled back with the rest of
+    /// the resolver's work.
+    ///
+    /// A Cypher record with a single column, such as the node returned by `RETURN n`, is
+    /// deserialized from the value of that column, and a record with several columns from the
+    /// array of their values, such as into a tuple. Each Gremlin result is deserialized on its
+    /// own. Nodes and relationships are deserialized from maps of their properties, so a node can
+    /// be read into a struct with a field for each property of interest.
+    ///
+    /// [`managed_transaction`]: ./struct.ResolverFacade.html#method.managed_transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`TransactionInUse`] if a handle to the managed transaction
+    /// has not been dropped, [`TypeConversionFailed`] if a value returned by the query cannot be
+    /// converted, and [`JsonDeserializationFailed`] if a record cannot be deserialized into a
+    /// `T`. Other [`Error`] variants may be returned by the database back-end.
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`JsonDeserializationFailed`]: ../../error/enum.Error.html#variant.JsonDeserializationFailed
+    /// [`TransactionInUse`]: ../../error/enum.Error.html#variant.TransactionInUse
+    /// [`TypeConversionFailed`]: ../../error/enum.Error.html#variant.TypeConversionFailed
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use serde::Deserialize;
+    /// # use std::collections::HashMap;
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    /// # use warpgrapher::engine::value::Value;
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Dev {
+    ///     name: String,
+    /// }
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         let mut params = HashMap::new();
+    ///         params.insert("limit".to_string(), Value::Int64(3));
+    ///         let devs: Vec<Dev> = facade
+    ///             .query_as("MATCH (n:User) RETURN n LIMIT $limit", params)
+    ///             .await?;
+    ///
+    ///         facade.resolve_scalar_list(devs.into_iter().map(|d| d.name).collect())
+    ///     })
+    /// }
+    /// ```
+    pub async fn query_as<T: serde::de::DeserializeOwned>(
+        &self,
+        query: &str,
+        params: HashMap<String, Value>,
+    ) -> Result<Vec<T>, Error> {
+        let mut transaction = self.managed_transaction().await?;
+        let values = transaction
+            .execute_query_values(query.to_string(), params)
+            .await?;
+        std::mem::drop(transaction);
+
+        values
+            .into_iter()
+            .map(|v| {
+                let json_value =
+                    serde_json::Value::try_from(v).map_err(|_| Error::TypeConversionFailed {
+                        src: "warpgrapher::Value".to_string(),
+                        dst: "serde_json::Value".to_string(),
+                    })?;
+                serde_json::from_value(json_value)
+                    .map_err(|e| Error::JsonDeserializationFailed { source: e })
+            })
+            .collect()
+    }
+
     /// Returns the parent GraphQL object of the field being resolved as a [`Node`]
     ///
     /// # Errors
@@ -750,6 +1278,37 @@ where
         }
     }
 
+    /// Returns the fields of the parent node or relationship of the field being resolved, as
+    /// they were loaded for the parent. A resolver for a field computed from other fields of the
+    /// same object can read them here, without querying the database for the parent again.
+    /// Fields resolved by custom resolvers are not present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use warpgrapher::engine::resolvers::{ResolverFacade, ExecutionResult};
+    /// # use warpgrapher::engine::value::Value;
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn full_name(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         let fields = facade.parent_fields();
+    ///         match (fields.get("firstName"), fields.get("lastName")) {
+    ///             (Some(Value::String(first)), Some(Value::String(last))) => {
+    ///                 facade.resolve_scalar(format!("{} {}", first, last))
+    ///             }
+    ///             _ => facade.resolve_null(),
+    ///         }
+    ///     })
+    /// }
+    /// ```
+    pub fn parent_fields(&self) -> &HashMap<String, Value> {
+        match self.parent {
+            Object::Node(n) => n.fields(),
+            Object::Rel(r) => r.fields(),
+        }
+    }
+
     /// Returns a GraphQL Null
     ///
     /// # Examples
@@ -771,6 +1330,36 @@ where
         Ok(juniper::Value::Null)
     }
 
+    /// Returns a GraphQL error with the given message, carrying a machine-readable code under
+    /// `code` in the error's extensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - the machine-readable error code, such as `FORBIDDEN`
+    /// * `message` - the human-readable error message
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use warpgrapher::engine::resolvers::{ExecutionResult, ResolverFacade};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         if facade.metadata().get_str("role") != Some("admin") {
+    ///             return facade.error("FORBIDDEN", "Only administrators may call this endpoint");
+    ///         }
+    ///
+    ///         facade.resolve_scalar("Hello")
+    ///     })
+    /// }
+    /// ```
+    pub fn error(&self, code: &str, message: &str) -> ExecutionResult {
+        let mut extensions = juniper::Object::with_capacity(1);
+        extensions.add_field("code", juniper::Value::scalar(code.to_string()));
+        Err(FieldError::new(message, juniper::Value::object(extensions)))
+    }
+
     /// Returns a GraphQL Scalar
     ///
     /// # Examples
@@ -820,6 +1409,116 @@ where
         Ok(list)
     }
 
+    /// Returns a GraphQL enumeration value for a field whose type is an enum declared in the
+    /// configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`TypeNotExpected`] if the type of the field being resolved
+    /// is not an enumeration.
+    ///
+    /// Returns an [`Error`] variant [`TypeConversionFailed`] if the value is not one of the
+    /// variants declared for the enumeration.
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`TypeConversionFailed`]: ../../error/enum.Error.html#variant.TypeConversionFailed
+    /// [`TypeNotExpected`]: ../../error/enum.Error.html#variant.TypeNotExpected
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use warpgrapher::engine::resolvers::{ExecutionResult, ResolverFacade};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         // do work
+    ///
+    ///         // return a variant of the Status enum
+    ///         facade.resolve_enum("ACTIVE")
+    ///     })
+    /// }
+    /// ```
+    pub fn resolve_enum<T>(&self, v: T) -> ExecutionResult
+    where
+        T: Into<String>,
+    {
+        let v = v.into();
+        self.validate_enum_variant(&v)?;
+        Ok(juniper::Value::scalar(v))
+    }
+
+    /// Returns a list of GraphQL enumeration values for a field whose type is a list of an enum
+    /// declared in the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] variant [`TypeNotExpected`] if the type of the field being resolved
+    /// is not an enumeration.
+    ///
+    /// Returns an [`Error`] variant [`TypeConversionFailed`] if any of the values is not one of
+    /// the variants declared for the enumeration.
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`TypeConversionFailed`]: ../../error/enum.Error.html#variant.TypeConversionFailed
+    /// [`TypeNotExpected`]: ../../error/enum.Error.html#variant.TypeNotExpected
+    ///
+    /// # Examples
+    ///
+    /// ```rust, no_run
+    /// # use warpgrapher::engine::resolvers::{ExecutionResult, ResolverFacade};
+    /// # use warpgrapher::juniper::BoxFuture;
+    ///
+    /// fn custom_resolve(facade: ResolverFacade<()>) -> BoxFuture<ExecutionResult> {
+    ///     Box::pin(async move {
+    ///         // do work
+    ///
+    ///         // return variants of the Status enum
+    ///         facade.resolve_enum_list(vec!["ACTIVE", "ARCHIVED"])
+    ///     })
+    /// }
+    /// ```
+    pub fn resolve_enum_list<T>(&self, v: Vec<T>) -> ExecutionResult
+    where
+        T: Into<String>,
+    {
+        let list = v
+            .into_iter()
+            .map(|v| {
+                let v = v.into();
+                self.validate_enum_variant(&v)?;
+                Ok(juniper::Value::scalar(v))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(juniper::Value::List(list))
+    }
+
+    fn validate_enum_variant(&self, v: &str) -> Result<(), Error> {
+        let type_name = self
+            .info
+            .type_def()?
+            .property(&self.field_name)?
+            .type_name();
+
+        if !self.info.is_enum(type_name) {
+            return Err(Error::TypeNotExpected {
+                details: Some(format!(
+                    "Field {} is of type {}, which is not an enum",
+                    self.field_name, type_name
+                )),
+            });
+        }
+
+        self.info
+            .type_def_by_name(type_name)?
+            .property(v)
+            .map(|_| ())
+            .map_err(|_| Error::TypeConversionFailed {
+                src: v.to_string(),
+                dst: type_name.to_string(),
+            })
+    }
+
     /// Returns a GraphQL Object representing a graph node defined by a type and a map of props.
     ///
     /// # Examples
@@ -997,3 +1696,154 @@ where
         self.executor.context().request_context()
     }
 }
+
+/// The state of a resolver's managed transaction, shared between the [`ResolverFacade`] and any
+/// outstanding [`ManagedTransaction`] handle
+///
+/// [`ManagedTransaction`]: ./struct.ManagedTransaction.html
+/// [`ResolverFacade`]: ./struct.ResolverFacade.html
+struct ManagedTransactionState<T> {
+    transaction: Option<T>,
+    in_use: bool,
+}
+
+/// Holds a resolver's managed transaction, if one has been begun, between uses
+pub(crate) struct ManagedTransactionSlot<T> {
+    state: Arc<Mutex<ManagedTransactionState<T>>>,
+}
+
+impl<T> ManagedTransactionSlot<T>
+where
+    T: Transaction,
+{
+    fn new() -> Self {
+        ManagedTransactionSlot {
+            state: Arc::new(Mutex::new(ManagedTransactionState {
+                transaction: None,
+                in_use: false,
+            })),
+        }
+    }
+
+    /// Marks the slot in use and takes its transaction, if one has been begun
+    fn take(&self) -> Result<Option<T>, Error> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.in_use {
+            return Err(Error::TransactionInUse);
+        }
+        state.in_use = true;
+        Ok(state.transaction.take())
+    }
+
+    /// Returns a transaction to the slot, or just releases the slot if `transaction` is `None`
+    fn put(&self, transaction: Option<T>) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if transaction.is_some() {
+            state.transaction = transaction;
+        }
+        state.in_use = false;
+    }
+
+    fn release(&self) {
+        self.put(None)
+    }
+
+    /// Commits the managed transaction, if one was begun, when `result` is `Ok`, or rolls it
+    /// back when `result` is an `Err`. Returns `result`, unless the commit fails, in which case
+    /// the commit error is returned instead.
+    pub(crate) async fn finish(self, result: ExecutionResult) -> ExecutionResult {
+        let (transaction, in_use) = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            (state.transaction.take(), state.in_use)
+        };
+
+        if in_use {
+            // the resolver kept a handle past its own return, so the transaction can't be finished
+            return result.and(Err(Error::TransactionInUse.into()));
+        }
+
+        match transaction {
+            None => result,
+            Some(mut transaction) => {
+                if result.is_ok() {
+                    if let Err(e) = transaction.commit().await {
+                        let _ = transaction.rollback().await;
+                        return Err(e.into());
+                    }
+                } else {
+                    transaction.rollback().await?;
+                }
+                result
+            }
+        }
+    }
+}
+
+impl<T> Clone for ManagedTransactionSlot<T> {
+    fn clone(&self) -> Self {
+        ManagedTransactionSlot {
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// A handle to a resolver's managed transaction, returned by
+/// [`ResolverFacade::managed_transaction`]. The handle dereferences to the transaction, so it can
+/// be passed wherever a `&mut` transaction is expected. Dropping the handle gives the transaction
+/// back to the resolver framework, which commits or rolls it back once the resolver returns.
+///
+/// [`ResolverFacade::managed_transaction`]: ./struct.ResolverFacade.html#method.managed_transaction
+pub struct ManagedTransaction<T>
+where
+    T: Transaction,
+{
+    transaction: Option<T>,
+    slot: ManagedTransactionSlot<T>,
+}
+
+impl<T> ManagedTransaction<T>
+where
+    T: Transaction,
+{
+    fn new(transaction: T, slot: ManagedTransactionSlot<T>) -> Self {
+        ManagedTransaction {
+            transaction: Some(transaction),
+            slot,
+        }
+    }
+}
+
+impl<T> Deref for ManagedTransaction<T>
+where
+    T: Transaction,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // the transaction is only taken out of the handle when the handle is dropped
+        self.transaction
+            .as_ref()
+            .expect("managed transaction taken before drop")
+    }
+}
+
+impl<T> DerefMut for ManagedTransaction<T>
+where
+    T: Transaction,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        // the transaction is only taken out of the handle when the handle is dropped
+        self.transaction
+            .as_mut()
+            .expect("managed transaction taken before drop")
+    }
+}
+
+impl<T> Drop for ManagedTransaction<T>
+where
+    T: Transaction,
+{
+    fn drop(&mut self) {
+        self.slot.put(self.transaction.take());
+    }
+}
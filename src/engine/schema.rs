@@ -3,13 +3,16 @@
 //! and resolvers for common create, read, update, and delete operations.
 
 use super::config::{
-    Configuration, Endpoint, EndpointClass, GraphqlType, Relationship, Type, TypeDef,
+    Configuration, DefaultSort, Directive, Endpoint, EndpointClass, EnumSerialization, EnumType,
+    GraphqlType, Relationship, Type, TypeDef,
 };
 use super::objects::Node;
 use crate::engine::context::RequestContext;
 use crate::error::Error;
 use inflector::Inflector;
-use juniper::RootNode;
+use juniper::meta::MetaType;
+use juniper::parser::parse_document_source;
+use juniper::{DefaultScalarValue, Definition, InputValue, RootNode, SchemaType};
 use maplit::hashmap;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Values;
@@ -36,7 +39,8 @@ impl Info {
         Info { name, type_defs }
     }
 
-    pub(crate) fn name(&self) -> &str {
+    /// Returns the name of the GraphQL type described by the info
+    pub fn name(&self) -> &str {
         &self.name
     }
 
@@ -55,8 +59,19 @@ impl Info {
     pub(crate) fn type_defs(&self) -> Arc<HashMap<String, NodeType>> {
         self.type_defs.clone()
     }
+
+    /// Returns true if the named type is an enumeration in the GraphQL schema
+    pub(crate) fn is_enum(&self, name: &str) -> bool {
+        matches!(
+            self.type_defs.get(name).map(|nt| nt.type_kind()),
+            Some(TypeKind::Enum)
+        )
+    }
 }
 
+/// Number of nodes created in each transaction by a batched create that gives no batch size
+pub(crate) const DEFAULT_BATCH_SIZE: i32 = 100;
+
 pub(super) type RootRef<RequestCtx> =
     Arc<RootNode<'static, Node<RequestCtx>, Node<RequestCtx>, Node<RequestCtx>>>;
 
@@ -68,19 +83,36 @@ pub(crate) enum ArgumentKind {
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub(crate) enum PropertyKind {
+    Aggregate,
+    BulkFailed,
+    BulkSucceeded,
+    Changes,
+    ComputedCount { rel_name: String },
+    ComputedRel { rel_name: String, query: String },
     CustomResolver,
     DynamicScalar,
     DynamicRel { rel_name: String },
     Enum,
+    Etag,
     Input,
+    InverseRel { src_label: String, rel_name: String },
     NodeCreateMutation,
+    NodeCreateBatchMutation,
     NodeUpdateMutation,
+    NodeUpdateBulkMutation,
     NodeDeleteMutation { label: String },
+    NodeExistsQuery { label: String },
     Object,
     Rel { rel_name: String },
+    RelAggregate { rel_name: String },
+    RelBulkSucceeded,
+    RelByType { rel_name: String },
+    RelCount { rel_name: String },
     RelCreateMutation { src_label: String, rel_name: String },
+    RelCreateBulkMutation { src_label: String, rel_name: String },
     RelUpdateMutation { src_label: String, rel_name: String },
     RelDeleteMutation { src_label: String, rel_name: String },
+    RelGroup { rel_name: String, dst_label: String },
     Scalar,
     ScalarComp,
     Union,
@@ -102,6 +134,11 @@ pub(crate) struct NodeType {
     type_kind: TypeKind,
     type_name: String,
     union_types: Option<Vec<String>>,
+    symmetric: bool,
+    position: Option<String>,
+    default_sort: Vec<DefaultSort>,
+    description: Option<String>,
+    directives: Vec<Directive>,
 }
 
 impl NodeType {
@@ -111,6 +148,11 @@ impl NodeType {
             type_kind,
             type_name,
             union_types: None,
+            symmetric: false,
+            position: None,
+            default_sort: Vec::new(),
+            description: None,
+            directives: Vec::new(),
         }
     }
 
@@ -122,10 +164,36 @@ impl NodeType {
             })
     }
 
+    /// Returns the property stored in the database under the given name, which differs from the
+    /// property's name if the configuration gives it a database name
+    #[cfg(any(feature = "gremlin", feature = "cypher"))]
+    pub(crate) fn db_property(&self, db_name: &str) -> Result<&Property, Error> {
+        match self.props.get(db_name) {
+            Some(p) if p.db_name() == db_name => Ok(p),
+            _ => self
+                .props
+                .values()
+                .find(|p| p.db_name() == db_name)
+                .ok_or_else(|| Error::SchemaItemNotFound {
+                    name: self.type_name.to_string() + "::" + db_name,
+                }),
+        }
+    }
+
     pub(crate) fn props(&self) -> Values<String, Property> {
         self.props.values()
     }
 
+    /// Returns true if the given stored name is the shadow name under which the lowercased value
+    /// of a property that is unique ignoring case is stored. Shadow values serve only the
+    /// uniqueness check, and aren't loaded into nodes.
+    #[cfg(any(feature = "gremlin", feature = "cypher"))]
+    pub(crate) fn is_unique_shadow(&self, db_name: &str) -> bool {
+        self.props()
+            .filter_map(|p| p.unique_shadow_name())
+            .any(|shadow_name| shadow_name == db_name)
+    }
+
     pub(crate) fn type_kind(&self) -> &TypeKind {
         &self.type_kind
     }
@@ -137,6 +205,35 @@ impl NodeType {
     pub(crate) fn union_types(&self) -> Option<Iter<String>> {
         self.union_types.as_ref().map(|uts| uts.iter())
     }
+
+    /// Returns true if the type is a relationship object or relationship query input for a
+    /// symmetric relationship
+    pub(crate) fn symmetric(&self) -> bool {
+        self.symmetric
+    }
+
+    /// Returns the name of the property that holds the position of a relationship among its
+    /// siblings, if the relationship is ordered
+    pub(crate) fn position(&self) -> Option<&str> {
+        self.position.as_deref()
+    }
+
+    /// Returns the sort order applied to reads of a node object type when the caller provides
+    /// no sort
+    pub(crate) fn default_sort(&self) -> &[DefaultSort] {
+        &self.default_sort
+    }
+
+    /// Returns the description of the type in the GraphQL schema, if one is configured
+    pub(crate) fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the directives applied to the object type, enforced when any of its fields is
+    /// resolved
+    pub(crate) fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -150,6 +247,14 @@ pub(crate) struct Property {
     arguments: HashMap<String, Argument>,
     resolver: Option<String>,
     validator: Option<String>,
+    default: Option<serde_json::Value>,
+    db_name: Option<String>,
+    unique_case_insensitive: bool,
+    unique_where_null: Option<String>,
+    description: Option<String>,
+    deprecated: Option<String>,
+    directives: Vec<Directive>,
+    stored_value: Option<serde_json::Value>,
 }
 
 impl Property {
@@ -164,6 +269,14 @@ impl Property {
             arguments: HashMap::new(),
             resolver: None,
             validator: None,
+            default: None,
+            db_name: None,
+            unique_case_insensitive: false,
+            unique_where_null: None,
+            description: None,
+            deprecated: None,
+            directives: Vec::new(),
+            stored_value: None,
         }
     }
 
@@ -171,6 +284,35 @@ impl Property {
         self.arguments.values()
     }
 
+    pub(crate) fn default_value(&self) -> Option<&serde_json::Value> {
+        self.default.as_ref()
+    }
+
+    /// Returns the name under which the property is stored in the database, which is the
+    /// property's own name unless the configuration gives it a database name
+    pub(crate) fn db_name(&self) -> &str {
+        self.db_name.as_deref().unwrap_or(&self.name)
+    }
+
+    pub(crate) fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Returns the code stored in the database for a value of an enumeration, if the value isn't
+    /// stored as its name
+    pub(crate) fn stored_value(&self) -> Option<&serde_json::Value> {
+        self.stored_value.as_ref()
+    }
+
+    pub(crate) fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the directives applied to the field, enforced when it is resolved
+    pub(crate) fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
     pub(crate) fn input_type_definition<'i>(&self, info: &'i Info) -> Result<&'i NodeType, Error> {
         self.arguments
             .get("input")
@@ -214,6 +356,19 @@ impl Property {
         &self.type_name
     }
 
+    /// Returns the name under which the lowercased value of the property is stored, if the
+    /// property is unique ignoring case
+    pub(crate) fn unique_shadow_name(&self) -> Option<String> {
+        self.unique_case_insensitive
+            .then(|| self.db_name().to_string() + "_lower")
+    }
+
+    /// Returns the stored name of the property that must be null on a node for the uniqueness of
+    /// this property to apply to it
+    pub(crate) fn unique_where_null(&self) -> Option<&str> {
+        self.unique_where_null.as_deref()
+    }
+
     pub(crate) fn validator(&self) -> Option<&String> {
         self.validator.as_ref()
     }
@@ -223,6 +378,46 @@ impl Property {
         self
     }
 
+    fn with_default(mut self, default: Option<&serde_json::Value>) -> Self {
+        self.default = default.cloned();
+        self
+    }
+
+    fn with_db_name(mut self, db_name: Option<&str>) -> Self {
+        self.db_name = db_name.map(|n| n.to_string());
+        self
+    }
+
+    fn with_unique_case_insensitive(mut self, unique_case_insensitive: bool) -> Self {
+        self.unique_case_insensitive = unique_case_insensitive;
+        self
+    }
+
+    fn with_unique_where_null(mut self, unique_where_null: Option<&str>) -> Self {
+        self.unique_where_null = unique_where_null.map(|n| n.to_string());
+        self
+    }
+
+    fn with_deprecated(mut self, deprecated: Option<&str>) -> Self {
+        self.deprecated = deprecated.map(|d| d.to_string());
+        self
+    }
+
+    fn with_stored_value(mut self, stored_value: Option<&serde_json::Value>) -> Self {
+        self.stored_value = stored_value.cloned();
+        self
+    }
+
+    fn with_description(mut self, description: Option<&str>) -> Self {
+        self.description = description.map(|d| d.to_string());
+        self
+    }
+
+    fn with_directives<'d>(mut self, directives: impl Iterator<Item = &'d Directive>) -> Self {
+        self.directives = directives.cloned().collect();
+        self
+    }
+
     fn with_hidden(mut self, hidden: bool) -> Self {
         self.hidden = hidden;
         self
@@ -289,8 +484,8 @@ fn generate_create_props(props: &[crate::engine::config::Property]) -> HashMap<S
             .with_required(false),
     );
 
-    // insert properties into hashmap
-    props.iter().for_each(|p| {
+    // insert properties into hashmap, leaving out counts, which are not stored
+    props.iter().filter(|p| p.count().is_none()).for_each(|p| {
         match &p.resolver() {
             None => {
                 hm.insert(
@@ -300,10 +495,15 @@ fn generate_create_props(props: &[crate::engine::config::Property]) -> HashMap<S
                         PropertyKind::Scalar,
                         p.type_name().to_string(),
                     )
-                    .with_required(p.required())
+                    .with_required(p.required() && p.default_value().is_none())
                     .with_hidden(!p.uses().create())
                     .with_list(p.list())
-                    .with_validator(p.validator().cloned()),
+                    .with_validator(p.validator().cloned())
+                    .with_default(p.default_value())
+                    .with_db_name(p.db_name())
+                    .with_unique_case_insensitive(p.unique_case_insensitive())
+                    .with_unique_where_null(unique_scope_db_name(props, p))
+                    .with_description(p.description()),
                 );
             }
             Some(r) => {
@@ -318,7 +518,8 @@ fn generate_create_props(props: &[crate::engine::config::Property]) -> HashMap<S
                     .with_hidden(!p.uses().create())
                     .with_list(p.list())
                     .with_resolver(r)
-                    .with_validator(p.validator().cloned()),
+                    .with_validator(p.validator().cloned())
+                    .with_description(p.description()),
                 );
             }
         };
@@ -327,13 +528,27 @@ fn generate_create_props(props: &[crate::engine::config::Property]) -> HashMap<S
     hm
 }
 
+/// Returns the stored name of the property that limits the uniqueness of a property, if any
+fn unique_scope_db_name<'a>(
+    props: &'a [crate::engine::config::Property],
+    p: &'a crate::engine::config::Property,
+) -> Option<&'a str> {
+    p.unique_where_null().map(|scope| {
+        props
+            .iter()
+            .find(|p2| p2.name() == scope)
+            .and_then(|p2| p2.db_name())
+            .unwrap_or(scope)
+    })
+}
+
 /// Takes a vector of WG Properties and returns a map of Property structs that
 /// represent the property fields in a graphql schema component for an object to be updated
 fn generate_update_props(props: &[crate::engine::config::Property]) -> HashMap<String, Property> {
     let mut hm = HashMap::new();
 
-    // insert properties into hashmap
-    props.iter().for_each(|p| {
+    // insert properties into hashmap, leaving out counts, which are not stored
+    props.iter().filter(|p| p.count().is_none()).for_each(|p| {
         match &p.resolver() {
             None => {
                 hm.insert(
@@ -346,7 +561,11 @@ fn generate_update_props(props: &[crate::engine::config::Property]) -> HashMap<S
                     .with_required(false)
                     .with_hidden(!p.uses().update())
                     .with_list(p.list())
-                    .with_validator(p.validator().cloned()),
+                    .with_validator(p.validator().cloned())
+                    .with_db_name(p.db_name())
+                    .with_unique_case_insensitive(p.unique_case_insensitive())
+                    .with_unique_where_null(unique_scope_db_name(props, p))
+                    .with_description(p.description()),
                 );
             }
             Some(r) => {
@@ -361,7 +580,8 @@ fn generate_update_props(props: &[crate::engine::config::Property]) -> HashMap<S
                     .with_hidden(!p.uses().update())
                     .with_list(p.list())
                     .with_resolver(r)
-                    .with_validator(p.validator().cloned()),
+                    .with_validator(p.validator().cloned())
+                    .with_description(p.description()),
                 );
             }
         };
@@ -383,8 +603,25 @@ fn generate_output_props(props: &[crate::engine::config::Property]) -> HashMap<S
 
     // insert properties into hashmap
     props.iter().for_each(|p| {
-        match &p.resolver() {
-            None => {
+        match (&p.resolver(), p.count()) {
+            (_, Some(rel_name)) => {
+                hm.insert(
+                    p.name().to_string(),
+                    Property::new(
+                        p.name().to_string(),
+                        PropertyKind::ComputedCount {
+                            rel_name: rel_name.to_string(),
+                        },
+                        p.type_name().to_string(),
+                    )
+                    .with_required(p.required())
+                    .with_hidden(!p.uses().output())
+                    .with_description(p.description())
+                    .with_deprecated(p.deprecated())
+                    .with_directives(p.directives()),
+                );
+            }
+            (None, None) => {
                 hm.insert(
                     p.name().to_string(),
                     Property::new(
@@ -395,10 +632,16 @@ fn generate_output_props(props: &[crate::engine::config::Property]) -> HashMap<S
                     .with_required(p.required())
                     .with_hidden(!p.uses().output())
                     .with_list(p.list())
-                    .with_validator(p.validator().cloned()),
+                    .with_arguments(generate_list_arguments(p))
+                    .with_validator(p.validator().cloned())
+                    .with_db_name(p.db_name())
+                    .with_unique_case_insensitive(p.unique_case_insensitive())
+                    .with_description(p.description())
+                    .with_deprecated(p.deprecated())
+                    .with_directives(p.directives()),
                 );
             }
-            Some(r) => {
+            (Some(r), None) => {
                 hm.insert(
                     p.name().to_string(),
                     Property::new(
@@ -406,11 +649,16 @@ fn generate_output_props(props: &[crate::engine::config::Property]) -> HashMap<S
                         PropertyKind::DynamicScalar,
                         p.type_name().to_string(),
                     )
-                    .with_required(p.required())
+                    // A failing custom resolver nulls only its own field, so that sibling
+                    // fields still return data rather than the error propagating upward.
+                    .with_required(false)
                     .with_hidden(!p.uses().output())
                     .with_list(p.list())
                     .with_resolver(r)
-                    .with_validator(p.validator().cloned()),
+                    .with_validator(p.validator().cloned())
+                    .with_description(p.description())
+                    .with_deprecated(p.deprecated())
+                    .with_directives(p.directives()),
                 );
             }
         };
@@ -419,6 +667,46 @@ fn generate_output_props(props: &[crate::engine::config::Property]) -> HashMap<S
     hm
 }
 
+/// Takes a WG Property and returns the arguments of its output field. A scalar list property
+/// takes optional `first` and `skip` arguments that slice the list returned to the client, and a
+/// list of String or ID values also takes a `contains` argument that keeps only the values
+/// containing the given text.
+fn generate_list_arguments(p: &crate::engine::config::Property) -> HashMap<String, Argument> {
+    let mut arguments = HashMap::new();
+    if !p.list() {
+        return arguments;
+    }
+
+    arguments.insert(
+        "first".to_string(),
+        Argument::new(
+            "first".to_string(),
+            ArgumentKind::Optional,
+            "Int".to_string(),
+        ),
+    );
+    arguments.insert(
+        "skip".to_string(),
+        Argument::new(
+            "skip".to_string(),
+            ArgumentKind::Optional,
+            "Int".to_string(),
+        ),
+    );
+    if p.type_name() == "String" || p.type_name() == "ID" {
+        arguments.insert(
+            "contains".to_string(),
+            Argument::new(
+                "contains".to_string(),
+                ArgumentKind::Optional,
+                "String".to_string(),
+            ),
+        );
+    }
+
+    arguments
+}
+
 /// Takes a vector of WG Properties and returns a map of the appropriate
 /// query input which in turn is a nested map with the different type of
 /// comparison operations available to the scalar type.
@@ -435,6 +723,12 @@ fn generate_query_props(
         ),
     );
     for p in props.iter() {
+        // Binary values can't be compared, and counts aren't stored, so they're left out of query
+        // inputs
+        if p.type_name() == "Base64" || p.count().is_some() {
+            continue;
+        }
+
         query_props.insert(
             p.name().to_string(),
             Property::new(
@@ -444,27 +738,26 @@ fn generate_query_props(
                     "ID" => PropertyKind::ScalarComp,
                     "String" => PropertyKind::ScalarComp,
                     "Int" => PropertyKind::ScalarComp,
+                    "Long" => PropertyKind::ScalarComp,
                     "Float" => PropertyKind::ScalarComp,
-                    _ => {
-                        return Err(Error::SchemaItemNotFound {
-                            name: p.type_name().to_string(),
-                        })
-                    }
+                    "Duration" => PropertyKind::ScalarComp,
+                    // Enums are matched by equality, like booleans
+                    _ => PropertyKind::Scalar,
                 },
                 match p.type_name() {
                     "Boolean" => "Boolean".to_string(),
                     "ID" => fmt_string_query_input_name(),
                     "String" => fmt_string_query_input_name(),
                     "Int" => fmt_int_query_input_name(),
+                    "Long" => fmt_long_query_input_name(),
                     "Float" => fmt_float_query_input_name(),
-                    _ => {
-                        return Err(Error::SchemaItemNotFound {
-                            name: p.type_name().to_string(),
-                        })
-                    }
+                    "Duration" => fmt_duration_query_input_name(),
+                    _ => p.type_name().to_string(),
                 },
             )
-            .with_hidden(!p.uses().query()),
+            .with_hidden(!p.uses().query())
+            .with_db_name(p.db_name())
+            .with_description(p.description()),
         );
     }
     Ok(query_props)
@@ -518,27 +811,125 @@ fn generate_node_object(t: &Type) -> NodeType {
 
         let mut p = Property::new(
             r.name().to_string(),
-            match r.resolver() {
-                None => PropertyKind::Rel {
+            match (r.resolver(), r.query()) {
+                (None, None) => PropertyKind::Rel {
+                    rel_name: r.name().to_string(),
+                },
+                (Some(_), _) => PropertyKind::DynamicRel {
                     rel_name: r.name().to_string(),
                 },
-                Some(_) => PropertyKind::DynamicRel {
+                (None, Some(query)) => PropertyKind::ComputedRel {
                     rel_name: r.name().to_string(),
+                    query: query.to_string(),
                 },
             },
             fmt_rel_object_name(t, r),
         )
         .with_list(r.list())
-        .with_arguments(arguments);
+        .with_required(r.required() && r.resolver().is_none())
+        .with_description(r.description())
+        .with_deprecated(r.deprecated());
+
+        if r.query().is_none() {
+            p = p.with_arguments(arguments);
+        }
 
         if let Some(resolver) = r.resolver() {
             p = p.with_resolver(resolver);
         }
 
         props.insert(r.name().to_string(), p);
+
+        // Relationships stored in the database can also be aggregated
+        if r.resolver().is_none() && r.query().is_none() {
+            props.insert(
+                fmt_rel_aggregate_field_name(r),
+                Property::new(
+                    fmt_rel_aggregate_field_name(r),
+                    PropertyKind::RelAggregate {
+                        rel_name: r.name().to_string(),
+                    },
+                    fmt_rel_aggregate_name(t, r),
+                )
+                .with_required(true),
+            );
+
+            // The number of relationships can be read without reading the relationships
+            let mut count_arguments = HashMap::new();
+            count_arguments.insert(
+                "input".to_string(),
+                Argument::new(
+                    "input".to_string(),
+                    ArgumentKind::Optional,
+                    fmt_rel_query_input_name(t, r),
+                ),
+            );
+            let count_description = format!(
+                "The number of {} relationships matching the input, counted without reading them",
+                r.name()
+            );
+            props.insert(
+                fmt_rel_count_field_name(r),
+                Property::new(
+                    fmt_rel_count_field_name(r),
+                    PropertyKind::RelCount {
+                        rel_name: r.name().to_string(),
+                    },
+                    "Int".to_string(),
+                )
+                .with_required(true)
+                .with_arguments(count_arguments)
+                .with_description(Some(&count_description)),
+            );
+
+            // Relationships to more than one type of node can be grouped by destination type
+            if r.nodes().count() > 1 {
+                props.insert(
+                    fmt_rel_by_type_field_name(r),
+                    Property::new(
+                        fmt_rel_by_type_field_name(r),
+                        PropertyKind::RelByType {
+                            rel_name: r.name().to_string(),
+                        },
+                        fmt_rel_by_type_name(t, r),
+                    )
+                    .with_required(true),
+                );
+            }
+        }
+    });
+
+    // The entity tag of the node, for HTTP caching, unless a prop of the same name shadows it
+    props.entry("_etag".to_string()).or_insert_with(|| {
+        Property::new(
+            "_etag".to_string(),
+            PropertyKind::Etag,
+            "String".to_string(),
+        )
+        .with_required(true)
+        .with_description(Some(
+            "A hash of the node's properties, which changes whenever one of them does",
+        ))
+    });
+
+    // The properties changed by an update, unless a prop of the same name shadows it
+    props.entry("_changes".to_string()).or_insert_with(|| {
+        Property::new(
+            "_changes".to_string(),
+            PropertyKind::Changes,
+            fmt_field_change_name(),
+        )
+        .with_list(true)
+        .with_description(Some(
+            "The properties changed by the update mutation that returned the node, if selected",
+        ))
     });
 
-    NodeType::new(t.name().to_string(), TypeKind::Object, props)
+    let mut nt = NodeType::new(t.name().to_string(), TypeKind::Object, props);
+    nt.default_sort = t.default_sort().cloned().collect();
+    nt.description = t.description().map(|d| d.to_string());
+    nt.directives = t.directives().cloned().collect();
+    nt
 }
 
 /// Takes a WG type and returns the name of the corresponding GqlNodeQueryInput
@@ -570,7 +961,8 @@ fn generate_node_query_input(t: &Type) -> Result<NodeType, Error> {
                 r.name().to_string(),
                 PropertyKind::Input,
                 fmt_rel_query_input_name(t, r),
-            ), //.with_list(r.list()),
+            )
+            .with_description(r.description()), //.with_list(r.list()),
         );
     });
     Ok(NodeType::new(
@@ -580,6 +972,39 @@ fn generate_node_query_input(t: &Type) -> Result<NodeType, Error> {
     ))
 }
 
+/// Takes a WG type and the configuration, and returns the fields of the type's
+/// GqlNodeQueryInput that filter nodes of the type by the source nodes of relationships to them.
+/// Each relationship that has an inverse and that may end at the type contributes one field,
+/// named by the inverse, that takes the query input of the relationship's source type.
+///
+/// Ex:
+/// input BugQueryInput {
+///     project: ProjectQueryInput
+/// }
+fn generate_node_inverse_query_props(t: &Type, c: &Configuration) -> HashMap<String, Property> {
+    c.types()
+        .flat_map(|st| {
+            st.rels()
+                .filter(|r| r.nodes().any(|n| n == t.name()))
+                .filter_map(move |r| {
+                    r.inverse().map(|inverse| {
+                        (
+                            inverse.to_string(),
+                            Property::new(
+                                inverse.to_string(),
+                                PropertyKind::InverseRel {
+                                    src_label: st.name().to_string(),
+                                    rel_name: r.name().to_string(),
+                                },
+                                fmt_node_query_input_name(st),
+                            ),
+                        )
+                    })
+                })
+        })
+        .collect()
+}
+
 /// Takes a WG type and returns the name of the corresponding GqlNodeCreateMutationInput
 fn fmt_node_create_mutation_input_name(t: &Type) -> String {
     t.name().to_string() + "CreateMutationInput"
@@ -601,7 +1026,8 @@ fn fmt_node_create_mutation_input_name(t: &Type) -> String {
 fn generate_node_create_mutation_input(t: &Type) -> NodeType {
     let mut props = generate_create_props(t.props_as_slice());
 
-    t.rels().for_each(|r| {
+    // computed rels are read from a stored query, so they can't be written
+    t.rels().filter(|r| r.query().is_none()).for_each(|r| {
         props.insert(
             r.name().to_string(),
             Property::new(
@@ -609,7 +1035,8 @@ fn generate_node_create_mutation_input(t: &Type) -> NodeType {
                 PropertyKind::Input,
                 fmt_rel_create_mutation_input_name(t, r),
             )
-            .with_list(r.list()),
+            .with_list(r.list())
+            .with_description(r.description()),
         );
     });
 
@@ -620,6 +1047,53 @@ fn generate_node_create_mutation_input(t: &Type) -> NodeType {
     )
 }
 
+/// Takes a WG type and returns the name of the corresponding GqlNodeCreateBatchInput
+fn fmt_node_create_batch_input_name(t: &Type) -> String {
+    t.name().to_string() + "CreateBatchInput"
+}
+
+/// Takes a WG type and returns a NodeType representing a GqlNodeCreateBatchInput
+///
+/// Format:
+/// input GqlNodeCreateBatchInput {
+///     nodes: [<GqlNodeCreateMutationInput>!]!
+///     batchSize: Int = 100
+/// }
+///
+/// Ex:
+/// input ProjectCreateBatchInput {
+///     nodes: [ProjectCreateMutationInput!]!
+///     batchSize: Int = 100
+/// }
+fn generate_node_create_batch_input(t: &Type) -> NodeType {
+    let mut props = HashMap::new();
+    props.insert(
+        "nodes".to_string(),
+        Property::new(
+            "nodes".to_string(),
+            PropertyKind::Input,
+            fmt_node_create_mutation_input_name(t),
+        )
+        .with_required(true)
+        .with_list(true)
+        .with_description(Some("The nodes to create, in order.")),
+    );
+    props.insert(
+        "batchSize".to_string(),
+        Property::new(
+            "batchSize".to_string(),
+            PropertyKind::Scalar,
+            "Int".to_string(),
+        )
+        .with_default(Some(&serde_json::json!(DEFAULT_BATCH_SIZE)))
+        .with_description(Some(
+            "The number of nodes created in each transaction. Each batch is committed before the next begins.",
+        )),
+    );
+
+    NodeType::new(fmt_node_create_batch_input_name(t), TypeKind::Input, props)
+}
+
 /// Takes a WG type and returns the name of the corresponding GqlNodeCreateMutationInput
 fn fmt_node_update_mutation_input_name(t: &Type) -> String {
     t.name().to_string() + "UpdateMutationInput"
@@ -642,7 +1116,8 @@ fn fmt_node_update_mutation_input_name(t: &Type) -> String {
 fn generate_node_update_mutation_input(t: &Type) -> NodeType {
     let mut props = generate_update_props(t.props_as_slice());
 
-    t.rels().for_each(|r| {
+    // computed rels are read from a stored query, so they can't be written
+    t.rels().filter(|r| r.query().is_none()).for_each(|r| {
         props.insert(
             r.name().to_string(),
             Property::new(
@@ -650,7 +1125,8 @@ fn generate_node_update_mutation_input(t: &Type) -> NodeType {
                 PropertyKind::Input,
                 fmt_rel_change_input_name(t, r),
             )
-            .with_list(r.list()),
+            .with_list(r.list())
+            .with_description(r.description()),
         );
     });
 
@@ -797,7 +1273,7 @@ fn fmt_node_delete_mutation_input_name(t: &Type) -> String {
 /// }
 fn generate_node_delete_mutation_input(t: &Type) -> NodeType {
     let mut props = HashMap::new();
-    t.rels().for_each(|r| {
+    t.rels().filter(|r| r.query().is_none()).for_each(|r| {
         props.insert(
             r.name().to_string(),
             Property::new(
@@ -825,11 +1301,19 @@ fn fmt_node_options_name(t: &Type) -> String {
 /// Format:
 /// input GqlNodeOptions {
 ///     sort: [GqlSort]
+///     distinct: Boolean
+///     profile: Boolean
+///     allowUnfilteredUpdate: Boolean
+///     allowUnfilteredRead: Boolean
 /// }
 ///
 /// Ex:
 /// input ProjectOptions {
 ///     sort: [ProjectSort]
+///     distinct: Boolean
+///     profile: Boolean
+///     allowUnfilteredUpdate: Boolean
+///     allowUnfilteredRead: Boolean
 /// }
 fn generate_node_options(t: &Type) -> Result<NodeType, Error> {
     let mut props = HashMap::new();
@@ -844,6 +1328,42 @@ fn generate_node_options(t: &Type) -> Result<NodeType, Error> {
         .with_list(true)
         .with_required(false),
     );
+    props.insert(
+        "distinct".to_string(),
+        Property::new(
+            "distinct".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
+    props.insert(
+        "profile".to_string(),
+        Property::new(
+            "profile".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
+    props.insert(
+        "allowUnfilteredUpdate".to_string(),
+        Property::new(
+            "allowUnfilteredUpdate".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
+    props.insert(
+        "allowUnfilteredRead".to_string(),
+        Property::new(
+            "allowUnfilteredRead".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
 
     Ok(NodeType::new(
         fmt_node_options_name(t),
@@ -852,6 +1372,52 @@ fn generate_node_options(t: &Type) -> Result<NodeType, Error> {
     ))
 }
 
+/// Takes a WG type and returns the name of the corresponding GqlNodeUpdateOptions
+fn fmt_node_update_options_name(t: &Type) -> String {
+    t.name().to_string() + "UpdateOptions"
+}
+
+/// Takes a WG type and returns a NodeType representing a GqlNodeUpdateOptions, the options of a
+/// node update mutation, which are those of a GqlNodeOptions and `continueOnError`
+///
+/// Format:
+/// input GqlNodeUpdateOptions {
+///     sort: [GqlSort]
+///     distinct: Boolean
+///     profile: Boolean
+///     allowUnfilteredUpdate: Boolean
+///     allowUnfilteredRead: Boolean
+///     continueOnError: Boolean
+/// }
+///
+/// Ex:
+/// input ProjectUpdateOptions {
+///     sort: [ProjectSort]
+///     distinct: Boolean
+///     profile: Boolean
+///     allowUnfilteredUpdate: Boolean
+///     allowUnfilteredRead: Boolean
+///     continueOnError: Boolean
+/// }
+fn generate_node_update_options(t: &Type) -> Result<NodeType, Error> {
+    let mut nt = generate_node_options(t)?;
+    nt.type_name = fmt_node_update_options_name(t);
+    nt.props.insert(
+        "continueOnError".to_string(),
+        Property::new(
+            "continueOnError".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false)
+        .with_description(Some(
+            "Updates each matched node in a transaction of its own, so that a failed update doesn't roll back the others.",
+        )),
+    );
+
+    Ok(nt)
+}
+
 /// Takes a WG type and returns the name of the corersponding GqlNodeSort
 fn fmt_node_sort_name(t: &Type) -> String {
     t.name().to_string() + "Sort"
@@ -917,7 +1483,7 @@ fn generate_node_order_by(t: &Type) -> Result<NodeType, Error> {
     let mut hm = t
         .props_as_slice()
         .iter()
-        .filter(|p| p.resolver().is_none())
+        .filter(|p| p.resolver().is_none() && p.count().is_none())
         .map(|p| {
             Ok((
                 p.name().to_string(),
@@ -983,6 +1549,40 @@ fn generate_node_read_endpoint(t: &Type) -> Property {
     .with_arguments(arguments)
 }
 
+/// Takes a WG type and returns the name of the corresponding GqlNodeExistsEndpoint
+fn fmt_node_exists_endpoint_name(t: &Type) -> String {
+    t.name().to_string() + "Exists"
+}
+
+/// Takes a WG type and returns a NodeType representing a GqlNodeExistsEndpoint
+///
+/// Format:
+/// GqlNodeExistsEndpoint(input: <GqlNodeQueryInput>): Boolean!
+///
+/// Ex:
+/// ProjectExists(input: ProjectQueryInput): Boolean!
+fn generate_node_exists_endpoint(t: &Type) -> Property {
+    let mut arguments = HashMap::new();
+    arguments.insert(
+        "input".to_string(),
+        Argument::new(
+            "input".to_string(),
+            ArgumentKind::Optional,
+            fmt_node_query_input_name(t),
+        ),
+    );
+
+    Property::new(
+        fmt_node_exists_endpoint_name(t),
+        PropertyKind::NodeExistsQuery {
+            label: t.name().to_string(),
+        },
+        "Boolean".to_string(),
+    )
+    .with_required(true)
+    .with_arguments(arguments)
+}
+
 /// Takes a WG type and returns the name of the corresponding GqlNodeCreateEndpoint
 fn fmt_node_create_endpoint_name(t: &Type) -> String {
     t.name().to_string() + "Create"
@@ -1023,6 +1623,47 @@ fn generate_node_create_endpoint(t: &Type) -> Property {
     .with_arguments(arguments)
 }
 
+/// Takes a WG type and returns the name of the corresponding GqlNodeCreateBatchEndpoint
+fn fmt_node_create_batch_endpoint_name(t: &Type) -> String {
+    t.name().to_string() + "CreateBatch"
+}
+
+/// Takes a WG type and returns a NodeType representing a GqlNodeCreateBatchEndpoint
+///
+/// Format:
+/// GqlNodeCreateBatchEndpoint (input: <GqlNodeCreateBatchInput>): [<Node>]
+///
+/// Ex:
+/// ProjectCreateBatch (input: ProjectCreateBatchInput): [Project]
+fn generate_node_create_batch_endpoint(t: &Type) -> Property {
+    let mut arguments = HashMap::new();
+    arguments.insert(
+        "input".to_string(),
+        Argument::new(
+            "input".to_string(),
+            ArgumentKind::Required,
+            fmt_node_create_batch_input_name(t),
+        ),
+    );
+
+    arguments.insert(
+        "options".to_string(),
+        Argument::new(
+            "options".to_string(),
+            ArgumentKind::Optional,
+            fmt_node_options_name(t),
+        ),
+    );
+
+    Property::new(
+        fmt_node_create_batch_endpoint_name(t),
+        PropertyKind::NodeCreateBatchMutation,
+        t.name().to_string(),
+    )
+    .with_list(true)
+    .with_arguments(arguments)
+}
+
 /// Takes a WG type and returns the name of the corresponding GqlNodeCreateEndpoint
 fn fmt_node_update_endpoint_name(t: &Type) -> String {
     t.name().to_string() + "Update"
@@ -1051,7 +1692,7 @@ fn generate_node_update_endpoint(t: &Type) -> Property {
         Argument::new(
             "options".to_string(),
             ArgumentKind::Optional,
-            fmt_node_options_name(t),
+            fmt_node_update_options_name(t),
         ),
     );
 
@@ -1064,6 +1705,77 @@ fn generate_node_update_endpoint(t: &Type) -> Property {
     .with_arguments(arguments)
 }
 
+/// Takes a WG type and returns the name of the corresponding GqlNodeUpdateBulkResult
+fn fmt_node_update_bulk_result_name(t: &Type) -> String {
+    t.name().to_string() + "UpdateBulkResult"
+}
+
+/// Takes a WG type and returns a NodeType representing a GqlNodeUpdateBulkResult, the outcome of
+/// a bulk node update
+///
+/// Format:
+/// type GqlNodeUpdateBulkResult {
+///     succeeded: [<Node>!]!
+///     failed: [BatchItemError!]!
+/// }
+///
+/// Ex:
+/// type ProjectUpdateBulkResult {
+///     succeeded: [Project!]!
+///     failed: [BatchItemError!]!
+/// }
+fn generate_node_update_bulk_result(t: &Type) -> NodeType {
+    NodeType::new(
+        fmt_node_update_bulk_result_name(t),
+        TypeKind::Object,
+        bulk_result_props(
+            t.name().to_string(),
+            PropertyKind::BulkSucceeded,
+            "The nodes that were updated.",
+        ),
+    )
+}
+
+/// Takes a WG type and returns the name of the corresponding GqlNodeUpdateBulkEndpoint
+fn fmt_node_update_bulk_endpoint_name(t: &Type) -> String {
+    t.name().to_string() + "UpdateBulk"
+}
+
+/// Takes a WG type and returns a NodeType representing a GqlNodeUpdateBulkEndpoint
+///
+/// Format:
+/// GqlNodeUpdateBulkEndpoint (input: <GqlNodeUpdateInput>, options: <GqlNodeUpdateOptions>): <GqlNodeUpdateBulkResult>
+///
+/// Ex:
+/// ProjectUpdateBulk (input: ProjectUpdateInput, options: ProjectUpdateOptions): ProjectUpdateBulkResult
+fn generate_node_update_bulk_endpoint(t: &Type) -> Property {
+    let mut arguments = HashMap::new();
+    arguments.insert(
+        "input".to_string(),
+        Argument::new(
+            "input".to_string(),
+            ArgumentKind::Required,
+            fmt_node_update_input_name(t),
+        ),
+    );
+
+    arguments.insert(
+        "options".to_string(),
+        Argument::new(
+            "options".to_string(),
+            ArgumentKind::Optional,
+            fmt_node_update_options_name(t),
+        ),
+    );
+
+    Property::new(
+        fmt_node_update_bulk_endpoint_name(t),
+        PropertyKind::NodeUpdateBulkMutation,
+        fmt_node_update_bulk_result_name(t),
+    )
+    .with_arguments(arguments)
+}
+
 /// Takes a WG type and returns the name of the corresponding GqlNodeDeleteEndpoint
 fn fmt_node_delete_endpoint_name(t: &Type) -> String {
     t.name().to_string() + "Delete"
@@ -1129,36 +1841,187 @@ fn fmt_rel_name(r: &Relationship) -> String {
 /// }
 ///
 /// Ex:
-/// type ProjectOwnerRel {
-///     id: ID
-///     since: String
-///     dst: ProjectOwnerNodesUnion
-///     src: Project
+/// type ProjectOwnerRel {
+///     id: ID
+///     since: String
+///     dst: ProjectOwnerNodesUnion
+///     src: Project
+/// }
+fn generate_rel_object(t: &Type, r: &Relationship) -> NodeType {
+    let mut props = generate_output_props(r.props_as_slice());
+
+    props.insert(
+        "src".to_string(),
+        Property::new(
+            "src".to_string(),
+            PropertyKind::Object,
+            t.name().to_string(),
+        )
+        .with_required(true),
+    );
+
+    props.insert(
+        "dst".to_string(),
+        Property::new(
+            "dst".to_string(),
+            PropertyKind::Union,
+            fmt_rel_nodes_union_name(t, r),
+        )
+        .with_required(true),
+    );
+
+    let mut nt = NodeType::new(fmt_rel_object_name(t, r), TypeKind::Rel, props);
+    nt.symmetric = r.symmetric();
+    nt.description = r.description().map(|d| d.to_string());
+    nt
+}
+
+/// Takes a WG rel and returns the name of the node object field that aggregates the rel
+fn fmt_rel_aggregate_field_name(r: &Relationship) -> String {
+    r.name().to_string() + "Aggregate"
+}
+
+/// Takes a WG rel and returns the name of the node object field that counts the rel
+fn fmt_rel_count_field_name(r: &Relationship) -> String {
+    r.name().to_string() + "Count"
+}
+
+/// Takes a WG type and rel and returns the name of the corresponding GqlRelAggregate
+fn fmt_rel_aggregate_name(t: &Type, r: &Relationship) -> String {
+    fmt_rel_object_name(t, r) + "Aggregate"
+}
+
+/// Takes a WG Type and Rel and returns a NodeType representing a GqlRelAggregate, which holds the
+/// number of relationships and the aggregates of each numeric relationship property
+///
+/// Format:
+/// type GqlRelAggregate {
+///     count: Int!
+///     prop[n]: <Scalar>Aggregate
+/// }
+///
+/// Ex:
+/// type TaskWorkLogRelAggregate {
+///     count: Int!
+///     hours: FloatAggregate
+/// }
+fn generate_rel_aggregate(t: &Type, r: &Relationship) -> NodeType {
+    let mut props = HashMap::new();
+
+    props.insert(
+        "count".to_string(),
+        Property::new("count".to_string(), PropertyKind::Scalar, "Int".to_string())
+            .with_required(true),
+    );
+
+    // Only single numeric values stored in the database can be aggregated
+    r.props_as_slice()
+        .iter()
+        .filter(|p| {
+            ["Int", "Long", "Float"].contains(&p.type_name())
+                && !p.list()
+                && p.resolver().is_none()
+                && p.count().is_none()
+        })
+        .for_each(|p| {
+            props.insert(
+                p.name().to_string(),
+                Property::new(
+                    p.name().to_string(),
+                    PropertyKind::Aggregate,
+                    fmt_scalar_aggregate_name(p.type_name()),
+                )
+                .with_hidden(!p.uses().output())
+                .with_db_name(p.db_name()),
+            );
+        });
+
+    NodeType::new(fmt_rel_aggregate_name(t, r), TypeKind::Object, props)
+}
+
+/// Takes a WG rel and returns the name of the node object field that groups the rel by
+/// destination type
+fn fmt_rel_by_type_field_name(r: &Relationship) -> String {
+    r.name().to_string() + "ByType"
+}
+
+/// Takes a WG type and rel and returns the name of the corresponding GqlRelByType
+fn fmt_rel_by_type_name(t: &Type, r: &Relationship) -> String {
+    fmt_rel_object_name(t, r) + "ByType"
+}
+
+/// Takes a WG Type and Rel and returns a NodeType representing a GqlRelByType, which holds the
+/// relationships to each type of destination node in a separate list, paginated on its own
+///
+/// Format:
+/// type GqlRelByType {
+///     <Node[n]>(input: <GqlRelQueryInput>, options: <GqlRelOptions>, first: Int, skip: Int):
+///         [<GqlRelObject>!]!
+/// }
+///
+/// Ex:
+/// type ProjectIssuesRelByType {
+///     Bug(input: ProjectIssuesQueryInput, options: ProjectIssuesOptions, first: Int, skip: Int):
+///         [ProjectIssuesRel!]!
+///     Feature(input: ProjectIssuesQueryInput, options: ProjectIssuesOptions, first: Int,
+///         skip: Int): [ProjectIssuesRel!]!
 /// }
-fn generate_rel_object(t: &Type, r: &Relationship) -> NodeType {
-    let mut props = generate_output_props(r.props_as_slice());
-
-    props.insert(
-        "src".to_string(),
-        Property::new(
-            "src".to_string(),
-            PropertyKind::Object,
-            t.name().to_string(),
-        )
-        .with_required(true),
+fn generate_rel_by_type(t: &Type, r: &Relationship) -> NodeType {
+    let mut arguments = HashMap::new();
+    arguments.insert(
+        "input".to_string(),
+        Argument::new(
+            "input".to_string(),
+            ArgumentKind::Optional,
+            fmt_rel_query_input_name(t, r),
+        ),
     );
-
-    props.insert(
-        "dst".to_string(),
-        Property::new(
-            "dst".to_string(),
-            PropertyKind::Union,
-            fmt_rel_nodes_union_name(t, r),
-        )
-        .with_required(true),
+    arguments.insert(
+        "options".to_string(),
+        Argument::new(
+            "options".to_string(),
+            ArgumentKind::Optional,
+            fmt_rel_options_name(t, r),
+        ),
+    );
+    arguments.insert(
+        "first".to_string(),
+        Argument::new(
+            "first".to_string(),
+            ArgumentKind::Optional,
+            "Int".to_string(),
+        ),
     );
+    arguments.insert(
+        "skip".to_string(),
+        Argument::new(
+            "skip".to_string(),
+            ArgumentKind::Optional,
+            "Int".to_string(),
+        ),
+    );
+
+    let props = r
+        .nodes()
+        .map(|n| {
+            (
+                n.to_string(),
+                Property::new(
+                    n.to_string(),
+                    PropertyKind::RelGroup {
+                        rel_name: r.name().to_string(),
+                        dst_label: n.to_string(),
+                    },
+                    fmt_rel_object_name(t, r),
+                )
+                .with_list(true)
+                .with_required(true)
+                .with_arguments(arguments.clone()),
+            )
+        })
+        .collect();
 
-    NodeType::new(fmt_rel_object_name(t, r), TypeKind::Rel, props)
+    NodeType::new(fmt_rel_by_type_name(t, r), TypeKind::Object, props)
 }
 
 /// Takes a WG type and rel and returns the name of the corresponding GqlRelNodesUnion
@@ -1231,11 +2094,9 @@ fn generate_rel_query_input(t: &Type, r: &Relationship) -> Result<NodeType, Erro
             fmt_rel_dst_query_input_name(t, r),
         ),
     );
-    Ok(NodeType::new(
-        fmt_rel_query_input_name(t, r),
-        TypeKind::Input,
-        props,
-    ))
+    let mut nt = NodeType::new(fmt_rel_query_input_name(t, r), TypeKind::Input, props);
+    nt.symmetric = r.symmetric();
+    Ok(nt)
 }
 
 /// Takes a WG type and rel and returns the name of the corresponding GqlRelCreateMutationInput
@@ -1355,8 +2216,26 @@ fn fmt_rel_update_mutation_input_name(t: &Type, r: &Relationship) -> String {
 ///     src: ProjectOwnerSrcUpdateMutationInput
 ///     dst: ProjectOwnerDstUpdateMutationInput
 /// }
+///
+/// Rels with numeric props also have an INCREMENT input, and rels with list props a PUSH input.
 fn generate_rel_update_mutation_input(t: &Type, r: &Relationship) -> NodeType {
     let mut props = generate_update_props(r.props_as_slice());
+    if let Some(increment) = generate_rel_increment_input(t, r) {
+        props.insert(
+            "INCREMENT".to_string(),
+            Property::new(
+                "INCREMENT".to_string(),
+                PropertyKind::Input,
+                increment.type_name,
+            ),
+        );
+    }
+    if let Some(push) = generate_rel_push_input(t, r) {
+        props.insert(
+            "PUSH".to_string(),
+            Property::new("PUSH".to_string(), PropertyKind::Input, push.type_name),
+        );
+    }
     props.insert(
         "src".to_string(),
         Property::new(
@@ -1373,11 +2252,96 @@ fn generate_rel_update_mutation_input(t: &Type, r: &Relationship) -> NodeType {
             fmt_rel_dst_update_mutation_input_name(t, r),
         ),
     );
-    NodeType::new(
+    let mut nt = NodeType::new(
         fmt_rel_update_mutation_input_name(t, r),
         TypeKind::Input,
         props,
-    )
+    );
+    nt.position = r.position().map(|p| p.to_string());
+    nt
+}
+
+/// Takes a WG type and rel and returns the name of the corresponding GqlRelIncrementInput
+fn fmt_rel_increment_input_name(t: &Type, r: &Relationship) -> String {
+    t.name().to_string()
+        + &*((r.name().to_string().to_title_case())
+            .split_whitespace()
+            .collect::<String>())
+        + "IncrementInput"
+}
+
+/// Takes a WG Type and Rel and returns a NodeType representing a GqlRelIncrementInput, holding
+/// the amount to add to each of the rel's single numeric props, or None if the rel has no
+/// numeric props that can be updated
+///
+/// Format:
+/// input GqlRelIncrementInput {
+///     prop[n]: <Int | Long | Float>
+/// }
+///
+/// Ex:
+/// input ProjectWorkLogIncrementInput {
+///     hours: Float
+/// }
+fn generate_rel_increment_input(t: &Type, r: &Relationship) -> Option<NodeType> {
+    let props = generate_update_props(r.props_as_slice())
+        .into_iter()
+        .filter(|(_, p)| {
+            p.kind == PropertyKind::Scalar
+                && !p.hidden
+                && !p.list
+                && ["Int", "Long", "Float"].contains(&p.type_name())
+        })
+        .map(|(k, p)| (k, p.with_validator(None)))
+        .collect::<HashMap<String, Property>>();
+    if props.is_empty() {
+        None
+    } else {
+        Some(NodeType::new(
+            fmt_rel_increment_input_name(t, r),
+            TypeKind::Input,
+            props,
+        ))
+    }
+}
+
+/// Takes a WG type and rel and returns the name of the corresponding GqlRelPushInput
+fn fmt_rel_push_input_name(t: &Type, r: &Relationship) -> String {
+    t.name().to_string()
+        + &*((r.name().to_string().to_title_case())
+            .split_whitespace()
+            .collect::<String>())
+        + "PushInput"
+}
+
+/// Takes a WG Type and Rel and returns a NodeType representing a GqlRelPushInput, holding the
+/// values to append to each of the rel's list props, or None if the rel has no list props that
+/// can be updated
+///
+/// Format:
+/// input GqlRelPushInput {
+///     prop[n]: [<Scalar>]
+/// }
+///
+/// Ex:
+/// input ProjectWorkLogPushInput {
+///     notes: [String!]
+/// }
+fn generate_rel_push_input(t: &Type, r: &Relationship) -> Option<NodeType> {
+    let props = generate_update_props(r.props_as_slice())
+        .into_iter()
+        .filter(|(_, p)| p.kind == PropertyKind::Scalar && !p.hidden && p.list)
+        .map(|(k, p)| (k, p.with_validator(None)))
+        .collect::<HashMap<String, Property>>();
+    if props.is_empty() {
+        None
+    } else {
+        Some(NodeType::new(
+            fmt_rel_push_input_name(t, r),
+            TypeKind::Input,
+            props,
+        ))
+    }
 }
 
 /// Takes a WG type and rel and returns the name of the corresponding GqlRelSrcUpdateMutationInput
@@ -1579,12 +2543,14 @@ fn fmt_rel_create_input_name(t: &Type, r: &Relationship) -> String {
 /// input GqlRelCreateInput {
 ///     MATCH: <GqlNodeQueryInput>
 ///     CREATE: <GqlRelCreateMutationInput>
+///     ifDstExists: Boolean
 /// }
 ///
 /// Ex:
 /// input ProjectOwnerCreateInput   {
 ///     MATCH: ProjectQueryInput
 ///     CREATE: ProjectOwnerCreateMutationInput
+///     ifDstExists: Boolean
 /// }
 fn generate_rel_create_input(t: &Type, r: &Relationship) -> NodeType {
     let mut props = HashMap::new();
@@ -1605,6 +2571,14 @@ fn generate_rel_create_input(t: &Type, r: &Relationship) -> NodeType {
         )
         .with_list(r.list()),
     );
+    props.insert(
+        "ifDstExists".to_string(),
+        Property::new(
+            "ifDstExists".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        ),
+    );
     NodeType::new(fmt_rel_create_input_name(t, r), TypeKind::Input, props)
 }
 
@@ -1795,11 +2769,17 @@ fn fmt_rel_options_name(t: &Type, r: &Relationship) -> String {
 /// Format:
 /// input GqlRelOptions {
 ///     sort: [GqlRelSort]
+///     profile: Boolean
+///     allowUnfilteredUpdate: Boolean
+///     allowUnfilteredRead: Boolean
 /// }
 ///
 /// Ex:
 /// input ProjectCommitOptions {
 ///     sort: [ProjectCommitSort]
+///     profile: Boolean
+///     allowUnfilteredUpdate: Boolean
+///     allowUnfilteredRead: Boolean
 /// }
 fn generate_rel_options(t: &Type, r: &Relationship) -> Result<NodeType, Error> {
     let mut props = HashMap::new();
@@ -1813,6 +2793,33 @@ fn generate_rel_options(t: &Type, r: &Relationship) -> Result<NodeType, Error> {
         .with_list(true)
         .with_required(false),
     );
+    props.insert(
+        "profile".to_string(),
+        Property::new(
+            "profile".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
+    props.insert(
+        "allowUnfilteredUpdate".to_string(),
+        Property::new(
+            "allowUnfilteredUpdate".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
+    props.insert(
+        "allowUnfilteredRead".to_string(),
+        Property::new(
+            "allowUnfilteredRead".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
 
     Ok(NodeType::new(
         fmt_rel_options_name(t, r),
@@ -1821,6 +2828,68 @@ fn generate_rel_options(t: &Type, r: &Relationship) -> Result<NodeType, Error> {
     ))
 }
 
+/// Takes a WG type and rel and returns the name of the corresponding GqlRelCreateOptions
+fn fmt_rel_create_options_name(t: &Type, r: &Relationship) -> String {
+    t.name().to_string()
+        + &*((&r.name().to_string().to_title_case())
+            .split_whitespace()
+            .collect::<String>())
+        + "CreateOptions"
+}
+
+/// Takes a WG type and rel and returns a NodeType representing a GqlRelCreateOptions, the options
+/// of a relationship create mutation
+///
+/// Format:
+/// input GqlRelCreateOptions {
+///     sort: [GqlRelSort]
+///     profile: Boolean
+///     continueOnError: Boolean
+/// }
+///
+/// Ex:
+/// input ProjectCommitCreateOptions {
+///     sort: [ProjectCommitSort]
+///     profile: Boolean
+///     continueOnError: Boolean
+/// }
+fn generate_rel_create_options(t: &Type, r: &Relationship) -> NodeType {
+    let mut props = HashMap::new();
+    props.insert(
+        "sort".to_string(),
+        Property::new(
+            "sort".to_string(),
+            PropertyKind::Input,
+            fmt_rel_sort_name(t, r),
+        )
+        .with_list(true)
+        .with_required(false),
+    );
+    props.insert(
+        "profile".to_string(),
+        Property::new(
+            "profile".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false),
+    );
+    props.insert(
+        "continueOnError".to_string(),
+        Property::new(
+            "continueOnError".to_string(),
+            PropertyKind::Scalar,
+            "Boolean".to_string(),
+        )
+        .with_required(false)
+        .with_description(Some(
+            "Creates each item of the CREATE list in a transaction of its own, so that a failed item doesn't roll back the others.",
+        )),
+    );
+
+    NodeType::new(fmt_rel_create_options_name(t, r), TypeKind::Input, props)
+}
+
 /// Takes a WG type and returns the name of the corresponding GqlNodeQueryInput
 fn fmt_rel_sort_name(t: &Type, r: &Relationship) -> String {
     t.name().to_string()
@@ -1911,7 +2980,7 @@ fn generate_rel_order_by(t: &Type, r: &Relationship, dsts: &[&Type]) -> Result<N
         .flat_map(|dst| {
             dst.props_as_slice()
                 .iter()
-                .filter(|p| p.resolver().is_none())
+                .filter(|p| p.resolver().is_none() && p.count().is_none())
                 .map(|p| {
                     Ok((
                         "dst:".to_string() + p.name(),
@@ -2018,7 +3087,7 @@ fn generate_rel_create_endpoint(t: &Type, r: &Relationship) -> Property {
         Argument::new(
             "options".to_string(),
             ArgumentKind::Optional,
-            fmt_rel_options_name(t, r),
+            fmt_rel_create_options_name(t, r),
         ),
     );
 
@@ -2034,6 +3103,83 @@ fn generate_rel_create_endpoint(t: &Type, r: &Relationship) -> Property {
     .with_arguments(arguments)
 }
 
+/// Takes a WG type and rel and returns the name of the corresponding GqlRelCreateBulkResult
+fn fmt_rel_create_bulk_result_name(t: &Type, r: &Relationship) -> String {
+    t.name().to_string()
+        + &*((&r.name().to_string().to_title_case())
+            .split_whitespace()
+            .collect::<String>())
+        + "CreateBulkResult"
+}
+
+/// Takes a WG type and rel and returns a NodeType representing a GqlRelCreateBulkResult, the
+/// outcome of a bulk relationship create
+///
+/// Format:
+/// type GqlRelCreateBulkResult {
+///     succeeded: [<GqlRelObject>!]!
+///     failed: [BatchItemError!]!
+/// }
+///
+/// Ex:
+/// type ProjectOwnerCreateBulkResult {
+///     succeeded: [ProjectOwnerRel!]!
+///     failed: [BatchItemError!]!
+/// }
+fn generate_rel_create_bulk_result(t: &Type, r: &Relationship) -> NodeType {
+    NodeType::new(
+        fmt_rel_create_bulk_result_name(t, r),
+        TypeKind::Object,
+        bulk_result_props(
+            fmt_rel_object_name(t, r),
+            PropertyKind::RelBulkSucceeded,
+            "The relationships that were created.",
+        ),
+    )
+}
+
+/// Takes a WG type and rel and returns the name of the corresponding GqlRelCreateBulkEndpoint
+fn fmt_rel_create_bulk_endpoint_name(t: &Type, r: &Relationship) -> String {
+    fmt_rel_create_endpoint_name(t, r) + "Bulk"
+}
+
+/// Takes a WG Type and Rel and returns a NodeType representing a GqlRelCreateBulkEndpoint
+///
+/// Format:
+/// GqlRelCreateBulkEndpoint (input: <GqlRelCreateInput>, options: <GqlRelCreateOptions>): <GqlRelCreateBulkResult>
+///
+/// Ex:
+/// ProjectOwnerCreateBulk(input: ProjectOwnerCreateInput, options: ProjectOwnerCreateOptions): ProjectOwnerCreateBulkResult
+fn generate_rel_create_bulk_endpoint(t: &Type, r: &Relationship) -> Property {
+    let mut arguments = HashMap::new();
+    arguments.insert(
+        "input".to_string(),
+        Argument::new(
+            "input".to_string(),
+            ArgumentKind::Required,
+            fmt_rel_create_input_name(t, r),
+        ),
+    );
+    arguments.insert(
+        "options".to_string(),
+        Argument::new(
+            "options".to_string(),
+            ArgumentKind::Optional,
+            fmt_rel_create_options_name(t, r),
+        ),
+    );
+
+    Property::new(
+        fmt_rel_create_bulk_endpoint_name(t, r),
+        PropertyKind::RelCreateBulkMutation {
+            src_label: fmt_node_object_name(t),
+            rel_name: fmt_rel_name(r),
+        },
+        fmt_rel_create_bulk_result_name(t, r),
+    )
+    .with_arguments(arguments)
+}
+
 /// Takes a WG type and rel and returns the name of the corresponding GqlRelUpdateEndpoint
 fn fmt_rel_update_endpoint_name(t: &Type, r: &Relationship) -> String {
     t.name().to_string()
@@ -2184,6 +3330,7 @@ fn generate_custom_endpoint(e: &Endpoint) -> Property {
     .with_required(e.output().required())
     .with_list(e.output().list())
     .with_arguments(arguments)
+    .with_description(e.description())
 }
 
 fn generate_custom_endpoint_input(t: &Type) -> NodeType {
@@ -2196,10 +3343,13 @@ fn generate_custom_endpoint_input(t: &Type) -> NodeType {
                 PropertyKind::Input,
                 fmt_rel_query_input_name(t, r),
             )
-            .with_list(r.list()),
+            .with_list(r.list())
+            .with_description(r.description()),
         );
     });
-    NodeType::new(t.name().to_string(), TypeKind::Input, props)
+    let mut nt = NodeType::new(t.name().to_string(), TypeKind::Input, props);
+    nt.description = t.description().map(|d| d.to_string());
+    nt
 }
 
 fn generate_static_version_query() -> Property {
@@ -2248,32 +3398,94 @@ fn string_array_input(name: &str) -> Property {
         arguments: HashMap::new(),
         resolver: None,
         validator: None,
+        default: None,
+        db_name: None,
+        unique_case_insensitive: false,
+        unique_where_null: None,
+        description: None,
+        deprecated: None,
+        directives: Vec::new(),
+        stored_value: None,
     }
 }
 
-fn fmt_int_query_input_name() -> String {
-    "IntQueryInput".to_string()
+fn fmt_int_query_input_name() -> String {
+    "IntQueryInput".to_string()
+}
+
+fn int_query_input() -> NodeType {
+    NodeType::new(
+        fmt_int_query_input_name(),
+        TypeKind::Input,
+        hashmap! {
+            "EQ".to_string() => int_input("EQ"),
+            "NOTEQ".to_string() => int_input("NOTEQ"),
+            "IN".to_string() => int_input("IN"),
+            "NOTIN".to_string() => int_input("NOTIN"),
+            "GT".to_string() => int_input("GT"),
+            "GTE".to_string() => int_input("GTE"),
+            "LT".to_string() => int_input("LT"),
+            "LTE".to_string() => int_input("LTE"),
+        },
+    )
+}
+
+fn int_input(name: &str) -> Property {
+    Property::new(name.to_string(), PropertyKind::Scalar, "Int".to_string())
+}
+
+pub(crate) fn fmt_long_query_input_name() -> String {
+    "LongQueryInput".to_string()
+}
+
+fn long_query_input() -> NodeType {
+    NodeType::new(
+        fmt_long_query_input_name(),
+        TypeKind::Input,
+        hashmap! {
+            "EQ".to_string() => long_input("EQ"),
+            "NOTEQ".to_string() => long_input("NOTEQ"),
+            "IN".to_string() => long_input("IN").with_list(true),
+            "NOTIN".to_string() => long_input("NOTIN").with_list(true),
+            "GT".to_string() => long_input("GT"),
+            "GTE".to_string() => long_input("GTE"),
+            "LT".to_string() => long_input("LT"),
+            "LTE".to_string() => long_input("LTE"),
+        },
+    )
+}
+
+fn long_input(name: &str) -> Property {
+    Property::new(name.to_string(), PropertyKind::Scalar, "Long".to_string())
 }
 
-fn int_query_input() -> NodeType {
+pub(crate) fn fmt_duration_query_input_name() -> String {
+    "DurationQueryInput".to_string()
+}
+
+fn duration_query_input() -> NodeType {
     NodeType::new(
-        fmt_int_query_input_name(),
+        fmt_duration_query_input_name(),
         TypeKind::Input,
         hashmap! {
-            "EQ".to_string() => int_input("EQ"),
-            "NOTEQ".to_string() => int_input("NOTEQ"),
-            "IN".to_string() => int_input("IN"),
-            "NOTIN".to_string() => int_input("NOTIN"),
-            "GT".to_string() => int_input("GT"),
-            "GTE".to_string() => int_input("GTE"),
-            "LT".to_string() => int_input("LT"),
-            "LTE".to_string() => int_input("LTE"),
+            "EQ".to_string() => duration_input("EQ"),
+            "NOTEQ".to_string() => duration_input("NOTEQ"),
+            "IN".to_string() => duration_input("IN").with_list(true),
+            "NOTIN".to_string() => duration_input("NOTIN").with_list(true),
+            "GT".to_string() => duration_input("GT"),
+            "GTE".to_string() => duration_input("GTE"),
+            "LT".to_string() => duration_input("LT"),
+            "LTE".to_string() => duration_input("LTE"),
         },
     )
 }
 
-fn int_input(name: &str) -> Property {
-    Property::new(name.to_string(), PropertyKind::Scalar, "Int".to_string())
+fn duration_input(name: &str) -> Property {
+    Property::new(
+        name.to_string(),
+        PropertyKind::Scalar,
+        "Duration".to_string(),
+    )
 }
 
 fn fmt_float_query_input_name() -> String {
@@ -2301,6 +3513,90 @@ fn float_input(name: &str) -> Property {
     Property::new(name.to_string(), PropertyKind::Scalar, "Float".to_string())
 }
 
+/// Takes the name of a numeric scalar and returns the name of the object holding the aggregates of
+/// its values
+fn fmt_scalar_aggregate_name(type_name: &str) -> String {
+    type_name.to_string() + "Aggregate"
+}
+
+/// Takes the name of a numeric scalar and returns a NodeType representing the aggregates of its
+/// values. The average of integers is not necessarily an integer, so it is always a Float.
+fn scalar_aggregate(type_name: &str) -> NodeType {
+    NodeType::new(
+        fmt_scalar_aggregate_name(type_name),
+        TypeKind::Object,
+        hashmap! {
+            "sum".to_string() => Property::new("sum".to_string(), PropertyKind::Scalar, type_name.to_string()),
+            "avg".to_string() => float_input("avg"),
+            "min".to_string() => Property::new("min".to_string(), PropertyKind::Scalar, type_name.to_string()),
+            "max".to_string() => Property::new("max".to_string(), PropertyKind::Scalar, type_name.to_string()),
+        },
+    )
+}
+
+fn fmt_field_change_name() -> String {
+    "FieldChange".to_string()
+}
+
+/// Returns a NodeType representing a change to a property of a node by an update, with the values
+/// before and after the update encoded as JSON
+fn field_change() -> NodeType {
+    NodeType::new(
+        fmt_field_change_name(),
+        TypeKind::Object,
+        hashmap! {
+            "field".to_string() => string_input("field").with_required(true),
+            "before".to_string() => string_input("before"),
+            "after".to_string() => string_input("after"),
+        },
+    )
+}
+
+fn fmt_batch_item_error_name() -> String {
+    "BatchItemError".to_string()
+}
+
+/// Returns a NodeType representing an item of a bulk mutation that failed, with its index in the
+/// mutation's items, the id of the node it applied to, if it had one, and the error message
+fn batch_item_error() -> NodeType {
+    NodeType::new(
+        fmt_batch_item_error_name(),
+        TypeKind::Object,
+        hashmap! {
+            "index".to_string() => int_input("index").with_required(true),
+            "id".to_string() => Property::new("id".to_string(), PropertyKind::Scalar, "ID".to_string()),
+            "error".to_string() => string_input("error").with_required(true),
+        },
+    )
+}
+
+/// Returns the properties of the result of a bulk mutation, the items of type `type_name` that
+/// succeeded and the items that failed
+fn bulk_result_props(
+    type_name: String,
+    succeeded_kind: PropertyKind,
+    succeeded_description: &str,
+) -> HashMap<String, Property> {
+    hashmap! {
+        "succeeded".to_string() => Property::new(
+            "succeeded".to_string(),
+            succeeded_kind,
+            type_name,
+        )
+        .with_required(true)
+        .with_list(true)
+        .with_description(Some(succeeded_description)),
+        "failed".to_string() => Property::new(
+            "failed".to_string(),
+            PropertyKind::BulkFailed,
+            fmt_batch_item_error_name(),
+        )
+        .with_required(true)
+        .with_list(true)
+        .with_description(Some("The items that failed, in the order they were applied.")),
+    }
+}
+
 fn fmt_direction_enum_name() -> String {
     "DirectionEnum".to_string()
 }
@@ -2316,6 +3612,41 @@ fn direction_enum() -> NodeType {
     )
 }
 
+/// Takes a WG enum declaration and returns a NodeType representing a GraphQL enum
+///
+/// Format:
+/// enum Name {
+///     valueA
+///     valueB
+/// }
+fn generate_enum(e: &EnumType) -> NodeType {
+    let props = e
+        .values()
+        .map(|v| {
+            (
+                v.to_string(),
+                string_input(v)
+                    .with_deprecated(e.deprecated(v))
+                    .with_stored_value(e.stored_value(v)),
+            )
+        })
+        .collect();
+
+    NodeType::new(e.name().to_string(), TypeKind::Enum, props)
+}
+
+/// Takes a WG enum declaration and returns the name of the scalar type in which the codes stored
+/// for its values are presented, `Int` for integer codes and `String` otherwise
+fn fmt_enum_value_type_name(e: &EnumType) -> &'static str {
+    if e.values()
+        .all(|v| e.stored_value(v).is_some_and(|s| s.is_i64()))
+    {
+        "Int"
+    } else {
+        "String"
+    }
+}
+
 /// Takes a WG config and returns a map of graphql schema components for model
 /// types, custom endpoints, and associated endpoint types
 pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeType>, Error> {
@@ -2329,12 +3660,60 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
     // NumberQueryInput
     nthm.insert(fmt_int_query_input_name(), int_query_input());
 
+    // LongQueryInput
+    nthm.insert(fmt_long_query_input_name(), long_query_input());
+
     // FloatQueryInput
     nthm.insert(fmt_float_query_input_name(), float_query_input());
 
+    // DurationQueryInput
+    nthm.insert(fmt_duration_query_input_name(), duration_query_input());
+
+    // IntAggregate, LongAggregate, and FloatAggregate
+    for type_name in ["Int", "Long", "Float"] {
+        nthm.insert(
+            fmt_scalar_aggregate_name(type_name),
+            scalar_aggregate(type_name),
+        );
+    }
+
+    // FieldChange
+    nthm.insert(fmt_field_change_name(), field_change());
+
+    // BatchItemError
+    nthm.insert(fmt_batch_item_error_name(), batch_item_error());
+
     // DirectionEnum
     nthm.insert(fmt_direction_enum_name(), direction_enum());
 
+    // enums declared in the configuration
+    for e in c.enums() {
+        nthm.insert(e.name().to_string(), generate_enum(e));
+    }
+
+    // properties must be a GraphQL scalar or a declared enum
+    let enum_names: HashSet<&str> = c.enums().map(|e| e.name()).collect();
+    c.types()
+        .flat_map(|t| {
+            t.props_as_slice()
+                .iter()
+                .chain(t.rels().flat_map(|r| r.props_as_slice().iter()))
+        })
+        .try_for_each(|p| {
+            if [
+                "Boolean", "ID", "String", "Int", "Long", "Float", "Base64", "Duration",
+            ]
+            .contains(&p.type_name())
+                || enum_names.contains(p.type_name())
+            {
+                Ok(())
+            } else {
+                Err(Error::SchemaItemNotFound {
+                    name: p.type_name().to_string(),
+                })
+            }
+        })?;
+
     // generate graphql schema components for warpgrapher types
     for t in c.types() {
         // GqlNodeType
@@ -2342,7 +3721,10 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
         nthm.insert(node_type.type_name.to_string(), node_type);
 
         // GqlNodeQueryInput
-        let node_query_input = generate_node_query_input(t)?;
+        let mut node_query_input = generate_node_query_input(t)?;
+        node_query_input
+            .props
+            .extend(generate_node_inverse_query_props(t, c));
         nthm.insert(node_query_input.type_name.to_string(), node_query_input);
 
         let node_order_by = generate_node_order_by(t)?;
@@ -2351,6 +3733,19 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
         let node_options = generate_node_options(t)?;
         nthm.insert(node_options.type_name().to_string(), node_options);
 
+        let node_update_options = generate_node_update_options(t)?;
+        nthm.insert(
+            node_update_options.type_name().to_string(),
+            node_update_options,
+        );
+
+        // GqlNodeUpdateBulkResult
+        let node_update_bulk_result = generate_node_update_bulk_result(t);
+        nthm.insert(
+            node_update_bulk_result.type_name.to_string(),
+            node_update_bulk_result,
+        );
+
         let node_sort = generate_node_sort(t)?;
         nthm.insert(node_sort.type_name().to_string(), node_sort);
 
@@ -2361,6 +3756,13 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
             node_create_mutation_input,
         );
 
+        // GqlNodeCreateBatchInput
+        let node_create_batch_input = generate_node_create_batch_input(t);
+        nthm.insert(
+            node_create_batch_input.type_name.to_string(),
+            node_create_batch_input,
+        );
+
         // GqlNodeUpdateMutationInput
         let node_update_mutation_input = generate_node_update_mutation_input(t);
         nthm.insert(
@@ -2391,18 +3793,36 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
         if t.endpoints().read() {
             let read_endpoint = generate_node_read_endpoint(t);
             query_props.insert(read_endpoint.name().to_string(), read_endpoint);
+
+            // GqlNodeExistsEndpoint
+            let exists_endpoint = generate_node_exists_endpoint(t);
+            query_props.insert(exists_endpoint.name().to_string(), exists_endpoint);
         }
 
         // GqlNodeCreateEndpoint
         if t.endpoints().create() {
             let create_endpoint = generate_node_create_endpoint(t);
             mutation_props.insert(create_endpoint.name().to_string(), create_endpoint);
+
+            // GqlNodeCreateBatchEndpoint
+            let create_batch_endpoint = generate_node_create_batch_endpoint(t);
+            mutation_props.insert(
+                create_batch_endpoint.name().to_string(),
+                create_batch_endpoint,
+            );
         }
 
         // GqlNodeUpdateEndpoint
         if t.endpoints().update() {
             let update_endpoint = generate_node_update_endpoint(t);
             mutation_props.insert(update_endpoint.name().to_string(), update_endpoint);
+
+            // GqlNodeUpdateBulkEndpoint
+            let update_bulk_endpoint = generate_node_update_bulk_endpoint(t);
+            mutation_props.insert(
+                update_bulk_endpoint.name().to_string(),
+                update_bulk_endpoint,
+            );
         }
 
         // GqlNodeDeleteEndpoint
@@ -2416,6 +3836,18 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
             let rel_object = generate_rel_object(t, r);
             nthm.insert(rel_object.type_name.to_string(), rel_object);
 
+            // GqlRelAggregate
+            if r.resolver().is_none() && r.query().is_none() {
+                let rel_aggregate = generate_rel_aggregate(t, r);
+                nthm.insert(rel_aggregate.type_name.to_string(), rel_aggregate);
+
+                // GqlRelByType
+                if r.nodes().count() > 1 {
+                    let rel_by_type = generate_rel_by_type(t, r);
+                    nthm.insert(rel_by_type.type_name.to_string(), rel_by_type);
+                }
+            }
+
             // GqlRelNodesUnion
             let rel_nodes_union = generate_rel_nodes_union(t, r);
             nthm.insert(rel_nodes_union.type_name.to_string(), rel_nodes_union);
@@ -2428,6 +3860,17 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
             let rel_options = generate_rel_options(t, r)?;
             nthm.insert(rel_options.type_name.to_string(), rel_options);
 
+            // GqlRelCreateOptions
+            let rel_create_options = generate_rel_create_options(t, r);
+            nthm.insert(rel_create_options.type_name.to_string(), rel_create_options);
+
+            // GqlRelCreateBulkResult
+            let rel_create_bulk_result = generate_rel_create_bulk_result(t, r);
+            nthm.insert(
+                rel_create_bulk_result.type_name.to_string(),
+                rel_create_bulk_result,
+            );
+
             let rel_order_by = generate_rel_order_by(
                 t,
                 r,
@@ -2458,6 +3901,19 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
                 rel_update_mutation_input,
             );
 
+            // GqlRelIncrementInput
+            if let Some(rel_increment_input) = generate_rel_increment_input(t, r) {
+                nthm.insert(
+                    rel_increment_input.type_name.to_string(),
+                    rel_increment_input,
+                );
+            }
+
+            // GqlRelPushInput
+            if let Some(rel_push_input) = generate_rel_push_input(t, r) {
+                nthm.insert(rel_push_input.type_name.to_string(), rel_push_input);
+            }
+
             // GqlRelSrcUpdateMutationInput
             let rel_src_update_mutation_input = generate_rel_src_update_mutation_input(t, r);
             nthm.insert(
@@ -2520,28 +3976,35 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
             );
 
             // GqlRelReadEndpoint
-            // Temporarily filter out rels with custom resolvers, as the custom resolver only addresses recursive
+            // Temporarily filter out rels with custom resolvers or queries, as the custom resolver only addresses recursive
 
             // shape resolution through nodes, not the root level endpoints.
-            if r.endpoints().read() && r.resolver().is_none() {
+            if r.endpoints().read() && r.resolver().is_none() && r.query().is_none() {
                 let rel_read_endpoint = generate_rel_read_endpoint(t, r);
                 query_props.insert(rel_read_endpoint.name().to_string(), rel_read_endpoint);
             }
 
             // GqlRelCreateEndpoint
-            if r.endpoints().create() && r.resolver().is_none() {
+            if r.endpoints().create() && r.resolver().is_none() && r.query().is_none() {
                 let rel_create_endpoint = generate_rel_create_endpoint(t, r);
                 mutation_props.insert(rel_create_endpoint.name().to_string(), rel_create_endpoint);
+
+                // GqlRelCreateBulkEndpoint
+                let rel_create_bulk_endpoint = generate_rel_create_bulk_endpoint(t, r);
+                mutation_props.insert(
+                    rel_create_bulk_endpoint.name().to_string(),
+                    rel_create_bulk_endpoint,
+                );
             }
 
             // GqlRelUpdateEndpoint
-            if r.endpoints().update() && r.resolver().is_none() {
+            if r.endpoints().update() && r.resolver().is_none() && r.query().is_none() {
                 let rel_update_endpoint = generate_rel_update_endpoint(t, r);
                 mutation_props.insert(rel_update_endpoint.name().to_string(), rel_update_endpoint);
             }
 
             // GqlRelDelete Endpoint
-            if r.endpoints().delete() && r.resolver().is_none() {
+            if r.endpoints().delete() && r.resolver().is_none() && r.query().is_none() {
                 let rel_delete_endpoint = generate_rel_delete_endpoint(t, r);
                 mutation_props.insert(rel_delete_endpoint.name().to_string(), rel_delete_endpoint);
             }
@@ -2576,6 +4039,43 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
         }
     });
 
+    // custom endpoint outputs must be a GraphQL scalar, a declared enum, or a type in the schema
+    c.endpoints()
+        .try_for_each(|e| match e.output().type_def() {
+            TypeDef::Existing(name)
+                if !["ID", "Long", "Base64", "Duration"].contains(&name.as_str())
+                    && !nthm.contains_key(name) =>
+            {
+                Err(Error::SchemaItemNotFound {
+                    name: name.to_string(),
+                })
+            }
+            _ => Ok(()),
+        })?;
+
+    // props of custom endpoint input types must be a GraphQL scalar, a declared enum, or an input
+    // type in the schema, which may itself hold nested inputs
+    c.endpoints()
+        .try_for_each(|e| match e.input().map(|i| i.type_def()) {
+            Some(TypeDef::Custom(t)) => t
+                .props()
+                .try_for_each(|p| validate_endpoint_input_prop_type(&nthm, e, p.type_name())),
+            _ => Ok(()),
+        })?;
+
+    // enums serialized by their stored codes are presented as the scalar type of the codes
+    for e in c
+        .enums()
+        .filter(|e| e.serialization() == EnumSerialization::Value)
+    {
+        let scalar = fmt_enum_value_type_name(e);
+        nthm.values_mut()
+            .filter(|nt| matches!(nt.type_kind, TypeKind::Object | TypeKind::Rel))
+            .flat_map(|nt| nt.props.values_mut())
+            .filter(|p| p.type_name == e.name())
+            .for_each(|p| p.type_name = scalar.to_string());
+    }
+
     // static endpoints
     query_props.insert("_version".to_string(), generate_static_version_query());
 
@@ -2598,6 +4098,36 @@ pub(crate) fn generate_schema(c: &Configuration) -> Result<HashMap<String, NodeT
     Ok(nthm)
 }
 
+/// Takes the generated schema types, a custom endpoint, and the type name of a prop of the
+/// endpoint's custom input type, and returns an error unless the type can appear in a GraphQL input
+fn validate_endpoint_input_prop_type(
+    nthm: &HashMap<String, NodeType>,
+    e: &Endpoint,
+    type_name: &str,
+) -> Result<(), Error> {
+    if [
+        "Base64", "Boolean", "Duration", "Float", "ID", "Int", "Long", "String",
+    ]
+    .contains(&type_name)
+    {
+        return Ok(());
+    }
+
+    match nthm.get(type_name).map(|nt| nt.type_kind()) {
+        Some(TypeKind::Enum) | Some(TypeKind::Input) => Ok(()),
+        Some(_) => Err(Error::ConfigItemInvalid {
+            type_name: e.name().to_string(),
+            details: format!(
+                "the input prop type {} is not an input or enum type",
+                type_name
+            ),
+        }),
+        None => Err(Error::SchemaItemNotFound {
+            name: type_name.to_string(),
+        }),
+    }
+}
+
 /// Takes a Warpgrapher configuration and returns the Juniper RootNode for a
 /// GraphQL schema that matches the Warpgrapher configuration.
 ///
@@ -2645,6 +4175,98 @@ where
     })
 }
 
+/// Removes the fields of a request's variables that are not fields of the input types declared
+/// for the variables by the operation being run, so that a request from a client built against a
+/// slightly different schema is not rejected. Returns the path of each field removed, such as
+/// `$input.legacyName`. Fields written as literals in the query itself are left as they are.
+///
+/// Nothing is removed if the query cannot be parsed, or if the operation to run cannot be
+/// determined, as the request is rejected when it is executed in either case.
+pub(super) fn remove_unknown_input_fields<RequestCtx>(
+    root_node: &RootRef<RequestCtx>,
+    query: &str,
+    operation_name: Option<&str>,
+    variables: &mut InputValue,
+) -> Vec<String>
+where
+    RequestCtx: RequestContext,
+{
+    let document = match parse_document_source(query, &root_node.schema) {
+        Ok(document) => document,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut operations = document
+        .iter()
+        .filter_map(|d| match d {
+            Definition::Operation(op) => Some(&op.item),
+            Definition::Fragment(_) => None,
+        })
+        .filter(|op| {
+            operation_name.is_none() || op.name.as_ref().map(|n| n.item) == operation_name
+        });
+    let operation = match (operations.next(), operations.next()) {
+        (Some(operation), None) => operation,
+        _ => return Vec::new(),
+    };
+
+    let mut removed = Vec::new();
+    if let (Some(defs), InputValue::Object(fields)) = (&operation.variable_definitions, variables) {
+        for (name, def) in defs.item.items.iter() {
+            if let Some((_, value)) = fields.iter_mut().find(|(k, _)| k.item == name.item) {
+                remove_unknown_fields(
+                    &root_node.schema,
+                    def.var_type.item.innermost_name(),
+                    &mut value.item,
+                    &format!("${}", name.item),
+                    &mut removed,
+                );
+            }
+        }
+    }
+
+    removed
+}
+
+/// Removes the fields of `value` that are not fields of the input type named `type_name`, and
+/// then those of its nested input objects, adding the path of each field removed to `removed`
+fn remove_unknown_fields(
+    schema: &SchemaType<DefaultScalarValue>,
+    type_name: &str,
+    value: &mut InputValue,
+    path: &str,
+    removed: &mut Vec<String>,
+) {
+    match value {
+        InputValue::List(items) => items.iter_mut().for_each(|item| {
+            remove_unknown_fields(schema, type_name, &mut item.item, path, removed)
+        }),
+        InputValue::Object(fields) => {
+            if let Some(MetaType::InputObject(meta)) = schema.concrete_type_by_name(type_name) {
+                fields.retain(|(k, _)| {
+                    let known = meta.input_fields.iter().any(|f| f.name == k.item);
+                    if !known {
+                        removed.push(format!("{}.{}", path, k.item));
+                    }
+                    known
+                });
+                for (k, v) in fields.iter_mut() {
+                    if let Some(f) = meta.input_fields.iter().find(|f| f.name == k.item) {
+                        remove_unknown_fields(
+                            schema,
+                            f.arg_type.innermost_name(),
+                            &mut v.item,
+                            &format!("{}.{}", path, k.item),
+                            removed,
+                        );
+                    }
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
 pub(crate) fn type_name_variants(t: &Type) -> HashSet<String> {
     let mut hs = HashSet::new();
 
@@ -2657,6 +4279,9 @@ pub(crate) fn type_name_variants(t: &Type) -> HashSet<String> {
     hs.insert(fmt_node_delete_mutation_input_name(t));
     hs.insert(fmt_node_create_endpoint_name(t));
     hs.insert(fmt_node_update_endpoint_name(t));
+    hs.insert(fmt_node_update_options_name(t));
+    hs.insert(fmt_node_update_bulk_result_name(t));
+    hs.insert(fmt_node_update_bulk_endpoint_name(t));
     hs.insert(fmt_node_delete_endpoint_name(t));
 
     hs
@@ -2684,6 +4309,9 @@ pub(crate) fn rel_name_variants(t: &Type, r: &Relationship) -> HashSet<String> {
     hs.insert(fmt_rel_dst_delete_mutation_input_name(t, r));
     hs.insert(fmt_rel_read_endpoint_name(t, r));
     hs.insert(fmt_rel_create_endpoint_name(t, r));
+    hs.insert(fmt_rel_create_options_name(t, r));
+    hs.insert(fmt_rel_create_bulk_result_name(t, r));
+    hs.insert(fmt_rel_create_bulk_endpoint_name(t, r));
     hs.insert(fmt_rel_update_endpoint_name(t, r));
     hs.insert(fmt_rel_delete_endpoint_name(t, r));
 
@@ -2693,40 +4321,50 @@ pub(crate) fn rel_name_variants(t: &Type, r: &Relationship) -> HashSet<String> {
 #[cfg(test)]
 mod tests {
     use super::{
-        create_root_node, fmt_node_create_endpoint_name, fmt_node_create_mutation_input_name,
-        fmt_node_delete_endpoint_name, fmt_node_delete_input_name,
-        fmt_node_delete_mutation_input_name, fmt_node_input_name, fmt_node_object_name,
-        fmt_node_query_input_name, fmt_node_read_endpoint_name, fmt_node_update_endpoint_name,
-        fmt_node_update_input_name, fmt_node_update_mutation_input_name, fmt_rel_change_input_name,
-        fmt_rel_create_endpoint_name, fmt_rel_create_input_name,
-        fmt_rel_create_mutation_input_name, fmt_rel_delete_endpoint_name,
-        fmt_rel_delete_input_name, fmt_rel_dst_delete_mutation_input_name,
-        fmt_rel_dst_query_input_name, fmt_rel_dst_update_mutation_input_name,
-        fmt_rel_nodes_mutation_input_union_name, fmt_rel_nodes_union_name, fmt_rel_object_name,
-        fmt_rel_query_input_name, fmt_rel_read_endpoint_name,
-        fmt_rel_src_delete_mutation_input_name, fmt_rel_src_query_input_name,
-        fmt_rel_src_update_mutation_input_name, fmt_rel_update_endpoint_name,
-        fmt_rel_update_input_name, fmt_rel_update_mutation_input_name, generate_custom_endpoint,
+        create_root_node, fmt_node_create_batch_endpoint_name, fmt_node_create_endpoint_name,
+        fmt_node_create_mutation_input_name, fmt_node_delete_endpoint_name,
+        fmt_node_delete_input_name, fmt_node_delete_mutation_input_name, fmt_node_input_name,
+        fmt_node_object_name, fmt_node_query_input_name, fmt_node_read_endpoint_name,
+        fmt_node_update_bulk_endpoint_name, fmt_node_update_endpoint_name,
+        fmt_node_update_input_name, fmt_node_update_mutation_input_name, fmt_rel_aggregate_name,
+        fmt_rel_by_type_name, fmt_rel_change_input_name, fmt_rel_create_endpoint_name,
+        fmt_rel_create_input_name, fmt_rel_create_mutation_input_name,
+        fmt_rel_delete_endpoint_name, fmt_rel_delete_input_name,
+        fmt_rel_dst_delete_mutation_input_name, fmt_rel_dst_query_input_name,
+        fmt_rel_dst_update_mutation_input_name, fmt_rel_nodes_mutation_input_union_name,
+        fmt_rel_nodes_union_name, fmt_rel_object_name, fmt_rel_query_input_name,
+        fmt_rel_read_endpoint_name, fmt_rel_src_delete_mutation_input_name,
+        fmt_rel_src_query_input_name, fmt_rel_src_update_mutation_input_name,
+        fmt_rel_update_endpoint_name, fmt_rel_update_input_name,
+        fmt_rel_update_mutation_input_name, generate_custom_endpoint,
+        generate_node_create_batch_endpoint, generate_node_create_batch_input,
         generate_node_create_endpoint, generate_node_create_mutation_input,
         generate_node_delete_endpoint, generate_node_delete_input,
-        generate_node_delete_mutation_input, generate_node_input, generate_node_object,
-        generate_node_query_input, generate_node_read_endpoint, generate_node_update_endpoint,
-        generate_node_update_input, generate_node_update_mutation_input, generate_rel_change_input,
-        generate_rel_create_endpoint, generate_rel_create_input,
-        generate_rel_create_mutation_input, generate_rel_delete_endpoint,
-        generate_rel_delete_input, generate_rel_dst_delete_mutation_input,
-        generate_rel_dst_query_input, generate_rel_dst_update_mutation_input,
+        generate_node_delete_mutation_input, generate_node_exists_endpoint, generate_node_input,
+        generate_node_object, generate_node_query_input, generate_node_read_endpoint,
+        generate_node_update_bulk_endpoint, generate_node_update_bulk_result,
+        generate_node_update_endpoint, generate_node_update_input,
+        generate_node_update_mutation_input, generate_node_update_options, generate_rel_aggregate,
+        generate_rel_by_type, generate_rel_change_input, generate_rel_create_bulk_endpoint,
+        generate_rel_create_bulk_result, generate_rel_create_endpoint, generate_rel_create_input,
+        generate_rel_create_mutation_input, generate_rel_create_options,
+        generate_rel_delete_endpoint, generate_rel_delete_input,
+        generate_rel_dst_delete_mutation_input, generate_rel_dst_query_input,
+        generate_rel_dst_update_mutation_input, generate_rel_increment_input,
         generate_rel_nodes_mutation_input_union, generate_rel_nodes_union, generate_rel_object,
-        generate_rel_query_input, generate_rel_read_endpoint,
-        generate_rel_src_delete_mutation_input, generate_rel_src_update_mutation_input,
-        generate_rel_update_endpoint, generate_rel_update_input,
-        generate_rel_update_mutation_input, generate_schema, ArgumentKind, Info, NodeType,
-        Property, PropertyKind, TypeKind,
+        generate_rel_options, generate_rel_push_input, generate_rel_query_input,
+        generate_rel_read_endpoint, generate_rel_src_delete_mutation_input,
+        generate_rel_src_update_mutation_input, generate_rel_update_endpoint,
+        generate_rel_update_input, generate_rel_update_mutation_input, generate_schema,
+        ArgumentKind, Info, NodeType, Property, PropertyKind, TypeKind, DEFAULT_BATCH_SIZE,
     };
     use crate::engine::config::{
         mock_config, mock_endpoint_one, mock_endpoint_three, mock_endpoint_two,
-        mock_endpoints_filter, mock_project_config, mock_project_type,
+        mock_endpoints_filter, mock_project_config, mock_project_type, Configuration, DefaultSort,
+        Endpoint, EndpointClass, EndpointType, EndpointsFilter, EnumSerialization, EnumType,
+        GraphqlType, Relationship, Type, TypeDef, UsesFilter,
     };
+    use crate::Error;
     use std::collections::HashMap;
     use std::sync::Arc;
 
@@ -2786,13 +4424,34 @@ mod tests {
                 commits(input: ProjectCommitsQueryInput): ProjectCommitsRel
                 issues(input: ProjectIssuesQueryInput): ProjectIssuesRel
                 board(input: ProjectBoardQueryInput): ProjectBoardRel
+                ownerAggregate: ProjectOwnerRelAggregate!
+                commitsAggregate: ProjectCommitsRelAggregate!
+                issuesAggregate: ProjectIssuesRelAggregate!
+                boardAggregate: ProjectBoardRelAggregate!
+                ownerCount(input: ProjectOwnerQueryInput): Int!
+                commitsCount(input: ProjectCommitsQueryInput): Int!
+                issuesCount(input: ProjectIssuesQueryInput): Int!
+                boardCount(input: ProjectBoardQueryInput): Int!
+                issuesByType: ProjectIssuesRelByType!
+                boardByType: ProjectBoardRelByType!
+                _etag: String!
+                _changes: [FieldChange!]
             }
         */
         let project_type = mock_project_type();
         let project_node_object = generate_node_object(&project_type);
         assert!(project_node_object.type_name == "Project");
-        assert!(project_node_object.props.len() == 8);
+        assert!(project_node_object.props.len() == 20);
         assert!(project_node_object.type_kind == TypeKind::Object);
+        let project_etag = project_node_object.props.get("_etag").unwrap();
+        assert!(project_etag.kind == PropertyKind::Etag);
+        assert!(project_etag.type_name == "String");
+        assert!(project_etag.required);
+        let project_changes = project_node_object.props.get("_changes").unwrap();
+        assert!(project_changes.kind == PropertyKind::Changes);
+        assert!(project_changes.type_name == "FieldChange");
+        assert!(project_changes.list);
+        assert!(!project_changes.required);
         let project_id = project_node_object.props.get("id").unwrap();
         assert!(project_id.name == "id");
         assert!(project_id.kind == PropertyKind::Scalar);
@@ -2813,7 +4472,10 @@ mod tests {
         assert!(project_tags.type_name == "String");
         assert!(!project_tags.required);
         assert!(project_tags.list);
-        assert!(project_tags.arguments.is_empty());
+        assert_eq!(project_tags.arguments.len(), 3);
+        assert!(project_tags.arguments.contains_key("first"));
+        assert!(project_tags.arguments.contains_key("skip"));
+        assert!(project_tags.arguments.contains_key("contains"));
         let project_public = project_node_object.props.get("public").unwrap();
         assert!(project_public.name == "public");
         assert!(project_public.kind == PropertyKind::Scalar);
@@ -2836,6 +4498,41 @@ mod tests {
             assert!(input.kind == ArgumentKind::Optional);
             assert!(input.type_name == "ProjectOwnerQueryInput");
         }
+        let project_owner_aggregate = project_node_object.props.get("ownerAggregate").unwrap();
+        assert!(match &project_owner_aggregate.kind {
+            PropertyKind::RelAggregate { rel_name } => rel_name == "owner",
+            _ => false,
+        });
+        assert!(project_owner_aggregate.type_name == "ProjectOwnerRelAggregate");
+        assert!(project_owner_aggregate.required);
+        assert!(!project_owner_aggregate.list);
+        assert!(project_owner_aggregate.arguments.is_empty());
+        let project_issues_count = project_node_object.props.get("issuesCount").unwrap();
+        assert!(match &project_issues_count.kind {
+            PropertyKind::RelCount { rel_name } => rel_name == "issues",
+            _ => false,
+        });
+        assert!(project_issues_count.type_name == "Int");
+        assert!(project_issues_count.required);
+        assert!(!project_issues_count.list);
+        assert!(project_issues_count.description.is_some());
+        assert_eq!(project_issues_count.arguments.len(), 1);
+        if let Some(input) = project_issues_count.arguments.get("input") {
+            assert!(input.kind == ArgumentKind::Optional);
+            assert!(input.type_name == "ProjectIssuesQueryInput");
+        } else {
+            panic!("issuesCount has no input argument");
+        }
+        assert!(!project_node_object.props.contains_key("ownerByType"));
+        let project_issues_by_type = project_node_object.props.get("issuesByType").unwrap();
+        assert!(match &project_issues_by_type.kind {
+            PropertyKind::RelByType { rel_name } => rel_name == "issues",
+            _ => false,
+        });
+        assert!(project_issues_by_type.type_name == "ProjectIssuesRelByType");
+        assert!(project_issues_by_type.required);
+        assert!(!project_issues_by_type.list);
+        assert!(project_issues_by_type.arguments.is_empty());
         let project_board = project_node_object.props.get("board").unwrap();
         assert!(project_board.name == "board");
         assert!(match &project_board.kind {
@@ -2883,6 +4580,139 @@ mod tests {
         }
     }
 
+    /// Passes if a symmetric relationship marks its rel object and rel query input as symmetric
+    #[test]
+    fn test_generate_rel_symmetric() {
+        let user_type = Type::new(
+            "User".to_string(),
+            vec![],
+            vec![Relationship::new(
+                "friends".to_string(),
+                true,
+                vec!["User".to_string()],
+                vec![],
+                EndpointsFilter::all(),
+                None,
+            )
+            .with_symmetric(true)],
+            EndpointsFilter::all(),
+        );
+        let friends_rel = user_type.rels().next().unwrap();
+        assert!(generate_rel_object(&user_type, friends_rel).symmetric());
+        assert!(generate_rel_query_input(&user_type, friends_rel)
+            .unwrap()
+            .symmetric());
+
+        let project_type = mock_project_type();
+        let project_owner_rel = project_type.rels().find(|&r| r.name() == "owner").unwrap();
+        assert!(!generate_rel_object(&project_type, project_owner_rel).symmetric());
+    }
+
+    /// Passes if a required relationship generates a required rel property, and an optional
+    /// relationship generates an optional one
+    #[test]
+    fn test_generate_node_object_required_rel() {
+        let project_type = Type::new(
+            "Project".to_string(),
+            vec![],
+            vec![
+                Relationship::new(
+                    "owner".to_string(),
+                    false,
+                    vec!["User".to_string()],
+                    vec![],
+                    EndpointsFilter::all(),
+                    None,
+                )
+                .with_required(true),
+                Relationship::new(
+                    "reviewer".to_string(),
+                    false,
+                    vec!["User".to_string()],
+                    vec![],
+                    EndpointsFilter::all(),
+                    None,
+                ),
+            ],
+            EndpointsFilter::all(),
+        );
+        let project_node_object = generate_node_object(&project_type);
+
+        let owner = project_node_object.props.get("owner").unwrap();
+        assert!(owner.required());
+        assert!(!owner.list());
+
+        let reviewer = project_node_object.props.get("reviewer").unwrap();
+        assert!(!reviewer.required());
+        assert!(!reviewer.list());
+    }
+
+    /// Passes if a relationship with a query generates a computed rel property with no arguments
+    #[test]
+    fn test_generate_node_object_computed_rel() {
+        let project_type = Type::new(
+            "Project".to_string(),
+            vec![],
+            vec![Relationship::new(
+                "top_contributor".to_string(),
+                false,
+                vec!["User".to_string()],
+                vec![],
+                EndpointsFilter::all(),
+                None,
+            )
+            .with_query("MATCH (:Project { id: $src_id })<--(u:User) RETURN u".to_string())],
+            EndpointsFilter::all(),
+        );
+        let project_node_object = generate_node_object(&project_type);
+        let top_contributor = project_node_object.props.get("top_contributor").unwrap();
+        assert!(match &top_contributor.kind {
+            PropertyKind::ComputedRel { rel_name, query } =>
+                rel_name == "top_contributor" && query.contains("$src_id"),
+            _ => false,
+        });
+        assert!(top_contributor.type_name == "ProjectTopContributorRel");
+        assert!(top_contributor.arguments.is_empty());
+    }
+
+    /// Passes if a relationship with a query is left out of the node's create, update, and
+    /// delete mutation inputs
+    #[test]
+    fn test_generate_node_mutation_inputs_skip_computed_rel() {
+        let project_type = Type::new(
+            "Project".to_string(),
+            vec![],
+            vec![
+                Relationship::new(
+                    "owner".to_string(),
+                    false,
+                    vec!["User".to_string()],
+                    vec![],
+                    EndpointsFilter::all(),
+                    None,
+                ),
+                Relationship::new(
+                    "top_contributor".to_string(),
+                    false,
+                    vec!["User".to_string()],
+                    vec![],
+                    EndpointsFilter::all(),
+                    None,
+                )
+                .with_query("MATCH (:Project { id: $src_id })<--(u:User) RETURN u".to_string()),
+            ],
+            EndpointsFilter::all(),
+        );
+        for input in [
+            generate_node_create_mutation_input(&project_type),
+            generate_node_update_mutation_input(&project_type),
+            generate_node_delete_mutation_input(&project_type),
+        ] {
+            assert!(input.props.contains_key("owner"));
+            assert!(!input.props.contains_key("top_contributor"));
+        }
+    }
+
     /// Passes if the right schema elements are generated
     #[test]
     fn test_fmt_node_query_input_name() {
@@ -3310,6 +5140,33 @@ mod tests {
         }
     }
 
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_generate_node_exists_endpoint() {
+        /*
+            ProjectExists(input: ProjectQueryInput): Boolean!
+        */
+        let project_type = mock_project_type();
+        let project_exists_endpoint = generate_node_exists_endpoint(&project_type);
+        assert!(project_exists_endpoint.name == "ProjectExists");
+        assert!(
+            project_exists_endpoint.kind
+                == PropertyKind::NodeExistsQuery {
+                    label: "Project".to_string()
+                }
+        );
+        assert!(project_exists_endpoint.type_name == "Boolean");
+        assert!(project_exists_endpoint.required);
+        assert!(!project_exists_endpoint.list);
+        if let Some(input) = project_exists_endpoint.arguments.get("input") {
+            assert!(input.name == "input");
+            assert!(input.kind == ArgumentKind::Optional);
+            assert!(input.type_name == "ProjectQueryInput");
+        } else {
+            panic!("Expected input argument");
+        }
+    }
+
     /// Passes if the right schema elements are generated
     #[test]
     fn test_fmt_node_create_endpoint_name() {
@@ -3340,30 +5197,161 @@ mod tests {
 
     /// Passes if the right schema elements are generated
     #[test]
-    fn test_fmt_node_update_endpoint_name() {
+    fn test_generate_node_create_batch_endpoint() {
+        /*
+            ProjectCreateBatch(input: ProjectCreateBatchInput!, options: ProjectOptions): [Project]
+        */
+        let project_type = mock_project_type();
+        assert!(fmt_node_create_batch_endpoint_name(&project_type) == "ProjectCreateBatch");
+        let project_create_batch_endpoint = generate_node_create_batch_endpoint(&project_type);
+        assert!(project_create_batch_endpoint.name == "ProjectCreateBatch");
+        assert!(project_create_batch_endpoint.kind == PropertyKind::NodeCreateBatchMutation);
+        assert!(project_create_batch_endpoint.type_name == "Project");
+        assert!(!project_create_batch_endpoint.required);
+        assert!(project_create_batch_endpoint.list);
+        if let Some(input) = project_create_batch_endpoint.arguments.get("input") {
+            assert!(input.name == "input");
+            assert!(input.kind == ArgumentKind::Required);
+            assert!(input.type_name == "ProjectCreateBatchInput");
+        } else {
+            panic!("Expected input argument");
+        }
+    }
+
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_generate_node_create_batch_input() {
+        /*
+            input ProjectCreateBatchInput {
+                nodes: [ProjectCreateMutationInput!]!
+                batchSize: Int = 100
+            }
+        */
+        let project_type = mock_project_type();
+        let project_create_batch_input = generate_node_create_batch_input(&project_type);
+        assert!(project_create_batch_input.type_name == "ProjectCreateBatchInput");
+        assert!(project_create_batch_input.type_kind == TypeKind::Input);
+        assert!(project_create_batch_input.props.len() == 2);
+        let nodes = project_create_batch_input.props.get("nodes").unwrap();
+        assert!(nodes.kind == PropertyKind::Input);
+        assert!(nodes.type_name == "ProjectCreateMutationInput");
+        assert!(nodes.required);
+        assert!(nodes.list);
+        let batch_size = project_create_batch_input.props.get("batchSize").unwrap();
+        assert!(batch_size.kind == PropertyKind::Scalar);
+        assert!(batch_size.type_name == "Int");
+        assert!(!batch_size.required);
+        assert!(!batch_size.list);
+        assert_eq!(
+            Some(&serde_json::json!(DEFAULT_BATCH_SIZE)),
+            batch_size.default_value()
+        );
+    }
+
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_fmt_node_update_endpoint_name() {
+        let project_type = mock_project_type();
+        assert!(fmt_node_update_endpoint_name(&project_type) == "ProjectUpdate");
+    }
+
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_generate_node_update_endpoint() {
+        /*
+            ProjectUpdate(input: ProjectUpdateInput): [Project]
+        */
+        let project_type = mock_project_type();
+        let project_update_endpoint = generate_node_update_endpoint(&project_type);
+        assert!(project_update_endpoint.name == "ProjectUpdate");
+        assert!(project_update_endpoint.kind == PropertyKind::NodeUpdateMutation);
+        assert!(project_update_endpoint.type_name == "Project");
+        assert!(!project_update_endpoint.required);
+        assert!(project_update_endpoint.list);
+        assert!(project_update_endpoint.arguments.contains_key("input"));
+        if let Some(input) = project_update_endpoint.arguments.get("input") {
+            assert!(input.name == "input");
+            assert!(input.kind == ArgumentKind::Required);
+            assert!(input.type_name == "ProjectUpdateInput");
+        }
+        assert!(
+            project_update_endpoint
+                .arguments
+                .get("options")
+                .unwrap()
+                .type_name
+                == "ProjectUpdateOptions"
+        );
+    }
+
+    /// Passes if the update options add continueOnError to the node options
+    #[test]
+    fn test_generate_node_update_options() {
+        let project_type = mock_project_type();
+        let project_update_options = generate_node_update_options(&project_type).unwrap();
+        assert!(project_update_options.type_name == "ProjectUpdateOptions");
+        assert!(project_update_options.type_kind == TypeKind::Input);
+        let continue_on_error = project_update_options.props.get("continueOnError").unwrap();
+        assert!(continue_on_error.type_name == "Boolean");
+        assert!(!continue_on_error.required);
+        assert!(project_update_options.props.contains_key("sort"));
+        assert!(project_update_options
+            .props
+            .contains_key("allowUnfilteredUpdate"));
+    }
+
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_fmt_node_update_bulk_endpoint_name() {
         let project_type = mock_project_type();
-        assert!(fmt_node_update_endpoint_name(&project_type) == "ProjectUpdate");
+        assert!(fmt_node_update_bulk_endpoint_name(&project_type) == "ProjectUpdateBulk");
     }
 
     /// Passes if the right schema elements are generated
     #[test]
-    fn test_generate_node_update_endpoint() {
+    fn test_generate_node_update_bulk_endpoint() {
         /*
-            ProjectUpdate(input: ProjectUpdateInput): [Project]
+            ProjectUpdateBulk(input: ProjectUpdateInput, options: ProjectUpdateOptions): ProjectUpdateBulkResult
         */
         let project_type = mock_project_type();
-        let project_update_endpoint = generate_node_update_endpoint(&project_type);
-        assert!(project_update_endpoint.name == "ProjectUpdate");
-        assert!(project_update_endpoint.kind == PropertyKind::NodeUpdateMutation);
-        assert!(project_update_endpoint.type_name == "Project");
-        assert!(!project_update_endpoint.required);
-        assert!(project_update_endpoint.list);
-        assert!(project_update_endpoint.arguments.contains_key("input"));
-        if let Some(input) = project_update_endpoint.arguments.get("input") {
-            assert!(input.name == "input");
-            assert!(input.kind == ArgumentKind::Required);
-            assert!(input.type_name == "ProjectUpdateInput");
-        }
+        let project_update_bulk_endpoint = generate_node_update_bulk_endpoint(&project_type);
+        assert!(project_update_bulk_endpoint.name == "ProjectUpdateBulk");
+        assert!(project_update_bulk_endpoint.kind == PropertyKind::NodeUpdateBulkMutation);
+        assert!(project_update_bulk_endpoint.type_name == "ProjectUpdateBulkResult");
+        assert!(!project_update_bulk_endpoint.required);
+        assert!(!project_update_bulk_endpoint.list);
+        let input = project_update_bulk_endpoint.arguments.get("input").unwrap();
+        assert!(input.kind == ArgumentKind::Required);
+        assert!(input.type_name == "ProjectUpdateInput");
+        let options = project_update_bulk_endpoint
+            .arguments
+            .get("options")
+            .unwrap();
+        assert!(options.kind == ArgumentKind::Optional);
+        assert!(options.type_name == "ProjectUpdateOptions");
+    }
+
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_generate_node_update_bulk_result() {
+        /*
+            type ProjectUpdateBulkResult {
+                succeeded: [Project!]!
+                failed: [BatchItemError!]!
+            }
+        */
+        let project_type = mock_project_type();
+        let result = generate_node_update_bulk_result(&project_type);
+        assert!(result.type_name == "ProjectUpdateBulkResult");
+        assert!(result.type_kind == TypeKind::Object);
+        let succeeded = result.props.get("succeeded").unwrap();
+        assert!(succeeded.kind == PropertyKind::BulkSucceeded);
+        assert!(succeeded.type_name == "Project");
+        assert!(succeeded.required && succeeded.list);
+        let failed = result.props.get("failed").unwrap();
+        assert!(failed.kind == PropertyKind::BulkFailed);
+        assert!(failed.type_name == "BatchItemError");
+        assert!(failed.required && failed.list);
     }
 
     /// Passes if the right schema elements are generated
@@ -3405,6 +5393,121 @@ mod tests {
         assert!(fmt_rel_object_name(&project_type, project_owner_rel) == "ProjectOwnerRel");
     }
 
+    /// Passes if a rel aggregate holds the count and an aggregate of each single numeric prop
+    /// stored in the database, and leaves out other props
+    #[test]
+    fn test_generate_rel_aggregate() {
+        /*
+            type TaskWorkLogRelAggregate {
+                count: Int!
+                hours: FloatAggregate
+                minutes: IntAggregate
+            }
+        */
+        let numeric_prop = |name: &str, type_name: &str, list: bool| {
+            crate::engine::config::Property::new(
+                name.to_string(),
+                UsesFilter::all(),
+                type_name.to_string(),
+                false,
+                list,
+                None,
+                None,
+            )
+        };
+        let work_log = Relationship::new(
+            "work_log".to_string(),
+            true,
+            vec!["User".to_string()],
+            vec![
+                numeric_prop("hours", "Float", false),
+                numeric_prop("minutes", "Int", false),
+                numeric_prop("breaks", "Int", true),
+                numeric_prop("note", "String", false),
+            ],
+            EndpointsFilter::all(),
+            None,
+        );
+        let task_type = Type::new(
+            "Task".to_string(),
+            Vec::new(),
+            vec![work_log.clone()],
+            EndpointsFilter::all(),
+        );
+
+        assert_eq!(
+            fmt_rel_aggregate_name(&task_type, &work_log),
+            "TaskWorkLogRelAggregate"
+        );
+        let aggregate = generate_rel_aggregate(&task_type, &work_log);
+        assert_eq!(aggregate.type_kind, TypeKind::Object);
+        assert_eq!(aggregate.props.len(), 3);
+        let count = aggregate.props.get("count").unwrap();
+        assert_eq!(count.kind, PropertyKind::Scalar);
+        assert_eq!(count.type_name, "Int");
+        assert!(count.required);
+        let hours = aggregate.props.get("hours").unwrap();
+        assert_eq!(hours.kind, PropertyKind::Aggregate);
+        assert_eq!(hours.type_name, "FloatAggregate");
+        assert!(!hours.required);
+        let minutes = aggregate.props.get("minutes").unwrap();
+        assert_eq!(minutes.kind, PropertyKind::Aggregate);
+        assert_eq!(minutes.type_name, "IntAggregate");
+    }
+
+    /// Passes if a rel grouped by type has a list of rels for each destination type, each taking
+    /// the rel's query input and options, and its own pagination arguments
+    #[test]
+    fn test_generate_rel_by_type() {
+        /*
+            type ProjectIssuesRelByType {
+                Feature(input: ProjectIssuesQueryInput, options: ProjectIssuesOptions, first: Int,
+                    skip: Int): [ProjectIssuesRel!]!
+                Bug(input: ProjectIssuesQueryInput, options: ProjectIssuesOptions, first: Int,
+                    skip: Int): [ProjectIssuesRel!]!
+            }
+        */
+        let project_type = mock_project_type();
+        let project_issues_rel = project_type.rels().find(|&r| r.name() == "issues").unwrap();
+        assert_eq!(
+            fmt_rel_by_type_name(&project_type, project_issues_rel),
+            "ProjectIssuesRelByType"
+        );
+        let by_type = generate_rel_by_type(&project_type, project_issues_rel);
+        assert_eq!(by_type.type_kind, TypeKind::Object);
+        assert_eq!(by_type.props.len(), 2);
+        let bug = by_type.props.get("Bug").unwrap();
+        assert_eq!(
+            bug.kind,
+            PropertyKind::RelGroup {
+                rel_name: "issues".to_string(),
+                dst_label: "Bug".to_string()
+            }
+        );
+        assert_eq!(bug.type_name, "ProjectIssuesRel");
+        assert!(bug.list);
+        assert!(bug.required);
+        assert_eq!(bug.arguments.len(), 4);
+        assert_eq!(
+            bug.arguments.get("input").unwrap().type_name,
+            "ProjectIssuesQueryInput"
+        );
+        assert_eq!(
+            bug.arguments.get("options").unwrap().type_name,
+            "ProjectIssuesOptions"
+        );
+        assert_eq!(bug.arguments.get("first").unwrap().type_name, "Int");
+        assert_eq!(bug.arguments.get("skip").unwrap().type_name, "Int");
+        let feature = by_type.props.get("Feature").unwrap();
+        assert_eq!(
+            feature.kind,
+            PropertyKind::RelGroup {
+                rel_name: "issues".to_string(),
+                dst_label: "Feature".to_string()
+            }
+        );
+    }
+
     /// Passes if the right schema elements are generated
     #[allow(clippy::cognitive_complexity)]
     #[test]
@@ -4126,6 +6229,7 @@ mod tests {
             input ProjectOwnerCreateInput {
                 MATCH: ProjectQueryInput
                 CREATE: ProjectOwnerCreateMutationInput
+                ifDstExists: Boolean
             }
         */
         let project_type = mock_project_type();
@@ -4134,7 +6238,7 @@ mod tests {
             generate_rel_create_input(&project_type, project_owner_rel);
         assert!(project_owner_create_input.type_name == "ProjectOwnerCreateInput");
         assert!(project_owner_create_input.type_kind == TypeKind::Input);
-        assert!(project_owner_create_input.props.len() == 2);
+        assert!(project_owner_create_input.props.len() == 3);
         let project_owner_match = project_owner_create_input.props.get("MATCH").unwrap();
         assert!(project_owner_match.name == "MATCH");
         assert!(project_owner_match.kind == PropertyKind::Input);
@@ -4149,10 +6253,16 @@ mod tests {
         assert!(!project_owner_create.required);
         assert!(!project_owner_create.list);
         assert!(project_owner_create.arguments.is_empty());
+        let project_owner_if_dst_exists =
+            project_owner_create_input.props.get("ifDstExists").unwrap();
+        assert!(project_owner_if_dst_exists.kind == PropertyKind::Scalar);
+        assert!(project_owner_if_dst_exists.type_name == "Boolean");
+        assert!(!project_owner_if_dst_exists.required);
         /*
             input ProjectBoardCreateInput {
                 MATCH: ProjectQueryInput
                 CREATE: ProjectBoardCreateMutationInput
+                ifDstExists: Boolean
             }
         */
         let project_board_rel = project_type.rels().find(|&r| r.name() == "board").unwrap();
@@ -4160,7 +6270,7 @@ mod tests {
             generate_rel_create_input(&project_type, project_board_rel);
         assert!(project_board_create_input.type_name == "ProjectBoardCreateInput");
         assert!(project_board_create_input.type_kind == TypeKind::Input);
-        assert!(project_board_create_input.props.len() == 2);
+        assert!(project_board_create_input.props.len() == 3);
         let project_board_match = project_board_create_input.props.get("MATCH").unwrap();
         assert!(project_board_match.name == "MATCH");
         assert!(project_board_match.kind == PropertyKind::Input);
@@ -4175,6 +6285,11 @@ mod tests {
         assert!(!project_board_create.required);
         assert!(!project_board_create.list);
         assert!(project_board_create.arguments.is_empty());
+        let project_board_if_dst_exists =
+            project_board_create_input.props.get("ifDstExists").unwrap();
+        assert!(project_board_if_dst_exists.kind == PropertyKind::Scalar);
+        assert!(project_board_if_dst_exists.type_name == "Boolean");
+        assert!(!project_board_if_dst_exists.required);
     }
 
     /// Passes if the right schema elements are generated
@@ -4489,6 +6604,82 @@ mod tests {
             assert!(input.kind == ArgumentKind::Required);
             assert!(input.type_name == "ProjectOwnerCreateInput");
         }
+        assert!(
+            project_owner_create_endpoint
+                .arguments
+                .get("options")
+                .unwrap()
+                .type_name
+                == "ProjectOwnerCreateOptions"
+        );
+    }
+
+    /// Passes if continueOnError is an option of relationship creates only
+    #[test]
+    fn test_generate_rel_create_options() {
+        let project_type = mock_project_type();
+        let project_owner_rel = project_type.rels().find(|&r| r.name() == "owner").unwrap();
+        let create_options = generate_rel_create_options(&project_type, project_owner_rel);
+        assert!(create_options.type_name == "ProjectOwnerCreateOptions");
+        assert!(create_options.type_kind == TypeKind::Input);
+        assert!(create_options.props.contains_key("continueOnError"));
+        assert!(create_options.props.contains_key("sort"));
+        assert!(!create_options.props.contains_key("allowUnfilteredUpdate"));
+
+        let rel_options = generate_rel_options(&project_type, project_owner_rel).unwrap();
+        assert!(rel_options.type_name == "ProjectOwnerOptions");
+        assert!(!rel_options.props.contains_key("continueOnError"));
+    }
+
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_generate_rel_create_bulk_endpoint() {
+        /*
+            ProjectOwnerCreateBulk(input: ProjectOwnerCreateInput, options: ProjectOwnerCreateOptions): ProjectOwnerCreateBulkResult
+        */
+        let project_type = mock_project_type();
+        let project_owner_rel = project_type.rels().find(|&r| r.name() == "owner").unwrap();
+        let endpoint = generate_rel_create_bulk_endpoint(&project_type, project_owner_rel);
+        assert!(endpoint.name == "ProjectOwnerCreateBulk");
+        assert!(match &endpoint.kind {
+            PropertyKind::RelCreateBulkMutation {
+                src_label,
+                rel_name,
+            } => src_label == "Project" && rel_name == "owner",
+            _ => false,
+        });
+        assert!(endpoint.type_name == "ProjectOwnerCreateBulkResult");
+        assert!(!endpoint.required);
+        assert!(!endpoint.list);
+        let input = endpoint.arguments.get("input").unwrap();
+        assert!(input.kind == ArgumentKind::Required);
+        assert!(input.type_name == "ProjectOwnerCreateInput");
+        let options = endpoint.arguments.get("options").unwrap();
+        assert!(options.kind == ArgumentKind::Optional);
+        assert!(options.type_name == "ProjectOwnerCreateOptions");
+    }
+
+    /// Passes if the right schema elements are generated
+    #[test]
+    fn test_generate_rel_create_bulk_result() {
+        /*
+            type ProjectOwnerCreateBulkResult {
+                succeeded: [ProjectOwnerRel!]!
+                failed: [BatchItemError!]!
+            }
+        */
+        let project_type = mock_project_type();
+        let project_owner_rel = project_type.rels().find(|&r| r.name() == "owner").unwrap();
+        let result = generate_rel_create_bulk_result(&project_type, project_owner_rel);
+        assert!(result.type_name == "ProjectOwnerCreateBulkResult");
+        assert!(result.type_kind == TypeKind::Object);
+        let succeeded = result.props.get("succeeded").unwrap();
+        assert!(succeeded.kind == PropertyKind::RelBulkSucceeded);
+        assert!(succeeded.type_name == "ProjectOwnerRel");
+        assert!(succeeded.required && succeeded.list);
+        let failed = result.props.get("failed").unwrap();
+        assert!(failed.kind == PropertyKind::BulkFailed);
+        assert!(failed.type_name == "BatchItemError");
     }
 
     /// Passes if the right schema elements are generated
@@ -4651,6 +6842,173 @@ mod tests {
         assert!(schema.contains_key("Mutation"));
     }
 
+    /// Passes if a type's default sort is carried to its node object
+    #[test]
+    fn test_generate_node_object_default_sort() {
+        let project_type = mock_project_type().with_default_sort(vec![DefaultSort::new(
+            "name".to_string(),
+            Some("descending".to_string()),
+        )]);
+        let project_node_object = generate_node_object(&project_type);
+        assert_eq!(project_node_object.default_sort().len(), 1);
+        assert_eq!(project_node_object.default_sort()[0].order_by(), "name");
+
+        let project_node_object = generate_node_object(&mock_project_type());
+        assert!(project_node_object.default_sort().is_empty());
+    }
+
+    /// Passes if a custom endpoint may output a declared enum, and an unknown output type is
+    /// rejected
+    #[test]
+    fn test_generate_schema_endpoint_enum_output() {
+        let endpoint = Endpoint::new(
+            "ProjectStatuses".to_string(),
+            EndpointClass::Query,
+            None,
+            EndpointType::new(TypeDef::Existing("Status".to_string()), true, true),
+        );
+
+        let config = Configuration::new(1, vec![mock_project_type()], vec![endpoint.clone()])
+            .with_enums(vec![EnumType::new(
+                "Status".to_string(),
+                vec!["ACTIVE".to_string(), "ARCHIVED".to_string()],
+            )]);
+        let schema = generate_schema(&config).unwrap();
+        let p = schema
+            .get("Query")
+            .unwrap()
+            .property("ProjectStatuses")
+            .unwrap();
+        assert!(p.kind == PropertyKind::CustomResolver);
+        assert!(p.type_name == "Status");
+        assert!(p.list);
+
+        let config = Configuration::new(1, vec![mock_project_type()], vec![endpoint]);
+        assert!(matches!(
+            generate_schema(&config),
+            Err(Error::SchemaItemNotFound { name }) if name == "Status"
+        ));
+    }
+
+    /// Passes if the variants of an enum carry their stored codes, and an enum serialized by its
+    /// codes is presented as the scalar type of the codes on node types while inputs keep the enum
+    #[test]
+    fn test_generate_schema_enum_stored_values() {
+        let priority = EnumType::new(
+            "Priority".to_string(),
+            vec!["LOW".to_string(), "HIGH".to_string()],
+        )
+        .with_stored_value("LOW".to_string(), serde_json::json!(1))
+        .with_stored_value("HIGH".to_string(), serde_json::json!(3));
+        let config = |priority: EnumType| {
+            Configuration::new(
+                1,
+                vec![Type::new(
+                    "Project".to_string(),
+                    vec![crate::engine::config::Property::new(
+                        "priority".to_string(),
+                        UsesFilter::all(),
+                        "Priority".to_string(),
+                        false,
+                        false,
+                        None,
+                        None,
+                    )],
+                    vec![],
+                    EndpointsFilter::all(),
+                )],
+                vec![],
+            )
+            .with_enums(vec![priority])
+        };
+
+        let schema = generate_schema(&config(priority.clone())).unwrap();
+        let high = schema.get("Priority").unwrap().property("HIGH").unwrap();
+        assert_eq!(high.stored_value(), Some(&serde_json::json!(3)));
+        let p = schema.get("Project").unwrap().property("priority").unwrap();
+        assert_eq!(p.type_name(), "Priority");
+
+        let schema = generate_schema(&config(
+            priority.with_serialization(EnumSerialization::Value),
+        ))
+        .unwrap();
+        let p = schema.get("Project").unwrap().property("priority").unwrap();
+        assert_eq!(p.type_name(), "Int");
+        let input = schema
+            .get("ProjectCreateMutationInput")
+            .unwrap()
+            .property("priority")
+            .unwrap();
+        assert_eq!(input.type_name(), "Priority");
+        let query = schema
+            .get("ProjectQueryInput")
+            .unwrap()
+            .property("priority")
+            .unwrap();
+        assert_eq!(query.type_name(), "Priority");
+    }
+
+    /// Passes if the props of a custom endpoint input type may nest generated input types, and
+    /// props typed as an object type or an unknown type are rejected
+    #[test]
+    fn test_generate_schema_endpoint_nested_input() {
+        let order_endpoint = |items_type: &str| {
+            Endpoint::new(
+                "PlaceOrder".to_string(),
+                EndpointClass::Mutation,
+                Some(EndpointType::new(
+                    TypeDef::Custom(Type::new(
+                        "OrderInput".to_string(),
+                        vec![crate::engine::config::Property::new(
+                            "items".to_string(),
+                            UsesFilter::all(),
+                            items_type.to_string(),
+                            false,
+                            true,
+                            None,
+                            None,
+                        )],
+                        vec![],
+                        EndpointsFilter::all(),
+                    )),
+                    false,
+                    true,
+                )),
+                EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true),
+            )
+        };
+
+        let config = Configuration::new(
+            1,
+            vec![mock_project_type()],
+            vec![order_endpoint("ProjectCreateMutationInput")],
+        );
+        let schema = generate_schema(&config).unwrap();
+        let p = schema.get("OrderInput").unwrap().property("items").unwrap();
+        assert!(p.type_name == "ProjectCreateMutationInput");
+        assert!(p.list);
+
+        let config = Configuration::new(
+            1,
+            vec![mock_project_type()],
+            vec![order_endpoint("Project")],
+        );
+        assert!(matches!(
+            generate_schema(&config),
+            Err(Error::ConfigItemInvalid { type_name, .. }) if type_name == "PlaceOrder"
+        ));
+
+        let config = Configuration::new(
+            1,
+            vec![mock_project_type()],
+            vec![order_endpoint("Missing")],
+        );
+        assert!(matches!(
+            generate_schema(&config),
+            Err(Error::SchemaItemNotFound { name }) if name == "Missing"
+        ));
+    }
+
     /// Passes if the right schema elements are generated
     #[test]
     fn test_wg_type_endpoints_filter() {
@@ -4659,7 +7017,9 @@ mod tests {
         let query = schema.get("Query").unwrap();
         let mutation = schema.get("Mutation").unwrap();
         assert!(query.props.len() == 1);
-        assert!(mutation.props.len() == 1);
+        assert!(mutation.props.len() == 2);
+        assert!(mutation.props.contains_key("UserCreate"));
+        assert!(mutation.props.contains_key("UserCreateBatch"));
     }
 
     /// Passes if the right schema elements are generated
@@ -4674,6 +7034,943 @@ mod tests {
         assert!(root_node.is_ok());
     }
 
+    /// Passes if descriptions configured on types, props, rels, and endpoints appear in the
+    /// generated schema
+    #[test]
+    fn test_create_root_node_descriptions() {
+        let config = Configuration::new(
+            1,
+            vec![Type::new(
+                "User".to_string(),
+                vec![crate::engine::config::Property::new(
+                    "name".to_string(),
+                    UsesFilter::all(),
+                    "String".to_string(),
+                    true,
+                    false,
+                    None,
+                    None,
+                )
+                .with_description("The full name of the user".to_string())],
+                vec![Relationship::new(
+                    "manager".to_string(),
+                    false,
+                    vec!["User".to_string()],
+                    vec![],
+                    EndpointsFilter::all(),
+                    None,
+                )
+                .with_description("The user to whom this user reports".to_string())],
+                EndpointsFilter::all(),
+            )
+            .with_description("A person with an account".to_string())],
+            vec![Endpoint::new(
+                "UserCount".to_string(),
+                EndpointClass::Query,
+                None,
+                EndpointType::new(TypeDef::Scalar(GraphqlType::Int), false, true),
+            )
+            .with_description("Counts the users with accounts".to_string())],
+        );
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+
+        assert!(sdl.contains("A person with an account"));
+        assert!(sdl.contains("The full name of the user"));
+        assert!(sdl.contains("The user to whom this user reports"));
+        assert!(sdl.contains("Counts the users with accounts"));
+    }
+
+    /// Passes if a type whose generated writes are turned off keeps its object type, read
+    /// queries, and input types, so that a custom mutation can take over its writes
+    #[test]
+    fn test_create_root_node_custom_only_writes() {
+        let config = Configuration::new(
+            1,
+            vec![Type::new(
+                "User".to_string(),
+                vec![crate::engine::config::Property::new(
+                    "name".to_string(),
+                    UsesFilter::all(),
+                    "String".to_string(),
+                    true,
+                    false,
+                    None,
+                    None,
+                )],
+                vec![],
+                EndpointsFilter::new(true, false, false, false),
+            )],
+            vec![Endpoint::new(
+                "RegisterUser".to_string(),
+                EndpointClass::Mutation,
+                Some(EndpointType::new(
+                    TypeDef::Existing("UserCreateMutationInput".to_string()),
+                    false,
+                    true,
+                )),
+                EndpointType::new(TypeDef::Existing("User".to_string()), false, true),
+            )],
+        );
+
+        let root_node = create_root_node::<()>(&config).unwrap();
+        let sdl = root_node.as_schema_language();
+
+        assert!(sdl.contains("type User "));
+        assert!(sdl.contains("  User(") && sdl.contains("input: UserQueryInput"));
+        assert!(sdl.contains("RegisterUser(input: UserCreateMutationInput!): User!"));
+        assert!(!sdl.contains("UserCreate("));
+        assert!(!sdl.contains("UserUpdate("));
+        assert!(!sdl.contains("UserDelete("));
+    }
+
+    /// Passes if the bulk endpoints return their results, and continueOnError is an option of
+    /// relationship creates and node updates only
+    #[test]
+    fn test_create_root_node_bulk_endpoints() {
+        let config = Configuration::try_from(
+            "
+version: 1
+model:
+  - name: User
+    props:
+      - name: name
+        type: String
+  - name: Project
+    props:
+      - name: name
+        type: String
+    rels:
+      - name: owner
+        nodes: [User]
+"
+            .to_string(),
+        )
+        .unwrap();
+        let root_node = create_root_node::<()>(&config).unwrap();
+        let sdl = root_node.as_schema_language();
+
+        let endpoint = |name: &str| {
+            sdl.lines()
+                .find(|l| l.trim_start().starts_with(&format!("{}(", name)))
+                .unwrap()
+                .to_string()
+        };
+        let update_bulk = endpoint("ProjectUpdateBulk");
+        assert!(update_bulk.contains("input: ProjectUpdateInput!"));
+        assert!(update_bulk.contains("options: ProjectUpdateOptions"));
+        assert!(update_bulk.ends_with("): ProjectUpdateBulkResult"));
+        let create_bulk = endpoint("ProjectOwnerCreateBulk");
+        assert!(create_bulk.contains("input: ProjectOwnerCreateInput!"));
+        assert!(create_bulk.contains("options: ProjectOwnerCreateOptions"));
+        assert!(create_bulk.ends_with("): ProjectOwnerCreateBulkResult"));
+        assert!(endpoint("ProjectOwnerCreate").contains("options: ProjectOwnerCreateOptions"));
+        assert!(endpoint("ProjectOwnerUpdate").contains("options: ProjectOwnerOptions"));
+        assert!(sdl.contains("succeeded: [ProjectOwnerRel!]!"));
+        assert!(sdl.contains("failed: [BatchItemError!]!"));
+        assert!(sdl.contains("type BatchItemError {"));
+
+        let input_block = |name: &str| {
+            let start = sdl.find(&format!("input {} {{", name)).unwrap();
+            let end = start + sdl[start..].find('}').unwrap();
+            sdl[start..end].to_string()
+        };
+        assert!(input_block("ProjectOwnerCreateOptions").contains("continueOnError"));
+        assert!(input_block("ProjectUpdateOptions").contains("continueOnError"));
+        assert!(!input_block("ProjectOwnerOptions").contains("continueOnError"));
+        assert!(!input_block("ProjectOptions").contains("continueOnError"));
+    }
+
+    /// Passes if a relationship prop hidden from output is left out of the relationship's output
+    /// type, while remaining in the input used to create the relationship
+    #[test]
+    fn test_create_root_node_hidden_rel_prop() {
+        let config = Configuration::new(
+            1,
+            vec![
+                Type::new(
+                    "User".to_string(),
+                    vec![crate::engine::config::Property::new(
+                        "name".to_string(),
+                        UsesFilter::all(),
+                        "String".to_string(),
+                        true,
+                        false,
+                        None,
+                        None,
+                    )],
+                    vec![],
+                    EndpointsFilter::all(),
+                ),
+                Type::new(
+                    "Organization".to_string(),
+                    vec![],
+                    vec![Relationship::new(
+                        "members".to_string(),
+                        true,
+                        vec!["User".to_string()],
+                        vec![
+                            crate::engine::config::Property::new(
+                                "role".to_string(),
+                                UsesFilter::all(),
+                                "String".to_string(),
+                                false,
+                                false,
+                                None,
+                                None,
+                            ),
+                            crate::engine::config::Property::new(
+                                "internalNotes".to_string(),
+                                UsesFilter::new(true, false, true, false),
+                                "String".to_string(),
+                                false,
+                                false,
+                                None,
+                                None,
+                            ),
+                        ],
+                        EndpointsFilter::all(),
+                        None,
+                    )],
+                    EndpointsFilter::all(),
+                ),
+            ],
+            vec![],
+        );
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+
+        let rel_start = sdl.find("type OrganizationMembersRel {").unwrap();
+        let rel_end = rel_start + sdl[rel_start..].find('}').unwrap();
+        let rel_type = &sdl[rel_start..rel_end];
+        assert!(rel_type.contains("role: String"));
+        assert!(!rel_type.contains("internalNotes"));
+        assert!(sdl.contains("internalNotes: String"));
+    }
+
+    /// Passes if a required relationship prop with a default is optional in the create input,
+    /// carries its default, and remains required in the relationship's output type
+    #[test]
+    fn test_generate_schema_rel_prop_default() {
+        let config = Configuration::new(
+            1,
+            vec![
+                Type::new("User".to_string(), vec![], vec![], EndpointsFilter::all()),
+                Type::new(
+                    "Organization".to_string(),
+                    vec![],
+                    vec![Relationship::new(
+                        "members".to_string(),
+                        true,
+                        vec!["User".to_string()],
+                        vec![crate::engine::config::Property::new(
+                            "role".to_string(),
+                            UsesFilter::all(),
+                            "String".to_string(),
+                            true,
+                            false,
+                            None,
+                            Some("RoleValidator".to_string()),
+                        )
+                        .with_default(serde_json::json!("member"))],
+                        EndpointsFilter::all(),
+                        None,
+                    )],
+                    EndpointsFilter::all(),
+                ),
+            ],
+            vec![],
+        );
+
+        let schema = generate_schema(&config).unwrap();
+        let create_role = schema
+            .get("OrganizationMembersCreateMutationInput")
+            .unwrap()
+            .property("role")
+            .unwrap();
+        assert!(!create_role.required());
+        assert_eq!(
+            Some(&serde_json::json!("member")),
+            create_role.default_value()
+        );
+        assert_eq!(Some(&"RoleValidator".to_string()), create_role.validator());
+
+        let output_role = schema
+            .get("OrganizationMembersRel")
+            .unwrap()
+            .property("role")
+            .unwrap();
+        assert!(output_role.required());
+    }
+
+    /// Passes if the configured defaults of props are declared as the defaults of the create input
+    /// fields in the exported SDL, with defaults of enumeration props given as enum values
+    #[test]
+    fn test_create_root_node_input_defaults() {
+        let prop = |name: &str, type_name: &str, list: bool| {
+            crate::engine::config::Property::new(
+                name.to_string(),
+                UsesFilter::all(),
+                type_name.to_string(),
+                true,
+                list,
+                None,
+                None,
+            )
+        };
+        let config = Configuration::new(
+            1,
+            vec![
+                Type::new("User".to_string(), vec![], vec![], EndpointsFilter::all()),
+                Type::new(
+                    "Project".to_string(),
+                    vec![
+                        prop("status", "Status", false).with_default(serde_json::json!("ACTIVE")),
+                        prop("priority", "Int", false).with_default(serde_json::json!(3)),
+                        prop("tags", "String", true).with_default(serde_json::json!(["new"])),
+                        prop("name", "String", false),
+                    ],
+                    vec![Relationship::new(
+                        "members".to_string(),
+                        true,
+                        vec!["User".to_string()],
+                        vec![
+                            prop("role", "String", false).with_default(serde_json::json!("member"))
+                        ],
+                        EndpointsFilter::all(),
+                        None,
+                    )],
+                    EndpointsFilter::all(),
+                ),
+            ],
+            vec![],
+        )
+        .with_enums(vec![EnumType::new(
+            "Status".to_string(),
+            vec!["ACTIVE".to_string(), "ARCHIVED".to_string()],
+        )]);
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+        let input_start = sdl.find("input ProjectCreateMutationInput {").unwrap();
+        let input_end = input_start + sdl[input_start..].find('}').unwrap();
+        let create_input = &sdl[input_start..input_end];
+        assert!(create_input.contains("status: Status = ACTIVE"));
+        assert!(create_input.contains("priority: Int = 3"));
+        assert!(create_input.contains("tags: [String!] = [\"new\"]"));
+        assert!(create_input.contains("name: String!\n"));
+        assert!(sdl.contains("role: String = \"member\""));
+    }
+
+    /// Passes if Long props are declared with the Long scalar on node types and inputs, and are
+    /// filtered with the Long comparison input
+    #[test]
+    fn test_create_root_node_long_props() {
+        let config = Configuration::new(
+            1,
+            vec![Type::new(
+                "Project".to_string(),
+                vec![crate::engine::config::Property::new(
+                    "views".to_string(),
+                    UsesFilter::all(),
+                    "Long".to_string(),
+                    false,
+                    false,
+                    None,
+                    None,
+                )],
+                vec![],
+                EndpointsFilter::all(),
+            )],
+            vec![],
+        );
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+        assert!(sdl.contains("scalar Long"));
+        let type_start = sdl.find("type Project {").unwrap();
+        let type_end = type_start + sdl[type_start..].find('}').unwrap();
+        assert!(sdl[type_start..type_end].contains("views: Long\n"));
+        let input_start = sdl.find("input ProjectCreateMutationInput {").unwrap();
+        let input_end = input_start + sdl[input_start..].find('}').unwrap();
+        assert!(sdl[input_start..input_end].contains("views: Long\n"));
+        let query_start = sdl.find("input ProjectQueryInput {").unwrap();
+        let query_end = query_start + sdl[query_start..].find('}').unwrap();
+        assert!(sdl[query_start..query_end].contains("views: LongQueryInput\n"));
+        let long_start = sdl.find("input LongQueryInput {").unwrap();
+        let long_end = long_start + sdl[long_start..].find('}').unwrap();
+        assert!(sdl[long_start..long_end].contains("GT: Long\n"));
+        assert!(sdl[long_start..long_end].contains("IN: [Long!]\n"));
+    }
+
+    /// Passes if Duration props are declared with the Duration scalar on node types and inputs,
+    /// and are filtered with the Duration comparison input
+    #[test]
+    fn test_create_root_node_duration_props() {
+        let config = Configuration::new(
+            1,
+            vec![Type::new(
+                "Ticket".to_string(),
+                vec![crate::engine::config::Property::new(
+                    "resolution".to_string(),
+                    UsesFilter::all(),
+                    "Duration".to_string(),
+                    false,
+                    false,
+                    None,
+                    None,
+                )],
+                vec![],
+                EndpointsFilter::all(),
+            )],
+            vec![],
+        );
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+        assert!(sdl.contains("scalar Duration"));
+        let type_start = sdl.find("type Ticket {").unwrap();
+        let type_end = type_start + sdl[type_start..].find('}').unwrap();
+        assert!(sdl[type_start..type_end].contains("resolution: Duration\n"));
+        let input_start = sdl.find("input TicketCreateMutationInput {").unwrap();
+        let input_end = input_start + sdl[input_start..].find('}').unwrap();
+        assert!(sdl[input_start..input_end].contains("resolution: Duration\n"));
+        let query_start = sdl.find("input TicketQueryInput {").unwrap();
+        let query_end = query_start + sdl[query_start..].find('}').unwrap();
+        assert!(sdl[query_start..query_end].contains("resolution: DurationQueryInput\n"));
+        let duration_start = sdl.find("input DurationQueryInput {").unwrap();
+        let duration_end = duration_start + sdl[duration_start..].find('}').unwrap();
+        assert!(sdl[duration_start..duration_end].contains("LT: Duration\n"));
+        assert!(sdl[duration_start..duration_end].contains("IN: [Duration!]\n"));
+    }
+
+    /// Passes if a node type has an aggregate field for each of its rels, holding the rel count
+    /// and aggregates of the rel's numeric props
+    #[test]
+    fn test_create_root_node_rel_aggregates() {
+        let config = Configuration::new(
+            1,
+            vec![
+                Type::new(
+                    "Task".to_string(),
+                    vec![],
+                    vec![Relationship::new(
+                        "work_log".to_string(),
+                        true,
+                        vec!["User".to_string()],
+                        vec![crate::engine::config::Property::new(
+                            "hours".to_string(),
+                            UsesFilter::all(),
+                            "Float".to_string(),
+                            false,
+                            false,
+                            None,
+                            None,
+                        )],
+                        EndpointsFilter::all(),
+                        None,
+                    )],
+                    EndpointsFilter::all(),
+                ),
+                Type::new("User".to_string(), vec![], vec![], EndpointsFilter::all()),
+            ],
+            vec![],
+        );
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+        let type_start = sdl.find("type Task {").unwrap();
+        let type_end = type_start + sdl[type_start..].find('}').unwrap();
+        assert!(sdl[type_start..type_end].contains("work_logAggregate: TaskWorkLogRelAggregate!\n"));
+        let agg_start = sdl.find("type TaskWorkLogRelAggregate {").unwrap();
+        let agg_end = agg_start + sdl[agg_start..].find('}').unwrap();
+        assert!(sdl[agg_start..agg_end].contains("count: Int!\n"));
+        assert!(sdl[agg_start..agg_end].contains("hours: FloatAggregate\n"));
+        let float_start = sdl.find("type FloatAggregate {").unwrap();
+        let float_end = float_start + sdl[float_start..].find('}').unwrap();
+        assert!(sdl[float_start..float_end].contains("sum: Float\n"));
+        assert!(sdl[float_start..float_end].contains("avg: Float\n"));
+        assert!(sdl[float_start..float_end].contains("min: Float\n"));
+        assert!(sdl[float_start..float_end].contains("max: Float\n"));
+    }
+
+    /// Passes if a node type has a field grouping each of its rels to more than one type of node
+    /// by destination type, and no such field for rels to a single type of node
+    #[test]
+    fn test_create_root_node_rel_by_type() {
+        let config = Configuration::new(
+            1,
+            vec![
+                Type::new(
+                    "Project".to_string(),
+                    vec![],
+                    vec![
+                        Relationship::new(
+                            "issues".to_string(),
+                            true,
+                            vec!["Bug".to_string(), "Feature".to_string()],
+                            vec![],
+                            EndpointsFilter::all(),
+                            None,
+                        ),
+                        Relationship::new(
+                            "owner".to_string(),
+                            false,
+                            vec!["User".to_string()],
+                            vec![],
+                            EndpointsFilter::all(),
+                            None,
+                        ),
+                    ],
+                    EndpointsFilter::all(),
+                ),
+                Type::new("Bug".to_string(), vec![], vec![], EndpointsFilter::all()),
+                Type::new(
+                    "Feature".to_string(),
+                    vec![],
+                    vec![],
+                    EndpointsFilter::all(),
+                ),
+                Type::new("User".to_string(), vec![], vec![], EndpointsFilter::all()),
+            ],
+            vec![],
+        );
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+        let type_start = sdl.find("type Project {").unwrap();
+        let type_end = type_start + sdl[type_start..].find('}').unwrap();
+        assert!(sdl[type_start..type_end].contains("issuesByType: ProjectIssuesRelByType!\n"));
+        assert!(!sdl[type_start..type_end].contains("ownerByType"));
+        assert!(!sdl.contains("ProjectOwnerRelByType"));
+        let by_type_start = sdl.find("type ProjectIssuesRelByType {").unwrap();
+        let by_type_end = by_type_start + sdl[by_type_start..].find('}').unwrap();
+        let by_type = &sdl[by_type_start..by_type_end];
+        assert!(by_type.contains("Bug("));
+        assert!(by_type.contains("Feature("));
+        assert!(by_type.contains("input: ProjectIssuesQueryInput"));
+        assert!(by_type.contains("options: ProjectIssuesOptions"));
+        assert!(by_type.contains("first: Int"));
+        assert!(by_type.contains("skip: Int"));
+        assert!(by_type.contains("): [ProjectIssuesRel!]!\n"));
+    }
+
+    /// Passes if scalar list props on nodes and relationships take first and skip arguments, with
+    /// a contains argument for String lists, while single-valued props take no arguments
+    #[test]
+    fn test_create_root_node_scalar_list_arguments() {
+        let tags = |type_name: &str| {
+            crate::engine::config::Property::new(
+                "tags".to_string(),
+                UsesFilter::all(),
+                type_name.to_string(),
+                false,
+                true,
+                None,
+                None,
+            )
+        };
+        let config = Configuration::new(
+            1,
+            vec![Type::new(
+                "User".to_string(),
+                vec![
+                    tags("String"),
+                    crate::engine::config::Property::new(
+                        "name".to_string(),
+                        UsesFilter::all(),
+                        "String".to_string(),
+                        false,
+                        false,
+                        None,
+                        None,
+                    ),
+                ],
+                vec![Relationship::new(
+                    "teams".to_string(),
+                    true,
+                    vec!["User".to_string()],
+                    vec![tags("Int")],
+                    EndpointsFilter::all(),
+                    None,
+                )],
+                EndpointsFilter::all(),
+            )],
+            vec![],
+        );
+
+        let schema = generate_schema(&config).unwrap();
+        let user = schema.get("User").unwrap();
+        let mut user_args = user
+            .property("tags")
+            .unwrap()
+            .arguments()
+            .map(|a| a.name())
+            .collect::<Vec<&str>>();
+        user_args.sort_unstable();
+        assert_eq!(vec!["contains", "first", "skip"], user_args);
+        assert_eq!(0, user.property("name").unwrap().arguments().count());
+
+        let mut rel_args = schema
+            .get("UserTeamsRel")
+            .unwrap()
+            .property("tags")
+            .unwrap()
+            .arguments()
+            .map(|a| a.name())
+            .collect::<Vec<&str>>();
+        rel_args.sort_unstable();
+        assert_eq!(vec!["first", "skip"], rel_args);
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+        let rel_start = sdl.find("type UserTeamsRel {").unwrap();
+        let rel_end = rel_start + sdl[rel_start..].find('}').unwrap();
+        let tags_field = sdl[rel_start..rel_end]
+            .lines()
+            .find(|l| l.trim_start().starts_with("tags("))
+            .unwrap();
+        assert!(tags_field.contains("first: Int"));
+        assert!(tags_field.contains("skip: Int"));
+    }
+
+    /// Passes if a count prop is a computed output field of the node that is left out of the
+    /// node's create, update, query, and sort inputs
+    #[test]
+    fn test_generate_schema_prop_count() {
+        let config = Configuration::new(
+            1,
+            vec![
+                Type::new("Issue".to_string(), vec![], vec![], EndpointsFilter::all()),
+                Type::new(
+                    "Project".to_string(),
+                    vec![crate::engine::config::Property::new(
+                        "issueCount".to_string(),
+                        UsesFilter::all(),
+                        "Int".to_string(),
+                        true,
+                        false,
+                        None,
+                        None,
+                    )
+                    .with_count("issues".to_string())],
+                    vec![Relationship::new(
+                        "issues".to_string(),
+                        true,
+                        vec!["Issue".to_string()],
+                        vec![],
+                        EndpointsFilter::all(),
+                        None,
+                    )],
+                    EndpointsFilter::all(),
+                ),
+            ],
+            vec![],
+        );
+
+        let schema = generate_schema(&config).unwrap();
+        let count = schema
+            .get("Project")
+            .unwrap()
+            .property("issueCount")
+            .unwrap();
+        assert_eq!(
+            &PropertyKind::ComputedCount {
+                rel_name: "issues".to_string()
+            },
+            count.kind()
+        );
+        assert!(count.required());
+
+        for input in &[
+            "ProjectCreateMutationInput",
+            "ProjectUpdateMutationInput",
+            "ProjectQueryInput",
+            "ProjectOrderByEnum",
+        ] {
+            assert!(schema.get(*input).unwrap().property("issueCount").is_err());
+        }
+    }
+
+    /// Passes if a property with a database name keeps its GraphQL name in the schema, while
+    /// the create, update, query, and output fields carry the database name
+    #[test]
+    fn test_generate_schema_prop_db_name() {
+        let config = Configuration::new(
+            1,
+            vec![Type::new(
+                "User".to_string(),
+                vec![crate::engine::config::Property::new(
+                    "username".to_string(),
+                    UsesFilter::all(),
+                    "String".to_string(),
+                    true,
+                    false,
+                    None,
+                    None,
+                )
+                .with_db_name("user_name".to_string())],
+                vec![],
+                EndpointsFilter::all(),
+            )],
+            vec![],
+        );
+
+        let schema = generate_schema(&config).unwrap();
+        for type_name in &[
+            "User",
+            "UserCreateMutationInput",
+            "UserUpdateMutationInput",
+            "UserQueryInput",
+        ] {
+            let username = schema
+                .get(*type_name)
+                .unwrap()
+                .property("username")
+                .unwrap();
+            assert_eq!("user_name", username.db_name());
+            assert!(schema
+                .get(*type_name)
+                .unwrap()
+                .property("user_name")
+                .is_err());
+        }
+
+        let user = schema.get("User").unwrap();
+        assert_eq!("username", user.db_property("user_name").unwrap().name());
+        assert_eq!("id", user.db_property("id").unwrap().name());
+        assert!(user.db_property("username").is_err());
+    }
+
+    /// Passes if a relationship with an inverse adds a field taking the source type's query
+    /// input to the query input of its destination type
+    #[test]
+    fn test_generate_schema_rel_inverse() {
+        let config = Configuration::new(
+            1,
+            vec![
+                Type::new("Bug".to_string(), vec![], vec![], EndpointsFilter::all()),
+                Type::new(
+                    "Project".to_string(),
+                    vec![],
+                    vec![Relationship::new(
+                        "issues".to_string(),
+                        true,
+                        vec!["Bug".to_string()],
+                        vec![],
+                        EndpointsFilter::all(),
+                        None,
+                    )
+                    .with_inverse("project".to_string())],
+                    EndpointsFilter::all(),
+                ),
+            ],
+            vec![],
+        );
+
+        let schema = generate_schema(&config).unwrap();
+        let project = schema
+            .get("BugQueryInput")
+            .unwrap()
+            .property("project")
+            .unwrap();
+        assert_eq!(
+            &PropertyKind::InverseRel {
+                src_label: "Project".to_string(),
+                rel_name: "issues".to_string()
+            },
+            project.kind()
+        );
+        assert_eq!("ProjectQueryInput", project.type_name());
+        assert!(schema
+            .get("ProjectQueryInput")
+            .unwrap()
+            .property("project")
+            .is_err());
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+        let input_start = sdl.find("input BugQueryInput {").unwrap();
+        let input_end = input_start + sdl[input_start..].find('}').unwrap();
+        assert!(sdl[input_start..input_end].contains("project: ProjectQueryInput"));
+    }
+
+    /// Passes if the position property of an ordered relationship is recorded on the
+    /// relationship's update mutation input
+    #[test]
+    fn test_generate_rel_update_mutation_input_position() {
+        let rank = crate::engine::config::Property::new(
+            "rank".to_string(),
+            UsesFilter::all(),
+            "Int".to_string(),
+            false,
+            false,
+            None,
+            None,
+        );
+        let tasks = Relationship::new(
+            "tasks".to_string(),
+            true,
+            vec!["Task".to_string()],
+            vec![rank],
+            EndpointsFilter::all(),
+            None,
+        );
+        let sprint = Type::new(
+            "Sprint".to_string(),
+            vec![],
+            vec![tasks.clone()],
+            EndpointsFilter::all(),
+        );
+        assert!(generate_rel_update_mutation_input(&sprint, &tasks)
+            .position()
+            .is_none());
+
+        let tasks = tasks.with_position("rank".to_string());
+        let update_input = generate_rel_update_mutation_input(&sprint, &tasks);
+        assert_eq!(Some("rank"), update_input.position());
+        assert!(update_input.property("rank").is_ok());
+    }
+
+    /// Passes if a rel update input has an INCREMENT input for its numeric props and a PUSH
+    /// input for its list props, and neither when the rel has no such props
+    #[test]
+    fn test_generate_rel_update_mutation_input_operations() {
+        let prop = |name: &str, type_name: &str, list: bool| {
+            crate::engine::config::Property::new(
+                name.to_string(),
+                UsesFilter::all(),
+                type_name.to_string(),
+                false,
+                list,
+                None,
+                None,
+            )
+        };
+        let work_log = Relationship::new(
+            "workLog".to_string(),
+            true,
+            vec!["User".to_string()],
+            vec![
+                prop("hours", "Float", false),
+                prop("sessions", "Long", false),
+                prop("note", "String", false),
+                prop("tags", "String", true),
+            ],
+            EndpointsFilter::all(),
+            None,
+        );
+        let project = Type::new(
+            "Project".to_string(),
+            vec![],
+            vec![work_log.clone()],
+            EndpointsFilter::all(),
+        );
+
+        let update_input = generate_rel_update_mutation_input(&project, &work_log);
+        assert_eq!(
+            update_input.property("INCREMENT").unwrap().type_name(),
+            "ProjectWorkLogIncrementInput"
+        );
+        assert_eq!(
+            update_input.property("PUSH").unwrap().type_name(),
+            "ProjectWorkLogPushInput"
+        );
+
+        let increment = generate_rel_increment_input(&project, &work_log).unwrap();
+        let mut names = increment.props.keys().collect::<Vec<&String>>();
+        names.sort();
+        assert_eq!(names, vec!["hours", "sessions"]);
+        assert_eq!(increment.property("sessions").unwrap().type_name(), "Long");
+
+        let push = generate_rel_push_input(&project, &work_log).unwrap();
+        assert_eq!(push.props.keys().collect::<Vec<&String>>(), vec!["tags"]);
+        assert!(push.property("tags").unwrap().list());
+
+        let owner = Relationship::new(
+            "owner".to_string(),
+            false,
+            vec!["User".to_string()],
+            vec![prop("since", "String", false)],
+            EndpointsFilter::all(),
+            None,
+        );
+        assert!(generate_rel_increment_input(&project, &owner).is_none());
+        assert!(generate_rel_push_input(&project, &owner).is_none());
+        let update_input = generate_rel_update_mutation_input(&project, &owner);
+        assert!(update_input.property("INCREMENT").is_err());
+        assert!(update_input.property("PUSH").is_err());
+    }
+
+    /// Passes if deprecated props, rels, and enum values are marked deprecated, with their
+    /// reasons, in the generated schema
+    #[test]
+    fn test_create_root_node_deprecations() {
+        let config = Configuration::new(
+            1,
+            vec![Type::new(
+                "User".to_string(),
+                vec![
+                    crate::engine::config::Property::new(
+                        "nickname".to_string(),
+                        UsesFilter::all(),
+                        "String".to_string(),
+                        false,
+                        false,
+                        None,
+                        None,
+                    )
+                    .with_deprecated("Use displayName instead".to_string()),
+                    crate::engine::config::Property::new(
+                        "status".to_string(),
+                        UsesFilter::all(),
+                        "Status".to_string(),
+                        false,
+                        false,
+                        None,
+                        None,
+                    ),
+                ],
+                vec![Relationship::new(
+                    "lead".to_string(),
+                    false,
+                    vec!["User".to_string()],
+                    vec![],
+                    EndpointsFilter::all(),
+                    None,
+                )
+                .with_deprecated("Use manager instead".to_string())],
+                EndpointsFilter::all(),
+            )],
+            vec![],
+        )
+        .with_enums(vec![EnumType::new(
+            "Status".to_string(),
+            vec!["ACTIVE".to_string(), "DORMANT".to_string()],
+        )
+        .with_deprecated("DORMANT".to_string(), "Use INACTIVE instead".to_string())]);
+
+        let sdl = create_root_node::<()>(&config)
+            .unwrap()
+            .as_schema_language();
+
+        assert!(sdl.contains("nickname: String @deprecated(reason: \"Use displayName instead\")"));
+        assert!(sdl.contains("@deprecated(reason: \"Use manager instead\")"));
+        assert!(sdl.contains("DORMANT @deprecated(reason: \"Use INACTIVE instead\")"));
+        assert!(!sdl.contains("ACTIVE @deprecated"));
+    }
+
     /// Passes if a broken reference creates an error
     #[test]
     fn type_lookup_error() {
@@ -0,0 +1,128 @@
+//! This module tracks the requests running in an engine, so that an engine being shut down can
+//! turn away new requests and wait for the requests already running to finish before its
+//! database pool is closed.
+use crate::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    in_flight: usize,
+    draining: bool,
+    wakers: Vec<Waker>,
+}
+
+/// Engine-wide count of the requests in flight. Clones of the tracker share the count.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RequestTracker {
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl RequestTracker {
+    pub(crate) fn new() -> Self {
+        RequestTracker::default()
+    }
+
+    /// Records the start of a request, returning a guard that records the end of the request
+    /// when it is dropped. Returns an [`Error`] variant [`EngineShuttingDown`] once the tracker
+    /// is draining.
+    ///
+    /// [`Error`]: ../../error/enum.Error.html
+    /// [`EngineShuttingDown`]: ../../error/enum.Error.html#variant.EngineShuttingDown
+    pub(crate) fn begin(&self) -> Result<RequestGuard, Error> {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.draining {
+            return Err(Error::EngineShuttingDown);
+        }
+        state.in_flight += 1;
+        Ok(RequestGuard {
+            tracker: self.clone(),
+        })
+    }
+
+    /// Stops new requests from beginning, and returns a future that completes once the requests
+    /// in flight have ended
+    pub(crate) fn drain(&self) -> Drained {
+        self.state
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .draining = true;
+        Drained {
+            tracker: self.clone(),
+        }
+    }
+}
+
+/// Marks a request in flight until it is dropped
+#[derive(Debug)]
+pub(crate) struct RequestGuard {
+    tracker: RequestTracker,
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        let mut state = self.tracker.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.in_flight -= 1;
+        if state.in_flight == 0 {
+            state.wakers.drain(..).for_each(Waker::wake);
+        }
+    }
+}
+
+/// Completes once no requests are in flight
+#[derive(Debug)]
+pub(crate) struct Drained {
+    tracker: RequestTracker,
+}
+
+impl Future for Drained {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.tracker.state.lock().unwrap_or_else(|e| e.into_inner());
+        if state.in_flight == 0 {
+            Poll::Ready(())
+        } else {
+            if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RequestTracker;
+    use crate::Error;
+    use futures::FutureExt;
+
+    /// Passes if draining turns away new requests and completes only once the requests in flight
+    /// have ended
+    #[test]
+    fn drain_waits_for_requests_in_flight() {
+        let tracker = RequestTracker::new();
+        let first = tracker.begin().unwrap();
+        let second = tracker.begin().unwrap();
+
+        let mut drained = tracker.drain();
+        assert!(matches!(tracker.begin(), Err(Error::EngineShuttingDown)));
+        assert!((&mut drained).now_or_never().is_none());
+
+        drop(first);
+        assert!((&mut drained).now_or_never().is_none());
+
+        drop(second);
+        assert!(drained.now_or_never().is_some());
+    }
+
+    /// Passes if draining a tracker with no requests in flight completes at once
+    #[test]
+    fn drain_idle() {
+        let tracker = RequestTracker::new();
+        drop(tracker.begin().unwrap());
+        assert!(tracker.drain().now_or_never().is_some());
+    }
+}
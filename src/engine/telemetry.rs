@@ -0,0 +1,147 @@
+//! This module links the engine's tracing spans into a distributed trace, using the
+//! OpenTelemetry trace context carried in the request metadata, such as the `traceparent` and
+//! `tracestate` HTTP headers. The module is available with the `otel` feature.
+//!
+//! The engine extracts the context from the metadata of each request, and makes it the parent of
+//! the request's `wg-execute` span, so that the spans of the database queries and resolvers of
+//! the request appear under the caller's trace. The HTTP [`Client`] likewise injects the context
+//! of the current span into the headers of the requests it sends.
+//!
+//! The context is read and written with the global text map propagator, so the application sets
+//! one, such as the W3C `TraceContextPropagator` of the `opentelemetry_sdk` crate, and adds a
+//! `tracing-opentelemetry` layer to its tracing subscriber.
+//!
+//! [`Client`]: ../../client/enum.Client.html
+use crate::engine::metadata::Metadata;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use log::warn;
+use opentelemetry::propagation::{Extractor, Injector};
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::{global, Context};
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+impl Extractor for Metadata {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.get_str(key)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        (**self).keys().map(|k| k.as_str()).collect()
+    }
+}
+
+/// Writes the entries of a trace context into the headers of an HTTP request
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl<'a> Injector for HeaderInjector<'a> {
+    fn set(&mut self, key: &str, value: String) {
+        match (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            (Ok(name), Ok(value)) => {
+                self.0.insert(name, value);
+            }
+            _ => warn!("telemetry::inject_context -- invalid header: {}", key),
+        }
+    }
+}
+
+/// Returns the OpenTelemetry context carried in the request metadata, as read by the global text
+/// map propagator. If the metadata carries no trace context, the returned context has no span.
+///
+/// # Arguments
+///
+/// * metadata - the metadata of a request, such as its HTTP headers
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// # use warpgrapher::engine::metadata::Metadata;
+/// # use warpgrapher::engine::telemetry::extract_context;
+///
+/// let mut headers = HashMap::new();
+/// headers.insert(
+///     "traceparent".to_string(),
+///     "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".to_string(),
+/// );
+///
+/// let cx = extract_context(&Metadata::from(headers));
+/// ```
+pub fn extract_context(metadata: &Metadata) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(metadata))
+}
+
+/// Writes the OpenTelemetry context of the current span into the headers, as written by the
+/// global text map propagator, so that the spans of the receiving service join the same trace
+///
+/// # Arguments
+///
+/// * headers - the headers of an outbound HTTP request
+///
+/// # Examples
+///
+/// ```rust
+/// # use http::header::HeaderMap;
+/// # use warpgrapher::engine::telemetry::inject_context;
+///
+/// let mut headers = HeaderMap::new();
+/// inject_context(&mut headers);
+/// ```
+pub fn inject_context(headers: &mut HeaderMap) {
+    let cx = Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&cx, &mut HeaderInjector(headers))
+    });
+}
+
+/// Makes the trace context carried in the request metadata the parent of the current span, if
+/// the metadata carries one. Otherwise the current span keeps its parent.
+pub(crate) fn set_parent(metadata: &Metadata) {
+    let cx = extract_context(metadata);
+    if cx.span().span_context().is_valid() {
+        Span::current().set_parent(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HeaderInjector;
+    use crate::engine::metadata::Metadata;
+    use http::header::HeaderMap;
+    use opentelemetry::propagation::{Extractor, Injector};
+
+    /// Passes if trace context entries are read from metadata without regard to case
+    #[test]
+    fn metadata_extractor() {
+        let metadata: Metadata = vec![
+            ("Traceparent".to_string(), "00-abc-def-01".to_string()),
+            ("tracestate".to_string(), "vendor=1".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            Extractor::get(&metadata, "traceparent"),
+            Some("00-abc-def-01")
+        );
+        assert_eq!(Extractor::get(&metadata, "tracestate"), Some("vendor=1"));
+        assert_eq!(Extractor::get(&metadata, "baggage"), None);
+
+        let mut keys = Extractor::keys(&metadata);
+        keys.sort_unstable();
+        assert_eq!(keys, vec!["Traceparent", "tracestate"]);
+    }
+
+    /// Passes if trace context entries are written as headers, and invalid entries are skipped
+    #[test]
+    fn header_injector() {
+        let mut headers = HeaderMap::new();
+        let mut injector = HeaderInjector(&mut headers);
+        injector.set("traceparent", "00-abc-def-01".to_string());
+        injector.set("bad header", "value".to_string());
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["traceparent"], "00-abc-def-01");
+    }
+}
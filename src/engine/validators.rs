@@ -17,20 +17,20 @@ use std::collections::HashMap;
 ///     let name = match value {
 ///         Value::Map(m) => match m.get("name") {
 ///             Some(n) => n,
-///             None => return Err(Error::ValidationFailed {message: "Name missing.".to_string()}),
+///             None => return Err(Error::validation_failed("Name missing.".to_string())),
 ///         },
-///         _ => return Err(Error::ValidationFailed {message: "Field map missing.".to_string()}),
+///         _ => return Err(Error::validation_failed("Field map missing.".to_string())),
 ///     };
 ///
 ///     match name {
 ///         Value::String(s) => if s == "KENOBI" {
-///                 return Err(Error::ValidationFailed {
-///                     message: "Cannot be named KENOBI.".to_string()
-///                 });
+///                 return Err(Error::validation_failed(
+///                     "Cannot be named KENOBI.".to_string()
+///                 ));
 ///             } else {
 ///                 return Ok(())
 ///             },
-///         _ => Err(Error::ValidationFailed {message: "Expected a string value.".to_string()}),
+///         _ => Err(Error::validation_failed("Expected a string value.".to_string())),
 ///     }
 /// }
 ///
@@ -51,20 +51,20 @@ pub type ValidatorFunc = fn(&Value) -> Result<(), Error>;
 ///     let name = match value {
 ///         Value::Map(m) => match m.get("name") {
 ///             Some(n) => n,
-///             None => return Err(Error::ValidationFailed {message: "Name missing.".to_string()}),
+///             None => return Err(Error::validation_failed("Name missing.".to_string())),
 ///         },
-///         _ => return Err(Error::ValidationFailed {message: "Field map missing.".to_string()}),
+///         _ => return Err(Error::validation_failed("Field map missing.".to_string())),
 ///     };
 ///
 ///     match name {
 ///         Value::String(s) => if s == "KENOBI" {
-///                 return Err(Error::ValidationFailed {
-///                     message: "Cannot be named KENOBI.".to_string()
-///                 });
+///                 return Err(Error::validation_failed(
+///                     "Cannot be named KENOBI.".to_string()
+///                 ));
 ///             } else {
 ///                 return Ok(());
 ///             },
-///         _ => Err(Error::ValidationFailed {message: "Expected a string value.".to_string()}),
+///         _ => Err(Error::validation_failed("Expected a string value.".to_string())),
 ///      }
 /// }
 ///
@@ -72,3 +72,55 @@ pub type ValidatorFunc = fn(&Value) -> Result<(), Error>;
 /// validators.insert("name_validator".to_string(), Box::new(name_validator));
 /// ```
 pub type Validators = HashMap<String, Box<ValidatorFunc>>;
+
+/// Describes a single input validation failure, as part of an [`Error`] of variant
+/// [`ValidationFailed`]
+///
+/// [`Error`]: ../../enum.Error.html
+/// [`ValidationFailed`]: ../../enum.Error.html#variant.ValidationFailed
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::Error;
+/// # use warpgrapher::engine::validators::ValidationError;
+///
+/// let e = Error::ValidationFailed {
+///     errors: vec![
+///         ValidationError::new("name".to_string(), "Name is too long.".to_string()),
+///         ValidationError::new("email".to_string(), "Email is invalid.".to_string()),
+///     ],
+/// };
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+    field: String,
+    message: String,
+}
+
+impl ValidationError {
+    /// Returns a new validation failure for the named input `field`, described by `message`. If
+    /// `field` is empty, the engine sets it to the name of the field whose validator returned the
+    /// failure.
+    pub fn new(field: String, message: String) -> ValidationError {
+        ValidationError { field, message }
+    }
+
+    /// Returns the name of the input field that failed validation
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the reason the field failed validation
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Sets the field name to `field`, if no field name was set
+    pub(crate) fn with_default_field(mut self, field: &str) -> ValidationError {
+        if self.field.is_empty() {
+            self.field = field.to_string();
+        }
+        self
+    }
+}
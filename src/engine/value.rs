@@ -1,5 +1,6 @@
 use crate::Error;
-use juniper::{DefaultScalarValue, FromInputValue, InputValue};
+use juniper::parser::{ParseError, ScalarToken, Token};
+use juniper::{DefaultScalarValue, FromInputValue, InputValue, ParseScalarResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
@@ -23,6 +24,8 @@ use uuid::Uuid;
 pub enum Value {
     Array(Vec<Value>),
     Bool(bool),
+    Bytes(Vec<u8>),
+    Duration(std::time::Duration),
     Float64(f64),
     Int64(i64),
     Map(HashMap<String, Value>),
@@ -32,6 +35,88 @@ pub enum Value {
     Uuid(Uuid),
 }
 
+impl Value {
+    /// Decodes a base64-encoded string, or an array of such strings, received as input for a
+    /// `Base64` property into [`Value::Bytes`]. Other values are returned unchanged.
+    pub(crate) fn decode_base64(self) -> Result<Value, base64::DecodeError> {
+        match self {
+            Value::String(s) => Ok(Value::Bytes(base64::decode(s)?)),
+            Value::Array(a) => Ok(Value::Array(
+                a.into_iter()
+                    .map(Value::decode_base64)
+                    .collect::<Result<Vec<Value>, base64::DecodeError>>()?,
+            )),
+            _ => Ok(self),
+        }
+    }
+
+    /// Parses a string received as input for a `Long` property, or each such string in an array
+    /// or in a map of comparison operands, into [`Value::Int64`]. Other values are returned
+    /// unchanged.
+    pub(crate) fn parse_long(self) -> Result<Value, Error> {
+        match self {
+            Value::String(s) => {
+                s.parse::<i64>()
+                    .map(Value::Int64)
+                    .map_err(|_| Error::TypeConversionFailed {
+                        src: s,
+                        dst: "i64".to_string(),
+                    })
+            }
+            Value::Array(a) => Ok(Value::Array(
+                a.into_iter()
+                    .map(Value::parse_long)
+                    .collect::<Result<Vec<Value>, Error>>()?,
+            )),
+            Value::Map(m) => Ok(Value::Map(
+                m.into_iter()
+                    .map(|(k, v)| Ok((k, v.parse_long()?)))
+                    .collect::<Result<HashMap<String, Value>, Error>>()?,
+            )),
+            _ => Ok(self),
+        }
+    }
+
+    /// Parses an ISO-8601 string received as input for a `Duration` property, or each such
+    /// string in an array or in a map of comparison operands, into [`Value::Duration`]. Other
+    /// values are returned unchanged.
+    pub(crate) fn parse_duration(self) -> Result<Value, Error> {
+        match self {
+            Value::String(s) => Duration::parse(&s).map(|d| Value::Duration(d.0)).ok_or(
+                Error::TypeConversionFailed {
+                    src: s,
+                    dst: "Duration".to_string(),
+                },
+            ),
+            Value::Array(a) => Ok(Value::Array(
+                a.into_iter()
+                    .map(Value::parse_duration)
+                    .collect::<Result<Vec<Value>, Error>>()?,
+            )),
+            Value::Map(m) => Ok(Value::Map(
+                m.into_iter()
+                    .map(|(k, v)| Ok((k, v.parse_duration()?)))
+                    .collect::<Result<HashMap<String, Value>, Error>>()?,
+            )),
+            _ => Ok(self),
+        }
+    }
+
+    /// Converts a `Duration` property read from a database that stores durations as a number of
+    /// milliseconds, or each such number in an array, into [`Value::Duration`]. Other values are
+    /// returned unchanged.
+    pub(crate) fn millis_to_duration(self) -> Value {
+        match self {
+            Value::Int64(i) if i >= 0 => {
+                Value::Duration(std::time::Duration::from_millis(i as u64))
+            }
+            Value::UInt64(i) => Value::Duration(std::time::Duration::from_millis(i)),
+            Value::Array(a) => Value::Array(a.into_iter().map(Value::millis_to_duration).collect()),
+            _ => self,
+        }
+    }
+}
+
 impl Display for Value {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(
@@ -53,6 +138,8 @@ impl Display for Value {
                     s + "]"
                 }
                 Value::Bool(b) => b.to_string(),
+                Value::Bytes(b) => base64::encode(b),
+                Value::Duration(d) => Duration(*d).to_string(),
                 Value::Float64(f) => f.to_string(),
                 Value::Int64(i) => i.to_string(),
                 Value::Map(m) => {
@@ -79,6 +166,291 @@ impl Display for Value {
     }
 }
 
+/// Binary data for properties of the `Base64` GraphQL scalar type. The data is sent to and from
+/// clients as a base64-encoded string, and is passed to the database as [`Value::Bytes`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::value::Base64;
+///
+/// let b = Base64(vec![0xde, 0xad, 0xbe, 0xef]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Base64(pub Vec<u8>);
+
+#[juniper::graphql_scalar(
+    name = "Base64",
+    description = "Binary data, transported as a base64-encoded string"
+)]
+impl<S> GraphQLScalar for Base64
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> juniper::Value {
+        juniper::Value::scalar(base64::encode(&self.0))
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Base64> {
+        v.as_string_value()
+            .and_then(|s| base64::decode(s).ok())
+            .map(Base64)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+/// A 64-bit signed integer, for properties of the `Long` GraphQL scalar type. The GraphQL `Int`
+/// type holds only 32-bit integers, so large counters and timestamps in milliseconds are
+/// declared as `Long`. The value is sent to clients as a string, because many clients store
+/// numbers as doubles, which can't hold every 64-bit integer exactly. Input is accepted as a
+/// string or an integer literal, and is passed to the database as [`Value::Int64`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::value::Long;
+///
+/// let l = Long(1_610_000_000_000);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Long(pub i64);
+
+#[juniper::graphql_scalar(
+    name = "Long",
+    description = "A 64-bit signed integer, transported as a string"
+)]
+impl<S> GraphQLScalar for Long
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> juniper::Value {
+        juniper::Value::scalar(self.0.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Long> {
+        v.as_string_value()
+            .and_then(|s| s.parse::<i64>().ok())
+            .or_else(|| v.as_int_value().map(i64::from))
+            .map(Long)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        match value {
+            ScalarToken::String(value) => Ok(S::from(value.to_owned())),
+            // An integer literal outside the range of i32 is carried as a string, and parsed by
+            // from_input_value
+            ScalarToken::Int(value) => Ok(value
+                .parse::<i32>()
+                .map(S::from)
+                .unwrap_or_else(|_| S::from(value.to_owned()))),
+            _ => Err(ParseError::UnexpectedToken(Token::Scalar(value))),
+        }
+    }
+}
+
+impl TryFrom<Value> for Long {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Long, Self::Error> {
+        match value {
+            Value::Int64(i) => Ok(Long(i)),
+            Value::UInt64(i) => Ok(Long(i64::try_from(i).map_err(|_| {
+                Error::TypeConversionFailed {
+                    src: i.to_string(),
+                    dst: "i64".to_string(),
+                }
+            })?)),
+            _ => Err(Error::TypeConversionFailed {
+                src: format!("{:#?}", value),
+                dst: "i64".to_string(),
+            }),
+        }
+    }
+}
+
+/// A span of time, for properties of the `Duration` GraphQL scalar type. The value is sent to and
+/// from clients as an ISO-8601 duration, such as `PT2H30M`. Weeks, days, hours, minutes, and
+/// seconds are accepted, with a fraction allowed on any of them. A day is taken to be exactly 24
+/// hours. Years and months are rejected, because their length varies. Databases store the value
+/// as a whole number of milliseconds.
+///
+/// # Examples
+///
+/// ```rust
+/// # use warpgrapher::engine::value::Duration;
+///
+/// let d = Duration::parse("PT2H30M").unwrap();
+/// assert_eq!(d.0, std::time::Duration::from_secs(9000));
+/// assert_eq!(d.to_string(), "PT2H30M");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Duration(pub std::time::Duration);
+
+impl Duration {
+    /// Parses an ISO-8601 duration, such as `P1DT12H` or `PT0.5S`, returning `None` if the string
+    /// isn't a duration, or uses years or months.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::engine::value::Duration;
+    ///
+    /// assert_eq!(
+    ///     Duration::parse("P1DT0.5S").unwrap().0,
+    ///     std::time::Duration::from_millis(86_400_500)
+    /// );
+    /// assert!(Duration::parse("P1M").is_none());
+    /// ```
+    pub fn parse(s: &str) -> Option<Duration> {
+        let rest = s.strip_prefix('P')?;
+        let (date, time) = match rest.find('T') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+        if time == Some("") {
+            return None;
+        }
+
+        let date_units: &[(char, u128)] = &[('W', 604_800), ('D', 86_400)];
+        let time_units: &[(char, u128)] = &[('H', 3_600), ('M', 60), ('S', 1)];
+        let mut nanos: u128 = 0;
+        let mut components = 0;
+        for (mut part, units) in [(date, date_units), (time.unwrap_or(""), time_units)] {
+            let mut next_unit = 0;
+            while !part.is_empty() {
+                let end = part.find(|c: char| c.is_ascii_alphabetic())?;
+                let unit = part[end..].chars().next()?;
+                // units must appear in order, and each at most once
+                let i = next_unit + units[next_unit..].iter().position(|(u, _)| *u == unit)?;
+                nanos = nanos
+                    .checked_add(parse_decimal_nanos(&part[..end])?.checked_mul(units[i].1)?)?;
+                next_unit = i + 1;
+                components += 1;
+                part = &part[end + 1..];
+            }
+        }
+        if components == 0 {
+            return None;
+        }
+
+        Some(Duration(std::time::Duration::new(
+            u64::try_from(nanos / 1_000_000_000).ok()?,
+            (nanos % 1_000_000_000) as u32,
+        )))
+    }
+}
+
+/// Parses a decimal number of a duration's units, with up to nine digits after the decimal point,
+/// into billionths of that unit.
+fn parse_decimal_nanos(num: &str) -> Option<u128> {
+    let (whole, fraction) = match num.find(['.', ',']) {
+        Some(i) => (&num[..i], &num[i + 1..]),
+        None => (num, ""),
+    };
+    if whole.is_empty()
+        || fraction.len() > 9
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole = whole.parse::<u128>().ok()?;
+    let fraction = format!("{:0<9}", fraction).parse::<u128>().ok()?;
+    whole.checked_mul(1_000_000_000)?.checked_add(fraction)
+}
+
+impl Display for Duration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let secs = self.0.as_secs();
+        let nanos = self.0.subsec_nanos();
+        let (days, hours, minutes, seconds) = (
+            secs / 86_400,
+            secs % 86_400 / 3_600,
+            secs % 3_600 / 60,
+            secs % 60,
+        );
+
+        write!(f, "P")?;
+        if days > 0 {
+            write!(f, "{}D", days)?;
+        }
+        if hours == 0 && minutes == 0 && seconds == 0 && nanos == 0 {
+            return if days == 0 { write!(f, "T0S") } else { Ok(()) };
+        }
+
+        write!(f, "T")?;
+        if hours > 0 {
+            write!(f, "{}H", hours)?;
+        }
+        if minutes > 0 {
+            write!(f, "{}M", minutes)?;
+        }
+        if seconds > 0 || nanos > 0 {
+            write!(f, "{}", seconds)?;
+            if nanos > 0 {
+                write!(f, ".{}", format!("{:09}", nanos).trim_end_matches('0'))?;
+            }
+            write!(f, "S")?;
+        }
+        Ok(())
+    }
+}
+
+#[juniper::graphql_scalar(
+    name = "Duration",
+    description = "A span of time, transported as an ISO-8601 duration, such as PT2H30M"
+)]
+impl<S> GraphQLScalar for Duration
+where
+    S: ScalarValue,
+{
+    fn resolve(&self) -> juniper::Value {
+        juniper::Value::scalar(self.to_string())
+    }
+
+    fn from_input_value(v: &InputValue) -> Option<Duration> {
+        v.as_string_value().and_then(Duration::parse)
+    }
+
+    fn from_str<'a>(value: ScalarToken<'a>) -> ParseScalarResult<'a, S> {
+        if let ScalarToken::String(value) = value {
+            Ok(S::from(value.to_owned()))
+        } else {
+            Err(ParseError::UnexpectedToken(Token::Scalar(value)))
+        }
+    }
+}
+
+impl TryFrom<Value> for Duration {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Duration, Self::Error> {
+        match value.millis_to_duration() {
+            Value::Duration(d) => Ok(Duration(d)),
+            v => Err(Error::TypeConversionFailed {
+                src: format!("{:#?}", v),
+                dst: "Duration".to_string(),
+            }),
+        }
+    }
+}
+
+impl From<std::time::Duration> for Value {
+    fn from(v: std::time::Duration) -> Self {
+        Value::Duration(v)
+    }
+}
+
 impl From<bool> for Value {
     fn from(v: bool) -> Self {
         Value::Bool(v)
@@ -152,6 +524,8 @@ impl PartialEq for Value {
         match (self, other) {
             (Value::Array(a), Value::Array(oa)) => a == oa,
             (Value::Bool(b), Value::Bool(ob)) => b == ob,
+            (Value::Bytes(b), Value::Bytes(ob)) => b == ob,
+            (Value::Duration(d), Value::Duration(od)) => d == od,
             (Value::Float64(f), Value::Float64(of)) => f == of,
             (Value::Int64(i), Value::Int64(oi)) => i == oi,
             (Value::Map(m), Value::Map(om)) => m == om,
@@ -279,6 +653,17 @@ impl TryFrom<Value> for serde_json::Value {
                     .collect::<Result<Vec<_>, Error>>()?,
             )),
             Value::Bool(b) => Ok(serde_json::Value::Bool(b)),
+            Value::Bytes(b) => Ok(serde_json::Value::Array(
+                b.into_iter()
+                    .map(|byte| serde_json::Value::Number(byte.into()))
+                    .collect(),
+            )),
+            // Serialized in the form serde uses for std::time::Duration, so that a node can be
+            // deserialized into a struct with a Duration field
+            Value::Duration(d) => Ok(serde_json::json!({
+                "secs": d.as_secs(),
+                "nanos": d.subsec_nanos()
+            })),
             Value::Float64(f) => Ok(serde_json::Value::Number(
                 serde_json::Number::from_f64(f).ok_or_else(|| Error::TypeConversionFailed {
                     src: "Value::Float64".to_string(),
@@ -344,7 +729,10 @@ impl TryFrom<Value> for HashMap<String, Value> {
 
 #[cfg(test)]
 mod tests {
-    use super::Value;
+    use super::{Duration, Long, Value};
+    use crate::engine::objects::Node;
+    use serde::Deserialize;
+    use std::collections::HashMap;
 
     /// Passes if the Value implements the Send trait
     #[test]
@@ -359,4 +747,175 @@ mod tests {
         fn assert_sync<T: Sync>() {}
         assert_sync::<Value>();
     }
+
+    /// Passes if base64-encoded input strings, including those in arrays, are decoded to bytes
+    #[test]
+    fn test_value_decode_base64() {
+        assert_eq!(
+            Value::String("3q2+7w==".to_string())
+                .decode_base64()
+                .unwrap(),
+            Value::Bytes(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(
+            Value::Array(vec![Value::String("AAE=".to_string())])
+                .decode_base64()
+                .unwrap(),
+            Value::Array(vec![Value::Bytes(vec![0x00, 0x01])])
+        );
+        assert!(Value::String("not base64!".to_string())
+            .decode_base64()
+            .is_err());
+    }
+
+    /// Passes if Long input strings, including those in arrays and comparison maps, are parsed
+    /// to integers, and strings that aren't integers are rejected
+    #[test]
+    fn test_value_parse_long() {
+        assert_eq!(
+            Value::String("9007199254740993".to_string())
+                .parse_long()
+                .unwrap(),
+            Value::Int64(9_007_199_254_740_993)
+        );
+        assert_eq!(Value::Int64(42).parse_long().unwrap(), Value::Int64(42));
+        let mut m = HashMap::new();
+        m.insert(
+            "IN".to_string(),
+            Value::Array(vec![
+                Value::String("-1".to_string()),
+                Value::String("3000000000".to_string()),
+            ]),
+        );
+        let mut expected = HashMap::new();
+        expected.insert(
+            "IN".to_string(),
+            Value::Array(vec![Value::Int64(-1), Value::Int64(3_000_000_000)]),
+        );
+        assert_eq!(Value::Map(m).parse_long().unwrap(), Value::Map(expected));
+        assert!(Value::String("12.5".to_string()).parse_long().is_err());
+    }
+
+    /// Passes if integers convert to Long, and unsigned integers too large for an i64 are
+    /// rejected rather than wrapped
+    #[test]
+    fn test_value_try_into_long() {
+        assert_eq!(
+            Long::try_from(Value::Int64(i64::MAX)).unwrap(),
+            Long(i64::MAX)
+        );
+        assert_eq!(Long::try_from(Value::UInt64(7)).unwrap(), Long(7));
+        assert!(Long::try_from(Value::UInt64(u64::MAX)).is_err());
+        assert!(i32::try_from(Value::Int64(3_000_000_000)).is_err());
+    }
+
+    /// Passes if a node with a bytes field deserializes into a struct with a Vec<u8> field
+    #[test]
+    fn test_node_deser_bytes() {
+        #[derive(Deserialize)]
+        struct Thumbnail {
+            id: String,
+            data: Vec<u8>,
+        }
+
+        let mut fields = HashMap::new();
+        fields.insert("id".to_string(), Value::String("1234".to_string()));
+        fields.insert(
+            "data".to_string(),
+            Value::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+        );
+        let node = Node::<()>::new("Thumbnail".to_string(), fields);
+
+        let t: Thumbnail = node.deser().unwrap();
+        assert_eq!(t.id, "1234");
+        assert_eq!(t.data, vec![0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    /// Passes if ISO-8601 durations are parsed, including fractions, weeks, and days, and
+    /// durations with years, months, misordered units, or no units are rejected
+    #[test]
+    fn test_duration_parse() {
+        let parse = |s| Duration::parse(s).map(|d| d.0.as_millis());
+        assert_eq!(parse("PT2H30M"), Some(9_000_000));
+        assert_eq!(parse("PT0.5S"), Some(500));
+        assert_eq!(parse("PT1,25S"), Some(1_250));
+        assert_eq!(parse("P1W"), Some(604_800_000));
+        assert_eq!(parse("P1DT1M"), Some(86_460_000));
+        assert_eq!(parse("PT0S"), Some(0));
+        assert_eq!(parse("PT1.5H"), Some(5_400_000));
+        for s in &[
+            "", "P", "PT", "P1Y", "P1M", "PT1M1H", "PT1H1H", "2H", "PT-1H", "PT.5S", "P1DT",
+        ] {
+            assert_eq!(parse(s), None, "{}", s);
+        }
+    }
+
+    /// Passes if durations are formatted as ISO-8601, with days for whole days and the smallest
+    /// number of fractional digits
+    #[test]
+    fn test_duration_display() {
+        let fmt = |ms| Duration(std::time::Duration::from_millis(ms)).to_string();
+        assert_eq!(fmt(9_000_000), "PT2H30M");
+        assert_eq!(fmt(0), "PT0S");
+        assert_eq!(fmt(500), "PT0.5S");
+        assert_eq!(fmt(86_400_000), "P1D");
+        assert_eq!(fmt(90_061_001), "P1DT1H1M1.001S");
+        assert_eq!(
+            Value::Duration(std::time::Duration::from_secs(60)).to_string(),
+            "PT1M"
+        );
+    }
+
+    /// Passes if Duration input strings, including those in comparison maps, are parsed, and
+    /// milliseconds read from the database convert to durations
+    #[test]
+    fn test_value_parse_duration() {
+        let mut m = HashMap::new();
+        m.insert("LT".to_string(), Value::String("PT1H".to_string()));
+        let mut expected = HashMap::new();
+        expected.insert(
+            "LT".to_string(),
+            Value::Duration(std::time::Duration::from_secs(3600)),
+        );
+        assert_eq!(
+            Value::Map(m).parse_duration().unwrap(),
+            Value::Map(expected)
+        );
+        assert!(Value::String("P1M".to_string()).parse_duration().is_err());
+
+        assert_eq!(
+            Value::Array(vec![Value::Int64(1500)]).millis_to_duration(),
+            Value::Array(vec![Value::Duration(std::time::Duration::from_millis(
+                1500
+            ))])
+        );
+        assert_eq!(
+            Duration::try_from(Value::Int64(1500)).unwrap(),
+            Duration(std::time::Duration::from_millis(1500))
+        );
+        assert!(Duration::try_from(Value::Int64(-1)).is_err());
+    }
+
+    /// Passes if a node with a duration field deserializes into a struct with a
+    /// std::time::Duration field
+    #[test]
+    fn test_node_deser_duration() {
+        #[derive(Deserialize)]
+        struct Ticket {
+            id: String,
+            resolution: std::time::Duration,
+        }
+
+        let mut fields = HashMap::new();
+        fields.insert("id".to_string(), Value::String("1234".to_string()));
+        fields.insert(
+            "resolution".to_string(),
+            Value::Duration(std::time::Duration::from_millis(5_400_250)),
+        );
+        let node = Node::<()>::new("Ticket".to_string(), fields);
+
+        let t: Ticket = node.deser().unwrap();
+        assert_eq!(t.id, "1234");
+        assert_eq!(t.resolution, std::time::Duration::from_millis(5_400_250));
+    }
 }
@@ -1,5 +1,6 @@
 //! Provides the [`Error`] type for Warpgrapher
 
+use crate::engine::validators::ValidationError;
 #[cfg(feature = "gremlin")]
 use gremlin_client::GremlinError;
 use http::header::{InvalidHeaderName, InvalidHeaderValue};
@@ -26,6 +27,37 @@ pub enum Error {
         source: bolt_client::error::CommunicationError,
     },
 
+    /// Returned if async-graphql rejects the schema generated by
+    /// [`Engine::async_graphql_schema`]. This indicates a bug in the schema translation.
+    ///
+    /// [`Engine::async_graphql_schema`]: ../engine/struct.Engine.html#method.async_graphql_schema
+    #[cfg(feature = "async-graphql")]
+    AsyncGraphqlSchemaFailed {
+        source: async_graphql::dynamic::SchemaError,
+    },
+
+    /// Returned if a value for a `Base64` property is not a valid base64-encoded string
+    Base64DecodingFailed {
+        source: base64::DecodeError,
+    },
+
+    /// Returned if a mutation run with the `continueOnError` option applies some of its items but
+    /// not others. The `succeeded` field holds the indices of the items that were applied, and
+    /// the `failed` field holds the index of each item that was not applied, together with the
+    /// error that prevented it.
+    BatchItemsFailed {
+        succeeded: Vec<usize>,
+        failed: Vec<(usize, Error)>,
+    },
+
+    /// Returned if a batched create stops at an item that cannot be created. The `committed`
+    /// field holds the number of items created by the batches committed before the failure. The
+    /// batch holding the failed item is rolled back, and the items after it are not attempted.
+    BatchCommitFailed {
+        committed: usize,
+        source: Box<dyn std::error::Error + Sync + Send>,
+    },
+
     /// Returned if a [`Client`] is unable to submit a request to the server, such as due to a
     /// network or server error, or the response cannot be parsed as valid JSON. Inspect the
     /// [`reqwest::Error`] included as a source error for additional detail.
@@ -41,6 +73,14 @@ pub enum Error {
         type_name: String,
     },
 
+    /// Returned if a configuration item is defined with values that are inconsistent or cannot be
+    /// used, such as a relationship with both a custom resolver and a query. The `type_name` field
+    /// contains the name of the item, and `details` describes the problem.
+    ConfigItemInvalid {
+        type_name: String,
+        details: String,
+    },
+
     /// Returned if a Warpgrapher endpoint or type is defined with a name that is a reserved
     /// word, such as "ID" or a GraphQL scalar. The field `type_name` is the name that triggered the
     /// error.
@@ -68,6 +108,50 @@ pub enum Error {
     /// never be done in production
     DatabaseNotFound,
 
+    /// Returned if the database back-end selected at runtime is not one that Warpgrapher was
+    /// built to support. The `name` field contains the name of the back-end that was selected.
+    DatabaseTypeUnknown {
+        name: String,
+    },
+
+    /// Returned if a directive is applied to a type or property in the configuration, but no
+    /// handler is registered for it. The `name` field contains the name of the directive.
+    DirectiveNotFound {
+        name: String,
+    },
+
+    /// Returned if a request is received by an engine that is shutting down. See
+    /// [`Engine::shutdown`].
+    ///
+    /// [`Engine::shutdown`]: ../engine/struct.Engine.html#method.shutdown
+    EngineShuttingDown,
+
+    /// Returned by [`Engine::explain`] if executing the GraphQL operation produced errors. The
+    /// `errors` field holds the `errors` array of the GraphQL response, and the `statements` field
+    /// holds the database statements recorded before the errors occurred.
+    ///
+    /// [`Engine::explain`]: ../engine/struct.Engine.html#method.explain
+    ExplainFailed {
+        errors: serde_json::Value,
+        statements: Vec<String>,
+    },
+
+    /// Returned by [`Engine::explain`] if the database back-end can't record the statements it
+    /// would send to the database without executing them. See
+    /// [`DatabasePool::with_statement_log`].
+    ///
+    /// [`DatabasePool::with_statement_log`]: ../engine/database/trait.DatabasePool.html#method.with_statement_log
+    /// [`Engine::explain`]: ../engine/struct.Engine.html#method.explain
+    ExplainNotSupported,
+
+    /// Returned if a create or update would give two nodes of the type named `type_name` values
+    /// of the property named `property` that differ only in case, where the property is
+    /// configured to be unique ignoring case
+    UniquenessViolation {
+        type_name: String,
+        property: String,
+    },
+
     /// Returned if a `serde_json::Value` struct fails to deserialize into a struct
     JsonDeserializationFailed {
         source: serde_json::Error,
@@ -102,6 +186,13 @@ pub enum Error {
         source: Box<gremlin_client::GremlinError>,
     },
 
+    /// Returned if a create mutation is sent with an idempotency key that was recorded for a
+    /// create with a different input. The `key` field contains the key under which the earlier
+    /// create was recorded.
+    IdempotencyInputMismatched {
+        key: String,
+    },
+
     /// Returned if a GraphQL query is missing an expected argument. For example, if a create
     /// mutation call were missing its input argument. Also returned if an input argument is
     /// missing an expected field.
@@ -109,6 +200,14 @@ pub enum Error {
         name: String,
     },
 
+    /// Returned if a property or field of the GraphQL `Int` type holds a value that doesn't fit
+    /// in the 32-bit range of the `Int` type. The `name` field contains the name of the field and
+    /// `value` the value read. Properties that hold 64-bit integers are declared as `Long`.
+    IntOutOfRange {
+        name: String,
+        value: String,
+    },
+
     /// Returned if an invalid header name is passed to the constructor for creating an http client.
     InvalidHeaderName {
         source: InvalidHeaderName,
@@ -136,7 +235,7 @@ pub enum Error {
     /// error contains additional information.
     #[cfg(feature = "cypher")]
     CypherPoolFailed {
-        source: mobc::Error<<mobc_bolt::Manager as mobc::Manager>::Error>,
+        source: mobc::Error<bolt_client::error::Error>,
     },
 
     /// Returned if a cypher query fails to execute correctly
@@ -145,6 +244,28 @@ pub enum Error {
         message: bolt_proto::message::Message,
     },
 
+    /// Returned if a TLS certificate or private key configured for a Cypher endpoint cannot be
+    /// loaded, such as because the file cannot be read or doesn't contain a PEM-encoded
+    /// certificate or key. The `path` field holds the file that could not be loaded, or the
+    /// database host if it is not a valid TLS server name, and `details` describes the problem.
+    #[cfg(feature = "cypher")]
+    CypherTlsConfigFailed {
+        path: String,
+        details: String,
+    },
+
+    /// Returned if a create or update mutation nests node writes more deeply than the limit set
+    /// with [`EngineBuilder::with_max_write_depth`] or
+    /// [`EngineBuilder::with_type_max_write_depth`]. The `type_name` field holds the type of the
+    /// outermost node being written, and `max_depth` holds the limit that was exceeded.
+    ///
+    /// [`EngineBuilder::with_max_write_depth`]: ./engine/struct.EngineBuilder.html#method.with_max_write_depth
+    /// [`EngineBuilder::with_type_max_write_depth`]: ./engine/struct.EngineBuilder.html#method.with_type_max_write_depth
+    NestedInputTooDeep {
+        type_name: String,
+        max_depth: usize,
+    },
+
     /// Returned if a [`Client`] receives a valid JSON response that does not contain the
     /// expected 'data' or 'errors' objects.
     ///
@@ -155,6 +276,27 @@ pub enum Error {
         response: serde_json::Value,
     },
 
+    /// Returned if a database endpoint is configured with a connection pool setting that its
+    /// database client can't honor. The `option` field names the setting.
+    PoolOptionNotSupported {
+        option: String,
+    },
+
+    /// Returned if a mutation asks for a query profile with `options: { profile: true }`, but
+    /// profiling has not been enabled with [`EngineBuilder::with_profiling`].
+    ///
+    /// [`EngineBuilder::with_profiling`]: ./engine/struct.EngineBuilder.html#method.with_profiling
+    ProfilingDisabled,
+
+    /// Returned if the engine has a query allowlist, set with
+    /// [`EngineBuilder::with_query_allowlist`], and a request's query is not in it. The `hash`
+    /// field holds the hash of the rejected query.
+    ///
+    /// [`EngineBuilder::with_query_allowlist`]: ./engine/struct.EngineBuilder.html#method.with_query_allowlist
+    QueryNotAllowed {
+        hash: String,
+    },
+
     /// Return if a query tries to read and return a relationship defined in the GraphQL schema as
     /// being a single relationship (one-to-one), for which the back-end database has multiple
     /// outgoing relationship edges (one-to-many or many-to-many).  The `rel_name` field holds the
@@ -172,6 +314,13 @@ pub enum Error {
         name: String,
     },
 
+    /// Returned if a custom resolver runs longer than the timeout registered for it, and the
+    /// timeout has no fallback value. The `name` field holds the name of the resolver.
+    ResolverTimedOut {
+        name: String,
+        timeout: std::time::Duration,
+    },
+
     /// Returned if a database query is missing a set of results altogether, where one is expected.
     /// This likely indicates an internal bug. Thus, if you happen to see it, please open an issue
     /// at the Warpgrapher project.
@@ -210,6 +359,10 @@ pub enum Error {
     /// Returned if a transaction is used after it is committed or rolled back.
     TransactionFinished,
 
+    /// Returned if a custom resolver's managed transaction is requested, or the resolver returns,
+    /// while a handle to the managed transaction from an earlier request is still held.
+    TransactionInUse,
+
     /// Warpgrapher transforms data between different serialization formats in the course of
     /// relaying data between GraphQL and database back-ends. If data fails to convert successfully,
     /// this error is thrown. The `src` field contains the source type name or value that could not
@@ -238,6 +391,20 @@ pub enum Error {
         details: Option<String>,
     },
 
+    /// Returned if an update or delete mutation has no `MATCH` criteria, and so would change every
+    /// node or relationship of its type, without the `allowUnfilteredUpdate` option set. The
+    /// `name` field holds the name of the mutation.
+    UnfilteredMutationRejected {
+        name: String,
+    },
+
+    /// Returned if the engine requires reads to be filtered, and a query has no input to filter
+    /// the nodes or relationships it reads, without the `allowUnfilteredRead` option set. The
+    /// `name` field holds the name of the query.
+    UnfilteredReadRejected {
+        name: String,
+    },
+
     /// Returned when encapsulating an error thrown in event handlers provided by users of
     /// Warpgrapher
     UserDefinedError {
@@ -249,10 +416,15 @@ pub enum Error {
         source: uuid::Error,
     },
 
-    /// This error is returned by a custom input validator when the validation fails. The message
-    /// String describes the reason the field failed validation.
+    /// This error is returned by a custom input validator when the validation fails. Each
+    /// [`ValidationError`] names a field that failed validation and describes the reason. When
+    /// an input fails more than one validator, the engine combines the failures into a single
+    /// error, which is reported to GraphQL clients with the `field` and `message` of each
+    /// failure listed under `validationErrors` in the error's extensions.
+    ///
+    /// [`ValidationError`]: ../engine/validators/struct.ValidationError.html
     ValidationFailed {
-        message: String,
+        errors: Vec<ValidationError>,
     },
 
     /// Returned if a custom input validator is defined, but the corresponding validator is not
@@ -267,6 +439,44 @@ pub enum Error {
     },
 }
 
+impl Error {
+    /// Returns a [`ValidationFailed`] error with a single failure described by `message`. When
+    /// returned from a validator, the engine fills in the name of the field being validated.
+    ///
+    /// [`ValidationFailed`]: #variant.ValidationFailed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use warpgrapher::Error;
+    ///
+    /// let e = Error::validation_failed("Cannot be named KENOBI.".to_string());
+    /// assert_eq!(e.validation_message(), Some("Cannot be named KENOBI.".to_string()));
+    /// ```
+    pub fn validation_failed(message: String) -> Error {
+        Error::ValidationFailed {
+            errors: vec![ValidationError::new(String::new(), message)],
+        }
+    }
+
+    /// Returns the messages of a [`ValidationFailed`] error combined into one string, separated
+    /// by semicolons, or `None` for any other kind of error.
+    ///
+    /// [`ValidationFailed`]: #variant.ValidationFailed
+    pub fn validation_message(&self) -> Option<String> {
+        match self {
+            Error::ValidationFailed { errors } => Some(
+                errors
+                    .iter()
+                    .map(|e| e.message())
+                    .collect::<Vec<&str>>()
+                    .join("; "),
+            ),
+            _ => None,
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         match self {
@@ -274,12 +484,46 @@ impl Display for Error {
             Error::BoltClientFailed { source } => {
                 write!(f, "Bolt client failed. Source error: {}.", source)
             }
+            #[cfg(feature = "async-graphql")]
+            Error::AsyncGraphqlSchemaFailed { source } => {
+                write!(
+                    f,
+                    "Failed to build async-graphql schema. Source error: {}",
+                    source
+                )
+            }
+            Error::Base64DecodingFailed { source } => {
+                write!(f, "Failed to decode base64 value. Source error: {}", source)
+            }
+            Error::BatchItemsFailed { succeeded, failed } => {
+                write!(
+                    f,
+                    "{} of {} items failed: {}",
+                    failed.len(),
+                    succeeded.len() + failed.len(),
+                    failed
+                        .iter()
+                        .map(|(index, e)| format!("item {}: {}", index, e))
+                        .collect::<Vec<String>>()
+                        .join("; ")
+                )
+            }
+            Error::BatchCommitFailed { committed, source } => {
+                write!(
+                    f,
+                    "Batch stopped after committing {} items. Source error: {}",
+                    committed, source
+                )
+            }
             Error::ClientRequestFailed { source } => {
                 write!(f, "Client request failed. Source error: {}", source)
             }
             Error::ConfigItemDuplicated { type_name } => {
                 write!(f, "Config model contains duplicate item: {}", type_name)
             }
+            Error::ConfigItemInvalid { type_name, details } => {
+                write!(f, "Config item {} is invalid: {}", type_name, details)
+            }
             Error::ConfigItemReserved { type_name } => {
                 write!(
                     f,
@@ -304,9 +548,54 @@ impl Display for Error {
             Error::DatabaseNotFound => {
                 write!(f, "Use of resolvers required a database back-end. Please select either cypher or gremlin.")
             }
+            Error::DatabaseTypeUnknown { name } => {
+                write!(
+                    f,
+                    "Unknown database type: {}. Please select either cypher or gremlin.",
+                    name
+                )
+            }
+            Error::DirectiveNotFound { name } => {
+                write!(f, "A directive handler named {} could not be found", name)
+            }
+            Error::UniquenessViolation {
+                type_name,
+                property,
+            } => {
+                write!(
+                    f,
+                    "Another {} already has this value of {}, ignoring case.",
+                    type_name, property
+                )
+            }
+            Error::EngineShuttingDown => {
+                write!(
+                    f,
+                    "The engine is shutting down and accepts no new requests."
+                )
+            }
+            Error::ExplainFailed {
+                errors,
+                statements: _,
+            } => {
+                write!(f, "Explaining the GraphQL operation failed: {}", errors)
+            }
+            Error::ExplainNotSupported => {
+                write!(
+                    f,
+                    "The database back-end can't record statements without executing them."
+                )
+            }
             Error::EnvironmentVariableNotFound { name } => {
                 write!(f, "Could not find environment variable: {}", name)
             }
+            Error::IdempotencyInputMismatched { key } => {
+                write!(
+                    f,
+                    "The idempotency key {} was used for a create with a different input.",
+                    key
+                )
+            }
             Error::EnvironmentVariableBoolNotParsed { source } => {
                 write!(
                     f,
@@ -339,6 +628,13 @@ impl Display for Error {
                     name
                 )
             }
+            Error::IntOutOfRange { name, value } => {
+                write!(
+                    f,
+                    "The value {} of field {} is outside the range of the Int type. Declare the property as Long to hold 64-bit integers.",
+                    value, name
+                )
+            }
             Error::InvalidHeaderName { source } => {
                 write!(f, "Invalid HTTP header given to Client: {}", source)
             }
@@ -367,6 +663,24 @@ impl Display for Error {
                     message
                 )
             }
+            #[cfg(feature = "cypher")]
+            Error::CypherTlsConfigFailed { path, details } => {
+                write!(
+                    f,
+                    "Could not load TLS configuration from {}: {}",
+                    path, details
+                )
+            }
+            Error::NestedInputTooDeep {
+                type_name,
+                max_depth,
+            } => {
+                write!(
+                    f,
+                    "Nested write input for type {} exceeds the maximum depth of {}.",
+                    type_name, max_depth
+                )
+            }
             Error::PayloadNotFound { response } => {
                 write!(
                     f,
@@ -374,12 +688,39 @@ impl Display for Error {
                     response
                 )
             }
+            Error::PoolOptionNotSupported { option } => {
+                write!(
+                    f,
+                    "The database client doesn't support the connection pool setting {}.",
+                    option
+                )
+            }
+            Error::ProfilingDisabled => {
+                write!(
+                    f,
+                    "Query profiling is not enabled for this engine. Enable it with EngineBuilder::with_profiling."
+                )
+            }
+            Error::QueryNotAllowed { hash } => {
+                write!(
+                    f,
+                    "The query with hash {} is not in the engine's query allowlist.",
+                    hash
+                )
+            }
             Error::RelDuplicated { rel_name, ids } => {
                 write!(f, "Tried to read the single-node (i.e. one-to-one) relationship named {}, but found multipled ids: {}", rel_name, ids)
             }
             Error::ResolverNotFound { name } => {
                 write!(f, "Could not find a custom resolver named {}", name)
             }
+            Error::ResolverTimedOut { name, timeout } => {
+                write!(
+                    f,
+                    "The custom resolver named {} did not finish within {:?}",
+                    name, timeout
+                )
+            }
             Error::ResponseItemNotFound { name } => {
                 write!(
                     f,
@@ -417,6 +758,12 @@ impl Display for Error {
                     "Cannot use a database transaction already committed or rolled back."
                 )
             }
+            Error::TransactionInUse => {
+                write!(
+                    f,
+                    "Cannot use the managed transaction while a handle to it is still held."
+                )
+            }
             Error::TypeConversionFailed { src, dst } => {
                 write!(
                     f,
@@ -435,6 +782,20 @@ impl Display for Error {
                     }
                 )
             }
+            Error::UnfilteredMutationRejected { name } => {
+                write!(
+                    f,
+                    "The {} mutation has no MATCH criteria, and would change everything of its type. Set the allowUnfilteredUpdate option to allow it.",
+                    name
+                )
+            }
+            Error::UnfilteredReadRejected { name } => {
+                write!(
+                    f,
+                    "The {} query has no input to filter what it reads, and would read everything of its type. Set the allowUnfilteredRead option to allow it.",
+                    name
+                )
+            }
             Error::UserDefinedError { source } => {
                 write!(f, "User defined error. Source error: {:#?}", source)
             }
@@ -445,8 +806,8 @@ impl Display for Error {
                     source
                 )
             }
-            Error::ValidationFailed { message } => {
-                write!(f, "{}", message)
+            Error::ValidationFailed { errors: _ } => {
+                write!(f, "{}", self.validation_message().unwrap_or_default())
             }
             Error::ValidatorNotFound { name } => {
                 write!(f, "A validator function named {} could not be found", name)
@@ -467,8 +828,25 @@ impl std::error::Error for Error {
         match self {
             #[cfg(feature = "cypher")]
             Error::BoltClientFailed { source } => Some(source),
+            #[cfg(feature = "async-graphql")]
+            Error::AsyncGraphqlSchemaFailed { source } => Some(source),
+            Error::Base64DecodingFailed { source } => Some(source),
+            Error::BatchItemsFailed {
+                succeeded: _,
+                failed,
+            } => failed
+                .first()
+                .map(|(_, e)| e as &(dyn std::error::Error + 'static)),
+            Error::BatchCommitFailed {
+                committed: _,
+                source,
+            } => Some(source.as_ref()),
             Error::ClientRequestFailed { source } => Some(source),
             Error::ConfigItemDuplicated { type_name: _ } => None,
+            Error::ConfigItemInvalid {
+                type_name: _,
+                details: _,
+            } => None,
             Error::ConfigItemReserved { type_name: _ } => None,
             Error::ConfigOpenFailed { source } => Some(source),
             Error::ConfigVersionMismatched {
@@ -476,13 +854,27 @@ impl std::error::Error for Error {
                 found: _,
             } => None,
             Error::DatabaseNotFound => None,
+            Error::DatabaseTypeUnknown { name: _ } => None,
+            Error::DirectiveNotFound { name: _ } => None,
+            Error::EngineShuttingDown => None,
+            Error::ExplainFailed {
+                errors: _,
+                statements: _,
+            } => None,
+            Error::ExplainNotSupported => None,
+            Error::UniquenessViolation {
+                type_name: _,
+                property: _,
+            } => None,
             Error::EnvironmentVariableNotFound { name: _ } => None,
+            Error::IdempotencyInputMismatched { key: _ } => None,
             Error::EnvironmentVariableBoolNotParsed { source } => Some(source),
             Error::EnvironmentVariableIntNotParsed { source } => Some(source),
             Error::EventError { source } => Some(source.as_ref()),
             #[cfg(feature = "gremlin")]
             Error::GremlinActionFailed { source } => Some(source),
             Error::InputItemNotFound { name: _ } => None,
+            Error::IntOutOfRange { name: _, value: _ } => None,
             Error::InvalidHeaderName { source } => Some(source),
             Error::InvalidHeaderValue { source } => Some(source),
             Error::JsonDeserializationFailed { source } => Some(source),
@@ -493,23 +885,42 @@ impl std::error::Error for Error {
             Error::CypherPoolFailed { source } => Some(source),
             #[cfg(feature = "cypher")]
             Error::CypherQueryFailed { message: _ } => None,
+            #[cfg(feature = "cypher")]
+            Error::CypherTlsConfigFailed {
+                path: _,
+                details: _,
+            } => None,
+            Error::NestedInputTooDeep {
+                type_name: _,
+                max_depth: _,
+            } => None,
             Error::PayloadNotFound { response: _ } => None,
+            Error::PoolOptionNotSupported { option: _ } => None,
+            Error::ProfilingDisabled => None,
+            Error::QueryNotAllowed { hash: _ } => None,
             Error::RelDuplicated {
                 rel_name: _,
                 ids: _,
             } => None,
             Error::ResolverNotFound { name: _ } => None,
+            Error::ResolverTimedOut {
+                name: _,
+                timeout: _,
+            } => None,
             Error::ResponseItemNotFound { name: _ } => None,
             Error::ResponseSetNotFound => None,
             Error::SerializationFailed { source } => Some(source),
             Error::SchemaItemNotFound { name: _ } => None,
             Error::ThreadCommunicationFailed { source } => Some(source),
             Error::TransactionFinished => None,
+            Error::TransactionInUse => None,
             Error::TypeConversionFailed { src: _, dst: _ } => None,
             Error::TypeNotExpected { details: _ } => None,
+            Error::UnfilteredMutationRejected { name: _ } => None,
+            Error::UnfilteredReadRejected { name: _ } => None,
             Error::UserDefinedError { source: _ } => None,
             Error::UuidNotParsed { source } => Some(source),
-            Error::ValidationFailed { message: _ } => None,
+            Error::ValidationFailed { errors: _ } => None,
             Error::ValidatorNotFound { name: _ } => None,
             Error::YamlDeserializationFailed { source } => Some(source),
         }
@@ -522,6 +933,12 @@ impl From<Box<dyn std::error::Error + Sync + Send>> for Error {
     }
 }
 
+impl From<base64::DecodeError> for Error {
+    fn from(e: base64::DecodeError) -> Self {
+        Error::Base64DecodingFailed { source: e }
+    }
+}
+
 #[cfg(feature = "cypher")]
 impl From<bolt_client::error::CommunicationError> for Error {
     fn from(e: bolt_client::error::CommunicationError) -> Self {
@@ -556,8 +973,8 @@ impl From<bolt_proto::error::ConversionError> for Error {
 }
 
 #[cfg(feature = "cypher")]
-impl From<mobc::Error<<mobc_bolt::Manager as mobc::Manager>::Error>> for Error {
-    fn from(e: mobc::Error<<mobc_bolt::Manager as mobc::Manager>::Error>) -> Self {
+impl From<mobc::Error<bolt_client::error::Error>> for Error {
+    fn from(e: mobc::Error<bolt_client::error::Error>) -> Self {
         Error::CypherPoolFailed { source: e }
     }
 }
@@ -622,6 +1039,7 @@ impl From<uuid::Error> for Error {
 #[cfg(test)]
 mod tests {
     use super::Error;
+    use crate::engine::validators::ValidationError;
 
     /// Passes if a new error with no wrapped source error is created
     #[test]
@@ -643,6 +1061,74 @@ mod tests {
         );
     }
 
+    /// Passes if an out of range Int error names the field and value, and points to the Long type
+    #[test]
+    fn int_out_of_range_display() {
+        let e = Error::IntOutOfRange {
+            name: "views".to_string(),
+            value: "3000000000".to_string(),
+        };
+
+        assert_eq!(
+            "The value 3000000000 of field views is outside the range of the Int type. Declare the property as Long to hold 64-bit integers.",
+            &format!("{}", e)
+        );
+        assert!(std::error::Error::source(&e).is_none());
+    }
+
+    /// Passes if a batch error reports the failed items and exposes the first failure as its
+    /// source
+    #[test]
+    fn batch_items_failed_display() {
+        let e = Error::BatchItemsFailed {
+            succeeded: vec![0, 2],
+            failed: vec![(1, Error::LabelNotFound)],
+        };
+
+        assert_eq!(
+            format!("1 of 3 items failed: item 1: {}", Error::LabelNotFound),
+            format!("{}", e)
+        );
+        assert!(std::error::Error::source(&e).is_some());
+    }
+
+    /// Passes if a batch commit error reports the number of committed items and exposes the
+    /// failure as its source
+    #[test]
+    fn batch_commit_failed_display() {
+        let e = Error::BatchCommitFailed {
+            committed: 200,
+            source: Box::new(Error::LabelNotFound),
+        };
+
+        assert_eq!(
+            format!(
+                "Batch stopped after committing 200 items. Source error: {}",
+                Error::LabelNotFound
+            ),
+            format!("{}", e)
+        );
+        assert!(std::error::Error::source(&e).is_some());
+    }
+
+    /// Passes if a validation error combines the messages of all of its failures
+    #[test]
+    fn validation_failed_display() {
+        let e = Error::ValidationFailed {
+            errors: vec![
+                ValidationError::new("name".to_string(), "Name is too long.".to_string()),
+                ValidationError::new("email".to_string(), "Email is invalid.".to_string()),
+            ],
+        };
+
+        assert_eq!(
+            Some("Name is too long.; Email is invalid.".to_string()),
+            e.validation_message()
+        );
+        assert_eq!("Name is too long.; Email is invalid.", &format!("{}", e));
+        assert!(Error::LabelNotFound.validation_message().is_none());
+    }
+
     /// Passes if Error implements the Send trait
     #[test]
     fn test_send() {
@@ -14,6 +14,8 @@
 
 #![doc(html_root_url = "https://docs.rs/warpgrapher/0.11.2")]
 
+#[cfg(feature = "async-graphql")]
+pub use async_graphql;
 #[cfg(feature = "cypher")]
 pub use bolt_client;
 #[cfg(feature = "cypher")]
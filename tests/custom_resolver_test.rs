@@ -3,7 +3,25 @@ mod setup;
 #[cfg(feature = "cypher")]
 use serde_json::json;
 #[cfg(feature = "cypher")]
-use setup::{clear_db, cypher_test_client, init};
+use setup::{clear_db, cypher_test_client, init, CypherRequestCtx};
+#[cfg(feature = "cypher")]
+use std::collections::HashMap;
+#[cfg(feature = "cypher")]
+use std::convert::TryInto;
+#[cfg(feature = "cypher")]
+use std::fs::File;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::config::Directive;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::database::cypher::CypherEndpoint;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::database::DatabaseEndpoint;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::resolvers::{Directives, FieldError, ResolverFacade};
+#[cfg(feature = "cypher")]
+use warpgrapher::juniper::BoxFuture;
+#[cfg(feature = "cypher")]
+use warpgrapher::{Configuration, Engine};
 
 /// Passes if the custom resolvers executes correctly
 #[cfg(feature = "cypher")]
@@ -35,7 +53,13 @@ async fn custom_endpoint_returning_scalar() {
 
     // count projects via custom resolver
     let result = client
-        .graphql("query { ProjectCount }", None, None, Some("ProjectCount"))
+        .graphql(
+            "query { ProjectCount }",
+            None,
+            None,
+            None,
+            Some("ProjectCount"),
+        )
         .await
         .unwrap();
 
@@ -46,6 +70,85 @@ async fn custom_endpoint_returning_scalar() {
     // shutdown server
 }
 
+/// Passes if a custom mutation endpoint commits its writes on success and rolls them back on error
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn custom_mutation_endpoint_commits_and_rolls_back() {
+    init();
+    clear_db().await;
+    let mut client = cypher_test_client("./tests/fixtures/config.yml").await;
+
+    let query = "mutation Create($input: ProjectCreateMutationInput) {
+        ProjectCreateChecked(input: $input) { id name }
+    }";
+
+    let result = client
+        .graphql(
+            query,
+            None,
+            Some(&json!({"name": "ORION"})),
+            None,
+            Some("ProjectCreateChecked"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.get("name").unwrap(), "ORION");
+
+    let result = client
+        .graphql(query, None, Some(&json!({"name": "RESERVED"})), None, None)
+        .await
+        .unwrap();
+    assert!(result.get("ProjectCreateChecked").unwrap().is_null());
+
+    let projects = client
+        .read_node("Project", "name", None, None)
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert_eq!(projects_a[0].get("name").unwrap(), "ORION");
+}
+
+/// Passes if a custom mutation's managed transaction is committed when the resolver succeeds and
+/// rolled back when it returns an error
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn custom_mutation_managed_transaction_commits_and_rolls_back() {
+    init();
+    clear_db().await;
+    let mut client = cypher_test_client("./tests/fixtures/config.yml").await;
+
+    let query = "mutation Create($input: ProjectCreateMutationInput) {
+        ProjectCreateManaged(input: $input) { id name }
+    }";
+
+    let result = client
+        .graphql(
+            query,
+            None,
+            Some(&json!({"name": "ORION"})),
+            None,
+            Some("ProjectCreateManaged"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.get("name").unwrap(), "ORION");
+
+    let result = client
+        .graphql(query, None, Some(&json!({"name": "RESERVED"})), None, None)
+        .await
+        .unwrap();
+    assert!(result.get("ProjectCreateManaged").unwrap().is_null());
+
+    let projects = client
+        .read_node("Project", "name", None, None)
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert_eq!(projects_a[0].get("name").unwrap(), "ORION");
+}
+
 #[cfg(feature = "cypher")]
 #[tokio::test]
 async fn custom_endpoint_returning_scalar_list() {
@@ -60,6 +163,7 @@ async fn custom_endpoint_returning_scalar_list() {
                 GlobalTopTags 
             }
          ",
+            None,
             None,
             None,
             Some("GlobalTopTags"),
@@ -74,6 +178,61 @@ async fn custom_endpoint_returning_scalar_list() {
     // shutdown server
 }
 
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn custom_endpoint_returning_enum() {
+    init();
+    clear_db().await;
+    let mut client = cypher_test_client("./tests/fixtures/config.yml").await;
+
+    let result = client
+        .graphql(
+            "query { GlobalStatus }",
+            None,
+            None,
+            None,
+            Some("GlobalStatus"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result, json!("ACTIVE"));
+
+    let result = client
+        .graphql(
+            "query { GlobalStatuses }",
+            None,
+            None,
+            None,
+            Some("GlobalStatuses"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(result, json!(["ACTIVE", "ARCHIVED"]));
+
+    let result = client
+        .graphql(
+            "query { __type(name: \"Query\") { fields { name type { kind name } } } }",
+            None,
+            None,
+            None,
+            Some("__type"),
+        )
+        .await
+        .unwrap();
+    let field = result
+        .get("fields")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f.get("name").unwrap() == "GlobalStatus")
+        .unwrap();
+    assert_eq!(
+        field.get("type").unwrap(),
+        &json!({"kind": "ENUM", "name": "Status"})
+    );
+}
+
 #[cfg(feature = "cypher")]
 #[tokio::test]
 async fn custom_endpoint_returning_node() {
@@ -90,6 +249,7 @@ async fn custom_endpoint_returning_node() {
                 }
             }
         ",
+            None,
             None,
             None,
             Some("GlobalTopDev"),
@@ -120,7 +280,13 @@ async fn custom_field_resolver_returning_scalar() {
         .unwrap();
 
     let result = client
-        .graphql("query { Project{id, points}}", None, None, Some("Project"))
+        .graphql(
+            "query { Project{id, points}}",
+            None,
+            None,
+            None,
+            Some("Project"),
+        )
         .await
         .unwrap();
     let points = result[0].get("points").unwrap();
@@ -301,3 +467,85 @@ async fn custom_rel_returning_rel_list() {
     assert_eq!(p0_topissues.len(), 2);
     // shutdown server
 }
+
+/// Allows a field if the request's role metadata matches the directive's role argument, or, for a
+/// directive without a role, if the request has any role
+#[cfg(feature = "cypher")]
+fn auth<'a>(
+    directive: &'a Directive,
+    facade: ResolverFacade<'a, CypherRequestCtx>,
+) -> BoxFuture<'a, Result<(), FieldError>> {
+    Box::pin(async move {
+        let role = facade.metadata().get_str("role");
+        let allowed = match directive.arg("role").and_then(|r| r.as_str()) {
+            Some(required) => role == Some(required),
+            None => role.is_some(),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            facade.error("FORBIDDEN", "Not authorized").map(|_| ())
+        }
+    })
+}
+
+/// Passes if directives on a type and on a property are enforced by the registered handler
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn custom_directive_enforced() {
+    init();
+    clear_db().await;
+
+    let config: Configuration =
+        File::open("./tests/fixtures/config-validation/test_config_directives.yml")
+            .expect("Failed to load config file")
+            .try_into()
+            .unwrap();
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+    let mut directives = Directives::<CypherRequestCtx>::new();
+    directives.insert("auth".to_string(), Box::new(auth));
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .with_directives(directives)
+        .build()
+        .expect("Could not create warpgrapher engine");
+    let role = |r: &str| {
+        let mut metadata = HashMap::new();
+        metadata.insert("role".to_string(), r.to_string());
+        metadata
+    };
+
+    let created = engine
+        .execute(
+            "mutation { PayrollCreate(input: {name: \"Alistair\", salary: 100}) { name salary } }"
+                .to_string(),
+            None,
+            None,
+            role("admin"),
+        )
+        .await
+        .unwrap();
+    assert!(created.get("errors").is_none());
+    assert_eq!(created["data"]["PayrollCreate"]["name"], "Alistair");
+    assert_eq!(created["data"]["PayrollCreate"]["salary"], 100);
+
+    let query = "query { Payroll { name salary } }".to_string();
+
+    let staff = engine
+        .execute(query.clone(), None, None, role("staff"))
+        .await
+        .unwrap();
+    assert_eq!(staff["data"]["Payroll"][0]["name"], "Alistair");
+    assert!(staff["data"]["Payroll"][0]["salary"].is_null());
+    let errors = staff["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["extensions"]["code"], "FORBIDDEN");
+    assert_eq!(errors[0]["path"], json!(["Payroll", 0, "salary"]));
+
+    let anonymous = engine
+        .execute(query, None, None, HashMap::new())
+        .await
+        .unwrap();
+    assert!(anonymous["data"]["Payroll"][0]["name"].is_null());
+    assert!(anonymous["data"]["Payroll"][0]["salary"].is_null());
+    assert_eq!(anonymous["errors"].as_array().unwrap().len(), 2);
+}
@@ -5,7 +5,13 @@ use log::trace;
 #[cfg(feature = "cypher")]
 use serde_json::json;
 #[cfg(feature = "cypher")]
-use setup::{clear_db, cypher_test_client, init};
+use setup::{clear_db, cypher_test_client, cypher_test_client_with_validators, init};
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::validators::Validators;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::value::Value;
+#[cfg(feature = "cypher")]
+use warpgrapher::Error;
 
 /// Passes if the custom validator executes correctly on create mutation
 #[cfg(feature = "cypher")]
@@ -89,3 +95,182 @@ async fn custom_input_validator_update() {
 
     // shutdown server
 }
+
+#[cfg(feature = "cypher")]
+fn role_validator(value: &Value) -> Result<(), Error> {
+    match value {
+        Value::Map(m) => match m.get("role") {
+            Some(Value::String(role)) if role == "member" || role == "admin" => Ok(()),
+            None => Ok(()),
+            _ => Err(Error::validation_failed(
+                "Input validator for role failed. Must be member or admin".to_string(),
+            )),
+        },
+        _ => Err(Error::validation_failed(
+            "Input validator for role failed.".to_string(),
+        )),
+    }
+}
+
+#[cfg(feature = "cypher")]
+async fn rel_prop_default_client() -> warpgrapher::Client<setup::CypherRequestCtx> {
+    let mut validators = Validators::new();
+    validators.insert("RoleValidator".to_string(), Box::new(role_validator));
+    cypher_test_client_with_validators(
+        "./tests/fixtures/config-validation/test_config_rel_prop_default.yml",
+        validators,
+    )
+    .await
+}
+
+/// Passes if a relationship created without a role is given the default role, and a role that
+/// fails the relationship prop's validator is rejected
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn rel_prop_default_and_validator_create() {
+    init();
+    clear_db().await;
+    let mut client = rel_prop_default_client().await;
+
+    let org = client
+        .create_node(
+            "Organization",
+            "id name status members { role tags dst { ...on User { name } } }",
+            &json!({"name": "Org Zero", "members": {"dst": {"User": {"NEW": {"name": "User Zero"}}}}}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(org.get("status").unwrap(), "ACTIVE");
+    let members = org.get("members").unwrap().as_array().unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].get("role").unwrap(), "member");
+    assert_eq!(members[0].get("tags").unwrap(), &json!([]));
+
+    let rels = client
+        .create_rel(
+            "Organization",
+            "members",
+            "role",
+            &json!({"name": {"EQ": "Org Zero"}}),
+            &json!({"role": "admin", "dst": {"User": {"NEW": {"name": "User One"}}}}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rels.as_array().unwrap()[0].get("role").unwrap(), "admin");
+
+    let rels = client
+        .create_rel(
+            "Organization",
+            "members",
+            "role",
+            &json!({"name": {"EQ": "Org Zero"}}),
+            &json!({"role": "owner", "dst": {"User": {"NEW": {"name": "User Two"}}}}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(rels.is_null());
+}
+
+/// Passes if a relationship create with a list of items, one of which fails the relationship
+/// prop's validator, is rolled back as a whole, creating neither relationships nor nodes
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn rel_create_list_rolled_back_on_failed_item() {
+    init();
+    clear_db().await;
+    let mut client = rel_prop_default_client().await;
+
+    client
+        .create_node("Organization", "id", &json!({"name": "Org Zero"}), None)
+        .await
+        .unwrap();
+
+    let rels = client
+        .create_rel(
+            "Organization",
+            "members",
+            "role",
+            &json!({"name": {"EQ": "Org Zero"}}),
+            &json!([
+                {"role": "admin", "dst": {"User": {"NEW": {"name": "User One"}}}},
+                {"role": "owner", "dst": {"User": {"NEW": {"name": "User Two"}}}}
+            ]),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(rels.is_null());
+
+    let members = client
+        .read_rel("Organization", "members", "id", None, None)
+        .await
+        .unwrap();
+    assert!(members.as_array().unwrap().is_empty());
+
+    let users = client.read_node("User", "id", None, None).await.unwrap();
+    assert!(users.as_array().unwrap().is_empty());
+}
+
+/// Passes if a relationship update keeps the defaulted role unless the update sets it, and a role
+/// that fails the relationship prop's validator is rejected
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn rel_prop_default_and_validator_update() {
+    init();
+    clear_db().await;
+    let mut client = rel_prop_default_client().await;
+
+    client
+        .create_node(
+            "Organization",
+            "id",
+            &json!({"name": "Org Zero", "members": {"dst": {"User": {"NEW": {"name": "User Zero"}}}}}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let rels = client
+        .update_rel(
+            "Organization",
+            "members",
+            "role tags",
+            Some(&json!({"src": {"Organization": {"name": {"EQ": "Org Zero"}}}})),
+            &json!({"tags": ["founding"]}),
+            None,
+        )
+        .await
+        .unwrap();
+    let rels = rels.as_array().unwrap();
+    assert_eq!(rels[0].get("role").unwrap(), "member");
+    assert_eq!(rels[0].get("tags").unwrap(), &json!(["founding"]));
+
+    let rels = client
+        .update_rel(
+            "Organization",
+            "members",
+            "role",
+            Some(&json!({"src": {"Organization": {"name": {"EQ": "Org Zero"}}}})),
+            &json!({"role": "admin"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rels.as_array().unwrap()[0].get("role").unwrap(), "admin");
+
+    let rels = client
+        .update_rel(
+            "Organization",
+            "members",
+            "role",
+            Some(&json!({"src": {"Organization": {"name": {"EQ": "Org Zero"}}}})),
+            &json!({"role": "owner"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(rels.is_null());
+}
@@ -11,6 +11,8 @@ use warpgrapher::engine::database::QueryResult;
 #[cfg(feature = "cypher")]
 use warpgrapher::engine::events::{EventFacade, EventHandlerBag};
 #[cfg(feature = "cypher")]
+use warpgrapher::engine::metadata::Metadata;
+#[cfg(feature = "cypher")]
 use warpgrapher::engine::objects::Options;
 #[cfg(feature = "cypher")]
 use warpgrapher::engine::value::Value;
@@ -35,7 +37,7 @@ async fn read_projects(client: &mut Client<Rctx>) -> Result<serde_json::Value, E
 fn mock_handler(
     r: Rctx,
     mut ef: EventFacade<Rctx>,
-    _meta: HashMap<String, String>,
+    _meta: Metadata,
 ) -> BoxFuture<Result<Rctx, Error>> {
     Box::pin(async move {
         // create node
@@ -7,10 +7,12 @@ use setup::CypherRequestCtx;
 #[cfg(feature = "cypher")]
 use setup::{clear_db, cypher_test_client_with_events, init};
 #[cfg(feature = "cypher")]
-use std::collections::HashMap;
+use warpgrapher::engine::database::CrudOperation;
 #[cfg(feature = "cypher")]
 use warpgrapher::engine::events::{EventFacade, EventHandlerBag};
 #[cfg(feature = "cypher")]
+use warpgrapher::engine::metadata::Metadata;
+#[cfg(feature = "cypher")]
 use warpgrapher::engine::objects::{Node, Rel};
 #[cfg(feature = "cypher")]
 use warpgrapher::engine::value::Value;
@@ -33,11 +35,7 @@ impl std::fmt::Display for TestError {
 }
 
 #[cfg(feature = "cypher")]
-fn breqf(
-    _r: Rctx,
-    _ef: EventFacade<Rctx>,
-    _meta: HashMap<String, String>,
-) -> BoxFuture<Result<Rctx, Error>> {
+fn breqf(_r: Rctx, _ef: EventFacade<Rctx>, _meta: Metadata) -> BoxFuture<Result<Rctx, Error>> {
     Box::pin(async move {
         Err(Error::UserDefinedError {
             source: Box::new(TestError {}),
@@ -102,6 +100,18 @@ fn aref(
     })
 }
 
+#[cfg(feature = "cypher")]
+fn authf(op: &CrudOperation, _ef: &EventFacade<Rctx>) -> Result<(), Error> {
+    match op {
+        CrudOperation::DeleteNode(type_name) if type_name == "Project" => {
+            Err(Error::UserDefinedError {
+                source: Box::new(TestError {}),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg(feature = "cypher")]
 #[tokio::test]
 async fn test_before_request_handler() {
@@ -943,3 +953,101 @@ async fn test_after_rel_delete_handler() {
 
     assert!(rd.is_null());
 }
+
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn test_authorizer() {
+    init();
+    clear_db().await;
+
+    let mut ehb = EventHandlerBag::new();
+    ehb.register_authorizer(authf);
+
+    let mut client = cypher_test_client_with_events("./tests/fixtures/minimal.yml", ehb).await;
+
+    let p0 = client
+        .create_node("Project", "id name", &json!({"name": "MJOLNIR"}), None)
+        .await
+        .unwrap();
+    assert_eq!(p0.get("name").unwrap(), "MJOLNIR");
+
+    let pd = client
+        .delete_node(
+            "Project",
+            Some(&json!({"name": {"EQ": "MJOLNIR"}})),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(pd.is_null());
+
+    let projects = client
+        .read_node("Project", "id name", None, None)
+        .await
+        .unwrap();
+    assert_eq!(projects.as_array().unwrap().len(), 1);
+}
+
+#[cfg(feature = "cypher")]
+fn normalize_name(mut v: Value, _ef: EventFacade<Rctx>) -> BoxFuture<Result<Value, Error>> {
+    Box::pin(async move {
+        if let Value::Map(m) = &mut v {
+            if let Some(Value::String(name)) = m.get_mut("name") {
+                *name = name.trim().to_uppercase();
+            }
+        }
+        Ok(v)
+    })
+}
+
+/// Passes if input normalizers run on create and update inputs before the validators, so that
+/// the validators see the normalized input
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn test_input_normalizer() {
+    init();
+    clear_db().await;
+
+    let mut ehb = EventHandlerBag::new();
+    ehb.register_input_normalizer(vec!["User".to_string()], normalize_name);
+
+    let mut client = cypher_test_client_with_events("./tests/fixtures/config.yml", ehb).await;
+
+    let u0 = client
+        .create_node("User", "id name", &json!({"name": " orion "}), None)
+        .await
+        .unwrap();
+    assert_eq!(u0.get("name").unwrap(), "ORION");
+
+    let u1 = client
+        .create_node("User", "id name", &json!({"name": " kenobi "}), None)
+        .await
+        .unwrap();
+    assert!(u1.is_null());
+
+    let u2 = client
+        .update_node(
+            "User",
+            "id name",
+            Some(&json!({"name": {"EQ": "ORION"}})),
+            &json!({"name": "kenobi"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(u2.is_null());
+
+    let u3 = client
+        .update_node(
+            "User",
+            "id name",
+            Some(&json!({"name": {"EQ": "ORION"}})),
+            &json!({"name": " luke "}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(u3.as_array().unwrap().len(), 1);
+    assert_eq!(u3[0].get("name").unwrap(), "LUKE");
+}
@@ -414,6 +414,57 @@ async fn read_mnmt_by_dst_props<RequestCtx: RequestContext>(mut client: Client<R
         .any(|i| i.get("dst").unwrap().get("__typename").unwrap() == "Feature"));
 }
 
+/// Passes if a node that matches a read through several of its relationships is returned once
+/// when the distinct option is set, with the results still sorted
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_distinct<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    for (project, features) in [
+        (
+            "Project Zero",
+            ["Feature Zero", "Feature One", "Feature Two"],
+        ),
+        (
+            "Project One",
+            ["Feature Three", "Feature Four", "Feature Five"],
+        ),
+    ] {
+        client
+            .create_node(
+                "Project",
+                "id",
+                &json!({
+                    "name": project,
+                    "issues": features
+                        .iter()
+                        .map(|name| json!({"dst": {"Feature": {"NEW": {"name": name}}}}))
+                        .collect::<Vec<serde_json::Value>>()
+                }),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    let projects = client
+        .read_node(
+            "Project",
+            "id name",
+            Some(&json!({"issues": {"dst": {"Feature": {"name": {"CONTAINS": "Feature"}}}}})),
+            Some(&json!({
+                "distinct": true,
+                "sort": [{"direction": "descending", "orderBy": "name"}]
+            })),
+        )
+        .await
+        .unwrap();
+
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    assert_eq!(projects_a[0].get("name").unwrap(), "Project Zero");
+    assert_eq!(projects_a[1].get("name").unwrap(), "Project One");
+}
+
 /// Passes if warpgrapher can query for a relationship by the properties of a destination node two hops away
 #[wg_test]
 #[allow(clippy::cognitive_complexity, dead_code)]
@@ -6,10 +6,22 @@ use serde_json::json;
 use setup::{bolt_transaction, clear_db, cypher_test_client, init, CypherRequestCtx};
 #[cfg(feature = "cypher")]
 use std::collections::HashMap;
+#[cfg(feature = "cypher")]
+use std::convert::TryInto;
+#[cfg(feature = "cypher")]
+use std::fs::File;
+#[cfg(feature = "cypher")]
+use std::time::Duration;
 use warpgrapher::client::Client;
 use warpgrapher::engine::context::RequestContext;
 #[cfg(feature = "cypher")]
-use warpgrapher::engine::database::Transaction;
+use warpgrapher::engine::database::cypher::CypherEndpoint;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::database::{DatabaseEndpoint, Transaction};
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::value::Value;
+#[cfg(feature = "cypher")]
+use warpgrapher::{Configuration, Engine};
 use warpgrapher_macros::wg_test;
 
 /// Passes if the create mutation and the read query both succeed.
@@ -61,6 +73,40 @@ async fn create_single_node<RequestCtx: RequestContext>(mut client: Client<Reque
     assert!(projects_a[0].get("active").unwrap().as_bool().unwrap());
 }
 
+/// Passes if a read by a list of ids returns the nodes found in the order of the ids, leaving out
+/// the ids for which no node exists
+#[wg_test]
+#[allow(dead_code)]
+async fn read_nodes_by_ids<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    let p0 = client
+        .create_node("Project", "id", &json!({"name": "Project Zero"}), None)
+        .await
+        .unwrap();
+    let p1 = client
+        .create_node("Project", "id", &json!({"name": "Project One"}), None)
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "id name",
+            Some(&json!({"id": {"IN": [
+                p1.get("id").unwrap(),
+                "missing-id",
+                p0.get("id").unwrap()
+            ]}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    assert_eq!(projects_a[0].get("name").unwrap(), "Project One");
+    assert_eq!(projects_a[1].get("name").unwrap(), "Project Zero");
+}
+
 /// Passes if the create mutation and the read query both succeed, with a specified id.
 #[wg_test]
 #[allow(dead_code)]
@@ -177,6 +223,31 @@ async fn read_with_no_result<RequestCtx: RequestContext>(mut client: Client<Requ
     assert!(projects_a.is_empty());
 }
 
+/// Passes if the exists query reports whether a matching node exists
+#[wg_test]
+#[allow(dead_code)]
+async fn node_exists<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    assert!(!client
+        .node_exists("Project", Some(&json!({"name": {"EQ": "MJOLNIR"}})))
+        .await
+        .unwrap());
+
+    client
+        .create_node("Project", "id", &json!({"name": "MJOLNIR"}), None)
+        .await
+        .unwrap();
+
+    assert!(client
+        .node_exists("Project", Some(&json!({"name": {"EQ": "MJOLNIR"}})))
+        .await
+        .unwrap());
+    assert!(!client
+        .node_exists("Project", Some(&json!({"name": {"EQ": "ORION"}})))
+        .await
+        .unwrap());
+    assert!(client.node_exists("Project", None).await.unwrap());
+}
+
 /// Passes if reading nodes with specific ordering works
 #[wg_test]
 #[allow(dead_code)]
@@ -433,7 +504,212 @@ async fn update_mutation<RequestCtx: RequestContext>(mut client: Client<RequestC
     assert_eq!(after_projects_a[0].get("status").unwrap(), "ACTIVE");
 }
 
-/// Passes if the update mutation succeeds with a null match, meaning update all nodes
+/// Passes if an update that sets a property to null removes the property, an update that omits a
+/// property leaves it unchanged, and an update that sets a required property to null is rejected
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn update_mutation_null_property<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project1", "status": "PENDING", "description": "First"}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let pu = client
+        .update_node(
+            "Project",
+            "name status description",
+            Some(&json!({"name": {"EQ": "Project1"}})),
+            &json!({"status": null}),
+            None,
+        )
+        .await
+        .unwrap();
+    let pu_a = pu.as_array().unwrap();
+    assert_eq!(pu_a.len(), 1);
+    assert_eq!(pu_a[0].get("name").unwrap(), "Project1");
+    assert!(pu_a[0].get("status").unwrap().is_null());
+    assert_eq!(pu_a[0].get("description").unwrap(), "First");
+
+    let pu = client
+        .update_node(
+            "Project",
+            "name status description",
+            Some(&json!({"name": {"EQ": "Project1"}})),
+            &json!({"description": "Second"}),
+            None,
+        )
+        .await
+        .unwrap();
+    let pu_a = pu.as_array().unwrap();
+    assert!(pu_a[0].get("status").unwrap().is_null());
+    assert_eq!(pu_a[0].get("description").unwrap(), "Second");
+
+    let projects = client
+        .read_node(
+            "Project",
+            "name status description",
+            Some(&json!({"name": {"EQ": "Project1"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert!(projects_a[0].get("status").unwrap().is_null());
+    assert_eq!(projects_a[0].get("description").unwrap(), "Second");
+
+    client
+        .create_node("User", "id", &json!({"name": "User One"}), None)
+        .await
+        .unwrap();
+
+    let uu = client
+        .update_node(
+            "User",
+            "name",
+            Some(&json!({"name": {"EQ": "User One"}})),
+            &json!({"name": null}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(uu.is_null());
+
+    let users = client
+        .read_node(
+            "User",
+            "name",
+            Some(&json!({"name": {"EQ": "User One"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(users.as_array().unwrap().len(), 1);
+}
+
+/// Passes if an update setting a required property to null fails with a validation error naming
+/// the property, and leaves the node unchanged. The required User name has no validator, so the
+/// update is rejected by the required check alone.
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn update_mutation_required_null_cypher() {
+    init();
+    clear_db().await;
+
+    let config: Configuration = File::open("./tests/fixtures/minimal.yml")
+        .expect("Failed to load config file")
+        .try_into()
+        .unwrap();
+    assert!(config
+        .types()
+        .find(|t| t.name() == "User")
+        .and_then(|t| t.props_as_slice().iter().find(|p| p.name() == "name"))
+        .map(|p| p.required() && p.validator().is_none())
+        .unwrap());
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .build()
+        .expect("Could not create warpgrapher engine");
+
+    let created = engine
+        .execute(
+            "mutation { UserCreate(input: {name: \"User One\"}) { id name } }".to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(created["data"]["UserCreate"]["name"], "User One");
+
+    let updated = engine
+        .execute(
+            "mutation { UserUpdate(input: {MATCH: {name: {EQ: \"User One\"}}, SET: {name: null}}) { id name } }"
+                .to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert!(updated["data"]["UserUpdate"].is_null());
+    assert_eq!(
+        updated["errors"][0]["extensions"]["validationErrors"][0]["field"],
+        "name"
+    );
+    assert_eq!(
+        updated["errors"][0]["extensions"]["validationErrors"][0]["message"],
+        "is required"
+    );
+
+    let users = engine
+        .execute(
+            "query { User { name } }".to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(users["data"]["User"], json!([{"name": "User One"}]));
+}
+
+/// Passes if an update that selects _changes returns the properties it changed, with their values
+/// before and after the update, and an update that doesn't select it is unaffected
+#[wg_test]
+#[allow(dead_code)]
+async fn update_mutation_changes<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project1", "status": "PENDING", "description": "First"}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let pu = client
+        .update_node(
+            "Project",
+            "name _changes { field before after }",
+            Some(&json!({"name": {"EQ": "Project1"}})),
+            &json!({"name": "Project1", "status": "ACTIVE", "description": null}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let pu_a = pu.as_array().unwrap();
+    assert_eq!(pu_a.len(), 1);
+    assert_eq!(
+        pu_a[0].get("_changes").unwrap(),
+        &json!([
+            {"field": "description", "before": "\"First\"", "after": null},
+            {"field": "status", "before": "\"PENDING\"", "after": "\"ACTIVE\""}
+        ])
+    );
+
+    let pu = client
+        .update_node(
+            "Project",
+            "status",
+            Some(&json!({"name": {"EQ": "Project1"}})),
+            &json!({"status": "CLOSED"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(pu.as_array().unwrap()[0].get("status").unwrap(), "CLOSED");
+}
+
+/// Passes if the update mutation succeeds with a null match, meaning update all nodes, when
+/// unfiltered updates are allowed
 #[wg_test]
 #[allow(clippy::cognitive_complexity, dead_code)]
 async fn update_mutation_null_query<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
@@ -480,7 +756,7 @@ async fn update_mutation_null_query<RequestCtx: RequestContext>(mut client: Clie
             "__typename id name status",
             None,
             &json!({"status": "ACTIVE"}),
-            None,
+            Some(&json!({"allowUnfilteredUpdate": true})),
         )
         .await
         .unwrap();
@@ -503,6 +779,85 @@ async fn update_mutation_null_query<RequestCtx: RequestContext>(mut client: Clie
     assert_eq!(after_projects_a[1].get("status").unwrap(), "ACTIVE");
 }
 
+/// Passes if update and delete mutations with no match criteria are rejected, leaving every node
+/// and relationship unchanged, unless unfiltered updates are allowed
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn unfiltered_mutations_rejected<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    for name in ["Project1", "Project2"].iter() {
+        client
+            .create_node(
+                "Project",
+                "id",
+                &json!({"name": name, "status": "PENDING", "issues": [
+                    {"dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+                ]}),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    let pu = client
+        .update_node(
+            "Project",
+            "id status",
+            None,
+            &json!({"status": "ACTIVE"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(pu.is_null());
+
+    let pu = client
+        .update_node(
+            "Project",
+            "id status",
+            Some(&json!({})),
+            &json!({"status": "ACTIVE"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(pu.is_null());
+
+    let pd = client
+        .delete_node("Project", None, None, None)
+        .await
+        .unwrap();
+    assert!(pd.is_null());
+
+    let rd = client
+        .delete_rel("Project", "issues", None, None, None, None)
+        .await
+        .unwrap();
+    assert!(rd.is_null());
+
+    let projects = client
+        .read_node("Project", "status issues { id }", None, None)
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    for p in projects_a.iter() {
+        assert_eq!(p.get("status").unwrap(), "PENDING");
+        assert_eq!(p.get("issues").unwrap().as_array().unwrap().len(), 1);
+    }
+
+    let pu = client
+        .update_node(
+            "Project",
+            "id status",
+            None,
+            &json!({"status": "ACTIVE"}),
+            Some(&json!({"allowUnfilteredUpdate": true})),
+        )
+        .await
+        .unwrap();
+    assert_eq!(pu.as_array().unwrap().len(), 2);
+}
+
 /// Passes if the delete mutation succeeds with a target node selected by attribute
 #[wg_test]
 #[allow(dead_code)]
@@ -568,7 +923,8 @@ async fn delete_mutation<RequestCtx: RequestContext>(mut client: Client<RequestC
     assert_eq!(after_projects_a.len(), 0);
 }
 
-/// Passes if the update mutation succeeds with a null match, meaning delete all nodes
+/// Passes if the delete mutation succeeds with a null match, meaning delete all nodes, when
+/// unfiltered deletes are allowed
 #[wg_test]
 #[allow(clippy::cognitive_complexity, dead_code)]
 async fn delete_mutation_null_query<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
@@ -610,7 +966,12 @@ async fn delete_mutation_null_query<RequestCtx: RequestContext>(mut client: Clie
     assert_eq!(before_projects_a.len(), 2);
 
     let pd = client
-        .delete_node("Project", None, None, None)
+        .delete_node(
+            "Project",
+            None,
+            None,
+            Some(&json!({"allowUnfilteredUpdate": true})),
+        )
         .await
         .unwrap();
 
@@ -631,50 +992,596 @@ async fn delete_mutation_null_query<RequestCtx: RequestContext>(mut client: Clie
     assert_eq!(after_projects_a.len(), 0);
 }
 
-#[cfg(feature = "cypher")]
-#[tokio::test]
-async fn error_on_node_missing_id_cypher() {
-    init();
-    clear_db().await;
-
-    let mut graph = bolt_transaction()
-        .await
-        .expect("Could not get database client.");
-    graph
-        .execute_query::<CypherRequestCtx>(
-            "CREATE (n:Project { name: 'Project One' })".to_string(),
-            HashMap::new(),
+/// Passes if creates and updates that would give two nodes values of a property that is unique
+/// ignoring case, differing only in case, are rejected, while other writes succeed
+#[wg_test]
+#[allow(dead_code)]
+async fn unique_case_insensitive_property<RequestCtx: RequestContext>(
+    mut client: Client<RequestCtx>,
+) {
+    let alice = client
+        .create_node(
+            "User",
+            "id email",
+            &json!({"name": "Alice", "email": "Alice@Example.com"}),
+            None,
         )
         .await
-        .expect("Expected successful query run.");
-
-    let client = cypher_test_client("./tests/fixtures/minimal.yml").await;
-    error_on_node_missing_id(client).await;
-}
+        .unwrap();
+    assert_eq!(alice.get("email").unwrap(), "Alice@Example.com");
 
-/// Passes if creating a node manually without an id throws an error upon access
-/// to that node.  There is no GraphSON variant of this test, because GraphSON
-/// data stores automatically assign a UUID id.
-#[allow(dead_code)]
-async fn error_on_node_missing_id<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
-    let projects = client
-        .read_node(
-            "Project",
-            "__typename id name",
-            Some(&json!({"name": {"EQ": "Project One"}})),
+    let duplicate = client
+        .create_node(
+            "User",
+            "id email",
+            &json!({"name": "Alice Again", "email": "alice@example.COM"}),
             None,
         )
         .await
         .unwrap();
+    assert!(duplicate.is_null());
 
-    assert!(projects.is_null());
-
-    let pu = client
-        .update_node(
-            "Project",
-            "__typename id name status",
-            Some(&json!({"name": {"EQ": "Project One"}})),
-            &json!({"status": "ACTIVE"}),
+    let bob = client
+        .create_node(
+            "User",
+            "id email",
+            &json!({"name": "Bob", "email": "bob@example.com"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(bob.get("email").unwrap(), "bob@example.com");
+
+    let users = client.read_node("User", "id", None, None).await.unwrap();
+    assert_eq!(users.as_array().unwrap().len(), 2);
+
+    let taken = client
+        .update_node(
+            "User",
+            "id email",
+            Some(&json!({"name": {"EQ": "Bob"}})),
+            &json!({"email": "ALICE@example.com"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(taken.is_null());
+
+    let recased = client
+        .update_node(
+            "User",
+            "id email",
+            Some(&json!({"name": {"EQ": "Alice"}})),
+            &json!({"email": "alice@example.com"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(recased[0].get("email").unwrap(), "alice@example.com");
+
+    let shared = client
+        .update_node(
+            "User",
+            "id email",
+            None,
+            &json!({"email": "shared@example.com"}),
+            Some(&json!({"allowUnfilteredUpdate": true})),
+        )
+        .await
+        .unwrap();
+    assert!(shared.is_null());
+
+    let bob = client
+        .read_node("User", "email", Some(&json!({"name": {"EQ": "Bob"}})), None)
+        .await
+        .unwrap();
+    assert_eq!(bob[0].get("email").unwrap(), "bob@example.com");
+}
+
+/// Passes if a soft deleted node, with deletedAt set, doesn't hold on to the value of a property
+/// that is unique only among nodes without deletedAt, and if restoring the deleted node is
+/// rejected once another node has taken its value. The fixture stores deletedAt under another
+/// name, so this also checks that the scope is looked up through its stored name.
+#[wg_test]
+#[allow(dead_code)]
+async fn unique_where_null_property<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "User",
+            "id",
+            &json!({"name": "Alice", "email": "alice@example.com"}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let deleted = client
+        .update_node(
+            "User",
+            "id deletedAt",
+            Some(&json!({"name": {"EQ": "Alice"}})),
+            &json!({"deletedAt": "2022-03-01T00:00:00Z"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(deleted[0].get("deletedAt").unwrap(), "2022-03-01T00:00:00Z");
+
+    let registered = client
+        .create_node(
+            "User",
+            "id email",
+            &json!({"name": "Alice Again", "email": "Alice@Example.com"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(registered.get("email").unwrap(), "Alice@Example.com");
+
+    let duplicate = client
+        .create_node(
+            "User",
+            "id email",
+            &json!({"name": "Alice Twice", "email": "alice@example.com"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(duplicate.is_null());
+
+    let restored = client
+        .update_node(
+            "User",
+            "id",
+            Some(&json!({"name": {"EQ": "Alice"}})),
+            &json!({"deletedAt": null}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(restored.is_null());
+
+    let users = client
+        .read_node(
+            "User",
+            "name",
+            Some(&json!({"email": {"EQ": "alice@example.com"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(users.as_array().unwrap().len(), 1);
+}
+
+/// Passes if a batched create commits its nodes in batches, reporting progress after each, and
+/// stops at a failed batch, keeping the batches committed before it
+#[wg_test]
+#[allow(dead_code)]
+async fn create_nodes_batched<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    let inputs = (0..5)
+        .map(|i| json!({"name": format!("Project {}", i)}))
+        .collect::<Vec<_>>();
+    let mut progress = Vec::new();
+    let created = client
+        .create_nodes_batched(
+            "Project",
+            "id name",
+            &inputs,
+            2,
+            None,
+            |nodes, committed| progress.push((nodes.as_array().unwrap().len(), committed)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(created, 5);
+    assert_eq!(progress, vec![(2, 2), (2, 4), (1, 5)]);
+
+    let projects = client
+        .read_node("Project", "name", None, None)
+        .await
+        .unwrap();
+    assert_eq!(projects.as_array().unwrap().len(), 5);
+
+    let inputs = vec![
+        json!({"name": "Alice", "email": "alice@example.com"}),
+        json!({"name": "Bob", "email": "bob@example.com"}),
+        json!({"name": "Alice Again", "email": "ALICE@example.com"}),
+        json!({"name": "Carol", "email": "carol@example.com"}),
+        json!({"name": "Dave", "email": "dave@example.com"}),
+    ];
+    let result = client
+        .create_nodes_batched("User", "id", &inputs, 2, None, |_, _| ())
+        .await;
+    assert!(matches!(
+        result,
+        Err(warpgrapher::Error::BatchCommitFailed { committed: 2, .. })
+    ));
+
+    let users = client.read_node("User", "name", None, None).await.unwrap();
+    let mut names = users
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u.get("name").unwrap().as_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+    names.sort();
+    assert_eq!(names, vec!["Alice".to_string(), "Bob".to_string()]);
+}
+
+/// Passes if the Nth node returned by a batched create is the one created from the Nth input,
+/// within each batch and across batches
+#[wg_test]
+#[allow(dead_code)]
+async fn create_nodes_batched_in_input_order<RequestCtx: RequestContext>(
+    mut client: Client<RequestCtx>,
+) {
+    let names = ["Omega", "Alpha", "Kappa", "Beta", "Zeta", "Delta", "Gamma"];
+    let inputs = names
+        .iter()
+        .map(|name| json!({ "name": name }))
+        .collect::<Vec<_>>();
+
+    let mut ids = Vec::new();
+    let created = client
+        .create_nodes_batched(
+            "Project",
+            "id name",
+            &inputs,
+            3,
+            None,
+            |nodes, committed| {
+                let nodes = nodes.as_array().unwrap();
+                for (i, node) in nodes.iter().enumerate() {
+                    let index = committed - nodes.len() + i;
+                    assert_eq!(node.get("name").unwrap(), names[index]);
+                    ids.push(node.get("id").unwrap().clone());
+                }
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(created, names.len());
+    assert_eq!(ids.len(), names.len());
+
+    for (id, name) in ids.iter().zip(names.iter()) {
+        let projects = client
+            .read_node("Project", "name", Some(&json!({"id": {"EQ": id}})), None)
+            .await
+            .unwrap();
+        let projects_a = projects.as_array().unwrap();
+        assert_eq!(projects_a.len(), 1);
+        assert_eq!(projects_a[0].get("name").unwrap(), name);
+    }
+}
+
+/// Passes if a Long property holds integers beyond the range of Int, returned as strings, and is
+/// filtered and updated by Long values given as strings or integer literals
+#[wg_test]
+#[allow(dead_code)]
+async fn long_property<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    let p0 = client
+        .create_node(
+            "Project",
+            "__typename id name views",
+            &json!({"name": "MJOLNIR", "views": "9007199254740993"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(p0.get("views").unwrap(), "9007199254740993");
+
+    client
+        .create_node(
+            "Project",
+            "__typename id name views",
+            &json!({"name": "SPARTAN", "views": 12}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "__typename id name views",
+            Some(&json!({"views": {"GT": "9007199254740992"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert_eq!(projects_a[0].get("name").unwrap(), "MJOLNIR");
+    assert_eq!(projects_a[0].get("views").unwrap(), "9007199254740993");
+
+    let pu = client
+        .update_node(
+            "Project",
+            "__typename id name views",
+            Some(&json!({"name": {"EQ": "SPARTAN"}})),
+            &json!({"views": "-3000000000"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        pu.as_array().unwrap()[0].get("views").unwrap(),
+        "-3000000000"
+    );
+}
+
+/// Passes if a Duration property is returned as an ISO-8601 duration, is filtered with
+/// comparison operators, and rejects durations measured in months
+#[wg_test]
+#[allow(dead_code)]
+async fn duration_property<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    let p0 = client
+        .create_node(
+            "Project",
+            "__typename id name cycleTime",
+            &json!({"name": "MJOLNIR", "cycleTime": "PT2H30M"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(p0.get("cycleTime").unwrap(), "PT2H30M");
+
+    client
+        .create_node(
+            "Project",
+            "__typename id name cycleTime",
+            &json!({"name": "SPARTAN", "cycleTime": "PT45M"}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "__typename id name cycleTime",
+            Some(&json!({"cycleTime": {"LT": "PT1H"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert_eq!(projects_a[0].get("name").unwrap(), "SPARTAN");
+    assert_eq!(projects_a[0].get("cycleTime").unwrap(), "PT45M");
+
+    let pu = client
+        .update_node(
+            "Project",
+            "__typename id name cycleTime",
+            Some(&json!({"name": {"EQ": "SPARTAN"}})),
+            &json!({"cycleTime": "P1DT0.5S"}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        pu.as_array().unwrap()[0].get("cycleTime").unwrap(),
+        "P1DT0.5S"
+    );
+
+    let nodes = client
+        .read_node(
+            "Project",
+            "__typename id name cycleTime",
+            Some(&json!({"cycleTime": {"GTE": "PT1H"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(nodes.as_array().unwrap().len(), 2);
+
+    assert!(client
+        .create_node(
+            "Project",
+            "__typename id name cycleTime",
+            &json!({"name": "ORION", "cycleTime": "P1M"}),
+            None,
+        )
+        .await
+        .is_err());
+}
+
+/// Passes if a property with a database name is created, filtered, sorted, updated, and read
+/// by its GraphQL name
+#[wg_test]
+#[allow(dead_code)]
+async fn db_name_property<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    for (name, code) in [("Project Alpha", "ALPHA"), ("Project Bravo", "BRAVO")] {
+        let p = client
+            .create_node(
+                "Project",
+                "__typename id name code",
+                &json!({"name": name, "code": code}),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(p.get("code").unwrap(), code);
+    }
+
+    let projects = client
+        .read_node(
+            "Project",
+            "__typename id name code",
+            None,
+            Some(&json!({"sort": [{"direction": "descending", "orderBy": "code"}]})),
+        )
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    assert_eq!(projects_a[0].get("code").unwrap(), "BRAVO");
+    assert_eq!(projects_a[1].get("code").unwrap(), "ALPHA");
+
+    let pu = client
+        .update_node(
+            "Project",
+            "__typename id name code",
+            Some(&json!({"code": {"EQ": "ALPHA"}})),
+            &json!({"code": "CHARLIE"}),
+            None,
+        )
+        .await
+        .unwrap();
+    let pu_a = pu.as_array().unwrap();
+    assert_eq!(pu_a.len(), 1);
+    assert_eq!(pu_a[0].get("name").unwrap(), "Project Alpha");
+    assert_eq!(pu_a[0].get("code").unwrap(), "CHARLIE");
+
+    let projects = client
+        .read_node(
+            "Project",
+            "__typename id name code",
+            Some(&json!({"code": {"EQ": "CHARLIE"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert_eq!(projects_a[0].get("name").unwrap(), "Project Alpha");
+}
+
+/// Passes if a node stored with a property's database name, rather than its GraphQL name, is
+/// read through the GraphQL name
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn db_name_property_stored_cypher() {
+    init();
+    clear_db().await;
+
+    let mut graph = bolt_transaction()
+        .await
+        .expect("Could not get database client.");
+    graph
+        .execute_query::<CypherRequestCtx>(
+            "CREATE (n:Project { id: 'project-one', name: 'Project One', project_code: 'ONE' })"
+                .to_string(),
+            HashMap::new(),
+        )
+        .await
+        .expect("Expected successful query run.");
+
+    let mut client = cypher_test_client("./tests/fixtures/minimal.yml").await;
+    let projects = client
+        .read_node(
+            "Project",
+            "__typename id name code",
+            Some(&json!({"code": {"EQ": "ONE"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert_eq!(projects_a[0].get("id").unwrap(), "project-one");
+    assert_eq!(projects_a[0].get("code").unwrap(), "ONE");
+}
+
+/// Passes if a batch of statements runs in order, returning a result for each statement, and a
+/// batch with a failing statement returns an error
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn execute_batch_cypher() {
+    init();
+    clear_db().await;
+
+    let mut graph = bolt_transaction()
+        .await
+        .expect("Could not get database client.");
+    let mut params = HashMap::new();
+    params.insert("name".to_string(), Value::String("Project Two".to_string()));
+    let results = graph
+        .execute_batch::<CypherRequestCtx>(vec![
+            (
+                "CREATE (n:Project { id: 'project-one', name: 'Project One' })".to_string(),
+                HashMap::new(),
+            ),
+            (
+                "CREATE (n:Project { id: 'project-two', name: $name })".to_string(),
+                params,
+            ),
+            (
+                "MATCH (n:Project) RETURN n.name".to_string(),
+                HashMap::new(),
+            ),
+        ])
+        .await
+        .expect("Expected successful batch run.");
+    assert_eq!(results.len(), 3);
+
+    let mut client = cypher_test_client("./tests/fixtures/minimal.yml").await;
+    let projects = client
+        .read_node("Project", "id name", None, None)
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    assert!(projects_a.iter().any(|p| p["name"] == "Project Two"));
+
+    let mut graph = bolt_transaction()
+        .await
+        .expect("Could not get database client.");
+    assert!(graph
+        .execute_batch::<CypherRequestCtx>(vec![
+            ("RETURN 1".to_string(), HashMap::new()),
+            ("NOT A QUERY".to_string(), HashMap::new()),
+            ("RETURN 2".to_string(), HashMap::new()),
+        ])
+        .await
+        .is_err());
+}
+
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn error_on_node_missing_id_cypher() {
+    init();
+    clear_db().await;
+
+    let mut graph = bolt_transaction()
+        .await
+        .expect("Could not get database client.");
+    graph
+        .execute_query::<CypherRequestCtx>(
+            "CREATE (n:Project { name: 'Project One' })".to_string(),
+            HashMap::new(),
+        )
+        .await
+        .expect("Expected successful query run.");
+
+    let client = cypher_test_client("./tests/fixtures/minimal.yml").await;
+    error_on_node_missing_id(client).await;
+}
+
+/// Passes if creating a node manually without an id throws an error upon access
+/// to that node.  There is no GraphSON variant of this test, because GraphSON
+/// data stores automatically assign a UUID id.
+#[allow(dead_code)]
+async fn error_on_node_missing_id<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    let projects = client
+        .read_node(
+            "Project",
+            "__typename id name",
+            Some(&json!({"name": {"EQ": "Project One"}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert!(projects.is_null());
+
+    let pu = client
+        .update_node(
+            "Project",
+            "__typename id name status",
+            Some(&json!({"name": {"EQ": "Project One"}})),
+            &json!({"status": "ACTIVE"}),
             None,
         )
         .await
@@ -685,3 +1592,304 @@ async fn error_on_node_missing_id<RequestCtx: RequestContext>(mut client: Client
     // No error thrown for deletion of nodes, because the node ID isn't accessed during deletion.
     // Therefore, there is no delete_node test here.
 }
+
+/// Passes if a write executed directly on a transaction returns the id of the node it creates,
+/// alongside any scalar values it returns
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn execute_write_returns_created_id_cypher() {
+    init();
+    clear_db().await;
+
+    let mut transaction = bolt_transaction()
+        .await
+        .expect("Could not get database client.");
+
+    let mut params = HashMap::new();
+    params.insert("id".to_string(), Value::String("project-one".to_string()));
+
+    let values = transaction
+        .execute_write(
+            "CREATE (n:Project { id: $id, name: 'Project One' }) RETURN n, n.name".to_string(),
+            params,
+        )
+        .await
+        .expect("Expected successful query run.");
+
+    assert_eq!(
+        vec![
+            Value::String("project-one".to_string()),
+            Value::String("Project One".to_string())
+        ],
+        values
+    );
+}
+
+/// Passes if a create repeated with the same idempotency key returns the original node without
+/// creating a second one, and a create with a different key creates a new node
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn create_node_idempotency_key_cypher() {
+    init();
+    clear_db().await;
+
+    let config: Configuration = File::open("./tests/fixtures/minimal.yml")
+        .expect("Failed to load config file")
+        .try_into()
+        .unwrap();
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .with_idempotency_ttl(Duration::from_secs(60))
+        .build()
+        .expect("Could not create warpgrapher engine");
+
+    let query = "mutation { ProjectCreate(input: {name: \"Project Zero\"}) { id name } }";
+    let mut metadata = HashMap::new();
+    metadata.insert("Idempotency-Key".to_string(), "key-one".to_string());
+
+    let first = engine
+        .execute(query.to_string(), None, None, metadata.clone())
+        .await
+        .unwrap();
+    let second = engine
+        .execute(query.to_string(), None, None, metadata.clone())
+        .await
+        .unwrap();
+    assert_eq!(first, second);
+
+    metadata.insert("Idempotency-Key".to_string(), "key-two".to_string());
+    let third = engine
+        .execute(query.to_string(), None, None, metadata)
+        .await
+        .unwrap();
+    assert_ne!(
+        first["data"]["ProjectCreate"]["id"],
+        third["data"]["ProjectCreate"]["id"]
+    );
+
+    let projects = engine
+        .execute(
+            "query { Project { id } }".to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(projects["data"]["Project"].as_array().unwrap().len(), 2);
+}
+
+/// Passes if a create repeated with the same idempotency key is resolved against the fields it
+/// selects, a repeat with a different input is rejected, and concurrent repeats create one node
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn create_node_idempotency_key_input_cypher() {
+    init();
+    clear_db().await;
+
+    let config: Configuration = File::open("./tests/fixtures/minimal.yml")
+        .expect("Failed to load config file")
+        .try_into()
+        .unwrap();
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .with_idempotency_ttl(Duration::from_secs(60))
+        .build()
+        .expect("Could not create warpgrapher engine");
+
+    let mut metadata = HashMap::new();
+    metadata.insert("Idempotency-Key".to_string(), "key-one".to_string());
+
+    let query = "mutation { ProjectCreate(input: {name: \"Project Zero\"}) { id } }";
+    let (first, second) = tokio::join!(
+        engine.execute(query.to_string(), None, None, metadata.clone()),
+        engine.execute(query.to_string(), None, None, metadata.clone())
+    );
+    let (first, second) = (first.unwrap(), second.unwrap());
+    assert_eq!(first, second);
+    assert!(first["data"]["ProjectCreate"].get("name").is_none());
+
+    let reshaped = engine
+        .execute(
+            "mutation { ProjectCreate(input: {name: \"Project Zero\"}) { id name } }".to_string(),
+            None,
+            None,
+            metadata.clone(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        first["data"]["ProjectCreate"]["id"],
+        reshaped["data"]["ProjectCreate"]["id"]
+    );
+    assert_eq!(reshaped["data"]["ProjectCreate"]["name"], "Project Zero");
+
+    let mismatched = engine
+        .execute(
+            "mutation { ProjectCreate(input: {name: \"Project One\"}) { id } }".to_string(),
+            None,
+            None,
+            metadata,
+        )
+        .await
+        .unwrap();
+    assert!(mismatched["data"]["ProjectCreate"].is_null());
+    assert!(mismatched["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("key-one"));
+
+    let projects = engine
+        .execute(
+            "query { Project { id } }".to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(projects["data"]["Project"].as_array().unwrap().len(), 1);
+}
+
+/// Passes if a mutation run with profiling returns the profile of its queries and still commits
+/// its writes
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn create_node_profile_cypher() {
+    init();
+    clear_db().await;
+
+    let config: Configuration = File::open("./tests/fixtures/minimal.yml")
+        .expect("Failed to load config file")
+        .try_into()
+        .unwrap();
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .with_profiling(true)
+        .build()
+        .expect("Could not create warpgrapher engine");
+
+    let created = engine
+        .execute(
+            "mutation { ProjectCreate(input: {name: \"Project Zero\"}, options: {profile: true}) { id name } }"
+                .to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(created["data"]["ProjectCreate"]["name"], "Project Zero");
+
+    let profiles = created["_profile"].as_array().unwrap();
+    assert!(!profiles.is_empty());
+    assert_eq!(profiles[0]["operation"], "create_node");
+    assert!(profiles[0]["query"].as_str().unwrap().contains("CREATE"));
+    assert!(profiles[0]["dbHits"].as_i64().unwrap() > 0);
+    assert_eq!(profiles[0]["rows"], 1);
+
+    let projects = engine
+        .execute(
+            "query { Project { id name } }".to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert!(projects.get("_profile").is_none());
+    assert_eq!(projects["data"]["Project"].as_array().unwrap().len(), 1);
+}
+
+/// Passes if a create nesting writes within the maximum depth succeeds, and one nesting them more
+/// deeply fails without writing any nodes
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn create_node_max_write_depth_cypher() {
+    init();
+    clear_db().await;
+
+    let config: Configuration = File::open("./tests/fixtures/minimal.yml")
+        .expect("Failed to load config file")
+        .try_into()
+        .unwrap();
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .with_max_write_depth(2)
+        .build()
+        .expect("Could not create warpgrapher engine");
+
+    let shallow = engine
+        .execute(
+            "mutation { ProjectCreate(input: {name: \"Project Zero\", owner: {dst: {User: {NEW: {name: \"User Zero\"}}}}}) { id name } }"
+                .to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(shallow["data"]["ProjectCreate"]["name"], "Project Zero");
+
+    let deep = engine
+        .execute(
+            "mutation { ProjectCreate(input: {name: \"Project One\", owner: {dst: {User: {NEW: {name: \"User One\", friends: [{dst: {User: {NEW: {name: \"User Two\"}}}}]}}}}}) { id name } }"
+                .to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert!(deep["data"]["ProjectCreate"].is_null());
+    assert!(deep["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("maximum depth of 2"));
+
+    let users = engine
+        .execute(
+            "query { User { id name } }".to_string(),
+            None,
+            None,
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(users["data"]["User"].as_array().unwrap().len(), 1);
+}
+
+/// Passes if reads later in a request see the nodes and relationships written earlier in the
+/// same request
+#[wg_test]
+#[allow(dead_code)]
+async fn read_after_write_in_request<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    let query = "mutation {
+        created: ProjectCreate(input: { name: \"Project Zero\" }) { id name }
+        updated: ProjectUpdate(input: {
+            MATCH: { name: { EQ: \"Project Zero\" } },
+            SET: { description: \"Read after write\" }
+        }) { id name description }
+        linked: ProjectIssuesCreate(input: {
+            MATCH: { name: { EQ: \"Project Zero\" } },
+            CREATE: { dst: { Bug: { NEW: { name: \"Bug Zero\" } } } }
+        }) { src { name description issues { dst { ...on Bug { name } } } } }
+    }";
+
+    let data = client.graphql(query, None, None, None, None).await.unwrap();
+
+    let created_id = &data["created"]["id"];
+    assert_eq!(data["created"]["name"], "Project Zero");
+
+    let updated = data["updated"].as_array().unwrap();
+    assert_eq!(updated.len(), 1);
+    assert_eq!(&updated[0]["id"], created_id);
+    assert_eq!(updated[0]["description"], "Read after write");
+
+    let linked = data["linked"].as_array().unwrap();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0]["src"]["description"], "Read after write");
+    let issues = linked[0]["src"]["issues"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["dst"]["name"], "Bug Zero");
+}
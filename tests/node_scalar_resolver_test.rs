@@ -80,6 +80,68 @@ async fn scalar_lists_test<RequestCtx: RequestContext>(mut client: Client<Reques
     assert_approx_eq!(floats.get(3).unwrap().as_f64().unwrap(), 3.3_f64);
 }
 
+#[cfg(feature = "gremlin")]
+#[tokio::test]
+async fn scalar_lists_slice_test_gremlin() {
+    init();
+    clear_db().await;
+
+    let client = gremlin_test_client("./tests/fixtures/scalars/scalar_list.yml").await;
+    scalar_lists_slice_test(client).await;
+}
+
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn scalar_lists_slice_test_cypher() {
+    init();
+    clear_db().await;
+
+    let client = cypher_test_client("./tests/fixtures/scalars/scalar_list.yml").await;
+    scalar_lists_slice_test(client).await;
+}
+
+/// Passes if the first, skip, and contains arguments of scalar list fields narrow the lists
+/// returned by a read query
+#[allow(dead_code)]
+async fn scalar_lists_slice_test<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "TestType",
+            "id",
+            &json!({
+                "string_list": ["tag0", "tag1", "other2", "tag3"],
+                "bool_list": [true, false],
+                "int_list": [0, 1, 2, 3],
+                "float_list": [0.0, 1.1, 2.2, 3.3]
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let results = client
+        .read_node(
+            "TestType",
+            "string_list(first: 2)
+             tags: string_list(contains: \"tag\", skip: 1)
+             int_list(skip: 1, first: 2)
+             bool_list(first: -1)
+             float_list(skip: 10)
+            ",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let result = results.get(0).unwrap();
+    assert_eq!(result.get("string_list").unwrap(), &json!(["tag0", "tag1"]));
+    assert_eq!(result.get("tags").unwrap(), &json!(["tag1", "tag3"]));
+    assert_eq!(result.get("int_list").unwrap(), &json!([1, 2]));
+    assert_eq!(result.get("bool_list").unwrap(), &json!([]));
+    assert_eq!(result.get("float_list").unwrap(), &json!([]));
+}
+
 #[cfg(feature = "gremlin")]
 #[tokio::test]
 async fn scalar_lists_no_array_gremlin() {
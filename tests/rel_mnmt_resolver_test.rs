@@ -1,10 +1,68 @@
 mod setup;
 
 use serde_json::json;
-use warpgrapher::client::Client;
+#[cfg(feature = "cypher")]
+use setup::{clear_db, cypher_test_client, init};
+use warpgrapher::client::{Client, Options};
 use warpgrapher::engine::context::RequestContext;
 use warpgrapher_macros::wg_test;
 
+/// Passes if a single relationship create connects a node to many existing and new destinations
+#[wg_test]
+#[allow(dead_code)]
+async fn create_mnmt_rels_many_dsts<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node("Project", "id", &json!({"name": "Project Zero"}), None)
+        .await
+        .unwrap();
+    for name in ["Feature Zero", "Feature One", "Feature Two"] {
+        client
+            .create_node("Feature", "id", &json!({"name": name}), None)
+            .await
+            .unwrap();
+    }
+
+    let rels = client
+        .create_rel(
+            "Project",
+            "issues",
+            "id dst{...on Feature{name} ...on Bug{name}}",
+            &json!({"name": {"EQ": "Project Zero"}}),
+            &json!([
+                {"dst": {"Feature": {"EXISTING": {"name": {"EQ": "Feature Zero"}}}}},
+                {"dst": {"Feature": {"EXISTING": {"name": {"EQ": "Feature One"}}}}},
+                {"dst": {"Feature": {"EXISTING": {"name": {"EQ": "Feature Two"}}}}},
+                {"dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ]),
+            None,
+        )
+        .await
+        .unwrap();
+    let rels_a = rels.as_array().unwrap();
+    assert_eq!(rels_a.len(), 4);
+    for name in ["Feature Zero", "Feature One", "Feature Two", "Bug Zero"] {
+        assert!(rels_a
+            .iter()
+            .any(|r| r.get("dst").unwrap().get("name").unwrap() == name));
+    }
+
+    let projects = client
+        .read_node("Project", "issues { id }", None, None)
+        .await
+        .unwrap();
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 1);
+    assert_eq!(
+        projects_a[0]
+            .get("issues")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+}
+
 /// Passes if warpgrapher can create a node with a relationship to another new node
 #[wg_test]
 #[allow(clippy::cognitive_complexity, dead_code)]
@@ -406,6 +464,241 @@ async fn read_mnmt_rel_by_dst_props<RequestCtx: RequestContext>(mut client: Clie
         .all(|i| i.get("dst").unwrap().get("name").unwrap() == "Feature Zero"));
 }
 
+/// Passes if relationships are read in the order of a property of their destination nodes, of
+/// either destination type, ascending and descending
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_rel_sorted_by_dst_prop<RequestCtx: RequestContext>(
+    mut client: Client<RequestCtx>,
+) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({
+                "name": "Project Zero",
+                "issues": [
+                    {
+                        "since": "yesterday",
+                        "dst": {"Bug": {"NEW": {"name": "Bug Zero", "priority": 2}}}
+                    },
+                    {
+                        "since": "today",
+                        "dst": {"Feature": {"NEW": {"name": "Feature Zero", "priority": 3}}}
+                    },
+                    {
+                        "since": "last week",
+                        "dst": {"Bug": {"NEW": {"name": "Bug One", "priority": 1}}}
+                    }
+                ]
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let shape = "since dst{...on Feature{name priority} ...on Bug{name priority}}";
+    let names = |issues: &serde_json::Value| {
+        issues
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["dst"]["name"].as_str().unwrap().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    let ascending = Options::new().with_dst_sort("priority", "asc").to_value();
+    let i0 = client
+        .read_rel("Project", "issues", shape, None, Some(&ascending))
+        .await
+        .unwrap();
+    assert_eq!(names(&i0), vec!["Bug One", "Bug Zero", "Feature Zero"]);
+
+    let descending = Options::new().with_dst_sort("priority", "desc").to_value();
+    let i1 = client
+        .read_rel("Project", "issues", shape, None, Some(&descending))
+        .await
+        .unwrap();
+    assert_eq!(names(&i1), vec!["Feature Zero", "Bug Zero", "Bug One"]);
+    assert_eq!(i1[0]["since"], "today");
+}
+
+/// Passes if a rel field nested in a node read returns only the relationships whose destination
+/// nodes match the destination filter, leaving out those to nodes of another type or name
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_nested_rel_by_dst_props<RequestCtx: RequestContext>(
+    mut client: Client<RequestCtx>,
+) {
+    let _p0 = client
+        .create_node(
+            "Project",
+            "__typename name",
+            &json!({
+                "name": "Project Zero",
+                "issues": [
+                    {
+                        "since": "yesterday",
+                        "dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}
+                    },
+                    {
+                        "since": "today",
+                        "dst": {"Feature": {"NEW": {"name": "Bug Zero"}}}
+                    },
+                    {
+                        "since": "last week",
+                        "dst": {"Bug": {"NEW": {"name": "Bug One"}}}
+                    }
+                ]
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "name
+            issues(input: {dst: {Bug: {name: {EQ: \"Bug Zero\"}}}}) {
+                since
+                dst { ...on Feature { __typename name } ...on Bug { __typename name } }
+            }",
+            Some(&json!({"name": {"EQ": "Project Zero"}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects = projects.as_array().unwrap();
+    assert_eq!(projects.len(), 1);
+
+    let issues = projects[0].get("issues").unwrap().as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].get("since").unwrap(), "yesterday");
+    assert_eq!(
+        issues[0].get("dst").unwrap().get("__typename").unwrap(),
+        "Bug"
+    );
+    assert_eq!(
+        issues[0].get("dst").unwrap().get("name").unwrap(),
+        "Bug Zero"
+    );
+}
+
+/// Passes if a rel read can filter on a chain of relationships more than one hop long, and on
+/// conditions against more than one relationship of the same source node
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_rel_by_multi_hop_src_props<RequestCtx: RequestContext>(
+    mut client: Client<RequestCtx>,
+) {
+    let _p0 = client
+        .create_node(
+            "Project",
+            "__typename name",
+            &json!({
+                "name": "Project Zero",
+                "owner": {"dst": {"User": {"NEW": {"name": "Alistair"}}}},
+                "activity": [{"dst": {"Commit": {"NEW": {"hash": "00000000"}}}}],
+                "issues": [
+                    {"dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}},
+                    {"dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}}
+                ]
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let _p1 = client
+        .create_node(
+            "Project",
+            "__typename name",
+            &json!({
+                "name": "Project One",
+                "owner": {"dst": {"User": {"NEW": {"name": "Balthazar"}}}},
+                "activity": [{"dst": {"Commit": {"NEW": {"hash": "11111111"}}}}],
+                "issues": [
+                    {"dst": {"Bug": {"NEW": {"name": "Bug One"}}}}
+                ]
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let i0 = client
+        .read_rel(
+            "Project",
+            "issues",
+            "__typename src{name} dst{...on Bug{__typename name} ...on Feature{__typename name}}",
+            Some(&json!({
+                "src": {"Project": {"owner": {"dst": {"User": {"name": {"EQ": "Alistair"}}}}}}
+            })),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let issues = i0.as_array().unwrap();
+    assert_eq!(issues.len(), 2);
+    assert!(issues
+        .iter()
+        .all(|i| i.get("src").unwrap().get("name").unwrap() == "Project Zero"));
+    assert!(issues
+        .iter()
+        .any(|i| i.get("dst").unwrap().get("name").unwrap() == "Bug Zero"));
+    assert!(issues
+        .iter()
+        .any(|i| i.get("dst").unwrap().get("name").unwrap() == "Feature Zero"));
+
+    let i1 = client
+        .read_rel(
+            "Project",
+            "issues",
+            "__typename src{name} dst{...on Bug{__typename name} ...on Feature{__typename name}}",
+            Some(&json!({
+                "src": {"Project": {
+                    "owner": {"dst": {"User": {"name": {"EQ": "Balthazar"}}}},
+                    "activity": {"dst": {"Commit": {"hash": {"EQ": "11111111"}}}}
+                }}
+            })),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let issues = i1.as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(
+        issues[0].get("src").unwrap().get("name").unwrap(),
+        "Project One"
+    );
+    assert_eq!(
+        issues[0].get("dst").unwrap().get("name").unwrap(),
+        "Bug One"
+    );
+
+    let i2 = client
+        .read_rel(
+            "Project",
+            "issues",
+            "__typename src{name} dst{...on Bug{__typename name} ...on Feature{__typename name}}",
+            Some(&json!({
+                "src": {"Project": {
+                    "owner": {"dst": {"User": {"name": {"EQ": "Alistair"}}}},
+                    "activity": {"dst": {"Commit": {"hash": {"EQ": "11111111"}}}}
+                }}
+            })),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(i2.as_array().unwrap().len(), 0);
+}
+
 #[wg_test]
 #[allow(clippy::cognitive_complexity, dead_code)]
 async fn update_mnmt_rel_by_rel_prop<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
@@ -1127,3 +1420,694 @@ async fn delete_mnmt_rel_by_src_and_dst_prop<RequestCtx: RequestContext>(
         .iter()
         .any(|i| i.get("dst").unwrap().get("name").unwrap() == "Feature One"));
 }
+
+/// Passes if a count property resolves to the number of relationships from each node, including
+/// nodes that have no relationships
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_rel_count<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project Zero", "issues": [
+                {"dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}},
+                {"dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project One", "issues": [
+                {"dst": {"Bug": {"NEW": {"name": "Bug One"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node("Project", "id", &json!({"name": "Project Two"}), None)
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node("Project", "name issueCount", None, None)
+        .await
+        .unwrap();
+
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 3);
+    let count = |name: &str| {
+        projects_a
+            .iter()
+            .find(|p| p.get("name").unwrap() == name)
+            .unwrap()
+            .get("issueCount")
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(count("Project Zero"), json!(2));
+    assert_eq!(count("Project One"), json!(1));
+    assert_eq!(count("Project Two"), json!(0));
+}
+
+/// Passes if a rel aggregate resolves to the number of relationships from each node and the
+/// aggregates of their numeric props, including nodes that have no relationships
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_rel_aggregate<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project Zero", "issues": [
+                {"effort": 1.5, "dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}},
+                {"effort": 2.5, "dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project One", "issues": [
+                {"dst": {"Bug": {"NEW": {"name": "Bug One"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node("Project", "id", &json!({"name": "Project Two"}), None)
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "name issuesAggregate { count effort { sum avg min max } }",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 3);
+    let aggregate = |name: &str| {
+        projects_a
+            .iter()
+            .find(|p| p.get("name").unwrap() == name)
+            .unwrap()
+            .get("issuesAggregate")
+            .unwrap()
+            .clone()
+    };
+
+    let zero = aggregate("Project Zero");
+    assert_eq!(zero.get("count").unwrap(), &json!(2));
+    let effort = zero.get("effort").unwrap();
+    assert_eq!(effort.get("sum").unwrap().as_f64().unwrap(), 4.0);
+    assert_eq!(effort.get("avg").unwrap().as_f64().unwrap(), 2.0);
+    assert_eq!(effort.get("min").unwrap().as_f64().unwrap(), 1.5);
+    assert_eq!(effort.get("max").unwrap().as_f64().unwrap(), 2.5);
+
+    let one = aggregate("Project One");
+    assert_eq!(one.get("count").unwrap(), &json!(1));
+    let effort = one.get("effort").unwrap();
+    assert_eq!(effort.get("sum").unwrap().as_f64().unwrap(), 0.0);
+    assert!(effort.get("avg").unwrap().is_null());
+    assert!(effort.get("min").unwrap().is_null());
+    assert!(effort.get("max").unwrap().is_null());
+
+    let two = aggregate("Project Two");
+    assert_eq!(two.get("count").unwrap(), &json!(0));
+    assert!(two.get("effort").unwrap().get("avg").unwrap().is_null());
+}
+
+/// Passes if nested rel reads with query inputs are scoped to their source node and filtered by
+/// their inputs, with each aliased selection of the same rel read with its own input
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_rel_filtered_nested<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project Zero", "issues": [
+                {"since": "yesterday", "dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}},
+                {"since": "today", "dst": {"Feature": {"NEW": {"name": "Feature One"}}}},
+                {"since": "today", "dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project One", "issues": [
+                {"since": "today", "dst": {"Bug": {"NEW": {"name": "Bug One"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "name
+            today: issues(input: {since: {EQ: \"today\"}}) {
+                dst { ... on Feature { name } ... on Bug { name } }
+            }
+            featureZero: issues(input: {dst: {Feature: {name: {EQ: \"Feature Zero\"}}}}) {
+                since
+            }
+            all: issues {
+                since
+            }",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    let project = |name: &str| {
+        projects_a
+            .iter()
+            .find(|p| p.get("name").unwrap() == name)
+            .unwrap()
+            .clone()
+    };
+    let dst_names = |rels: &serde_json::Value| {
+        let mut names = rels
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                r.get("dst")
+                    .unwrap()
+                    .get("name")
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+        names.sort();
+        names
+    };
+
+    let zero = project("Project Zero");
+    assert_eq!(
+        dst_names(zero.get("today").unwrap()),
+        vec!["Bug Zero".to_string(), "Feature One".to_string()]
+    );
+    let feature_zero = zero.get("featureZero").unwrap().as_array().unwrap();
+    assert_eq!(feature_zero.len(), 1);
+    assert_eq!(feature_zero[0].get("since").unwrap(), "yesterday");
+    assert_eq!(zero.get("all").unwrap().as_array().unwrap().len(), 3);
+
+    let one = project("Project One");
+    assert_eq!(
+        dst_names(one.get("today").unwrap()),
+        vec!["Bug One".to_string()]
+    );
+    assert!(one
+        .get("featureZero")
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .is_empty());
+    assert_eq!(one.get("all").unwrap().as_array().unwrap().len(), 1);
+}
+
+/// Passes if rel count fields count the relationships of their source node matching the input,
+/// without reading the relationships
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_rel_count_field<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project Zero", "issues": [
+                {"since": "yesterday", "dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}},
+                {"since": "today", "dst": {"Feature": {"NEW": {"name": "Feature One"}}}},
+                {"since": "today", "dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node("Project", "id", &json!({"name": "Project One"}), None)
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "name
+            issuesCount
+            today: issuesCount(input: {since: {EQ: \"today\"}})
+            bugs: issuesCount(input: {dst: {Bug: {}}})",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    let project = |name: &str| {
+        projects_a
+            .iter()
+            .find(|p| p.get("name").unwrap() == name)
+            .unwrap()
+            .clone()
+    };
+
+    let zero = project("Project Zero");
+    assert_eq!(zero.get("issuesCount").unwrap(), 3);
+    assert_eq!(zero.get("today").unwrap(), 2);
+    assert_eq!(zero.get("bugs").unwrap(), 1);
+
+    let one = project("Project One");
+    assert_eq!(one.get("issuesCount").unwrap(), 0);
+    assert_eq!(one.get("today").unwrap(), 0);
+    assert_eq!(one.get("bugs").unwrap(), 0);
+}
+
+/// Passes if rels grouped by type hold the relationships to each type of destination node, with
+/// each group sorted and paginated on its own
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_rel_by_type<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project Zero", "issues": [
+                {"effort": 3.0, "dst": {"Feature": {"NEW": {"name": "Feature Three"}}}},
+                {"effort": 1.0, "dst": {"Feature": {"NEW": {"name": "Feature One"}}}},
+                {"effort": 2.0, "dst": {"Feature": {"NEW": {"name": "Feature Two"}}}},
+                {"effort": 4.0, "dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node("Project", "id", &json!({"name": "Project One"}), None)
+        .await
+        .unwrap();
+
+    let projects = client
+        .read_node(
+            "Project",
+            "name 
+            issuesByType { 
+                Feature(options: {sort: [{direction: ascending, orderBy: effort}]}, first: 2, skip: 1) { 
+                    effort 
+                    dst { ... on Feature { name } } 
+                } 
+                Bug { 
+                    dst { ... on Bug { name } } 
+                } 
+            }",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let projects_a = projects.as_array().unwrap();
+    assert_eq!(projects_a.len(), 2);
+    let by_type = |name: &str| {
+        projects_a
+            .iter()
+            .find(|p| p.get("name").unwrap() == name)
+            .unwrap()
+            .get("issuesByType")
+            .unwrap()
+            .clone()
+    };
+
+    let zero = by_type("Project Zero");
+    let features = zero.get("Feature").unwrap().as_array().unwrap();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features[0].get("effort").unwrap().as_f64().unwrap(), 2.0);
+    assert_eq!(
+        features[0].get("dst").unwrap().get("name").unwrap(),
+        "Feature Two"
+    );
+    assert_eq!(features[1].get("effort").unwrap().as_f64().unwrap(), 3.0);
+    assert_eq!(
+        features[1].get("dst").unwrap().get("name").unwrap(),
+        "Feature Three"
+    );
+    let bugs = zero.get("Bug").unwrap().as_array().unwrap();
+    assert_eq!(bugs.len(), 1);
+    assert_eq!(bugs[0].get("dst").unwrap().get("name").unwrap(), "Bug Zero");
+
+    let one = by_type("Project One");
+    assert!(one.get("Feature").unwrap().as_array().unwrap().is_empty());
+    assert!(one.get("Bug").unwrap().as_array().unwrap().is_empty());
+}
+
+/// Passes if nodes can be read by the properties of the source nodes of relationships to them
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnmt_node_by_inverse_rel<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project Zero", "issues": [
+                {"dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}},
+                {"dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node(
+            "Project",
+            "id",
+            &json!({"name": "Project One", "issues": [
+                {"dst": {"Bug": {"NEW": {"name": "Bug One"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    client
+        .create_node("Bug", "id", &json!({"name": "Bug Two"}), None)
+        .await
+        .unwrap();
+
+    let bugs = client
+        .read_node(
+            "Bug",
+            "name",
+            Some(&json!({"project": {"name": {"EQ": "Project Zero"}}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let bugs_a = bugs.as_array().unwrap();
+    assert_eq!(bugs_a.len(), 1);
+    assert_eq!(bugs_a[0].get("name").unwrap(), "Bug Zero");
+
+    let bugs = client
+        .read_node(
+            "Bug",
+            "name",
+            Some(&json!({"name": {"NOTEQ": "Bug Zero"}, "project": {"name": {"CONTAINS": "Project"}}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let bugs_a = bugs.as_array().unwrap();
+    assert_eq!(bugs_a.len(), 1);
+    assert_eq!(bugs_a[0].get("name").unwrap(), "Bug One");
+
+    let features = client
+        .read_node(
+            "Feature",
+            "name",
+            Some(&json!({"project": {"name": {"EQ": "Project One"}}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert!(features.as_array().unwrap().is_empty());
+}
+
+/// Passes if a relationship create with ifDstExists creates nothing when an EXISTING destination
+/// matches no nodes, while the same create without it connects the destinations that exist
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn create_mnmt_rel_if_dst_exists_zero_match<RequestCtx: RequestContext>(
+    mut client: Client<RequestCtx>,
+) {
+    client
+        .create_node("Project", "id", &json!({"name": "Project Zero"}), None)
+        .await
+        .unwrap();
+    client
+        .create_node("Feature", "id", &json!({"name": "Feature Zero"}), None)
+        .await
+        .unwrap();
+
+    let query = "mutation Create($input: ProjectIssuesCreateInput!) {
+        ProjectIssuesCreate(input: $input) { id dst { ...on Feature { name } ...on Bug { name } } }
+    }";
+    let input = |if_dst_exists: bool| {
+        json!({
+            "MATCH": {"name": {"EQ": "Project Zero"}},
+            "CREATE": [
+                {"dst": {"Feature": {"EXISTING": {"name": {"EQ": "Feature Zero"}}}}},
+                {"dst": {"Feature": {"EXISTING": {"name": {"EQ": "Feature Missing"}}}}},
+                {"dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ],
+            "ifDstExists": if_dst_exists
+        })
+    };
+
+    let rels = client
+        .graphql(
+            query,
+            None,
+            Some(&input(true)),
+            None,
+            Some("ProjectIssuesCreate"),
+        )
+        .await
+        .unwrap();
+    assert!(rels.as_array().unwrap().is_empty());
+
+    let projects = client
+        .read_node(
+            "Project",
+            "issues { id }",
+            Some(&json!({"name": {"EQ": "Project Zero"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(projects[0]["issues"].as_array().unwrap().is_empty());
+
+    let bugs = client.read_node("Bug", "id", None, None).await.unwrap();
+    assert!(bugs.as_array().unwrap().is_empty());
+
+    let rels = client
+        .graphql(
+            query,
+            None,
+            Some(&input(false)),
+            None,
+            Some("ProjectIssuesCreate"),
+        )
+        .await
+        .unwrap();
+    let rels_a = rels.as_array().unwrap();
+    assert_eq!(rels_a.len(), 2);
+    assert!(rels_a
+        .iter()
+        .any(|r| r.get("dst").unwrap().get("name").unwrap() == "Feature Zero"));
+    assert!(rels_a
+        .iter()
+        .any(|r| r.get("dst").unwrap().get("name").unwrap() == "Bug Zero"));
+}
+
+/// Passes if a relationship create with ifDstExists connects the source to every node matched
+/// by an EXISTING destination that matches more than one node
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn create_mnmt_rel_if_dst_exists_multi_match<RequestCtx: RequestContext>(
+    mut client: Client<RequestCtx>,
+) {
+    client
+        .create_node("Project", "id", &json!({"name": "Project Zero"}), None)
+        .await
+        .unwrap();
+    for name in ["Feature Zero", "Feature One", "Feature Two"] {
+        client
+            .create_node("Feature", "id", &json!({"name": name}), None)
+            .await
+            .unwrap();
+    }
+
+    let rels = client
+        .graphql(
+            "mutation Create($input: ProjectIssuesCreateInput!) {
+                ProjectIssuesCreate(input: $input) { id dst { ...on Feature { name } } }
+            }",
+            None,
+            Some(&json!({
+                "MATCH": {"name": {"EQ": "Project Zero"}},
+                "CREATE": [
+                    {"dst": {"Feature": {"EXISTING": {"name": {"IN": ["Feature Zero", "Feature One"]}}}}}
+                ],
+                "ifDstExists": true
+            })),
+            None,
+            Some("ProjectIssuesCreate"),
+        )
+        .await
+        .unwrap();
+
+    let rels_a = rels.as_array().unwrap();
+    assert_eq!(rels_a.len(), 2);
+    assert!(rels_a
+        .iter()
+        .any(|r| r.get("dst").unwrap().get("name").unwrap() == "Feature Zero"));
+    assert!(rels_a
+        .iter()
+        .any(|r| r.get("dst").unwrap().get("name").unwrap() == "Feature One"));
+}
+
+/// Passes if INCREMENT adds to a numeric rel prop in the database, starting from zero when the
+/// prop is missing
+#[wg_test]
+#[allow(dead_code)]
+async fn update_mnmt_rel_increment<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node("Project", "id", &json!({"name": "Project Zero"}), None)
+        .await
+        .unwrap();
+    client
+        .create_rel(
+            "Project",
+            "issues",
+            "id",
+            &json!({"name": {"EQ": "Project Zero"}}),
+            &json!([
+                {"effort": 1.5, "dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}},
+                {"dst": {"Bug": {"NEW": {"name": "Bug Zero"}}}}
+            ]),
+            None,
+        )
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        client
+            .update_rel(
+                "Project",
+                "issues",
+                "id effort",
+                None,
+                &json!({"INCREMENT": {"effort": 2}}),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    let rels = client
+        .read_rel(
+            "Project",
+            "issues",
+            "effort dst{...on Feature{name} ...on Bug{name}}",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let rels_a = rels.as_array().unwrap();
+    assert_eq!(rels_a.len(), 2);
+    let effort = |name: &str| {
+        rels_a
+            .iter()
+            .find(|r| r.get("dst").unwrap().get("name").unwrap() == name)
+            .unwrap()
+            .get("effort")
+            .unwrap()
+            .as_f64()
+            .unwrap()
+    };
+    assert_eq!(effort("Feature Zero"), 5.5);
+    assert_eq!(effort("Bug Zero"), 4.0);
+}
+
+/// Passes if PUSH appends to a list rel prop in the database, starting from an empty list when
+/// the prop is missing, and is applied after the props set by the same update
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn update_mnmt_rel_push_cypher() {
+    init();
+    clear_db().await;
+    let mut client = cypher_test_client("./tests/fixtures/minimal.yml").await;
+
+    client
+        .create_node("Project", "id", &json!({"name": "Project Zero"}), None)
+        .await
+        .unwrap();
+    client
+        .create_rel(
+            "Project",
+            "issues",
+            "id",
+            &json!({"name": {"EQ": "Project Zero"}}),
+            &json!([{"dst": {"Feature": {"NEW": {"name": "Feature Zero"}}}}]),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let r0 = client
+        .update_rel(
+            "Project",
+            "issues",
+            "labels",
+            None,
+            &json!({"PUSH": {"labels": ["late"]}}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(r0[0].get("labels").unwrap(), &json!(["late"]));
+
+    let r1 = client
+        .update_rel(
+            "Project",
+            "issues",
+            "labels effort",
+            None,
+            &json!({"effort": 1.0, "PUSH": {"labels": ["blocked", "urgent"]}}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        r1[0].get("labels").unwrap(),
+        &json!(["late", "blocked", "urgent"])
+    );
+    assert_eq!(r1[0].get("effort").unwrap(), 1.0);
+}
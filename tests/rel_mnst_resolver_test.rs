@@ -1,6 +1,12 @@
 mod setup;
 
 use serde_json::json;
+#[cfg(feature = "cypher")]
+use setup::cypher_test_client;
+#[cfg(feature = "gremlin")]
+use setup::gremlin_test_client;
+#[cfg(any(feature = "gremlin", feature = "cypher"))]
+use setup::{clear_db, init};
 use warpgrapher::client::Client;
 use warpgrapher::engine::context::RequestContext;
 use warpgrapher_macros::wg_test;
@@ -1087,3 +1093,220 @@ async fn delete_mnst_rel_by_src_prop<RequestCtx: RequestContext>(mut client: Cli
         .iter()
         .any(|a| a.get("dst").unwrap().get("hash").unwrap() == "33333"));
 }
+
+/// Passes if a symmetric relationship created in one direction is read from the node at either
+/// end, and matched by a destination filter regardless of the direction in which it is stored
+#[wg_test]
+#[allow(clippy::cognitive_complexity, dead_code)]
+async fn read_mnst_symmetric_rel<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node("User", "__typename name", &json!({"name": "Alice"}), None)
+        .await
+        .unwrap();
+
+    client
+        .create_rel(
+            "User",
+            "friends",
+            "__typename dst{...on User{__typename name}}",
+            &json!({"name": {"EQ": "Alice"}}),
+            &json!([{"dst": {"User": {"NEW": {"name": "Bob"}}}}]),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let users = client
+        .read_node(
+            "User",
+            "name friends{__typename dst{...on User{__typename name}}}",
+            Some(&json!({"name": {"EQ": "Bob"}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let users_a = users.as_array().unwrap();
+    assert_eq!(users_a.len(), 1);
+    let friends = users_a[0].get("friends").unwrap().as_array().unwrap();
+    assert_eq!(friends.len(), 1);
+    assert_eq!(friends[0].get("__typename").unwrap(), "UserFriendsRel");
+    assert_eq!(friends[0].get("dst").unwrap().get("name").unwrap(), "Alice");
+
+    let alices = client
+        .read_node(
+            "User",
+            "name friends{dst{...on User{name}}}",
+            Some(&json!({"name": {"EQ": "Alice"}})),
+            None,
+        )
+        .await
+        .unwrap();
+    let alice_friends = alices.as_array().unwrap()[0]
+        .get("friends")
+        .unwrap()
+        .as_array()
+        .unwrap();
+    assert_eq!(alice_friends.len(), 1);
+    assert_eq!(
+        alice_friends[0].get("dst").unwrap().get("name").unwrap(),
+        "Bob"
+    );
+
+    let friends_of_alice = client
+        .read_node(
+            "User",
+            "name",
+            Some(&json!({"friends": {"dst": {"User": {"name": {"EQ": "Alice"}}}}})),
+            None,
+        )
+        .await
+        .unwrap();
+    let friends_of_alice_a = friends_of_alice.as_array().unwrap();
+    assert_eq!(friends_of_alice_a.len(), 1);
+    assert_eq!(friends_of_alice_a[0].get("name").unwrap(), "Bob");
+
+    let rels = client
+        .read_rel(
+            "User",
+            "friends",
+            "dst{...on User{name}}",
+            Some(&json!({"src": {"User": {"name": {"EQ": "Bob"}}}})),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(rels.as_array().unwrap().len(), 1);
+}
+
+#[cfg(feature = "gremlin")]
+#[tokio::test]
+async fn move_mnst_rel_gremlin() {
+    init();
+    clear_db().await;
+
+    let client =
+        gremlin_test_client("./tests/fixtures/config-validation/test_config_rel_position.yml")
+            .await;
+    move_mnst_rel(client).await;
+}
+
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn move_mnst_rel_cypher() {
+    init();
+    clear_db().await;
+
+    let client =
+        cypher_test_client("./tests/fixtures/config-validation/test_config_rel_position.yml").await;
+    move_mnst_rel(client).await;
+}
+
+#[allow(dead_code)]
+async fn read_task_ranks<RequestCtx: RequestContext>(
+    client: &mut Client<RequestCtx>,
+) -> Vec<(String, i64)> {
+    let tasks = client
+        .read_rel(
+            "Sprint",
+            "tasks",
+            "rank dst{...on Task{name}}",
+            Some(&json!({"src": {"Sprint": {"name": {"EQ": "Sprint Zero"}}}})),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let mut ranks = tasks
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| {
+            (
+                t.get("dst")
+                    .unwrap()
+                    .get("name")
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+                t.get("rank").unwrap().as_i64().unwrap(),
+            )
+        })
+        .collect::<Vec<(String, i64)>>();
+    ranks.sort_by_key(|(_, rank)| *rank);
+    ranks
+}
+
+/// Passes if setting the position of an ordered relationship moves it among its siblings,
+/// shifting the siblings, closing gaps, and clamping positions past the end of the list
+#[allow(dead_code)]
+async fn move_mnst_rel<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
+    client
+        .create_node(
+            "Sprint",
+            "id",
+            &json!({"name": "Sprint Zero", "tasks": [
+                {"rank": 0, "dst": {"Task": {"NEW": {"name": "A"}}}},
+                {"rank": 1, "dst": {"Task": {"NEW": {"name": "B"}}}},
+                {"rank": 2, "dst": {"Task": {"NEW": {"name": "C"}}}},
+                {"rank": 3, "dst": {"Task": {"NEW": {"name": "D"}}}}
+            ]}),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let moved = client
+        .update_rel(
+            "Sprint",
+            "tasks",
+            "rank dst{...on Task{name}}",
+            Some(&json!({"dst": {"Task": {"name": {"EQ": "D"}}}})),
+            &json!({"rank": 1}),
+            None,
+        )
+        .await
+        .unwrap();
+    let moved = moved.as_array().unwrap();
+    assert_eq!(moved.len(), 1);
+    assert_eq!(moved[0].get("rank").unwrap(), 1);
+
+    let names = |ranks: Vec<(String, i64)>| {
+        assert!(ranks.iter().enumerate().all(|(i, (_, r))| *r == i as i64));
+        ranks.into_iter().map(|(n, _)| n).collect::<Vec<String>>()
+    };
+    assert_eq!(
+        names(read_task_ranks(&mut client).await),
+        vec!["A", "D", "B", "C"]
+    );
+
+    // A gap left by a sibling added with a large rank is closed by the next move, and a position
+    // past the end of the list moves the relationship to the end
+    client
+        .create_rel(
+            "Sprint",
+            "tasks",
+            "rank",
+            &json!({"name": {"EQ": "Sprint Zero"}}),
+            &json!({"rank": 10, "dst": {"Task": {"NEW": {"name": "E"}}}}),
+            None,
+        )
+        .await
+        .unwrap();
+    client
+        .update_rel(
+            "Sprint",
+            "tasks",
+            "rank",
+            Some(&json!({"dst": {"Task": {"name": {"EQ": "A"}}}})),
+            &json!({"rank": 100}),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        names(read_task_ranks(&mut client).await),
+        vec!["D", "B", "C", "E", "A"]
+    );
+}
@@ -1,10 +1,40 @@
 mod setup;
 
 use serde_json::json;
+#[cfg(feature = "cypher")]
+use setup::{clear_db, cypher_test_client_with_interceptor, init};
+#[cfg(feature = "cypher")]
+use std::collections::HashMap;
+#[cfg(feature = "cypher")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "cypher")]
+use std::sync::Arc;
 use warpgrapher::client::Client;
 use warpgrapher::engine::context::RequestContext;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::database::QueryInterceptor;
+#[cfg(feature = "cypher")]
+use warpgrapher::engine::value::Value;
+#[cfg(feature = "cypher")]
+use warpgrapher::Error;
 use warpgrapher_macros::wg_test;
 
+#[cfg(feature = "cypher")]
+struct QueryCounter(Arc<AtomicUsize>);
+
+#[cfg(feature = "cypher")]
+impl QueryInterceptor for QueryCounter {
+    fn intercept(
+        &self,
+        _operation: &str,
+        query: String,
+        params: HashMap<String, Value>,
+    ) -> Result<(String, HashMap<String, Value>), Error> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Ok((query, params))
+    }
+}
+
 /// Passes if warpgrapher can create a node with a relationship to another new node
 #[wg_test]
 #[allow(clippy::cognitive_complexity, dead_code)]
@@ -311,6 +341,92 @@ async fn read_snst_rel_by_src_props<RequestCtx: RequestContext>(mut client: Clie
         .all(|o| o.get("dst").unwrap().get("name").unwrap() == "User Zero"));
 }
 
+/// Passes if reading only the ids of a relationship's src and dst nodes doesn't read the nodes
+#[cfg(feature = "cypher")]
+#[tokio::test]
+async fn read_snst_rel_ids_without_node_reads() {
+    init();
+    clear_db().await;
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let mut client = cypher_test_client_with_interceptor(
+        "./tests/fixtures/minimal.yml",
+        Box::new(QueryCounter(count.clone())),
+    )
+    .await;
+
+    let p0 = client
+        .create_node(
+            "Project",
+            "id owner { dst { ...on User { id } } }",
+            &json!({
+                "name": "Project Zero",
+                "owner": {
+                    "since": "yesterday",
+                    "dst": {"User": {"NEW": {"name": "User Zero"}}}
+                }
+            }),
+            None,
+        )
+        .await
+        .unwrap();
+    let p0_id = p0.get("id").unwrap();
+    let u0_id = p0
+        .get("owner")
+        .unwrap()
+        .get("dst")
+        .unwrap()
+        .get("id")
+        .unwrap();
+
+    count.store(0, Ordering::SeqCst);
+    let o0 = client
+        .read_rel(
+            "Project",
+            "owner",
+            "__typename src { __typename id } dst { ...on User { __typename id } }",
+            Some(&json!({"src": {"Project": {"name": {"EQ": "Project Zero"}}}})),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    let owner = o0.as_array().unwrap();
+    assert_eq!(owner.len(), 1);
+    assert_eq!(
+        owner[0].get("src").unwrap().get("__typename").unwrap(),
+        "Project"
+    );
+    assert_eq!(owner[0].get("src").unwrap().get("id").unwrap(), p0_id);
+    assert_eq!(
+        owner[0].get("dst").unwrap().get("__typename").unwrap(),
+        "User"
+    );
+    assert_eq!(owner[0].get("dst").unwrap().get("id").unwrap(), u0_id);
+
+    count.store(0, Ordering::SeqCst);
+    let o0 = client
+        .read_rel(
+            "Project",
+            "owner",
+            "__typename src { id name } dst { ...on User { id name } }",
+            Some(&json!({"src": {"Project": {"name": {"EQ": "Project Zero"}}}})),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(count.load(Ordering::SeqCst) > 1);
+    assert_eq!(
+        o0.as_array().unwrap()[0]
+            .get("dst")
+            .unwrap()
+            .get("name")
+            .unwrap(),
+        "User Zero"
+    );
+}
+
 #[wg_test]
 #[allow(clippy::cognitive_complexity, dead_code)]
 async fn read_snst_rel_by_dst_props<RequestCtx: RequestContext>(mut client: Client<RequestCtx>) {
@@ -4,6 +4,8 @@ use gremlin_client::TlsOptions;
 use gremlin_client::{ConnectionOptions, GraphSON, GremlinClient};
 use log::trace;
 #[cfg(feature = "cypher")]
+use serde::Deserialize;
+#[cfg(feature = "cypher")]
 use std::collections::HashMap;
 #[cfg(feature = "cypher")]
 use std::convert::TryFrom;
@@ -28,7 +30,7 @@ use warpgrapher::engine::database::DatabaseEndpoint;
 #[cfg(feature = "cypher")]
 use warpgrapher::engine::database::QueryResult;
 #[cfg(feature = "cypher")]
-use warpgrapher::engine::database::{DatabasePool, Transaction};
+use warpgrapher::engine::database::{DatabasePool, QueryInterceptor, Transaction};
 #[cfg(feature = "cypher")]
 use warpgrapher::engine::events::EventHandlerBag;
 #[cfg(feature = "cypher")]
@@ -134,9 +136,19 @@ pub(crate) async fn cypher_test_client_with_events(
 
     // load resolvers
     let mut resolvers: Resolvers<CypherRequestCtx> = Resolvers::new();
+    resolvers.insert("GlobalStatus".to_owned(), Box::new(global_status));
+    resolvers.insert("GlobalStatuses".to_owned(), Box::new(global_statuses));
     resolvers.insert("GlobalTopDev".to_owned(), Box::new(global_top_dev));
     resolvers.insert("GlobalTopTags".to_owned(), Box::new(global_top_tags));
     resolvers.insert("ProjectCount".to_owned(), Box::new(project_count));
+    resolvers.insert(
+        "ProjectCreateChecked".to_string(),
+        Box::new(project_create_checked),
+    );
+    resolvers.insert(
+        "ProjectCreateManaged".to_string(),
+        Box::new(project_create_managed),
+    );
     resolvers.insert("ProjectPoints".to_string(), Box::new(project_points));
     resolvers.insert("ProjectTopDev".to_string(), Box::new(project_top_dev));
     resolvers.insert("ProjectTopIssues".to_string(), Box::new(project_top_issues));
@@ -162,6 +174,50 @@ pub(crate) async fn cypher_test_client_with_events(
     Client::new_with_engine(engine, None)
 }
 
+#[allow(dead_code)]
+#[cfg(feature = "cypher")]
+pub(crate) async fn cypher_test_client_with_interceptor(
+    config_path: &str,
+    interceptor: Box<dyn QueryInterceptor>,
+) -> Client<CypherRequestCtx> {
+    let config: Configuration = File::open(config_path)
+        .expect("Failed to load config file")
+        .try_into()
+        .unwrap();
+
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .with_version("1.0".to_string())
+        .with_query_interceptor(interceptor)
+        .build()
+        .expect("Could not create warpgrapher engine");
+
+    Client::new_with_engine(engine, None)
+}
+
+#[allow(dead_code)]
+#[cfg(feature = "cypher")]
+pub(crate) async fn cypher_test_client_with_validators(
+    config_path: &str,
+    validators: Validators,
+) -> Client<CypherRequestCtx> {
+    let config: Configuration = File::open(config_path)
+        .expect("Failed to load config file")
+        .try_into()
+        .unwrap();
+
+    let database_pool = CypherEndpoint::from_env().unwrap().pool().await.unwrap();
+
+    let engine = Engine::<CypherRequestCtx>::new(config, database_pool)
+        .with_version("1.0".to_string())
+        .with_validators(validators)
+        .build()
+        .expect("Could not create warpgrapher engine");
+
+    Client::new_with_engine(engine, None)
+}
+
 #[allow(dead_code)]
 #[cfg(feature = "gremlin")]
 pub(crate) async fn gremlin_test_client(config_path: &str) -> Client<GremlinRequestCtx> {
@@ -293,43 +349,35 @@ fn name_validator(value: &Value) -> Result<(), Error> {
         Value::Map(m) => match m.get("name") {
             Some(n) => n,
             None => {
-                return Err(Error::ValidationFailed {
-                    message: format!(
-                        "Input validator for {field_name} failed.",
-                        field_name = "name"
-                    ),
-                })
+                return Err(Error::validation_failed(format!(
+                    "Input validator for {field_name} failed.",
+                    field_name = "name"
+                )))
             }
         },
         _ => {
-            return Err(Error::ValidationFailed {
-                message: format!(
-                    "Input validator for {field_name} failed.",
-                    field_name = "name"
-                ),
-            })
+            return Err(Error::validation_failed(format!(
+                "Input validator for {field_name} failed.",
+                field_name = "name"
+            )))
         }
     };
 
     match name {
         Value::String(s) => {
             if s == "KENOBI" {
-                Err(Error::ValidationFailed {
-                    message: format!(
-                        "Input validator for {field_name} failed. Cannot be named KENOBI",
-                        field_name = "name"
-                    ),
-                })
+                Err(Error::validation_failed(format!(
+                    "Input validator for {field_name} failed. Cannot be named KENOBI",
+                    field_name = "name"
+                )))
             } else {
                 Ok(())
             }
         }
-        _ => Err(Error::ValidationFailed {
-            message: format!(
-                "Input validator for {field_name} failed.",
-                field_name = "name"
-            ),
-        }),
+        _ => Err(Error::validation_failed(format!(
+            "Input validator for {field_name} failed.",
+            field_name = "name"
+        ))),
     }
 }
 
@@ -354,6 +402,82 @@ pub(crate) fn project_count(
     })
 }
 
+/// custom mutation endpoint that creates a project, then rolls the creation back if the project
+/// has a reserved name
+#[cfg(feature = "cypher")]
+pub(crate) fn project_create_checked(
+    facade: ResolverFacade<CypherRequestCtx>,
+) -> BoxFuture<ExecutionResult> {
+    Box::pin(async move {
+        let input =
+            facade
+                .args()
+                .get::<Value>("input")
+                .ok_or_else(|| Error::InputItemNotFound {
+                    name: "input".to_string(),
+                })?;
+        let mut transaction = facade.transaction().await?;
+        let result = ResolverFacade::create_node(
+            &facade,
+            "Project",
+            Options::default(),
+            input,
+            &mut transaction,
+        )
+        .await
+        .and_then(|project| match project.fields().get("name") {
+            Some(Value::String(name)) if name == "RESERVED" => Err(Error::validation_failed(
+                "Project name is reserved".to_string(),
+            )),
+            _ => Ok(project),
+        });
+
+        if result.is_ok() {
+            transaction.commit().await?;
+        } else {
+            transaction.rollback().await?;
+        }
+        let project = result?;
+        std::mem::drop(transaction);
+        facade.resolve_node(&project).await
+    })
+}
+
+/// custom mutation endpoint that creates a project in the managed transaction, returning an error
+/// so that the framework rolls the creation back if the project has a reserved name
+#[cfg(feature = "cypher")]
+pub(crate) fn project_create_managed(
+    facade: ResolverFacade<CypherRequestCtx>,
+) -> BoxFuture<ExecutionResult> {
+    Box::pin(async move {
+        let input =
+            facade
+                .args()
+                .get::<Value>("input")
+                .ok_or_else(|| Error::InputItemNotFound {
+                    name: "input".to_string(),
+                })?;
+        let mut transaction = facade.managed_transaction().await?;
+        let project = ResolverFacade::create_node(
+            &facade,
+            "Project",
+            Options::default(),
+            input,
+            &mut transaction,
+        )
+        .await?;
+        if let Some(Value::String(name)) = project.fields().get("name") {
+            if name == "RESERVED" {
+                return Err(
+                    Error::validation_failed("Project name is reserved".to_string()).into(),
+                );
+            }
+        }
+        std::mem::drop(transaction);
+        facade.resolve_node(&project).await
+    })
+}
+
 /// custom endpoint returning scalar_list:
 #[cfg(feature = "cypher")]
 pub(crate) fn global_top_tags(
@@ -364,6 +488,22 @@ pub(crate) fn global_top_tags(
     })
 }
 
+/// custom endpoint returning enum
+#[cfg(feature = "cypher")]
+pub(crate) fn global_status(
+    facade: ResolverFacade<CypherRequestCtx>,
+) -> BoxFuture<ExecutionResult> {
+    Box::pin(async move { facade.resolve_enum("ACTIVE") })
+}
+
+/// custom endpoint returning enum list
+#[cfg(feature = "cypher")]
+pub(crate) fn global_statuses(
+    facade: ResolverFacade<CypherRequestCtx>,
+) -> BoxFuture<ExecutionResult> {
+    Box::pin(async move { facade.resolve_enum_list(vec!["ACTIVE", "ARCHIVED"]) })
+}
+
 /// custom endpoint returning node
 #[cfg(feature = "cypher")]
 pub(crate) fn global_top_dev(
@@ -399,43 +539,31 @@ pub(crate) fn project_top_dev(
     facade: ResolverFacade<CypherRequestCtx>,
 ) -> BoxFuture<ExecutionResult> {
     Box::pin(async move {
-        let mut transaction = facade.executor().context().pool().transaction().await?;
-        transaction.begin().await?;
-        let query = "MATCH (n:User) RETURN (n);".to_string();
-        let qr = transaction
-            .execute_query::<CypherRequestCtx>(query, HashMap::new())
-            .await
-            .expect("Expected successful query run.");
-
-        if let QueryResult::Cypher(result) = qr {
-            let dev_id = if let bolt_proto::value::Value::Node(n) =
-                &result.get(0).expect("Expected result").fields()[0]
-            {
-                Value::try_from(n.properties().get("id").expect("Expected id").clone())
-                    .expect("Expected string")
-            } else {
-                panic!("Expected node.")
-            };
+        #[derive(Deserialize)]
+        struct Dev {
+            id: String,
+        }
 
-            transaction.commit().await?;
-            std::mem::drop(transaction);
+        let devs: Vec<Dev> = facade
+            .query_as("MATCH (n:User) RETURN (n);", HashMap::new())
+            .await?;
+        let dev = devs
+            .first()
+            .ok_or(warpgrapher::Error::ResponseSetNotFound)?;
 
-            facade
-                .resolve_rel(
-                    &facade
-                        .create_rel(
-                            Value::String("1234567890".to_string()),
-                            "topdev",
-                            HashMap::new(),
-                            dev_id,
-                            Options::default(),
-                        )
-                        .expect("Expected new rel"),
-                )
-                .await
-        } else {
-            Err(warpgrapher::Error::TypeNotExpected { details: None }.into())
-        }
+        facade
+            .resolve_rel(
+                &facade
+                    .create_rel(
+                        Value::String("1234567890".to_string()),
+                        "topdev",
+                        HashMap::new(),
+                        Value::String(dev.id.clone()),
+                        Options::default(),
+                    )
+                    .expect("Expected new rel"),
+            )
+            .await
     })
 }
 
@@ -494,21 +622,21 @@ pub(crate) fn project_top_issues(
         facade
             .resolve_rel_list(vec![
                 &facade
-                    .create_rel(
+                    .create_rel_with_dst_id(
                         Value::String("1234567890".to_string()),
                         "topissues",
                         HashMap::new(),
+                        "Bug",
                         bug_id,
-                        Options::default(),
                     )
                     .expect("Expected rel"),
                 &facade
-                    .create_rel(
+                    .create_rel_with_dst_id(
                         Value::String("0987654321".to_string()),
                         "topissues",
                         HashMap::new(),
+                        "Feature",
                         feature_id,
-                        Options::default(),
                     )
                     .expect("Expected rel"),
             ])